@@ -0,0 +1,51 @@
+//! Build metadata served by `GET /version`
+//!
+//! The commit comes from `GIT_COMMIT` or `RAILWAY_GIT_COMMIT_SHA` when the build
+//! environment provides one (Docker builds have no `.git`), otherwise from git.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let commit = env_value("GIT_COMMIT")
+        .or_else(|| env_value("RAILWAY_GIT_COMMIT_SHA"))
+        .or_else(|| command_output("git", &["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version =
+        command_output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string());
+    let built_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+
+    println!("cargo:rustc-env=BUILD_GIT_COMMIT={commit}");
+    println!("cargo:rustc-env=BUILD_RUSTC_VERSION={rustc_version}");
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={built_at}");
+
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+    println!("cargo:rerun-if-env-changed=RAILWAY_GIT_COMMIT_SHA");
+    println!("cargo:rerun-if-changed=src");
+    // A missing path would rerun the script on every build
+    for path in [".git/HEAD", ".git/refs/heads"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
@@ -479,7 +479,9 @@ function getReportConfig() {
     // These will be set by the server-side template
     return {
         reportId: window.REPORT_ID || null,
-        sandboxToken: window.SANDBOX_TOKEN || null
+        sandboxToken: window.SANDBOX_TOKEN || null,
+        // Signed `expires` / `sig` query of the sandboxed URL
+        sandboxUrlQuery: window.SANDBOX_URL_QUERY || ''
     };
 }
 
@@ -500,11 +502,10 @@ function initializeIframe() {
             
             const currentLang = (window.languageManager && window.languageManager.currentLanguage) || 'vi';
             const baseUrl = `/api/crypto_reports/${config.reportId}/sandboxed`;
-            const params = new URLSearchParams({
-                token: config.sandboxToken,
-                lang: currentLang,
-                chart_modules: 'true'
-            });
+            const params = new URLSearchParams(config.sandboxUrlQuery);
+            params.set('token', config.sandboxToken);
+            params.set('lang', currentLang);
+            params.set('chart_modules', 'true');
             iframe.src = `${baseUrl}?${params.toString()}`;
             console.log('🔗 Parent: Set iframe src with language:', currentLang);
         }
@@ -584,11 +585,10 @@ window.addEventListener('languageChanged', function(event) {
         }
         
         const baseUrl = `/api/crypto_reports/${config.reportId}/sandboxed`;
-        const params = new URLSearchParams({
-            token: config.sandboxToken,
-            lang: event.detail.language,
-            chart_modules: 'true'
-        });
+        const params = new URLSearchParams(config.sandboxUrlQuery);
+        params.set('token', config.sandboxToken);
+        params.set('lang', event.detail.language);
+        params.set('chart_modules', 'true');
         iframe.src = `${baseUrl}?${params.toString()}`;
         console.log('🔄 Parent: Updated iframe src for language change:', event.detail.language);
     }
@@ -0,0 +1,23 @@
+-- Report ownership and contributor attribution
+--
+-- One report may have several contributing analysts; `position` keeps the byline order.
+
+CREATE TABLE IF NOT EXISTS authors (
+    id          SERIAL PRIMARY KEY,
+    slug        TEXT NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$' AND length(slug) <= 64),
+    name        TEXT NOT NULL,
+    bio         TEXT,
+    avatar_url  TEXT,
+    profile_url TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS crypto_report_authors (
+    report_id INTEGER NOT NULL REFERENCES crypto_report(id) ON DELETE CASCADE,
+    author_id INTEGER NOT NULL REFERENCES authors(id) ON DELETE CASCADE,
+    position  SMALLINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (report_id, author_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_crypto_report_authors_author
+    ON crypto_report_authors (author_id, report_id);
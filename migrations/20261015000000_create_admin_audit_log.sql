@@ -0,0 +1,21 @@
+-- Admin operation audit trail
+--
+-- One row per admin action (cache clear/purge, stream replay, ...) with who ran it,
+-- the request parameters and whether it succeeded. Rows are never updated.
+
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id          BIGSERIAL PRIMARY KEY,
+    action      TEXT NOT NULL,
+    actor       TEXT NOT NULL,
+    parameters  JSONB NOT NULL DEFAULT '{}'::jsonb,
+    -- `ok` or `error: <message>`
+    outcome     TEXT NOT NULL,
+    request_id  TEXT,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_created_at
+    ON admin_audit_log (created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_action
+    ON admin_audit_log (action, created_at DESC);
@@ -0,0 +1,18 @@
+-- API keys for admin and write APIs
+--
+-- Only the blake3 hash of a key is stored (hex, as printed by `b3sum`), so a leaked
+-- table does not leak usable keys. Scopes: `read`, `write`, `admin`; each grants the
+-- ones before it. Issue a key with:
+--   KEY=$(openssl rand -hex 32); printf %s "$KEY" | b3sum
+--   INSERT INTO api_keys (name, key_hash, scopes) VALUES ('ci', '<hash>', '{write}');
+-- and revoke it by setting `revoked_at`.
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id           BIGSERIAL PRIMARY KEY,
+    name         TEXT NOT NULL UNIQUE,
+    key_hash     TEXT NOT NULL UNIQUE CHECK (key_hash ~ '^[0-9a-f]{64}$'),
+    scopes       TEXT[] NOT NULL DEFAULT '{read}',
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at   TIMESTAMPTZ
+);
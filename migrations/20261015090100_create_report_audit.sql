@@ -0,0 +1,22 @@
+-- Report modification audit trail
+--
+-- One row per report mutation (create, update, delete), written by the crypto reports
+-- DataManager. `changed_fields` names the columns the mutation set and `changes_hash`
+-- is the hex blake3 hash of their new values, so a change can be matched against a
+-- known revision without storing report content twice. Rows are never updated.
+
+CREATE TABLE IF NOT EXISTS report_audit (
+    id             BIGSERIAL PRIMARY KEY,
+    report_id      INTEGER NOT NULL,
+    action         TEXT NOT NULL CHECK (action IN ('create', 'update', 'delete')),
+    -- Key that authenticated the request; NULL for JWT callers, ADMIN_TOKEN and jobs
+    api_key_id     BIGINT REFERENCES api_keys (id) ON DELETE SET NULL,
+    actor          TEXT NOT NULL,
+    changed_fields TEXT[] NOT NULL DEFAULT '{}',
+    changes_hash   TEXT NOT NULL CHECK (changes_hash ~ '^[0-9a-f]{64}$'),
+    request_id     TEXT,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_audit_report
+    ON report_audit (report_id, created_at DESC);
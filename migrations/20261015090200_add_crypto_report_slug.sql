@@ -0,0 +1,31 @@
+-- Human-readable report URLs: /crypto_report/{id}-{slug}
+--
+-- The slug is the Vietnamese report title without its `#id`, with diacritics folded
+-- and the date taken in Vietnamese time (UTC+7), e.g.
+-- `phan-tich-thi-truong-crypto-10-03-2024`. It must stay identical to
+-- `report_slug` in src/services/data_communication/report_slug.rs, which builds
+-- links without reading the column.
+
+ALTER TABLE crypto_report ADD COLUMN IF NOT EXISTS slug TEXT;
+
+CREATE OR REPLACE FUNCTION crypto_report_slug(created TIMESTAMPTZ) RETURNS TEXT
+    LANGUAGE SQL IMMUTABLE AS $$
+    SELECT 'phan-tich-thi-truong-crypto-'
+        || to_char((created AT TIME ZONE 'UTC') + INTERVAL '7 hours', 'DD-MM-YYYY')
+$$;
+
+-- Generated on create (and kept in step if `created_at` is ever corrected)
+CREATE OR REPLACE FUNCTION crypto_report_set_slug() RETURNS TRIGGER
+    LANGUAGE plpgsql AS $$
+BEGIN
+    NEW.slug := crypto_report_slug(NEW.created_at);
+    RETURN NEW;
+END;
+$$;
+
+DROP TRIGGER IF EXISTS crypto_report_slug ON crypto_report;
+CREATE TRIGGER crypto_report_slug
+    BEFORE INSERT OR UPDATE OF created_at ON crypto_report
+    FOR EACH ROW EXECUTE FUNCTION crypto_report_set_slug();
+
+UPDATE crypto_report SET slug = crypto_report_slug(created_at) WHERE slug IS NULL;
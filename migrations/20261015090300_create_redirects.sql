@@ -0,0 +1,17 @@
+-- URL redirects served by `RedirectManager`
+--
+-- Legacy URLs (old Flask paths, renamed slugs) answer `status_code` with a Location
+-- of `target`. `source_path` is a path without query string; a trailing `/*` matches
+-- every path under it, the remainder being appended to a `target` ending in `/*`:
+--   INSERT INTO redirects (source_path, target) VALUES ('/report/*', '/crypto_report/*');
+-- Rows are managed through `/admin/redirects` and reloaded by every instance.
+
+CREATE TABLE IF NOT EXISTS redirects (
+    id          BIGSERIAL PRIMARY KEY,
+    source_path TEXT NOT NULL UNIQUE CHECK (source_path LIKE '/%'),
+    target      TEXT NOT NULL,
+    status_code SMALLINT NOT NULL DEFAULT 301 CHECK (status_code IN (301, 302)),
+    created_by  TEXT NOT NULL DEFAULT 'migration',
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
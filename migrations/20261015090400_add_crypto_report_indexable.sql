@@ -0,0 +1,10 @@
+-- Per-report indexing flag
+--
+-- Embargoed or client-only reports set `indexable = false`: their pages carry
+-- `noindex` robots meta tags and an `X-Robots-Tag` header, and they are left out of
+-- the sitemaps, the news sitemap, llms.txt and the RSS feeds. They stay reachable
+-- by URL and in the reports list. After changing the flag, purge the report
+-- (`POST /admin/cache/purge` with `report_id`) so cached pages and sitemaps follow:
+--   UPDATE crypto_report SET indexable = false WHERE id = 42;
+
+ALTER TABLE crypto_report ADD COLUMN IF NOT EXISTS indexable BOOLEAN NOT NULL DEFAULT TRUE;
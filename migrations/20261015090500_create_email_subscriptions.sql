@@ -0,0 +1,20 @@
+-- Email digest subscriptions of the notifications island
+--
+-- `POST /api/subscribe` adds (or updates) a row; the digest scheduler sends each
+-- subscriber the reports published since `last_sent_at` (or since subscribing),
+-- daily or weekly, in `language`. Every digest links
+-- `/api/unsubscribe?token=<unsubscribe_token>`, which deletes the row.
+
+CREATE TABLE IF NOT EXISTS email_subscriptions (
+    id                BIGSERIAL PRIMARY KEY,
+    -- Stored lowercase, so one address has one subscription
+    email             TEXT NOT NULL UNIQUE CHECK (email = lower(email)),
+    frequency         TEXT NOT NULL DEFAULT 'daily' CHECK (frequency IN ('daily', 'weekly')),
+    language          TEXT NOT NULL DEFAULT 'vi' CHECK (language IN ('vi', 'en')),
+    unsubscribe_token TEXT NOT NULL UNIQUE,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_sent_at      TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_subscriptions_due
+    ON email_subscriptions (frequency, last_sent_at);
@@ -0,0 +1,28 @@
+-- Market data time series
+--
+-- One row per accepted `market_data` stream entry, written by the Layer 3
+-- `HistoryRecorder` on every instance. `recorded_at` is the millisecond timestamp
+-- of the stream entry ID, so replicas recording the same entry insert the same key
+-- and all but the first insert are ignored. `data` is the entry's dashboard
+-- payload; `/api/crypto/history` averages one of its numeric fields per bucket.
+
+CREATE TABLE IF NOT EXISTS market_data_history (
+    recorded_at TIMESTAMPTZ NOT NULL,
+    entry_id    TEXT NOT NULL,
+    data        JSONB NOT NULL,
+    PRIMARY KEY (recorded_at, entry_id)
+);
+
+-- Chunk by week when TimescaleDB is installed; plain Postgres uses the primary key
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'timescaledb') THEN
+        PERFORM create_hypertable(
+            'market_data_history',
+            'recorded_at',
+            chunk_time_interval => INTERVAL '7 days',
+            if_not_exists => TRUE
+        );
+    END IF;
+END
+$$;
@@ -0,0 +1,13 @@
+-- Daily Fear & Greed index
+--
+-- One row per UTC day, written by the Layer 3 `FngHistory` recorder from the
+-- `fng_value` of `market_data` stream entries. The index is published once a day,
+-- so each row keeps the value of the newest entry of its day (`updated_at` is that
+-- entry's timestamp, letting replicas race without moving a day backwards).
+-- `/api/crypto/fng/history` and the homepage gauge sparkline read the last days.
+
+CREATE TABLE IF NOT EXISTS fng_daily (
+    day        DATE PRIMARY KEY,
+    value      SMALLINT NOT NULL CHECK (value BETWEEN 0 AND 100),
+    updated_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,14 @@
+-- Double opt-in of email digest subscriptions
+--
+-- `POST /api/subscribe` stores an unconfirmed row and mails a link to
+-- `/api/subscribe/confirm?token=<confirmation_token>`; digests only go to rows with
+-- `confirmed_at`. `confirmation_sent_at` throttles repeated confirmation emails to
+-- one address, and unconfirmed rows are deleted after a week. Subscriptions made
+-- before confirmation existed are kept as confirmed.
+
+ALTER TABLE email_subscriptions
+    ADD COLUMN IF NOT EXISTS confirmation_token   TEXT UNIQUE,
+    ADD COLUMN IF NOT EXISTS confirmation_sent_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS confirmed_at         TIMESTAMPTZ;
+
+UPDATE email_subscriptions SET confirmed_at = created_at WHERE confirmed_at IS NULL;
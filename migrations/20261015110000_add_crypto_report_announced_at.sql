@@ -0,0 +1,9 @@
+-- When a report's publication was announced (WebSub hub / IndexNow)
+--
+-- The publish notifier claims a report by setting `announced_at` before pinging, so
+-- each report is announced once however many workers and replicas read its
+-- `report_published` event. Existing reports count as announced.
+
+ALTER TABLE crypto_report ADD COLUMN IF NOT EXISTS announced_at TIMESTAMPTZ;
+
+UPDATE crypto_report SET announced_at = created_at WHERE announced_at IS NULL;
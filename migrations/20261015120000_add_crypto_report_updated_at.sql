@@ -0,0 +1,31 @@
+-- When a report last changed: the `Last-Modified` of its pages
+--
+-- Bumped by a trigger on every update that changes a report column (content edits,
+-- slug or `indexable` changes), so conditional GETs see mutations made after
+-- publication. Claiming the publish announcement (`announced_at`) is not a change of
+-- the page. Existing reports start at their `created_at`.
+
+ALTER TABLE crypto_report ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ;
+
+UPDATE crypto_report SET updated_at = created_at WHERE updated_at IS NULL;
+
+ALTER TABLE crypto_report
+    ALTER COLUMN updated_at SET DEFAULT now(),
+    ALTER COLUMN updated_at SET NOT NULL;
+
+CREATE OR REPLACE FUNCTION crypto_report_touch_updated_at() RETURNS TRIGGER
+    LANGUAGE plpgsql AS $$
+BEGIN
+    IF (to_jsonb(NEW) - 'updated_at' - 'announced_at')
+        IS DISTINCT FROM (to_jsonb(OLD) - 'updated_at' - 'announced_at') THEN
+        NEW.updated_at := now();
+    END IF;
+    RETURN NEW;
+END;
+$$;
+
+-- Named to fire after `crypto_report_slug`, so a regenerated slug counts as a change
+DROP TRIGGER IF EXISTS crypto_report_updated_at ON crypto_report;
+CREATE TRIGGER crypto_report_updated_at
+    BEFORE UPDATE ON crypto_report
+    FOR EACH ROW EXECUTE FUNCTION crypto_report_touch_updated_at();
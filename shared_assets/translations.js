@@ -21,6 +21,9 @@ function get_translations_data()
     'report-display': { vi: 'Hiển thị báo cáo', en: 'Displaying report' },
     'site-title': { vi: 'Toàn Cảnh Thị Trường Tiền Mã Hóa', en: 'Crypto Market Overview' },
     'created-at': { vi: 'Tạo lúc', en: 'Created at' },
+    'written-by': { vi: 'Tác giả', en: 'Written by' },
+    'author-reports': { vi: 'Báo cáo của tác giả', en: 'Reports by this author' },
+    'author-rss': { vi: 'Theo dõi RSS', en: 'Follow via RSS' },
     'analysis-summary': { vi: 'Bài phân tích và tổng hợp', en: 'Analysis and summary' },
     'close': { vi: 'Đóng', en: 'Close' },
     'no-report-created': { vi: 'Chưa có báo cáo nào được tạo.', en: 'No reports have been created yet.' },
@@ -44,6 +44,9 @@ class MarketIndicatorsDashboard {
             usStockIndices: null
         };
 
+        // Daily Fear & Greed values ({ date, value }) for the gauge sparkline
+        this.fearGreedHistory = [];
+
         // Dominance history for charts (last 20 data points)
         this.dominanceHistory = {
             btc: [],
@@ -57,6 +60,7 @@ class MarketIndicatorsDashboard {
     init() {
         debugLog('🚀 Initializing Market Indicators Dashboard');
         this.initializeElements();
+        this.loadFearGreedHistory();
 
         // Request initial data immediately before establishing WebSocket
         this.requestInitialData();
@@ -318,6 +322,14 @@ class MarketIndicatorsDashboard {
     }
 
     updateMarketData(data) {
+        // Grey out the numbers while the server reports outdated market data
+        if (data.stale !== undefined) {
+            const container = document.getElementById('market-indicators-dashboard');
+            if (container) {
+                container.classList.toggle('market-data-stale', data.stale === true);
+            }
+        }
+
         // Compact logging: only show summary instead of verbose details
         const cryptoPrices = ['btc_price_usd', 'eth_price_usd', 'sol_price_usd', 'xrp_price_usd', 'ada_price_usd', 'link_price_usd', 'bnb_price_usd'];
         const availablePrices = cryptoPrices.filter(key => data[key] !== undefined);
@@ -542,9 +554,63 @@ class MarketIndicatorsDashboard {
         // Render gauge chart
         this.renderGaugeChart('fear-greed', index);
 
+        // Today's point follows the live value
+        this.recordFearGreedToday(index);
+
         debugLog('✅ Fear & Greed Index updated:', { index, class: indexClass });
     }
 
+    /**
+     * Đọc lịch sử Fear & Greed do server chèn vào trang (30 ngày gần nhất)
+     */
+    loadFearGreedHistory() {
+        const dataElement = document.getElementById('fear-greed-sparkline-data');
+        if (!dataElement) return;
+
+        try {
+            const points = JSON.parse(dataElement.textContent || '[]');
+            this.fearGreedHistory = Array.isArray(points) ? points : [];
+        } catch (error) {
+            debugError('❌ Invalid Fear & Greed history:', error);
+            this.fearGreedHistory = [];
+        }
+        this.renderFearGreedSparkline();
+    }
+
+    recordFearGreedToday(index) {
+        const today = new Date().toISOString().slice(0, 10);
+        const last = this.fearGreedHistory[this.fearGreedHistory.length - 1];
+        if (last && last.date === today) {
+            last.value = index;
+        } else {
+            this.fearGreedHistory.push({ date: today, value: index });
+            if (this.fearGreedHistory.length > 30) {
+                this.fearGreedHistory.shift();
+            }
+        }
+        this.renderFearGreedSparkline();
+    }
+
+    /**
+     * Vẽ sparkline xu hướng Fear & Greed (thang 0-100) dưới gauge
+     */
+    renderFearGreedSparkline() {
+        const svg = document.getElementById('fear-greed-sparkline');
+        if (!svg) return;
+
+        const values = this.fearGreedHistory.map(point => Number(point.value));
+        if (values.length < 2) {
+            svg.innerHTML = '';
+            return;
+        }
+
+        const step = 100 / (values.length - 1);
+        const points = values
+            .map((value, i) => `${(i * step).toFixed(2)},${(24 - (value / 100) * 24).toFixed(2)}`)
+            .join(' ');
+        svg.innerHTML = `<polyline points="${points}"></polyline>`;
+    }
+
     updateBtcDominance(value) {
         const element = this.elements.btcDominance;
         if (!element) return;
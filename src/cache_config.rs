@@ -0,0 +1,259 @@
+//! Cache TTL Configuration
+//!
+//! Server-side cache TTLs and browser `max-age` values per content class, loaded
+//! once at startup and exposed as `AppState::cache_config`.
+//!
+//! Sources, later ones overriding earlier ones:
+//! 1. Built-in defaults (the values the server always shipped with)
+//! 2. JSON file at `CACHE_CONFIG_PATH`, e.g. `{"report": {"ttl_secs": 600}}`
+//! 3. `CACHE_TTL_<CLASS>_SECS` / `CACHE_MAX_AGE_<CLASS>_SECS`, e.g. `CACHE_TTL_RSS_SECS=7200`
+//!
+//! Classes: `market_data`, `report`, `reports_list` (also author archives), `homepage`,
+//! `sitemap`, `rss` (also per-author feeds) and `snapshot` (dashboard PNG card).
+
+use multi_tier_cache::CacheStrategy;
+use serde::Deserialize;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::{info, warn};
+
+static CACHE_CONFIG: OnceLock<CacheConfig> = OnceLock::new();
+
+/// Load the configuration once at startup
+///
+/// # Errors
+/// Returns an error if `CACHE_CONFIG_PATH` is set but unreadable or invalid
+pub fn init_cache_config() -> Result<&'static CacheConfig, String> {
+    if let Some(config) = CACHE_CONFIG.get() {
+        return Ok(config);
+    }
+    let config = CacheConfig::load()?;
+    info!("⏱️ Cache TTLs: {}", config.summary());
+    Ok(CACHE_CONFIG.get_or_init(|| config))
+}
+
+/// Global cache configuration (built-in defaults plus env if `init_cache_config` never ran)
+#[must_use]
+pub fn cache_config() -> &'static CacheConfig {
+    CACHE_CONFIG.get_or_init(|| {
+        CacheConfig::load().unwrap_or_else(|e| {
+            warn!("⚠️ Invalid cache config, using defaults: {}", e);
+            CacheConfig::default()
+        })
+    })
+}
+
+/// Cache lifetimes of one content class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    ttl: Duration,
+    max_age_secs: u32,
+    cache_control: String,
+}
+
+impl CachePolicy {
+    /// Policy with a server-side `ttl` and a browser `max_age` (seconds)
+    #[must_use]
+    pub fn new(ttl: Duration, max_age_secs: u32) -> Self {
+        Self {
+            ttl,
+            max_age_secs,
+            cache_control: format!("public, max-age={max_age_secs}"),
+        }
+    }
+
+    /// Server-side (L1/L2) TTL
+    #[must_use]
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Strategy to pass to the cache manager
+    #[must_use]
+    pub const fn strategy(&self) -> CacheStrategy {
+        CacheStrategy::Custom(self.ttl)
+    }
+
+    /// Browser `max-age` in seconds
+    #[must_use]
+    pub const fn max_age_secs(&self) -> u32 {
+        self.max_age_secs
+    }
+
+    /// `Cache-Control` header value
+    #[must_use]
+    pub fn cache_control(&self) -> &str {
+        &self.cache_control
+    }
+
+    fn with_overrides(&self, ttl_secs: Option<u64>, max_age_secs: Option<u32>) -> Self {
+        Self::new(
+            ttl_secs.map_or(self.ttl, Duration::from_secs),
+            max_age_secs.unwrap_or(self.max_age_secs),
+        )
+    }
+}
+
+/// Cache policies per content class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Market data snapshot and dashboard JSON
+    pub market_data: CachePolicy,
+    /// Rendered report pages
+    pub report: CachePolicy,
+    /// Reports list and author archive pages
+    pub reports_list: CachePolicy,
+    pub homepage: CachePolicy,
+    pub sitemap: CachePolicy,
+    /// Site-wide and per-author RSS feeds
+    pub rss: CachePolicy,
+    /// Dashboard snapshot image
+    pub snapshot: CachePolicy,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            market_data: CachePolicy::new(Duration::from_secs(10), 10),
+            report: CachePolicy::new(Duration::from_mins(5), 300),
+            reports_list: CachePolicy::new(Duration::from_mins(5), 60),
+            homepage: CachePolicy::new(Duration::from_mins(5), 300),
+            sitemap: CachePolicy::new(Duration::from_hours(1), 3600),
+            rss: CachePolicy::new(Duration::from_hours(1), 3600),
+            snapshot: CachePolicy::new(Duration::from_mins(1), 60),
+        }
+    }
+}
+
+/// Partial policy as written in the config file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyOverride {
+    ttl_secs: Option<u64>,
+    max_age_secs: Option<u32>,
+}
+
+/// Config file layout (every class optional)
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    #[serde(default)]
+    market_data: PolicyOverride,
+    #[serde(default)]
+    report: PolicyOverride,
+    #[serde(default)]
+    reports_list: PolicyOverride,
+    #[serde(default)]
+    homepage: PolicyOverride,
+    #[serde(default)]
+    sitemap: PolicyOverride,
+    #[serde(default)]
+    rss: PolicyOverride,
+    #[serde(default)]
+    snapshot: PolicyOverride,
+}
+
+impl CacheConfig {
+    /// Defaults, then `CACHE_CONFIG_PATH`, then `CACHE_TTL_*` / `CACHE_MAX_AGE_*`
+    ///
+    /// # Errors
+    /// Returns an error if `CACHE_CONFIG_PATH` is set but unreadable or invalid
+    pub fn load() -> Result<Self, String> {
+        let file = match std::env::var("CACHE_CONFIG_PATH") {
+            Ok(path) => {
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {path}: {e}"))?;
+                Some(content)
+            }
+            Err(_) => None,
+        };
+        Self::from_sources(file.as_deref(), |name| std::env::var(name).ok())
+    }
+
+    fn from_sources(
+        file: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, String> {
+        let file: FileConfig = match file {
+            Some(content) => serde_json::from_str(content)
+                .map_err(|e| format!("Invalid cache config file: {e}"))?,
+            None => FileConfig::default(),
+        };
+
+        let defaults = Self::default();
+        let resolve = |class: &str, default: &CachePolicy, file: &PolicyOverride| {
+            let upper = class.to_ascii_uppercase();
+            let from_file = default.with_overrides(file.ttl_secs, file.max_age_secs);
+            from_file.with_overrides(
+                env(&format!("CACHE_TTL_{upper}_SECS")).and_then(|v| v.trim().parse().ok()),
+                env(&format!("CACHE_MAX_AGE_{upper}_SECS")).and_then(|v| v.trim().parse().ok()),
+            )
+        };
+
+        Ok(Self {
+            market_data: resolve("market_data", &defaults.market_data, &file.market_data),
+            report: resolve("report", &defaults.report, &file.report),
+            reports_list: resolve("reports_list", &defaults.reports_list, &file.reports_list),
+            homepage: resolve("homepage", &defaults.homepage, &file.homepage),
+            sitemap: resolve("sitemap", &defaults.sitemap, &file.sitemap),
+            rss: resolve("rss", &defaults.rss, &file.rss),
+            snapshot: resolve("snapshot", &defaults.snapshot, &file.snapshot),
+        })
+    }
+
+    /// One-line `class=ttl/max-age` summary for the startup log
+    fn summary(&self) -> String {
+        [
+            ("market_data", &self.market_data),
+            ("report", &self.report),
+            ("reports_list", &self.reports_list),
+            ("homepage", &self.homepage),
+            ("sitemap", &self.sitemap),
+            ("rss", &self.rss),
+            ("snapshot", &self.snapshot),
+        ]
+        .iter()
+        .map(|(class, policy)| {
+            format!("{class}={}s/{}s", policy.ttl.as_secs(), policy.max_age_secs)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_previous_values() {
+        let config = CacheConfig::default();
+        assert_eq!(
+            config.market_data.ttl(),
+            CacheStrategy::RealTime.to_duration()
+        );
+        assert_eq!(config.report.ttl(), CacheStrategy::ShortTerm.to_duration());
+        assert_eq!(config.rss.ttl(), CacheStrategy::MediumTerm.to_duration());
+        assert_eq!(config.reports_list.cache_control(), "public, max-age=60");
+    }
+
+    #[test]
+    fn test_env_overrides_file() -> Result<(), String> {
+        let file = r#"{"report": {"ttl_secs": 600, "max_age_secs": 120}, "rss": {"ttl_secs": 60}}"#;
+        let env = |name: &str| (name == "CACHE_TTL_REPORT_SECS").then(|| "900".to_string());
+        let config = CacheConfig::from_sources(Some(file), env)?;
+
+        assert_eq!(config.report.ttl(), Duration::from_mins(15));
+        assert_eq!(config.report.cache_control(), "public, max-age=120");
+        assert_eq!(config.rss.ttl(), Duration::from_mins(1));
+        assert_eq!(config.rss.max_age_secs(), 3600);
+        assert_eq!(config.sitemap, CacheConfig::default().sitemap);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_file_keys_rejected() {
+        let file = r#"{"reports": {"ttl_secs": 600}}"#;
+        assert!(CacheConfig::from_sources(Some(file), |_| None).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Cross-Instance Cache Invalidation
+//!
+//! Every replica (and every worker in cluster mode) keeps its own L1 Moka cache in
+//! front of the shared L2. When one instance purges or rewrites an entry,
+//! `CacheInvalidationBus` announces the affected keys on a Redis pub/sub channel and
+//! every subscribed instance evicts them from its L1, so no replica keeps serving its
+//! old copy until the L1 TTL runs out. The next read there falls through to L2.
+//!
+//! Subscribers only touch L1: L2 and the disk tier were already updated by the
+//! publishing instance. Instances also receive their own messages, which costs one
+//! L2 read for the entry they just wrote.
+//!
+//! Environment:
+//! - `CACHE_INVALIDATION`: `false` disables the bus (publishing becomes a no-op)
+//! - `CACHE_INVALIDATION_CHANNEL`: pub/sub channel (default `cache:invalidate`)
+
+use multi_tier_cache::{
+    CacheBackend, CacheResult, InvalidationConfig, InvalidationMessage, InvalidationPublisher,
+    InvalidationStats, InvalidationSubscriber,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::redis_endpoint::RedisEndpoint;
+use crate::tasks::TaskRegistry;
+
+/// Publishes and applies L1 invalidations across instances
+pub struct CacheInvalidationBus {
+    inner: Option<Arc<BusConnection>>,
+    l1: Arc<dyn CacheBackend>,
+}
+
+struct BusConnection {
+    publisher: Mutex<InvalidationPublisher>,
+    subscriber: InvalidationSubscriber,
+}
+
+impl CacheInvalidationBus {
+    /// Connect to the Redis master, or build a disabled bus when `CACHE_INVALIDATION=false`
+    ///
+    /// The bus stays on the master resolved now, also across Sentinel failovers.
+    ///
+    /// # Errors
+    /// Returns an error if the master is unknown or invalid, or the publisher cannot connect
+    pub async fn from_env(
+        endpoint: &RedisEndpoint,
+        l1: Arc<dyn CacheBackend>,
+    ) -> anyhow::Result<Self> {
+        if !bus_enabled(std::env::var("CACHE_INVALIDATION").ok().as_deref()) {
+            info!("⏭️ Cross-instance cache invalidation disabled (CACHE_INVALIDATION=false)");
+            return Ok(Self::disabled(l1));
+        }
+
+        let config = InvalidationConfig {
+            channel: std::env::var("CACHE_INVALIDATION_CHANNEL")
+                .unwrap_or_else(|_| InvalidationConfig::default().channel),
+            ..InvalidationConfig::default()
+        };
+        let redis_url = endpoint
+            .master_url()
+            .ok_or_else(|| anyhow::anyhow!("Redis master not resolved"))?;
+        let connection = redis::Client::open(redis_url.as_str())?
+            .get_connection_manager()
+            .await?;
+        let subscriber = InvalidationSubscriber::new(&redis_url, config.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to create invalidation subscriber: {e}"))?;
+        info!("📡 Cache invalidation bus on channel '{}'", config.channel);
+
+        Ok(Self {
+            inner: Some(Arc::new(BusConnection {
+                publisher: Mutex::new(InvalidationPublisher::new(connection, config)),
+                subscriber,
+            })),
+            l1,
+        })
+    }
+
+    /// Bus that neither publishes nor subscribes
+    #[must_use]
+    pub fn disabled(l1: Arc<dyn CacheBackend>) -> Self {
+        Self { inner: None, l1 }
+    }
+
+    /// Whether messages are actually sent and received
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Start applying remote invalidations to L1, supervised as the `cache_invalidation` task
+    pub fn start(&self, tasks: &TaskRegistry) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+        let (inner, l1) = (Arc::clone(inner), Arc::clone(&self.l1));
+        tasks.supervise("cache_invalidation", move || {
+            let l1 = Arc::clone(&l1);
+            let subscriber = inner.subscriber.start(move |message| {
+                let l1 = Arc::clone(&l1);
+                async move { evict_from_l1(l1.as_ref(), message).await }
+            });
+            async move { subscriber.await.map_err(|e| e.to_string()) }
+        });
+    }
+
+    /// Messages received and failures so far (`None` when disabled)
+    #[must_use]
+    pub fn stats(&self) -> Option<InvalidationStats> {
+        self.inner.as_ref().map(|inner| inner.subscriber.stats())
+    }
+
+    /// Tell the other instances to drop `key` from their L1
+    pub async fn publish_remove(&self, key: &str) {
+        self.publish(InvalidationMessage::remove(key)).await;
+    }
+
+    /// Tell the other instances to drop keys matching the glob `pattern` from their L1
+    pub async fn publish_pattern(&self, pattern: &str) {
+        self.publish(InvalidationMessage::remove_pattern(pattern))
+            .await;
+    }
+
+    /// Publish failures are logged only: the local write already succeeded and the
+    /// other instances converge once their L1 entries expire.
+    async fn publish(&self, message: InvalidationMessage) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+        if let Err(e) = inner.publisher.lock().await.publish(&message).await {
+            warn!(
+                "⚠️ Failed to publish cache invalidation {:?}: {}",
+                message, e
+            );
+        }
+    }
+}
+
+/// Apply one invalidation message to the local L1
+///
+/// Updates are treated as removals so the fresh value is read from L2 instead of
+/// trusting the message payload.
+async fn evict_from_l1(l1: &dyn CacheBackend, message: InvalidationMessage) -> CacheResult<()> {
+    debug!("📡 Applying cache invalidation: {:?}", message);
+    match message {
+        InvalidationMessage::Remove { key } | InvalidationMessage::Update { key, .. } => {
+            l1.remove(&key).await
+        }
+        InvalidationMessage::RemovePattern { pattern } => l1.remove_pattern(&pattern).await,
+        InvalidationMessage::RemoveBulk { keys } => {
+            for key in keys {
+                l1.remove(&key).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// `CACHE_INVALIDATION` is on unless explicitly disabled
+fn bus_enabled(value: Option<&str>) -> bool {
+    !value.is_some_and(|v| {
+        matches!(
+            v.trim().to_ascii_lowercase().as_str(),
+            "false" | "0" | "off"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_tier_cache::{Bytes, MokaCache, backends::moka_cache::MokaCacheConfig};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_messages_evict_from_l1() -> CacheResult<()> {
+        let l1 = MokaCache::new(MokaCacheConfig::default())?;
+        for key in ["report_1", "report_2", "report_3"] {
+            l1.set_with_ttl(key, Bytes::from_static(b"html"), Duration::from_mins(5))
+                .await?;
+        }
+
+        evict_from_l1(&l1, InvalidationMessage::remove("report_1")).await?;
+        assert!(l1.get("report_1").await.is_none());
+        assert!(l1.get("report_2").await.is_some());
+
+        evict_from_l1(
+            &l1,
+            InvalidationMessage::update("report_2", Bytes::from_static(b"new"), None),
+        )
+        .await?;
+        assert!(l1.get("report_2").await.is_none());
+
+        evict_from_l1(&l1, InvalidationMessage::remove_pattern("report_*")).await?;
+        assert!(l1.get("report_3").await.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_enabled_unless_disabled() {
+        assert!(bus_enabled(None));
+        assert!(bus_enabled(Some("true")));
+        assert!(!bus_enabled(Some("false")));
+        assert!(!bus_enabled(Some(" OFF ")));
+    }
+}
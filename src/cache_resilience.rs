@@ -0,0 +1,295 @@
+//! L1-Only Degradation When Redis Is Down
+//!
+//! `ResilientL2Backend` sits between the cache manager and the Redis backend. While
+//! Redis is reachable it forwards every call; when the connection cannot be made at
+//! startup or a health probe / write fails at runtime, it switches to degraded mode:
+//! reads miss and writes are skipped immediately, so pages keep being served from
+//! (and cached in) L1 instead of every request waiting on a dead connection.
+//!
+//! A background monitor probes Redis every `CACHE_L2_RECONNECT_SECS` (default 10),
+//! connecting for the first time if startup found Redis down, and leaves degraded
+//! mode on the first successful probe. After a Sentinel failover the probe
+//! reconnects to the new master instead of checking the old one.
+
+use futures::future::BoxFuture;
+use multi_tier_cache::{
+    Bytes, CacheBackend, CacheError, CacheResult, L2CacheBackend, backends::redis_cache::RedisCache,
+};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::redis_endpoint::RedisEndpoint;
+use crate::tasks::TaskRegistry;
+
+/// Default delay between Redis probes
+const DEFAULT_RECONNECT_INTERVAL: Duration = Duration::from_secs(10);
+/// Max time a probe may wait on Redis before counting as failed
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// L2 backend that degrades to "always miss" while Redis is unavailable
+pub struct ResilientL2Backend {
+    inner: RwLock<Option<Arc<dyn L2CacheBackend>>>,
+    /// `RedisEndpoint::generation` the inner backend was connected under
+    generation: AtomicU64,
+    available: AtomicBool,
+    /// Times the backend entered degraded mode
+    outages: AtomicU64,
+}
+
+impl ResilientL2Backend {
+    /// Connect to the Redis master, starting in degraded mode if it is unreachable
+    pub async fn connect(endpoint: &RedisEndpoint) -> Self {
+        let generation = endpoint.generation();
+        let connected = match endpoint.master_url() {
+            Some(url) => RedisCache::with_url(&url).await.map_err(|e| e.to_string()),
+            None => Err("Redis master not resolved".to_string()),
+        };
+        match connected {
+            Ok(redis) => {
+                let backend = Self::with_backend(Arc::new(redis));
+                backend.generation.store(generation, Ordering::Relaxed);
+                backend
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ Redis unavailable at startup, serving from L1 only until it reconnects: {}",
+                    e
+                );
+                Self {
+                    inner: RwLock::new(None),
+                    generation: AtomicU64::new(generation),
+                    available: AtomicBool::new(false),
+                    outages: AtomicU64::new(1),
+                }
+            }
+        }
+    }
+
+    /// Wrap an already connected backend
+    #[must_use]
+    pub fn with_backend(inner: Arc<dyn L2CacheBackend>) -> Self {
+        Self {
+            inner: RwLock::new(Some(inner)),
+            generation: AtomicU64::new(0),
+            available: AtomicBool::new(true),
+            outages: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether calls currently reach Redis
+    #[must_use]
+    pub fn is_available(&self) -> bool {
+        self.available.load(Ordering::Relaxed)
+    }
+
+    /// Times the backend entered degraded mode since startup
+    #[must_use]
+    pub fn outages(&self) -> u64 {
+        self.outages.load(Ordering::Relaxed)
+    }
+
+    /// Spawn the reconnect/health monitor, supervised as the `cache_l2_monitor` task
+    pub fn spawn_monitor(self: &Arc<Self>, endpoint: Arc<RedisEndpoint>, tasks: &TaskRegistry) {
+        let interval = reconnect_interval(std::env::var("CACHE_L2_RECONNECT_SECS").ok().as_deref());
+        let backend = Arc::clone(self);
+        tasks.supervise("cache_l2_monitor", move || {
+            let (backend, endpoint) = (Arc::clone(&backend), Arc::clone(&endpoint));
+            async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    backend.probe(&endpoint).await;
+                }
+            }
+        });
+    }
+
+    /// Check Redis once, connecting first if startup never did or the master moved
+    async fn probe(&self, endpoint: &RedisEndpoint) {
+        let generation = endpoint.generation();
+        let current = self
+            .current()
+            .filter(|_| self.generation.load(Ordering::Relaxed) == generation);
+        let healthy = match (current, endpoint.master_url()) {
+            (Some(inner), _) => tokio::time::timeout(PROBE_TIMEOUT, inner.health_check())
+                .await
+                .unwrap_or(false),
+            (None, Some(url)) => {
+                match tokio::time::timeout(PROBE_TIMEOUT, RedisCache::with_url(&url)).await {
+                    Ok(Ok(redis)) => {
+                        if let Ok(mut inner) = self.inner.write() {
+                            *inner = Some(Arc::new(redis));
+                        }
+                        self.generation.store(generation, Ordering::Relaxed);
+                        true
+                    }
+                    Ok(Err(_)) | Err(_) => false,
+                }
+            }
+            (None, None) => false,
+        };
+        if healthy {
+            self.mark_available();
+        } else {
+            self.mark_unavailable("health check failed");
+        }
+    }
+
+    fn current(&self) -> Option<Arc<dyn L2CacheBackend>> {
+        self.inner.read().ok().and_then(|inner| inner.clone())
+    }
+
+    /// Backend to call, `None` while degraded
+    fn backend(&self) -> Option<Arc<dyn L2CacheBackend>> {
+        if self.is_available() {
+            self.current()
+        } else {
+            None
+        }
+    }
+
+    fn mark_available(&self) {
+        if !self.available.swap(true, Ordering::Relaxed) {
+            info!("✅ Redis reachable again, L2 cache re-enabled");
+        }
+    }
+
+    fn mark_unavailable(&self, reason: &str) {
+        if self.available.swap(false, Ordering::Relaxed) {
+            self.outages.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "⚠️ Redis L2 unavailable ({}), serving from L1 only until it reconnects",
+                reason
+            );
+        }
+    }
+
+    fn unavailable_error() -> CacheError {
+        CacheError::BackendError("L2 cache unavailable (degraded to L1 only)".to_string())
+    }
+}
+
+impl CacheBackend for ResilientL2Backend {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Bytes>> {
+        Box::pin(async move {
+            match self.backend() {
+                Some(inner) => inner.get(key).await,
+                None => None,
+            }
+        })
+    }
+
+    /// Failed or skipped writes are not errors: the value is still cached in L1
+    fn set_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+        value: Bytes,
+        ttl: Duration,
+    ) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            if let Some(inner) = self.backend()
+                && let Err(e) = inner.set_with_ttl(key, value, ttl).await
+            {
+                self.mark_unavailable(&e.to_string());
+            }
+            Ok(())
+        })
+    }
+
+    /// Removals report the outage so purges don't silently leave entries in Redis
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            match self.backend() {
+                Some(inner) => inner.remove(key).await,
+                None => Err(Self::unavailable_error()),
+            }
+        })
+    }
+
+    fn remove_pattern<'a>(&'a self, pattern: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            match self.backend() {
+                Some(inner) => inner.remove_pattern(pattern).await,
+                None => Err(Self::unavailable_error()),
+            }
+        })
+    }
+
+    fn health_check(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            match self.backend() {
+                Some(inner) => inner.health_check().await,
+                None => false,
+            }
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "ResilientRedis"
+    }
+}
+
+impl L2CacheBackend for ResilientL2Backend {
+    fn get_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Option<(Bytes, Option<Duration>)>> {
+        Box::pin(async move {
+            match self.backend() {
+                Some(inner) => inner.get_with_ttl(key).await,
+                None => None,
+            }
+        })
+    }
+}
+
+/// `CACHE_L2_RECONNECT_SECS`, at least one second
+fn reconnect_interval(value: Option<&str>) -> Duration {
+    value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map_or(DEFAULT_RECONNECT_INTERVAL, |secs| {
+            Duration::from_secs(secs.max(1))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_tier_cache::{MokaCache, backends::moka_cache::MokaCacheConfig};
+
+    #[tokio::test]
+    async fn test_degraded_mode_skips_backend() -> CacheResult<()> {
+        let inner = Arc::new(MokaCache::new(MokaCacheConfig::default())?);
+        let backend =
+            ResilientL2Backend::with_backend(Arc::clone(&inner) as Arc<dyn L2CacheBackend>);
+        backend
+            .set_with_ttl("page", Bytes::from_static(b"html"), Duration::from_mins(1))
+            .await?;
+        assert!(backend.get("page").await.is_some());
+
+        backend.mark_unavailable("test");
+        assert!(!backend.is_available());
+        assert_eq!(backend.outages(), 1);
+        assert!(backend.get("page").await.is_none());
+        assert!(backend.get_with_ttl("page").await.is_none());
+        // Writes keep succeeding (L1 still caches), removals report the outage
+        backend
+            .set_with_ttl("other", Bytes::from_static(b"x"), Duration::from_mins(1))
+            .await?;
+        assert!(inner.get("other").await.is_none());
+        assert!(backend.remove("page").await.is_err());
+
+        backend.mark_available();
+        assert_eq!(backend.get("page").await, Some(Bytes::from_static(b"html")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reconnect_interval() {
+        assert_eq!(reconnect_interval(None), DEFAULT_RECONNECT_INTERVAL);
+        assert_eq!(reconnect_interval(Some("30")), Duration::from_secs(30));
+        assert_eq!(reconnect_interval(Some("0")), Duration::from_secs(1));
+        assert_eq!(reconnect_interval(Some("soon")), DEFAULT_RECONNECT_INTERVAL);
+    }
+}
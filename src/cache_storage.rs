@@ -0,0 +1,316 @@
+//! L2 Cache Storage Encoding
+//!
+//! Rendered pages are cached as gzip because that is what we serve, but gzip is a poor
+//! storage format for thousands of near-identical per-report, per-language HTML entries.
+//! `TranscodingL2Backend` wraps the Redis backend and re-encodes gzip values with a
+//! denser `BlobCodec` on write (zstd with an optional HTML dictionary when the
+//! `zstd-cache` feature is enabled), converting back to gzip on read. L1 keeps the
+//! ready-to-serve gzip bytes, so only L2 hits pay the transcode.
+//!
+//! Stored blobs carry a codec magic prefix; values without it (typed JSON, legacy
+//! entries) pass through untouched.
+
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use futures::future::BoxFuture;
+use multi_tier_cache::{Bytes, CacheBackend, CacheError, CacheResult, L2CacheBackend};
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Gzip member header magic bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Storage codec used for cached blobs in L2
+pub trait BlobCodec: Send + Sync + 'static {
+    /// Codec name for logs
+    fn name(&self) -> &'static str;
+    /// Prefix marking blobs written by this codec
+    fn magic(&self) -> &'static [u8];
+    /// Encode raw (uncompressed) content
+    ///
+    /// # Errors
+    /// Returns an error if encoding fails
+    fn encode(&self, raw: &[u8]) -> io::Result<Vec<u8>>;
+    /// Decode content produced by `encode`
+    ///
+    /// # Errors
+    /// Returns an error if the blob is corrupt
+    fn decode(&self, encoded: &[u8]) -> io::Result<Vec<u8>>;
+}
+
+/// L2 backend wrapper that stores gzip values with a denser codec
+pub struct TranscodingL2Backend<C: BlobCodec> {
+    inner: Arc<dyn L2CacheBackend>,
+    codec: Arc<C>,
+}
+
+impl<C: BlobCodec> TranscodingL2Backend<C> {
+    /// Wrap an L2 backend with the given storage codec
+    #[must_use]
+    pub fn new(inner: Arc<dyn L2CacheBackend>, codec: C) -> Self {
+        Self {
+            inner,
+            codec: Arc::new(codec),
+        }
+    }
+
+    /// Convert a value into its storage form (gzip → codec blob when smaller)
+    fn to_storage(codec: &C, value: Bytes) -> Bytes {
+        if !value.starts_with(&GZIP_MAGIC) {
+            return value;
+        }
+
+        let encoded = gunzip(&value).and_then(|raw| codec.encode(&raw));
+        match encoded {
+            Ok(encoded) if codec.magic().len() + encoded.len() < value.len() => {
+                debug!(
+                    "🗜️ L2 storage: gzip {}B → {} {}B",
+                    value.len(),
+                    codec.name(),
+                    codec.magic().len() + encoded.len()
+                );
+                let mut blob = Vec::with_capacity(codec.magic().len() + encoded.len());
+                blob.extend_from_slice(codec.magic());
+                blob.extend_from_slice(&encoded);
+                Bytes::from(blob)
+            }
+            Ok(_) => value,
+            Err(e) => {
+                warn!(
+                    "⚠️ L2 storage: {} encoding failed, storing gzip: {}",
+                    codec.name(),
+                    e
+                );
+                value
+            }
+        }
+    }
+
+    /// Convert a stored value back into the served gzip form
+    fn from_storage(codec: &C, stored: Bytes) -> Option<Bytes> {
+        let Some(encoded) = stored.strip_prefix(codec.magic()) else {
+            return Some(stored);
+        };
+
+        match codec.decode(encoded).and_then(|raw| gzip(&raw)) {
+            Ok(gzipped) => Some(Bytes::from(gzipped)),
+            Err(e) => {
+                // Treat corrupt blobs as a miss so the page is re-rendered
+                warn!("⚠️ L2 storage: {} decoding failed: {}", codec.name(), e);
+                None
+            }
+        }
+    }
+
+    /// Run a transcode on the blocking pool (entries can be several MB)
+    async fn transcode<T, F>(codec: &Arc<C>, f: F) -> CacheResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&C) -> T + Send + 'static,
+    {
+        let codec = Arc::clone(codec);
+        tokio::task::spawn_blocking(move || f(&codec))
+            .await
+            .map_err(|e| CacheError::InternalError(format!("Transcode task failed: {e}")))
+    }
+}
+
+impl<C: BlobCodec> CacheBackend for TranscodingL2Backend<C> {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Bytes>> {
+        Box::pin(async move {
+            let stored = self.inner.get(key).await?;
+            Self::transcode(&self.codec, move |codec| Self::from_storage(codec, stored))
+                .await
+                .ok()
+                .flatten()
+        })
+    }
+
+    fn set_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+        value: Bytes,
+        ttl: Duration,
+    ) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            let stored =
+                Self::transcode(&self.codec, move |codec| Self::to_storage(codec, value)).await?;
+            self.inner.set_with_ttl(key, stored, ttl).await
+        })
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        self.inner.remove(key)
+    }
+
+    fn remove_pattern<'a>(&'a self, pattern: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        self.inner.remove_pattern(pattern)
+    }
+
+    fn health_check(&self) -> BoxFuture<'_, bool> {
+        self.inner.health_check()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+impl<C: BlobCodec> L2CacheBackend for TranscodingL2Backend<C> {
+    fn get_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Option<(Bytes, Option<Duration>)>> {
+        Box::pin(async move {
+            let (stored, ttl) = self.inner.get_with_ttl(key).await?;
+            let value =
+                Self::transcode(&self.codec, move |codec| Self::from_storage(codec, stored))
+                    .await
+                    .ok()
+                    .flatten()?;
+            Some((value, ttl))
+        })
+    }
+}
+
+fn gunzip(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut raw = Vec::with_capacity(data.len() * 4);
+    GzDecoder::new(data).read_to_end(&mut raw)?;
+    Ok(raw)
+}
+
+fn gzip(raw: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::with_capacity(raw.len() / 3), Compression::default());
+    encoder.write_all(raw)?;
+    encoder.finish()
+}
+
+/// Zstd storage codec with optional trained dictionary
+///
+/// Environment:
+/// - `CACHE_ZSTD_LEVEL`: compression level (default 9)
+/// - `CACHE_ZSTD_DICT_PATH`: dictionary trained on report HTML (`zstd --train`)
+#[cfg(feature = "zstd-cache")]
+pub struct ZstdCodec {
+    level: i32,
+    dictionary: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "zstd-cache")]
+impl ZstdCodec {
+    /// Default zstd level - good ratio while staying fast enough for per-render writes
+    const DEFAULT_LEVEL: i32 = 9;
+
+    /// Build the codec from `CACHE_ZSTD_LEVEL` / `CACHE_ZSTD_DICT_PATH`
+    ///
+    /// # Errors
+    /// Returns an error if the configured dictionary file cannot be read
+    pub fn from_env() -> io::Result<Self> {
+        let level = std::env::var("CACHE_ZSTD_LEVEL")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(Self::DEFAULT_LEVEL);
+        let dictionary = match std::env::var("CACHE_ZSTD_DICT_PATH") {
+            Ok(path) => {
+                let dictionary = std::fs::read(&path)?;
+                tracing::info!(
+                    "📚 Loaded zstd cache dictionary {} ({}KB)",
+                    path,
+                    dictionary.len() / 1024
+                );
+                Some(dictionary)
+            }
+            Err(_) => None,
+        };
+        Ok(Self { level, dictionary })
+    }
+}
+
+#[cfg(feature = "zstd-cache")]
+impl BlobCodec for ZstdCodec {
+    fn name(&self) -> &'static str {
+        "zstd"
+    }
+
+    fn magic(&self) -> &'static [u8] {
+        b"WSRZ1"
+    }
+
+    fn encode(&self, raw: &[u8]) -> io::Result<Vec<u8>> {
+        let mut encoder = match &self.dictionary {
+            Some(dictionary) => {
+                zstd::stream::write::Encoder::with_dictionary(Vec::new(), self.level, dictionary)?
+            }
+            None => zstd::stream::write::Encoder::new(Vec::new(), self.level)?,
+        };
+        encoder.write_all(raw)?;
+        encoder.finish()
+    }
+
+    fn decode(&self, encoded: &[u8]) -> io::Result<Vec<u8>> {
+        let mut raw = Vec::with_capacity(encoded.len() * 6);
+        match &self.dictionary {
+            Some(dictionary) => {
+                zstd::stream::read::Decoder::with_dictionary(encoded, dictionary)?
+                    .read_to_end(&mut raw)?;
+            }
+            None => {
+                zstd::stream::read::Decoder::new(encoded)?.read_to_end(&mut raw)?;
+            }
+        }
+        Ok(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Toy codec for payloads made of one 16-byte unit repeated 64 times:
+    /// stores just the unit, so the envelope and gzip round trip can be verified without zstd.
+    struct RawCodec;
+
+    impl BlobCodec for RawCodec {
+        fn name(&self) -> &'static str {
+            "raw"
+        }
+
+        fn magic(&self) -> &'static [u8] {
+            b"RAW1"
+        }
+
+        fn encode(&self, raw: &[u8]) -> io::Result<Vec<u8>> {
+            Ok(raw.get(..raw.len().min(16)).unwrap_or_default().to_vec())
+        }
+
+        fn decode(&self, encoded: &[u8]) -> io::Result<Vec<u8>> {
+            Ok(encoded.repeat(64))
+        }
+    }
+
+    type Backend = TranscodingL2Backend<RawCodec>;
+
+    #[test]
+    fn test_non_gzip_values_pass_through() {
+        let json = Bytes::from_static(br#"{"btc_price_usd": 1}"#);
+        let stored = Backend::to_storage(&RawCodec, json.clone());
+        assert_eq!(stored, json);
+        assert_eq!(Backend::from_storage(&RawCodec, stored), Some(json));
+    }
+
+    #[test]
+    fn test_gzip_values_round_trip_through_codec() -> Result<(), Box<dyn std::error::Error>> {
+        let html = "<i>abcdefghi</i>".repeat(64).into_bytes();
+        let gzipped = Bytes::from(gzip(&html)?);
+
+        let stored = Backend::to_storage(&RawCodec, gzipped.clone());
+        assert!(stored.starts_with(b"RAW1"));
+        assert!(stored.len() < gzipped.len());
+
+        let served = Backend::from_storage(&RawCodec, stored).ok_or("decode failed")?;
+        assert!(served.starts_with(&GZIP_MAGIC));
+        assert_eq!(gunzip(&served)?, html);
+        Ok(())
+    }
+}
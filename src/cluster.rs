@@ -0,0 +1,421 @@
+//! Multi-Process Clustering
+//!
+//! Optional pre-fork model for large hosts: a supervisor process spawns N worker
+//! processes of the same binary. Every worker builds its own `AppState` (Tera,
+//! L1 cache, DB pool) and binds the same port with `SO_REUSEPORT`, so the kernel
+//! load-balances connections while Redis (L2 cache + streams) stays shared.
+//!
+//! Environment:
+//! - `WORKERS`: number of worker processes (`auto` = CPU cores, default 1 = single process)
+//! - `WORKER_ID`: set by the supervisor on each child; marks the process as a worker
+//! - `REUSE_PORT`: `true` to bind with `SO_REUSEPORT` when workers are managed
+//!   externally (systemd, multiple containers sharing the host network)
+//!
+//! Background jobs that must run once per host (cache warmup, sitemap refresher,
+//! publish notifier, recorders, digests, heartbeat, stream retention) only start on
+//! worker 0 (`runs_singletons`). Rate-limit and crawl buckets stay per process, so
+//! with N workers a client may get up to N times the configured burst.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, TcpSocket};
+use tokio::process::{Child, Command};
+use tokio::sync::watch;
+use tracing::{error, info, warn};
+
+/// Initial delay before restarting a crashed worker
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+/// Upper bound for the exponential restart backoff
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+/// A worker that stays up this long is considered healthy and resets its backoff
+const HEALTHY_UPTIME: Duration = Duration::from_mins(1);
+/// How long the supervisor waits for workers to exit on shutdown before killing them
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(15);
+
+/// Clustering configuration resolved from the environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterConfig {
+    /// Number of worker processes (1 = classic single-process mode)
+    pub workers: usize,
+    /// Worker index when running as a supervised child
+    pub worker_id: Option<usize>,
+    /// Bind the listener with `SO_REUSEPORT`
+    pub reuse_port: bool,
+}
+
+impl ClusterConfig {
+    /// Read clustering settings from `WORKERS`, `WORKER_ID` and `REUSE_PORT`
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_values(
+            std::env::var("WORKERS").ok().as_deref(),
+            std::env::var("WORKER_ID").ok().as_deref(),
+            std::env::var("REUSE_PORT").ok().as_deref(),
+        )
+    }
+
+    /// Build configuration from raw values (invalid values fall back to single-process mode)
+    #[must_use]
+    pub fn from_values(
+        workers: Option<&str>,
+        worker_id: Option<&str>,
+        reuse_port: Option<&str>,
+    ) -> Self {
+        let workers = match workers.map(str::trim) {
+            Some("auto") => num_cpus::get(),
+            Some(value) => value.parse::<usize>().unwrap_or_else(|e| {
+                warn!("⚠️ Invalid WORKERS value '{}', using 1: {}", value, e);
+                1
+            }),
+            None => 1,
+        }
+        .max(1);
+
+        let worker_id = worker_id.and_then(|id| id.trim().parse::<usize>().ok());
+        let reuse_port = workers > 1
+            || worker_id.is_some()
+            || reuse_port.is_some_and(|v| v.eq_ignore_ascii_case("true") || v == "1");
+
+        Self {
+            workers,
+            worker_id,
+            reuse_port,
+        }
+    }
+
+    /// Process-wide configuration from the environment
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static CONFIG: OnceLock<ClusterConfig> = OnceLock::new();
+        CONFIG.get_or_init(Self::from_env)
+    }
+
+    /// Whether this process should supervise workers instead of serving requests
+    #[must_use]
+    pub fn is_supervisor(&self) -> bool {
+        self.workers > 1 && self.worker_id.is_none()
+    }
+
+    /// Whether this process starts the once-per-host background jobs
+    /// (single process or worker 0)
+    #[must_use]
+    pub fn runs_singletons(&self) -> bool {
+        self.worker_id.is_none_or(|id| id == 0)
+    }
+}
+
+/// Signal that asked the process to shut down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownSignal {
+    /// Ctrl+C (`SIGINT`)
+    Interrupt,
+    /// `SIGTERM`, sent by `docker stop`, systemd and Kubernetes
+    Terminate,
+}
+
+impl ShutdownSignal {
+    /// Signal name for logs
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Interrupt => "SIGINT",
+            Self::Terminate => "SIGTERM",
+        }
+    }
+
+    #[cfg(unix)]
+    const fn as_raw(self) -> libc::c_int {
+        match self {
+            Self::Interrupt => libc::SIGINT,
+            Self::Terminate => libc::SIGTERM,
+        }
+    }
+}
+
+/// Wait for Ctrl+C or, on unix, `SIGTERM`
+///
+/// A handler that fails to install is logged and never fires, so the process keeps
+/// running until the other signal arrives instead of shutting down at once.
+pub async fn shutdown_signal() -> ShutdownSignal {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("⚠️ Failed to install CTRL+C signal handler: {}", e);
+            std::future::pending::<()>().await;
+        }
+        ShutdownSignal::Interrupt
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut stream) => {
+                if stream.recv().await.is_none() {
+                    std::future::pending::<()>().await;
+                }
+            }
+            Err(e) => {
+                warn!("⚠️ Failed to install SIGTERM signal handler: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+        ShutdownSignal::Terminate
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<ShutdownSignal>();
+
+    tokio::select! {
+        signal = interrupt => signal,
+        signal = terminate => signal,
+    }
+}
+
+/// Bind the HTTP listener, optionally with `SO_REUSEPORT`
+///
+/// # Errors
+///
+/// Returns an error if the socket cannot be created, configured or bound
+pub fn bind_listener(addr: SocketAddr, reuse_port: bool) -> io::Result<TcpListener> {
+    let socket = if addr.is_ipv4() {
+        TcpSocket::new_v4()?
+    } else {
+        TcpSocket::new_v6()?
+    };
+    socket.set_reuseaddr(true)?;
+
+    if reuse_port {
+        #[cfg(unix)]
+        socket.set_reuseport(true)?;
+        #[cfg(not(unix))]
+        warn!("⚠️ SO_REUSEPORT is not supported on this platform - binding without it");
+    }
+
+    socket.bind(addr)?;
+    socket.listen(1024)
+}
+
+/// Compute the next restart delay for a worker
+///
+/// Doubles the previous delay up to `MAX_RESTART_BACKOFF`, and resets to the
+/// initial delay when the worker ran long enough to be considered healthy.
+#[must_use]
+pub fn next_restart_backoff(previous: Duration, uptime: Duration) -> Duration {
+    if uptime >= HEALTHY_UPTIME {
+        INITIAL_RESTART_BACKOFF
+    } else {
+        (previous * 2).min(MAX_RESTART_BACKOFF)
+    }
+}
+
+/// Run the supervisor: spawn `config.workers` children and restart them when they exit
+///
+/// Children re-execute the current binary with the same arguments plus `WORKER_ID`.
+/// On Ctrl+C or `SIGTERM` the supervisor stops restarting, forwards the signal to the
+/// workers, waits for them to drain and kills stragglers after a grace period.
+///
+/// # Errors
+///
+/// Returns an error if the current executable path cannot be resolved
+pub async fn run_supervisor(config: &ClusterConfig) -> Result<(), anyhow::Error> {
+    let exe = std::env::current_exe()?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (shutdown_tx, shutdown_rx) = watch::channel(None);
+
+    info!(
+        "🧭 Supervisor starting {} workers (SO_REUSEPORT) from {}",
+        config.workers,
+        exe.display()
+    );
+
+    let mut handles = Vec::with_capacity(config.workers);
+    for worker_id in 0..config.workers {
+        let exe = exe.clone();
+        let args = args.clone();
+        let shutdown_rx = shutdown_rx.clone();
+        handles.push(tokio::spawn(async move {
+            supervise_worker(worker_id, &exe, &args, shutdown_rx).await;
+        }));
+    }
+
+    let signal = shutdown_signal().await;
+    info!(
+        "🛑 Supervisor received {} - stopping workers",
+        signal.as_str()
+    );
+    let _ = shutdown_tx.send(Some(signal));
+
+    for handle in handles {
+        if let Err(e) = handle.await {
+            error!("❌ Worker supervision task failed: {}", e);
+        }
+    }
+
+    info!("👋 Supervisor shutdown complete");
+    Ok(())
+}
+
+/// Keep one worker slot alive until shutdown
+async fn supervise_worker(
+    worker_id: usize,
+    exe: &std::path::Path,
+    args: &[String],
+    mut shutdown_rx: watch::Receiver<Option<ShutdownSignal>>,
+) {
+    let mut backoff = INITIAL_RESTART_BACKOFF;
+
+    while shutdown_rx.borrow().is_none() {
+        let started = Instant::now();
+        let mut child = match Command::new(exe)
+            .args(args)
+            .env("WORKER_ID", worker_id.to_string())
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                error!("❌ Failed to spawn worker {}: {}", worker_id, e);
+                backoff = next_restart_backoff(backoff, Duration::ZERO);
+                tokio::select! {
+                    () = tokio::time::sleep(backoff) => continue,
+                    _ = shutdown_rx.changed() => break,
+                }
+            }
+        };
+        info!(
+            "👷 Worker {} started (pid {})",
+            worker_id,
+            child.id().unwrap_or_default()
+        );
+
+        tokio::select! {
+            status = child.wait() => {
+                let uptime = started.elapsed();
+                match status {
+                    Ok(status) => warn!(
+                        "⚠️ Worker {} exited with {} after {:?}",
+                        worker_id, status, uptime
+                    ),
+                    Err(e) => error!("❌ Failed to wait for worker {}: {}", worker_id, e),
+                }
+                backoff = next_restart_backoff(backoff, uptime);
+                info!("🔁 Restarting worker {} in {:?}", worker_id, backoff);
+                tokio::select! {
+                    () = tokio::time::sleep(backoff) => {}
+                    _ = shutdown_rx.changed() => break,
+                }
+            }
+            _ = shutdown_rx.changed() => {
+                let signal = *shutdown_rx.borrow();
+                if let Some(signal) = signal {
+                    forward_signal(worker_id, &child, signal);
+                }
+                if tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, child.wait()).await.is_err() {
+                    warn!(
+                        "⚠️ Worker {} did not exit within {:?} - killing",
+                        worker_id, SHUTDOWN_GRACE_PERIOD
+                    );
+                    if let Err(e) = child.kill().await {
+                        error!("❌ Failed to kill worker {}: {}", worker_id, e);
+                    }
+                }
+                break;
+            }
+        }
+    }
+
+    info!("✅ Worker {} slot stopped", worker_id);
+}
+
+/// Send `signal` to a running worker so it drains like a single-process server
+#[cfg(unix)]
+fn forward_signal(worker_id: usize, child: &Child, signal: ShutdownSignal) {
+    // No id once the child has been reaped: it already exited
+    let Some(pid) = child.id().and_then(|pid| libc::pid_t::try_from(pid).ok()) else {
+        return;
+    };
+    // SAFETY: kill() has no memory-safety preconditions, and `pid` is our own child
+    // that has not been reaped yet, so the id cannot have been reused.
+    if unsafe { libc::kill(pid, signal.as_raw()) } != 0 {
+        warn!(
+            "⚠️ Failed to forward {} to worker {}: {}",
+            signal.as_str(),
+            worker_id,
+            io::Error::last_os_error()
+        );
+    }
+}
+
+/// Consoles deliver Ctrl+C to the whole process group, and there is no `SIGTERM`
+#[cfg(not(unix))]
+fn forward_signal(_worker_id: usize, _child: &Child, _signal: ShutdownSignal) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_to_single_process() {
+        let config = ClusterConfig::from_values(None, None, None);
+        assert_eq!(config.workers, 1);
+        assert!(!config.is_supervisor());
+        assert!(!config.reuse_port);
+
+        let invalid = ClusterConfig::from_values(Some("many"), None, None);
+        assert_eq!(invalid.workers, 1);
+    }
+
+    #[test]
+    fn test_supervisor_and_worker_roles() {
+        let supervisor = ClusterConfig::from_values(Some("4"), None, None);
+        assert!(supervisor.is_supervisor());
+        assert!(supervisor.reuse_port);
+
+        let worker = ClusterConfig::from_values(Some("4"), Some("2"), None);
+        assert!(!worker.is_supervisor());
+        assert_eq!(worker.worker_id, Some(2));
+        assert!(worker.reuse_port);
+
+        // Externally pre-forked: single process per instance, shared port
+        let external = ClusterConfig::from_values(None, None, Some("true"));
+        assert!(!external.is_supervisor());
+        assert!(external.reuse_port);
+    }
+
+    #[test]
+    fn test_singleton_jobs_run_on_worker_zero_only() {
+        assert!(ClusterConfig::from_values(None, None, None).runs_singletons());
+        assert!(ClusterConfig::from_values(Some("4"), Some("0"), None).runs_singletons());
+        assert!(!ClusterConfig::from_values(Some("4"), Some("3"), None).runs_singletons());
+    }
+
+    #[test]
+    fn test_restart_backoff() {
+        let short = Duration::from_secs(2);
+        assert_eq!(
+            next_restart_backoff(INITIAL_RESTART_BACKOFF, short),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            next_restart_backoff(Duration::from_secs(20), short),
+            MAX_RESTART_BACKOFF
+        );
+        assert_eq!(
+            next_restart_backoff(MAX_RESTART_BACKOFF, HEALTHY_UPTIME),
+            INITIAL_RESTART_BACKOFF
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_forward_signal_reaches_worker() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let Ok(mut child) = Command::new("sleep").arg("30").kill_on_drop(true).spawn() else {
+            return; // no `sleep` binary in this environment
+        };
+        forward_signal(0, &child, ShutdownSignal::Terminate);
+        let status = tokio::time::timeout(Duration::from_secs(5), child.wait()).await;
+        assert!(matches!(status, Ok(Ok(status)) if status.signal() == Some(libc::SIGTERM)));
+    }
+}
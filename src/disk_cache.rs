@@ -0,0 +1,617 @@
+//! L3 Disk Cache Tier
+//!
+//! Optional persistent tier below Redis for served artifacts - gzip-compressed pages
+//! and feeds, and PDFs. A restart empties L1 and a Redis flush empties L2; with this
+//! tier enabled the first request for each page is answered from disk (and promoted
+//! back up) instead of re-rendering every report.
+//!
+//! Other values (typed JSON, market data) are not persisted: they are cheap to
+//! rebuild and go stale quickly.
+//!
+//! Each entry is one file named after the BLAKE3 hash of its key, holding a small
+//! header (magic, expiry, key) followed by the value. Writes go through a temp file
+//! and a rename so readers never see a partial entry.
+//!
+//! Environment:
+//! - `CACHE_DISK_DIR`: directory for entries (unset disables the tier)
+//! - `CACHE_DISK_MAX_MB`: size budget; the oldest entries are evicted above it (default 1024)
+//! - `CACHE_DISK_TTL_SCALE`: entry TTL as a multiple of the fresh TTL (default 12)
+
+use futures::future::BoxFuture;
+use multi_tier_cache::{Bytes, CacheBackend, CacheError, CacheResult, L2CacheBackend};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+use crate::l1_cache::TierUsage;
+
+/// Entry file magic (format version 1)
+const ENTRY_MAGIC: &[u8; 5] = b"WSRD1";
+/// Entry file extension
+const ENTRY_EXTENSION: &str = "entry";
+/// Gzip member header magic bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// PDF file signature
+const PDF_MAGIC: &[u8; 5] = b"%PDF-";
+/// Run an eviction sweep after this many writes
+const SWEEP_EVERY_WRITES: u64 = 100;
+
+const DEFAULT_MAX_BYTES: u64 = 1024 * 1024 * 1024;
+const DEFAULT_TTL_SCALE: f64 = 12.0;
+
+/// Disk tier settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskCacheConfig {
+    pub dir: PathBuf,
+    pub max_bytes: u64,
+    /// Multiplier applied by the cache manager to every TTL written to this tier
+    pub ttl_scale: f64,
+}
+
+impl DiskCacheConfig {
+    /// Read `CACHE_DISK_*`; `None` when `CACHE_DISK_DIR` is unset or empty
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let dir = std::env::var("CACHE_DISK_DIR").ok()?;
+        Self::parse(
+            &dir,
+            std::env::var("CACHE_DISK_MAX_MB").ok().as_deref(),
+            std::env::var("CACHE_DISK_TTL_SCALE").ok().as_deref(),
+        )
+    }
+
+    fn parse(dir: &str, max_mb: Option<&str>, ttl_scale: Option<&str>) -> Option<Self> {
+        let dir = dir.trim();
+        if dir.is_empty() {
+            return None;
+        }
+        let max_bytes = max_mb
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|mb| *mb > 0)
+            .map_or(DEFAULT_MAX_BYTES, |mb| mb.saturating_mul(1024 * 1024));
+        let ttl_scale = ttl_scale
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|scale| scale.is_finite() && *scale >= 1.0)
+            .unwrap_or(DEFAULT_TTL_SCALE);
+        Some(Self {
+            dir: PathBuf::from(dir),
+            max_bytes,
+            ttl_scale,
+        })
+    }
+}
+
+/// File-per-entry cache backend for the L3 tier
+pub struct DiskCache {
+    dir: Arc<PathBuf>,
+    max_bytes: u64,
+    writes: AtomicU64,
+    counters: Arc<DiskCounters>,
+}
+
+/// Size and removal totals; size is exact after each sweep and grows with writes in
+/// between (an overwritten key counts twice until the next sweep)
+#[derive(Default)]
+struct DiskCounters {
+    entries: AtomicU64,
+    bytes: AtomicU64,
+    evictions: AtomicU64,
+    evicted_bytes: AtomicU64,
+    expirations: AtomicU64,
+}
+
+impl DiskCounters {
+    fn record_sweep(&self, outcome: &SweepOutcome) {
+        self.entries.store(outcome.entries, Ordering::Relaxed);
+        self.bytes.store(outcome.bytes, Ordering::Relaxed);
+        self.evictions.fetch_add(outcome.evicted, Ordering::Relaxed);
+        self.evicted_bytes
+            .fetch_add(outcome.evicted_bytes, Ordering::Relaxed);
+        self.expirations
+            .fetch_add(outcome.expired, Ordering::Relaxed);
+    }
+
+    fn record_write(&self, bytes: u64) {
+        self.entries.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+}
+
+impl DiskCache {
+    /// Open (creating if needed) the cache directory and evict expired entries
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be created
+    pub fn open(config: &DiskCacheConfig) -> io::Result<Self> {
+        std::fs::create_dir_all(&config.dir)?;
+        remove_temp_files(&config.dir);
+        let cache = Self {
+            dir: Arc::new(config.dir.clone()),
+            max_bytes: config.max_bytes,
+            writes: AtomicU64::new(0),
+            counters: Arc::new(DiskCounters::default()),
+        };
+        let outcome = sweep(&cache.dir, cache.max_bytes);
+        cache.counters.record_sweep(&outcome);
+        info!(
+            "💽 L3 disk cache at {} (budget {}MB, {} entries / {}MB, {} entries removed)",
+            cache.dir.display(),
+            cache.max_bytes / (1024 * 1024),
+            outcome.entries,
+            outcome.bytes / (1024 * 1024),
+            outcome.expired + outcome.evicted
+        );
+        Ok(cache)
+    }
+
+    /// Size on disk and removal totals since startup
+    #[must_use]
+    pub fn usage(&self) -> TierUsage {
+        let counters = &self.counters;
+        TierUsage {
+            entries: counters.entries.load(Ordering::Relaxed),
+            bytes: counters.bytes.load(Ordering::Relaxed),
+            max_bytes: self.max_bytes,
+            evictions: counters.evictions.load(Ordering::Relaxed),
+            evicted_bytes: counters.evicted_bytes.load(Ordering::Relaxed),
+            expirations: counters.expirations.load(Ordering::Relaxed),
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        entry_path(&self.dir, key)
+    }
+
+    async fn read(&self, key: &str) -> Option<(Bytes, Duration)> {
+        let path = self.entry_path(key);
+        let data = tokio::fs::read(&path).await.ok()?;
+        match decode_entry(&data, key, now_secs()) {
+            Some(EntryRead::Fresh { value, ttl }) => Some((Bytes::copy_from_slice(value), ttl)),
+            Some(EntryRead::Expired) => {
+                if tokio::fs::remove_file(&path).await.is_ok() {
+                    self.counters.expirations.fetch_add(1, Ordering::Relaxed);
+                }
+                None
+            }
+            None => {
+                warn!("⚠️ L3 disk cache: unreadable entry for {}, removing", key);
+                let _ = tokio::fs::remove_file(&path).await;
+                None
+            }
+        }
+    }
+
+    /// Evict in the background after every `SWEEP_EVERY_WRITES`-th write
+    fn maybe_sweep(&self, write_id: u64) {
+        if !write_id.is_multiple_of(SWEEP_EVERY_WRITES) {
+            return;
+        }
+        let dir = Arc::clone(&self.dir);
+        let max_bytes = self.max_bytes;
+        let counters = Arc::clone(&self.counters);
+        tokio::task::spawn_blocking(move || {
+            let outcome = sweep(&dir, max_bytes);
+            counters.record_sweep(&outcome);
+            if outcome.evicted > 0 {
+                debug!(
+                    "🧹 L3 disk cache: evicted {} entries ({} bytes) over budget",
+                    outcome.evicted, outcome.evicted_bytes
+                );
+            }
+        });
+    }
+}
+
+impl CacheBackend for DiskCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Bytes>> {
+        Box::pin(async move { self.read(key).await.map(|(value, _)| value) })
+    }
+
+    fn set_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+        value: Bytes,
+        ttl: Duration,
+    ) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            if !is_persistable(&value) {
+                return Ok(());
+            }
+
+            let write_id = self.writes.fetch_add(1, Ordering::Relaxed) + 1;
+            let path = self.entry_path(key);
+            // Unique per write so concurrent writers of one key never share a temp file
+            let temp_path = path.with_extension(format!("tmp{write_id}"));
+            let entry = encode_entry(key, &value, now_secs().saturating_add(ttl.as_secs()));
+            let entry_bytes = u64::try_from(entry.len()).unwrap_or(u64::MAX);
+            tokio::fs::write(&temp_path, entry)
+                .await
+                .map_err(|e| CacheError::BackendError(format!("L3 write {key}: {e}")))?;
+            tokio::fs::rename(&temp_path, &path)
+                .await
+                .map_err(|e| CacheError::BackendError(format!("L3 rename {key}: {e}")))?;
+
+            self.counters.record_write(entry_bytes);
+            self.maybe_sweep(write_id);
+            Ok(())
+        })
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(self.entry_path(key)).await {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(CacheError::BackendError(format!("L3 remove {key}: {e}"))),
+            }
+        })
+    }
+
+    fn remove_pattern<'a>(&'a self, pattern: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            let dir = Arc::clone(&self.dir);
+            let owned_pattern = pattern.to_string();
+            let removed =
+                tokio::task::spawn_blocking(move || remove_matching(&dir, &owned_pattern))
+                    .await
+                    .map_err(|e| {
+                        CacheError::InternalError(format!("L3 pattern task failed: {e}"))
+                    })?;
+            debug!(
+                "🧹 L3 disk cache: removed {} entries matching {}",
+                removed, pattern
+            );
+            Ok(())
+        })
+    }
+
+    fn health_check(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async move {
+            tokio::fs::metadata(self.dir.as_path())
+                .await
+                .is_ok_and(|metadata| metadata.is_dir())
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "Disk"
+    }
+}
+
+impl L2CacheBackend for DiskCache {
+    fn get_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Option<(Bytes, Option<Duration>)>> {
+        // The remaining disk TTL is scaled up; promoted copies fall back to the
+        // manager's default TTL instead of inheriting it
+        Box::pin(async move { self.read(key).await.map(|(value, _)| (value, None)) })
+    }
+}
+
+/// Only served artifacts are worth persisting
+fn is_persistable(value: &[u8]) -> bool {
+    value.starts_with(&GZIP_MAGIC) || value.starts_with(PDF_MAGIC)
+}
+
+enum EntryRead<'a> {
+    Fresh { value: &'a [u8], ttl: Duration },
+    Expired,
+}
+
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    let hash = blake3::hash(key.as_bytes());
+    dir.join(format!("{}.{ENTRY_EXTENSION}", hash.to_hex()))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Layout: magic | `expires_at` (u64 BE, unix secs) | key length (u32 BE) | key | value
+fn encode_entry(key: &str, value: &[u8], expires_at: u64) -> Vec<u8> {
+    let key_len = u32::try_from(key.len()).unwrap_or(u32::MAX);
+    let mut entry = Vec::with_capacity(ENTRY_MAGIC.len() + 12 + key.len() + value.len());
+    entry.extend_from_slice(ENTRY_MAGIC);
+    entry.extend_from_slice(&expires_at.to_be_bytes());
+    entry.extend_from_slice(&key_len.to_be_bytes());
+    entry.extend_from_slice(key.as_bytes());
+    entry.extend_from_slice(value);
+    entry
+}
+
+/// Header of an entry file: (`expires_at`, key, value)
+fn parse_entry(data: &[u8]) -> Option<(u64, &[u8], &[u8])> {
+    let rest = data.strip_prefix(ENTRY_MAGIC)?;
+    let (expires_at, rest) = rest.split_first_chunk::<8>()?;
+    let (key_len, rest) = rest.split_first_chunk::<4>()?;
+    let key_len = usize::try_from(u32::from_be_bytes(*key_len)).ok()?;
+    let key = rest.get(..key_len)?;
+    let value = rest.get(key_len..)?;
+    Some((u64::from_be_bytes(*expires_at), key, value))
+}
+
+/// `None` for corrupt entries or hash collisions (stored key differs)
+fn decode_entry<'a>(data: &'a [u8], key: &str, now: u64) -> Option<EntryRead<'a>> {
+    let (expires_at, stored_key, value) = parse_entry(data)?;
+    if stored_key != key.as_bytes() {
+        return None;
+    }
+    if expires_at <= now {
+        return Some(EntryRead::Expired);
+    }
+    Some(EntryRead::Fresh {
+        value,
+        ttl: Duration::from_secs(expires_at - now),
+    })
+}
+
+/// Read just enough of an entry file to get its expiry and key
+fn read_header(path: &Path) -> Option<(u64, String)> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut fixed = [0_u8; ENTRY_MAGIC.len() + 12];
+    file.read_exact(&mut fixed).ok()?;
+    let (_, key_len_bytes) = fixed.split_last_chunk::<4>()?;
+    let key_len = usize::try_from(u32::from_be_bytes(*key_len_bytes)).ok()?;
+    let mut key = vec![0_u8; key_len];
+    file.read_exact(&mut key).ok()?;
+
+    let mut header = fixed.to_vec();
+    header.extend_from_slice(&key);
+    let (expires_at, key, _) = parse_entry(&header)?;
+    Some((expires_at, String::from_utf8_lossy(key).into_owned()))
+}
+
+fn entry_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == ENTRY_EXTENSION))
+        .collect()
+}
+
+/// Entries removed by one sweep and what is left on disk
+#[derive(Debug, Default, PartialEq, Eq)]
+struct SweepOutcome {
+    /// Expired or unreadable entries
+    expired: u64,
+    /// Live entries removed to get under the budget
+    evicted: u64,
+    evicted_bytes: u64,
+    entries: u64,
+    bytes: u64,
+}
+
+/// Remove expired and unreadable entries, then the oldest ones until under `max_bytes`
+fn sweep(dir: &Path, max_bytes: u64) -> SweepOutcome {
+    let now = now_secs();
+    let mut outcome = SweepOutcome::default();
+    let mut live = Vec::new();
+
+    for path in entry_files(dir) {
+        let expired = read_header(&path).is_none_or(|(expires_at, _)| expires_at <= now);
+        if expired {
+            outcome.expired += u64::from(std::fs::remove_file(&path).is_ok());
+            continue;
+        }
+        if let Ok(metadata) = std::fs::metadata(&path) {
+            let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+            live.push((modified, metadata.len(), path));
+        }
+    }
+
+    let live_count = u64::try_from(live.len()).unwrap_or(u64::MAX);
+    let mut total: u64 = live.iter().map(|(_, size, _)| size).sum();
+    if total > max_bytes {
+        live.sort_by_key(|(modified, _, _)| *modified);
+        for (_, size, path) in live {
+            if total <= max_bytes {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(size);
+                outcome.evicted += 1;
+                outcome.evicted_bytes += size;
+            }
+        }
+    }
+    outcome.entries = live_count.saturating_sub(outcome.evicted);
+    outcome.bytes = total;
+    outcome
+}
+
+/// Remove temp files left behind by writes interrupted by a crash
+fn remove_temp_files(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        let is_temp = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.starts_with("tmp"));
+        if is_temp {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+/// Remove entries whose key matches a Redis-style glob (`*`, `?`)
+fn remove_matching(dir: &Path, pattern: &str) -> usize {
+    entry_files(dir)
+        .into_iter()
+        .filter(|path| read_header(path).is_some_and(|(_, key)| glob_matches(pattern, &key)))
+        .filter(|path| std::fs::remove_file(path).is_ok())
+        .count()
+}
+
+/// Redis-style glob match of `key` against `pattern`
+pub(crate) fn glob_matches(pattern: &str, key: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let key: Vec<char> = key.chars().collect();
+    let (mut p, mut k) = (0, 0);
+    // Position of the last `*` and the key index it was matched against
+    let mut star: Option<(usize, usize)> = None;
+
+    while k < key.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, k));
+                p += 1;
+            }
+            Some(c) if *c == '?' || Some(c) == key.get(k) => {
+                p += 1;
+                k += 1;
+            }
+            _ => match star {
+                Some((star_p, star_k)) => {
+                    p = star_p + 1;
+                    k = star_k + 1;
+                    star = Some((star_p, star_k + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern
+        .get(p..)
+        .is_some_and(|rest| rest.iter().all(|c| *c == '*'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str) -> io::Result<DiskCache> {
+        let dir =
+            std::env::temp_dir().join(format!("wsr-disk-cache-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        DiskCache::open(&DiskCacheConfig {
+            dir,
+            max_bytes: DEFAULT_MAX_BYTES,
+            ttl_scale: DEFAULT_TTL_SCALE,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_persists_only_served_artifacts() -> Result<(), Box<dyn std::error::Error>> {
+        let cache = temp_cache("artifacts")?;
+        let page = Bytes::from_static(&[0x1f, 0x8b, 8, 0, 1, 2, 3]);
+        let ttl = Duration::from_mins(5);
+
+        cache
+            .set_with_ttl("compressed_report_dsd_42_vi", page.clone(), ttl)
+            .await?;
+        cache
+            .set_with_ttl("latest_market_data", Bytes::from_static(b"{}"), ttl)
+            .await?;
+
+        assert_eq!(cache.get("compressed_report_dsd_42_vi").await, Some(page));
+        assert_eq!(cache.get("latest_market_data").await, None);
+
+        cache.remove("compressed_report_dsd_42_vi").await?;
+        assert_eq!(cache.get("compressed_report_dsd_42_vi").await, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_pattern_matches_stored_keys() -> Result<(), Box<dyn std::error::Error>> {
+        let cache = temp_cache("pattern")?;
+        let pdf = Bytes::from_static(b"%PDF-1.7 ...");
+        let ttl = Duration::from_mins(5);
+        for key in [
+            "compressed_report_dsd_7_vi",
+            "compressed_report_dsd_7_en",
+            "dashboard_homepage_vi_compressed",
+        ] {
+            cache.set_with_ttl(key, pdf.clone(), ttl).await?;
+        }
+
+        cache.remove_pattern("compressed_report_dsd_7_*").await?;
+        assert_eq!(cache.get("compressed_report_dsd_7_en").await, None);
+        assert_eq!(
+            cache.get("dashboard_homepage_vi_compressed").await,
+            Some(pdf)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_budget_sweep_counts_evictions() -> Result<(), Box<dyn std::error::Error>> {
+        let cache = temp_cache("budget")?;
+        let page: Bytes = GZIP_MAGIC
+            .into_iter()
+            .chain(std::iter::repeat_n(0, 4094))
+            .collect();
+        for id in 0..4 {
+            cache
+                .set_with_ttl(&format!("page_{id}"), page.clone(), Duration::from_mins(5))
+                .await?;
+        }
+        assert_eq!(cache.usage().entries, 4);
+
+        // Reopen with room for two entries: the two oldest are evicted
+        let reopened = DiskCache::open(&DiskCacheConfig {
+            dir: cache.dir.as_ref().clone(),
+            max_bytes: 2 * 4200,
+            ttl_scale: DEFAULT_TTL_SCALE,
+        })?;
+        let usage = reopened.usage();
+        assert_eq!(usage.entries, 2);
+        assert_eq!(usage.evictions, 2);
+        assert!(usage.bytes <= usage.max_bytes);
+        assert!(usage.evicted_bytes > 2 * 4096);
+        Ok(())
+    }
+
+    #[test]
+    fn test_entry_expiry_and_key_check() {
+        let entry = encode_entry("a", b"value", 100);
+        assert!(matches!(
+            decode_entry(&entry, "a", 40),
+            Some(EntryRead::Fresh { value: b"value", ttl }) if ttl == Duration::from_mins(1)
+        ));
+        assert!(matches!(
+            decode_entry(&entry, "a", 100),
+            Some(EntryRead::Expired)
+        ));
+        assert!(decode_entry(&entry, "b", 40).is_none());
+        assert!(decode_entry(b"garbage", "a", 40).is_none());
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("*", "anything"));
+        assert!(glob_matches(
+            "author_page_*_compressed",
+            "author_page_jane_vi_compressed"
+        ));
+        assert!(glob_matches("report_?", "report_7"));
+        assert!(!glob_matches("report_?", "report_42"));
+        assert!(!glob_matches("rss_feed_*", "sitemap_xml_compressed"));
+    }
+
+    #[test]
+    fn test_config_parse() {
+        assert_eq!(DiskCacheConfig::parse(" ", None, None), None);
+        let config = DiskCacheConfig::parse("/var/cache/wsr", Some("64"), Some("0.5"));
+        assert_eq!(
+            config,
+            Some(DiskCacheConfig {
+                dir: PathBuf::from("/var/cache/wsr"),
+                max_bytes: 64 * 1024 * 1024,
+                ttl_scale: DEFAULT_TTL_SCALE,
+            })
+        );
+    }
+}
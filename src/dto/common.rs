@@ -7,6 +7,8 @@ use serde::Serialize;
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     Healthy,
+    /// Working, but with an elevated error rate
+    Degraded,
     Unhealthy,
 }
 
@@ -29,6 +31,10 @@ mod tests {
             serde_json::to_string(&HealthStatus::Healthy)?,
             "\"healthy\""
         );
+        assert_eq!(
+            serde_json::to_string(&HealthStatus::Degraded)?,
+            "\"degraded\""
+        );
         assert_eq!(
             serde_json::to_string(&HealthStatus::Unhealthy)?,
             "\"unhealthy\""
@@ -0,0 +1,7 @@
+//! Mobile API DTOs
+//!
+//! Versioned contract for the companion app, kept separate from the web DTOs so the
+//! web responses can evolve freely. A version module is frozen once released; breaking
+//! changes go into a new module (`v2`) behind a new route prefix.
+
+pub mod v1;
@@ -0,0 +1,183 @@
+//! Mobile API v1 DTOs (`/api/mobile/v1/*`)
+//!
+//! Field names and shapes are a public contract with released app builds; the tests
+//! below pin the exact JSON and must only change together with a new API version.
+
+use serde::{Deserialize, Serialize};
+
+/// Feed sections that can be skipped in a delta response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MobileFeedSection {
+    LatestReport,
+    Market,
+    TopMovers,
+}
+
+/// Response for GET /api/mobile/v1/feed
+///
+/// Sections listed in `unchanged` are omitted: the app keeps its previous copy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MobileFeedResponse {
+    pub version: u8,
+    /// Opaque token to send back as `?since=` for a delta response
+    pub cursor: String,
+    pub generated_at: String,
+    pub cache: MobileCacheHints,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unchanged: Vec<MobileFeedSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_report: Option<MobileReportSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market: Option<MobileMarketSnapshot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_movers: Option<Vec<MobileMover>>,
+}
+
+/// How long the app may reuse the feed before refreshing (seconds)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MobileCacheHints {
+    pub max_age: u32,
+    pub stale_while_revalidate: u32,
+}
+
+/// Latest report card on the home screen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MobileReportSummary {
+    pub id: i32,
+    pub title: String,
+    /// Plain-text excerpt of the report body
+    pub summary: String,
+    pub url: String,
+    pub published_at: String,
+}
+
+/// Global market figures
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MobileMarketSnapshot {
+    pub market_cap_usd: f64,
+    pub market_cap_change_24h: f64,
+    pub volume_24h_usd: f64,
+    pub btc_dominance: f64,
+    pub fear_greed: i32,
+    pub updated_at: String,
+    /// Some upstream sources failed; figures may be partly outdated
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+}
+
+/// One coin in the top movers list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MobileMover {
+    pub symbol: String,
+    pub price_usd: f64,
+    pub change_24h: f64,
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn full_feed() -> MobileFeedResponse {
+        MobileFeedResponse {
+            version: 1,
+            cursor: "1.aaaaaaaaaaaa.bbbbbbbbbbbb.cccccccccccc".to_string(),
+            generated_at: "2026-10-14T08:00:00Z".to_string(),
+            cache: MobileCacheHints {
+                max_age: 30,
+                stale_while_revalidate: 120,
+            },
+            unchanged: vec![],
+            latest_report: Some(MobileReportSummary {
+                id: 42,
+                title: "Crypto Market Analysis Report #42 - 2026-10-14".to_string(),
+                summary: "Bitcoin holds above support...".to_string(),
+                url: "https://cryptodashboard.me/crypto_report/42".to_string(),
+                published_at: "2026-10-14T07:00:00Z".to_string(),
+            }),
+            market: Some(MobileMarketSnapshot {
+                market_cap_usd: 3.3e12,
+                market_cap_change_24h: -1.25,
+                volume_24h_usd: 2.6e11,
+                btc_dominance: 57.0,
+                fear_greed: 40,
+                updated_at: "2026-10-14T07:59:50Z".to_string(),
+                partial: false,
+            }),
+            top_movers: Some(vec![MobileMover {
+                symbol: "SOL".to_string(),
+                price_usd: 142.5,
+                change_24h: 6.5,
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_full_feed_contract() {
+        let value = serde_json::to_value(full_feed()).expect("Failed to serialize");
+        assert_eq!(
+            value,
+            json!({
+                "version": 1,
+                "cursor": "1.aaaaaaaaaaaa.bbbbbbbbbbbb.cccccccccccc",
+                "generated_at": "2026-10-14T08:00:00Z",
+                "cache": { "max_age": 30, "stale_while_revalidate": 120 },
+                "latest_report": {
+                    "id": 42,
+                    "title": "Crypto Market Analysis Report #42 - 2026-10-14",
+                    "summary": "Bitcoin holds above support...",
+                    "url": "https://cryptodashboard.me/crypto_report/42",
+                    "published_at": "2026-10-14T07:00:00Z"
+                },
+                "market": {
+                    "market_cap_usd": 3.3e12,
+                    "market_cap_change_24h": -1.25,
+                    "volume_24h_usd": 2.6e11,
+                    "btc_dominance": 57.0,
+                    "fear_greed": 40,
+                    "updated_at": "2026-10-14T07:59:50Z"
+                },
+                "top_movers": [
+                    { "symbol": "SOL", "price_usd": 142.5, "change_24h": 6.5 }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_delta_feed_contract() {
+        let delta = MobileFeedResponse {
+            unchanged: vec![
+                MobileFeedSection::LatestReport,
+                MobileFeedSection::TopMovers,
+            ],
+            latest_report: None,
+            top_movers: None,
+            ..full_feed()
+        };
+        let value = serde_json::to_value(&delta).expect("Failed to serialize");
+
+        assert_eq!(
+            value.get("unchanged"),
+            Some(&json!(["latest_report", "top_movers"]))
+        );
+        assert!(value.get("latest_report").is_none());
+        assert!(value.get("top_movers").is_none());
+        assert!(value.get("market").is_some());
+
+        // Older app builds parse the delta with the same types
+        let parsed: MobileFeedResponse =
+            serde_json::from_value(value).expect("Failed to deserialize");
+        assert_eq!(parsed, delta);
+    }
+
+    #[test]
+    fn test_partial_market_flag_only_when_set() {
+        let mut market = full_feed().market.expect("market");
+        market.partial = true;
+        let value = serde_json::to_value(&market).expect("Failed to serialize");
+        assert_eq!(value.get("partial"), Some(&json!(true)));
+    }
+}
@@ -1,10 +1,13 @@
-//! Data Transfer Objects (DTOs) for API responses
+//! Data Transfer Objects (DTOs) for API requests and responses
 //!
-//! This module provides type-safe response structures for all API endpoints,
+//! This module provides type-safe request and response structures for all API endpoints,
 //! replacing ad-hoc `serde_json::Value` usage with proper Rust structs.
 
 pub mod common;
+pub mod mobile;
+pub mod requests;
 pub mod responses;
+pub mod streams;
 
 // Re-export common types for convenience
 pub use common::*;
@@ -0,0 +1,14 @@
+//! Cache-related request DTOs
+
+use serde::{Deserialize, Serialize};
+
+/// Body for POST /admin/cache/purge - exactly one field must be set
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CachePurgeRequest {
+    /// Exact cache key, e.g. `0.1.0:dashboard_homepage_vi_compressed`
+    pub key: Option<String>,
+    /// Key prefix, e.g. `crypto_reports_list_page_`
+    pub prefix: Option<String>,
+    /// Report id - purges its rendered pages in every language
+    pub report_id: Option<i32>,
+}
@@ -0,0 +1,14 @@
+//! Request DTOs for API endpoints
+
+pub mod cache;
+pub mod redirects;
+pub mod reports;
+pub mod streams;
+pub mod subscriptions;
+
+// Re-export all request types for convenience
+pub use cache::*;
+pub use redirects::*;
+pub use reports::*;
+pub use streams::*;
+pub use subscriptions::*;
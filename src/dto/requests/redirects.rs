@@ -0,0 +1,14 @@
+//! Redirect request DTOs
+
+use serde::{Deserialize, Serialize};
+
+/// Body for POST /admin/redirects - creates the redirect or replaces its target
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RedirectUpsertRequest {
+    /// Path to redirect, e.g. `/report/42`, or a prefix such as `/report/*`
+    pub source: String,
+    /// Path or http(s) URL, ending in `/*` to append the rest of a prefix source
+    pub target: String,
+    /// 301 (default) or 302
+    pub status: Option<u16>,
+}
@@ -0,0 +1,238 @@
+//! Crypto report query DTOs
+//!
+//! Query strings are deserialized as raw strings and then validated, so a bad value
+//! is reported against its field instead of failing the whole query (or being
+//! replaced by a default). Unknown parameters (`utm_*`, `fbclid`, ...) are ignored.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::services::data_communication::SUPPORTED_LANGUAGES;
+
+/// Highest page number accepted by the reports list
+pub const MAX_PAGE: i64 = 10_000;
+
+/// One invalid query parameter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+/// A query type built from raw (string) parameters
+pub trait ValidateQuery: Sized {
+    /// Raw parameters as deserialized from the query string
+    type Params: DeserializeOwned;
+
+    /// Check every field of `params`
+    ///
+    /// # Errors
+    /// Returns one `FieldError` per invalid field
+    fn validate(params: Self::Params) -> Result<Self, Vec<FieldError>>;
+}
+
+/// Raw `GET /crypto_reports_list` parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct ReportsListParams {
+    pub page: Option<String>,
+    pub lang: Option<String>,
+}
+
+/// Validated `GET /crypto_reports_list` query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportsListQuery {
+    /// 1-based page number (default 1)
+    pub page: i64,
+    /// Explicit language; `None` falls back to cookie / `Accept-Language`
+    pub lang: Option<String>,
+}
+
+impl ValidateQuery for ReportsListQuery {
+    type Params = ReportsListParams;
+
+    fn validate(params: ReportsListParams) -> Result<Self, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let page = params.page.as_deref().map_or(Some(1), |value| {
+            let page = value
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|page| (1..=MAX_PAGE).contains(page));
+            if page.is_none() {
+                errors.push(FieldError::new(
+                    "page",
+                    format!("must be an integer between 1 and {MAX_PAGE}, got {value:?}"),
+                ));
+            }
+            page
+        });
+        let lang = validate_lang(params.lang.as_deref(), &mut errors);
+        match page {
+            Some(page) if errors.is_empty() => Ok(Self { page, lang }),
+            _ => Err(errors),
+        }
+    }
+}
+
+impl ReportsListQuery {
+    /// Parameters in the form the language detection expects
+    #[must_use]
+    pub fn params(&self) -> HashMap<String, String> {
+        self.lang
+            .iter()
+            .map(|lang| ("lang".to_string(), lang.clone()))
+            .collect()
+    }
+}
+
+/// Raw parameters of the report pages (`/crypto_report`, `/crypto_report/{id}`)
+#[derive(Debug, Default, Deserialize)]
+pub struct ReportPageParams {
+    pub id: Option<String>,
+    pub lang: Option<String>,
+    pub render: Option<String>,
+}
+
+/// Validated report page query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportPageQuery {
+    /// Report to show on `/crypto_report`; `None` is the latest report
+    pub id: Option<i32>,
+    /// Explicit language; `None` falls back to cookie / `Accept-Language`
+    pub lang: Option<String>,
+    /// Render strategy override (honored only when overrides are enabled)
+    pub render: Option<String>,
+}
+
+impl ValidateQuery for ReportPageQuery {
+    type Params = ReportPageParams;
+
+    fn validate(params: ReportPageParams) -> Result<Self, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let id = params.id.as_deref().and_then(|value| {
+            let id = value.trim().parse::<i32>().ok().filter(|id| *id > 0);
+            if id.is_none() {
+                errors.push(FieldError::new(
+                    "id",
+                    format!("must be a positive integer, got {value:?}"),
+                ));
+            }
+            id
+        });
+        let lang = validate_lang(params.lang.as_deref(), &mut errors);
+        if errors.is_empty() {
+            Ok(Self {
+                id,
+                lang,
+                render: params.render.filter(|render| !render.is_empty()),
+            })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl ReportPageQuery {
+    /// Parameters in the form the language detection and render strategy selection expect
+    #[must_use]
+    pub fn params(&self) -> HashMap<String, String> {
+        self.lang
+            .iter()
+            .map(|lang| ("lang".to_string(), lang.clone()))
+            .chain(
+                self.render
+                    .iter()
+                    .map(|render| ("render".to_string(), render.clone())),
+            )
+            .collect()
+    }
+}
+
+/// `lang` must be a supported language (case-insensitive); an empty value means unset
+fn validate_lang(value: Option<&str>, errors: &mut Vec<FieldError>) -> Option<String> {
+    let value = value.map(str::trim).filter(|value| !value.is_empty())?;
+    let lang = value.to_ascii_lowercase();
+    if SUPPORTED_LANGUAGES.contains(&lang.as_str()) {
+        Some(lang)
+    } else {
+        errors.push(FieldError::new(
+            "lang",
+            format!(
+                "must be one of {}, got {value:?}",
+                SUPPORTED_LANGUAGES.join(", ")
+            ),
+        ));
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(page: Option<&str>, lang: Option<&str>) -> Result<ReportsListQuery, Vec<FieldError>> {
+        ReportsListQuery::validate(ReportsListParams {
+            page: page.map(ToString::to_string),
+            lang: lang.map(ToString::to_string),
+        })
+    }
+
+    #[test]
+    fn test_reports_list_query() {
+        assert_eq!(
+            list(None, None),
+            Ok(ReportsListQuery {
+                page: 1,
+                lang: None
+            })
+        );
+        assert_eq!(
+            list(Some("3"), Some("EN")),
+            Ok(ReportsListQuery {
+                page: 3,
+                lang: Some("en".to_string())
+            })
+        );
+
+        // Every invalid field is reported
+        let errors = list(Some("0"), Some("fr")).err().unwrap_or_default();
+        let fields: Vec<_> = errors.iter().map(|e| e.field).collect();
+        assert_eq!(fields, ["page", "lang"]);
+        assert!(list(Some("abc"), None).is_err());
+        assert!(list(Some("99999999999"), None).is_err());
+    }
+
+    #[test]
+    fn test_report_page_query() {
+        let query = ReportPageQuery::validate(ReportPageParams {
+            id: Some("42".to_string()),
+            lang: Some(String::new()),
+            render: Some("iframe".to_string()),
+        });
+        assert_eq!(
+            query.as_ref().map(|q| (q.id, q.lang.clone())),
+            Ok((Some(42), None))
+        );
+        let params = query.map(|q| q.params()).unwrap_or_default();
+        assert_eq!(params.get("render").map(String::as_str), Some("iframe"));
+        assert!(!params.contains_key("lang"));
+
+        let errors = ReportPageQuery::validate(ReportPageParams {
+            id: Some("-1".to_string()),
+            ..ReportPageParams::default()
+        })
+        .err()
+        .unwrap_or_default();
+        assert_eq!(errors.first().map(|e| e.field), Some("id"));
+    }
+}
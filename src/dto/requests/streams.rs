@@ -0,0 +1,12 @@
+//! Stream-related request DTOs
+
+use serde::{Deserialize, Serialize};
+
+/// Body for POST /admin/streams/replay
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct StreamReplayRequest {
+    /// Registry name of the stream (default `market_data`)
+    pub stream: Option<String>,
+    /// Number of newest entries to reprocess (default 100, at most 1000)
+    pub count: Option<usize>,
+}
@@ -0,0 +1,13 @@
+//! Email subscription request DTOs
+
+use serde::{Deserialize, Serialize};
+
+/// Body for POST /api/subscribe - subscribes an address to the email digest
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SubscribeRequest {
+    pub email: String,
+    /// `daily` (default) or `weekly`
+    pub frequency: Option<String>,
+    /// `vi` (default) or `en`
+    pub language: Option<String>,
+}
@@ -0,0 +1,32 @@
+//! Market asset DTOs
+
+use serde::{Deserialize, Serialize};
+
+/// Latest quote of one asset
+/// Used by:
+/// - GET /api/crypto/assets (listing)
+/// - GET /api/crypto/assets/{symbol}
+/// - `assets` of the dashboard summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetQuoteResponse {
+    /// Uppercase ticker, e.g. `BTC`, `XAU`, `DXY`
+    pub symbol: String,
+    pub name: String,
+    /// `crypto`, `commodity` or `index`
+    pub kind: String,
+    /// USD, or index points for `index` assets
+    pub price: f64,
+    /// Percent change over 24h, when published
+    pub change_24h: Option<f64>,
+    /// Currency of `price` when converted with `?currency=` (never for `index` assets)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+/// Response for GET /api/crypto/assets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetsResponse {
+    /// Assets quoted by the latest market data, in dashboard order
+    pub assets: Vec<AssetQuoteResponse>,
+    pub last_updated: Option<String>,
+}
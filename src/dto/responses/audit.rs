@@ -0,0 +1,54 @@
+//! Admin and report audit log DTOs
+
+use serde::Serialize;
+
+/// Response for GET /admin/audit endpoint
+#[derive(Debug, Serialize)]
+pub struct AuditLogResponse {
+    pub limit: i64,
+    pub offset: i64,
+    /// Newest first
+    pub entries: Vec<AuditLogEntryResponse>,
+}
+
+/// One recorded admin action
+#[derive(Debug, Serialize)]
+pub struct AuditLogEntryResponse {
+    pub id: i64,
+    /// e.g. `cache_clear`, `cache_purge`, `stream_replay`
+    pub action: String,
+    pub actor: String,
+    pub parameters: serde_json::Value,
+    /// `ok` or `error: <message>`
+    pub outcome: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub created_at: String,
+}
+
+/// Response for GET /api/crypto/reports/{id}/audit
+#[derive(Debug, Serialize)]
+pub struct ReportAuditResponse {
+    pub report_id: i32,
+    pub limit: i64,
+    pub offset: i64,
+    /// Newest first
+    pub entries: Vec<ReportAuditEntryResponse>,
+}
+
+/// One recorded report mutation
+#[derive(Debug, Serialize)]
+pub struct ReportAuditEntryResponse {
+    pub id: i64,
+    /// `create`, `update` or `delete`
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<i64>,
+    pub actor: String,
+    pub changed_fields: Vec<String>,
+    /// Hex blake3 hash of the new values of `changed_fields`
+    pub changes_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub created_at: String,
+}
@@ -10,20 +10,13 @@ pub struct CacheClearResponse {
     pub status: CacheOperationStatus,
 }
 
-/// Response for GET /metrics endpoint
+/// Response for POST /admin/cache/purge endpoint
 #[derive(Debug, Serialize)]
-pub struct PerformanceMetricsResponse {
-    pub performance: PerformanceInfo,
-    pub cache_info: String,
-}
-
-/// Performance information for metrics
-#[derive(Debug, Serialize)]
-pub struct PerformanceInfo {
-    pub services_active: u8,
-    pub uptime: String,
-    pub memory_usage: String,
-    pub cache_status: String,
+pub struct CachePurgeResponse {
+    pub message: String,
+    pub status: CacheOperationStatus,
+    /// Keys and patterns that were invalidated
+    pub purged: Vec<String>,
 }
 
 /// Response for GET /admin/cache/stats endpoint
@@ -40,6 +33,8 @@ pub enum CacheStatsResponse {
 pub struct CacheStatsAvailable {
     pub cache: CacheSystemInfo,
     pub statistics: CacheStatistics,
+    pub tiers: Vec<CacheTierStatistics>,
+    pub top_keys: Vec<CacheKeyUsage>,
     pub configuration: CacheConfiguration,
     pub health: CacheHealth,
 }
@@ -73,10 +68,50 @@ pub struct CacheStatistics {
     pub in_flight_requests: usize,
 }
 
+/// Hit/miss breakdown and size estimates for one cache tier
+///
+/// L1 and disk tier sizes are measured; L2 entry counts and memory are estimated
+/// from tracked page cache writes.
+#[derive(Debug, Serialize)]
+pub struct CacheTierStatistics {
+    pub tier: String,
+    pub backend: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_rate: String,
+    pub estimated_entries: usize,
+    pub estimated_memory_bytes: u64,
+    /// Measured size and evictions (tiers that track them: L1 and the disk tier)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<CacheTierUsage>,
+}
+
+/// Measured size of a tier against its byte budget
+#[derive(Debug, Serialize)]
+pub struct CacheTierUsage {
+    pub entries: u64,
+    pub bytes: u64,
+    pub max_bytes: u64,
+    pub budget_used: String,
+    /// Entries evicted to stay within `max_bytes`
+    pub evictions: u64,
+    pub evicted_bytes: u64,
+    pub expirations: u64,
+}
+
+/// Usage of one cache key
+#[derive(Debug, Serialize)]
+pub struct CacheKeyUsage {
+    pub key: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub size_bytes: usize,
+}
+
 /// Cache configuration details
 #[derive(Debug, Serialize)]
 pub struct CacheConfiguration {
-    pub l1_max_capacity: usize,
+    pub l1_max_bytes: u64,
     pub l1_ttl: String,
     pub l2_ttl: String,
     pub eviction: String,
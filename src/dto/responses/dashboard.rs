@@ -1,9 +1,12 @@
 //! Dashboard data response DTOs
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
-/// Response for dashboard summary endpoints
+use super::AssetQuoteResponse;
+
+/// Response for dashboard summary endpoints (fields of the `market_data` stream)
 /// Used by:
 /// - GET /api/dashboard/data
 /// - GET /api/crypto/dashboard-summary
@@ -60,12 +63,44 @@ pub struct DashboardDataResponse {
     /// Optional note field (only present in fallback scenarios)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
+
+    /// Quotes of every asset in the entry (see `MARKET_ASSETS`), including the
+    /// ones without a field above (gold, DXY)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<AssetQuoteResponse>,
+
+    /// Set when the market data is older than the stream's `stale_after` (or there
+    /// is none), so the numbers should be shown as outdated
+    #[serde(default)]
+    pub stale: bool,
+
+    /// Seconds since the newest market data entry was published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_age_secs: Option<u64>,
+
+    /// When each field last arrived (RFC 3339); a field missing from recent
+    /// entries keeps the time of the last entry that carried it
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fields_last_updated: HashMap<String, String>,
+
+    /// Currency of the money fields (`*_usd`, asset prices) when converted with
+    /// `?currency=`; the field names keep their `_usd` suffix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+
+    /// Units of `currency` per USD used for the conversion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fx_rate: Option<f64>,
+
+    /// Stream fields outside this schema, passed through as published
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// US Stock Index data structure
 /// Symbol is the `HashMap` key in `DashboardDataResponse.us_stock_indices`
 /// Display name mapping should be handled by the frontend
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StockIndexData {
     pub price: f64,
     pub change: f64,
@@ -120,6 +155,16 @@ mod tests {
             last_updated: "2024-03-20T10:00:00Z".to_string(),
             timestamp: "2024-03-20T10:00:00Z".to_string(),
             note: None,
+            assets: Vec::new(),
+            stale: false,
+            data_age_secs: Some(4),
+            fields_last_updated: HashMap::from([(
+                "btc_price_usd".to_string(),
+                "2024-03-20T10:00:00Z".to_string(),
+            )]),
+            currency: None,
+            fx_rate: None,
+            extra: Map::new(),
         };
 
         let serialized = serde_json::to_string(&response).expect("Failed to serialize");
@@ -135,6 +180,10 @@ mod tests {
             .price;
         assert!((spx_price - 5000.0).abs() < f64::EPSILON);
         assert!(deserialized.note.is_none());
+        assert_eq!(deserialized.data_age_secs, Some(4));
+        assert_eq!(deserialized.fields_last_updated.len(), 1);
+        // Freshness is never mistaken for a passed-through stream field
+        assert!(deserialized.extra.is_empty());
     }
 
     #[test]
@@ -166,11 +215,18 @@ mod tests {
             "partial_failure": true,
             "last_updated": "2024-03-20T10:00:00Z",
             "timestamp": "2024-03-20T10:00:00Z",
-            "note": "Fallback active"
+            "note": "Fallback active",
+            "gold_price_usd": 2400.0
         });
 
         let deserialized: DashboardDataResponse =
             serde_json::from_value(json_data).expect("Failed to deserialize");
         assert_eq!(deserialized.note, Some("Fallback active".to_string()));
+        assert!(deserialized.assets.is_empty());
+        assert!(!deserialized.stale);
+        assert_eq!(
+            deserialized.extra.get("gold_price_usd"),
+            Some(&json!(2400.0))
+        );
     }
 }
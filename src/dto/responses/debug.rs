@@ -0,0 +1,71 @@
+//! Runtime profiling DTOs (`profiling` feature)
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::health::MemoryStats;
+
+/// Response for GET /admin/debug/tasks endpoint
+#[derive(Debug, Serialize)]
+pub struct RuntimeDumpResponse {
+    pub workers: usize,
+    /// Tasks spawned on the runtime and not yet finished
+    pub alive_tasks: usize,
+    /// Tasks waiting in the runtime's shared queue
+    pub global_queue_depth: usize,
+    pub worker_stats: Vec<WorkerStats>,
+    /// Named background tasks, sorted by name
+    pub tasks: Vec<TaskDumpEntry>,
+}
+
+/// Counters of one runtime worker since startup
+#[derive(Debug, Serialize)]
+pub struct WorkerStats {
+    pub worker: usize,
+    pub busy_secs: f64,
+    pub park_count: u64,
+}
+
+/// Status of one named background task
+#[derive(Debug, Serialize)]
+pub struct TaskDumpEntry {
+    pub name: String,
+    /// `running`, `completed`, `failed`, `panicked` or `restarting`
+    pub state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub restarts: u32,
+}
+
+/// Response for GET /admin/debug/profile endpoint
+#[derive(Debug, Serialize)]
+pub struct CpuProfileResponse {
+    pub duration_ms: u64,
+    /// Average share (0-1) of the profile the workers spent busy
+    pub mean_utilization: f64,
+    /// Deepest shared queue seen while sampling
+    pub max_global_queue_depth: usize,
+    pub workers: Vec<WorkerProfile>,
+}
+
+/// Activity of one runtime worker during the profile
+#[derive(Debug, Serialize)]
+pub struct WorkerProfile {
+    pub worker: usize,
+    /// Share (0-1) of the profile spent polling tasks
+    pub utilization: f64,
+    /// Times the worker ran out of work and parked
+    pub parks: u64,
+}
+
+/// Response for GET /admin/debug/heap endpoint
+#[derive(Debug, Serialize)]
+pub struct HeapSnapshotResponse {
+    pub memory: MemoryStats,
+    /// Size figures of `/proc/self/status` (e.g. `RssAnon`, `VmData`) in bytes; empty
+    /// where `/proc` is unavailable
+    pub proc_status_bytes: BTreeMap<String, u64>,
+    pub l1_entries: u64,
+}
@@ -0,0 +1,51 @@
+//! Diagnostics response DTOs
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// How urgently a check needs attention (ordered from least to most urgent)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Ok,
+    Warning,
+    Critical,
+}
+
+/// Response for GET /admin/diagnostics endpoint
+#[derive(Debug, Serialize)]
+pub struct DiagnosticsResponse {
+    /// Most severe check result
+    pub status: DiagnosticSeverity,
+    pub generated_at: String,
+    pub duration_ms: u64,
+    /// Check results, most severe first
+    pub checks: Vec<DiagnosticCheck>,
+    pub recent_errors: Vec<RecentErrorEntry>,
+    /// Server errors per kind since startup
+    pub error_totals: BTreeMap<String, u64>,
+}
+
+/// Result of one diagnostic check
+#[derive(Debug, Serialize)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub severity: DiagnosticSeverity,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Suggested next step for on-call when the check is not ok
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+}
+
+/// A recent server-side error
+#[derive(Debug, Serialize)]
+pub struct RecentErrorEntry {
+    pub at: String,
+    pub kind: String,
+    pub status: u16,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
@@ -8,6 +8,132 @@ use serde::Serialize;
 pub struct HealthCheckResponse {
     pub status: HealthStatus,
     pub services: ServicesInfo,
+    pub streams: Vec<StreamFreshnessInfo>,
+}
+
+/// Freshness of one registered stream, as consumed by this instance
+#[derive(Debug, Serialize)]
+pub struct StreamFreshnessInfo {
+    pub name: String,
+    pub stream_key: String,
+    /// `fresh`, `stale` or `no_data`
+    pub status: &'static str,
+    /// Seconds between now and the producer timestamp of the newest consumed entry
+    pub data_age_secs: Option<u64>,
+    /// Seconds since this instance last consumed an entry
+    pub last_consumed_secs_ago: Option<u64>,
+    pub stale_after_secs: Option<u64>,
+    pub entries_consumed: u64,
+    /// Stream receiving this stream's rejected entries
+    pub dead_letter_stream: String,
+    /// Entries rejected as malformed since startup
+    pub dead_lettered: u64,
+    /// Approximate length the stream is trimmed to (`None`: never trimmed)
+    pub max_len: Option<usize>,
+    /// Entries trimmed by the retention task since startup
+    pub entries_trimmed: u64,
+    pub last_trim_secs_ago: Option<u64>,
+    /// Last entry ID processed by the background consumers
+    pub checkpoint: Option<String>,
+    /// Already-processed entries skipped since startup
+    pub duplicates_skipped: u64,
+}
+
+/// Response for GET /health/details: per-island results of the last health check
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// `healthy` only when every island is
+    pub status: HealthStatus,
+    pub checked_at: String,
+    pub duration_ms: u64,
+    pub islands: Vec<IslandHealth>,
+    pub db_pool: DbPoolStats,
+    pub memory: MemoryStats,
+}
+
+/// Database pool usage; `SELECT 1` alone hides a pool that is exhausted
+#[derive(Debug, Clone, Serialize)]
+pub struct DbPoolStats {
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max_connections: u32,
+    /// Wait of the most recent sampled acquire
+    pub last_acquire_ms: f64,
+    /// 95th percentile of sampled acquire waits since startup
+    pub acquire_p95_ms: Option<f64>,
+    /// Acquires that hit the pool's acquire timeout since startup
+    pub acquire_timeouts: u64,
+}
+
+/// Process memory next to the L1 cache weight, to tell a leak from a full cache
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryStats {
+    /// Resident memory (`None` where `/proc` is unavailable)
+    pub rss_bytes: Option<u64>,
+    pub peak_rss_bytes: Option<u64>,
+    /// Resident memory change since startup
+    pub rss_growth_bytes: Option<i64>,
+    pub threads: Option<u64>,
+    pub l1_cache_bytes: u64,
+    pub l1_cache_max_bytes: u64,
+}
+
+impl HealthReport {
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        matches!(self.status, HealthStatus::Healthy)
+    }
+}
+
+/// Health of one island
+#[derive(Debug, Clone, Serialize)]
+pub struct IslandHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    pub checked_at: String,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for GET /version endpoint
+#[derive(Debug, Serialize)]
+pub struct VersionResponse {
+    pub name: &'static str,
+    pub version: &'static str,
+    /// Full commit hash, `unknown` when the build had no git metadata
+    pub git_commit: &'static str,
+    pub build_time: Option<String>,
+    pub rustc_version: &'static str,
+    pub uptime_secs: u64,
+}
+
+/// Response for GET /health/live endpoint
+#[derive(Debug, Serialize)]
+pub struct LivenessResponse {
+    pub status: HealthStatus,
+    pub uptime_secs: u64,
+}
+
+/// Response for GET /health/ready endpoint (503 when not ready)
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    /// `healthy` only when every component is ready, `degraded` when only the error
+    /// rate is over its limit
+    pub status: HealthStatus,
+    pub checked_at: String,
+    pub components: Vec<ComponentReadiness>,
+}
+
+/// Readiness of one dependency
+#[derive(Debug, Serialize)]
+pub struct ComponentReadiness {
+    pub name: String,
+    pub ready: bool,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
 }
 
 /// Services information for health checks
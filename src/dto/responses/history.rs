@@ -0,0 +1,68 @@
+//! Market data history DTOs
+
+use serde::{Deserialize, Serialize};
+
+/// Response for GET /api/crypto/history
+#[derive(Debug, Serialize)]
+pub struct MarketHistoryResponse {
+    pub metric: String,
+    /// `1h`, `24h`, `7d`, `30d`, `90d` or `1y`
+    pub range: String,
+    /// Width of the bucket each point averages
+    pub interval_secs: i64,
+    /// Oldest first; buckets without data are omitted
+    pub points: Vec<MarketHistoryPoint>,
+}
+
+/// Average of the metric over one bucket
+#[derive(Debug, Serialize)]
+pub struct MarketHistoryPoint {
+    /// Start of the bucket (RFC 3339)
+    pub timestamp: String,
+    pub value: f64,
+}
+
+/// Response for GET /api/crypto/ohlc (cached server-side like the dashboard data)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OhlcResponse {
+    /// Uppercase coin symbol, e.g. `BTC`
+    pub symbol: String,
+    /// `5m`, `15m`, `1h`, `4h` or `1d`
+    pub interval: String,
+    /// Oldest first; the last candle is still forming
+    pub candles: Vec<CandleResponse>,
+    /// Currency of the prices when converted with `?currency=`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+/// One candle of prices (USD unless converted)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandleResponse {
+    /// Start of the interval (RFC 3339)
+    pub open_time: String,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Recorded market data entries the candle aggregates
+    pub samples: i64,
+}
+
+/// Response for GET /api/crypto/fng/history (cached server-side like the dashboard data)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FngHistoryResponse {
+    /// Days covered, ending today (UTC)
+    pub days: i32,
+    /// Oldest first; days without a recorded value are omitted
+    pub points: Vec<FngHistoryPoint>,
+}
+
+/// Fear & Greed index of one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FngHistoryPoint {
+    /// UTC day (`YYYY-MM-DD`)
+    pub date: String,
+    /// 0 (extreme fear) to 100 (extreme greed)
+    pub value: i16,
+}
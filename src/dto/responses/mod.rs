@@ -1,12 +1,34 @@
 //! Response DTOs for API endpoints
 
+pub mod assets;
+pub mod audit;
 pub mod cache;
 pub mod dashboard;
+#[cfg(feature = "profiling")]
+pub mod debug;
+pub mod diagnostics;
 pub mod health;
+pub mod history;
+pub mod perf;
+pub mod redirects;
+pub mod reports;
+pub mod streams;
+pub mod subscriptions;
 pub mod websocket;
 
 // Re-export all response types for convenience
+pub use assets::*;
+pub use audit::*;
 pub use cache::*;
 pub use dashboard::{DashboardDataResponse, StockIndexData};
+#[cfg(feature = "profiling")]
+pub use debug::*;
+pub use diagnostics::*;
 pub use health::*;
+pub use history::*;
+pub use perf::*;
+pub use redirects::*;
+pub use reports::*;
+pub use streams::*;
+pub use subscriptions::*;
 pub use websocket::*;
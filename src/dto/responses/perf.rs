@@ -0,0 +1,52 @@
+//! Request latency summary and SLO DTOs
+
+use serde::Serialize;
+
+/// Response for GET /admin/perf endpoint
+#[derive(Debug, Serialize)]
+pub struct PerfResponse {
+    pub uptime_secs: u64,
+    pub total_requests: u64,
+    /// Routes ordered by request count, busiest first
+    pub routes: Vec<RoutePerfEntry>,
+}
+
+/// Latency percentiles of one route since startup
+///
+/// Percentiles are estimated from the `/metrics` histogram buckets.
+#[derive(Debug, Serialize)]
+pub struct RoutePerfEntry {
+    /// Route path as registered, e.g. `/crypto_report/{id}`
+    pub route: String,
+    pub requests: u64,
+    pub mean_ms: Option<f64>,
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+}
+
+/// Response for GET /admin/slo endpoint
+#[derive(Debug, Serialize)]
+pub struct SloResponse {
+    pub window_secs: u64,
+    pub objectives: Vec<SloEntry>,
+}
+
+/// Compliance of one objective over the rolling window
+#[derive(Debug, Serialize)]
+pub struct SloEntry {
+    pub name: String,
+    pub description: String,
+    /// `availability` or `latency`
+    pub kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_threshold_ms: Option<u64>,
+    /// Share of good requests to reach (0-1)
+    pub objective: f64,
+    pub total_requests: u64,
+    pub good_requests: u64,
+    /// Share of good requests, `None` without traffic
+    pub compliance: Option<f64>,
+    /// Share of the error budget left; negative once it is overspent
+    pub error_budget_remaining: f64,
+}
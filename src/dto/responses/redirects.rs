@@ -0,0 +1,32 @@
+//! Redirect management DTOs
+
+use serde::Serialize;
+
+/// Response for GET /admin/redirects
+#[derive(Debug, Serialize)]
+pub struct RedirectsResponse {
+    /// Rules currently served by this instance
+    pub active: usize,
+    /// Oldest first
+    pub redirects: Vec<RedirectEntryResponse>,
+}
+
+/// One stored redirect
+#[derive(Debug, Serialize)]
+pub struct RedirectEntryResponse {
+    pub id: i64,
+    pub source: String,
+    pub target: String,
+    /// 301 or 302
+    pub status: i16,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Response for DELETE /admin/redirects/{id}
+#[derive(Debug, Serialize)]
+pub struct RedirectDeleteResponse {
+    pub id: i64,
+    pub deleted: bool,
+}
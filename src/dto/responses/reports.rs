@@ -0,0 +1,23 @@
+//! Report administration response DTOs
+
+use serde::Serialize;
+
+/// Response for GET /admin/reports/oversized endpoint
+#[derive(Debug, Serialize)]
+pub struct OversizedReportsResponse {
+    /// Size threshold used for the scan (bytes)
+    pub threshold_bytes: i64,
+    pub count: usize,
+    pub reports: Vec<OversizedReportEntry>,
+}
+
+/// A single report exceeding the HTML size limit
+#[derive(Debug, Serialize)]
+pub struct OversizedReportEntry {
+    pub id: i32,
+    pub html_bytes: i64,
+    pub html_en_bytes: Option<i64>,
+    pub total_bytes: i64,
+    pub created_at: String,
+    pub url: String,
+}
@@ -0,0 +1,16 @@
+//! Stream-related response DTOs
+
+use crate::dto::common::CacheOperationStatus;
+use serde::Serialize;
+
+/// Response for POST /admin/streams/replay
+#[derive(Debug, Serialize)]
+pub struct StreamReplayResponse {
+    pub stream: String,
+    pub status: CacheOperationStatus,
+    pub entries_read: usize,
+    /// Entries that failed validation and were dead-lettered
+    pub rejected: usize,
+    /// Entry now cached as the stream's latest snapshot
+    pub cached_entry_id: Option<String>,
+}
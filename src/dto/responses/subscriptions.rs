@@ -0,0 +1,16 @@
+//! Email subscription DTOs
+
+use serde::Serialize;
+
+/// Response for POST /api/subscribe
+///
+/// Identical for new and existing subscriptions, so the endpoint does not
+/// reveal which addresses are subscribed.
+#[derive(Debug, Serialize)]
+pub struct SubscribeResponse {
+    pub email: String,
+    /// `daily` or `weekly`
+    pub frequency: String,
+    /// `vi` or `en`
+    pub language: String,
+}
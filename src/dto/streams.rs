@@ -0,0 +1,331 @@
+//! Stream entry DTOs
+//!
+//! Typed payloads of the Redis streams published by the websocket service. Entries
+//! are deserialized from the JSON object built by the stream reader, where each
+//! field value was read as JSON (`"45000.5"` -> number, `"{...}"` -> object).
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::dto::responses::StockIndexData;
+
+/// Why a stream entry was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEntryError {
+    /// The entry has no payload fields
+    Empty,
+    /// A field expected to hold JSON does not parse
+    InvalidJson { field: String },
+    /// Missing field or a value of the wrong type
+    Schema(String),
+    /// A value outside its allowed range
+    OutOfRange {
+        field: &'static str,
+        value: f64,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for StreamEntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "payload has no fields"),
+            Self::InvalidJson { field } => write!(f, "field `{field}` is not valid JSON"),
+            Self::Schema(msg) => write!(f, "schema violation: {msg}"),
+            Self::OutOfRange {
+                field,
+                value,
+                expected,
+            } => write!(f, "`{field}` must be {expected}, got {value}"),
+        }
+    }
+}
+
+impl std::error::Error for StreamEntryError {}
+
+/// One market data snapshot from `market_data_stream`
+///
+/// Only `btc_price_usd` is required; fields outside the schema are kept in
+/// `extra` as published.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketDataStreamEntry {
+    pub btc_price_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub btc_change_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub btc_market_cap_percentage: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub btc_rsi_14: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_price_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_change_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_market_cap_percentage: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bnb_price_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bnb_change_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sol_price_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sol_change_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xrp_price_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xrp_change_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ada_price_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ada_change_24h: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_price_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_change_24h: Option<f64>,
+
+    /// Gold (XAU) in USD per troy ounce
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gold_price_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gold_change_24h: Option<f64>,
+    /// US Dollar Index (points)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dxy_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dxy_change_24h: Option<f64>,
+
+    /// Vietnamese dong per USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usd_vnd_rate: Option<f64>,
+    /// Euro per USD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usd_eur_rate: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_cap_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_cap_change_percentage_24h_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_24h_usd: Option<f64>,
+
+    /// Fear & Greed Index (0-100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fng_value: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub us_stock_indices: Option<HashMap<String, StockIndexData>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch_duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_failure: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "text",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_updated: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "text",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timestamp: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl MarketDataStreamEntry {
+    /// Deserialize and range-check a decoded payload
+    ///
+    /// # Errors
+    /// Returns the first schema or range violation.
+    pub fn from_value(payload: Value) -> Result<Self, StreamEntryError> {
+        if payload.as_object().is_some_and(serde_json::Map::is_empty) {
+            return Err(StreamEntryError::Empty);
+        }
+        let entry: Self =
+            serde_json::from_value(payload).map_err(|e| StreamEntryError::Schema(e.to_string()))?;
+        entry.validate()?;
+        Ok(entry)
+    }
+
+    /// Check value ranges (prices positive, percentages and indexes within 0-100)
+    ///
+    /// # Errors
+    /// Returns the first value outside its range.
+    pub fn validate(&self) -> Result<(), StreamEntryError> {
+        let prices = [
+            ("btc_price_usd", Some(self.btc_price_usd)),
+            ("eth_price_usd", self.eth_price_usd),
+            ("bnb_price_usd", self.bnb_price_usd),
+            ("sol_price_usd", self.sol_price_usd),
+            ("xrp_price_usd", self.xrp_price_usd),
+            ("ada_price_usd", self.ada_price_usd),
+            ("link_price_usd", self.link_price_usd),
+            ("gold_price_usd", self.gold_price_usd),
+            ("dxy_value", self.dxy_value),
+            ("usd_vnd_rate", self.usd_vnd_rate),
+            ("usd_eur_rate", self.usd_eur_rate),
+        ];
+        check_all(&prices, "a positive number", |v| v > 0.0)?;
+
+        let totals = [
+            ("market_cap_usd", self.market_cap_usd),
+            ("volume_24h_usd", self.volume_24h_usd),
+        ];
+        check_all(&totals, "a non-negative number", |v| v >= 0.0)?;
+
+        let bounded = [
+            ("btc_market_cap_percentage", self.btc_market_cap_percentage),
+            ("eth_market_cap_percentage", self.eth_market_cap_percentage),
+            ("btc_rsi_14", self.btc_rsi_14),
+            ("fng_value", self.fng_value.map(f64::from)),
+        ];
+        check_all(&bounded, "within 0-100", |v| (0.0..=100.0).contains(&v))?;
+
+        let changes = [
+            ("btc_change_24h", self.btc_change_24h),
+            ("eth_change_24h", self.eth_change_24h),
+            ("bnb_change_24h", self.bnb_change_24h),
+            ("sol_change_24h", self.sol_change_24h),
+            ("xrp_change_24h", self.xrp_change_24h),
+            ("ada_change_24h", self.ada_change_24h),
+            ("link_change_24h", self.link_change_24h),
+            ("gold_change_24h", self.gold_change_24h),
+            ("dxy_change_24h", self.dxy_change_24h),
+            (
+                "market_cap_change_percentage_24h_usd",
+                self.market_cap_change_percentage_24h_usd,
+            ),
+        ];
+        check_all(&changes, "a finite number", |_| true)
+    }
+}
+
+/// Every present value must be finite and pass `valid`
+fn check_all(
+    values: &[(&'static str, Option<f64>)],
+    expected: &'static str,
+    valid: impl Fn(f64) -> bool,
+) -> Result<(), StreamEntryError> {
+    for &(field, value) in values {
+        if let Some(value) = value
+            && !(value.is_finite() && valid(value))
+        {
+            return Err(StreamEntryError::OutOfRange {
+                field,
+                value,
+                expected,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Text field that producers sometimes send as a bare number (unix timestamps)
+fn text<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(None),
+        Value::String(text) => Ok(Some(text)),
+        Value::Number(number) => Ok(Some(number.to_string())),
+        other => Err(D::Error::custom(format!(
+            "invalid type: {other}, expected a string"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_payload_parses_into_schema() -> Result<(), StreamEntryError> {
+        let entry = MarketDataStreamEntry::from_value(json!({
+            "btc_price_usd": 45000.5,
+            "btc_change_24h": -2.5,
+            "fng_value": 75,
+            "partial_failure": false,
+            "timestamp": 1_710_928_800,
+            "us_stock_indices": {"SPX": {"price": 5000.0, "change": 10.0, "change_percent": 0.2, "status": "open"}},
+            "source": "websocket-1",
+        }))?;
+        assert!((entry.btc_price_usd - 45000.5).abs() < f64::EPSILON);
+        assert_eq!(entry.btc_change_24h, Some(-2.5));
+        assert_eq!(entry.fng_value, Some(75));
+        assert_eq!(entry.partial_failure, Some(false));
+        assert_eq!(entry.timestamp.as_deref(), Some("1710928800"));
+        assert!(
+            entry
+                .us_stock_indices
+                .as_ref()
+                .is_some_and(|indices| indices.contains_key("SPX"))
+        );
+        assert_eq!(entry.extra.get("source"), Some(&json!("websocket-1")));
+
+        // Absent optional fields stay absent when the entry is cached
+        let typed = json!({"btc_price_usd": 60000.0, "fng_value": 40});
+        let entry = MarketDataStreamEntry::from_value(typed.clone())?;
+        assert_eq!(serde_json::to_value(&entry).ok(), Some(typed));
+        Ok(())
+    }
+
+    #[test]
+    fn test_violations_are_structured() {
+        let error = |payload: Value| MarketDataStreamEntry::from_value(payload).err();
+        assert_eq!(error(json!({})), Some(StreamEntryError::Empty));
+        assert!(matches!(
+            error(json!({"btc_change_24h": 1.0})),
+            Some(StreamEntryError::Schema(msg)) if msg.contains("btc_price_usd")
+        ));
+        assert!(matches!(
+            error(json!({"btc_price_usd": "n/a"})),
+            Some(StreamEntryError::Schema(_))
+        ));
+        assert_eq!(
+            error(json!({"btc_price_usd": -1})),
+            Some(StreamEntryError::OutOfRange {
+                field: "btc_price_usd",
+                value: -1.0,
+                expected: "a positive number",
+            })
+        );
+        assert!(matches!(
+            error(json!({"btc_price_usd": 1.0, "fng_value": 140})),
+            Some(StreamEntryError::OutOfRange {
+                field: "fng_value",
+                ..
+            })
+        ));
+        assert!(matches!(
+            error(json!({"btc_price_usd": 1.0, "usd_vnd_rate": -25000})),
+            Some(StreamEntryError::OutOfRange {
+                field: "usd_vnd_rate",
+                ..
+            })
+        ));
+        assert!(matches!(
+            error(json!({"btc_price_usd": 1.0, "dxy_value": 0})),
+            Some(StreamEntryError::OutOfRange {
+                field: "dxy_value",
+                ..
+            })
+        ));
+        assert!(matches!(
+            error(json!({"btc_price_usd": 1.0, "volume_24h_usd": -5})),
+            Some(StreamEntryError::OutOfRange {
+                field: "volume_24h_usd",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,320 @@
+//! Byte-Weighted L1 Cache
+//!
+//! In-memory tier in front of Redis. Its budget is counted in bytes (key plus
+//! value) rather than entries, so a few multi-megabyte reports can no longer push
+//! the process far past its memory limit while the entry count still looks small:
+//! once the budget is full, the least useful entries are evicted to make room.
+//!
+//! Entries expire after the TTL they were written with (capped at
+//! `L1_TIME_TO_LIVE`) or after `L1_TIME_TO_IDLE` without a read. Size evictions,
+//! expirations and evicted bytes are counted for `/admin/cache/stats`.
+//!
+//! Environment:
+//! - `CACHE_L1_MAX_MB`: memory budget (default 256)
+
+use futures::future::BoxFuture;
+use moka::Expiry;
+use moka::future::Cache;
+use moka::notification::RemovalCause;
+use multi_tier_cache::{Bytes, CacheBackend, CacheResult, L2CacheBackend};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use crate::disk_cache::glob_matches;
+
+/// Longest time an entry stays in memory, capping every strategy TTL
+pub const L1_TIME_TO_LIVE: Duration = Duration::from_mins(30);
+/// Entries not read for this long are dropped
+pub const L1_TIME_TO_IDLE: Duration = Duration::from_mins(2);
+
+const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Size and eviction totals of one cache tier
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TierUsage {
+    pub entries: u64,
+    pub bytes: u64,
+    pub max_bytes: u64,
+    /// Entries removed to stay within `max_bytes`
+    pub evictions: u64,
+    pub evicted_bytes: u64,
+    /// Entries removed because their TTL ran out
+    pub expirations: u64,
+}
+
+#[derive(Clone)]
+struct L1Entry {
+    value: Bytes,
+    expires_at: Instant,
+}
+
+/// Per-entry TTL, capped at `L1_TIME_TO_LIVE`
+struct EntryExpiry;
+
+impl EntryExpiry {
+    fn remaining(entry: &L1Entry, now: Instant) -> Duration {
+        entry
+            .expires_at
+            .saturating_duration_since(now)
+            .min(L1_TIME_TO_LIVE)
+    }
+}
+
+impl Expiry<String, L1Entry> for EntryExpiry {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &L1Entry,
+        created_at: Instant,
+    ) -> Option<Duration> {
+        Some(Self::remaining(value, created_at))
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        value: &L1Entry,
+        updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        Some(Self::remaining(value, updated_at))
+    }
+}
+
+#[derive(Default)]
+struct RemovalCounters {
+    evictions: AtomicU64,
+    evicted_bytes: AtomicU64,
+    expirations: AtomicU64,
+}
+
+impl RemovalCounters {
+    fn record(&self, cause: RemovalCause, weight: u32) {
+        match cause {
+            RemovalCause::Size => {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+                self.evicted_bytes
+                    .fetch_add(u64::from(weight), Ordering::Relaxed);
+            }
+            RemovalCause::Expired => {
+                self.expirations.fetch_add(1, Ordering::Relaxed);
+            }
+            RemovalCause::Explicit | RemovalCause::Replaced => {}
+        }
+    }
+}
+
+/// Budget weight of an entry: key plus value bytes
+fn entry_weight(key: &str, entry: &L1Entry) -> u32 {
+    u32::try_from(key.len().saturating_add(entry.value.len())).unwrap_or(u32::MAX)
+}
+
+/// Moka cache with a byte budget and eviction accounting
+pub struct WeightedL1Cache {
+    cache: Cache<String, L1Entry>,
+    max_bytes: u64,
+    counters: Arc<RemovalCounters>,
+}
+
+impl WeightedL1Cache {
+    /// Cache holding at most `max_bytes` of keys and values
+    #[must_use]
+    pub fn new(max_bytes: u64) -> Self {
+        let counters = Arc::new(RemovalCounters::default());
+        let listener_counters = Arc::clone(&counters);
+        let cache = Cache::builder()
+            .max_capacity(max_bytes)
+            .weigher(|key: &String, entry: &L1Entry| entry_weight(key, entry))
+            .expire_after(EntryExpiry)
+            .time_to_idle(L1_TIME_TO_IDLE)
+            .eviction_listener(move |key: Arc<String>, entry: L1Entry, cause| {
+                listener_counters.record(cause, entry_weight(&key, &entry));
+            })
+            .build();
+        Self {
+            cache,
+            max_bytes,
+            counters,
+        }
+    }
+
+    /// Budget from `CACHE_L1_MAX_MB`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let max_bytes = max_bytes(std::env::var("CACHE_L1_MAX_MB").ok().as_deref());
+        info!("🧠 L1 cache budget {}MB", max_bytes / (1024 * 1024));
+        Self::new(max_bytes)
+    }
+
+    /// Current size and eviction totals (applies pending evictions first)
+    pub async fn usage(&self) -> TierUsage {
+        self.cache.run_pending_tasks().await;
+        TierUsage {
+            entries: self.cache.entry_count(),
+            bytes: self.cache.weighted_size(),
+            max_bytes: self.max_bytes,
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+            evicted_bytes: self.counters.evicted_bytes.load(Ordering::Relaxed),
+            expirations: self.counters.expirations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl CacheBackend for WeightedL1Cache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Option<Bytes>> {
+        Box::pin(async move { self.cache.get(key).await.map(|entry| entry.value) })
+    }
+
+    fn set_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+        value: Bytes,
+        ttl: Duration,
+    ) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            let entry = L1Entry {
+                value,
+                expires_at: Instant::now() + ttl,
+            };
+            self.cache.insert(key.to_string(), entry).await;
+            Ok(())
+        })
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            self.cache.invalidate(key).await;
+            Ok(())
+        })
+    }
+
+    fn remove_pattern<'a>(&'a self, pattern: &'a str) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            if pattern == "*" {
+                self.cache.invalidate_all();
+                return Ok(());
+            }
+            let matching: Vec<Arc<String>> = self
+                .cache
+                .iter()
+                .map(|(key, _)| key)
+                .filter(|key| glob_matches(pattern, key))
+                .collect();
+            for key in matching {
+                self.cache.invalidate(key.as_str()).await;
+            }
+            Ok(())
+        })
+    }
+
+    fn health_check(&self) -> BoxFuture<'_, bool> {
+        Box::pin(async { true })
+    }
+
+    fn name(&self) -> &'static str {
+        "Moka"
+    }
+}
+
+impl L2CacheBackend for WeightedL1Cache {
+    fn get_with_ttl<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Option<(Bytes, Option<Duration>)>> {
+        Box::pin(async move {
+            self.cache.get(key).await.map(|entry| {
+                let ttl = EntryExpiry::remaining(&entry, Instant::now());
+                (entry.value, Some(ttl))
+            })
+        })
+    }
+}
+
+/// `CACHE_L1_MAX_MB` in bytes, at least one megabyte
+fn max_bytes(value: Option<&str>) -> u64 {
+    value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|mb| *mb > 0)
+        .map_or(DEFAULT_MAX_BYTES, |mb| mb.saturating_mul(1024 * 1024))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_byte_budget_evicts_and_counts() -> CacheResult<()> {
+        let cache = WeightedL1Cache::new(64 * 1024);
+        let report = Bytes::from(vec![b'x'; 20 * 1024]);
+        for id in 0..6 {
+            cache
+                .set_with_ttl(
+                    &format!("report_{id}"),
+                    report.clone(),
+                    Duration::from_mins(5),
+                )
+                .await?;
+        }
+
+        let usage = cache.usage().await;
+        assert!(usage.bytes <= usage.max_bytes);
+        assert!(usage.entries < 6);
+        assert_eq!(usage.evictions, 6 - usage.entries);
+        assert!(usage.evicted_bytes >= usage.evictions * 20 * 1024);
+
+        // Larger than the whole budget: rejected right away
+        cache
+            .set_with_ttl(
+                "huge",
+                Bytes::from(vec![b'x'; 128 * 1024]),
+                Duration::from_mins(5),
+            )
+            .await?;
+        let after_huge = cache.usage().await;
+        assert!(cache.get("huge").await.is_none());
+        assert_eq!(after_huge.evictions, usage.evictions + 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_pattern_and_expiry() -> CacheResult<()> {
+        let cache = WeightedL1Cache::new(DEFAULT_MAX_BYTES);
+        let page = Bytes::from_static(b"html");
+        for key in ["report_1_vi", "report_1_en", "homepage_vi"] {
+            cache
+                .set_with_ttl(key, page.clone(), Duration::from_mins(5))
+                .await?;
+        }
+        cache.remove_pattern("report_1_*").await?;
+        assert!(cache.get("report_1_en").await.is_none());
+        assert_eq!(cache.get("homepage_vi").await, Some(page.clone()));
+
+        cache
+            .set_with_ttl("short", page, Duration::from_millis(20))
+            .await?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(cache.get("short").await.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_removal_causes_counted() {
+        let counters = RemovalCounters::default();
+        counters.record(RemovalCause::Size, 2048);
+        counters.record(RemovalCause::Expired, 512);
+        counters.record(RemovalCause::Explicit, 512);
+        counters.record(RemovalCause::Replaced, 512);
+        assert_eq!(counters.evictions.load(Ordering::Relaxed), 1);
+        assert_eq!(counters.evicted_bytes.load(Ordering::Relaxed), 2048);
+        assert_eq!(counters.expirations.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_max_bytes() {
+        assert_eq!(max_bytes(None), DEFAULT_MAX_BYTES);
+        assert_eq!(max_bytes(Some("64")), 64 * 1024 * 1024);
+        assert_eq!(max_bytes(Some("0")), DEFAULT_MAX_BYTES);
+    }
+}
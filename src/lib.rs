@@ -1,8 +1,19 @@
 pub mod assets;
+pub mod cache_config;
+pub mod cache_invalidation;
+pub mod cache_resilience;
+pub mod cache_storage;
+pub mod cluster;
+pub mod disk_cache;
 pub mod dto;
 pub mod error;
+pub mod l1_cache;
 pub mod performance;
+pub mod redis_endpoint;
 pub mod routes;
+pub mod secrets;
 pub mod services;
 pub mod state;
 pub mod stream;
+pub mod tasks;
+pub mod warmup;
@@ -2,30 +2,78 @@
 use dotenvy::dotenv;
 use std::{env, net::SocketAddr, sync::Arc};
 use tracing::{info, warn};
-use tracing_subscriber::EnvFilter;
 
-use web_server_report::{routes::create_router, state::AppState};
+use web_server_report::{
+    cluster::{self, ClusterConfig},
+    routes::{create_router, seo},
+    secrets::SecretsAudit,
+    services::data_communication::{spawn_fng_recorder, spawn_history_recorder},
+    services::health_system::{init_error_reporting, init_tracing, spawn_heartbeat},
+    services::notifications::spawn_digest_scheduler,
+    services::shared::spawn_publish_notifier,
+    state::AppState,
+    warmup,
+};
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     dotenv().ok();
 
-    // Initialize tracing subscriber with env_filter support
-    // Use RUST_LOG environment variable (defaults to "info" if not set)
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+    // Initialize tracing subscriber: RUST_LOG filter (default "info"), LOG_FORMAT=json
+    // for one JSON object per line
+    init_tracing();
+    // Report server errors and panics (to Sentry when built with `sentry` and SENTRY_DSN is set)
+    init_error_reporting();
 
-    info!("🚀 Starting Web Server with Refactored Architecture...");
+    // Validate secrets before connecting anything (APP_ENV=production refuses to start
+    // with missing, malformed or placeholder values)
+    SecretsAudit::from_env().enforce()?;
+
+    // Multi-process mode: supervisor only spawns and restarts workers
+    let cluster_config = ClusterConfig::current();
+    if cluster_config.is_supervisor() {
+        return cluster::run_supervisor(cluster_config).await;
+    }
+
+    if let Some(worker_id) = cluster_config.worker_id {
+        info!(
+            "🚀 Starting Web Server worker {} of {}...",
+            worker_id, cluster_config.workers
+        );
+    } else {
+        info!("🚀 Starting Web Server with Refactored Architecture...");
+    }
 
     // Initialize Application State
     info!("🏗️ Initializing AppState...");
     let state = Arc::new(AppState::new().await?);
 
-    // ✅ Pre-render homepage to multi-tier cache (L1 RAM + L2 Redis)
-    state.dashboard_handlers.init_homepage_cache(&state).await;
+    // ✅ Evict L1 entries purged or rewritten by other instances
+    state.cache_bus.start(&state.tasks);
+
+    // Once-per-host jobs: only worker 0 runs them when clustered
+    if cluster_config.runs_singletons() {
+        // ✅ Pre-render hot pages (latest report, list, homepage, sitemap, RSS) in the background
+        let _warmup = warmup::spawn_cache_warmup(&state);
+
+        // ✅ Regenerate the sitemaps listing a report when it is published or updated
+        seo::spawn_sitemap_refresher(&state);
+
+        // ✅ Ping the WebSub hub / IndexNow when a report is published
+        spawn_publish_notifier(&state);
+
+        // ✅ Store every market data entry for /api/crypto/history
+        spawn_history_recorder(&state);
+
+        // ✅ Keep the daily Fear & Greed index for /api/crypto/fng/history
+        spawn_fng_recorder(&state);
+
+        // ✅ Send daily / weekly email digests when SMTP_URL is set
+        spawn_digest_scheduler(&state);
+
+        // ✅ Push heartbeats to an external monitor when HEARTBEAT_URL is set
+        spawn_heartbeat(&state);
+    }
 
     // Note: WebSocket and streaming functionality is now handled by separate websocket service
 
@@ -52,22 +100,24 @@ async fn main() -> Result<(), anyhow::Error> {
     info!("🌐 Server listening on http://{}", addr);
 
     // Setup graceful shutdown signal handler
+    // (Ctrl+C or SIGTERM, which the supervisor forwards to its workers)
     let shutdown_signal = async {
-        if let Err(e) = tokio::signal::ctrl_c().await {
-            warn!("⚠️ Failed to install CTRL+C signal handler: {}", e);
-            // Continue anyway - shutdown will still work via other signals
-        }
-        info!("\n🛑 Received shutdown signal (Ctrl+C)");
+        let signal = cluster::shutdown_signal().await;
+        info!("\n🛑 Received shutdown signal ({})", signal.as_str());
     };
 
-    // Create TCP listener
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    // Create TCP listener (SO_REUSEPORT when running as one of several workers)
+    let listener = cluster::bind_listener(addr, cluster_config.reuse_port)?;
     info!("✅ Server started - Press Ctrl+C to shutdown gracefully");
 
     // Start server with graceful shutdown support
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal)
-        .await?;
+    // Peer addresses are the client IP fallback when no proxy forwards one
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal)
+    .await?;
 
     // Note: Add any app state cleanup here if necessary when gracefully shutting down.
 
@@ -0,0 +1,483 @@
+//! Redis Deployment Resolution (Standalone / Sentinel)
+//!
+//! `REDIS_URL` may point at a single node (`redis://`, `rediss://`, `unix://`) or at
+//! a Sentinel deployment:
+//!
+//! `redis+sentinel://[[user]:password@]host[:port][,host[:port]...]/<master>[/<db>]`
+//!
+//! The credentials and database apply to the master; sentinels default to port
+//! 26379 and authenticate with `REDIS_SENTINEL_PASSWORD` when set. The master is
+//! resolved with `SENTINEL get-master-addr-by-name` at startup and re-checked every
+//! `REDIS_SENTINEL_CHECK_SECS` (default 5). When it moves, `generation()` changes
+//! and every connection holder reconnects to the new master:
+//! - the L2 backend on its next health probe
+//! - `FailoverStreams` (the cache manager's stream backend) on its next call
+//! - the stream consumers after their next Redis error
+//!
+//! The invalidation bus stays on the master resolved at startup; its L1 evictions
+//! are best-effort and bounded by the L1 TTL.
+//!
+//! Redis Cluster (`redis+cluster://`) is rejected: the cache backend and stream
+//! reader use single-node connections that cannot follow `MOVED` redirects.
+
+use futures::future::BoxFuture;
+use multi_tier_cache::traits::StreamEntry;
+use multi_tier_cache::{CacheError, CacheResult, RedisStreams, StreamingBackend};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::tasks::TaskRegistry;
+
+const SENTINEL_SCHEME: &str = "redis+sentinel://";
+const CLUSTER_SCHEMES: [&str; 2] = ["redis+cluster://", "rediss+cluster://"];
+/// Sentinel port used when an address has none
+const DEFAULT_SENTINEL_PORT: u16 = 26379;
+/// Default delay between master checks
+const DEFAULT_SENTINEL_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Max time one sentinel may take to answer
+const SENTINEL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sentinel deployment from `REDIS_URL`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentinelConfig {
+    /// `host:port` of every sentinel
+    pub sentinels: Vec<String>,
+    /// Monitored master name
+    pub master_name: String,
+    /// `user:password` (still URL-encoded) for the master
+    pub credentials: Option<String>,
+    pub db: Option<i64>,
+    pub sentinel_password: Option<String>,
+}
+
+impl SentinelConfig {
+    /// URL of the master at `host:port`
+    fn master_url(&self, host: &str, port: u16) -> String {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .map(|credentials| format!("{credentials}@"))
+            .unwrap_or_default();
+        let db = self.db.map(|db| format!("/{db}")).unwrap_or_default();
+        format!("redis://{credentials}{host}:{port}{db}")
+    }
+
+    /// URL for one sentinel, with `REDIS_SENTINEL_PASSWORD`
+    fn sentinel_url(&self, address: &str) -> String {
+        match &self.sentinel_password {
+            Some(password) => format!("redis://:{password}@{address}"),
+            None => format!("redis://{address}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Topology {
+    Standalone,
+    Sentinel(SentinelConfig),
+}
+
+/// Where the current Redis master is, kept up to date across Sentinel failovers
+#[derive(Debug)]
+pub struct RedisEndpoint {
+    topology: Topology,
+    /// Master URL; `None` until a sentinel answered
+    master_url: RwLock<Option<String>>,
+    /// Bumped every time the master changes
+    generation: AtomicU64,
+}
+
+impl RedisEndpoint {
+    /// Parse `redis_url` and resolve the master of a Sentinel deployment
+    ///
+    /// Unreachable sentinels are not an error: the endpoint stays unresolved and
+    /// connection holders retry until the monitor finds the master.
+    ///
+    /// # Errors
+    /// Returns an error for a Cluster URL or a malformed Sentinel URL.
+    pub async fn connect(redis_url: &str) -> Result<Self, String> {
+        let sentinel_password = std::env::var("REDIS_SENTINEL_PASSWORD")
+            .ok()
+            .filter(|password| !password.is_empty());
+        let endpoint = match parse_topology(redis_url, sentinel_password)? {
+            Topology::Standalone => Self::standalone(redis_url),
+            Topology::Sentinel(config) => {
+                let endpoint = Self {
+                    topology: Topology::Sentinel(config),
+                    master_url: RwLock::new(None),
+                    generation: AtomicU64::new(0),
+                };
+                endpoint.refresh().await;
+                endpoint
+            }
+        };
+        Ok(endpoint)
+    }
+
+    /// Single node at `redis_url`
+    #[must_use]
+    pub fn standalone(redis_url: &str) -> Self {
+        Self {
+            topology: Topology::Standalone,
+            master_url: RwLock::new(Some(redis_url.to_string())),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// URL of the current master, `None` while no sentinel could be reached
+    #[must_use]
+    pub fn master_url(&self) -> Option<String> {
+        self.master_url.read().ok().and_then(|url| url.clone())
+    }
+
+    /// Changes whenever the master moves; connections opened under an older value are stale
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Whether the master is resolved through Sentinel
+    #[must_use]
+    pub const fn is_sentinel(&self) -> bool {
+        matches!(self.topology, Topology::Sentinel(_))
+    }
+
+    /// Re-check the master periodically, supervised as the `redis_sentinel` task
+    /// (nothing to watch for a single node)
+    pub fn spawn_monitor(self: &Arc<Self>, tasks: &TaskRegistry) {
+        if !self.is_sentinel() {
+            return;
+        }
+        let interval =
+            sentinel_check_interval(std::env::var("REDIS_SENTINEL_CHECK_SECS").ok().as_deref());
+        let endpoint = Arc::clone(self);
+        tasks.supervise("redis_sentinel", move || {
+            let endpoint = Arc::clone(&endpoint);
+            async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    endpoint.refresh().await;
+                }
+            }
+        });
+    }
+
+    /// Ask the sentinels for the master and record it if it moved
+    async fn refresh(&self) {
+        let Topology::Sentinel(config) = &self.topology else {
+            return;
+        };
+        let Some((host, port)) = query_master(config).await else {
+            return;
+        };
+        let url = config.master_url(&host, port);
+        let Ok(mut current) = self.master_url.write() else {
+            return;
+        };
+        if current.as_deref() == Some(url.as_str()) {
+            return;
+        }
+        if current.is_some() {
+            warn!(
+                "🔀 Redis master '{}' moved to {}:{}, reconnecting",
+                config.master_name, host, port
+            );
+        } else {
+            info!(
+                "🛰️ Redis master '{}' resolved via Sentinel: {}:{}",
+                config.master_name, host, port
+            );
+        }
+        *current = Some(url);
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// First sentinel answer to `get-master-addr-by-name`
+async fn query_master(config: &SentinelConfig) -> Option<(String, u16)> {
+    for address in &config.sentinels {
+        let query = async {
+            let client = redis::Client::open(config.sentinel_url(address))?;
+            let mut conn = client.get_multiplexed_async_connection().await?;
+            redis::cmd("SENTINEL")
+                .arg("get-master-addr-by-name")
+                .arg(&config.master_name)
+                .query_async::<Option<(String, u16)>>(&mut conn)
+                .await
+        };
+        match tokio::time::timeout(SENTINEL_TIMEOUT, query).await {
+            Ok(Ok(Some(master))) => return Some(master),
+            Ok(Ok(None)) => warn!(
+                "⚠️ Sentinel {} does not monitor master '{}'",
+                address, config.master_name
+            ),
+            Ok(Err(e)) => warn!("⚠️ Sentinel {} query failed: {}", address, e),
+            Err(_) => warn!("⚠️ Sentinel {} timed out", address),
+        }
+    }
+    None
+}
+
+fn parse_topology(redis_url: &str, sentinel_password: Option<String>) -> Result<Topology, String> {
+    if CLUSTER_SCHEMES
+        .iter()
+        .any(|scheme| redis_url.starts_with(scheme))
+    {
+        return Err(
+            "Redis Cluster is not supported: the cache backend uses single-node connections; \
+             use Sentinel (redis+sentinel://) or a cluster proxy endpoint"
+                .to_string(),
+        );
+    }
+    let Some(rest) = redis_url.strip_prefix(SENTINEL_SCHEME) else {
+        return Ok(Topology::Standalone);
+    };
+
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let (credentials, hosts) = match authority.rsplit_once('@') {
+        Some((credentials, hosts)) => (Some(credentials.to_string()), hosts),
+        None => (None, authority),
+    };
+    let sentinels: Vec<String> = hosts
+        .split(',')
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+        .map(with_default_port)
+        .collect();
+    if sentinels.is_empty() {
+        return Err("REDIS_URL: no sentinel address given".to_string());
+    }
+
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let master_name = segments
+        .next()
+        .ok_or("REDIS_URL: missing Sentinel master name (redis+sentinel://hosts/<master>)")?
+        .to_string();
+    let db = segments
+        .next()
+        .map(|db| {
+            db.parse::<i64>()
+                .map_err(|_| format!("REDIS_URL: invalid database '{db}'"))
+        })
+        .transpose()?;
+
+    Ok(Topology::Sentinel(SentinelConfig {
+        sentinels,
+        master_name,
+        credentials,
+        db,
+        sentinel_password,
+    }))
+}
+
+/// `host` -> `host:26379`; `[::1]` style IPv6 addresses need brackets
+fn with_default_port(host: &str) -> String {
+    let has_port = match host.rfind(']') {
+        Some(bracket) => host.get(bracket..).is_some_and(|tail| tail.contains(':')),
+        None => host.contains(':'),
+    };
+    if has_port {
+        host.to_string()
+    } else {
+        format!("{host}:{DEFAULT_SENTINEL_PORT}")
+    }
+}
+
+/// `REDIS_SENTINEL_CHECK_SECS`, at least one second
+fn sentinel_check_interval(value: Option<&str>) -> Duration {
+    value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map_or(DEFAULT_SENTINEL_CHECK_INTERVAL, |secs| {
+            Duration::from_secs(secs.max(1))
+        })
+}
+
+/// Stream backend that reconnects to the current master after a failover
+///
+/// Also connects lazily, so streams work once Redis comes up even if it was down
+/// at startup.
+pub struct FailoverStreams {
+    endpoint: Arc<RedisEndpoint>,
+    /// Connection and the endpoint generation it was opened for
+    inner: tokio::sync::Mutex<Option<(u64, Arc<RedisStreams>)>>,
+}
+
+impl FailoverStreams {
+    #[must_use]
+    pub fn new(endpoint: Arc<RedisEndpoint>) -> Self {
+        Self {
+            endpoint,
+            inner: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// Connection to the current master, opened on first use and after a failover
+    ///
+    /// # Errors
+    /// Returns an error if the master is unknown or cannot be reached.
+    pub async fn backend(&self) -> CacheResult<Arc<RedisStreams>> {
+        let generation = self.endpoint.generation();
+        let mut inner = self.inner.lock().await;
+        if let Some((connected, streams)) = inner.as_ref()
+            && *connected == generation
+        {
+            return Ok(Arc::clone(streams));
+        }
+        let url = self
+            .endpoint
+            .master_url()
+            .ok_or_else(|| CacheError::BackendError("Redis master not resolved yet".to_string()))?;
+        let streams = Arc::new(RedisStreams::new(&url).await?);
+        *inner = Some((generation, Arc::clone(&streams)));
+        Ok(streams)
+    }
+}
+
+impl StreamingBackend for FailoverStreams {
+    fn stream_add<'a>(
+        &'a self,
+        stream_key: &'a str,
+        fields: Vec<(String, String)>,
+        maxlen: Option<usize>,
+    ) -> BoxFuture<'a, CacheResult<String>> {
+        Box::pin(async move {
+            self.backend()
+                .await?
+                .stream_add(stream_key, fields, maxlen)
+                .await
+        })
+    }
+
+    fn stream_read_latest<'a>(
+        &'a self,
+        stream_key: &'a str,
+        count: usize,
+    ) -> BoxFuture<'a, CacheResult<Vec<StreamEntry>>> {
+        Box::pin(async move {
+            self.backend()
+                .await?
+                .stream_read_latest(stream_key, count)
+                .await
+        })
+    }
+
+    fn stream_read<'a>(
+        &'a self,
+        stream_key: &'a str,
+        last_id: &'a str,
+        count: usize,
+        block_ms: Option<usize>,
+    ) -> BoxFuture<'a, CacheResult<Vec<StreamEntry>>> {
+        Box::pin(async move {
+            self.backend()
+                .await?
+                .stream_read(stream_key, last_id, count, block_ms)
+                .await
+        })
+    }
+
+    fn stream_create_group<'a>(
+        &'a self,
+        stream_key: &'a str,
+        group_name: &'a str,
+        id: &'a str,
+    ) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            self.backend()
+                .await?
+                .stream_create_group(stream_key, group_name, id)
+                .await
+        })
+    }
+
+    fn stream_read_group<'a>(
+        &'a self,
+        stream_key: &'a str,
+        group_name: &'a str,
+        consumer_name: &'a str,
+        count: usize,
+        block_ms: Option<usize>,
+    ) -> BoxFuture<'a, CacheResult<Vec<StreamEntry>>> {
+        Box::pin(async move {
+            self.backend()
+                .await?
+                .stream_read_group(stream_key, group_name, consumer_name, count, block_ms)
+                .await
+        })
+    }
+
+    fn stream_ack<'a>(
+        &'a self,
+        stream_key: &'a str,
+        group_name: &'a str,
+        ids: &'a [String],
+    ) -> BoxFuture<'a, CacheResult<()>> {
+        Box::pin(async move {
+            self.backend()
+                .await?
+                .stream_ack(stream_key, group_name, ids)
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_topology() -> Result<(), String> {
+        assert_eq!(
+            parse_topology("redis://127.0.0.1:6379", None)?,
+            Topology::Standalone
+        );
+        assert_eq!(
+            parse_topology("rediss://:pw@cache.internal:6380/2", None)?,
+            Topology::Standalone
+        );
+
+        let Topology::Sentinel(config) = parse_topology(
+            "redis+sentinel://:s3cret@sentinel-a,sentinel-b:26380,[::1]/mymaster/2",
+            Some("sentinel-pw".to_string()),
+        )?
+        else {
+            return Err("expected a Sentinel topology".to_string());
+        };
+        assert_eq!(
+            config.sentinels,
+            vec!["sentinel-a:26379", "sentinel-b:26380", "[::1]:26379"]
+        );
+        assert_eq!(config.master_name, "mymaster");
+        assert_eq!(
+            config.master_url("10.0.0.5", 6379),
+            "redis://:s3cret@10.0.0.5:6379/2"
+        );
+        assert_eq!(
+            config.sentinel_url("sentinel-a:26379"),
+            "redis://:sentinel-pw@sentinel-a:26379"
+        );
+
+        assert!(parse_topology("redis+sentinel://sentinel-a", None).is_err());
+        assert!(parse_topology("redis+sentinel://s/mymaster/db0", None).is_err());
+        assert!(parse_topology("redis+cluster://node-1:6379,node-2:6379", None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_standalone_endpoint_is_resolved() {
+        let endpoint = RedisEndpoint::standalone("redis://127.0.0.1:6379");
+        assert!(!endpoint.is_sentinel());
+        assert_eq!(
+            endpoint.master_url().as_deref(),
+            Some("redis://127.0.0.1:6379")
+        );
+        assert_eq!(endpoint.generation(), 0);
+        assert_eq!(sentinel_check_interval(Some("0")), Duration::from_secs(1));
+        assert_eq!(
+            sentinel_check_interval(None),
+            DEFAULT_SENTINEL_CHECK_INTERVAL
+        );
+    }
+}
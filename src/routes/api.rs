@@ -6,23 +6,71 @@
 use axum::{
     Router,
     extract::{Path, Query, State},
-    response::{IntoResponse, Json},
+    http::HeaderMap,
+    response::{
+        IntoResponse, Json,
+        sse::{Event, KeepAlive, Sse},
+    },
     routing::get,
 };
+use futures::Stream;
+use serde::Deserialize;
+use serde_json::{Map, Value};
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
 use crate::dto::{
     HealthStatus,
-    responses::{ApiHealthInfo, ApiHealthResponse, DashboardDataResponse, WebSocketStatsResponse},
+    responses::{
+        ApiHealthInfo, ApiHealthResponse, AssetQuoteResponse, AssetsResponse, CandleResponse,
+        DashboardDataResponse, FngHistoryResponse, MarketHistoryPoint, MarketHistoryResponse,
+        OhlcResponse, ReportAuditEntryResponse, ReportAuditResponse, WebSocketStatsResponse,
+    },
+};
+use crate::services::data_communication::{
+    AssetKind, CandleInterval, Currency, CurrencyService, DEFAULT_FNG_DAYS, FngHistory,
+    HistoryMetric, HistoryRange, HistoryRecorder, MAX_FNG_DAYS, MAX_REPORT_AUDIT_PAGE_SIZE,
+    MarketAsset, ReportAuditService, asset_quotes,
+};
+use crate::services::shared::{
+    Layer5Error, Layer5Result, authorize_admin, build_forbidden_response, cache_compressed_data,
+    render_dashboard_card, sandboxed_report_path, try_get_cached_compressed, verify_sandbox_url,
 };
 use crate::state::AppState;
+use crate::stream::{FreshnessStatus, StreamUpdate};
+
+/// Comment line sent while no update arrives, so proxies keep the connection open
+const SSE_HEARTBEAT: Duration = Duration::from_secs(15);
+/// Reconnect delay suggested to `EventSource` clients
+const SSE_RETRY: Duration = Duration::from_secs(3);
+/// Candles returned by `/api/crypto/ohlc` by default / at most
+const DEFAULT_CANDLES: i32 = 100;
+const MAX_CANDLES: i32 = 500;
+/// Cache key of the rendered dashboard snapshot card
+const SNAPSHOT_CACHE_KEY: &str = "dashboard_snapshot_png";
 
 /// Configure API routes
 pub fn configure_api_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/api/crypto/dashboard-summary", get(api_dashboard_summary))
+        .route(
+            "/api/crypto/dashboard-summary/stream",
+            get(api_dashboard_summary_stream),
+        )
+        .route(
+            "/api/crypto/dashboard-summary/snapshot.png",
+            get(api_dashboard_snapshot),
+        )
+        .route("/api/crypto/stream", get(api_market_data_stream))
+        .route("/api/crypto/history", get(api_market_history))
+        .route("/api/crypto/ohlc", get(api_ohlc))
+        .route("/api/crypto/fng/history", get(api_fng_history))
+        .route("/api/crypto/assets", get(api_assets))
+        .route("/api/crypto/assets/{symbol}", get(api_asset))
+        .route("/api/crypto/reports/{id}/audit", get(api_report_audit))
         .route("/api/dashboard/data", get(api_dashboard_data))
         .route(
             "/api/crypto_reports/{id}/sandboxed",
@@ -38,21 +86,27 @@ pub fn configure_api_routes() -> Router<Arc<AppState>> {
 
 /// Dashboard data API endpoint - Enhanced with Redis Streams
 /// Same functionality as `api_dashboard_summary` but with cleaner path
-async fn api_dashboard_data(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+///
+/// `?currency=` `USD` (default), `VND` or `EUR` converts the money fields.
+async fn api_dashboard_data(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<impl IntoResponse> {
+    let (currency, rate) = requested_currency(&state, &params).await?;
     let cache_key = "api_dashboard_data_json";
     let mut cache_hit = "MISS";
 
-    let response_data = state
+    let mut response_data = state
         .cache_manager
         .get_or_compute_typed(
             cache_key,
-            multi_tier_cache::CacheStrategy::RealTime,
+            state.cache_config.market_data.strategy(),
             || async {
                 debug!("🔍 [API] Cache MISS - reading from Redis Stream...");
                 // Phase 3: Primary reads from Redis Streams via RedisStreamReader
-                if let Ok(Some(data)) = state.redis_stream_reader.read_latest_market_data().await {
+                if let Ok(Some(data)) = state.streams.market_data().read_latest().await {
                     debug!("✅ [API] Data fetched from Redis Stream");
-                    if let Ok(typed_data) = serde_json::from_value::<DashboardDataResponse>(data) {
+                    if let Some(typed_data) = dashboard_response(&data) {
                         return Ok(typed_data);
                     }
                 }
@@ -70,8 +124,10 @@ async fn api_dashboard_data(State(state): State<Arc<AppState>>) -> impl IntoResp
     if cache_hit != "ERROR" && state.cache_manager.get(cache_key).await.is_ok() {
         cache_hit = "HIT";
     }
+    stamp_freshness(&state, &mut response_data).await;
+    convert_dashboard(&mut response_data, currency, rate);
 
-    ([("x-cache", cache_hit)], Json(response_data))
+    Ok(([("x-cache", cache_hit)], Json(response_data)))
 }
 
 fn get_fallback_dashboard_data() -> DashboardDataResponse {
@@ -103,17 +159,564 @@ fn get_fallback_dashboard_data() -> DashboardDataResponse {
         last_updated: chrono::Utc::now().to_rfc3339(),
         timestamp: chrono::Utc::now().to_rfc3339(),
         note: Some("Fallback data - fresh data will be available within 10 seconds".to_string()),
+        assets: Vec::new(),
+        stale: true,
+        data_age_secs: None,
+        fields_last_updated: HashMap::new(),
+        currency: None,
+        fx_rate: None,
+        extra: Map::new(),
+    }
+}
+
+/// `?currency=` of the market data endpoints and its units per USD (USD when absent)
+///
+/// 503 while no rate of the currency is known (neither published nor configured).
+async fn requested_currency(
+    state: &AppState,
+    params: &HashMap<String, String>,
+) -> Layer5Result<(Currency, f64)> {
+    let currency = match params.get("currency") {
+        None => Currency::Usd,
+        Some(code) => Currency::parse(code).ok_or_else(|| {
+            Layer5Error::InvalidInput(format!(
+                "currency must be one of {}",
+                Currency::ALL.map(Currency::code).join(", ")
+            ))
+        })?,
+    };
+    if currency == Currency::Usd {
+        return Ok((currency, 1.0));
+    }
+    let rate = CurrencyService::new()
+        .rates(state)
+        .await
+        .map_err(|e| Layer5Error::Cache(e.to_string()))?
+        .rate(currency)
+        .ok_or_else(|| {
+            Layer5Error::Unavailable(format!("No {} exchange rate available", currency.code()))
+        })?;
+    Ok((currency, rate))
+}
+
+/// Express the USD money fields of a dashboard response in `currency`
+fn convert_dashboard(response: &mut DashboardDataResponse, currency: Currency, rate: f64) {
+    if currency == Currency::Usd {
+        return;
+    }
+    for amount in [
+        &mut response.btc_price_usd,
+        &mut response.eth_price_usd,
+        &mut response.bnb_price_usd,
+        &mut response.sol_price_usd,
+        &mut response.xrp_price_usd,
+        &mut response.ada_price_usd,
+        &mut response.link_price_usd,
+        &mut response.market_cap_usd,
+        &mut response.volume_24h_usd,
+    ] {
+        *amount *= rate;
+    }
+    for quote in &mut response.assets {
+        convert_quote(quote, currency, rate);
+    }
+    // Passed-through stream fields follow the same `_usd` naming
+    for (_, value) in response
+        .extra
+        .iter_mut()
+        .filter(|(field, _)| field.ends_with("_usd"))
+    {
+        if let Some(converted) = value
+            .as_f64()
+            .and_then(|usd| serde_json::Number::from_f64(usd * rate))
+        {
+            *value = Value::Number(converted);
+        }
+    }
+    response.currency = Some(currency.code().to_string());
+    response.fx_rate = Some(rate);
+}
+
+/// Express an asset quote in `currency`; index points are left as they are
+fn convert_quote(quote: &mut AssetQuoteResponse, currency: Currency, rate: f64) {
+    if currency == Currency::Usd || quote.kind == AssetKind::Index.as_str() {
+        return;
+    }
+    quote.price *= rate;
+    quote.currency = Some(currency.code().to_string());
+}
+
+/// Set the freshness fields of a dashboard response from this instance's view of
+/// the `market_data` stream
+///
+/// Done per request rather than cached, as the age keeps growing once the
+/// websocket service stops publishing.
+async fn stamp_freshness(state: &AppState, response: &mut DashboardDataResponse) {
+    let reader = state.streams.market_data();
+    let data_age = match reader.freshness().data_age {
+        Some(age) => Some(age),
+        // Nothing consumed here yet, e.g. serving a snapshot cached by another replica
+        None => reader.latest_entry_age().await.ok().flatten(),
+    };
+    response.data_age_secs = data_age.map(|age| age.as_secs());
+    response.stale = response.note.is_some()
+        || FreshnessStatus::of(data_age, reader.definition.stale_after) != FreshnessStatus::Fresh;
+    response.fields_last_updated = reader
+        .live_updates()
+        .field_freshness()
+        .updated_ms()
+        .into_iter()
+        .filter_map(|(field, ms)| {
+            let at = chrono::DateTime::from_timestamp_millis(i64::try_from(ms).ok()?)?;
+            Some((field, at.to_rfc3339()))
+        })
+        .collect();
+}
+
+/// Market data entry as a dashboard summary, with the quotes of its assets
+fn dashboard_response(data: &Value) -> Option<DashboardDataResponse> {
+    let mut response = DashboardDataResponse::deserialize(data).ok()?;
+    response.assets = asset_quotes(data);
+    Some(response)
+}
+
+/// Market data over Server-Sent Events, for browsers that can't reach the WebSocket service
+///
+/// Sends the latest snapshot on connect, then every newer accepted stream entry as a
+/// `market_data` event whose `id` is the stream entry ID. A reconnecting
+/// `EventSource` sends that ID back as `Last-Event-ID`, so an unchanged snapshot
+/// is not sent twice. Heartbeat comments keep idle connections alive.
+async fn api_market_data_stream(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+    let reader = state.streams.market_data();
+    let updates = reader.live_updates().subscribe();
+    if updates.borrow().is_none() {
+        // Nothing consumed yet: read the stream once so new clients get a snapshot
+        let _ = reader.read_latest().await;
+    }
+    info!(
+        "📡 SSE market data client connected ({} connected)",
+        reader.live_updates().subscribers()
+    );
+
+    let events = futures::stream::unfold(
+        (updates, last_event_id),
+        |(mut updates, mut last_sent)| async move {
+            loop {
+                let update = updates.borrow_and_update().clone();
+                if let Some(update) = update
+                    && last_sent.as_deref() != Some(update.entry_id.as_str())
+                {
+                    last_sent = Some(update.entry_id.clone());
+                    return Some((Ok(market_data_event(&update)), (updates, last_sent)));
+                }
+                if updates.changed().await.is_err() {
+                    return None;
+                }
+            }
+        },
+    );
+    Sse::new(events).keep_alive(KeepAlive::new().interval(SSE_HEARTBEAT).text("heartbeat"))
+}
+
+fn market_data_event(update: &StreamUpdate) -> Event {
+    Event::default()
+        .event("market_data")
+        .id(update.entry_id.as_str())
+        .retry(SSE_RETRY)
+        .json_data(update.data.as_ref())
+        .unwrap_or_else(|e| Event::default().comment(format!("unserializable update: {e}")))
+}
+
+/// Dashboard summary over Server-Sent Events, updated as `RedisStreamReader` accepts entries
+///
+/// Sends the summary of the latest entry as a `summary` event on connect, then a
+/// `summary_delta` event per newer entry holding only the fields that changed
+/// (in the shape of `/api/crypto/dashboard-summary`). A reconnecting client whose
+/// `Last-Event-ID` is the latest entry still has that summary, so it only gets
+/// the deltas after it. Entries that are not dashboard data are skipped.
+async fn api_dashboard_summary_stream(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string);
+    let reader = state.streams.market_data();
+    let mut updates = reader.live_updates().subscribe();
+    if updates.borrow().is_none() {
+        let _ = reader.read_latest().await;
+    }
+    info!(
+        "📡 SSE dashboard summary client connected ({} connected)",
+        reader.live_updates().subscribers()
+    );
+
+    // The client already holds the summary of its Last-Event-ID when that is the latest entry
+    let mut previous = None;
+    let latest = updates.borrow_and_update().clone();
+    if let Some(update) = latest {
+        if last_event_id.as_deref() == Some(update.entry_id.as_str()) {
+            previous = dashboard_summary(&update.data);
+        } else {
+            updates.mark_changed();
+        }
+    }
+
+    let events = futures::stream::unfold(
+        (updates, previous),
+        |(mut updates, mut previous)| async move {
+            loop {
+                if updates.changed().await.is_err() {
+                    return None;
+                }
+                let Some(update) = updates.borrow_and_update().clone() else {
+                    continue;
+                };
+                let Some(summary) = dashboard_summary(&update.data) else {
+                    continue;
+                };
+                let event = match &previous {
+                    None => Event::default().event("summary").json_data(&summary),
+                    Some(before) => match summary_delta(before, &summary) {
+                        Some(delta) => Event::default().event("summary_delta").json_data(delta),
+                        None => continue,
+                    },
+                };
+                previous = Some(summary);
+                let event = event.map_or_else(
+                    |e| Event::default().comment(format!("unserializable summary: {e}")),
+                    |event| event.id(update.entry_id.as_str()).retry(SSE_RETRY),
+                );
+                return Some((Ok(event), (updates, previous)));
+            }
+        },
+    );
+    Sse::new(events).keep_alive(KeepAlive::new().interval(SSE_HEARTBEAT).text("heartbeat"))
+}
+
+/// Stream entry as served by `/api/crypto/dashboard-summary`, `None` if it is not dashboard data
+fn dashboard_summary(data: &Value) -> Option<Value> {
+    serde_json::to_value(dashboard_response(data)?).ok()
+}
+
+/// Top-level fields of `current` that differ from `previous` (`null` for removed
+/// ones), `None` when nothing changed
+fn summary_delta(previous: &Value, current: &Value) -> Option<Map<String, Value>> {
+    let (Some(previous), Some(current)) = (previous.as_object(), current.as_object()) else {
+        return None;
+    };
+    let mut delta: Map<String, Value> = current
+        .iter()
+        .filter(|(key, value)| previous.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    for key in previous.keys().filter(|key| !current.contains_key(*key)) {
+        delta.insert(key.clone(), Value::Null);
+    }
+    (!delta.is_empty()).then_some(delta)
+}
+
+/// Recorded market data of one metric, averaged per bucket
+///
+/// `?metric=btc_price` (see `HistoryMetric`) and `?range=` one of `1h`, `24h`
+/// (default), `7d`, `30d`, `90d` and `1y`.
+async fn api_market_history(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<Json<MarketHistoryResponse>> {
+    let metric = params
+        .get("metric")
+        .and_then(|metric| HistoryMetric::parse(metric))
+        .ok_or_else(|| {
+            Layer5Error::InvalidInput(format!(
+                "metric must be one of {}",
+                HistoryMetric::names().collect::<Vec<_>>().join(", ")
+            ))
+        })?;
+    let range = match params.get("range") {
+        None => HistoryRange::Day,
+        Some(range) => HistoryRange::parse(range).ok_or_else(|| {
+            Layer5Error::InvalidInput(format!(
+                "range must be one of {}",
+                HistoryRange::ALL.map(HistoryRange::as_str).join(", ")
+            ))
+        })?,
+    };
+
+    let points = HistoryRecorder::new()
+        .fetch_series(&state, metric, range, chrono::Utc::now())
+        .await?
+        .into_iter()
+        .map(|point| MarketHistoryPoint {
+            timestamp: point.bucket.to_rfc3339(),
+            value: point.value,
+        })
+        .collect();
+    Ok(Json(MarketHistoryResponse {
+        metric: metric.name.to_string(),
+        range: range.as_str().to_string(),
+        interval_secs: range.bucket().num_seconds(),
+        points,
+    }))
+}
+
+/// Price candles of an asset from the recorded market data
+///
+/// `?symbol=BTC`, `?interval=` one of `5m`, `15m`, `1h` (default), `4h` and `1d`,
+/// `?limit=` candles (default 100, at most 500) and `?currency=` (USD by default).
+/// Aggregated in Postgres and cached in USD with the market data TTL, as each new
+/// entry moves the last candle.
+async fn api_ohlc(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<impl IntoResponse> {
+    let (symbol, metric) = params
+        .get("symbol")
+        .and_then(|symbol| Some((symbol.to_ascii_uppercase(), HistoryMetric::price(symbol)?)))
+        .ok_or_else(|| {
+            Layer5Error::InvalidInput(format!(
+                "symbol must be one of {}",
+                HistoryMetric::price_symbols()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+    let interval = match params.get("interval") {
+        None => CandleInterval::Hour,
+        Some(interval) => CandleInterval::parse(interval).ok_or_else(|| {
+            Layer5Error::InvalidInput(format!(
+                "interval must be one of {}",
+                CandleInterval::ALL.map(CandleInterval::as_str).join(", ")
+            ))
+        })?,
+    };
+    let limit = match params.get("limit") {
+        None => DEFAULT_CANDLES,
+        Some(limit) => limit
+            .parse()
+            .ok()
+            .filter(|limit| (1..=MAX_CANDLES).contains(limit))
+            .ok_or_else(|| {
+                Layer5Error::InvalidInput(format!("limit must be between 1 and {MAX_CANDLES}"))
+            })?,
+    };
+
+    let (currency, rate) = match MarketAsset::find(&symbol) {
+        Some(asset) if asset.kind == AssetKind::Index => (Currency::Usd, 1.0),
+        _ => requested_currency(&state, &params).await?,
+    };
+
+    let cache_key = format!("api_ohlc:{symbol}:{}:{limit}", interval.as_str());
+    let mut cache_status = "HIT";
+    let mut response = state
+        .cache_manager
+        .get_or_compute_typed(
+            &cache_key,
+            state.cache_config.market_data.strategy(),
+            || async {
+                cache_status = "MISS";
+                let candles = HistoryRecorder::new()
+                    .fetch_candles(&state, metric, interval, limit, chrono::Utc::now())
+                    .await
+                    .map_err(|e| multi_tier_cache::CacheError::BackendError(e.to_string()))?;
+                Ok(OhlcResponse {
+                    symbol: symbol.clone(),
+                    interval: interval.as_str().to_string(),
+                    candles: candles
+                        .into_iter()
+                        .map(|candle| CandleResponse {
+                            open_time: candle.bucket.to_rfc3339(),
+                            open: candle.open,
+                            high: candle.high,
+                            low: candle.low,
+                            close: candle.close,
+                            samples: candle.samples,
+                        })
+                        .collect(),
+                    currency: None,
+                })
+            },
+        )
+        .await
+        .map_err(|e| Layer5Error::Database(e.to_string()))?;
+
+    if currency != Currency::Usd {
+        for candle in &mut response.candles {
+            candle.open *= rate;
+            candle.high *= rate;
+            candle.low *= rate;
+            candle.close *= rate;
+        }
+        response.currency = Some(currency.code().to_string());
     }
+
+    Ok(([("x-cache", cache_status)], Json(response)))
+}
+
+/// Daily Fear & Greed index, for the trend under the gauge
+///
+/// `?days=` up to today (default 30, at most 365). Cached with the market data
+/// TTL, as today's value follows the stream.
+async fn api_fng_history(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<impl IntoResponse> {
+    let days = match params.get("days") {
+        None => DEFAULT_FNG_DAYS,
+        Some(days) => days
+            .parse()
+            .ok()
+            .filter(|days| (1..=MAX_FNG_DAYS).contains(days))
+            .ok_or_else(|| {
+                Layer5Error::InvalidInput(format!("days must be between 1 and {MAX_FNG_DAYS}"))
+            })?,
+    };
+
+    let mut cache_status = "HIT";
+    let response = state
+        .cache_manager
+        .get_or_compute_typed(
+            &format!("api_fng_history:{days}"),
+            state.cache_config.market_data.strategy(),
+            || async {
+                cache_status = "MISS";
+                let points = FngHistory::new()
+                    .fetch_points(&state, days, chrono::Utc::now().date_naive())
+                    .await
+                    .map_err(|e| multi_tier_cache::CacheError::BackendError(e.to_string()))?;
+                Ok(FngHistoryResponse { days, points })
+            },
+        )
+        .await
+        .map_err(|e| Layer5Error::Database(e.to_string()))?;
+
+    Ok(([("x-cache", cache_status)], Json(response)))
+}
+
+/// Latest quote of every asset in the market data (see `MARKET_ASSETS`), in
+/// `?currency=` (USD by default)
+async fn api_assets(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<impl IntoResponse> {
+    let (currency, rate) = requested_currency(&state, &params).await?;
+    let mut cache_status = "HIT";
+    let mut response = state
+        .cache_manager
+        .get_or_compute_typed(
+            "api_crypto_assets",
+            state.cache_config.market_data.strategy(),
+            || async {
+                cache_status = "MISS";
+                let data = latest_market_data(&state).await?;
+                Ok(AssetsResponse {
+                    assets: asset_quotes(&data),
+                    last_updated: data
+                        .get("last_updated")
+                        .and_then(Value::as_str)
+                        .map(ToString::to_string),
+                })
+            },
+        )
+        .await
+        .map_err(|e| Layer5Error::Cache(e.to_string()))?;
+    for quote in &mut response.assets {
+        convert_quote(quote, currency, rate);
+    }
+    Ok(([("x-cache", cache_status)], Json(response)))
+}
+
+/// Latest quote of one asset, cached under its own key (404 for an unknown
+/// symbol or one the latest entry does not quote); `?currency=` as for the listing
+async fn api_asset(
+    State(state): State<Arc<AppState>>,
+    Path(symbol): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<impl IntoResponse> {
+    let asset = MarketAsset::find(&symbol)
+        .ok_or_else(|| Layer5Error::NotFound(format!("Unknown asset {symbol}")))?;
+    let (currency, rate) = requested_currency(&state, &params).await?;
+    let mut cache_status = "HIT";
+    let mut quote = state
+        .cache_manager
+        .get_or_compute_typed(
+            &asset.cache_key(),
+            state.cache_config.market_data.strategy(),
+            || async {
+                cache_status = "MISS";
+                Ok(asset.quote(&latest_market_data(&state).await?))
+            },
+        )
+        .await
+        .map_err(|e| Layer5Error::Cache(e.to_string()))?
+        .ok_or_else(|| Layer5Error::NotFound(format!("No quote for {}", asset.symbol)))?;
+    convert_quote(&mut quote, currency, rate);
+    Ok(([("x-cache", cache_status)], Json(quote)))
+}
+
+/// Latest `market_data` entry, for cache computations
+async fn latest_market_data(state: &AppState) -> Result<Value, multi_tier_cache::CacheError> {
+    state
+        .streams
+        .market_data()
+        .read_latest()
+        .await
+        .map_err(|e| multi_tier_cache::CacheError::BackendError(e.to_string()))?
+        .ok_or_else(|| multi_tier_cache::CacheError::BackendError("no market data yet".to_string()))
 }
 
 /// Dashboard summary API endpoint - Reads from Redis Stream via `RedisStreamReader`
-async fn api_dashboard_summary(state: State<Arc<AppState>>) -> impl IntoResponse {
-    api_dashboard_data(state).await
+async fn api_dashboard_summary(
+    state: State<Arc<AppState>>,
+    query: Query<HashMap<String, String>>,
+) -> Layer5Result<impl IntoResponse> {
+    api_dashboard_data(state, query).await
+}
+
+/// Current dashboard metrics as a PNG card for chat / social embeds, rendered at
+/// most once per `snapshot` cache TTL
+///
+/// Fallback figures are never drawn: without market data the request fails.
+async fn api_dashboard_snapshot(
+    State(state): State<Arc<AppState>>,
+) -> Layer5Result<impl IntoResponse> {
+    if let Some(png) = try_get_cached_compressed(&state.cache_manager, SNAPSHOT_CACHE_KEY).await {
+        return Ok(([("content-type", "image/png"), ("x-cache", "HIT")], png));
+    }
+
+    let data = latest_market_data(&state)
+        .await
+        .map_err(|e| Layer5Error::Cache(e.to_string()))?;
+    let mut summary = dashboard_response(&data)
+        .ok_or_else(|| Layer5Error::Internal("Unreadable market data entry".to_string()))?;
+    stamp_freshness(&state, &mut summary).await;
+    let png =
+        tokio::task::spawn_blocking(move || render_dashboard_card(&summary, chrono::Utc::now()))
+            .await?
+            .map_err(|e| Layer5Error::Internal(format!("Snapshot encoding failed: {e}")))?;
+    cache_compressed_data(
+        &state.cache_manager,
+        SNAPSHOT_CACHE_KEY,
+        &png,
+        state.cache_config.snapshot.strategy(),
+        "dashboard snapshot",
+    )
+    .await;
+
+    Ok(([("content-type", "image/png"), ("x-cache", "MISS")], png))
 }
 
 /// API health check endpoint
 async fn api_health(State(state): State<Arc<AppState>>) -> Json<ApiHealthResponse> {
-    let is_healthy = state.health_check().await;
+    let is_healthy = state.health_check().await.is_healthy();
 
     let response = ApiHealthResponse {
         api: ApiHealthInfo {
@@ -130,9 +733,59 @@ async fn api_health(State(state): State<Arc<AppState>>) -> Json<ApiHealthRespons
     Json(response)
 }
 
+/// Default number of entries returned by the report audit endpoint
+const DEFAULT_REPORT_AUDIT_LIMIT: i64 = 50;
+
+/// Report modification audit trail - newest mutations first
+///
+/// Requires the `admin` scope or `ADMIN_TOKEN`. `?limit=` (default 50, at most 200)
+/// and `?offset=` page through older entries.
+async fn api_report_audit(
+    Path(id): Path<i32>,
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<Json<ReportAuditResponse>> {
+    authorize_admin(&headers)?;
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_REPORT_AUDIT_LIMIT)
+        .clamp(1, MAX_REPORT_AUDIT_PAGE_SIZE);
+    let offset = params
+        .get("offset")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(0)
+        .max(0);
+
+    let entries = ReportAuditService::new()
+        .fetch_for_report(&state, id, limit, offset)
+        .await?
+        .into_iter()
+        .map(|entry| ReportAuditEntryResponse {
+            id: entry.id,
+            action: entry.action,
+            api_key_id: entry.api_key_id,
+            actor: entry.actor,
+            changed_fields: entry.changed_fields,
+            changes_hash: entry.changes_hash,
+            request_id: entry.request_id,
+            created_at: entry.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(Json(ReportAuditResponse {
+        report_id: id,
+        limit,
+        offset,
+        entries,
+    }))
+}
+
 /// Sandboxed report content API endpoint
 ///
-/// Serves sanitized HTML content for iframe embedding with security headers
+/// Serves sanitized HTML content for iframe embedding with security headers.
+/// Requires a signed, unexpired URL (`expires` / `sig`) besides the sandbox token.
 async fn api_sandboxed_report(
     Path(id): Path<String>,
     Query(params): Query<HashMap<String, String>>,
@@ -150,6 +803,22 @@ async fn api_sandboxed_report(
         return "Invalid report ID format".into_response();
     };
 
+    // Signed URL: the path and expiry must carry a valid signature, so copied iframe
+    // URLs stop working once they expire
+    let signed = params
+        .get("expires")
+        .zip(params.get("sig"))
+        .is_some_and(|(expires, sig)| {
+            verify_sandbox_url(&sandboxed_report_path(&id), expires, sig)
+        });
+    if !signed {
+        warn!(
+            "❌ [API] Invalid or expired sandbox URL for report {}",
+            report_id
+        );
+        return build_forbidden_response("Invalid or expired sandbox URL");
+    }
+
     // Get sandbox token from query parameters
     let Some(sandbox_token) = params.get("token") else {
         warn!("❌ [API] Missing sandbox token for report {}", report_id);
@@ -283,3 +952,60 @@ async fn api_websocket_stats(State(_state): State<Arc<AppState>>) -> Json<WebSoc
 
     Json(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_summary_delta() {
+        let before = json!({"btc_price_usd": 45000.0, "fng_value": 50, "note": "fallback"});
+        let after = json!({"btc_price_usd": 45100.0, "fng_value": 50});
+        let delta = summary_delta(&before, &after).unwrap_or_default();
+        assert_eq!(
+            Value::Object(delta),
+            json!({"btc_price_usd": 45100.0, "note": null})
+        );
+        assert!(summary_delta(&after, &after).is_none());
+    }
+
+    #[test]
+    fn test_dashboard_summary_skips_other_entries() {
+        assert!(dashboard_summary(&json!({"symbol": "BTC"})).is_none());
+        let fallback = serde_json::to_value(get_fallback_dashboard_data()).unwrap_or_default();
+        let summary = dashboard_summary(&fallback).unwrap_or_default();
+        assert_eq!(summary.get("fng_value"), Some(&json!(50)));
+    }
+
+    #[test]
+    fn test_convert_dashboard() {
+        let mut response = get_fallback_dashboard_data();
+        response.assets = asset_quotes(
+            &json!({"btc_price_usd": 100.0, "dxy_value": 104.0, "gold_price_usd": 2400.0}),
+        );
+        response
+            .extra
+            .insert("gold_price_usd".to_string(), json!(2400.0));
+        response
+            .extra
+            .insert("usd_vnd_rate".to_string(), json!(25000.0));
+
+        convert_dashboard(&mut response, Currency::Eur, 0.5);
+        assert!((response.btc_price_usd - 48000.0).abs() < f64::EPSILON);
+        assert!((response.btc_change_24h).abs() < f64::EPSILON);
+        assert_eq!(response.currency.as_deref(), Some("EUR"));
+        assert_eq!(response.extra.get("gold_price_usd"), Some(&json!(1200.0)));
+        assert_eq!(response.extra.get("usd_vnd_rate"), Some(&json!(25000.0)));
+        let prices: Vec<(f64, Option<&str>)> = response
+            .assets
+            .iter()
+            .map(|quote| (quote.price, quote.currency.as_deref()))
+            .collect();
+        // The dollar index stays in points
+        assert_eq!(
+            prices,
+            [(50.0, Some("EUR")), (1200.0, Some("EUR")), (104.0, None)]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Author Routes Module
+//!
+//! Handles author attribution endpoints:
+//! - /author/{slug} - Author archive page with their reports and `ProfilePage` JSON-LD
+//! - /author/{slug}/rss.xml - Per-author RSS 2.0 feed
+//!
+//! ✅ OPTIMIZED: Compressed output cached in L1/L2 per author slug and language
+
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, header},
+    response::Response,
+    routing::get,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::services::crypto_reports::handlers::{CryptoHandlers, RenderedContent};
+use crate::services::crypto_reports::rendering::{format_related_reports, generate_author_json_ld};
+use crate::services::data_communication::{
+    AuthorData, AuthorDataService, CacheKeyBuilder, CacheRoute, DEFAULT_LANGUAGE,
+    is_valid_author_slug,
+};
+use crate::services::shared::{
+    LANGUAGE_VARY, RssCreator, build_standard_compressed_response, cache_compressed_data,
+    compress_data,
+    error::{Layer5Error, Layer5Result},
+    public_origin, try_get_cached_compressed,
+};
+use crate::state::AppState;
+
+/// Max reports listed on an author archive page
+const AUTHOR_ARCHIVE_LIMIT: i64 = 100;
+/// Max reports in a per-author RSS feed
+const AUTHOR_RSS_LIMIT: i64 = 20;
+
+/// Configure author routes
+pub fn configure_author_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/author/{slug}", get(author_archive))
+        .route("/author/{slug}/rss.xml", get(author_rss_feed))
+}
+
+/// Look up an author, rejecting malformed slugs before touching the database
+async fn find_author(state: &Arc<AppState>, slug: &str) -> Layer5Result<AuthorData> {
+    if !is_valid_author_slug(slug) {
+        return Err(Layer5Error::NotFound(format!("Author {slug}")));
+    }
+    AuthorDataService::new()
+        .fetch_author_by_slug(state, slug)
+        .await?
+        .ok_or_else(|| Layer5Error::NotFound(format!("Author {slug}")))
+}
+
+/// Author archive page, cached per language
+async fn author_archive(
+    Path(slug): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Layer5Result<RenderedContent> {
+    let language = CryptoHandlers::detect_preferred_language(&params, &headers)
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+    let cache_key = CacheKeyBuilder::new(CacheRoute::AuthorPage)
+        .segment(&slug)
+        .language(&language)
+        .build();
+    if let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, &cache_key).await {
+        debug!("⚡ [Route] Cache HIT for author page {}", slug);
+        return Ok(RenderedContent {
+            data: cached_data.into(),
+            cache_control: None,
+            cache_status: "HIT",
+            last_modified: None,
+        });
+    }
+
+    let author = find_author(&state, &slug).await?;
+    let reports = AuthorDataService::new()
+        .fetch_author_reports(&state, author.id, AUTHOR_ARCHIVE_LIMIT)
+        .await?;
+    info!(
+        "👤 [Route] Rendering author page {} ({} reports)",
+        slug,
+        reports.len()
+    );
+
+    let author_path = author.archive_path();
+    let author_description = author.bio.clone().unwrap_or_else(|| {
+        format!(
+            "Báo cáo phân tích thị trường crypto của {} trên CryptoDashboard",
+            author.name
+        )
+    });
+
+    let mut context = tera::Context::new();
+    context.insert("author", &author);
+    context.insert("current_lang", &language);
+    context.insert("author_path", &author_path);
+    context.insert("author_url", &format!("{}{author_path}", public_origin()));
+    context.insert("author_description", &author_description);
+    context.insert("author_json_ld", &generate_author_json_ld(&author));
+    context.insert("reports", &format_related_reports(&reports));
+
+    let html = state
+        .tera
+        .render("crypto/routes/authors/archive.html", &context)?;
+    let compressed_data =
+        compress_data(&html).map_err(|e| Layer5Error::Compression(e.to_string()))?;
+
+    cache_compressed_data(
+        &state.cache_manager,
+        &cache_key,
+        &compressed_data,
+        state.cache_config.reports_list.strategy(),
+        "author page",
+    )
+    .await;
+
+    Ok(RenderedContent {
+        data: compressed_data.into(),
+        cache_control: None,
+        cache_status: "MISS",
+        last_modified: None,
+    })
+}
+
+/// Per-author RSS 2.0 feed
+///
+/// Uses the `rss` cache policy and per-language keys like the site-wide feed.
+async fn author_rss_feed(
+    Path(slug): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Layer5Result<Response> {
+    let language = CryptoHandlers::detect_preferred_language(&params, &headers)
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+    let cache_key = CacheKeyBuilder::new(CacheRoute::AuthorRss)
+        .segment(&slug)
+        .language(&language)
+        .build();
+    if let Some(cached_bytes) = try_get_cached_compressed(&state.cache_manager, &cache_key).await {
+        info!("🔥 RSS: Cache HIT for author {}", slug);
+        return Ok(with_language_vary(build_standard_compressed_response(
+            cached_bytes,
+            "application/rss+xml; charset=utf-8",
+            "HIT",
+        )));
+    }
+
+    let author = find_author(&state, &slug).await?;
+    let reports = AuthorDataService::new()
+        .fetch_author_rss_reports(&state, author.id, AUTHOR_RSS_LIMIT)
+        .await?;
+
+    let xml = RssCreator::generate_author_rss_xml(&author, &reports, &language)?;
+    let compressed_data =
+        compress_data(&xml).map_err(|e| Layer5Error::Compression(e.to_string()))?;
+
+    cache_compressed_data(
+        &state.cache_manager,
+        &cache_key,
+        &compressed_data,
+        state.cache_config.rss.strategy(),
+        "author RSS feed",
+    )
+    .await;
+
+    Ok(with_language_vary(build_standard_compressed_response(
+        compressed_data,
+        "application/rss+xml; charset=utf-8",
+        "MISS",
+    )))
+}
+
+/// Mark a localized feed response as varying by visitor language
+fn with_language_vary(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert(header::VARY, HeaderValue::from_static(LANGUAGE_VARY));
+    response
+}
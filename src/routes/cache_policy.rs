@@ -0,0 +1,420 @@
+//! Per-Route Cache Policy
+//!
+//! Declarative table of the cache headers each public route gets, applied by one
+//! middleware instead of every handler hard-coding its own `max-age`:
+//! - `Cache-Control`: `max-age` for browsers and `s-maxage` for shared caches (CDN),
+//!   both taken from the route's `CacheConfig` class
+//! - `Surrogate-Key`: purge tags such as `report-42`, so a CDN can drop every cached
+//!   variant of one report at once
+//!
+//! Only successful (2xx) and `304` responses to `GET`/`HEAD` are touched, and a
+//! `Cache-Control` set by the handler itself (e.g. stale copies) is kept.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{HeaderName, HeaderValue, Method, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+use crate::cache_config::{CacheConfig, CachePolicy};
+use crate::services::shared::response_builder::cache_control::NO_CACHE;
+use crate::state::AppState;
+
+/// `Surrogate-Key` response header (Fastly / Varnish style purge tags)
+pub const SURROGATE_KEY: HeaderName = HeaderName::from_static("surrogate-key");
+
+/// Cache lifetime class of a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheClass {
+    MarketData,
+    Report,
+    ReportsList,
+    Homepage,
+    Sitemap,
+    Rss,
+    Snapshot,
+    /// Operator endpoints: never stored
+    NoStore,
+}
+
+impl CacheClass {
+    fn policy(self, config: &CacheConfig) -> Option<&CachePolicy> {
+        match self {
+            Self::MarketData => Some(&config.market_data),
+            Self::Report => Some(&config.report),
+            Self::ReportsList => Some(&config.reports_list),
+            Self::Homepage => Some(&config.homepage),
+            Self::Sitemap => Some(&config.sitemap),
+            Self::Rss => Some(&config.rss),
+            Self::Snapshot => Some(&config.snapshot),
+            Self::NoStore => None,
+        }
+    }
+}
+
+/// Cache headers of one route
+#[derive(Debug)]
+pub struct RouteCachePolicy {
+    /// Route path as registered, e.g. `/crypto_report/{id}`
+    pub path: &'static str,
+    pub class: CacheClass,
+    /// Surrogate keys; `{param}` is replaced by the path parameter
+    pub surrogate_keys: &'static [&'static str],
+}
+
+const fn route(
+    path: &'static str,
+    class: CacheClass,
+    surrogate_keys: &'static [&'static str],
+) -> RouteCachePolicy {
+    RouteCachePolicy {
+        path,
+        class,
+        surrogate_keys,
+    }
+}
+
+/// Cache policy of every route that has one
+pub const ROUTE_CACHE_POLICIES: &[RouteCachePolicy] = &[
+    route("/", CacheClass::Homepage, &["homepage"]),
+    route(
+        "/crypto_report",
+        CacheClass::Report,
+        &["reports", "report-latest"],
+    ),
+    route(
+        "/crypto_report/{id}",
+        CacheClass::Report,
+        &["reports", "report-{id}"],
+    ),
+    route(
+        "/crypto_reports_list",
+        CacheClass::ReportsList,
+        &["reports", "reports-list"],
+    ),
+    route(
+        "/author/{slug}",
+        CacheClass::ReportsList,
+        &["authors", "author-{slug}"],
+    ),
+    route(
+        "/author/{slug}/rss.xml",
+        CacheClass::Rss,
+        &["feeds", "author-{slug}"],
+    ),
+    route("/rss.xml", CacheClass::Rss, &["feeds"]),
+    route("/rss", CacheClass::Rss, &["feeds"]),
+    route("/rss/{variant}", CacheClass::Rss, &["feeds"]),
+    route("/rss/{lang}/{tag}", CacheClass::Rss, &["feeds"]),
+    route("/atom.xml", CacheClass::Rss, &["feeds"]),
+    route("/feed.json", CacheClass::Rss, &["feeds"]),
+    route("/sitemap.xml", CacheClass::Sitemap, &["sitemap"]),
+    route("/sitemaps/{file}", CacheClass::Sitemap, &["sitemap"]),
+    route("/news-sitemap.xml", CacheClass::Sitemap, &["sitemap"]),
+    route("/robots.txt", CacheClass::Sitemap, &["sitemap"]),
+    route("/llms.txt", CacheClass::Sitemap, &["sitemap"]),
+    route("/llms-full.txt", CacheClass::Sitemap, &["sitemap"]),
+    route("/indexnow.txt", CacheClass::Sitemap, &["sitemap"]),
+    route(
+        "/api/crypto/dashboard-summary",
+        CacheClass::MarketData,
+        &["market-data"],
+    ),
+    route(
+        "/api/dashboard/data",
+        CacheClass::MarketData,
+        &["market-data"],
+    ),
+    route(
+        "/api/crypto/dashboard-summary/snapshot.png",
+        CacheClass::Snapshot,
+        &["market-data"],
+    ),
+    route(
+        "/api/crypto/dashboard-summary/stream",
+        CacheClass::NoStore,
+        &[],
+    ),
+    route("/api/crypto/stream", CacheClass::NoStore, &[]),
+    route(
+        "/api/crypto/history",
+        CacheClass::MarketData,
+        &["market-data"],
+    ),
+    route("/api/crypto/ohlc", CacheClass::MarketData, &["market-data"]),
+    route(
+        "/api/crypto/fng/history",
+        CacheClass::MarketData,
+        &["market-data"],
+    ),
+    route(
+        "/api/crypto/assets",
+        CacheClass::MarketData,
+        &["market-data"],
+    ),
+    route(
+        "/api/crypto/assets/{symbol}",
+        CacheClass::MarketData,
+        &["market-data"],
+    ),
+    route("/api/subscribe", CacheClass::NoStore, &[]),
+    route("/api/subscribe/confirm", CacheClass::NoStore, &[]),
+    route("/api/unsubscribe", CacheClass::NoStore, &[]),
+    route("/api/crypto/reports/{id}/audit", CacheClass::NoStore, &[]),
+    route("/api/health", CacheClass::NoStore, &[]),
+    route("/api/websocket/stats", CacheClass::NoStore, &[]),
+    route("/health", CacheClass::NoStore, &[]),
+    route("/health/live", CacheClass::NoStore, &[]),
+    route("/health/ready", CacheClass::NoStore, &[]),
+    route("/health/details", CacheClass::NoStore, &[]),
+    route("/metrics", CacheClass::NoStore, &[]),
+    route("/version", CacheClass::NoStore, &[]),
+    route("/admin/cache/clear", CacheClass::NoStore, &[]),
+    route("/admin/cache/purge", CacheClass::NoStore, &[]),
+    route("/admin/cache/stats", CacheClass::NoStore, &[]),
+    route("/admin/reports/oversized", CacheClass::NoStore, &[]),
+    route("/admin/diagnostics", CacheClass::NoStore, &[]),
+    route("/admin/perf", CacheClass::NoStore, &[]),
+    route("/admin/slo", CacheClass::NoStore, &[]),
+    route("/admin/streams/replay", CacheClass::NoStore, &[]),
+    route("/admin/audit", CacheClass::NoStore, &[]),
+    route("/admin/redirects", CacheClass::NoStore, &[]),
+    route("/admin/redirects/{id}", CacheClass::NoStore, &[]),
+    route("/admin/debug/tasks", CacheClass::NoStore, &[]),
+    route("/admin/debug/profile", CacheClass::NoStore, &[]),
+    route("/admin/debug/heap", CacheClass::NoStore, &[]),
+];
+
+/// Policy registered for a route path
+#[must_use]
+pub fn route_policy(route_path: &str) -> Option<&'static RouteCachePolicy> {
+    ROUTE_CACHE_POLICIES
+        .iter()
+        .find(|policy| policy.path == route_path)
+}
+
+impl RouteCachePolicy {
+    /// `Cache-Control` value under `config`
+    #[must_use]
+    pub fn cache_control(&self, config: &CacheConfig) -> String {
+        self.class.policy(config).map_or_else(
+            || NO_CACHE.to_string(),
+            |policy| {
+                format!(
+                    "{}, s-maxage={}",
+                    policy.cache_control(),
+                    policy.ttl().as_secs()
+                )
+            },
+        )
+    }
+
+    /// Space-separated surrogate keys for a request path (`None` when the route has none)
+    #[must_use]
+    pub fn surrogate_key(&self, request_path: &str) -> Option<String> {
+        if self.surrogate_keys.is_empty() {
+            return None;
+        }
+        let params: Vec<(&str, &str)> = self
+            .path
+            .split('/')
+            .zip(request_path.split('/'))
+            .filter_map(|(template, value)| {
+                template
+                    .strip_prefix('{')
+                    .and_then(|name| name.strip_suffix('}'))
+                    // Report ids may carry a slug (`42-some-slug`); keys use the id alone
+                    .map(|name| match (name, value.split_once('-')) {
+                        ("id", Some((id, _))) => (name, id),
+                        _ => (name, value),
+                    })
+            })
+            .collect();
+
+        let keys: Vec<String> = self
+            .surrogate_keys
+            .iter()
+            .map(|key| {
+                params
+                    .iter()
+                    .fold((*key).to_string(), |key, (name, value)| {
+                        key.replace(&format!("{{{name}}}"), value)
+                    })
+            })
+            .collect();
+        Some(keys.join(" "))
+    }
+}
+
+/// Middleware attaching the matched route's cache headers
+///
+/// Installed with `Router::route_layer` so the matched route path is known.
+pub async fn apply_cache_policy(
+    State(state): State<Arc<AppState>>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let cacheable_method = matches!(*request.method(), Method::GET | Method::HEAD);
+    let request_path = request.uri().path().to_string();
+    let mut response = next.run(request).await;
+
+    let Some(policy) = matched_path
+        .filter(|_| cacheable_method)
+        .and_then(|matched| route_policy(matched.as_str()))
+    else {
+        return response;
+    };
+    let status = response.status();
+    if !(status.is_success() || status == StatusCode::NOT_MODIFIED) {
+        return response;
+    }
+
+    let headers = response.headers_mut();
+    if !headers.contains_key(header::CACHE_CONTROL)
+        && let Ok(value) = HeaderValue::from_str(&policy.cache_control(state.cache_config))
+    {
+        headers.insert(header::CACHE_CONTROL, value);
+    }
+    if let Some(value) = policy
+        .surrogate_key(&request_path)
+        .and_then(|keys| HeaderValue::from_str(&keys).ok())
+    {
+        headers.insert(SURROGATE_KEY, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_control_from_config() {
+        let config = CacheConfig::default();
+        let report = route_policy("/crypto_report/{id}");
+        assert_eq!(
+            report.map(|policy| policy.cache_control(&config)),
+            Some("public, max-age=300, s-maxage=300".to_string())
+        );
+        let list = route_policy("/crypto_reports_list");
+        assert_eq!(
+            list.map(|policy| policy.cache_control(&config)),
+            Some("public, max-age=60, s-maxage=300".to_string())
+        );
+        let admin = route_policy("/admin/diagnostics");
+        assert_eq!(
+            admin.map(|policy| policy.cache_control(&config)),
+            Some(NO_CACHE.to_string())
+        );
+        assert!(route_policy("/api/mobile/v1/feed").is_none());
+    }
+
+    #[test]
+    fn test_surrogate_keys_fill_path_params() {
+        let report = route_policy("/crypto_report/{id}");
+        assert_eq!(
+            report.and_then(|policy| policy.surrogate_key("/crypto_report/42")),
+            Some("reports report-42".to_string())
+        );
+        assert_eq!(
+            report
+                .and_then(|policy| policy.surrogate_key("/crypto_report/42-phan-tich-10-03-2024")),
+            Some("reports report-42".to_string())
+        );
+        let author_feed = route_policy("/author/{slug}/rss.xml");
+        assert_eq!(
+            author_feed.and_then(|policy| policy.surrogate_key("/author/jane-doe/rss.xml")),
+            Some("feeds author-jane-doe".to_string())
+        );
+        let health = route_policy("/health");
+        assert_eq!(
+            health.and_then(|policy| policy.surrogate_key("/health")),
+            None
+        );
+    }
+
+    /// Routes whose handlers set their own `Cache-Control`
+    const HANDLER_MANAGED_ROUTES: &[&str] = &[
+        "/api/crypto_reports/{id}/sandboxed",
+        "/api/crypto_reports/{id}/shadow_dom",
+        "/api/mobile/v1/feed",
+    ];
+
+    /// Path of every `.route(...)` registered by the route modules
+    fn registered_routes() -> Vec<String> {
+        let sources = [
+            include_str!("api.rs"),
+            include_str!("authors.rs"),
+            include_str!("crypto_reports.rs"),
+            include_str!("debug.rs"),
+            include_str!("homepage.rs"),
+            include_str!("mobile.rs"),
+            include_str!("notifications.rs"),
+            include_str!("rss_feed.rs"),
+            include_str!("seo.rs"),
+            include_str!("system.rs"),
+        ];
+        let constants = [
+            ("CONFIRM_PATH", crate::services::notifications::CONFIRM_PATH),
+            (
+                "UNSUBSCRIBE_PATH",
+                crate::services::notifications::UNSUBSCRIBE_PATH,
+            ),
+            (
+                "INDEXNOW_KEY_PATH",
+                crate::services::shared::INDEXNOW_KEY_PATH,
+            ),
+        ];
+        sources
+            .iter()
+            .flat_map(|source| source.split(".route(").skip(1))
+            .map(|call| {
+                let argument = call.trim_start();
+                if let Some(literal) = argument.strip_prefix('"') {
+                    literal.split('"').next().unwrap_or_default().to_string()
+                } else {
+                    let name: String = argument
+                        .chars()
+                        .take_while(|c| c.is_ascii_uppercase() || *c == '_')
+                        .collect();
+                    constants
+                        .iter()
+                        .find(|(constant, _)| *constant == name)
+                        .map_or_else(
+                            || panic!("unknown route path constant `{name}`"),
+                            |(_, path)| (*path).to_string(),
+                        )
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_registered_route_has_a_policy() {
+        let routes = registered_routes();
+        assert!(routes.iter().any(|path| path == "/admin/redirects/{id}"));
+        for path in routes {
+            assert!(
+                route_policy(&path).is_some() || HANDLER_MANAGED_ROUTES.contains(&path.as_str()),
+                "no cache policy for {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_table_has_unique_paths() {
+        for (index, policy) in ROUTE_CACHE_POLICIES.iter().enumerate() {
+            assert!(
+                ROUTE_CACHE_POLICIES
+                    .iter()
+                    .skip(index + 1)
+                    .all(|other| other.path != policy.path),
+                "duplicate cache policy for {}",
+                policy.path
+            );
+        }
+    }
+}
@@ -2,19 +2,31 @@
 //!
 //! This module defines all HTTP routes for crypto reports functionality including
 //! report viewing and listing.
+//!
+//! Reports are served at `/crypto_report/{id}-{slug}`; bare-ID and outdated slug
+//! URLs answer 301 to that canonical form.
 
 use axum::{
     Router,
-    extract::{Path, Query, State},
-    http::HeaderMap,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode, Uri, header},
+    response::{IntoResponse, Response},
     routing::get,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::debug;
+use std::sync::atomic::Ordering;
+use tracing::{debug, info, warn};
 
+use crate::dto::requests::{ReportPageQuery, ReportsListQuery};
 use crate::services::crypto_reports::handlers::{CryptoHandlers, RenderedContent};
-use crate::services::shared::{error::Layer5Result, try_get_cached_compressed};
+use crate::services::data_communication::{
+    CryptoDataService, DEFAULT_LANGUAGE, ReportRoute, parse_report_segment,
+};
+use crate::services::shared::{
+    ValidatedQuery, cache_bypass_requested, cache_only_miss, error::Layer5Result,
+    try_get_cached_compressed,
+};
 use crate::state::AppState;
 
 /// Configure crypto reports routes
@@ -25,67 +37,134 @@ pub fn configure_crypto_reports_routes() -> Router<Arc<AppState>> {
         .route("/crypto_reports_list", get(crypto_reports_list))
 }
 
-/// List all crypto reports with pagination
+/// List all crypto reports with pagination (cached per language)
 async fn crypto_reports_list(
-    Query(params): Query<HashMap<String, String>>,
+    ValidatedQuery(query): ValidatedQuery<ReportsListQuery>,
     State(state): State<Arc<AppState>>,
-) -> Layer5Result<RenderedContent> {
+    headers: HeaderMap,
+) -> Layer5Result<Response> {
     debug!("🚀 [Route] crypto_reports_list called - fetching from Service Islands Layer 5");
 
-    // Parse pagination parameter
-    let page: i64 = params.get("page").and_then(|p| p.parse().ok()).unwrap_or(1);
+    let page = query.page;
     debug!("📄 [Route] Requesting page: {}", page);
 
+    let language = CryptoHandlers::detect_preferred_language(&query.params(), &headers)
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+
+    // 🚧 Admin cache bypass: skip cached and stale copies, render fresh
+    if cache_bypass_requested(&headers) {
+        return state
+            .crypto_handlers
+            .crypto_reports_list_with_tera(&state, page, &language, true)
+            .await
+            .map(IntoResponse::into_response);
+    }
+
     // ⚡ IMMEDIATE CACHE CHECK: Optimized pagination caching
-    let cache_key = format!("crypto_reports_list_page_{page}_compressed");
+    let cache_key = CryptoDataService::reports_list_cache_key(page, &language);
     if let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, &cache_key).await {
         debug!("⚡ [Route] Cache HIT for reports list page {}", page);
         return Ok(RenderedContent {
-            data: cached_data,
-            cache_control: "public, max-age=60",
+            data: cached_data.into(),
+            cache_control: None,
             cache_status: "HIT",
+            last_modified: None,
+        }
+        .into_response());
+    }
+
+    // ♻️ STALE-WHILE-REVALIDATE: serve the expired copy and re-render in the background
+    if let Some(stale_data) = state.stale_cache.get(&cache_key).await {
+        let refresh_state = Arc::clone(&state);
+        let refresh_language = language.clone();
+        state.stale_cache.spawn_refresh(&cache_key, async move {
+            if let Err(e) = refresh_state
+                .crypto_handlers
+                .crypto_reports_list_with_tera(&refresh_state, page, &refresh_language, false)
+                .await
+            {
+                warn!(
+                    "⚠️ [Route] Background refresh of reports list page {} failed: {}",
+                    page, e
+                );
+            }
         });
+        info!("♻️ [Route] Serving stale reports list page {}", page);
+        return Ok(RenderedContent {
+            data: stale_data.into(),
+            cache_control: None,
+            cache_status: "STALE",
+            last_modified: None,
+        }
+        .into_response());
+    }
+
+    // 🕷️ Cache-only crawlers never trigger renders
+    if let Some(response) = cache_only_miss() {
+        return Ok(response);
     }
 
     // Use Service Islands architecture to get reports list (compressed)
     state
         .crypto_handlers
-        .crypto_reports_list_with_tera(&state, page)
+        .crypto_reports_list_with_tera(&state, page, &language, false)
         .await
+        .map(IntoResponse::into_response)
 }
 
 /// Crypto reports index page using Declarative Shadow DOM
 /// Modern primary route for crypto reports
 /// ✅ OPTIMIZED: Full caching support with language-specific cache keys
+/// ♻️ Honors `If-Modified-Since` via the report's `Last-Modified` timestamp
 async fn crypto_index(
     State(state): State<Arc<AppState>>,
-    Query(params): Query<HashMap<String, String>>,
+    ValidatedQuery(query): ValidatedQuery<ReportPageQuery>,
     headers: HeaderMap,
-) -> Layer5Result<RenderedContent> {
-    debug!("🌓 [Route] crypto_index called - delegating to Service Islands Layer 5");
-
-    // Check if specific report ID is requested via query param
-    let report_id = params.get("id");
-    let report_id_value = if let Some(id_str) = report_id {
-        if let Ok(id) = id_str.parse::<i32>() {
-            id
-        } else {
-            return Err(crate::services::shared::error::Layer5Error::InvalidInput(
-                format!("Invalid report ID format: {id_str}"),
-            ));
-        }
+) -> Layer5Result<Response> {
+    let response = index_page(&state, &query, &headers).await?;
+    // Resolved after rendering, which records the latest report id
+    let report_id = query
+        .id
+        .unwrap_or_else(|| state.cached_latest_id.load(Ordering::Relaxed));
+    let route = if report_id > 0 {
+        lookup_report_route(&state, report_id).await
     } else {
-        -1 // Latest report
+        None
     };
+    Ok(with_robots_header(response, route.as_ref()))
+}
+
+/// Latest report (or `?id=`) page, from cache or freshly rendered
+async fn index_page(
+    state: &Arc<AppState>,
+    query: &ReportPageQuery,
+    headers: &HeaderMap,
+) -> Layer5Result<Response> {
+    debug!("🌓 [Route] crypto_index called - delegating to Service Islands Layer 5");
+
+    // Specific report requested via ?id=, else the latest (-1)
+    let report_id_value = query.id.unwrap_or(-1);
+    let params = query.params();
 
     // ⚡ IMMEDIATE CACHE CHECK: Language-aware DSD caching
     // 1. Detect language (default to "vi")
-    let preferred_language = CryptoHandlers::detect_preferred_language(&params, &headers)
+    let preferred_language = CryptoHandlers::detect_preferred_language(&params, headers)
         .unwrap_or_else(|| "vi".to_string());
 
-    // 2. Check cache immediately
-    let cache_key = format!("compressed_report_dsd_{report_id_value}_{preferred_language}");
-    if let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, &cache_key).await {
+    // 2. Check cache immediately (keyed by render strategy variant)
+    let strategies = &state.crypto_handlers.report_creator.render_strategies;
+    let cache_variant = strategies.cache_variant(strategies.select(&params, headers).as_ref());
+    let cache_key = CryptoDataService::report_dsd_cache_key(
+        report_id_value,
+        &preferred_language,
+        cache_variant,
+    );
+    let data_service = &state.crypto_handlers.report_creator.data_service;
+    // 🚧 Admin cache bypass: the handler renders fresh, skipping cached and stale copies
+    let bypass_cache = cache_bypass_requested(headers);
+    if !bypass_cache
+        && let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, &cache_key).await
+    {
         debug!(
             "⚡ [Route] DSD cache HIT for report {} (lang: {})",
             if report_id_value == -1 {
@@ -96,83 +175,271 @@ async fn crypto_index(
             preferred_language
         );
 
+        let last_modified = data_service
+            .get_report_last_modified(state, report_id_value)
+            .await;
+
         return Ok(RenderedContent {
-            data: cached_data,
-            cache_control: "public, max-age=300",
+            data: cached_data.into(),
+            cache_control: None,
             cache_status: "HIT",
-        });
+            last_modified,
+        }
+        .into_conditional_response(headers));
+    }
+
+    // ♻️ STALE-WHILE-REVALIDATE: serve the expired copy and re-render in the background
+    let report_id_opt = (report_id_value != -1).then_some(report_id_value);
+    if !bypass_cache
+        && let Some(response) =
+            serve_stale_report(state, &cache_key, &params, headers, report_id_opt).await
+    {
+        return Ok(response);
+    }
+
+    // 🕷️ Cache-only crawlers never trigger renders
+    if let Some(response) = cache_only_miss() {
+        return Ok(response);
     }
 
     // Get chart modules content
     let chart_modules_content = state
         .crypto_handlers
         .report_creator
-        .get_chart_modules_content(&state);
+        .get_chart_modules_content(state);
 
     // Delegate to handlers
     state
         .crypto_handlers
         .render_crypto_index_dsd(
-            &state,
+            state,
             &params,
-            &headers,
+            headers,
             chart_modules_content,
-            if report_id_value == -1 {
-                None
-            } else {
-                Some(report_id_value)
-            },
+            report_id_opt,
         )
         .await
+        .map(|content| content.into_conditional_response(headers))
 }
 
 /// View specific crypto report by ID using Declarative Shadow DOM
 /// Modern primary route for viewing specific reports
 /// ✅ OPTIMIZED: Full caching support with language-specific cache keys
+/// ♻️ Honors `If-Modified-Since` via the report's `Last-Modified` timestamp
 async fn crypto_view_report(
     Path(id): Path<String>,
     State(state): State<Arc<AppState>>,
-    Query(params): Query<HashMap<String, String>>,
+    ValidatedQuery(query): ValidatedQuery<ReportPageQuery>,
+    uri: Uri,
     headers: HeaderMap,
-) -> Layer5Result<RenderedContent> {
+) -> Layer5Result<Response> {
     debug!("🌓 [Route] crypto_view_report called for ID: {}", id);
 
-    // Parse report ID
-    let report_id: i32 = id.parse().map_err(|_| {
+    // Parse report ID and optional slug (`123` or `123-some-slug`)
+    let (report_id, slug) = parse_report_segment(&id).ok_or_else(|| {
         crate::services::shared::error::Layer5Error::InvalidInput(format!(
             "Invalid report ID format: {id}"
         ))
     })?;
 
+    // 🔗 Bare-ID and outdated slug URLs move to the canonical slug URL
+    let route = lookup_report_route(&state, report_id).await;
+    if let Some(response) = redirect_to_canonical_slug(route.as_ref(), report_id, slug, uri.query())
+    {
+        return Ok(response);
+    }
+    let response = report_page(&state, report_id, &query.params(), &headers).await?;
+    Ok(with_robots_header(response, route.as_ref()))
+}
+
+/// Report page, from cache or freshly rendered
+async fn report_page(
+    state: &Arc<AppState>,
+    report_id: i32,
+    params: &HashMap<String, String>,
+    headers: &HeaderMap,
+) -> Layer5Result<Response> {
     // ⚡ IMMEDIATE CACHE CHECK: Language-aware DSD caching
     // 1. Detect language (default to "vi")
-    let preferred_language = CryptoHandlers::detect_preferred_language(&params, &headers)
+    let preferred_language = CryptoHandlers::detect_preferred_language(params, headers)
         .unwrap_or_else(|| "vi".to_string());
 
-    // 2. Check cache immediately
-    let cache_key = format!("compressed_report_dsd_{report_id}_{preferred_language}");
-    if let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, &cache_key).await {
+    // 2. Check cache immediately (keyed by render strategy variant)
+    let strategies = &state.crypto_handlers.report_creator.render_strategies;
+    let cache_variant = strategies.cache_variant(strategies.select(params, headers).as_ref());
+    let cache_key =
+        CryptoDataService::report_dsd_cache_key(report_id, &preferred_language, cache_variant);
+    let data_service = &state.crypto_handlers.report_creator.data_service;
+    // 🚧 Admin cache bypass: the handler renders fresh, skipping cached and stale copies
+    let bypass_cache = cache_bypass_requested(headers);
+    if !bypass_cache
+        && let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, &cache_key).await
+    {
         debug!(
             "⚡ [Route] DSD cache HIT for report #{} (lang: {})",
             report_id, preferred_language
         );
 
+        let last_modified = data_service
+            .get_report_last_modified(state, report_id)
+            .await;
+
         return Ok(RenderedContent {
-            data: cached_data,
-            cache_control: "public, max-age=300",
+            data: cached_data.into(),
+            cache_control: None,
             cache_status: "HIT",
-        });
+            last_modified,
+        }
+        .into_conditional_response(headers));
+    }
+
+    // ♻️ STALE-WHILE-REVALIDATE: serve the expired copy and re-render in the background
+    if !bypass_cache
+        && let Some(response) =
+            serve_stale_report(state, &cache_key, params, headers, Some(report_id)).await
+    {
+        return Ok(response);
+    }
+
+    // 🕷️ Cache-only crawlers never trigger renders
+    if let Some(response) = cache_only_miss() {
+        return Ok(response);
     }
 
     // Get chart modules content
     let chart_modules_content = state
         .crypto_handlers
         .report_creator
-        .get_chart_modules_content(&state);
+        .get_chart_modules_content(state);
 
     // Delegate to handlers
     state
         .crypto_handlers
-        .render_crypto_report_dsd(&state, report_id, &params, &headers, chart_modules_content)
+        .render_crypto_report_dsd(state, report_id, params, headers, chart_modules_content)
         .await
+        .map(|content| content.into_conditional_response(headers))
+}
+
+/// Slug and indexing flag of a report; `None` for unknown reports and lookup failures
+async fn lookup_report_route(state: &Arc<AppState>, report_id: i32) -> Option<ReportRoute> {
+    match state
+        .crypto_handlers
+        .report_creator
+        .data_service
+        .fetch_report_route(state, report_id)
+        .await
+    {
+        Ok(route) => route,
+        Err(e) => {
+            warn!(
+                "⚠️ [Route] Slug lookup failed for report #{}: {}",
+                report_id, e
+            );
+            None
+        }
+    }
+}
+
+/// Add `X-Robots-Tag: noindex, nofollow` to the page of a report that is not indexable
+fn with_robots_header(mut response: Response, route: Option<&ReportRoute>) -> Response {
+    if route.is_some_and(|route| !route.indexable) {
+        response.headers_mut().insert(
+            HeaderName::from_static("x-robots-tag"),
+            HeaderValue::from_static("noindex, nofollow"),
+        );
+    }
+    response
+}
+
+/// 301 to `/crypto_report/{id}-{slug}` unless `slug` is already the report's slug
+///
+/// Unknown reports and lookup failures (`route` is `None`) fall through to the
+/// handler, which answers 404 or renders as usual.
+fn redirect_to_canonical_slug(
+    route: Option<&ReportRoute>,
+    report_id: i32,
+    slug: Option<&str>,
+    query: Option<&str>,
+) -> Option<Response> {
+    let canonical = &route?.slug;
+    if slug == Some(canonical.as_str()) {
+        return None;
+    }
+
+    let mut location = format!("/crypto_report/{report_id}-{canonical}");
+    if let Some(query) = query.filter(|query| !query.is_empty()) {
+        location.push('?');
+        location.push_str(query);
+    }
+    debug!(
+        "🔗 [Route] Redirecting report #{} to {}",
+        report_id, location
+    );
+    Some(
+        (
+            StatusCode::MOVED_PERMANENTLY,
+            [(header::LOCATION, location)],
+        )
+            .into_response(),
+    )
+}
+
+/// Serve the stale copy of an expired DSD report page, if any
+///
+/// Spawns (at most one per cache key) a background re-render that refreshes both
+/// the fresh entry and the stale copy.
+async fn serve_stale_report(
+    state: &Arc<AppState>,
+    cache_key: &str,
+    params: &HashMap<String, String>,
+    headers: &HeaderMap,
+    report_id_opt: Option<i32>,
+) -> Option<Response> {
+    let stale_data = state.stale_cache.get(cache_key).await?;
+
+    let refresh_state = Arc::clone(state);
+    let refresh_params = params.clone();
+    let refresh_headers = headers.clone();
+    let refresh_key = cache_key.to_string();
+    state.stale_cache.spawn_refresh(cache_key, async move {
+        let chart_modules_content = refresh_state
+            .crypto_handlers
+            .report_creator
+            .get_chart_modules_content(&refresh_state);
+        if let Err(e) = refresh_state
+            .crypto_handlers
+            .render_crypto_index_dsd(
+                &refresh_state,
+                &refresh_params,
+                &refresh_headers,
+                chart_modules_content,
+                report_id_opt,
+            )
+            .await
+        {
+            warn!(
+                "⚠️ [Route] Background refresh of {} failed: {}",
+                refresh_key, e
+            );
+        }
+    });
+
+    info!("♻️ [Route] Serving stale copy of {}", cache_key);
+    let last_modified = state
+        .crypto_handlers
+        .report_creator
+        .data_service
+        .get_report_last_modified(state, report_id_opt.unwrap_or(-1))
+        .await;
+
+    Some(
+        RenderedContent {
+            data: stale_data.into(),
+            // Short browser lifetime for stale copies, independent of the report policy
+            cache_control: Some("public, max-age=60"),
+            cache_status: "STALE",
+            last_modified,
+        }
+        .into_conditional_response(headers),
+    )
 }
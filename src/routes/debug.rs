@@ -0,0 +1,64 @@
+//! Runtime Profiling Routes (`profiling` feature)
+//!
+//! Admin-only views of the Tokio runtime and process memory, see
+//! `services::health_system::profiling`.
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Json,
+    routing::get,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+use crate::dto::responses::{CpuProfileResponse, HeapSnapshotResponse, RuntimeDumpResponse};
+use crate::services::health_system::profiling::{
+    DEFAULT_PROFILE_DURATION, cpu_profile, heap_snapshot, runtime_dump,
+};
+use crate::services::shared::{Layer5Result, authorize_admin};
+use crate::state::AppState;
+
+/// Configure the `/admin/debug/*` routes
+pub fn configure_debug_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/admin/debug/tasks", get(tasks))
+        .route("/admin/debug/profile", get(profile))
+        .route("/admin/debug/heap", get(heap))
+}
+
+/// Background tasks and runtime counters
+async fn tasks(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Layer5Result<Json<RuntimeDumpResponse>> {
+    authorize_admin(&headers)?;
+    Ok(Json(runtime_dump(&state)))
+}
+
+/// Worker utilization over `?seconds=` (default 5, at most 60)
+async fn profile(
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<Json<CpuProfileResponse>> {
+    authorize_admin(&headers)?;
+    let duration = params
+        .get("seconds")
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|&secs| secs > 0)
+        .map_or(DEFAULT_PROFILE_DURATION, Duration::from_secs);
+    info!("🔬 Runtime profile of {}s requested", duration.as_secs());
+    Ok(Json(cpu_profile(duration).await))
+}
+
+/// Process memory figures and L1 cache weight
+async fn heap(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Layer5Result<Json<HeapSnapshotResponse>> {
+    authorize_admin(&headers)?;
+    Ok(Json(heap_snapshot(&state).await))
+}
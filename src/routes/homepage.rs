@@ -3,12 +3,22 @@
 //! This module handles the main homepage route using the Service Islands Architecture.
 //! The homepage is served through the Dashboard Island.
 
-use axum::{Router, extract::State, routing::get};
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::HeaderMap,
+    routing::get,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::debug;
 
-use crate::services::crypto_reports::handlers::RenderedContent;
-use crate::services::shared::{error::Layer5Result, try_get_cached_compressed};
+use crate::services::crypto_reports::handlers::{CryptoHandlers, RenderedContent};
+use crate::services::dashboard_data_service::DashboardDataService;
+use crate::services::data_communication::DEFAULT_LANGUAGE;
+use crate::services::shared::{
+    cache_bypass_requested, error::Layer5Result, try_get_cached_compressed,
+};
 use crate::state::AppState;
 
 /// Configure homepage route
@@ -16,18 +26,33 @@ pub fn configure_homepage_route() -> Router<Arc<AppState>> {
     Router::new().route("/", get(homepage))
 }
 
-async fn homepage(State(state): State<Arc<AppState>>) -> Layer5Result<RenderedContent> {
+/// Homepage, cached per language
+async fn homepage(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Layer5Result<RenderedContent> {
+    let language = CryptoHandlers::detect_preferred_language(&params, &headers)
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+
     // ⚡ IMMEDIATE CACHE CHECK: Global multi-tier cache check (L1 -> L2)
-    let cache_key = "dashboard_homepage_compressed";
-    if let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, cache_key).await {
+    let bypass_cache = cache_bypass_requested(&headers);
+    let cache_key = DashboardDataService::homepage_cache_key(&language);
+    if !bypass_cache
+        && let Some(cached_data) = try_get_cached_compressed(&state.cache_manager, &cache_key).await
+    {
         debug!("⚡ [Route] Immediate cache HIT for homepage");
         return Ok(RenderedContent {
-            data: cached_data,
-            cache_control: "public, max-age=300",
+            data: cached_data.into(),
+            cache_control: None,
             cache_status: "HIT",
+            last_modified: None,
         });
     }
 
     // Fallback: Use the dashboard island's homepage handler for lazy init/rendering
-    state.dashboard_handlers.homepage_with_tera(&state).await
+    state
+        .dashboard_handlers
+        .homepage_with_tera(&state, &language, bypass_cache)
+        .await
 }
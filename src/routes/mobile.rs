@@ -0,0 +1,74 @@
+//! Mobile API Routes
+//!
+//! Versioned endpoints for the companion app:
+//! - /api/mobile/v1/feed - Home screen feed (latest report, market snapshot, top movers)
+//!
+//! Delta support: `?since=<cursor>` skips unchanged sections, answering
+//! `304 Not Modified` when nothing changed. `If-None-Match: "<cursor>"` only
+//! revalidates: it gets a 304 or the full feed, never a delta, as the response is
+//! shared-cacheable under the URL without `?since=`.
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::services::crypto_reports::handlers::CryptoHandlers;
+use crate::services::mobile_feed::{
+    FEED_MAX_AGE_SECS, FEED_STALE_WHILE_REVALIDATE_SECS, MobileFeedService,
+};
+use crate::services::shared::{LANGUAGE_VARY, etag_matches};
+use crate::state::AppState;
+
+/// Configure mobile API routes
+pub fn configure_mobile_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/api/mobile/v1/feed", get(mobile_feed))
+}
+
+/// Mobile home screen feed
+async fn mobile_feed(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    let language = CryptoHandlers::detect_preferred_language(&params, &headers)
+        .unwrap_or_else(|| "vi".to_string());
+    let since = params.get("since").map(String::as_str);
+
+    let feed = MobileFeedService::new()
+        .build_feed(&state, &language, since)
+        .await;
+    let etag = format!("\"{}\"", feed.response.cursor);
+    let not_modified = feed.not_modified || etag_matches(&headers, &etag);
+    debug!(
+        "📱 [Route] Mobile feed (lang: {}, not_modified: {})",
+        language, not_modified
+    );
+
+    let etag = HeaderValue::from_str(&etag).ok();
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        Json(feed.response).into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_str(&format!(
+            "public, max-age={FEED_MAX_AGE_SECS}, stale-while-revalidate={FEED_STALE_WHILE_REVALIDATE_SECS}"
+        ))
+        .unwrap_or_else(|_| HeaderValue::from_static("public, max-age=30")),
+    );
+    response_headers.insert(header::VARY, HeaderValue::from_static(LANGUAGE_VARY));
+    if let Some(etag) = etag {
+        response_headers.insert(header::ETAG, etag);
+    }
+    response
+}
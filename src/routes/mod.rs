@@ -4,8 +4,14 @@
 //! Routes are split into logical modules for better maintainability and organization.
 
 pub mod api;
+pub mod authors;
+pub mod cache_policy;
 pub mod crypto_reports;
+#[cfg(feature = "profiling")]
+pub mod debug;
 pub mod homepage;
+pub mod mobile;
+pub mod notifications;
 pub mod rss_feed;
 pub mod seo;
 pub mod static_files;
@@ -13,14 +19,19 @@ pub mod system;
 // WebSocket module moved to separate Web-server-Report-websocket service
 // pub mod websocket;
 
-use axum::Router;
+use axum::{Router, middleware};
 use std::sync::Arc;
 
 use crate::state::AppState;
 
 /// Create the main router by combining all route modules
 pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
+    let router = Router::new();
+    // Runtime profiling endpoints (admin only)
+    #[cfg(feature = "profiling")]
+    let router = router.merge(debug::configure_debug_routes());
+
+    router
         // Static file serving
         .merge(static_files::configure_static_routes())
         // Homepage
@@ -29,13 +40,76 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .merge(system::configure_system_routes())
         // Crypto Reports routes
         .merge(crypto_reports::configure_crypto_reports_routes())
-        // API endpoints
-        .merge(api::configure_api_routes())
+        // API endpoints, the companion app API (versioned) and digest subscriptions,
+        // callable cross-origin
+        .merge(with_api_cors(
+            api::configure_api_routes()
+                .merge(mobile::configure_mobile_routes())
+                .merge(notifications::configure_notification_routes()),
+        ))
         // SEO endpoints (sitemap.xml)
         .merge(seo::configure_seo_routes())
         // RSS feed endpoint
         .merge(rss_feed::configure_rss_routes())
+        // Author archive pages and per-author feeds
+        .merge(authors::configure_author_routes())
         // Note: WebSocket endpoint has been moved to Web-server-Report-websocket service
         // Client should connect to separate websocket service (port 8081)
+        // Double-submit CSRF token on mutating requests without API credentials
+        .route_layer(middleware::from_fn(crate::services::shared::csrf_protect))
+        // API key / JWT scopes for /admin/* and mutating requests (ADMIN_TOKEN fallback)
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            crate::services::data_communication::authenticate,
+        ))
+        // /admin/* and /metrics only from ADMIN_ALLOWLIST networks (checked before credentials)
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            crate::services::shared::enforce_admin_allowlist,
+        ))
+        // Cache-Control / Surrogate-Key per matched route
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            cache_policy::apply_cache_policy,
+        ))
+        // Per-IP token buckets on routes that force renders (429 + Retry-After)
+        .route_layer(middleware::from_fn(crate::services::shared::rate_limit))
+        // User-agent classes: per-crawler buckets (429) and cache-only AI crawlers
+        .route_layer(middleware::from_fn(crate::services::shared::crawl_control))
+        // Body size (global and per-route) and query length caps, 413 / 414 problem+json
+        .route_layer(middleware::from_fn(
+            crate::services::shared::enforce_request_limits,
+        ))
+        // Request counts and latency per matched route for /metrics
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            crate::services::health_system::record_request_metrics,
+        ))
+        // Stored 301 / 302 redirects of legacy URLs (also for paths without a route)
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            crate::services::shared::redirect_legacy_urls,
+        ))
+        // One access log line per request (inside the request span for its id)
+        .layer(middleware::from_fn(
+            crate::services::health_system::access_log,
+        ))
+        // Scheme and host of absolute URLs from trusted X-Forwarded-Proto / Host
+        .layer(middleware::from_fn(
+            crate::services::shared::resolve_public_origin,
+        ))
+        // X-Request-Id on every response (including unmatched routes) and its log span
+        .layer(middleware::from_fn(
+            crate::services::health_system::propagate_request_id,
+        ))
         .with_state(state)
 }
+
+/// Apply the `/api/*` CORS policy, when `CORS_ALLOWED_ORIGINS` enables one
+fn with_api_cors(router: Router<Arc<AppState>>) -> Router<Arc<AppState>> {
+    if let Some(cors) = crate::services::shared::CorsConfig::current().layer() {
+        router.layer(cors)
+    } else {
+        router
+    }
+}
@@ -0,0 +1,166 @@
+//! Notification Routes
+//!
+//! Email digest subscriptions of the notifications island:
+//! - POST /api/subscribe - subscribe an address (daily or weekly, `vi` or `en`),
+//!   pending the confirmation mailed to it
+//! - GET /api/subscribe/confirm?token= - the confirmation link, a page whose button
+//!   POSTs the token back to confirm the subscription
+//! - GET /api/unsubscribe?token= - the link of every digest, a page whose button
+//!   POSTs the token back to delete the subscription
+//!
+//! Links only render pages, so mail scanners prefetching them change nothing. All
+//! answer 404 while digests are disabled (`SMTP_URL` not set).
+
+use axum::{
+    Form, Router,
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::{Html, IntoResponse, Json, Response},
+    routing::{get, post},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::dto::{requests::SubscribeRequest, responses::SubscribeResponse};
+use crate::services::data_communication::DEFAULT_LANGUAGE;
+use crate::services::notifications::{
+    CONFIRM_PATH, DigestConfig, DigestFrequency, SubscriptionAction, SubscriptionService,
+    UNSUBSCRIBE_PATH, is_valid_token, send_confirmation, validate_subscription,
+};
+use crate::services::shared::{CsrfToken, Layer5Error, Layer5Result};
+use crate::state::AppState;
+
+/// Configure email subscription routes
+pub fn configure_notification_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/api/subscribe", post(subscribe))
+        .route(CONFIRM_PATH, get(confirm_page).post(confirm))
+        .route(UNSUBSCRIBE_PATH, get(unsubscribe_page).post(unsubscribe))
+}
+
+fn ensure_enabled() -> Layer5Result<()> {
+    if DigestConfig::current().is_enabled() {
+        Ok(())
+    } else {
+        Err(Layer5Error::NotFound(
+            "Email digests are not enabled".to_string(),
+        ))
+    }
+}
+
+/// Subscribe an address to the digest pending confirmation, or update the
+/// frequency and language of an unconfirmed subscription
+///
+/// Answers 202 with the same body whether or not the address was subscribed, and
+/// whether or not a confirmation email was sent.
+async fn subscribe(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SubscribeRequest>,
+) -> Layer5Result<Response> {
+    ensure_enabled()?;
+    let frequency = match request.frequency.as_deref() {
+        None => DigestFrequency::default(),
+        Some(value) => DigestFrequency::parse(value).ok_or_else(|| {
+            Layer5Error::InvalidInput("frequency must be daily or weekly".to_string())
+        })?,
+    };
+    let language = request.language.as_deref().unwrap_or(DEFAULT_LANGUAGE);
+    let email = validate_subscription(&request.email, language)?;
+
+    if let Some(token) = SubscriptionService::new()
+        .subscribe(&state.db, &email, frequency, language, chrono::Utc::now())
+        .await?
+    {
+        send_confirmation(&email, language, &token);
+        info!(
+            "📧 {} digest subscription pending confirmation",
+            frequency.as_str()
+        );
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(SubscribeResponse {
+            email,
+            frequency: frequency.as_str().to_string(),
+            language: language.to_string(),
+        }),
+    )
+        .into_response())
+}
+
+/// `?token=` of a link page (400 when missing or malformed)
+fn link_token(params: &HashMap<String, String>) -> Layer5Result<&str> {
+    params
+        .get("token")
+        .map(String::as_str)
+        .filter(|token| is_valid_token(token))
+        .ok_or_else(|| Layer5Error::InvalidInput("a valid token is required".to_string()))
+}
+
+/// Page of the confirmation link
+async fn confirm_page(
+    Query(params): Query<HashMap<String, String>>,
+    csrf: CsrfToken,
+) -> Layer5Result<Html<String>> {
+    ensure_enabled()?;
+    let token = link_token(&params)?;
+    Ok(Html(
+        SubscriptionAction::Confirm.render_page(token, &csrf.hidden_field()),
+    ))
+}
+
+/// Page of the unsubscribe link
+async fn unsubscribe_page(
+    Query(params): Query<HashMap<String, String>>,
+    csrf: CsrfToken,
+) -> Layer5Result<Html<String>> {
+    ensure_enabled()?;
+    let token = link_token(&params)?;
+    Ok(Html(
+        SubscriptionAction::Unsubscribe.render_page(token, &csrf.hidden_field()),
+    ))
+}
+
+/// Confirm the subscription of the posted `token` (404 for an unknown token)
+async fn confirm(
+    State(state): State<Arc<AppState>>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Layer5Result<Response> {
+    ensure_enabled()?;
+    let token = link_token(&form)?;
+    if !SubscriptionService::new()
+        .confirm(&state.db, token, chrono::Utc::now())
+        .await?
+    {
+        return Err(Layer5Error::NotFound("Subscription not found".to_string()));
+    }
+    info!("📧 Digest subscription confirmed");
+    Ok(done_response(SubscriptionAction::Confirm))
+}
+
+/// Delete the subscription of the posted `token` (404 for an unknown token)
+async fn unsubscribe(
+    State(state): State<Arc<AppState>>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Layer5Result<Response> {
+    ensure_enabled()?;
+    let token = link_token(&form)?;
+    if !SubscriptionService::new()
+        .unsubscribe(&state.db, token)
+        .await?
+    {
+        return Err(Layer5Error::NotFound("Subscription not found".to_string()));
+    }
+    info!("📧 Digest subscription removed");
+    Ok(done_response(SubscriptionAction::Unsubscribe))
+}
+
+fn done_response(action: SubscriptionAction) -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        action.done(),
+    )
+        .into_response()
+}
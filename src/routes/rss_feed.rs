@@ -1,7 +1,21 @@
 //! RSS Feed Routes Module
 //!
-//! Handles RSS feed endpoint for search engines, AI bots, and feed readers:
+//! Handles feed endpoints for search engines, AI bots, and feed readers:
 //! - /rss.xml - RSS 2.0 feed with latest crypto reports
+//! - /atom.xml - Atom 1.0 feed of the same reports, for Atom-only readers
+//! - /feed.json - JSON Feed 1.1 of the same reports, for scripts and bots
+//!
+//! Every feed takes `?lang=en` and `?tag=btc` (one of the topic tags of
+//! `report_tags`); the RSS feed also answers the path variants `/rss/en.xml`,
+//! `/rss/btc.xml` and `/rss/en/btc.xml`. Each language and tag is cached separately.
+//!
+//! The untagged RSS and Atom feeds are archived (RFC 5005): `?archive=N` serves
+//! full pages of `RSS_FEED_LIMIT` reports counted from the oldest, and every
+//! document links its neighbours, so readers can walk back the whole history.
+//!
+//! Responses carry an `ETag` (hash of the cached payload) and a `Last-Modified`
+//! (newest report of the feed), so pollers revalidating every few minutes get a
+//! bodiless 304 until a report is published or edited.
 //!
 //! These routes follow the Service Islands architecture (Layer 5 -> Layer 3 -> Layer 1)
 //! and are optimized for daily content discovery by bots and crawlers.
@@ -10,17 +24,24 @@
 use axum::{
     Router,
     body::Body,
-    extract::State,
-    http::{StatusCode, header},
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
     routing::get,
 };
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{error, info};
 
-use crate::services::data_communication::CryptoDataService;
+use crate::services::crypto_reports::handlers::CryptoHandlers;
+use crate::services::data_communication::{
+    CacheKeyBuilder, CacheRoute, CryptoDataService, DEFAULT_LANGUAGE,
+    crypto_data_service::ReportRssData,
+};
 use crate::services::shared::{
-    RssCreator, build_standard_compressed_response, cache_compressed_data, compress_data,
+    FeedBuilder, FeedFormat, LANGUAGE_VARY, cache_compressed_data, cache_last_modified,
+    compress_data, conditional_compressed_response, get_cached_last_modified, report_tag,
     try_get_cached_compressed,
 };
 use crate::state::AppState;
@@ -28,99 +49,291 @@ use crate::state::AppState;
 /// Default number of reports to include in RSS feed
 const RSS_FEED_LIMIT: i64 = 20;
 
-/// Configure RSS feed routes
+/// Latest reports searched for a tag feed's `RSS_FEED_LIMIT` items
+const TAG_FEED_CANDIDATES: i64 = 100;
+
+/// Configure RSS, Atom and JSON feed routes
 pub fn configure_rss_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/rss.xml", get(rss_feed))
         .route("/rss", get(rss_feed)) // Alternative path without .xml extension
+        .route("/rss/{variant}", get(rss_variant_feed))
+        .route("/rss/{lang}/{tag}", get(rss_language_tag_feed))
+        .route("/atom.xml", get(atom_feed))
+        .route("/feed.json", get(json_feed))
 }
 
 /// Generate and serve RSS 2.0 feed with L1/L2 cache
 ///
 /// Uses `MediumTerm` cache strategy (1 hour) since RSS changes infrequently.
-async fn rss_feed(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    info!("📡 Generating RSS feed");
+/// The feed is localized (`?lang=en`, language cookie or `Accept-Language`) and cached per language.
+pub(crate) async fn rss_feed(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    serve_feed(&state, &params, &headers, FeedFormat::Rss).await
+}
+
+/// `/rss/{variant}`: a language (`/rss/en.xml`) or tag (`/rss/btc.xml`) feed
+async fn rss_variant_feed(
+    Path(variant): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Query(mut params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    let name = variant.strip_suffix(".xml").unwrap_or(&variant);
+    let param = if matches!(name, "en" | "vi") {
+        "lang"
+    } else {
+        "tag"
+    };
+    params.insert(param.to_string(), name.to_string());
+    serve_feed(&state, &params, &headers, FeedFormat::Rss).await
+}
+
+/// `/rss/{lang}/{tag}`: a tag feed in one language (`/rss/en/btc.xml`)
+async fn rss_language_tag_feed(
+    Path((lang, tag)): Path<(String, String)>,
+    State(state): State<Arc<AppState>>,
+    Query(mut params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    if !matches!(lang.as_str(), "en" | "vi") {
+        return (StatusCode::NOT_FOUND, "Unknown feed language").into_response();
+    }
+    let tag = tag.strip_suffix(".xml").unwrap_or(&tag).to_string();
+    params.insert("lang".to_string(), lang);
+    params.insert("tag".to_string(), tag);
+    serve_feed(&state, &params, &headers, FeedFormat::Rss).await
+}
+
+/// Generate and serve the Atom 1.0 feed, cached like the RSS feed
+async fn atom_feed(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    serve_feed(&state, &params, &headers, FeedFormat::Atom).await
+}
+
+/// Generate and serve the JSON Feed 1.1, cached like the RSS feed
+async fn json_feed(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Response {
+    serve_feed(&state, &params, &headers, FeedFormat::Json).await
+}
+
+/// Serve the site-wide feed in `format` for the preferred language and `?tag=`
+async fn serve_feed(
+    state: &Arc<AppState>,
+    params: &HashMap<String, String>,
+    headers: &HeaderMap,
+    format: FeedFormat,
+) -> Response {
+    let tag = match params.get("tag").map(|tag| report_tag(tag)) {
+        None => None,
+        Some(Some(tag)) => Some(tag),
+        Some(None) => return (StatusCode::NOT_FOUND, "Unknown feed tag").into_response(),
+    };
+    // Archives cover the untagged RSS / Atom feeds
+    let archive = match params.get("archive").map(|page| page.parse::<u32>()) {
+        None => None,
+        Some(Ok(page)) if page > 0 && tag.is_none() && format != FeedFormat::Json => Some(page),
+        Some(_) => return (StatusCode::NOT_FOUND, "Unknown feed archive").into_response(),
+    };
+    let language = CryptoHandlers::detect_preferred_language(params, headers)
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+    let mut response = build_feed(state, headers, &language, tag, format, archive).await;
+    response
+        .headers_mut()
+        .insert(header::VARY, HeaderValue::from_static(LANGUAGE_VARY));
+    response
+}
+
+/// Cache key of the feed in `format` for one language, tag and archive page
+fn feed_cache_key(
+    format: FeedFormat,
+    language: &str,
+    tag: Option<&str>,
+    archive: Option<u32>,
+) -> String {
+    let route = match format {
+        FeedFormat::Rss => CacheRoute::RssFeed,
+        FeedFormat::Atom => CacheRoute::AtomFeed,
+        FeedFormat::Json => CacheRoute::JsonFeed,
+    };
+    let mut cache_key = CacheKeyBuilder::new(route);
+    if let Some(tag) = tag {
+        cache_key = cache_key.segment(format!("tag-{tag}"));
+    }
+    if let Some(page) = archive {
+        cache_key = cache_key.segment(format!("archive-{page}"));
+    }
+    cache_key.language(language).build()
+}
 
-    let cache_key = "rss_feed_xml_compressed";
+/// Reports of a feed document and the builder rendering them; `None` for an
+/// archive page that is not full yet
+///
+/// Untagged RSS / Atom documents are linked into the archive of full
+/// `RSS_FEED_LIMIT`-report pages.
+async fn load_feed(
+    state: &Arc<AppState>,
+    language: &str,
+    tag: Option<&'static str>,
+    format: FeedFormat,
+    archive: Option<u32>,
+) -> Result<Option<(Vec<ReportRssData>, FeedBuilder)>, sqlx::Error> {
+    let data_service = CryptoDataService::new();
+    let builder = FeedBuilder::site(language, format);
+    if let Some(tag) = tag {
+        let reports = data_service
+            .fetch_rss_reports(state, TAG_FEED_CANDIDATES)
+            .await?;
+        let max_items = usize::try_from(RSS_FEED_LIMIT).unwrap_or_default();
+        return Ok(Some((reports, builder.with_tag(tag, max_items))));
+    }
+    if format == FeedFormat::Json {
+        let reports = data_service
+            .fetch_rss_reports(state, RSS_FEED_LIMIT)
+            .await?;
+        return Ok(Some((reports, builder)));
+    }
+
+    let pages = data_service.count_feed_reports(state).await? / RSS_FEED_LIMIT;
+    let pages = u32::try_from(pages).unwrap_or(u32::MAX);
+    let reports = match archive {
+        Some(page) if page > pages => return Ok(None),
+        Some(page) => {
+            data_service
+                .fetch_rss_archive(state, i64::from(page), RSS_FEED_LIMIT)
+                .await?
+        }
+        None => {
+            data_service
+                .fetch_rss_reports(state, RSS_FEED_LIMIT)
+                .await?
+        }
+    };
+    Ok(Some((reports, builder.with_archive(archive, pages))))
+}
+
+/// Cache a generated feed and the date of its newest report (`Last-Modified`)
+async fn store_feed(
+    state: &AppState,
+    cache_key: &str,
+    compressed_data: &[u8],
+    last_modified: Option<&DateTime<Utc>>,
+) {
+    let strategy = state.cache_config.rss.strategy();
+    cache_compressed_data(
+        &state.cache_manager,
+        cache_key,
+        compressed_data,
+        strategy.clone(),
+        "RSS feed",
+    )
+    .await;
+    if let Some(last_modified) = last_modified {
+        cache_last_modified(&state.cache_manager, cache_key, last_modified, strategy).await;
+    }
+}
+
+/// Serve the feed for one language and tag from cache, generating it on a miss
+async fn build_feed(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    language: &str,
+    tag: Option<&'static str>,
+    format: FeedFormat,
+    archive: Option<u32>,
+) -> Response {
+    info!(
+        "📡 Generating {:?} feed (lang: {}, tag: {:?}, archive: {:?})",
+        format, language, tag, archive
+    );
+
+    let cache_key = feed_cache_key(format, language, tag, archive);
+    let cache_key = cache_key.as_str();
     let cache_manager = &state.cache_manager;
+    let content_type = format.content_type();
 
     // Step 1: Check L1/L2 cache first
     if let Some(cached_bytes) = try_get_cached_compressed(cache_manager, cache_key).await {
-        info!("🔥 RSS: Cache HIT - serving from cache");
-        return build_standard_compressed_response(
+        info!("🔥 {:?} feed: Cache HIT - serving from cache", format);
+        let last_modified = get_cached_last_modified(cache_manager, cache_key).await;
+        return conditional_compressed_response(
+            headers,
             cached_bytes,
-            "application/rss+xml; charset=utf-8",
-            3600,
+            content_type,
             "HIT",
+            last_modified.as_ref(),
         );
     }
 
-    info!("🔍 RSS: Cache MISS - generating from database");
+    info!(
+        "🔍 {:?} feed: Cache MISS - generating from database",
+        format
+    );
 
     // Step 2: Cache MISS - generate from database
-    let data_service = CryptoDataService::new();
-    let reports_result = data_service.fetch_rss_reports(&state, RSS_FEED_LIMIT).await;
-
-    match reports_result {
-        Ok(reports) => {
-            let report_count = reports.len();
-
-            match RssCreator::generate_rss_xml(&reports) {
-                Ok(xml) => {
-                    info!(
-                        "✅ RSS feed generated: {} items, {} bytes",
-                        report_count,
-                        xml.len()
-                    );
-
-                    match compress_data(&xml) {
-                        Ok(compressed_data) => {
-                            cache_compressed_data(
-                                cache_manager,
-                                cache_key,
-                                &compressed_data,
-                                multi_tier_cache::CacheStrategy::MediumTerm,
-                                "RSS feed",
-                            )
-                            .await;
-                            build_standard_compressed_response(
-                                compressed_data,
-                                "application/rss+xml; charset=utf-8",
-                                3600,
-                                "MISS",
-                            )
-                        }
-                        Err(e) => {
-                            error!("Failed to compress RSS XML: {}", e);
-                            Response::builder()
-                                .status(StatusCode::OK)
-                                .header(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")
-                                .header(header::CACHE_CONTROL, "public, max-age=3600")
-                                .header("X-Robots-Tag", "index, follow")
-                                .body(Body::from(xml))
-                                .unwrap_or_else(|e| {
-                                    error!("Failed to build fallback RSS response: {}", e);
-                                    Response::new(Body::from("Failed to generate RSS feed"))
-                                })
-                                .into_response()
-                        }
-                    }
+    let (reports, builder) = match load_feed(state, language, tag, format, archive).await {
+        Ok(Some(loaded)) => loaded,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Unknown feed archive").into_response(),
+        Err(e) => {
+            error!("❌ Failed to fetch reports for RSS feed: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to fetch RSS data",
+            )
+                .into_response();
+        }
+    };
+
+    match builder.build(&reports) {
+        Ok(xml) => {
+            info!(
+                "✅ {:?} feed generated from {} reports, {} bytes",
+                format,
+                reports.len(),
+                xml.len()
+            );
+
+            match compress_data(&xml) {
+                Ok(compressed_data) => {
+                    let last_modified = reports.iter().map(|report| report.created_at).max();
+                    store_feed(state, cache_key, &compressed_data, last_modified.as_ref()).await;
+                    conditional_compressed_response(
+                        headers,
+                        compressed_data,
+                        content_type,
+                        "MISS",
+                        last_modified.as_ref(),
+                    )
                 }
                 Err(e) => {
-                    error!("❌ Failed to generate RSS XML: {}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "Failed to generate RSS feed",
-                    )
+                    error!("Failed to compress RSS XML: {}", e);
+                    Response::builder()
+                        .status(StatusCode::OK)
+                        .header(header::CONTENT_TYPE, content_type)
+                        .header("X-Robots-Tag", "index, follow")
+                        .body(Body::from(xml))
+                        .unwrap_or_else(|e| {
+                            error!("Failed to build fallback RSS response: {}", e);
+                            Response::new(Body::from("Failed to generate RSS feed"))
+                        })
                         .into_response()
                 }
             }
         }
         Err(e) => {
-            error!("❌ Failed to fetch reports for RSS feed: {}", e);
+            error!("❌ Failed to generate RSS XML: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to fetch RSS data",
+                "Failed to generate RSS feed",
             )
                 .into_response()
         }
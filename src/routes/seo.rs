@@ -1,119 +1,505 @@
 //! SEO Routes Module
 //!
 //! Handles SEO-related endpoints including:
-//! - sitemap.xml generation
+//! - sitemap.xml index and `/sitemaps/` sub-sitemaps
+//! - news-sitemap.xml for Google News (reports of the last 48 hours)
+//! - robots.txt, generated per deployment mode (see `RobotsConfig`)
+//! - llms.txt / llms-full.txt, the site summary and citation guide for AI agents
 //!
 //! These routes are designed for search engine optimization and follow
 //! the Service Islands architecture (Layer 5 -> Layer 3 -> Layer 1).
-//! ✅ OPTIMIZED: Server-side L1/L2 cache with `MediumTerm` strategy (1 hour)
+//! ✅ OPTIMIZED: Server-side L1/L2 cache with `MediumTerm` strategy (1 hour); a
+//! published or updated report regenerates only the sitemaps that list it (see
+//! `spawn_sitemap_refresher`)
+//! ✅ Sitemaps and llms.txt carry an `ETag` and a `Last-Modified` (newest listed
+//! report) and answer conditional requests with a bodiless 304
 
 use axum::{
     Router,
     body::Body,
-    extract::State,
-    http::{StatusCode, header},
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
     routing::get,
 };
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
-use tracing::{error, info};
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
 
-use crate::services::data_communication::CryptoDataService;
+use crate::services::data_communication::{CacheKeyBuilder, CacheRoute, CryptoDataService};
 use crate::services::shared::{
-    SitemapCreator, build_standard_compressed_response, cache_compressed_data, compress_data,
+    INDEXNOW_KEY_PATH, Layer5Result, LlmsTxtCreator, NewsSitemapCreator, RobotsConfig,
+    SitemapCreator, SitemapSection, cache_compressed_data, cache_last_modified, compress_data,
+    conditional_compressed_response, get_cached_last_modified, indexnow_key,
     try_get_cached_compressed,
 };
 use crate::state::AppState;
+use crate::stream::REPORT_EVENTS;
+
+/// Content type of the sitemaps
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+/// Content type of llms.txt (Markdown, served as plain text)
+const TEXT_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
 
 /// Configure SEO routes
 pub fn configure_seo_routes() -> Router<Arc<AppState>> {
-    Router::new().route("/sitemap.xml", get(sitemap_xml))
+    Router::new()
+        .route("/sitemap.xml", get(sitemap_xml))
+        .route("/sitemaps/{file}", get(sitemap_section))
+        .route("/news-sitemap.xml", get(news_sitemap_xml))
+        .route("/robots.txt", get(robots_txt))
+        .route("/llms.txt", get(llms_txt))
+        .route(INDEXNOW_KEY_PATH, get(indexnow_key))
+        .route("/llms-full.txt", get(llms_full_txt))
+}
+
+/// Serve robots.txt from `RobotsConfig`
+async fn robots_txt() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        RobotsConfig::current().render(),
+    )
+}
+
+/// Serve `/llms.txt`: site summary, citation format and the latest reports
+async fn llms_txt(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    serve_llms_txt(&state, &headers, false).await
+}
+
+/// Serve `/llms-full.txt`: `/llms.txt` with every report of the index
+async fn llms_full_txt(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    serve_llms_txt(&state, &headers, true).await
+}
+
+/// Cache → database → generate → compress flow of the llms.txt variants
+async fn serve_llms_txt(state: &Arc<AppState>, headers: &HeaderMap, full: bool) -> Response {
+    let name = if full { "llms-full.txt" } else { "llms.txt" };
+    let cache_key = CacheKeyBuilder::new(CacheRoute::Sitemap)
+        .segment(name.trim_end_matches(".txt"))
+        .build();
+    let cache_key = cache_key.as_str();
+
+    if let Some(cached_bytes) = try_get_cached_compressed(&state.cache_manager, cache_key).await {
+        info!("🔥 SEO: {} Cache HIT - serving from cache", name);
+        return cached_response(state, headers, cache_key, cached_bytes, TEXT_CONTENT_TYPE).await;
+    }
+
+    info!("🔍 SEO: {} Cache MISS - generating from database", name);
+
+    let report_data = match fetch_report_index(state).await {
+        Ok(report_data) => report_data,
+        Err(e) => {
+            error!("Failed to fetch reports for {}: {}", name, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to fetch report index",
+            )
+                .into_response();
+        }
+    };
+
+    let last_modified = newest_report(&report_data);
+    match LlmsTxtCreator::generate(report_data, full) {
+        Ok(text) => {
+            let response = CachedBody::new(cache_key, name, TEXT_CONTENT_TYPE, last_modified);
+            cache_and_respond(state, headers, response, text).await
+        }
+        Err(e) => {
+            error!("Failed to generate {}: {}", name, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to generate llms.txt",
+            )
+                .into_response()
+        }
+    }
 }
 
-/// Generate and serve sitemap.xml with L1/L2 cache
+/// Generate and serve the sitemap index (`/sitemap.xml`) with L1/L2 cache
 ///
 /// Uses `MediumTerm` cache strategy (1 hour) since sitemap changes infrequently.
-async fn sitemap_xml(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    info!("Generating sitemap.xml");
+pub(crate) async fn sitemap_xml(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    serve_sitemap(&state, &headers, None).await
+}
+
+/// Serve one sub-sitemap listed by the index (`/sitemaps/{file}`)
+async fn sitemap_section(
+    State(state): State<Arc<AppState>>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    match SitemapSection::parse(&file) {
+        Some(section) => serve_sitemap(&state, &headers, Some(section)).await,
+        None => (StatusCode::NOT_FOUND, "Sitemap not found").into_response(),
+    }
+}
 
-    let cache_key = "sitemap_xml_compressed";
+/// Cache → database → generate → compress flow shared by the index (`None`)
+/// and the sub-sitemaps
+async fn serve_sitemap(
+    state: &Arc<AppState>,
+    headers: &HeaderMap,
+    section: Option<SitemapSection>,
+) -> Response {
+    let name = sitemap_name(section);
+    info!("Generating {}", name);
+
+    let cache_key = sitemap_cache_key(section);
+    let cache_key = cache_key.as_str();
     let cache_manager = &state.cache_manager;
 
     // Step 1: Check L1/L2 cache first
     if let Some(cached_bytes) = try_get_cached_compressed(cache_manager, cache_key).await {
-        info!("🔥 SEO: sitemap.xml Cache HIT - serving from cache");
-        return build_standard_compressed_response(
-            cached_bytes,
-            "application/xml; charset=utf-8",
-            3600,
-            "HIT",
-        );
+        info!("🔥 SEO: {} Cache HIT - serving from cache", name);
+        return cached_response(state, headers, cache_key, cached_bytes, XML_CONTENT_TYPE).await;
     }
 
-    info!("🔍 SEO: sitemap.xml Cache MISS - generating from database");
+    info!("🔍 SEO: {} Cache MISS - generating from database", name);
 
     // Step 2: Cache MISS - generate from database
-    let data_service = CryptoDataService::new();
-    let reports_result = data_service.fetch_all_report_ids_for_sitemap(&state).await;
-
-    match reports_result {
-        Ok(reports) => {
-            let report_data: Vec<(i32, chrono::DateTime<chrono::Utc>)> =
-                reports.into_iter().map(|r| (r.id, r.created_at)).collect();
-
-            match SitemapCreator::generate_sitemap_xml(report_data) {
-                Ok(xml) => {
-                    info!("Sitemap.xml generated successfully ({} bytes)", xml.len());
-
-                    match compress_data(&xml) {
-                        Ok(compressed_data) => {
-                            cache_compressed_data(
-                                cache_manager,
-                                cache_key,
-                                &compressed_data,
-                                multi_tier_cache::CacheStrategy::MediumTerm,
-                                "sitemap.xml",
-                            )
-                            .await;
-                            build_standard_compressed_response(
-                                compressed_data,
-                                "application/xml; charset=utf-8",
-                                3600,
-                                "MISS",
-                            )
-                        }
-                        Err(e) => {
-                            error!("Failed to compress sitemap XML: {}", e);
-                            Response::builder()
-                                .status(StatusCode::OK)
-                                .header(header::CONTENT_TYPE, "application/xml; charset=utf-8")
-                                .header(header::CACHE_CONTROL, "public, max-age=3600")
-                                .header("X-Robots-Tag", "noindex")
-                                .body(Body::from(xml))
-                                .unwrap_or_else(|e| {
-                                    error!("Failed to build fallback sitemap response: {}", e);
-                                    Response::new(Body::from("Failed to generate sitemap"))
-                                })
-                                .into_response()
-                        }
-                    }
+    let report_data = match fetch_report_index(state).await {
+        Ok(report_data) => report_data,
+        Err(e) => {
+            error!("Failed to fetch reports for sitemap: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to fetch sitemap data",
+            )
+                .into_response();
+        }
+    };
+
+    let last_modified = newest_report(&report_data);
+    let xml = match generate_sitemap(section, report_data) {
+        Ok(Some(xml)) => xml,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Sitemap not found").into_response(),
+        Err(e) => {
+            error!("Failed to generate sitemap XML: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to generate sitemap",
+            )
+                .into_response();
+        }
+    };
+    info!("{} generated successfully ({} bytes)", name, xml.len());
+
+    let response = CachedBody::new(cache_key, &name, XML_CONTENT_TYPE, last_modified);
+    cache_and_respond(state, headers, response, xml).await
+}
+
+/// File name of the sitemap index (`None`) or a sub-sitemap
+fn sitemap_name(section: Option<SitemapSection>) -> String {
+    section.map_or_else(|| "sitemap.xml".to_string(), SitemapSection::file_name)
+}
+
+/// Cache key of the sitemap index (`None`) or a sub-sitemap
+fn sitemap_cache_key(section: Option<SitemapSection>) -> String {
+    let mut cache_key = CacheKeyBuilder::new(CacheRoute::Sitemap);
+    if let Some(section) = section {
+        cache_key = cache_key.segment(section.file_name().trim_end_matches(".xml"));
+    }
+    cache_key.build()
+}
+
+/// XML of the sitemap index (`None`) or a sub-sitemap, `None` for a missing chunk
+fn generate_sitemap(
+    section: Option<SitemapSection>,
+    report_data: Vec<(i32, DateTime<Utc>)>,
+) -> Layer5Result<Option<String>> {
+    match section {
+        None => SitemapCreator::generate_sitemap_index(&report_data).map(Some),
+        Some(section) => SitemapCreator::generate_section_xml(section, report_data),
+    }
+}
+
+/// (`report_id`, `created_at`) of every report
+async fn fetch_report_index(
+    state: &Arc<AppState>,
+) -> Result<Vec<(i32, DateTime<Utc>)>, sqlx::Error> {
+    let reports = CryptoDataService::new()
+        .fetch_all_report_ids_for_sitemap(state)
+        .await?;
+    Ok(reports.into_iter().map(|r| (r.id, r.created_at)).collect())
+}
+
+/// Regenerate the sitemaps affected by a published / updated report
+///
+/// The index and the sections of `SitemapCreator::sections_for_report` are rendered
+/// from one database read and stored in L1/L2, and the other instances drop their
+/// L1 copy; full report chunks that do not hold the report are left untouched. The
+/// news sitemap and llms.txt variants are dropped and rebuilt by their next request.
+/// Copies cached for a non-default public origin expire with their TTL.
+///
+/// Returns the number of regenerated sitemaps.
+///
+/// # Errors
+///
+/// Returns `sqlx::Error` if the report index cannot be read
+pub(crate) async fn refresh_sitemaps_for_report(
+    state: &Arc<AppState>,
+    report_id: i32,
+) -> Result<usize, sqlx::Error> {
+    let report_data = fetch_report_index(state).await?;
+    let last_modified = newest_report(&report_data);
+    let sections = std::iter::once(None).chain(
+        SitemapCreator::sections_for_report(&report_data, report_id)
+            .into_iter()
+            .map(Some),
+    );
+
+    let mut refreshed = 0;
+    for section in sections {
+        let name = sitemap_name(section);
+        let cache_key = sitemap_cache_key(section);
+        let compressed = match generate_sitemap(section, report_data.clone()) {
+            Ok(Some(xml)) => compress_data(&xml).map_err(|e| e.to_string()),
+            Ok(None) => continue,
+            Err(e) => Err(e.to_string()),
+        };
+        match compressed {
+            Ok(compressed_data) => {
+                let strategy = state.cache_config.sitemap.strategy();
+                cache_compressed_data(
+                    &state.cache_manager,
+                    &cache_key,
+                    &compressed_data,
+                    strategy.clone(),
+                    &name,
+                )
+                .await;
+                if let Some(last_modified) = &last_modified {
+                    cache_last_modified(&state.cache_manager, &cache_key, last_modified, strategy)
+                        .await;
                 }
-                Err(e) => {
-                    error!("Failed to generate sitemap XML: {}", e);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "Failed to generate sitemap",
-                    )
-                        .into_response()
+                state.cache_bus.publish_remove(&cache_key).await;
+                refreshed += 1;
+            }
+            Err(e) => warn!("⚠️ Failed to regenerate {}: {}", name, e),
+        }
+    }
+
+    for segment in ["news", "llms", "llms-full"] {
+        let cache_key = CacheKeyBuilder::new(CacheRoute::Sitemap)
+            .segment(segment)
+            .build();
+        if let Err(e) = state.cache_manager.invalidate(&cache_key).await {
+            warn!("⚠️ Failed to invalidate {}: {}", cache_key, e);
+        }
+        state.cache_bus.publish_remove(&cache_key).await;
+    }
+
+    info!(
+        "🗺️ SEO: Regenerated {} sitemaps for report {}",
+        refreshed, report_id
+    );
+    Ok(refreshed)
+}
+
+/// Regenerate the affected sitemaps on every `report_events` entry, supervised as
+/// the `sitemap_refresher` task
+///
+/// Entries arrive through the stream consumers, so nothing happens when the
+/// `report_events` stream is not read (`STREAMS`); sitemaps then expire with their TTL.
+/// Every entry is handled, including several published in one read or during a
+/// refresh; every replica follows the whole stream, and only worker 0 of a
+/// cluster runs the refresher.
+pub fn spawn_sitemap_refresher(state: &Arc<AppState>) {
+    let Some(reader) = state.streams.get(REPORT_EVENTS).cloned() else {
+        info!("⏭️ Sitemap refresher disabled: the report_events stream is not read");
+        return;
+    };
+    let task_state = Arc::clone(state);
+    state.tasks.supervise("sitemap_refresher", move || {
+        let state = Arc::clone(&task_state);
+        let mut events = reader.live_updates().subscribe_events();
+        async move {
+            loop {
+                let update = match events.recv().await {
+                    Ok(update) => update,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!(
+                            "⚠️ Sitemap refresher missed {} report events, their sitemaps expire with the TTL",
+                            missed
+                        );
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let report_id = update
+                    .data
+                    .get("report_id")
+                    .and_then(serde_json::Value::as_i64)
+                    .and_then(|id| i32::try_from(id).ok());
+                let Some(report_id) = report_id else {
+                    continue;
+                };
+                if let Err(e) = refresh_sitemaps_for_report(&state, report_id).await {
+                    warn!(
+                        "⚠️ Failed to regenerate sitemaps for report {}: {}",
+                        report_id, e
+                    );
                 }
             }
+            Err("report_events updates closed".to_string())
         }
+    });
+    info!("🗺️ Sitemaps regenerated on report events");
+}
+
+/// Generate and serve the Google News sitemap (`/news-sitemap.xml`)
+async fn news_sitemap_xml(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let cache_key = CacheKeyBuilder::new(CacheRoute::Sitemap)
+        .segment("news")
+        .build();
+    let cache_key = cache_key.as_str();
+
+    if let Some(cached_bytes) = try_get_cached_compressed(&state.cache_manager, cache_key).await {
+        info!("🔥 SEO: news-sitemap.xml Cache HIT - serving from cache");
+        return cached_response(&state, &headers, cache_key, cached_bytes, XML_CONTENT_TYPE).await;
+    }
+
+    info!("🔍 SEO: news-sitemap.xml Cache MISS - generating from database");
+
+    let now = chrono::Utc::now();
+    let since = NewsSitemapCreator::window_start(now);
+    let report_data: Vec<(i32, DateTime<Utc>)> = match CryptoDataService::new()
+        .fetch_recent_report_ids(&state, since)
+        .await
+    {
+        Ok(reports) => reports.into_iter().map(|r| (r.id, r.created_at)).collect(),
         Err(e) => {
-            error!("Failed to fetch reports for sitemap: {}", e);
-            (
+            error!("Failed to fetch reports for news sitemap: {}", e);
+            return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Failed to fetch sitemap data",
             )
+                .into_response();
+        }
+    };
+
+    let last_modified = newest_report(&report_data);
+    match NewsSitemapCreator::generate_news_sitemap_xml(report_data, now) {
+        Ok(xml) => {
+            let response = CachedBody::new(
+                cache_key,
+                "news-sitemap.xml",
+                XML_CONTENT_TYPE,
+                last_modified,
+            );
+            cache_and_respond(&state, &headers, response, xml).await
+        }
+        Err(e) => {
+            error!("Failed to generate news sitemap XML: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to generate sitemap",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Where and how `cache_and_respond` stores and serves a generated body
+struct CachedBody<'a> {
+    cache_key: &'a str,
+    name: &'a str,
+    content_type: &'static str,
+    /// Newest report listed by the body (`Last-Modified`)
+    last_modified: Option<DateTime<Utc>>,
+}
+
+impl<'a> CachedBody<'a> {
+    fn new(
+        cache_key: &'a str,
+        name: &'a str,
+        content_type: &'static str,
+        last_modified: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            cache_key,
+            name,
+            content_type,
+            last_modified,
+        }
+    }
+}
+
+/// Newest creation date of a report index (`Last-Modified` of the documents listing it)
+fn newest_report(report_data: &[(i32, DateTime<Utc>)]) -> Option<DateTime<Utc>> {
+    report_data.iter().map(|&(_, created_at)| created_at).max()
+}
+
+/// Serve a cache hit with its `ETag` and cached `Last-Modified`, or a 304
+async fn cached_response(
+    state: &AppState,
+    headers: &HeaderMap,
+    cache_key: &str,
+    cached_bytes: Vec<u8>,
+    content_type: &'static str,
+) -> Response {
+    let last_modified = get_cached_last_modified(&state.cache_manager, cache_key).await;
+    conditional_compressed_response(
+        headers,
+        cached_bytes,
+        content_type,
+        "HIT",
+        last_modified.as_ref(),
+    )
+}
+
+/// Compress `body`, store it (and its `Last-Modified`) under its cache key and
+/// serve it, answering 304 to a matching conditional request
+async fn cache_and_respond(
+    state: &AppState,
+    headers: &HeaderMap,
+    target: CachedBody<'_>,
+    body: String,
+) -> Response {
+    let CachedBody {
+        cache_key,
+        name,
+        content_type,
+        last_modified,
+    } = target;
+    match compress_data(&body) {
+        Ok(compressed_data) => {
+            let strategy = state.cache_config.sitemap.strategy();
+            cache_compressed_data(
+                &state.cache_manager,
+                cache_key,
+                &compressed_data,
+                strategy.clone(),
+                name,
+            )
+            .await;
+            if let Some(last_modified) = &last_modified {
+                cache_last_modified(&state.cache_manager, cache_key, last_modified, strategy).await;
+            }
+            conditional_compressed_response(
+                headers,
+                compressed_data,
+                content_type,
+                "MISS",
+                last_modified.as_ref(),
+            )
+        }
+        Err(e) => {
+            error!("Failed to compress {}: {}", name, e);
+            Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, content_type)
+                .header("X-Robots-Tag", "noindex")
+                .body(Body::from(body))
+                .unwrap_or_else(|e| {
+                    error!("Failed to build fallback {} response: {}", name, e);
+                    Response::new(Body::from("Failed to generate response"))
+                })
                 .into_response()
         }
     }
@@ -7,7 +7,7 @@
 use axum::Router;
 use axum::http::{HeaderValue, header};
 use std::sync::Arc;
-use tower_http::services::{ServeDir, ServeFile};
+use tower_http::services::ServeDir;
 use tower_http::set_header::SetResponseHeaderLayer;
 
 use crate::state::AppState;
@@ -23,8 +23,6 @@ use crate::state::AppState;
 /// ✅ OPTIMIZED: All static files get `Cache-Control: public, max-age=86400` (24 hours)
 pub fn configure_static_routes() -> Router<Arc<AppState>> {
     Router::new()
-        // SEO files
-        .route_service("/robots.txt", ServeFile::new("robots.txt"))
         // Crypto Dashboard static files
         .nest_service(
             "/crypto_dashboard/shared",
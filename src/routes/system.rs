@@ -3,34 +3,76 @@
 //! This module handles all health checks, system monitoring, and administrative routes.
 //! Routes are handled through the Service Islands Architecture.
 
-use axum::{Router, extract::State, http::StatusCode, response::Json, routing::get};
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Json},
+    routing::{delete, get, post},
+};
+use serde_json::json;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use tracing::{info, warn};
 
 use crate::dto::{
     CacheOperationStatus, HealthStatus,
+    requests::{CachePurgeRequest, RedirectUpsertRequest, StreamReplayRequest},
     responses::{
-        CacheClearResponse, CacheConfiguration, CacheHealth, CacheStatistics, CacheStatsAvailable,
-        CacheStatsResponse, CacheSystemInfo, HealthCheckResponse, PerformanceInfo,
-        PerformanceMetricsResponse, ServicesInfo,
+        AuditLogEntryResponse, AuditLogResponse, CacheClearResponse, CacheConfiguration,
+        CacheHealth, CacheKeyUsage, CachePurgeResponse, CacheStatistics, CacheStatsAvailable,
+        CacheStatsResponse, CacheSystemInfo, CacheTierStatistics, CacheTierUsage,
+        DiagnosticsResponse, HealthCheckResponse, HealthReport, LivenessResponse,
+        OversizedReportEntry, OversizedReportsResponse, PerfResponse, ReadinessResponse,
+        RedirectDeleteResponse, RedirectEntryResponse, RedirectsResponse, RoutePerfEntry,
+        ServicesInfo, SloEntry, SloResponse, StreamFreshnessInfo, StreamReplayResponse,
+        VersionResponse,
     },
 };
+use crate::l1_cache::{L1_TIME_TO_IDLE, L1_TIME_TO_LIVE, TierUsage};
+use crate::services::data_communication::{
+    AuditLogService, MAX_AUDIT_PAGE_SIZE, RedirectRecord, RedirectService, ReportEvent,
+    report_html_max_bytes, report_path,
+};
+use crate::services::diagnostics::run_diagnostics;
+use crate::services::health_system::{MetricsWriter, SloKind, check_readiness, version_info};
+use crate::services::shared::{
+    CachePurgeTarget, Layer5Error, Layer5Result, TraceContext, admin_actor, authorize_admin,
+    cache_key_stats, purge_cache, validate_redirect,
+};
 use crate::state::AppState;
 
 /// Configure health and system monitoring routes
 pub fn configure_system_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/health", get(health_check))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
+        .route("/health/details", get(health_details))
         .route("/metrics", get(performance_metrics))
+        .route("/version", get(version))
         .route("/admin/cache/clear", get(clear_cache))
+        .route("/admin/cache/purge", post(purge_cache_entries))
         .route("/admin/cache/stats", get(cache_stats))
+        .route("/admin/reports/oversized", get(oversized_reports))
+        .route("/admin/diagnostics", get(diagnostics))
+        .route("/admin/perf", get(perf_summary))
+        .route("/admin/slo", get(slo_summary))
+        .route("/admin/streams/replay", post(replay_stream))
+        .route("/admin/audit", get(audit_log))
+        .route(
+            "/admin/redirects",
+            get(list_redirects).post(upsert_redirect),
+        )
+        .route("/admin/redirects/{id}", delete(delete_redirect))
 }
 
 /// Health check endpoint - delegates to Service Islands
 async fn health_check(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<HealthCheckResponse>, StatusCode> {
-    let health_status = state.health_check().await;
+    let health_status = state.health_check().await.is_healthy();
 
     let response = HealthCheckResponse {
         status: if health_status {
@@ -44,41 +86,128 @@ async fn health_check(
             architecture: "Standard Services".to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
         },
+        streams: stream_freshness(&state),
     };
 
     Ok(Json(response))
 }
 
-/// Performance metrics endpoint
-/// ✅ PRODUCTION-READY: Queries actual cache statistics from multi-tier-cache library
-async fn performance_metrics(
-    State(state): State<Arc<AppState>>,
-) -> Json<PerformanceMetricsResponse> {
-    // Get actual cache statistics from library
-    use crate::services::data_communication::CryptoDataService;
-    let cache_info = CryptoDataService::get_cache_stats(&state)
-        .unwrap_or_else(|| "Cache statistics unavailable".to_string());
-
-    let response = PerformanceMetricsResponse {
-        performance: PerformanceInfo {
-            services_active: 5,
-            uptime: "operational".to_string(),
-            memory_usage: "optimized".to_string(),
-            cache_status: "active".to_string(),
-        },
-        cache_info,
+/// Per-island health: status, check time, duration and failure reason of each
+async fn health_details(State(state): State<Arc<AppState>>) -> Json<HealthReport> {
+    Json(state.health_check().await)
+}
+
+/// Build details (version, commit, build time, compiler) and process uptime
+async fn version(State(state): State<Arc<AppState>>) -> Json<VersionResponse> {
+    Json(version_info(state.health.metrics.uptime()))
+}
+
+/// Liveness probe - the process is up and answering requests
+async fn liveness(State(state): State<Arc<AppState>>) -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        status: HealthStatus::Healthy,
+        uptime_secs: state.health.metrics.uptime().as_secs(),
+    })
+}
+
+/// Readiness probe - database, Redis, templates and stream data all usable
+///
+/// Answers 503 with the per-component breakdown when any of them is not (or the
+/// error rate of a route is over its limit), so the
+/// load balancer takes this replica out of rotation until it recovers.
+async fn readiness(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ReadinessResponse>) {
+    let report = check_readiness(&state).await;
+    let status = match report.status {
+        HealthStatus::Healthy => StatusCode::OK,
+        HealthStatus::Degraded | HealthStatus::Unhealthy => {
+            warn!(
+                "⚠️ Readiness check {:?}: {:?}",
+                report.status, report.components
+            );
+            StatusCode::SERVICE_UNAVAILABLE
+        }
     };
+    (status, Json(report))
+}
 
-    Json(response)
+/// Prometheus metrics endpoint, rendered by the Health System Island
+async fn performance_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, MetricsWriter::CONTENT_TYPE)],
+        state.health.render_metrics(&state).await,
+    )
+}
+
+/// Data age of every registered stream, so a stopped publisher shows up even while
+/// pages are still served from the cache
+fn stream_freshness(state: &AppState) -> Vec<StreamFreshnessInfo> {
+    state
+        .streams
+        .iter()
+        .map(|reader| {
+            let definition = &reader.definition;
+            let freshness = reader.freshness();
+            StreamFreshnessInfo {
+                name: definition.name.clone(),
+                stream_key: definition.stream_key.clone(),
+                status: freshness.status(definition.stale_after).as_str(),
+                data_age_secs: freshness.data_age.map(|age| age.as_secs()),
+                last_consumed_secs_ago: freshness.since_consumed.map(|age| age.as_secs()),
+                stale_after_secs: definition.stale_after.map(|limit| limit.as_secs()),
+                entries_consumed: freshness.entries_consumed,
+                dead_letter_stream: reader.dead_letters().stream_key().to_string(),
+                dead_lettered: reader.dead_letters().count(),
+                max_len: definition.max_len,
+                entries_trimmed: reader.retention().trimmed(),
+                last_trim_secs_ago: reader.retention().last_trim_secs_ago(),
+                checkpoint: reader.checkpoint().last_entry_id(),
+                duplicates_skipped: reader.checkpoint().skipped(),
+            }
+        })
+        .collect()
 }
 
 /// Clear cache endpoint - invalidates all cached entries
-async fn clear_cache(State(state): State<Arc<AppState>>) -> Json<CacheClearResponse> {
-    info!("🗑️ Cache clear requested via admin endpoint");
+///
+/// Notifies the websocket service through the service events stream, forwarding
+/// the caller's `traceparent` so both sides of the purge share one trace.
+async fn clear_cache(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Json<CacheClearResponse> {
+    let trace = TraceContext::from_headers(&headers);
+    info!(trace_id = %trace.trace_id, "🗑️ Cache clear requested via admin endpoint");
+
+    let result = state.cache_manager.invalidate_pattern("*").await;
+    let outcome = result
+        .as_ref()
+        .map_or_else(|e| format!("error: {e}"), |()| "ok".to_string());
+    AuditLogService::new()
+        .record(
+            &state,
+            "cache_clear",
+            &admin_actor(&headers),
+            &json!({ "pattern": "*" }),
+            &outcome,
+        )
+        .await;
 
-    match state.cache_manager.invalidate_pattern("*").await {
+    match result {
         Ok(()) => {
             info!("✅ Cache cleared successfully via invalidate_pattern");
+            state.cache_bus.publish_pattern("*").await;
+            if let Err(e) = state
+                .streams
+                .market_data()
+                .publish_service_event(
+                    "cache_cleared",
+                    vec![("pattern".to_string(), "*".to_string())],
+                    &trace,
+                )
+                .await
+            {
+                warn!("⚠️ Failed to publish cache_cleared event: {}", e);
+            }
             Json(CacheClearResponse {
                 message: "Cache cleared successfully".to_string(),
                 status: CacheOperationStatus::Completed,
@@ -94,14 +223,300 @@ async fn clear_cache(State(state): State<Arc<AppState>>) -> Json<CacheClearRespo
     }
 }
 
+/// Targeted cache purge endpoint - clears one key, a key prefix or a report's pages
+///
+/// Requires `ADMIN_TOKEN` (as `Authorization: Bearer` or `X-Admin-Token`).
+/// Entries are removed from L1 and L2 (including stale copies) and from the other
+/// instances' L1 via the invalidation bus, then the purge is
+/// announced on the service events stream like a full cache clear.
+async fn purge_cache_entries(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<CachePurgeRequest>,
+) -> Layer5Result<Json<CachePurgeResponse>> {
+    authorize_admin(&headers)?;
+    let parameters = json!(request);
+    let result = run_cache_purge(&state, &headers, request).await;
+    audit_admin_action(&state, &headers, "cache_purge", &parameters, &result).await;
+    result.map(Json)
+}
+
+async fn run_cache_purge(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: CachePurgeRequest,
+) -> Layer5Result<CachePurgeResponse> {
+    let target = CachePurgeTarget::from_parts(
+        request.key,
+        request.prefix,
+        request.report_id,
+        state.cached_latest_id.load(Ordering::Relaxed),
+    )?;
+    let trace = TraceContext::from_headers(headers);
+    info!(trace_id = %trace.trace_id, "🧹 Cache purge requested via admin endpoint: {:?}", target);
+
+    let purged = purge_cache(&state.cache_manager, &state.cache_bus, &target).await?;
+
+    if let Err(e) = state
+        .streams
+        .market_data()
+        .publish_service_event(
+            "cache_purged",
+            vec![("keys".to_string(), purged.join(","))],
+            &trace,
+        )
+        .await
+    {
+        warn!("⚠️ Failed to publish cache_purged event: {}", e);
+    }
+    // Report pages are purged after the report was edited
+    if let CachePurgeTarget::Report { report_id, .. } = target
+        && let Err(e) = state
+            .report_events
+            .publish_report_event(ReportEvent::Updated, report_id, &trace)
+            .await
+    {
+        warn!(
+            "⚠️ Failed to publish report_updated for {}: {}",
+            report_id, e
+        );
+    }
+
+    Ok(CachePurgeResponse {
+        message: format!("Purged {} cache keys/patterns", purged.len()),
+        status: CacheOperationStatus::Completed,
+        purged,
+    })
+}
+
+/// Default / max number of entries reprocessed by one replay
+const DEFAULT_REPLAY_COUNT: usize = 100;
+const MAX_REPLAY_COUNT: usize = 1000;
+
+/// Stream replay endpoint - reprocesses the newest entries of a registered stream
+///
+/// Requires `ADMIN_TOKEN`. Entries are validated again (malformed ones go to the
+/// dead-letter stream), the newest valid one is cached, and the other instances
+/// drop their L1 copy via the invalidation bus, then the replay is announced on
+/// the service events stream.
+async fn replay_stream(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<StreamReplayRequest>,
+) -> Layer5Result<Json<StreamReplayResponse>> {
+    authorize_admin(&headers)?;
+    let parameters = json!(request);
+    let result = run_stream_replay(&state, &headers, request).await;
+    audit_admin_action(&state, &headers, "stream_replay", &parameters, &result).await;
+    result.map(Json)
+}
+
+async fn run_stream_replay(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: StreamReplayRequest,
+) -> Layer5Result<StreamReplayResponse> {
+    let name = request
+        .stream
+        .unwrap_or_else(|| crate::stream::MARKET_DATA.to_string());
+    let reader = state
+        .streams
+        .get(&name)
+        .ok_or_else(|| Layer5Error::NotFound(format!("Unknown stream '{name}'")))?;
+    let count = request
+        .count
+        .unwrap_or(DEFAULT_REPLAY_COUNT)
+        .clamp(1, MAX_REPLAY_COUNT);
+    let trace = TraceContext::from_headers(headers);
+    info!(trace_id = %trace.trace_id, "🔁 Replay of {} newest {} entries requested via admin endpoint", count, name);
+
+    let outcome = reader
+        .replay(count)
+        .await
+        .map_err(|e| Layer5Error::Cache(format!("Replay of '{name}' failed: {e}")))?;
+    if outcome.cached_entry_id.is_some() {
+        state
+            .cache_bus
+            .publish_remove(&reader.definition.cache_key)
+            .await;
+    }
+
+    if let Err(e) = state
+        .streams
+        .market_data()
+        .publish_service_event(
+            "stream_replayed",
+            vec![
+                ("stream".to_string(), reader.definition.stream_key.clone()),
+                ("count".to_string(), outcome.entries_read.to_string()),
+            ],
+            &trace,
+        )
+        .await
+    {
+        warn!("⚠️ Failed to publish stream_replayed event: {}", e);
+    }
+
+    Ok(StreamReplayResponse {
+        stream: name,
+        status: CacheOperationStatus::Completed,
+        entries_read: outcome.entries_read,
+        rejected: outcome.rejected,
+        cached_entry_id: outcome.cached_entry_id,
+    })
+}
+
+/// Record an admin action and its outcome in the audit log
+async fn audit_admin_action<T>(
+    state: &AppState,
+    headers: &HeaderMap,
+    action: &str,
+    parameters: &serde_json::Value,
+    result: &Layer5Result<T>,
+) {
+    let outcome = result
+        .as_ref()
+        .map_or_else(|e| format!("error: {e}"), |_| "ok".to_string());
+    AuditLogService::new()
+        .record(state, action, &admin_actor(headers), parameters, &outcome)
+        .await;
+}
+
+/// Default number of audit entries returned by `/admin/audit`
+const DEFAULT_AUDIT_LIMIT: i64 = 50;
+
+/// Admin audit log endpoint - newest admin actions first
+///
+/// Requires `ADMIN_TOKEN`. `?action=` filters by action (e.g. `cache_purge`),
+/// `?limit=` (default 50, at most 500) and `?offset=` page through older entries.
+async fn audit_log(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<Json<AuditLogResponse>> {
+    authorize_admin(&headers)?;
+    let limit = params
+        .get("limit")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_AUDIT_LIMIT)
+        .clamp(1, MAX_AUDIT_PAGE_SIZE);
+    let offset = params
+        .get("offset")
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(0)
+        .max(0);
+    let action = params.get("action").map(String::as_str);
+
+    let entries = AuditLogService::new()
+        .fetch_recent(&state, action, limit, offset)
+        .await?
+        .into_iter()
+        .map(|entry| AuditLogEntryResponse {
+            id: entry.id,
+            action: entry.action,
+            actor: entry.actor,
+            parameters: serde_json::from_str(&entry.parameters)
+                .unwrap_or(serde_json::Value::String(entry.parameters)),
+            outcome: entry.outcome,
+            request_id: entry.request_id,
+            created_at: entry.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(Json(AuditLogResponse {
+        limit,
+        offset,
+        entries,
+    }))
+}
+
+/// Default / max number of keys in the `top_keys` list
+const DEFAULT_TOP_KEYS: usize = 10;
+const MAX_TOP_KEYS: usize = 100;
+
 /// Cache statistics endpoint - delegates to Cache System Island
 /// ✅ PRODUCTION-READY: Queries detailed statistics from multi-tier-cache library
-async fn cache_stats(State(app_state): State<Arc<AppState>>) -> Json<CacheStatsResponse> {
+///
+/// Per-tier hit/miss counts come from `CacheManagerStats`; entry counts, memory
+/// estimates and the `?top=` most requested keys come from per-key tracking.
+/// L1 and the disk tier also report their measured bytes, budget and evictions.
+async fn cache_stats(
+    State(app_state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Json<CacheStatsResponse> {
+    let top = params
+        .get("top")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_TOP_KEYS)
+        .min(MAX_TOP_KEYS);
+
     // Get actual cache statistics from the multi-tier-cache library
     let stats = app_state.cache_manager.get_stats();
+    let key_stats = cache_key_stats();
+    let l2_residency = key_stats.residency(None);
+    let l1_usage = app_state.l1_cache.usage().await;
+
+    // Every request reaches L1; only L1 misses reach L2
+    let l1_misses = stats.total_requests.saturating_sub(stats.l1_hits);
+    let mut tiers = vec![
+        CacheTierStatistics {
+            tier: "L1".to_string(),
+            backend: "moka".to_string(),
+            hits: stats.l1_hits,
+            misses: l1_misses,
+            hit_rate: format!("{:.1}%", stats.l1_hit_rate),
+            estimated_entries: usize::try_from(l1_usage.entries).unwrap_or(usize::MAX),
+            estimated_memory_bytes: l1_usage.bytes,
+            usage: Some(tier_usage(l1_usage)),
+        },
+        CacheTierStatistics {
+            tier: "L2".to_string(),
+            backend: "redis".to_string(),
+            hits: stats.l2_hits,
+            misses: stats.misses,
+            hit_rate: format!("{:.1}%", percentage(stats.l2_hits, l1_misses)),
+            estimated_entries: l2_residency.entries,
+            estimated_memory_bytes: l2_residency.bytes,
+            usage: None,
+        },
+    ];
+    if let Some(disk_cache) = &app_state.disk_cache {
+        let disk_usage = disk_cache.usage();
+        let l3_hits = app_state
+            .cache_manager
+            .get_tier_stats()
+            .iter()
+            .find(|tier| tier.tier_level == 3)
+            .map_or(0, |tier| tier.hits.load(Ordering::Relaxed));
+        tiers.push(CacheTierStatistics {
+            tier: "L3".to_string(),
+            backend: "disk".to_string(),
+            hits: l3_hits,
+            misses: stats.misses,
+            hit_rate: format!(
+                "{:.1}%",
+                percentage(l3_hits, l3_hits.saturating_add(stats.misses))
+            ),
+            estimated_entries: usize::try_from(disk_usage.entries).unwrap_or(usize::MAX),
+            estimated_memory_bytes: disk_usage.bytes,
+            usage: Some(tier_usage(disk_usage)),
+        });
+    }
+
+    let top_keys = key_stats
+        .top_keys(top)
+        .into_iter()
+        .map(|usage| CacheKeyUsage {
+            key: usage.key,
+            hits: usage.hits,
+            misses: usage.misses,
+            size_bytes: usage.size_bytes,
+        })
+        .collect();
+
     let response = CacheStatsResponse::Available(Box::new(CacheStatsAvailable {
         cache: CacheSystemInfo {
-            system: "multi-tier-cache library v0.5.2".to_string(),
+            system: "multi-tier-cache library".to_string(),
             l1_cache: "active (moka)".to_string(),
             l2_cache: "active (Redis)".to_string(),
             status: "operational".to_string(),
@@ -116,18 +531,303 @@ async fn cache_stats(State(app_state): State<Arc<AppState>>) -> Json<CacheStatsR
             hit_rate: format!("{:.1}%", stats.hit_rate),
             in_flight_requests: stats.in_flight_requests,
         },
+        tiers,
+        top_keys,
         configuration: CacheConfiguration {
-            l1_max_capacity: 100,
-            l1_ttl: "30 minutes TTL, 5 minutes TTI".to_string(),
+            l1_max_bytes: l1_usage.max_bytes,
+            l1_ttl: format!(
+                "{}s TTL, {}s TTI",
+                L1_TIME_TO_LIVE.as_secs(),
+                L1_TIME_TO_IDLE.as_secs()
+            ),
             l2_ttl: "1 hour (default)".to_string(),
-            eviction: "automatic (size + TTL based)".to_string(),
+            eviction: "automatic (byte budget + TTL based)".to_string(),
             stampede_protection: "enabled (DashMap coalescing)".to_string(),
         },
-        health: CacheHealth {
-            status: "healthy".to_string(),
-            recommendation: "Cache operating normally with automatic memory management".to_string(),
-        },
+        health: cache_health(&l1_usage),
     }));
 
     Json(response)
 }
+
+fn tier_usage(usage: TierUsage) -> CacheTierUsage {
+    CacheTierUsage {
+        entries: usage.entries,
+        bytes: usage.bytes,
+        max_bytes: usage.max_bytes,
+        budget_used: format!("{:.1}%", percentage(usage.bytes, usage.max_bytes)),
+        evictions: usage.evictions,
+        evicted_bytes: usage.evicted_bytes,
+        expirations: usage.expirations,
+    }
+}
+
+/// Warn once L1 has had to evict entries to stay within its byte budget
+fn cache_health(l1_usage: &TierUsage) -> CacheHealth {
+    if l1_usage.evictions == 0 {
+        return CacheHealth {
+            status: "healthy".to_string(),
+            recommendation: "Cache operating normally with automatic memory management".to_string(),
+        };
+    }
+    CacheHealth {
+        status: "evicting".to_string(),
+        recommendation: format!(
+            "L1 evicted {} entries ({} bytes) to stay within its {}MB budget; raise CACHE_L1_MAX_MB if large reports keep missing L1",
+            l1_usage.evictions,
+            l1_usage.evicted_bytes,
+            l1_usage.max_bytes / (1024 * 1024)
+        ),
+    }
+}
+
+/// `part / total` as a percentage (0 when `total` is 0)
+#[allow(clippy::cast_precision_loss)] // f64 conversion for percentage display
+fn percentage(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
+}
+
+/// Oversized reports endpoint - lists reports above the HTML size limit for editorial cleanup
+///
+/// Threshold defaults to `REPORT_HTML_MAX_BYTES`; override with `?min_bytes=`.
+async fn oversized_reports(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Layer5Result<Json<OversizedReportsResponse>> {
+    let threshold_bytes = match params.get("min_bytes") {
+        Some(value) => value
+            .parse::<i64>()
+            .map_err(|_| Layer5Error::InvalidInput(format!("Invalid min_bytes value: {value}")))?,
+        None => i64::try_from(report_html_max_bytes()).unwrap_or(i64::MAX),
+    };
+
+    let reports = state
+        .crypto_handlers
+        .report_creator
+        .data_service
+        .fetch_oversized_reports(&state, threshold_bytes)
+        .await?;
+
+    info!(
+        "📏 Oversized report scan (> {} bytes) found {} reports",
+        threshold_bytes,
+        reports.len()
+    );
+
+    let reports: Vec<OversizedReportEntry> = reports
+        .into_iter()
+        .map(|report| OversizedReportEntry {
+            id: report.id,
+            html_bytes: report.html_bytes,
+            html_en_bytes: report.html_en_bytes,
+            total_bytes: report.total_bytes,
+            created_at: report.created_at.to_rfc3339(),
+            url: report_path(report.id, report.created_at),
+        })
+        .collect();
+
+    Ok(Json(OversizedReportsResponse {
+        threshold_bytes,
+        count: reports.len(),
+        reports,
+    }))
+}
+
+/// Diagnostics endpoint - runs all runbook checks and returns them most severe first
+///
+/// Requires `ADMIN_TOKEN`. Always answers 200; the overall result is in `status`.
+async fn diagnostics(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Layer5Result<Json<DiagnosticsResponse>> {
+    authorize_admin(&headers)?;
+    let report = run_diagnostics(&state).await;
+    info!(
+        "🩺 Diagnostics finished in {}ms: {:?}",
+        report.duration_ms, report.status
+    );
+    Ok(Json(report))
+}
+
+/// Per-route latency summary - p50/p95/p99 from the `/metrics` histograms as JSON,
+/// for a quick look without a Prometheus stack
+///
+/// Requires `ADMIN_TOKEN`.
+async fn perf_summary(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Layer5Result<Json<PerfResponse>> {
+    authorize_admin(&headers)?;
+    let metrics = &state.health.metrics;
+    let routes: Vec<RoutePerfEntry> = metrics
+        .route_latencies()
+        .into_iter()
+        .map(|latency| RoutePerfEntry {
+            #[allow(clippy::cast_precision_loss)]
+            mean_ms: (latency.count > 0).then(|| round_ms(latency.sum_secs / latency.count as f64)),
+            p50_ms: latency.p50_secs.map(round_ms),
+            p95_ms: latency.p95_secs.map(round_ms),
+            p99_ms: latency.p99_secs.map(round_ms),
+            route: latency.route,
+            requests: latency.count,
+        })
+        .collect();
+
+    Ok(Json(PerfResponse {
+        uptime_secs: metrics.uptime().as_secs(),
+        total_requests: routes.iter().map(|route| route.requests).sum(),
+        routes,
+    }))
+}
+
+/// Compliance and remaining error budget of every SLO over the rolling window
+async fn slo_summary(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Layer5Result<Json<SloResponse>> {
+    authorize_admin(&headers)?;
+    let slos = &state.health.slos;
+    let objectives = slos
+        .statuses()
+        .into_iter()
+        .map(|status| {
+            let definition = status.definition;
+            let (kind, latency_threshold_ms) = match definition.kind {
+                SloKind::Availability => ("availability", None),
+                SloKind::Latency(threshold) => (
+                    "latency",
+                    Some(u64::try_from(threshold.as_millis()).unwrap_or(u64::MAX)),
+                ),
+            };
+            SloEntry {
+                name: definition.name.to_string(),
+                description: definition.description.to_string(),
+                kind,
+                latency_threshold_ms,
+                objective: definition.objective,
+                total_requests: status.total,
+                good_requests: status.good,
+                compliance: status.compliance,
+                error_budget_remaining: status.budget_remaining,
+            }
+        })
+        .collect();
+
+    Ok(Json(SloResponse {
+        window_secs: slos.window().as_secs(),
+        objectives,
+    }))
+}
+
+/// Seconds to milliseconds, rounded to 0.01ms
+fn round_ms(secs: f64) -> f64 {
+    (secs * 100_000.0).round() / 100.0
+}
+
+/// Stored redirects, oldest first
+///
+/// Requires `ADMIN_TOKEN`. `active` is the number of rules this instance serves,
+/// which catches up with the table within `REDIRECTS_RELOAD_SECS`.
+async fn list_redirects(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Layer5Result<Json<RedirectsResponse>> {
+    authorize_admin(&headers)?;
+    let redirects = RedirectService::new()
+        .fetch_all(&state.db)
+        .await?
+        .into_iter()
+        .map(redirect_entry)
+        .collect();
+    Ok(Json(RedirectsResponse {
+        active: state.redirects.table().len(),
+        redirects,
+    }))
+}
+
+/// Create a redirect, or replace the target and status of an existing source
+///
+/// Requires `ADMIN_TOKEN`. The redirect applies on this instance immediately and on
+/// the others at their next reload.
+async fn upsert_redirect(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<RedirectUpsertRequest>,
+) -> Layer5Result<Json<RedirectEntryResponse>> {
+    authorize_admin(&headers)?;
+    let parameters = json!(request);
+    let result = run_redirect_upsert(&state, &headers, &request).await;
+    audit_admin_action(&state, &headers, "redirect_upsert", &parameters, &result).await;
+    result.map(Json)
+}
+
+async fn run_redirect_upsert(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: &RedirectUpsertRequest,
+) -> Layer5Result<RedirectEntryResponse> {
+    let status = validate_redirect(&request.source, &request.target, request.status)?;
+    let status_code = i16::try_from(status.as_u16())
+        .map_err(|e| Layer5Error::Internal(format!("Invalid redirect status: {e}")))?;
+    let record = RedirectService::new()
+        .upsert(
+            &state.db,
+            &request.source,
+            &request.target,
+            status_code,
+            &admin_actor(headers),
+        )
+        .await?;
+    info!(
+        "🔀 Redirect {} -> {} ({}) saved via admin endpoint",
+        record.source_path, record.target, record.status_code
+    );
+    state.redirects.reload(&state.db).await?;
+    Ok(redirect_entry(record))
+}
+
+/// Delete a redirect by id (404 when it does not exist)
+///
+/// Requires `ADMIN_TOKEN`.
+async fn delete_redirect(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> Layer5Result<Json<RedirectDeleteResponse>> {
+    authorize_admin(&headers)?;
+    let result = run_redirect_delete(&state, id).await;
+    audit_admin_action(
+        &state,
+        &headers,
+        "redirect_delete",
+        &json!({ "id": id }),
+        &result,
+    )
+    .await;
+    result.map(Json)
+}
+
+async fn run_redirect_delete(state: &AppState, id: i64) -> Layer5Result<RedirectDeleteResponse> {
+    if !RedirectService::new().delete(&state.db, id).await? {
+        return Err(Layer5Error::NotFound(format!("Redirect {id} not found")));
+    }
+    info!("🔀 Redirect #{} deleted via admin endpoint", id);
+    state.redirects.reload(&state.db).await?;
+    Ok(RedirectDeleteResponse { id, deleted: true })
+}
+
+fn redirect_entry(record: RedirectRecord) -> RedirectEntryResponse {
+    RedirectEntryResponse {
+        id: record.id,
+        source: record.source_path,
+        target: record.target,
+        status: record.status_code,
+        created_by: record.created_by,
+        created_at: record.created_at.to_rfc3339(),
+        updated_at: record.updated_at.to_rfc3339(),
+    }
+}
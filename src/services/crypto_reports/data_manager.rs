@@ -1,7 +1,37 @@
 //! Data Manager Component
 //!
 //! This component handles data processing and analytics for crypto reports,
-//! including insights generation and data transformation.
+//! including insights generation and data transformation. Every report mutation
+//! goes through `record_mutation`, which writes the `report_audit` trail.
+
+use std::collections::BTreeMap;
+
+use crate::services::data_communication::{
+    CallerIdentity, ReportAuditRecord, ReportAuditService, current_caller,
+};
+use crate::state::AppState;
+
+/// Actor recorded for mutations outside an authenticated request (jobs, scripts)
+const SYSTEM_ACTOR: &str = "system";
+
+/// Kind of report mutation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportMutation {
+    Create,
+    Update,
+    Delete,
+}
+
+impl ReportMutation {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
 
 /// Data Manager
 ///
@@ -25,6 +55,20 @@ impl DataManager {
         }
     }
 
+    /// Record a report mutation in `report_audit`, attributed to the current caller
+    ///
+    /// `changes` maps each column the mutation set to its new value (empty for deletes).
+    pub async fn record_mutation(
+        &self,
+        state: &AppState,
+        report_id: i32,
+        mutation: ReportMutation,
+        changes: &BTreeMap<String, serde_json::Value>,
+    ) {
+        let record = audit_record(report_id, mutation, changes, current_caller().as_ref());
+        ReportAuditService::new().record(state, &record).await;
+    }
+
     /// Health check for data manager
     #[must_use]
     pub fn health_check(&self) -> bool {
@@ -32,3 +76,56 @@ impl DataManager {
         true // Will implement actual health check
     }
 }
+
+/// Audit row of a mutation: caller identity, changed field names and the hash of their values
+#[must_use]
+pub fn audit_record(
+    report_id: i32,
+    mutation: ReportMutation,
+    changes: &BTreeMap<String, serde_json::Value>,
+    caller: Option<&CallerIdentity>,
+) -> ReportAuditRecord {
+    // BTreeMap keys serialize sorted, so equal changes always hash the same
+    let canonical = serde_json::to_vec(changes).unwrap_or_default();
+    ReportAuditRecord {
+        report_id,
+        action: mutation.as_str(),
+        api_key_id: caller.and_then(|caller| caller.key_id),
+        actor: caller.map_or_else(|| SYSTEM_ACTOR.to_string(), CallerIdentity::actor),
+        changed_fields: changes.keys().cloned().collect(),
+        changes_hash: blake3::hash(&canonical).to_hex().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::data_communication::{ApiKey, ApiScope};
+
+    #[test]
+    fn test_audit_record() {
+        let caller = CallerIdentity::from(ApiKey {
+            id: 9,
+            name: "editor".to_string(),
+            scopes: vec![ApiScope::Write],
+        });
+        let mut changes = BTreeMap::new();
+        changes.insert("html_content".to_string(), "<p>v2</p>".into());
+        changes.insert("css_content".to_string(), serde_json::Value::Null);
+
+        let record = audit_record(42, ReportMutation::Update, &changes, Some(&caller));
+        assert_eq!(record.action, "update");
+        assert_eq!(record.api_key_id, Some(9));
+        assert_eq!(record.actor, "key:editor");
+        assert_eq!(record.changed_fields, ["css_content", "html_content"]);
+        assert_eq!(record.changes_hash.len(), 64);
+
+        // Same values hash the same; different values do not
+        let again = audit_record(42, ReportMutation::Update, &changes, None);
+        assert_eq!(again.changes_hash, record.changes_hash);
+        assert_eq!((again.actor.as_str(), again.api_key_id), ("system", None));
+        changes.insert("html_content".to_string(), "<p>v3</p>".into());
+        let edited = audit_record(42, ReportMutation::Update, &changes, None);
+        assert_ne!(edited.changes_hash, record.changes_hash);
+    }
+}
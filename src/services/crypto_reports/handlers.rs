@@ -11,11 +11,10 @@ use axum::{
 };
 use flate2::{Compression, write::GzEncoder};
 use std::collections::HashMap;
-use std::collections::hash_map::DefaultHasher;
 use std::error::Error as StdError;
-use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::sync::{Arc, atomic::Ordering};
+use std::time::Instant;
 use tracing::{debug, error, info, warn};
 
 use crate::services::crypto_reports::rendering::{
@@ -26,33 +25,150 @@ use crate::services::crypto_reports::rendering::{
 use crate::state::AppState;
 
 // Import from our specialized components
+use super::rendering::RenderStrategy;
 use super::report_creator::ReportCreator;
 use super::template_orchestrator::TemplateOrchestrator;
+use crate::services::data_communication::{
+    AuthorDataService, CryptoDataService, report_html_max_bytes,
+};
+use crate::services::health_system::template_metrics;
 use crate::services::shared::error::Layer5Result;
+use crate::services::shared::response_builder::cache_control;
+use crate::services::shared::{
+    LANGUAGE_VARY, SingleFlight, build_not_modified_response, cache_bypass_requested,
+    format_http_date, is_not_modified, stream_html_to_gzip,
+};
+
+/// Gzip payload of a rendered page
+pub enum RenderedBody {
+    /// Fully buffered compressed bytes (normal path, cacheable)
+    Buffered(Vec<u8>),
+    /// Compressed on the fly and streamed (oversized content, never cached)
+    Streamed(Body),
+}
+
+impl From<Vec<u8>> for RenderedBody {
+    fn from(data: Vec<u8>) -> Self {
+        Self::Buffered(data)
+    }
+}
+
+impl From<RenderedBody> for Body {
+    fn from(body: RenderedBody) -> Self {
+        match body {
+            RenderedBody::Buffered(data) => Body::from(data),
+            RenderedBody::Streamed(body) => body,
+        }
+    }
+}
 
 /// Rendered content ready for HTTP response
 /// Decouples business logic from HTTP transport
 pub struct RenderedContent {
-    pub data: Vec<u8>,
-    pub cache_control: &'static str,
+    pub data: RenderedBody,
+    /// Per-response override; `None` leaves `Cache-Control` to the route's cache policy
+    pub cache_control: Option<&'static str>,
     pub cache_status: &'static str,
+    /// Source timestamp for the `Last-Modified` header (reports only)
+    pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl RenderedContent {
+    /// Convert into a response, honoring `If-Modified-Since`
+    ///
+    /// Returns a bodiless `304 Not Modified` when the client's copy is still current,
+    /// otherwise the full gzip response.
+    #[must_use]
+    pub fn into_conditional_response(self, headers: &HeaderMap) -> Response {
+        if let Some(last_modified) = self.last_modified
+            && is_not_modified(headers, &last_modified)
+        {
+            debug!("♻️ [Handler] If-Modified-Since matched - returning 304");
+            return build_not_modified_response(self.cache_control, &last_modified);
+        }
+        self.into_response()
+    }
+
+    /// Mark a fresh render forced by `X-Cache-Bypass`: `x-cache: BYPASS`, never stored by
+    /// browsers or shared caches
+    #[must_use]
+    pub fn bypassed(self) -> Self {
+        Self {
+            cache_control: Some(cache_control::NO_CACHE),
+            cache_status: "BYPASS",
+            ..self
+        }
+    }
 }
 
 impl IntoResponse for RenderedContent {
     fn into_response(self) -> Response {
-        Response::builder()
+        let mut builder = Response::builder()
             .status(StatusCode::OK)
-            .header("cache-control", self.cache_control)
             .header("content-type", "text/html; charset=utf-8")
             .header("content-encoding", "gzip")
             .header("x-render-mode", "declarative-shadow-dom")
             .header("x-cache", self.cache_status)
+            // Pages are cached and rendered per visitor language
+            .header("vary", LANGUAGE_VARY);
+        if let Some(cache_control) = self.cache_control {
+            builder = builder.header("cache-control", cache_control);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            builder = builder.header("last-modified", format_http_date(last_modified));
+        }
+        builder
             .body(Body::from(self.data))
             .unwrap_or_else(|_| Response::new(Body::from("Response build error")))
             .into_response()
     }
 }
 
+/// Declarative Shadow DOM report template
+const DSD_TEMPLATE: &str = "crypto/routes/reports/view_dsd.html";
+
+/// DSD render outcome, shared by all requests coalesced onto one render
+#[derive(Clone)]
+struct DsdRender {
+    payload: DsdPayload,
+    cache_status: &'static str,
+    last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Clone)]
+enum DsdPayload {
+    /// Compressed page (as cached)
+    Compressed(Arc<Vec<u8>>),
+    /// Oversized HTML, gzip-streamed separately for each request
+    Oversized(Arc<String>),
+}
+
+impl DsdRender {
+    /// Response content; `joined` marks a request that waited on another request's render
+    fn into_content(self, joined: bool) -> RenderedContent {
+        let (data, cache_status) = match self.payload {
+            DsdPayload::Compressed(data) => (
+                Arc::unwrap_or_clone(data).into(),
+                if joined && self.cache_status == "MISS" {
+                    "COALESCED"
+                } else {
+                    self.cache_status
+                },
+            ),
+            DsdPayload::Oversized(html) => (
+                RenderedBody::Streamed(stream_html_to_gzip(Arc::unwrap_or_clone(html))),
+                self.cache_status,
+            ),
+        };
+        RenderedContent {
+            data,
+            cache_control: None,
+            cache_status,
+            last_modified: self.last_modified,
+        }
+    }
+}
+
 /// Crypto Handlers
 ///
 /// Contains all HTTP request handlers for crypto reports-related operations.
@@ -61,6 +177,8 @@ impl IntoResponse for RenderedContent {
 pub struct CryptoHandlers {
     pub report_creator: ReportCreator,
     pub template_orchestrator: TemplateOrchestrator,
+    /// In-flight DSD renders keyed by page cache key
+    dsd_renders: SingleFlight<Layer5Result<DsdRender>>,
 }
 
 impl Default for CryptoHandlers {
@@ -79,6 +197,7 @@ impl CryptoHandlers {
         Self {
             report_creator,
             template_orchestrator,
+            dsd_renders: SingleFlight::new(),
         }
     }
 
@@ -111,7 +230,6 @@ impl CryptoHandlers {
     pub fn create_cached_response(&self, html: String, cache_status: &str) -> Response {
         Response::builder()
             .status(StatusCode::OK)
-            .header("cache-control", "public, max-age=15")
             .header("content-type", "text/html; charset=utf-8")
             .header("x-cache", cache_status)
             .body(html)
@@ -132,7 +250,6 @@ impl CryptoHandlers {
     pub fn create_compressed_response(compressed_data: Vec<u8>) -> Response {
         Response::builder()
             .status(StatusCode::OK)
-            .header("cache-control", "public, max-age=15")
             .header("x-cache", "compressed")
             .header("content-type", "text/html; charset=utf-8")
             .header("content-encoding", "gzip")
@@ -493,10 +610,12 @@ impl CryptoHandlers {
         &self,
         state: &Arc<AppState>,
         page: i64,
+        language: &str,
+        bypass_cache: bool,
     ) -> Layer5Result<RenderedContent> {
         info!(
-            "📋 Layer 5: Nhận yêu cầu cho crypto reports list page {}",
-            page
+            "📋 Layer 5: Nhận yêu cầu cho crypto reports list page {} (lang: {})",
+            page, language
         );
 
         // Increment request counter to monitor performance
@@ -512,12 +631,23 @@ impl CryptoHandlers {
 
         // BƯỚC 1: ỦY QUYỀN CHO LAYER 3 ĐỂ XỬ LÝ CACHE VÀ DATABASE (returns compressed data)
         let data_service = &self.report_creator.data_service; // Truy cập data_service
-        let per_page: i64 = 10;
+        let per_page = crate::services::shared::REPORTS_LIST_PAGE_SIZE;
 
-        match data_service
-            .fetch_reports_list_with_cache(state, page, per_page)
-            .await
-        {
+        let result = if bypass_cache {
+            info!(
+                "🚧 Layer 5: Cache bypass - rendering reports list page {} fresh",
+                page
+            );
+            data_service
+                .render_reports_list(state, page, per_page, language)
+                .await
+                .map(Some)
+        } else {
+            data_service
+                .fetch_reports_list_with_cache(state, page, per_page, language)
+                .await
+        };
+        match result {
             Ok(Some(compressed_data)) => {
                 let size_kb = compressed_data.len() / 1024;
                 info!(
@@ -525,10 +655,16 @@ impl CryptoHandlers {
                     page, size_kb
                 );
 
-                Ok(RenderedContent {
-                    data: compressed_data,
-                    cache_control: "public, max-age=60",
+                let content = RenderedContent {
+                    data: compressed_data.into(),
+                    cache_control: None,
                     cache_status: "Layer5-Compressed",
+                    last_modified: None,
+                };
+                Ok(if bypass_cache {
+                    content.bypassed()
+                } else {
+                    content
                 })
             }
             Ok(None) => {
@@ -691,10 +827,13 @@ impl CryptoHandlers {
     /// Render Crypto Index DSD (Latest Report)
     /// Encapsulates all logic for the `crypto_index` route
     ///
+    /// Concurrent cache misses for the same page share a single render; the waiting
+    /// requests are answered with `x-cache: COALESCED`. An admin `X-Cache-Bypass`
+    /// request skips the cache and the coalescing and always renders (`x-cache: BYPASS`).
+    ///
     /// # Errors
     ///
     /// Returns error if database fetch or template rendering fails
-    #[allow(clippy::too_many_lines)] // Orchestration function requiring multiple steps (cache, DB, DSD, metadata, rendering)
     #[allow(clippy::needless_pass_by_value)] // Arc is passed by value to maintain API compatibility
     pub async fn render_crypto_index_dsd(
         &self,
@@ -720,48 +859,38 @@ impl CryptoHandlers {
             }
         );
 
-        // STEP 1: Quick cache check with default language (Vietnamese - 90% of traffic)
-        // This avoids expensive language detection for cache hits
-        let data_service = &self.report_creator.data_service;
-        let default_language = "vi";
+        // STEP 1: Cache check keyed by the visitor's language and render strategy
+        // (checking a default-language key first would serve Vietnamese pages to English visitors)
+        let strategies = &self.report_creator.render_strategies;
+        let strategy = strategies.select(params, headers);
+        let cache_variant = strategies.cache_variant(strategy.as_ref());
+        let preferred_language =
+            Self::detect_preferred_language(params, headers).unwrap_or_else(|| "vi".to_string());
 
-        if let Ok(Some(cached_compressed)) = data_service
-            .get_rendered_report_dsd_compressed(state, report_id_value, default_language)
-            .await
-        {
+        if cache_bypass_requested(headers) {
             info!(
-                "✅ [Handler] DSD cache HIT (default language) - returning compressed HTML for {}",
-                if report_id_value == -1 {
-                    "latest".to_string()
-                } else {
-                    format!("#{report_id_value}")
-                }
+                "🚧 [Handler] Cache bypass - rendering report {} (lang: {}) fresh",
+                report_id_value, preferred_language
             );
-
-            return Ok(RenderedContent {
-                data: cached_compressed,
-                cache_control: "public, max-age=300",
-                cache_status: "HIT",
-            });
+            return self
+                .render_report_dsd_uncached(
+                    state,
+                    report_id_value,
+                    &preferred_language,
+                    strategy.as_ref(),
+                    cache_variant,
+                    &chart_modules_content,
+                )
+                .await
+                .map(|render| render.into_content(false).bypassed());
         }
 
-        // STEP 1.1: Cache miss with default language - now detect preferred language for rendering
-        let preferred_language =
-            Self::detect_preferred_language(params, headers).unwrap_or_else(|| "vi".to_string());
-
-        debug!(
-            "🔍 [Handler] DSD cache MISS (default language) - detected preferred language: {}",
-            preferred_language
-        );
-
-        // STEP 1.2: If preferred language differs from default, try cache with preferred language
-        if preferred_language != default_language
-            && let Ok(Some(cached_compressed)) = data_service
-                .get_rendered_report_dsd_compressed(state, report_id_value, &preferred_language)
-                .await
+        if let Some(cached) = self
+            .cached_report_dsd(state, report_id_value, &preferred_language, cache_variant)
+            .await
         {
             info!(
-                "✅ [Handler] DSD cache HIT (preferred language: {}) - returning compressed HTML for {}",
+                "✅ [Handler] DSD cache HIT (language: {}) - returning compressed HTML for {}",
                 preferred_language,
                 if report_id_value == -1 {
                     "latest".to_string()
@@ -769,15 +898,89 @@ impl CryptoHandlers {
                     format!("#{report_id_value}")
                 }
             );
+            return Ok(cached.into_content(false));
+        }
 
-            return Ok(RenderedContent {
-                data: cached_compressed,
-                cache_control: "public, max-age=300",
-                cache_status: "HIT",
-            });
+        // STEP 1.1: Coalesce concurrent misses - one DB fetch + render per cache key,
+        // every other request for the same page waits for that result
+        let cache_key = CryptoDataService::report_dsd_cache_key(
+            report_id_value,
+            &preferred_language,
+            cache_variant,
+        );
+        let flight = self
+            .dsd_renders
+            .run(&cache_key, || async {
+                // Double-check: a previous flight may have cached the page just before this one
+                if let Some(cached) = self
+                    .cached_report_dsd(state, report_id_value, &preferred_language, cache_variant)
+                    .await
+                {
+                    return Ok(cached);
+                }
+                self.render_report_dsd_uncached(
+                    state,
+                    report_id_value,
+                    &preferred_language,
+                    strategy.as_ref(),
+                    cache_variant,
+                    &chart_modules_content,
+                )
+                .await
+            })
+            .await;
+
+        let joined = flight.is_joined();
+        if joined {
+            debug!(
+                "🤝 [Handler] DSD render coalesced with in-flight request for {}",
+                cache_key
+            );
         }
+        flight
+            .into_inner()
+            .map(|render| render.into_content(joined))
+    }
+
+    /// Cached compressed DSD page plus its `Last-Modified`, if present
+    async fn cached_report_dsd(
+        &self,
+        state: &Arc<AppState>,
+        report_id_value: i32,
+        language: &str,
+        cache_variant: Option<&str>,
+    ) -> Option<DsdRender> {
+        let data_service = &self.report_creator.data_service;
+        let cached_compressed = data_service
+            .get_rendered_report_dsd_compressed(state, report_id_value, language, cache_variant)
+            .await
+            .ok()??;
+
+        Some(DsdRender {
+            payload: DsdPayload::Compressed(Arc::new(cached_compressed)),
+            cache_status: "HIT",
+            last_modified: data_service
+                .get_report_last_modified(state, report_id_value)
+                .await,
+        })
+    }
 
+    /// DSD miss path: DB fetch, render, compress and cache
+    ///
+    /// Runs once per cache key at a time (see `render_crypto_index_dsd`), except for
+    /// cache-bypass requests.
+    #[allow(clippy::too_many_lines)] // Orchestration function requiring multiple steps (DB, DSD, metadata, rendering)
+    async fn render_report_dsd_uncached(
+        &self,
+        state: &Arc<AppState>,
+        report_id_value: i32,
+        preferred_language: &str,
+        strategy: &dyn RenderStrategy,
+        cache_variant: Option<&str>,
+        chart_modules_content: &str,
+    ) -> Layer5Result<DsdRender> {
         debug!("🔍 [Handler] DSD cache MISS - generating fresh HTML");
+        let data_service = &self.report_creator.data_service;
 
         // STEP 2: Fetch report from database (uses existing data cache)
         let report_result = if report_id_value == -1 {
@@ -804,30 +1007,34 @@ impl CryptoHandlers {
             }
         };
 
-        // STEP 3: Generate shadow_dom_token
-        let mut hasher = DefaultHasher::new();
-        report.id.hash(&mut hasher);
-        report.created_at.hash(&mut hasher);
-        let shadow_dom_token = format!("sb_{:x}", hasher.finish());
+        // STEP 3: Generate the signed, expiring shadow_dom_token
+        let shadow_dom_token =
+            crate::services::shared::generate_sandbox_token(report.id, &report.created_at);
 
-        // STEP 4: Get chart modules content and generate shadow DOM content
-        let sandboxed_report = self
-            .report_creator
-            .create_sandboxed_report(&report, Some(chart_modules_content.as_str()));
-        let shadow_dom_content = self.report_creator.generate_shadow_dom_content(
-            &sandboxed_report,
-            Some(&preferred_language),
-            Some(chart_modules_content.as_str()),
-        );
+        // STEP 4: Render the report body with the selected strategy
+        let report_body = strategy.render(&report, preferred_language, Some(chart_modules_content));
 
         info!(
-            "🌐 [Handler] render_crypto_index_dsd rendering with language: {}",
-            preferred_language
+            "🌐 [Handler] render_crypto_index_dsd rendering with language: {}, strategy: {}",
+            preferred_language,
+            strategy.name()
         );
 
+        // STEP 4.1: Fetch report authors for byline and structured data
+        let report_authors = match AuthorDataService::new()
+            .fetch_report_authors(state, report.id)
+            .await
+        {
+            Ok(authors) => authors,
+            Err(e) => {
+                warn!("⚠️ [Handler] Failed to fetch report authors: {}", e);
+                vec![] // Fallback to organization byline
+            }
+        };
+
         // STEP 5: Generate GEO metadata for AI bots (Grok, GPT, Claude)
         let (geo_meta_tags, geo_json_ld, geo_title) =
-            generate_complete_geo_metadata(&report, Some(&preferred_language));
+            generate_complete_geo_metadata(&report, Some(preferred_language), &report_authors);
         debug!(
             "📊 [Handler] GEO metadata generated for report {} - title: {}",
             report.id, geo_title
@@ -847,7 +1054,7 @@ impl CryptoHandlers {
 
         // STEP 5.2: Generate breadcrumbs and related reports data
         let (breadcrumb_items, breadcrumbs_schema, related_reports) =
-            generate_breadcrumbs_and_related(report.id, &related_reports_data);
+            generate_breadcrumbs_and_related(report.id, report.created_at, &related_reports_data);
         debug!(
             "📊 [Handler] Breadcrumbs and {} related reports generated for report {}",
             related_reports.len(),
@@ -857,9 +1064,11 @@ impl CryptoHandlers {
         // STEP 6: Render template with GEO metadata
         let mut context = tera::Context::new();
         context.insert("report", &report);
+        context.insert("current_lang", preferred_language);
         context.insert("shadow_dom_token", &shadow_dom_token);
-        context.insert("shadow_dom_content", &shadow_dom_content);
-        context.insert("chart_modules_content", chart_modules_content.as_ref());
+        context.insert("report_body", &report_body);
+        context.insert("render_strategy", strategy.name());
+        context.insert("chart_modules_content", chart_modules_content);
         context.insert(
             "websocket_url",
             &std::env::var("WEBSOCKET_SERVICE_URL")
@@ -873,12 +1082,19 @@ impl CryptoHandlers {
         context.insert("breadcrumb_items", &breadcrumb_items);
         context.insert("breadcrumbs_schema", &breadcrumbs_schema);
         context.insert("related_reports", &related_reports);
-
-        let html = match state
-            .tera
-            .render("crypto/routes/reports/view_dsd.html", &context)
-        {
-            Ok(html) => html,
+        // Byline linking to author archive pages
+        context.insert("report_authors", &report_authors);
+
+        let render_started = Instant::now();
+        let html = match state.tera.render(DSD_TEMPLATE, &context) {
+            Ok(html) => {
+                template_metrics().record_render(
+                    DSD_TEMPLATE,
+                    render_started.elapsed(),
+                    html.len(),
+                );
+                html
+            }
             Err(e) => {
                 error!("❌ [Handler] Failed to render DSD template: {}", e);
                 return Err(crate::services::shared::error::Layer5Error::TemplateRender(
@@ -887,9 +1103,31 @@ impl CryptoHandlers {
             }
         };
 
+        // STEP 6.1: Oversized guard - stream without caching so a pathological report
+        // neither buffers its whole gzip payload nor evicts the L1 cache
+        let html_limit = report_html_max_bytes();
+        if html.len() > html_limit {
+            warn!(
+                report_id = report.id,
+                html_bytes = html.len(),
+                limit_bytes = html_limit,
+                language = %preferred_language,
+                "⚠️ [Handler] Oversized report HTML - streaming response, skipping cache"
+            );
+
+            return Ok(DsdRender {
+                payload: DsdPayload::Oversized(Arc::new(html)),
+                cache_status: "BYPASS-OVERSIZED",
+                last_modified: Some(report.updated_at),
+            });
+        }
+
         // STEP 7: Compress HTML
         let compressed_data = match Self::compress_html_to_gzip(&html) {
-            Ok(data) => data,
+            Ok(data) => {
+                template_metrics().record_compression(DSD_TEMPLATE, html.len(), data.len());
+                data
+            }
             Err(e) => {
                 error!("❌ [Handler] Failed to compress DSD HTML: {}", e);
                 return Err(crate::services::shared::error::Layer5Error::Compression(
@@ -905,20 +1143,26 @@ impl CryptoHandlers {
                 state,
                 report_id_value,
                 &compressed_data,
-                &preferred_language,
+                preferred_language,
+                cache_variant,
             )
             .await
         {
             warn!("⚠️ [Handler] Failed to cache DSD compressed content: {}", e);
         }
 
+        // STEP 8.1: Remember Last-Modified so route-level cache hits can answer conditional GETs
+        data_service
+            .cache_report_last_modified(state, report_id_value, &report.updated_at)
+            .await;
+
         info!("✅ [Handler] render_crypto_index_dsd completed successfully");
 
         // STEP 9: Return compressed response
-        Ok(RenderedContent {
-            data: compressed_data,
-            cache_control: "public, max-age=300",
+        Ok(DsdRender {
+            payload: DsdPayload::Compressed(Arc::new(compressed_data)),
             cache_status: "MISS",
+            last_modified: Some(report.updated_at),
         })
     }
 
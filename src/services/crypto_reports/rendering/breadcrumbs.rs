@@ -7,12 +7,11 @@
 //!
 //! Part of Layer 5 Business Logic - Rendering strategies
 
-use crate::services::data_communication::ReportSummaryData;
+use crate::services::data_communication::{ReportSummaryData, report_path};
+use crate::services::shared::public_origin;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-/// Base URL for the website (used in JSON-LD schema)
-const BASE_URL: &str = "https://cryptodashboard.io";
-
 /// Related report data for template rendering
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RelatedReportItem {
@@ -38,7 +37,7 @@ pub struct BreadcrumbItem {
 /// Creates a hierarchical breadcrumb trail:
 /// Home > Crypto Reports > Report #ID
 #[must_use]
-pub fn generate_breadcrumb_items(report_id: i32) -> Vec<BreadcrumbItem> {
+pub fn generate_breadcrumb_items(report_id: i32, created_at: DateTime<Utc>) -> Vec<BreadcrumbItem> {
     vec![
         BreadcrumbItem {
             name: "Trang chu".to_string(),
@@ -58,7 +57,7 @@ pub fn generate_breadcrumb_items(report_id: i32) -> Vec<BreadcrumbItem> {
             name: format!("Report #{report_id}"),
             name_vi: format!("Bao cao #{report_id}"),
             name_en: format!("Report #{report_id}"),
-            url: format!("/crypto_report/{report_id}"),
+            url: report_path(report_id, created_at),
             is_current: true,
         },
     ]
@@ -71,8 +70,9 @@ pub fn generate_breadcrumb_items(report_id: i32) -> Vec<BreadcrumbItem> {
 /// - AI bots navigate site structure
 /// - Rich snippets in search results
 #[must_use]
-pub fn generate_breadcrumbs_schema(report_id: i32) -> String {
-    let items = generate_breadcrumb_items(report_id);
+pub fn generate_breadcrumbs_schema(report_id: i32, created_at: DateTime<Utc>) -> String {
+    let items = generate_breadcrumb_items(report_id, created_at);
+    let origin = public_origin();
 
     let list_elements: Vec<serde_json::Value> = items
         .iter()
@@ -82,7 +82,7 @@ pub fn generate_breadcrumbs_schema(report_id: i32) -> String {
                 "@type": "ListItem",
                 "position": index + 1,
                 "name": item.name_en,
-                "item": format!("{}{}", BASE_URL, item.url)
+                "item": format!("{origin}{}", item.url)
             })
         })
         .collect();
@@ -101,6 +101,150 @@ pub fn generate_breadcrumbs_schema(report_id: i32) -> String {
     )
 }
 
+/// Generate breadcrumb items for a reports list page
+///
+/// Creates the trail Home > Crypto Reports, followed by the crumb of a tag / date
+/// narrowing (`/crypto_reports_list?tag=btc`) when the page has one, and by
+/// Page N past the first page
+#[must_use]
+pub fn generate_list_breadcrumb_items(
+    page: i64,
+    filter: Option<&BreadcrumbItem>,
+) -> Vec<BreadcrumbItem> {
+    let mut items = vec![
+        BreadcrumbItem {
+            name: "Trang chu".to_string(),
+            name_vi: "Trang chu".to_string(),
+            name_en: "Home".to_string(),
+            url: "/".to_string(),
+            is_current: false,
+        },
+        BreadcrumbItem {
+            name: "Bao cao".to_string(),
+            name_vi: "Bao cao".to_string(),
+            name_en: "Reports".to_string(),
+            url: "/crypto_reports_list".to_string(),
+            is_current: false,
+        },
+    ];
+    items.extend(filter.cloned());
+    if page > 1 {
+        let list_url = items
+            .last()
+            .map(|item| item.url.clone())
+            .unwrap_or_default();
+        let separator = if list_url.contains('?') { '&' } else { '?' };
+        items.push(BreadcrumbItem {
+            name: format!("Page {page}"),
+            name_vi: format!("Trang {page}"),
+            name_en: format!("Page {page}"),
+            url: format!("{list_url}{separator}page={page}"),
+            is_current: false,
+        });
+    }
+    for item in &mut items {
+        item.is_current = false;
+    }
+    if let Some(last) = items.last_mut() {
+        last.is_current = true;
+    }
+    items
+}
+
+/// Generate JSON-LD `CollectionPage` + `BreadcrumbList` schema for a reports list page
+///
+/// The `CollectionPage` lists the reports shown on the page as an `ItemList` and
+/// points to the `BreadcrumbList` of `generate_list_breadcrumb_items`.
+///
+/// # Arguments
+/// * `page` - 1-based page number
+/// * `filter` - Crumb of the tag / date narrowing of the list, if any
+/// * `language` - Language of the page (`vi` / `en`)
+/// * `report_paths` - Paths of the listed reports, in display order
+#[must_use]
+pub fn generate_list_page_schema(
+    page: i64,
+    filter: Option<&BreadcrumbItem>,
+    language: &str,
+    report_paths: &[&str],
+) -> String {
+    let items = generate_list_breadcrumb_items(page, filter);
+    let origin = public_origin();
+    let page_url = items
+        .last()
+        .map(|item| format!("{origin}{}", item.url))
+        .unwrap_or_default();
+    let name = items
+        .iter()
+        .skip(1)
+        .map(|item| item.name_en.as_str())
+        .collect::<Vec<_>>()
+        .join(" - ");
+
+    let crumbs: Vec<serde_json::Value> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            serde_json::json!({
+                "@type": "ListItem",
+                "position": index + 1,
+                "name": item.name_en,
+                "item": format!("{origin}{}", item.url)
+            })
+        })
+        .collect();
+    let reports: Vec<serde_json::Value> = report_paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            serde_json::json!({
+                "@type": "ListItem",
+                "position": index + 1,
+                "url": format!("{origin}{path}")
+            })
+        })
+        .collect();
+
+    let schema = serde_json::json!({
+        "@context": "https://schema.org",
+        "@graph": [
+            {
+                "@type": "CollectionPage",
+                "@id": page_url,
+                "url": page_url,
+                "name": format!("Crypto Dashboard - {name}"),
+                "inLanguage": language,
+                "isPartOf": {
+                    "@type": "WebSite",
+                    "name": "Crypto Dashboard",
+                    "url": format!("{origin}/")
+                },
+                "breadcrumb": { "@id": format!("{page_url}#breadcrumb") },
+                "mainEntity": {
+                    "@type": "ItemList",
+                    "numberOfItems": reports.len(),
+                    "itemListElement": reports
+                }
+            },
+            {
+                "@type": "BreadcrumbList",
+                "@id": format!("{page_url}#breadcrumb"),
+                "itemListElement": crumbs
+            }
+        ]
+    });
+
+    // Filter names may come from user input; keep them from closing the script
+    format!(
+        r#"<script type="application/ld+json">
+{}
+</script>"#,
+        serde_json::to_string_pretty(&schema)
+            .unwrap_or_default()
+            .replace('<', "\\u003c")
+    )
+}
+
 /// Convert `ReportSummaryData` to `RelatedReportItem` for template
 ///
 /// Formats dates to Vietnam timezone (UTC+7) for display
@@ -113,7 +257,7 @@ pub fn format_related_report(report: &ReportSummaryData) -> RelatedReportItem {
         created_at: report.created_at.to_rfc3339(),
         created_date_display: dt.format("%d/%m/%Y").to_string(),
         created_time_display: format!("{} UTC+7", dt.format("%H:%M")),
-        url: format!("/crypto_report/{}", report.id),
+        url: report_path(report.id, report.created_at),
     }
 }
 
@@ -131,10 +275,11 @@ pub fn format_related_reports(reports: &[ReportSummaryData]) -> Vec<RelatedRepor
 #[must_use]
 pub fn generate_breadcrumbs_and_related(
     report_id: i32,
+    created_at: DateTime<Utc>,
     related_reports_data: &[ReportSummaryData],
 ) -> (Vec<BreadcrumbItem>, String, Vec<RelatedReportItem>) {
-    let breadcrumb_items = generate_breadcrumb_items(report_id);
-    let breadcrumbs_schema = generate_breadcrumbs_schema(report_id);
+    let breadcrumb_items = generate_breadcrumb_items(report_id, created_at);
+    let breadcrumbs_schema = generate_breadcrumbs_schema(report_id, created_at);
     let related_reports = format_related_reports(related_reports_data);
 
     (breadcrumb_items, breadcrumbs_schema, related_reports)
@@ -147,19 +292,69 @@ mod tests {
 
     #[test]
     fn test_generate_breadcrumb_items() {
-        let items = generate_breadcrumb_items(123);
+        let created_at = Utc::now();
+        let items = generate_breadcrumb_items(123, created_at);
         assert_eq!(items.len(), 3);
         assert_eq!(items[0].url, "/");
         assert_eq!(items[1].url, "/crypto_reports_list");
-        assert_eq!(items[2].url, "/crypto_report/123");
+        assert_eq!(items[2].url, report_path(123, created_at));
+        assert!(items[2].url.starts_with("/crypto_report/123-"));
         assert!(items[2].is_current);
     }
 
+    #[test]
+    fn test_generate_list_breadcrumb_items() {
+        let items = generate_list_breadcrumb_items(1, None);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].url, "/crypto_reports_list");
+        assert!(items[1].is_current && !items[0].is_current);
+
+        let items = generate_list_breadcrumb_items(3, None);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[2].url, "/crypto_reports_list?page=3");
+        assert!(items[2].is_current && !items[1].is_current);
+
+        let tag = BreadcrumbItem {
+            name: "Tag: btc".to_string(),
+            name_vi: "The: btc".to_string(),
+            name_en: "Tag: btc".to_string(),
+            url: "/crypto_reports_list?tag=btc".to_string(),
+            is_current: false,
+        };
+        let items = generate_list_breadcrumb_items(2, Some(&tag));
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[2].url, "/crypto_reports_list?tag=btc");
+        assert_eq!(items[3].url, "/crypto_reports_list?tag=btc&page=2");
+        assert!(items[3].is_current && !items[2].is_current);
+    }
+
+    #[test]
+    fn test_generate_list_page_schema() {
+        let schema =
+            generate_list_page_schema(2, None, "en", &["/crypto_report/9-a", "/crypto_report/8-b"]);
+        assert!(schema.contains("\"CollectionPage\""));
+        assert!(schema.contains("\"BreadcrumbList\""));
+        assert!(schema.contains("\"https://cryptodashboard.me/crypto_reports_list?page=2\""));
+        assert!(schema.contains("\"https://cryptodashboard.me/crypto_report/9-a\""));
+        assert!(schema.contains("\"numberOfItems\": 2"));
+        assert!(schema.contains("\"inLanguage\": \"en\""));
+
+        let tag = BreadcrumbItem {
+            name: "</script>".to_string(),
+            name_vi: "</script>".to_string(),
+            name_en: "</script>".to_string(),
+            url: "/crypto_reports_list?tag=x".to_string(),
+            is_current: false,
+        };
+        let schema = generate_list_page_schema(1, Some(&tag), "vi", &[]);
+        assert_eq!(schema.matches("</script>").count(), 1);
+    }
+
     #[test]
     fn test_generate_breadcrumbs_schema() {
-        let schema = generate_breadcrumbs_schema(456);
+        let schema = generate_breadcrumbs_schema(456, Utc::now());
         assert!(schema.contains("BreadcrumbList"));
         assert!(schema.contains("Report #456"));
-        assert!(schema.contains("https://cryptodashboard.io"));
+        assert!(schema.contains("\"https://cryptodashboard.me/crypto_reports_list\""));
     }
 }
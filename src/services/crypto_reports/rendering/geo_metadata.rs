@@ -4,21 +4,26 @@
 //! for crypto reports. It helps AI bots (Grok, GPT, Claude) better understand
 //! page content through:
 //! - Dynamic Open Graph and Twitter Card meta tags
-//! - JSON-LD structured data (Schema.org Article)
+//! - hreflang alternates linking the vi/en variants of each report
+//! - RSS alternates for the site feed and the feeds of the report's topic tags
+//! - JSON-LD structured data (Schema.org Article, author `ProfilePage`), plus
+//!   `FAQPage` / `HowTo` derived from report sections (see `section_schema`)
 //! - Semantic HTML recommendations
 
 use serde::Serialize;
 
+use super::section_schema::{SectionSchemaRules, generate_section_json_ld, json_ld_script};
 use super::shared::Report;
+use crate::services::data_communication::{AuthorData, REPORT_TITLE_VI, report_path};
+use crate::services::shared::{
+    LanguageAlternate, feed_query, public_origin, report_language_alternates, report_tags,
+};
 
-/// Base URL for the website
-const SITE_BASE_URL: &str = "https://cryptodashboard.me";
+/// Path of the publisher logo (absolute URLs use the request's public origin)
+const PUBLISHER_LOGO_PATH: &str = "/shared_assets/images/logo.png";
 
-/// Default logo URL for publisher
-const PUBLISHER_LOGO_URL: &str = "https://cryptodashboard.me/shared_assets/images/logo.png";
-
-/// Default OG image URL
-const DEFAULT_OG_IMAGE: &str = "https://cryptodashboard.me/shared_assets/images/image.jpg";
+/// Path of the default OG image
+const DEFAULT_OG_IMAGE_PATH: &str = "/shared_assets/images/image.jpg";
 
 /// GEO Metadata container
 ///
@@ -49,6 +54,34 @@ pub struct GeoMetadata {
     pub date_display_en: String,
     /// OG image URL
     pub og_image: String,
+    /// Report authors in byline order (empty = published by the organization)
+    pub authors: Vec<GeoAuthor>,
+    /// Language variants (vi, en and `x-default`)
+    pub alternates: Vec<LanguageAlternate>,
+    /// `false` emits `noindex, nofollow` robots meta tags
+    pub indexable: bool,
+    /// Topic tags whose RSS feeds are advertised (see `with_feed_tags`)
+    pub feed_tags: Vec<&'static str>,
+}
+
+/// Report author as exposed in meta tags and JSON-LD
+#[derive(Debug, Clone, Serialize)]
+pub struct GeoAuthor {
+    pub name: String,
+    /// Absolute URL of the author archive page
+    pub url: String,
+    /// External profile URL (`sameAs`)
+    pub profile_url: Option<String>,
+}
+
+impl From<&AuthorData> for GeoAuthor {
+    fn from(author: &AuthorData) -> Self {
+        Self {
+            name: author.name.clone(),
+            url: format!("{}{}", public_origin(), author.archive_path()),
+            profile_url: author.profile_url.clone(),
+        }
+    }
 }
 
 impl GeoMetadata {
@@ -76,7 +109,7 @@ impl GeoMetadata {
 
         // Generate titles
         let title_vi = format!(
-            "Phân Tích Thị Trường Crypto #{} - {}",
+            "{REPORT_TITLE_VI} #{} - {}",
             report_id,
             vn_time.format("%d/%m/%Y")
         );
@@ -98,8 +131,9 @@ impl GeoMetadata {
         );
         let description = description_vi.clone();
 
-        // Generate canonical URL
-        let canonical_url = format!("{SITE_BASE_URL}/crypto_report/{report_id}");
+        // Generate canonical URL (slug form, see `report_path`) on the public origin
+        let origin = public_origin();
+        let canonical_url = format!("{origin}{}", report_path(report_id, created_at));
 
         Self {
             report_id,
@@ -113,8 +147,39 @@ impl GeoMetadata {
             date_published,
             date_display_vi,
             date_display_en,
-            og_image: DEFAULT_OG_IMAGE.to_string(),
+            og_image: format!("{origin}{DEFAULT_OG_IMAGE_PATH}"),
+            authors: Vec::new(),
+            alternates: report_language_alternates(report_id, created_at),
+            indexable: report.indexable,
+            feed_tags: Vec::new(),
+        }
+    }
+
+    /// Attach the report's authors
+    #[must_use]
+    pub fn with_authors(mut self, authors: &[AuthorData]) -> Self {
+        self.authors = authors.iter().map(GeoAuthor::from).collect();
+        self
+    }
+
+    /// Advertise the tag feeds of the report content `html` (in the page language)
+    #[must_use]
+    pub fn with_feed_tags(mut self, html: &str) -> Self {
+        self.feed_tags = report_tags(html);
+        self
+    }
+
+    /// Byline for `author` meta tags (organization name when no author is linked)
+    #[must_use]
+    pub fn byline(&self) -> String {
+        if self.authors.is_empty() {
+            return "CryptoDashboard".to_string();
         }
+        self.authors
+            .iter()
+            .map(|author| author.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
     }
 }
 
@@ -164,8 +229,9 @@ pub fn generate_meta_tags(metadata: &GeoMetadata, language: Option<&str>) -> Str
     <meta property="og:image:height" content="630" />
     <meta property="og:site_name" content="CryptoDashboard" />
     <meta property="og:locale" content="{locale}" />
+    <meta property="og:locale:alternate" content="{locale_alternate}" />
     <meta property="article:published_time" content="{published}" />
-    <meta property="article:author" content="CryptoDashboard" />
+    <meta property="article:author" content="{byline}" />
     <meta property="article:section" content="Cryptocurrency" />
     <meta property="article:tag" content="Bitcoin" />
     <meta property="article:tag" content="Cryptocurrency" />
@@ -180,21 +246,74 @@ pub fn generate_meta_tags(metadata: &GeoMetadata, language: Option<&str>) -> Str
     <meta name="twitter:creator" content="@cryptodashboard" />
 
     <!-- Additional SEO Meta Tags -->
-    <meta name="robots" content="index, follow, max-image-preview:large" />
-    <meta name="author" content="CryptoDashboard" />
+    <meta name="robots" content="{robots}" />
+    <meta name="author" content="{byline}" />
     <meta name="keywords" content="crypto, bitcoin, ethereum, market analysis, BTC, ETH, cryptocurrency, trading" />"#,
             description = escape_html_attr(description),
             title = escape_html_attr(title),
             canonical = &metadata.canonical_url,
             og_image = &metadata.og_image,
             locale = if lang == "en" { "en_US" } else { "vi_VN" },
+            locale_alternate = if lang == "en" { "vi_VN" } else { "en_US" },
             published = &metadata.date_published,
+            byline = escape_html_attr(&metadata.byline()),
+            robots = if metadata.indexable {
+                "index, follow, max-image-preview:large"
+            } else {
+                "noindex, nofollow"
+            },
         ),
     );
+    html.push_str(&generate_hreflang_links(metadata));
+    html.push_str(&generate_feed_links(metadata, lang));
 
     html
 }
 
+/// Generate `<link rel="alternate" type="application/rss+xml">` tags for the site
+/// feed and the report's tag feeds in `language`
+#[must_use]
+pub fn generate_feed_links(metadata: &GeoMetadata, language: &str) -> String {
+    let feed_url =
+        |tag: Option<&str>| format!("{}/rss.xml{}", public_origin(), feed_query(language, tag));
+    let mut html = String::from("\n\n    <!-- Feeds -->");
+    let _ = std::fmt::Write::write_fmt(
+        &mut html,
+        format_args!(
+            "\n    <link rel=\"alternate\" type=\"application/rss+xml\" title=\"CryptoDashboard RSS\" href=\"{}\" />",
+            escape_html_attr(&feed_url(None))
+        ),
+    );
+    for tag in &metadata.feed_tags {
+        let _ = std::fmt::Write::write_fmt(
+            &mut html,
+            format_args!(
+                "\n    <link rel=\"alternate\" type=\"application/rss+xml\" title=\"CryptoDashboard RSS - {}\" href=\"{}\" />",
+                tag.to_uppercase(),
+                escape_html_attr(&feed_url(Some(tag)))
+            ),
+        );
+    }
+    html
+}
+
+/// Generate `<link rel="alternate" hreflang>` tags for the report's language variants
+#[must_use]
+pub fn generate_hreflang_links(metadata: &GeoMetadata) -> String {
+    let mut html = String::from("\n\n    <!-- Language Alternates (hreflang) -->");
+    for alternate in &metadata.alternates {
+        let _ = std::fmt::Write::write_fmt(
+            &mut html,
+            format_args!(
+                "\n    <link rel=\"alternate\" hreflang=\"{}\" href=\"{}\" />",
+                alternate.hreflang,
+                escape_html_attr(&alternate.href)
+            ),
+        );
+    }
+    html
+}
+
 /// Generate JSON-LD structured data for Schema.org Article
 ///
 /// Creates structured data that helps AI bots and search engines
@@ -235,18 +354,14 @@ pub fn generate_json_ld(metadata: &GeoMetadata, language: Option<&str>) -> Strin
             width: 1200,
             height: 630,
         },
-        author: JsonLdOrganization {
-            type_field: "Organization",
-            name: "CryptoDashboard".to_string(),
-            url: SITE_BASE_URL.to_string(),
-        },
+        author: json_ld_authors(&metadata.authors),
         publisher: JsonLdPublisher {
             type_field: "Organization",
             name: "CryptoDashboard".to_string(),
-            url: SITE_BASE_URL.to_string(),
+            url: public_origin(),
             logo: JsonLdImage {
                 type_field: "ImageObject",
-                url: PUBLISHER_LOGO_URL.to_string(),
+                url: format!("{}{PUBLISHER_LOGO_PATH}", public_origin()),
                 width: 512,
                 height: 512,
             },
@@ -272,25 +387,20 @@ pub fn generate_json_ld(metadata: &GeoMetadata, language: Option<&str>) -> Strin
         ],
     };
 
-    // Serialize to JSON with proper escaping
-    match serde_json::to_string_pretty(&json_ld) {
-        Ok(json_str) => format!(
-            r#"<script type="application/ld+json">
-{json_str}
-    </script>"#
-        ),
-        Err(_) => String::new(), // Fallback: no JSON-LD if serialization fails
-    }
+    // Titles and descriptions come from report content: `<` is escaped
+    json_ld_script(&json_ld)
 }
 
 /// Generate complete GEO metadata HTML (meta tags + JSON-LD)
 ///
 /// Convenience function that combines both meta tags and JSON-LD
-/// into a single HTML string for easy injection into templates.
+/// into a single HTML string for easy injection into templates. The JSON-LD
+/// includes the `FAQPage` / `HowTo` schemas of the report content in `language`.
 ///
 /// # Arguments
 /// * `report` - The report to generate metadata for
 /// * `language` - Optional language code ("vi" or "en")
+/// * `authors` - Report authors in byline order (may be empty)
 ///
 /// # Returns
 /// Tuple of (`meta_tags_html`, `json_ld_html`, `dynamic_title`)
@@ -298,12 +408,20 @@ pub fn generate_json_ld(metadata: &GeoMetadata, language: Option<&str>) -> Strin
 pub fn generate_complete_geo_metadata(
     report: &Report,
     language: Option<&str>,
+    authors: &[AuthorData],
 ) -> (String, String, String) {
-    let metadata = GeoMetadata::from_report(report);
     let lang = language.unwrap_or("vi");
+    let content = match (lang, &report.html_content_en) {
+        ("en", Some(html_en)) => html_en,
+        _ => &report.html_content,
+    };
+    let metadata = GeoMetadata::from_report(report)
+        .with_authors(authors)
+        .with_feed_tags(content);
 
     let meta_tags = generate_meta_tags(&metadata, Some(lang));
-    let json_ld = generate_json_ld(&metadata, Some(lang));
+    let json_ld = generate_json_ld(&metadata, Some(lang))
+        + &generate_section_json_ld(content, SectionSchemaRules::current());
     let title = if lang == "en" {
         metadata.title_en
     } else {
@@ -313,6 +431,55 @@ pub fn generate_complete_geo_metadata(
     (meta_tags, json_ld, title)
 }
 
+/// Generate JSON-LD `ProfilePage` structured data for an author archive page
+#[must_use]
+pub fn generate_author_json_ld(author: &AuthorData) -> String {
+    let profile = GeoAuthor::from(author);
+    let json_ld = JsonLdProfilePage {
+        context: "https://schema.org",
+        type_field: "ProfilePage",
+        url: profile.url.clone(),
+        main_entity: JsonLdPerson {
+            type_field: "Person",
+            name: profile.name,
+            url: profile.url,
+            description: author.bio.clone(),
+            image: author.avatar_url.clone(),
+            same_as: profile.profile_url.into_iter().collect(),
+            works_for: organization(),
+        },
+    };
+
+    // The name and bio are editable: `<` is escaped
+    json_ld_script(&json_ld)
+}
+
+/// The site as a Schema.org `Organization`
+fn organization() -> JsonLdAgent {
+    JsonLdAgent {
+        type_field: "Organization",
+        name: "CryptoDashboard".to_string(),
+        url: public_origin(),
+        same_as: Vec::new(),
+    }
+}
+
+/// Article `author` entries: one `Person` per author, or the organization
+fn json_ld_authors(authors: &[GeoAuthor]) -> Vec<JsonLdAgent> {
+    if authors.is_empty() {
+        return vec![organization()];
+    }
+    authors
+        .iter()
+        .map(|author| JsonLdAgent {
+            type_field: "Person",
+            name: author.name.clone(),
+            url: author.url.clone(),
+            same_as: author.profile_url.iter().cloned().collect(),
+        })
+        .collect()
+}
+
 // ============================================================================
 // Helper structs for JSON-LD serialization
 // ============================================================================
@@ -333,7 +500,7 @@ struct JsonLdArticle {
     date_modified: String,
     url: String,
     image: JsonLdImage,
-    author: JsonLdOrganization,
+    author: Vec<JsonLdAgent>,
     publisher: JsonLdPublisher,
     #[serde(rename = "mainEntityOfPage")]
     main_entity_of_page: JsonLdWebPage,
@@ -352,11 +519,40 @@ struct JsonLdImage {
 }
 
 #[derive(Serialize)]
-struct JsonLdOrganization {
+struct JsonLdAgent {
+    #[serde(rename = "@type")]
+    type_field: &'static str,
+    name: String,
+    url: String,
+    #[serde(rename = "sameAs", skip_serializing_if = "Vec::is_empty")]
+    same_as: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonLdProfilePage {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@type")]
+    type_field: &'static str,
+    url: String,
+    #[serde(rename = "mainEntity")]
+    main_entity: JsonLdPerson,
+}
+
+#[derive(Serialize)]
+struct JsonLdPerson {
     #[serde(rename = "@type")]
     type_field: &'static str,
     name: String,
     url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(rename = "sameAs", skip_serializing_if = "Vec::is_empty")]
+    same_as: Vec<String>,
+    #[serde(rename = "worksFor")]
+    works_for: JsonLdAgent,
 }
 
 #[derive(Serialize)]
@@ -401,6 +597,7 @@ fn escape_html_attr(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::data_communication::slugify;
     use chrono::Utc;
 
     fn create_test_report() -> Report {
@@ -412,6 +609,8 @@ mod tests {
             html_content_en: None,
             js_content_en: None,
             created_at: Utc::now(),
+            updated_at: Utc::now(),
+            indexable: true,
         }
     }
 
@@ -423,7 +622,13 @@ mod tests {
         assert_eq!(metadata.report_id, 123);
         assert!(metadata.title_vi.contains("#123"));
         assert!(metadata.title_en.contains("#123"));
-        assert!(metadata.canonical_url.contains("/crypto_report/123"));
+        assert!(metadata.canonical_url.contains("/crypto_report/123-"));
+        // The slug is the Vietnamese title without its `#id`
+        assert!(
+            metadata
+                .canonical_url
+                .ends_with(&slugify(&metadata.title_vi.replace(" #123", "")))
+        );
     }
 
     #[test]
@@ -448,6 +653,63 @@ mod tests {
         assert!(html.contains("Crypto Market Analysis"));
     }
 
+    #[test]
+    fn test_meta_tags_of_unindexable_report() {
+        let report = create_test_report();
+        let html = generate_meta_tags(&GeoMetadata::from_report(&report), Some("vi"));
+        assert!(html.contains(r#"<meta name="robots" content="index, follow"#));
+
+        let report = Report {
+            indexable: false,
+            ..report
+        };
+        let html = generate_meta_tags(&GeoMetadata::from_report(&report), Some("vi"));
+        assert!(html.contains(r#"<meta name="robots" content="noindex, nofollow" />"#));
+    }
+
+    #[test]
+    fn test_meta_tags_link_tag_feeds() {
+        let report = create_test_report();
+        let metadata = GeoMetadata::from_report(&report).with_feed_tags("<p>Bitcoin và DeFi</p>");
+        let html = generate_meta_tags(&metadata, Some("en"));
+
+        assert!(html.contains(
+            r#"<link rel="alternate" type="application/rss+xml" title="CryptoDashboard RSS" href="https://cryptodashboard.me/rss.xml?lang=en" />"#
+        ));
+        assert!(html.contains(
+            r#"title="CryptoDashboard RSS - BTC" href="https://cryptodashboard.me/rss.xml?tag=btc&amp;lang=en" />"#
+        ));
+        assert!(html.contains("rss.xml?tag=defi&amp;lang=en"));
+        assert!(!html.contains("tag=eth"));
+
+        let html = generate_meta_tags(&GeoMetadata::from_report(&report), Some("vi"));
+        assert!(html.contains(r#"href="https://cryptodashboard.me/rss.xml" />"#));
+        assert!(!html.contains("?tag="));
+    }
+
+    #[test]
+    fn test_meta_tags_link_language_alternates() {
+        let report = create_test_report();
+        let metadata = GeoMetadata::from_report(&report);
+        let html = generate_meta_tags(&metadata, Some("en"));
+        let url = format!(
+            "https://cryptodashboard.me{}",
+            report_path(123, report.created_at)
+        );
+
+        assert!(html.contains(&format!(
+            r#"<link rel="alternate" hreflang="vi" href="{url}?lang=vi" />"#
+        )));
+        assert!(html.contains(&format!(
+            r#"<link rel="alternate" hreflang="en" href="{url}?lang=en" />"#
+        )));
+        assert!(html.contains(&format!(
+            r#"<link rel="alternate" hreflang="x-default" href="{url}" />"#
+        )));
+        assert!(html.contains(&format!(r#"<link rel="canonical" href="{url}" />"#)));
+        assert!(html.contains(r#"<meta property="og:locale:alternate" content="vi_VN" />"#));
+    }
+
     #[test]
     fn test_generate_json_ld() {
         let report = create_test_report();
@@ -458,6 +720,53 @@ mod tests {
         assert!(html.contains("@context"));
         assert!(html.contains("schema.org"));
         assert!(html.contains("Article"));
+
+        let mut metadata = GeoMetadata::from_report(&report);
+        metadata.description_vi = "</script><img src=x>".to_string();
+        let html = generate_json_ld(&metadata, Some("vi"));
+        assert_eq!(html.matches("</script>").count(), 1);
+    }
+
+    fn create_test_author() -> AuthorData {
+        AuthorData {
+            id: 1,
+            slug: "le-anh".to_string(),
+            name: "Lê Anh".to_string(),
+            bio: Some("On-chain analyst".to_string()),
+            avatar_url: None,
+            profile_url: Some("https://x.com/leanh".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_json_ld_lists_report_authors() {
+        let report = create_test_report();
+        let organization_only = generate_json_ld(&GeoMetadata::from_report(&report), None);
+        assert!(organization_only.contains(r#""@type": "Organization""#));
+        assert!(!organization_only.contains("Person"));
+
+        let metadata = GeoMetadata::from_report(&report).with_authors(&[create_test_author()]);
+        let html = generate_json_ld(&metadata, None);
+        assert!(html.contains(r#""@type": "Person""#));
+        assert!(html.contains("https://cryptodashboard.me/author/le-anh"));
+        assert!(html.contains("https://x.com/leanh"));
+
+        let meta_tags = generate_meta_tags(&metadata, None);
+        assert!(meta_tags.contains(r#"<meta name="author" content="Lê Anh" />"#));
+    }
+
+    #[test]
+    fn test_generate_author_json_ld() {
+        let html = generate_author_json_ld(&create_test_author());
+        assert!(html.contains("ProfilePage"));
+        assert!(html.contains("On-chain analyst"));
+        assert!(html.contains("worksFor"));
+
+        let mut author = create_test_author();
+        author.bio = Some("</script><script>alert(1)</script>".to_string());
+        let html = generate_author_json_ld(&author);
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(html.contains(r"\u003c/script>\u003cscript>alert(1)"));
     }
 
     #[test]
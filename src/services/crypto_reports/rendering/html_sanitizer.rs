@@ -0,0 +1,648 @@
+//! Report HTML Sanitizer
+//!
+//! Report `html_content` comes from the ingestion pipeline and lands in the page DOM
+//! (inside the shadow root), outside the script sandbox. Before it is rendered or
+//! cached it is reduced to an allowlist of tags and attributes:
+//! - scripts, frames, embedded objects and other raw-text elements are dropped
+//!   together with their content
+//! - other unknown tags are unwrapped (their text is kept)
+//! - event handler attributes are removed, and URLs are limited to `http(s)`,
+//!   `mailto`, `tel` and relative links (plus `data:image/` for images)
+//! - unclosed elements are closed, stray closing tags dropped
+//!
+//! The default allowlist covers report markup and the chart containers the report
+//! scripts draw into (`id`, `class`, `style`, `data-*` on `div`, `canvas`, ...).
+//! `REPORT_HTML_ALLOWED_TAGS` and `REPORT_HTML_ALLOWED_ATTRIBUTES` (comma-separated)
+//! extend it; script-capable elements and `on*` attributes can never be allowed.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+use tracing::warn;
+
+/// Tags kept by default
+const DEFAULT_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "article",
+    "aside",
+    "b",
+    "blockquote",
+    "br",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "main",
+    "mark",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "section",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "u",
+    "ul",
+    "wbr",
+];
+
+/// Dropped together with their content, whatever the configuration
+const DROPPED_ELEMENTS: &[&str] = &[
+    "applet",
+    "frame",
+    "frameset",
+    "iframe",
+    "math",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "plaintext",
+    "script",
+    "style",
+    "svg",
+    "template",
+    "textarea",
+    "title",
+    "xmp",
+];
+
+/// Elements without content or closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Attributes kept on every allowed tag (besides `data-*` and `aria-*`)
+const GLOBAL_ATTRIBUTES: &[&str] = &["class", "dir", "id", "lang", "role", "style", "title"];
+
+/// Attributes kept on specific tags
+const TAG_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("a", &["href", "name", "rel", "target"]),
+    ("blockquote", &["cite"]),
+    ("canvas", &["height", "width"]),
+    ("col", &["span"]),
+    ("colgroup", &["span"]),
+    ("del", &["cite", "datetime"]),
+    ("details", &["open"]),
+    ("img", &["alt", "height", "loading", "src", "width"]),
+    ("ins", &["cite", "datetime"]),
+    ("ol", &["reversed", "start"]),
+    ("q", &["cite"]),
+    ("td", &["colspan", "rowspan"]),
+    ("th", &["colspan", "rowspan", "scope"]),
+    ("time", &["datetime"]),
+];
+
+/// Attributes holding a URL, checked against `URL_SCHEMES`
+const URL_ATTRIBUTES: &[&str] = &["cite", "href", "src"];
+const URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+/// Never kept: they load documents or run script (like every `on*` attribute)
+const FORBIDDEN_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "dynsrc",
+    "formaction",
+    "lowsrc",
+    "poster",
+    "srcdoc",
+    "srcset",
+    "xlink:href",
+];
+
+/// `style` values containing any of these are dropped
+const UNSAFE_STYLE: &[&str] = &[
+    "expression",
+    "javascript:",
+    "url(",
+    "behavior",
+    "binding",
+    "@import",
+    "/*",
+    "\\",
+];
+
+/// Character references decoded before attribute values are checked
+const NAMED_REFERENCES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("apos", '\''),
+    ("colon", ':'),
+    ("gt", '>'),
+    ("lpar", '('),
+    ("lt", '<'),
+    ("newline", '\n'),
+    ("nbsp", '\u{a0}'),
+    ("period", '.'),
+    ("quot", '"'),
+    ("rpar", ')'),
+    ("sol", '/'),
+    ("tab", '\t'),
+];
+
+/// Allowlist-based sanitizer for report HTML fragments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlSanitizer {
+    tags: HashSet<String>,
+    /// Extra attributes allowed on every tag
+    attributes: HashSet<String>,
+}
+
+impl Default for HtmlSanitizer {
+    fn default() -> Self {
+        Self {
+            tags: DEFAULT_TAGS.iter().map(ToString::to_string).collect(),
+            attributes: HashSet::new(),
+        }
+    }
+}
+
+impl HtmlSanitizer {
+    /// Default allowlist plus comma-separated extra tags and attributes
+    ///
+    /// Entries that can never be allowed are skipped with a warning.
+    #[must_use]
+    pub fn with_additions(tags: &str, attributes: &str) -> Self {
+        let mut sanitizer = Self::default();
+        for tag in split_names(tags) {
+            if DROPPED_ELEMENTS.contains(&tag.as_str()) || !is_valid_name(&tag) {
+                warn!("⚠️ Ignoring REPORT_HTML_ALLOWED_TAGS entry: {}", tag);
+            } else {
+                sanitizer.tags.insert(tag);
+            }
+        }
+        for attribute in split_names(attributes) {
+            if is_forbidden_attribute(&attribute) {
+                warn!(
+                    "⚠️ Ignoring REPORT_HTML_ALLOWED_ATTRIBUTES entry: {}",
+                    attribute
+                );
+            } else {
+                sanitizer.attributes.insert(attribute);
+            }
+        }
+        sanitizer
+    }
+
+    /// `REPORT_HTML_ALLOWED_TAGS` and `REPORT_HTML_ALLOWED_ATTRIBUTES`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        Self::with_additions(
+            &var("REPORT_HTML_ALLOWED_TAGS"),
+            &var("REPORT_HTML_ALLOWED_ATTRIBUTES"),
+        )
+    }
+
+    /// Process-wide sanitizer, configured from the environment on first use
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static SANITIZER: OnceLock<HtmlSanitizer> = OnceLock::new();
+        SANITIZER.get_or_init(Self::from_env)
+    }
+
+    /// Sanitize an HTML fragment
+    #[must_use]
+    pub fn sanitize(&self, html: &str) -> String {
+        let mut output = String::with_capacity(html.len());
+        let mut open = Vec::new();
+        let mut rest = html;
+        while let Some(lt) = rest.find('<') {
+            output.push_str(rest.get(..lt).unwrap_or_default());
+            rest = self.sanitize_markup(
+                rest.get(lt + 1..).unwrap_or_default(),
+                &mut output,
+                &mut open,
+            );
+        }
+        output.push_str(rest);
+        for tag in open.iter().rev() {
+            let _ = write!(output, "</{tag}>");
+        }
+        output
+    }
+
+    /// Handle the markup after a `<`; returns the input left after it
+    fn sanitize_markup<'a>(
+        &self,
+        markup: &'a str,
+        output: &mut String,
+        open: &mut Vec<String>,
+    ) -> &'a str {
+        if let Some(comment) = markup.strip_prefix("!--") {
+            return comment
+                .find("-->")
+                .and_then(|end| comment.get(end + 3..))
+                .unwrap_or_default();
+        }
+        if let Some(closing) = markup.strip_prefix('/') {
+            let Some((tag, rest)) = parse_tag(closing) else {
+                return "";
+            };
+            if let Some(pos) = open.iter().rposition(|name| *name == tag.name) {
+                for name in open.drain(pos..).rev() {
+                    let _ = write!(output, "</{name}>");
+                }
+            }
+            return rest;
+        }
+        match markup.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                let Some((tag, rest)) = parse_tag(markup) else {
+                    return "";
+                };
+                let is_void = VOID_ELEMENTS.contains(&tag.name.as_str());
+                if DROPPED_ELEMENTS.contains(&tag.name.as_str()) {
+                    return skip_element(&tag.name, rest);
+                }
+                if self.tags.contains(&tag.name) {
+                    self.write_open_tag(&tag, output);
+                    if !is_void {
+                        open.push(tag.name);
+                    }
+                }
+                rest
+            }
+            // Doctype, processing instruction or bogus comment
+            Some('!' | '?') => markup
+                .find('>')
+                .and_then(|end| markup.get(end + 1..))
+                .unwrap_or_default(),
+            // A lone `<` is text
+            _ => {
+                output.push_str("&lt;");
+                markup
+            }
+        }
+    }
+
+    fn write_open_tag(&self, tag: &Tag, output: &mut String) {
+        let allowed: Vec<&(String, String)> = tag
+            .attributes
+            .iter()
+            .filter(|(name, _)| self.allows_attribute(&tag.name, name))
+            .collect();
+        // Links opening a new browsing context never get a handle on the page
+        let has_target = tag.name == "a" && allowed.iter().any(|(name, _)| name == "target");
+
+        output.push('<');
+        output.push_str(&tag.name);
+        for (name, raw) in allowed {
+            if has_target && name == "rel" {
+                continue;
+            }
+            let value = if URL_ATTRIBUTES.contains(&name.as_str()) {
+                match safe_url(&tag.name, &decode_references(raw)) {
+                    Some(url) => escape_attribute(&url),
+                    None => continue,
+                }
+            } else if name == "style" {
+                let style = decode_references(raw);
+                let lower = style.to_ascii_lowercase();
+                if UNSAFE_STYLE.iter().any(|pattern| lower.contains(pattern)) {
+                    continue;
+                }
+                escape_attribute(&style)
+            } else {
+                // Character references are kept as written; only quoting matters
+                raw.replace('"', "&quot;")
+            };
+            let _ = write!(output, " {name}=\"{value}\"");
+        }
+        if has_target {
+            output.push_str(" rel=\"noopener noreferrer\"");
+        }
+        output.push('>');
+    }
+
+    fn allows_attribute(&self, tag: &str, name: &str) -> bool {
+        if is_forbidden_attribute(name) {
+            return false;
+        }
+        name.starts_with("data-")
+            || name.starts_with("aria-")
+            || GLOBAL_ATTRIBUTES.contains(&name)
+            || self.attributes.contains(name)
+            || TAG_ATTRIBUTES
+                .iter()
+                .any(|(t, attributes)| *t == tag && attributes.contains(&name))
+    }
+}
+
+/// Sanitize report HTML with the process-wide allowlist
+#[must_use]
+pub fn sanitize_report_html(html: &str) -> String {
+    HtmlSanitizer::current().sanitize(html)
+}
+
+/// A parsed start or end tag
+struct Tag {
+    name: String,
+    attributes: Vec<(String, String)>,
+}
+
+/// Parse a tag from just after `<` (or `</`) up to and including its `>`
+///
+/// Returns `None` when the input ends inside the tag.
+fn parse_tag(markup: &str) -> Option<(Tag, &str)> {
+    let is_space = |c: char| c.is_ascii_whitespace();
+    let name_end = markup
+        .find(|c: char| is_space(c) || c == '/' || c == '>')
+        .unwrap_or(markup.len());
+    let name = markup.get(..name_end)?.to_ascii_lowercase();
+    let mut rest = markup.get(name_end..)?;
+    let mut attributes = Vec::new();
+
+    loop {
+        rest = rest.trim_start_matches(is_space);
+        if let Some(after) = rest.strip_prefix('>') {
+            return Some((Tag { name, attributes }, after));
+        }
+        if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        }
+        // An attribute name may start with `=`, never end with it
+        let start = usize::from(rest.starts_with('='));
+        let attr_end = rest
+            .get(start..)?
+            .find(|c: char| is_space(c) || matches!(c, '/' | '>' | '='))
+            .map_or(rest.len(), |end| end + start);
+        if attr_end == 0 {
+            return None;
+        }
+        let attr_name = rest.get(..attr_end)?.to_ascii_lowercase();
+        rest = rest.get(attr_end..)?.trim_start_matches(is_space);
+
+        let mut value = "";
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start_matches(is_space);
+            let quote = after.chars().next().filter(|c| matches!(c, '"' | '\''));
+            let (parsed, remaining) = if let Some(quote) = quote {
+                let body = after.get(1..)?;
+                let end = body.find(quote)?;
+                (body.get(..end)?, body.get(end + 1..)?)
+            } else {
+                let end = after
+                    .find(|c: char| is_space(c) || c == '>')
+                    .unwrap_or(after.len());
+                (after.get(..end)?, after.get(end..)?)
+            };
+            value = parsed;
+            rest = remaining;
+        }
+        attributes.push((attr_name, value.to_string()));
+    }
+}
+
+/// Skip the content and end tag of a dropped element
+fn skip_element<'a>(name: &str, content: &'a str) -> &'a str {
+    // ASCII lowercasing keeps byte offsets
+    let lower = content.to_ascii_lowercase();
+    let end_tag = format!("</{name}");
+    let mut from = 0;
+    while let Some(found) = lower.get(from..).and_then(|s| s.find(&end_tag)) {
+        let after = from + found + end_tag.len();
+        let terminated = lower
+            .get(after..)
+            .and_then(|s| s.chars().next())
+            .is_none_or(|c| c.is_ascii_whitespace() || c == '/' || c == '>');
+        if terminated {
+            return content
+                .get(after..)
+                .and_then(|s| s.find('>').and_then(|end| s.get(end + 1..)))
+                .unwrap_or_default();
+        }
+        from = after;
+    }
+    ""
+}
+
+/// Allowed URL with control characters removed, or `None`
+fn safe_url(tag: &str, value: &str) -> Option<String> {
+    let url: String = value.chars().filter(|c| !c.is_ascii_control()).collect();
+    let url = url.trim();
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return Some(url.to_string());
+    };
+    if scheme.contains(['/', '?', '#']) {
+        // The colon is in the path or query of a relative URL
+        return Some(url.to_string());
+    }
+    let scheme = scheme.to_ascii_lowercase();
+    let allowed = URL_SCHEMES.contains(&scheme.as_str())
+        || (tag == "img" && scheme == "data" && {
+            let media = rest.trim_start().to_ascii_lowercase();
+            media.starts_with("image/") && !media.starts_with("image/svg")
+        });
+    allowed.then(|| url.to_string())
+}
+
+/// Decode numeric and a few named character references, as the browser would
+fn decode_references(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp) = rest.find('&') {
+        output.push_str(rest.get(..amp).unwrap_or_default());
+        let reference = rest.get(amp + 1..).unwrap_or_default();
+        if let Some((decoded, consumed)) = decode_reference(reference) {
+            output.push(decoded);
+            rest = reference.get(consumed..).unwrap_or_default();
+        } else {
+            output.push('&');
+            rest = reference;
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Character and length of the reference at the start of `reference` (after `&`)
+fn decode_reference(reference: &str) -> Option<(char, usize)> {
+    if let Some(numeric) = reference.strip_prefix('#') {
+        let (digits, radix, prefix) = match numeric.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 2),
+            None => (numeric, 10, 1),
+        };
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if len == 0 {
+            return None;
+        }
+        let decoded = u32::from_str_radix(digits.get(..len)?, radix)
+            .ok()
+            .and_then(char::from_u32)
+            .filter(|c| *c != '\0')
+            .unwrap_or('\u{fffd}');
+        // The semicolon is optional for numeric references
+        let semicolon = usize::from(digits.get(len..)?.starts_with(';'));
+        return Some((decoded, prefix + len + semicolon));
+    }
+    NAMED_REFERENCES.iter().find_map(|(name, decoded)| {
+        reference
+            .strip_prefix(name)
+            .filter(|rest| rest.starts_with(';'))
+            .map(|_| (*decoded, name.len() + 1))
+    })
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+}
+
+fn is_forbidden_attribute(name: &str) -> bool {
+    !is_valid_name(name) || name.starts_with("on") || FORBIDDEN_ATTRIBUTES.contains(&name)
+}
+
+fn split_names(list: &str) -> impl Iterator<Item = String> + '_ {
+    list.split(',')
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sanitize(html: &str) -> String {
+        HtmlSanitizer::default().sanitize(html)
+    }
+
+    #[test]
+    fn test_scripts_and_handlers_are_removed() {
+        assert_eq!(
+            sanitize("<p onclick=\"steal()\">Hi<script>alert(1)</script></p>"),
+            "<p>Hi</p>"
+        );
+        assert_eq!(
+            sanitize("<img src=x onerror=alert(1)><SCRIPT src=//evil></SCRIPT >ok"),
+            "<img src=\"x\">ok"
+        );
+        assert_eq!(
+            sanitize("<iframe srcdoc=\"<script>x</script>\"></iframe><svg onload=x><g/></svg>"),
+            ""
+        );
+        // Unwrapped tags keep their text; comments disappear
+        assert_eq!(
+            sanitize("<form action=/x><b>Bold</b></form><!-- <script>x</script> -->"),
+            "<b>Bold</b>"
+        );
+    }
+
+    #[test]
+    fn test_dangerous_urls_are_dropped() {
+        assert_eq!(
+            sanitize("<a href=\"javascript:alert(1)\">x</a>"),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize("<a href=\"jav&#x61;script&colon;alert(1)\">x</a>"),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize("<a href=\" java\tscript:alert(1)\">x</a>"),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize("<a href=\"https://example.com/?a=1&amp;b=2\" target=_blank rel=opener>x</a>"),
+            "<a href=\"https://example.com/?a=1&amp;b=2\" target=\"_blank\" rel=\"noopener noreferrer\">x</a>"
+        );
+        assert_eq!(
+            sanitize("<img src=\"data:image/png;base64,AAAA\"><img src=\"data:text/html,x\">"),
+            "<img src=\"data:image/png;base64,AAAA\"><img>"
+        );
+        assert_eq!(
+            sanitize("<a href=\"/crypto_report/1?x=a:b\">x</a>"),
+            "<a href=\"/crypto_report/1?x=a:b\">x</a>"
+        );
+    }
+
+    #[test]
+    fn test_chart_containers_are_kept() {
+        let html = "<div id=\"btc-chart\" class=\"chart-container\" data-symbol=\"BTC\" \
+                    style=\"height: 300px\"><canvas id=\"c1\" width=\"400\" height=\"300\"></canvas></div>";
+        assert_eq!(sanitize(html), html);
+        assert_eq!(
+            sanitize("<div style=\"background: url(javascript:x)\">x</div>"),
+            "<div>x</div>"
+        );
+    }
+
+    #[test]
+    fn test_output_is_balanced() {
+        assert_eq!(sanitize("<div><p>text"), "<div><p>text</p></div>");
+        assert_eq!(sanitize("</template></div>a < b"), "a &lt; b");
+        assert_eq!(sanitize("<div><span>x</div>"), "<div><span>x</span></div>");
+        // A tag cut off at the end is dropped
+        assert_eq!(sanitize("ok<a href=\"x"), "ok");
+    }
+
+    #[test]
+    fn test_allowlist_additions() {
+        let sanitizer =
+            HtmlSanitizer::with_additions("svg, section, script, bad<tag", "itemprop, onclick");
+        assert!(sanitizer.tags.contains("section"));
+        assert!(!sanitizer.tags.contains("script"));
+        assert!(!sanitizer.tags.contains("svg"));
+        assert_eq!(
+            sanitizer.sanitize("<span itemprop=\"name\" onclick=\"x\">BTC</span>"),
+            "<span itemprop=\"name\">BTC</span>"
+        );
+        assert_eq!(
+            sanitize("<span itemprop=\"name\">BTC</span>"),
+            "<span>BTC</span>"
+        );
+    }
+}
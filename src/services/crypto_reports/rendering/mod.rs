@@ -2,22 +2,35 @@
 //!
 //! This module contains rendering strategies for crypto reports:
 //! - `shadow_dom_renderer`: Modern Declarative Shadow DOM rendering
+//! - strategy: Pluggable `RenderStrategy` registry selected per request
 //! - shared: Common utilities and models used by rendering strategies
 //! - `geo_metadata`: GEO (Generative Engine Optimization) metadata for AI bots
+//! - `section_schema`: `FAQPage` / `HowTo` JSON-LD derived from report sections
+//! - `html_sanitizer`: Allowlist sanitization of stored report HTML
 //! - breadcrumbs: Breadcrumb navigation and related reports for GEO optimization
+//! - `template_safety`: Neutralization of template syntax in untrusted report content
 
 pub mod breadcrumbs;
 pub mod geo_metadata;
+pub mod html_sanitizer;
+pub mod section_schema;
 pub mod shadow_dom_renderer;
 pub mod shared;
+pub mod strategy;
+pub mod template_safety;
 
 // Re-export commonly used items
 pub use breadcrumbs::{
     BreadcrumbItem, RelatedReportItem, format_related_reports, generate_breadcrumb_items,
-    generate_breadcrumbs_and_related, generate_breadcrumbs_schema,
+    generate_breadcrumbs_and_related, generate_breadcrumbs_schema, generate_list_breadcrumb_items,
+    generate_list_page_schema,
 };
 pub use geo_metadata::{
-    GeoMetadata, generate_complete_geo_metadata, generate_json_ld, generate_meta_tags,
+    GeoAuthor, GeoMetadata, generate_author_json_ld, generate_complete_geo_metadata,
+    generate_feed_links, generate_hreflang_links, generate_json_ld, generate_meta_tags,
 };
+pub use html_sanitizer::{HtmlSanitizer, sanitize_report_html};
+pub use section_schema::{SectionSchemaRules, generate_section_json_ld};
 pub use shadow_dom_renderer::ShadowDomRenderer;
 pub use shared::{Report, SandboxedReport};
+pub use strategy::{RenderStrategy, RenderStrategyRegistry};
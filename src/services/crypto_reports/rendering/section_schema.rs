@@ -0,0 +1,373 @@
+//! FAQ and `HowTo` Structured Data
+//!
+//! Derives Schema.org `FAQPage` and `HowTo` JSON-LD from the sections of a report
+//! (each `<h2>` / `<h3>` heading and the content up to the next one), emitted next
+//! to the `Article` schema so reports are eligible for those rich results:
+//! - FAQ: sections whose heading matches `GEO_FAQ_PATTERN` (default: ends with
+//!   `?`) become questions, answered by the section text
+//! - `HowTo`: the first section whose heading matches `GEO_HOWTO_PATTERN` (default:
+//!   starts with "Hướng dẫn", "Cách", "Chiến lược", "How to" or "Strategy") and
+//!   holds an ordered list becomes a guide, one step per list item
+//!
+//! Patterns are regexes matched against the plain heading text; an empty value
+//! disables that schema. `GEO_FAQ_MAX_ITEMS` (default 10) caps the questions.
+
+use regex::Regex;
+use serde::Serialize;
+use std::sync::{LazyLock, OnceLock};
+use tracing::warn;
+
+const DEFAULT_FAQ_PATTERN: &str = r"\?\s*$";
+const DEFAULT_HOWTO_PATTERN: &str = r"(?i)^\s*(hướng dẫn|cách|chiến lược|how to|strategy)\b";
+const DEFAULT_MAX_FAQ_ITEMS: usize = 10;
+
+/// Max characters of an answer or step text
+const MAX_TEXT_LENGTH: usize = 1000;
+
+/// Min steps for a `HowTo`
+const MIN_HOWTO_STEPS: usize = 2;
+
+#[allow(clippy::expect_used)] // Safe: Regex patterns are hardcoded and verified
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<h[23][^>]*>(.*?)</h[23]\s*>").expect("Invalid regex"));
+
+#[allow(clippy::expect_used)] // Safe: Regex patterns are hardcoded and verified
+static ORDERED_LIST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<ol[^>]*>(.*?)</ol\s*>").expect("Invalid regex"));
+
+#[allow(clippy::expect_used)] // Safe: Regex patterns are hardcoded and verified
+static LIST_ITEM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<li[^>]*>(.*?)</li\s*>").expect("Invalid regex"));
+
+#[allow(clippy::expect_used)] // Safe: Regex patterns are hardcoded and verified
+static RAW_TEXT_ELEMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<(script|style)[^>]*>.*?</(script|style)\s*>").expect("Invalid regex")
+});
+
+/// Which sections become FAQ questions and `HowTo` guides
+#[derive(Debug, Clone)]
+pub struct SectionSchemaRules {
+    /// `None` disables `FAQPage`
+    pub faq_heading: Option<Regex>,
+    /// `None` disables `HowTo`
+    pub howto_heading: Option<Regex>,
+    pub max_faq_items: usize,
+}
+
+impl SectionSchemaRules {
+    /// Build from the raw variable values (`None` = unset, empty = disabled); an
+    /// invalid pattern is logged and replaced by the default
+    #[must_use]
+    pub fn parse(faq: Option<&str>, howto: Option<&str>, max_faq_items: Option<&str>) -> Self {
+        Self {
+            faq_heading: parse_pattern("GEO_FAQ_PATTERN", faq, DEFAULT_FAQ_PATTERN),
+            howto_heading: parse_pattern("GEO_HOWTO_PATTERN", howto, DEFAULT_HOWTO_PATTERN),
+            max_faq_items: max_faq_items
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(DEFAULT_MAX_FAQ_ITEMS),
+        }
+    }
+
+    /// `GEO_FAQ_PATTERN`, `GEO_HOWTO_PATTERN` and `GEO_FAQ_MAX_ITEMS`
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::parse(
+            std::env::var("GEO_FAQ_PATTERN").ok().as_deref(),
+            std::env::var("GEO_HOWTO_PATTERN").ok().as_deref(),
+            std::env::var("GEO_FAQ_MAX_ITEMS").ok().as_deref(),
+        )
+    }
+
+    /// Process-wide rules, read on first use
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static RULES: OnceLock<SectionSchemaRules> = OnceLock::new();
+        RULES.get_or_init(Self::from_env)
+    }
+}
+
+fn parse_pattern(name: &str, value: Option<&str>, default: &str) -> Option<Regex> {
+    let pattern = value.map_or(default, str::trim);
+    if pattern.is_empty() {
+        return None;
+    }
+    Regex::new(pattern)
+        .or_else(|e| {
+            warn!("⚠️ Invalid {} ({}), using the default pattern", name, e);
+            Regex::new(default)
+        })
+        .ok()
+}
+
+/// One report section: plain heading text and the HTML up to the next heading
+struct Section<'a> {
+    heading: String,
+    body: &'a str,
+}
+
+fn sections(html: &str) -> Vec<Section<'_>> {
+    let headings: Vec<_> = HEADING.captures_iter(html).collect();
+    headings
+        .iter()
+        .enumerate()
+        .filter_map(|(i, captures)| {
+            let whole = captures.get(0)?;
+            let end = headings
+                .get(i + 1)
+                .and_then(|next| next.get(0))
+                .map_or(html.len(), |next| next.start());
+            Some(Section {
+                heading: plain_text(captures.get(1)?.as_str()),
+                body: html.get(whole.end()..end)?,
+            })
+        })
+        .collect()
+}
+
+/// Generate the `FAQPage` and `HowTo` JSON-LD scripts of a report's HTML
+///
+/// Returns an empty string when no section matches the rules.
+#[must_use]
+pub fn generate_section_json_ld(html: &str, rules: &SectionSchemaRules) -> String {
+    let sections = sections(html);
+    let mut scripts = String::new();
+    if let Some(faq) = rules
+        .faq_heading
+        .as_ref()
+        .and_then(|pattern| faq_page(&sections, pattern, rules.max_faq_items))
+    {
+        scripts.push_str(&json_ld_script(&faq));
+    }
+    if let Some(howto) = rules
+        .howto_heading
+        .as_ref()
+        .and_then(|pattern| how_to(&sections, pattern))
+    {
+        scripts.push_str(&json_ld_script(&howto));
+    }
+    scripts
+}
+
+fn faq_page(sections: &[Section<'_>], pattern: &Regex, max_items: usize) -> Option<JsonLdFaqPage> {
+    let questions: Vec<JsonLdQuestion> = sections
+        .iter()
+        .filter(|section| pattern.is_match(&section.heading))
+        .filter_map(|section| {
+            let answer = truncate(plain_text(section.body));
+            (!answer.is_empty()).then(|| JsonLdQuestion {
+                type_field: "Question",
+                name: section.heading.clone(),
+                accepted_answer: JsonLdAnswer {
+                    type_field: "Answer",
+                    text: answer,
+                },
+            })
+        })
+        .take(max_items)
+        .collect();
+    (!questions.is_empty()).then_some(JsonLdFaqPage {
+        context: "https://schema.org",
+        type_field: "FAQPage",
+        main_entity: questions,
+    })
+}
+
+fn how_to(sections: &[Section<'_>], pattern: &Regex) -> Option<JsonLdHowTo> {
+    sections
+        .iter()
+        .filter(|section| pattern.is_match(&section.heading))
+        .find_map(|section| {
+            let list = ORDERED_LIST.captures(section.body)?.get(1)?.as_str();
+            let steps: Vec<JsonLdHowToStep> = LIST_ITEM
+                .captures_iter(list)
+                .filter_map(|item| Some(truncate(plain_text(item.get(1)?.as_str()))))
+                .filter(|text| !text.is_empty())
+                .enumerate()
+                .map(|(i, text)| JsonLdHowToStep {
+                    type_field: "HowToStep",
+                    position: i + 1,
+                    text,
+                })
+                .collect();
+            (steps.len() >= MIN_HOWTO_STEPS).then(|| JsonLdHowTo {
+                context: "https://schema.org",
+                type_field: "HowTo",
+                name: section.heading.clone(),
+                step: steps,
+            })
+        })
+}
+
+/// Text content of an HTML fragment: tags dropped, common entities decoded,
+/// whitespace collapsed
+fn plain_text(html: &str) -> String {
+    let html = RAW_TEXT_ELEMENT.replace_all(html, " ");
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate(text: String) -> String {
+    match text.char_indices().nth(MAX_TEXT_LENGTH) {
+        Some((cut, _)) => format!("{}…", text.get(..cut).unwrap_or_default().trim_end()),
+        None => text,
+    }
+}
+
+/// `<script>` block of a JSON-LD value; `<` is escaped so report text cannot
+/// close the script element
+pub(crate) fn json_ld_script(value: &impl Serialize) -> String {
+    match serde_json::to_string_pretty(value) {
+        Ok(json_str) => format!(
+            "\n    <script type=\"application/ld+json\">\n{}\n    </script>",
+            json_str.replace('<', "\\u003c")
+        ),
+        Err(_) => String::new(),
+    }
+}
+
+// ============================================================================
+// Helper structs for JSON-LD serialization
+// ============================================================================
+
+#[derive(Serialize)]
+struct JsonLdFaqPage {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@type")]
+    type_field: &'static str,
+    #[serde(rename = "mainEntity")]
+    main_entity: Vec<JsonLdQuestion>,
+}
+
+#[derive(Serialize)]
+struct JsonLdQuestion {
+    #[serde(rename = "@type")]
+    type_field: &'static str,
+    name: String,
+    #[serde(rename = "acceptedAnswer")]
+    accepted_answer: JsonLdAnswer,
+}
+
+#[derive(Serialize)]
+struct JsonLdAnswer {
+    #[serde(rename = "@type")]
+    type_field: &'static str,
+    text: String,
+}
+
+#[derive(Serialize)]
+struct JsonLdHowTo {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@type")]
+    type_field: &'static str,
+    name: String,
+    step: Vec<JsonLdHowToStep>,
+}
+
+#[derive(Serialize)]
+struct JsonLdHowToStep {
+    #[serde(rename = "@type")]
+    type_field: &'static str,
+    position: usize,
+    text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT_HTML: &str = r#"
+        <h2>Tổng quan</h2><p>Thị trường tăng nhẹ.</p>
+        <h3 class="faq">Bitcoin có tiếp tục tăng?</h3>
+        <p>Xu hướng <strong>ngắn hạn</strong> vẫn tích cực &amp; ổn định.</p>
+        <h3>RSI là gì?</h3><p>Chỉ số sức mạnh tương đối.</p><script>alert("</h3>")</script>
+        <h2>Chiến lược giao dịch</h2>
+        <ol><li>Chờ điều chỉnh về <b>60k</b></li><li>Đặt stop-loss</li><li> </li></ol>
+    "#;
+
+    fn at<'a>(value: &'a serde_json::Value, pointer: &str) -> &'a serde_json::Value {
+        value.pointer(pointer).unwrap_or(&serde_json::Value::Null)
+    }
+
+    fn default_rules() -> SectionSchemaRules {
+        SectionSchemaRules::parse(None, None, None)
+    }
+
+    #[test]
+    fn test_faq_and_howto_from_sections() -> Result<(), Box<dyn std::error::Error>> {
+        let scripts = generate_section_json_ld(REPORT_HTML, &default_rules());
+        assert_eq!(scripts.matches("application/ld+json").count(), 2);
+
+        let blocks: Vec<serde_json::Value> = scripts
+            .split("</script>")
+            .filter_map(|block| block.split_once('>').map(|(_, json)| json.trim()))
+            .filter(|json| !json.is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        let faq = blocks.first().ok_or("missing FAQPage")?;
+        assert_eq!(at(faq, "/@type"), "FAQPage");
+        assert_eq!(at(faq, "/mainEntity/0/name"), "Bitcoin có tiếp tục tăng?");
+        assert_eq!(
+            at(faq, "/mainEntity/0/acceptedAnswer/text"),
+            "Xu hướng ngắn hạn vẫn tích cực & ổn định."
+        );
+        assert_eq!(at(faq, "/mainEntity/1/name"), "RSI là gì?");
+        assert_eq!(
+            at(faq, "/mainEntity/1/acceptedAnswer/text"),
+            "Chỉ số sức mạnh tương đối."
+        );
+
+        let howto = blocks.get(1).ok_or("missing HowTo")?;
+        assert_eq!(at(howto, "/@type"), "HowTo");
+        assert_eq!(at(howto, "/name"), "Chiến lược giao dịch");
+        assert_eq!(at(howto, "/step/0/text"), "Chờ điều chỉnh về 60k");
+        assert_eq!(at(howto, "/step/1/position"), 2);
+        assert!(at(howto, "/step/2").is_null());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rules_disable_and_limit() {
+        let faq_only = SectionSchemaRules::parse(None, Some(""), Some("1"));
+        let scripts = generate_section_json_ld(REPORT_HTML, &faq_only);
+        assert!(scripts.contains("FAQPage"));
+        assert!(!scripts.contains("HowTo"));
+        assert!(!scripts.contains("RSI"));
+
+        let custom = SectionSchemaRules::parse(Some("^Tổng"), Some("("), None);
+        assert!(custom.howto_heading.is_some());
+        let scripts = generate_section_json_ld(REPORT_HTML, &custom);
+        assert!(scripts.contains("Thị trường tăng nhẹ."));
+        assert!(!scripts.contains("Bitcoin có"));
+
+        assert!(generate_section_json_ld("<p>No sections</p>", &default_rules()).is_empty());
+    }
+
+    #[test]
+    fn test_script_content_is_escaped() {
+        let html = "<h3>Why?</h3><p>a &lt;/script&gt;&lt;script&gt;alert(1)</p>";
+        let scripts = generate_section_json_ld(html, &default_rules());
+        assert!(!scripts.contains("</script><script>"));
+        assert!(scripts.contains("\\u003c/script>"));
+    }
+}
@@ -7,7 +7,7 @@
 //! - Declarative Shadow DOM for better performance
 //! - Multi-language support (Vietnamese and English)
 //! - Pre-loaded templates for optimal performance
-//! - Content sanitization for security
+//! - Content sanitization for security (HTML allowlist, JS/CSS patterns)
 //! - Better SEO and accessibility compared to iframe
 
 use std::sync::{Arc, LazyLock};
@@ -21,7 +21,9 @@ use crate::services::shared::{
 };
 use crate::state::AppState;
 
+use super::html_sanitizer::sanitize_report_html;
 use super::shared::{Report, SandboxedReport, sanitize_css_content, sanitize_js_content};
+use super::template_safety::{neutralize_code, neutralize_report_html, substitute_placeholders};
 
 /// Pre-loaded Shadow DOM template for modern DSD architecture.
 /// Replaces iframe-based approach with Declarative Shadow DOM.
@@ -64,22 +66,28 @@ impl ShadowDomRenderer {
             "ShadowDomRenderer: Generated secure shadow DOM token"
         );
 
+        // Report content is untrusted: strip markup outside the allowlist, then
+        // neutralize Tera-like delimiters
         SandboxedReport {
             id: report.id,
-            html_content: report.html_content.clone(),
-            css_content: report.css_content.as_deref().map(sanitize_css_content),
+            html_content: neutralize_report_html(&sanitize_report_html(&report.html_content))
+                .into_owned(),
+            css_content: report
+                .css_content
+                .as_deref()
+                .map(|css| sanitize_css_content(&neutralize_code(css))),
             js_content: report
                 .js_content
                 .as_deref()
-                .map(|js| sanitize_js_content(js).into_owned()),
+                .map(|js| neutralize_code(&sanitize_js_content(js)).into_owned()),
             html_content_en: report
                 .html_content_en
                 .as_deref()
-                .map(std::string::ToString::to_string),
+                .map(|html| neutralize_report_html(&sanitize_report_html(html)).into_owned()),
             js_content_en: report
                 .js_content_en
                 .as_deref()
-                .map(|js| sanitize_js_content(js).into_owned()),
+                .map(|js| neutralize_code(&sanitize_js_content(js)).into_owned()),
             created_at: report.created_at,
             sandbox_token,
             chart_modules_content: chart_modules_content.map(ToOwned::to_owned),
@@ -94,6 +102,9 @@ impl ShadowDomRenderer {
     ///
     /// # Performance
     /// Uses `as_deref()` pattern for zero-allocation Option handling.
+    ///
+    /// # Security
+    /// Placeholders are substituted in one pass, so report content cannot expand other fields.
     #[must_use]
     pub fn generate_shadow_dom_content(
         &self,
@@ -127,18 +138,23 @@ impl ShadowDomRenderer {
             ("active", "")
         };
 
-        // Template substitution using pre-loaded template
-        VIEW_SHADOW_DOM_TEMPLATE
-            .replace("{{default_lang}}", lang)
-            .replace("{{report_id}}", &sandboxed_report.id.to_string())
-            .replace("{{vi_active_class}}", vi_active)
-            .replace("{{en_active_class}}", en_active)
-            .replace("{{css_content}}", css)
-            .replace("{{html_content_vi}}", html_vi)
-            .replace("{{html_content_en}}", html_en)
-            .replace("{{chart_modules}}", chart_modules)
-            .replace("{{js_content_vi}}", js_vi)
-            .replace("{{js_content_en}}", js_en)
+        // Single-pass template substitution: inserted content is never rescanned
+        let report_id = sandboxed_report.id.to_string();
+        substitute_placeholders(
+            &VIEW_SHADOW_DOM_TEMPLATE,
+            &[
+                ("default_lang", lang),
+                ("report_id", &report_id),
+                ("vi_active_class", vi_active),
+                ("en_active_class", en_active),
+                ("css_content", css),
+                ("html_content_vi", html_vi),
+                ("html_content_en", html_en),
+                ("chart_modules", chart_modules),
+                ("js_content_vi", js_vi),
+                ("js_content_en", js_en),
+            ],
+        )
     }
 
     /// Serve Shadow DOM content for Declarative Shadow DOM architecture.
@@ -59,6 +59,10 @@ pub struct Report {
     pub html_content_en: Option<String>,
     pub js_content_en: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Source of the `Last-Modified` header: bumped by every change to the report
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// `false` renders `noindex` robots meta tags
+    pub indexable: bool,
 }
 
 /// Implement From trait for automatic conversion from Layer 3 `ReportData`
@@ -74,6 +78,8 @@ impl From<ReportData> for Report {
             html_content_en: data.html_content_en,
             js_content_en: data.js_content_en,
             created_at: data.created_at,
+            updated_at: data.updated_at,
+            indexable: data.indexable,
         }
     }
 }
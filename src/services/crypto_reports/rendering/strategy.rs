@@ -0,0 +1,434 @@
+//! Render Strategy Registry
+//!
+//! Report pages embed the report body inside `#report-shadow-host` of `view_dsd.html`.
+//! How that body is produced is a pluggable `RenderStrategy`, looked up by name in a
+//! `RenderStrategyRegistry` and selected per request:
+//! - `shadow-dom`: Declarative Shadow DOM with report scripts and chart modules (default)
+//! - `iframe`: Sandboxed iframe pointing at `/api/crypto_reports/{id}/sandboxed`
+//! - `server-static`: Declarative Shadow DOM with HTML and CSS only, no scripts
+//!
+//! Environment:
+//! - `RENDER_STRATEGY`: default strategy name (default `shadow-dom`)
+//! - `RENDER_STRATEGY_AB`: `name:percent` experiment, e.g. `server-static:10`; visitors
+//!   are bucketed by their `visitor_id` cookie (the page varies on `Cookie`), and
+//!   requests without one get the default strategy
+//! - `RENDER_STRATEGY_OVERRIDE`: `true` to honor `?render=` / `render_strategy` cookie
+
+use axum::http::HeaderMap;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::services::shared::{generate_sandbox_token, sandboxed_report_path, sign_sandbox_url};
+
+use super::shadow_dom_renderer::ShadowDomRenderer;
+use super::shared::Report;
+
+/// Name of the built-in Declarative Shadow DOM strategy
+pub const SHADOW_DOM_STRATEGY: &str = "shadow-dom";
+/// Name of the built-in sandboxed iframe strategy
+pub const IFRAME_STRATEGY: &str = "iframe";
+/// Name of the built-in script-free static strategy
+pub const SERVER_STATIC_STRATEGY: &str = "server-static";
+
+/// Query parameter / cookie used to force a strategy when overrides are enabled
+const OVERRIDE_PARAM: &str = "render";
+const OVERRIDE_COOKIE: &str = "render_strategy";
+/// Cookie holding a stable visitor id for A/B bucketing
+const VISITOR_COOKIE: &str = "visitor_id";
+
+/// Produces the report body embedded in the report page
+pub trait RenderStrategy: Send + Sync {
+    /// Registry name, also used in config values and cache keys
+    fn name(&self) -> &'static str;
+
+    /// Render the report body for `language` (`vi` or `en`)
+    fn render(
+        &self,
+        report: &Report,
+        language: &str,
+        chart_modules_content: Option<&str>,
+    ) -> String;
+}
+
+/// Interactive Declarative Shadow DOM (the production renderer)
+#[derive(Clone, Copy, Default)]
+pub struct ShadowDomStrategy {
+    renderer: ShadowDomRenderer,
+}
+
+impl RenderStrategy for ShadowDomStrategy {
+    fn name(&self) -> &'static str {
+        SHADOW_DOM_STRATEGY
+    }
+
+    fn render(
+        &self,
+        report: &Report,
+        language: &str,
+        chart_modules_content: Option<&str>,
+    ) -> String {
+        let sandboxed_report = self
+            .renderer
+            .create_sandboxed_report(report, chart_modules_content);
+        let content = self.renderer.generate_shadow_dom_content(
+            &sandboxed_report,
+            Some(language),
+            chart_modules_content,
+        );
+        format!("<template shadowrootmode=\"open\">{content}</template>")
+    }
+}
+
+/// Legacy iframe sandbox, loading the report from the sandboxed API endpoint
+#[derive(Clone, Copy, Default)]
+pub struct IframeStrategy;
+
+impl RenderStrategy for IframeStrategy {
+    fn name(&self) -> &'static str {
+        IFRAME_STRATEGY
+    }
+
+    fn render(
+        &self,
+        report: &Report,
+        language: &str,
+        _chart_modules_content: Option<&str>,
+    ) -> String {
+        let token = generate_sandbox_token(report.id, &report.created_at);
+        let path = sandboxed_report_path(report.id);
+        let signature = sign_sandbox_url(&path).replace('&', "&amp;");
+        format!(
+            "<iframe src=\"{path}?{signature}&amp;token={token}&amp;lang={language}&amp;chart_modules=1\" \
+             sandbox=\"allow-scripts\" loading=\"lazy\" title=\"Crypto report #{id}\" \
+             class=\"w-full border-0\" style=\"min-height: 80vh;\"></iframe>",
+            id = report.id,
+        )
+    }
+}
+
+/// Script-free render for clients and crawlers that don't run JavaScript
+///
+/// Keeps the Shadow DOM for style isolation but ships only the report HTML and CSS.
+#[derive(Clone, Copy, Default)]
+pub struct ServerStaticStrategy {
+    renderer: ShadowDomRenderer,
+}
+
+impl RenderStrategy for ServerStaticStrategy {
+    fn name(&self) -> &'static str {
+        SERVER_STATIC_STRATEGY
+    }
+
+    fn render(
+        &self,
+        report: &Report,
+        language: &str,
+        _chart_modules_content: Option<&str>,
+    ) -> String {
+        // Reuse the shadow DOM sanitization, then drop every script
+        let sandboxed_report = self.renderer.create_sandboxed_report(report, None);
+        let html = if language == "en" {
+            sandboxed_report
+                .html_content_en
+                .as_deref()
+                .unwrap_or(&sandboxed_report.html_content)
+        } else {
+            &sandboxed_report.html_content
+        };
+
+        let mut body = String::from("<template shadowrootmode=\"open\">");
+        if let Some(css) = sandboxed_report.css_content.as_deref() {
+            let _ = write!(body, "<style>{css}</style>");
+        }
+        let _ = write!(
+            body,
+            "<div class=\"report-content\" lang=\"{language}\">{html}</div></template>"
+        );
+        body
+    }
+}
+
+/// A/B experiment sending a percentage of visitors to another strategy
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AbTest {
+    strategy: String,
+    percent: u8,
+}
+
+/// Named render strategies plus the per-request selection rules
+#[derive(Clone)]
+pub struct RenderStrategyRegistry {
+    strategies: HashMap<&'static str, Arc<dyn RenderStrategy>>,
+    default_strategy: Arc<dyn RenderStrategy>,
+    ab_test: Option<AbTest>,
+    allow_override: bool,
+}
+
+impl Default for RenderStrategyRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderStrategyRegistry {
+    /// Registry with the built-in strategies, defaulting to Shadow DOM
+    #[must_use]
+    pub fn new() -> Self {
+        let shadow_dom: Arc<dyn RenderStrategy> = Arc::new(ShadowDomStrategy::default());
+        let mut registry = Self {
+            strategies: HashMap::new(),
+            default_strategy: Arc::clone(&shadow_dom),
+            ab_test: None,
+            allow_override: false,
+        };
+        registry.register(shadow_dom);
+        registry.register(Arc::new(IframeStrategy));
+        registry.register(Arc::new(ServerStaticStrategy::default()));
+        registry
+    }
+
+    /// Built-in registry configured from `RENDER_STRATEGY*` environment variables
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut registry = Self::new();
+
+        if let Ok(name) = std::env::var("RENDER_STRATEGY")
+            && !registry.set_default(name.trim())
+        {
+            warn!(
+                "⚠️ Unknown RENDER_STRATEGY '{}', using {}",
+                name,
+                registry.default_strategy.name()
+            );
+        }
+
+        if let Ok(value) = std::env::var("RENDER_STRATEGY_AB") {
+            match parse_ab_test(&value) {
+                Some(ab_test) if registry.strategies.contains_key(ab_test.strategy.as_str()) => {
+                    registry.ab_test = Some(ab_test);
+                }
+                _ => warn!("⚠️ Ignoring invalid RENDER_STRATEGY_AB '{}'", value),
+            }
+        }
+
+        registry.allow_override = std::env::var("RENDER_STRATEGY_OVERRIDE")
+            .is_ok_and(|v| v.trim().eq_ignore_ascii_case("true") || v.trim() == "1");
+
+        registry
+    }
+
+    /// Add or replace a strategy under its name
+    pub fn register(&mut self, strategy: Arc<dyn RenderStrategy>) {
+        if strategy.name() == self.default_strategy.name() {
+            self.default_strategy = Arc::clone(&strategy);
+        }
+        self.strategies.insert(strategy.name(), strategy);
+    }
+
+    /// Make a registered strategy the default; returns `false` if unknown
+    pub fn set_default(&mut self, name: &str) -> bool {
+        match self.strategies.get(name) {
+            Some(strategy) => {
+                self.default_strategy = Arc::clone(strategy);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Look up a strategy by name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn RenderStrategy>> {
+        self.strategies.get(name)
+    }
+
+    /// Select the strategy for a request
+    ///
+    /// Priority: override (`?render=` then cookie, if enabled) > A/B bucket > default.
+    #[must_use]
+    pub fn select(
+        &self,
+        params: &HashMap<String, String>,
+        headers: &HeaderMap,
+    ) -> &Arc<dyn RenderStrategy> {
+        if self.allow_override {
+            let requested = params
+                .get(OVERRIDE_PARAM)
+                .map(String::as_str)
+                .or_else(|| cookie_value(headers, OVERRIDE_COOKIE));
+            if let Some(strategy) = requested.and_then(|name| self.get(name)) {
+                debug!("🎛️ [Render] Strategy override: {}", strategy.name());
+                return strategy;
+            }
+        }
+
+        if let Some(ab_test) = &self.ab_test
+            && let Some(visitor) = visitor_key(headers)
+            && ab_bucket(visitor) < ab_test.percent
+            && let Some(strategy) = self.get(&ab_test.strategy)
+        {
+            debug!("🧪 [Render] A/B strategy: {}", strategy.name());
+            return strategy;
+        }
+
+        self.default_strategy()
+    }
+
+    /// The default strategy
+    #[must_use]
+    pub fn default_strategy(&self) -> &Arc<dyn RenderStrategy> {
+        &self.default_strategy
+    }
+
+    /// Cache key variant (`CacheKeyBuilder::variant`) for a rendered page
+    ///
+    /// `None` for the default strategy so its keys stay plain per-language keys.
+    #[must_use]
+    pub fn cache_variant(&self, strategy: &dyn RenderStrategy) -> Option<&'static str> {
+        (strategy.name() != self.default_strategy.name()).then(|| strategy.name())
+    }
+}
+
+/// Parse `name:percent` (percent 0-100)
+fn parse_ab_test(value: &str) -> Option<AbTest> {
+    let (strategy, percent) = value.trim().split_once(':')?;
+    let percent = percent.trim().parse::<u8>().ok().filter(|p| *p <= 100)?;
+    Some(AbTest {
+        strategy: strategy.trim().to_string(),
+        percent,
+    })
+}
+
+/// Stable per-visitor identity: the `visitor_id` cookie
+///
+/// No IP fallback: shared caches only vary report pages on `Cookie`, so a bucket
+/// picked from the client address could be served to other visitors.
+fn visitor_key(headers: &HeaderMap) -> Option<&str> {
+    cookie_value(headers, VISITOR_COOKIE).filter(|v| !v.is_empty())
+}
+
+/// Deterministic 0-99 bucket for a visitor
+fn ab_bucket(visitor: &str) -> u8 {
+    let hash = blake3::hash(visitor.as_bytes());
+    let [first, second, ..] = *hash.as_bytes();
+    // u16 % 100 always fits in u8
+    u8::try_from(u16::from_le_bytes([first, second]) % 100).unwrap_or(0)
+}
+
+/// Value of a cookie from the `Cookie` header
+fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get("cookie")?
+        .to_str()
+        .ok()?
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(key, _)| key.trim() == name)
+        .map(|(_, value)| value.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> Report {
+        Report {
+            id: 7,
+            html_content: "<p>Xin chào</p>".to_string(),
+            css_content: Some("p { color: red; }".to_string()),
+            js_content: Some("console.log('vi')".to_string()),
+            html_content_en: Some("<p>Hello</p>".to_string()),
+            js_content_en: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            indexable: true,
+        }
+    }
+
+    #[test]
+    fn test_builtin_strategies_render() {
+        let registry = RenderStrategyRegistry::new();
+        let report = sample_report();
+
+        assert_eq!(registry.default_strategy().name(), SHADOW_DOM_STRATEGY);
+
+        let iframe = registry
+            .get(IFRAME_STRATEGY)
+            .map(|s| s.render(&report, "en", None));
+        assert!(iframe.is_some_and(|html| {
+            html.contains("/api/crypto_reports/7/sandboxed?expires=") && html.contains("&amp;sig=")
+        }));
+
+        let static_html = registry
+            .get(SERVER_STATIC_STRATEGY)
+            .map(|s| s.render(&report, "en", Some("chart()")))
+            .unwrap_or_default();
+        assert!(static_html.contains("<p>Hello</p>"));
+        assert!(static_html.contains("<style>"));
+        assert!(!static_html.contains("<script"));
+    }
+
+    #[test]
+    fn test_selection_priority() -> Result<(), Box<dyn std::error::Error>> {
+        let mut registry = RenderStrategyRegistry::new();
+        let mut params = HashMap::new();
+        params.insert("render".to_string(), IFRAME_STRATEGY.to_string());
+        let headers = HeaderMap::new();
+
+        // Overrides are ignored unless enabled
+        assert_eq!(
+            registry.select(&params, &headers).name(),
+            SHADOW_DOM_STRATEGY
+        );
+        registry.allow_override = true;
+        assert_eq!(registry.select(&params, &headers).name(), IFRAME_STRATEGY);
+
+        // 100% experiment applies to every identified visitor
+        registry.ab_test = parse_ab_test("server-static:100");
+        let mut visitor = HeaderMap::new();
+        visitor.insert("cookie", "visitor_id=abc; lang=vi".parse()?);
+        assert_eq!(
+            registry.select(&HashMap::new(), &visitor).name(),
+            SERVER_STATIC_STRATEGY
+        );
+        assert_eq!(
+            registry.select(&HashMap::new(), &headers).name(),
+            SHADOW_DOM_STRATEGY
+        );
+
+        // Without the cookie the client address does not pick a bucket
+        let mut forwarded = HeaderMap::new();
+        forwarded.insert("x-forwarded-for", "203.0.113.7".parse()?);
+        forwarded.insert("x-real-ip", "203.0.113.7".parse()?);
+        assert_eq!(
+            registry.select(&HashMap::new(), &forwarded).name(),
+            SHADOW_DOM_STRATEGY
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_variant_and_ab_parsing() {
+        let mut registry = RenderStrategyRegistry::new();
+        let shadow = ShadowDomStrategy::default();
+        assert_eq!(registry.cache_variant(&shadow), None);
+        assert_eq!(registry.cache_variant(&IframeStrategy), Some("iframe"));
+
+        assert!(registry.set_default(IFRAME_STRATEGY));
+        assert!(!registry.set_default("unknown"));
+        assert_eq!(registry.cache_variant(&IframeStrategy), None);
+
+        assert_eq!(
+            parse_ab_test("iframe:25"),
+            Some(AbTest {
+                strategy: "iframe".to_string(),
+                percent: 25
+            })
+        );
+        assert!(parse_ab_test("iframe:101").is_none());
+        assert!(parse_ab_test("iframe").is_none());
+        assert_eq!(ab_bucket("visitor"), ab_bucket("visitor"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Template Safety
+//!
+//! Report fragments come from the database and are spliced into templates, first via
+//! placeholder substitution (Shadow DOM template) and then as Tera context values.
+//! Any Tera-like delimiter in that content (`{{ }}`, `{% %}`, `{# #}`) is neutralized
+//! here so it can never be interpreted as template syntax - whether by Tera or by
+//! our own placeholder pass - and no context can be expanded into the page.
+//!
+//! - Markup: the delimiter brace is replaced by its character reference
+//!   (`&#123;` / `&#125;`), which renders identically in the browser.
+//! - Raw text (`<script>`, `<style>`, JS/CSS fields): a space is inserted between
+//!   the delimiter characters, which is insignificant to JS/CSS syntax.
+
+use regex::Regex;
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+/// Raw-text elements whose content must not receive HTML character references
+#[allow(clippy::expect_used)] // Safe: Regex pattern is hardcoded and verified
+static RAW_TEXT_ELEMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<script\b[^>]*>.*?</script\s*>|<style\b[^>]*>.*?</style\s*>")
+        .expect("Invalid regex")
+});
+
+/// Whether the content contains any template delimiter
+#[inline]
+#[must_use]
+pub fn contains_template_syntax(content: &str) -> bool {
+    ["{{", "}}", "{%", "%}", "{#", "#}"]
+        .iter()
+        .any(|delimiter| content.contains(delimiter))
+}
+
+/// Opening brace that starts a delimiter (`{{`, `{%`, `{#`)
+#[inline]
+fn opens_delimiter(next: Option<char>) -> bool {
+    matches!(next, Some('{' | '%' | '#'))
+}
+
+/// Closing brace that ends a delimiter (`}}`, `%}`, `#}`)
+#[inline]
+fn closes_delimiter(prev: Option<char>) -> bool {
+    matches!(prev, Some('}' | '%' | '#'))
+}
+
+/// Rewrite delimiter braces with the given replacements
+fn rewrite_delimiters(content: &str, open: &str, close: &str) -> String {
+    let mut output = String::with_capacity(content.len() + 16);
+    let mut prev = None;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if opens_delimiter(chars.peek().copied()) => output.push_str(open),
+            '}' if closes_delimiter(prev) => output.push_str(close),
+            _ => output.push(c),
+        }
+        prev = Some(c);
+    }
+    output
+}
+
+/// Neutralize template delimiters in JS/CSS code
+#[must_use]
+pub fn neutralize_code(code: &str) -> Cow<'_, str> {
+    if !contains_template_syntax(code) {
+        return Cow::Borrowed(code);
+    }
+    Cow::Owned(rewrite_delimiters(code, "{ ", " }"))
+}
+
+/// Neutralize template delimiters in HTML markup (no raw-text elements)
+#[must_use]
+pub fn neutralize_markup(markup: &str) -> Cow<'_, str> {
+    if !contains_template_syntax(markup) {
+        return Cow::Borrowed(markup);
+    }
+    Cow::Owned(rewrite_delimiters(markup, "&#123;", "&#125;"))
+}
+
+/// Neutralize template delimiters in a report HTML fragment
+///
+/// Markup gets character references; inline `<script>`/`<style>` blocks get
+/// the code treatment so their contents stay valid JS/CSS.
+#[must_use]
+pub fn neutralize_report_html(html: &str) -> Cow<'_, str> {
+    if !contains_template_syntax(html) {
+        return Cow::Borrowed(html);
+    }
+
+    let mut output = String::with_capacity(html.len() + 64);
+    let mut last_end = 0;
+    for raw_text in RAW_TEXT_ELEMENT.find_iter(html) {
+        if let Some(markup) = html.get(last_end..raw_text.start()) {
+            output.push_str(&neutralize_markup(markup));
+        }
+        output.push_str(&neutralize_code(raw_text.as_str()));
+        last_end = raw_text.end();
+    }
+    if let Some(markup) = html.get(last_end..) {
+        output.push_str(&neutralize_markup(markup));
+    }
+    Cow::Owned(output)
+}
+
+/// Substitute `{{name}}` placeholders in a single pass
+///
+/// Unlike chained `str::replace`, substituted values are never rescanned, so content
+/// that happens to contain another placeholder name cannot pull in other fields.
+/// Unknown placeholders are kept verbatim.
+#[must_use]
+pub fn substitute_placeholders(template: &str, values: &[(&str, &str)]) -> String {
+    let extra: usize = values.iter().map(|(_, value)| value.len()).sum();
+    let mut output = String::with_capacity(template.len() + extra);
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let (before, after_open) = rest.split_at(start);
+        output.push_str(before);
+
+        let after_open = after_open.get(2..).unwrap_or_default();
+        let Some(end) = after_open.find("}}") else {
+            output.push_str("{{");
+            rest = after_open;
+            continue;
+        };
+
+        let name = after_open.get(..end).unwrap_or_default();
+        if let Some((_, value)) = values.iter().find(|(key, _)| *key == name) {
+            output.push_str(value);
+        } else {
+            output.push_str("{{");
+            output.push_str(name);
+            output.push_str("}}");
+        }
+        rest = after_open.get(end + 2..).unwrap_or_default();
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_content_is_borrowed() {
+        let html = "<div class=\"report\"><p>BTC {price} up 5%</p></div>";
+        assert!(matches!(neutralize_report_html(html), Cow::Borrowed(_)));
+        assert!(matches!(
+            neutralize_code("function f(){ return {a: 1}; }"),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_hostile_markup_is_neutralized() {
+        let hostile = [
+            "{{ __tera_context }}",
+            "{% include \"crypto/routes/reports/list.html\" %}",
+            "{# unterminated comment",
+            "{{{ triple }}}",
+            "<a title=\"{{ config.secret }}\">x</a>",
+            "{%- raw -%}{{ shadow_dom_token }}{%- endraw -%}",
+        ];
+
+        for input in hostile {
+            let output = neutralize_report_html(input);
+            assert!(
+                !contains_template_syntax(&output),
+                "delimiters survived in {input:?}: {output:?}"
+            );
+        }
+
+        assert_eq!(
+            neutralize_markup("{{ x }}"),
+            "&#123;{ x }&#125;",
+            "markup uses character references"
+        );
+    }
+
+    #[test]
+    fn test_inline_script_keeps_valid_code() {
+        let html = "<p>{{ a }}</p><script>if (x) { y({z: 1}); }}</script><style>a{#b{}}</style>";
+        let output = neutralize_report_html(html);
+
+        assert!(!contains_template_syntax(&output));
+        assert!(output.contains("<p>&#123;{ a }&#125;</p>"));
+        // No character references inside raw-text elements
+        assert!(output.contains("<script>if (x) { y({z: 1}); } }</script>"));
+        assert!(output.contains("<style>a{ #b{} }</style>"));
+    }
+
+    #[test]
+    fn test_code_neutralization() {
+        let js = "const t = `{{name}}`; /* {% if %} */ let o = {{a: 1}};";
+        let output = neutralize_code(js);
+        assert!(!contains_template_syntax(&output));
+        assert!(output.contains("{ {a: 1} }"));
+    }
+
+    #[test]
+    fn test_substitute_placeholders_single_pass() {
+        let template =
+            "<div>{{html_content_vi}}</div><script>{{js_content_vi}}</script>{{unknown}}";
+        // Hostile content naming another placeholder must not be expanded
+        let output = substitute_placeholders(
+            template,
+            &[
+                ("html_content_vi", "<p>{{js_content_vi}}</p>"),
+                ("js_content_vi", "console.log('secret')"),
+            ],
+        );
+
+        assert_eq!(
+            output,
+            "<div><p>{{js_content_vi}}</p></div><script>console.log('secret')</script>{{unknown}}"
+        );
+        assert_eq!(substitute_placeholders("a {{ b", &[]), "a {{ b");
+    }
+}
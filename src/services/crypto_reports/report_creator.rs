@@ -5,23 +5,26 @@
 //!
 //! Rendering is handled by the `rendering` module:
 //! - `ShadowDomRenderer`: Modern Declarative Shadow DOM rendering
+//! - `RenderStrategyRegistry`: Per-request choice of how the report body is rendered
 
 use axum::http::StatusCode;
 use axum::response::Response;
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 // Import from current state - will be refactored when lower layers are implemented
 use crate::state::AppState;
 // Import Layer 3 data communication service - proper architecture
-use crate::services::data_communication::CryptoDataService;
+use crate::services::data_communication::{CryptoDataService, ReportEvent};
 // Chart modules are now in AppState
 
 // Import shared utilities
-use super::super::shared::{Layer5Result, build_error_response, build_not_found_response};
+use super::super::shared::{
+    Layer5Result, TraceContext, build_error_response, build_not_found_response,
+};
 
 // Import rendering modules
-use super::rendering::ShadowDomRenderer;
+use super::rendering::{RenderStrategyRegistry, ShadowDomRenderer};
 
 // Re-export for backward compatibility
 pub use super::rendering::{Report, SandboxedReport};
@@ -35,6 +38,7 @@ pub use super::rendering::{Report, SandboxedReport};
 pub struct ReportCreator {
     pub data_service: CryptoDataService,
     pub shadow_dom_renderer: ShadowDomRenderer,
+    pub render_strategies: RenderStrategyRegistry,
 }
 
 impl ReportCreator {
@@ -44,6 +48,7 @@ impl ReportCreator {
         Self {
             data_service: CryptoDataService::new(),
             shadow_dom_renderer: ShadowDomRenderer::new(),
+            render_strategies: RenderStrategyRegistry::from_env(),
         }
     }
 
@@ -84,9 +89,31 @@ impl ReportCreator {
             let report: Report = data.into();
 
             // Update latest id cache (business logic concern)
-            state
+            let previous = state
                 .cached_latest_id
-                .store(report.id, std::sync::atomic::Ordering::Relaxed);
+                .swap(report.id, std::sync::atomic::Ordering::Relaxed);
+            // A newer latest report means one was published since the last fetch
+            // (zero is the first fetch after startup, not a publication)
+            if previous != 0 && report.id > previous {
+                let state = Arc::clone(state);
+                let report_id = report.id;
+                tokio::spawn(async move {
+                    if let Err(e) = state
+                        .report_events
+                        .publish_report_event(
+                            ReportEvent::Published,
+                            report_id,
+                            &TraceContext::new_root(),
+                        )
+                        .await
+                    {
+                        warn!(
+                            "⚠️ Failed to publish report_published for {}: {}",
+                            report_id, e
+                        );
+                    }
+                });
+            }
             debug!(
                 "ReportCreator: Cached latest crypto report {} from data service",
                 report.id
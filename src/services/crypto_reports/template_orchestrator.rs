@@ -12,11 +12,22 @@ use tracing::{debug, error, info, warn};
 use super::report_creator::{Report, ReportCreator};
 
 // Import shared utilities
-use super::super::shared::{Layer5Error, Layer5Result, compress_html_to_gzip, get_websocket_url};
+use super::super::shared::{
+    Layer5Error, Layer5Result, compress_html_to_gzip, get_websocket_url, public_origin,
+    sandboxed_report_path, sign_sandbox_url,
+};
+use crate::services::data_communication::report_path;
+use crate::services::health_system::template_metrics;
+use std::time::Instant;
 use tokio::sync::OnceCell;
 
+/// Report view template (also the source of the cached report frame)
+const VIEW_TEMPLATE: &str = "crypto/routes/reports/view.html";
+
 // Placeholders for pre-rendering
 const PLACEHOLDER_SANDBOX_TOKEN: &str = "__PRE_RENDER_SANDBOX_TOKEN__";
+const PLACEHOLDER_SANDBOX_URL_QUERY: &str = "__PRE_RENDER_SANDBOX_URL_QUERY__";
+const PLACEHOLDER_REPORT_URL: &str = "__PRE_RENDER_REPORT_URL__";
 
 /// Template Context Data
 ///
@@ -61,14 +72,20 @@ impl TemplateOrchestrator {
         self.report_creator.health_check()
     }
 
-    /// Compress HTML content using shared compression utility
+    /// Compress HTML content using shared compression utility, recording the ratio
+    /// under `template_path`
     ///
     /// # Errors
     ///
     /// Returns error if gzip compression fails
     #[inline]
-    fn compress_html(html: &str) -> Layer5Result<Vec<u8>> {
+    fn compress_html(template_path: &str, html: &str) -> Layer5Result<Vec<u8>> {
         let (data, stats) = compress_html_to_gzip(html)?;
+        template_metrics().record_compression(
+            template_path,
+            stats.original_size,
+            stats.compressed_size,
+        );
         info!(
             "TemplateOrchestrator: Compression completed - Original: {}KB, Compressed: {}KB, Ratio: {:.1}%",
             stats.original_kb(),
@@ -94,6 +111,8 @@ impl TemplateOrchestrator {
             html_content_en: None,
             js_content_en: None,
             created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            indexable: true,
         };
 
         // Prepare context with placeholders
@@ -116,6 +135,14 @@ impl TemplateOrchestrator {
             "sandbox_token".to_string(),
             serde_json::Value::String(PLACEHOLDER_SANDBOX_TOKEN.to_string()),
         );
+        extra_context.insert(
+            "sandbox_url_query".to_string(),
+            serde_json::Value::String(PLACEHOLDER_SANDBOX_URL_QUERY.to_string()),
+        );
+        extra_context.insert(
+            "report_url".to_string(),
+            serde_json::Value::String(PLACEHOLDER_REPORT_URL.to_string()),
+        );
 
         // Let's use a distinct ID for the dummy report to avoid replacing legitimate 0s if they existed
         let placeholder_id = -999_999;
@@ -128,7 +155,7 @@ impl TemplateOrchestrator {
         context.additional_context = Some(extra_context);
 
         // Render the template
-        match self.render_template(tera, "crypto/routes/reports/view.html", context) {
+        match self.render_template(tera, VIEW_TEMPLATE, context) {
             Ok(html) => {
                 // Store the frame
                 if self.cached_report_frame.set(html).is_err() {
@@ -190,6 +217,11 @@ impl TemplateOrchestrator {
             .format("%Y-%m-%d %H:%M:%S UTC")
             .to_string();
         let pdf_url = format!("/crypto_report/{}/pdf", report.id);
+        let canonical_url = format!(
+            "{}{}",
+            public_origin(),
+            report_path(report.id, report.created_at)
+        );
 
         let mut context = TemplateContext {
             report: Arc::new(report),
@@ -207,6 +239,16 @@ impl TemplateOrchestrator {
             "sandbox_token".to_string(),
             serde_json::Value::String(sandboxed_report.sandbox_token),
         );
+        extra_context.insert(
+            "sandbox_url_query".to_string(),
+            serde_json::Value::String(sign_sandbox_url(&sandboxed_report_path(
+                sandboxed_report.id,
+            ))),
+        );
+        extra_context.insert(
+            "report_url".to_string(),
+            serde_json::Value::String(canonical_url),
+        );
         extra_context.insert(
             "websocket_url".to_string(),
             serde_json::Value::String(get_websocket_url()),
@@ -271,8 +313,10 @@ impl TemplateOrchestrator {
         }
 
         // Render template synchronously
+        let started = Instant::now();
         match tera.render(template_path, &tera_context) {
             Ok(html) => {
+                template_metrics().record_render(template_path, started.elapsed(), html.len());
                 info!("TemplateOrchestrator: Template rendered successfully");
                 Ok(html)
             }
@@ -328,7 +372,19 @@ impl TemplateOrchestrator {
 
             let html = frame
                 .replace(placeholder_id, &report_id_str)
-                .replace(PLACEHOLDER_SANDBOX_TOKEN, &sandbox_token_str);
+                .replace(PLACEHOLDER_SANDBOX_TOKEN, &sandbox_token_str)
+                .replace(
+                    PLACEHOLDER_SANDBOX_URL_QUERY,
+                    &sign_sandbox_url(&sandboxed_report_path(report.id)),
+                )
+                .replace(
+                    PLACEHOLDER_REPORT_URL,
+                    &format!(
+                        "{}{}",
+                        public_origin(),
+                        report_path(report.id, report.created_at)
+                    ),
+                );
 
             // Replace chart modules placeholder
             let chart_content = chart_modules_content.as_ref().map_or("", |s| s.as_str());
@@ -338,7 +394,7 @@ impl TemplateOrchestrator {
             );
 
             // Compress
-            let compressed_data = Self::compress_html(&html)?;
+            let compressed_data = Self::compress_html(VIEW_TEMPLATE, &html)?;
             info!("✅ Optimized render complete (RAM Cache + String Replacment)");
             return Ok(compressed_data);
         }
@@ -355,10 +411,10 @@ impl TemplateOrchestrator {
         )?;
 
         // Step 2: Render template
-        let html = self.render_template(tera, "crypto/routes/reports/view.html", context)?;
+        let html = self.render_template(tera, VIEW_TEMPLATE, context)?;
 
         // Step 3: Compress HTML
-        let compressed_data = Self::compress_html(&html)?;
+        let compressed_data = Self::compress_html(VIEW_TEMPLATE, &html)?;
 
         info!("TemplateOrchestrator: HTML compression completed successfully");
         Ok(compressed_data)
@@ -383,6 +439,8 @@ impl TemplateOrchestrator {
             html_content_en: None,
             js_content_en: None,
             created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            indexable: false,
         };
 
         // Prepare context
@@ -393,7 +451,7 @@ impl TemplateOrchestrator {
         context.pdf_url = "#".to_string();
 
         // Render template
-        self.render_template(tera, "crypto/routes/reports/view.html", context)
+        self.render_template(tera, VIEW_TEMPLATE, context)
     }
 
     /// Render 404 not found template
@@ -429,6 +487,8 @@ impl TemplateOrchestrator {
             html_content_en: None,
             js_content_en: None,
             created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            indexable: false,
         };
 
         // Prepare context
@@ -440,7 +500,7 @@ impl TemplateOrchestrator {
         context.pdf_url = "#".to_string();
 
         // Render template
-        self.render_template(tera, "crypto/routes/reports/view.html", context)
+        self.render_template(tera, VIEW_TEMPLATE, context)
     }
 }
 
@@ -4,7 +4,9 @@
 //! Originally located in src/handlers/api.rs, these handlers have been moved to the
 //! Dashboard Island as part of the Service Islands Architecture.
 
+use crate::dto::responses::FngHistoryPoint;
 use crate::services::dashboard_data_service::DashboardDataService;
+use crate::services::data_communication::{DEFAULT_FNG_DAYS, DEFAULT_LANGUAGE, FngHistory};
 use crate::state::AppState;
 use axum::{
     body::Body,
@@ -57,7 +59,6 @@ impl DashboardHandlers {
     pub fn create_compressed_response(compressed_data: Vec<u8>) -> Response {
         Response::builder()
             .status(StatusCode::OK)
-            .header("cache-control", "public, max-age=15")
             .header("x-cache", "compressed")
             .header("content-type", "text/html; charset=utf-8")
             .header("content-encoding", "gzip")
@@ -115,15 +116,16 @@ impl DashboardHandlers {
 
     /// Initialize homepage cache
     ///
-    /// Pre-renders the homepage and stores it in the cache.
+    /// Pre-renders the default-language homepage and stores it in the cache.
     /// Should be called during application startup.
     pub async fn init_homepage_cache(&self, state: &Arc<AppState>) {
         info!("🏗️ Pre-rendering homepage to cache...");
-        match Self::render_homepage_internal(state) {
+        let fng_sparkline = Self::fng_sparkline(state).await;
+        match Self::render_homepage_internal(state, DEFAULT_LANGUAGE, &fng_sparkline) {
             Ok(data) => {
                 if let Err(e) = self
                     .data_service
-                    .cache_rendered_homepage_compressed(state, &data)
+                    .cache_rendered_homepage_compressed(state, &data, DEFAULT_LANGUAGE)
                     .await
                 {
                     error!("❌ Failed to cache pre-rendered homepage: {}", e);
@@ -135,16 +137,30 @@ impl DashboardHandlers {
         }
     }
 
+    /// Daily Fear & Greed index drawn under the gauge, empty when it can't be read
+    async fn fng_sparkline(state: &Arc<AppState>) -> Vec<FngHistoryPoint> {
+        FngHistory::new()
+            .fetch_points(state, DEFAULT_FNG_DAYS, chrono::Utc::now().date_naive())
+            .await
+            .unwrap_or_else(|e| {
+                warn!("⚠️ Failed to load Fear & Greed sparkline: {}", e);
+                Vec::new()
+            })
+    }
+
     /// Internal function to render homepage
     fn render_homepage_internal(
         state: &Arc<AppState>,
+        language: &str,
+        fng_sparkline: &[FngHistoryPoint],
     ) -> Result<Vec<u8>, Box<dyn StdError + Send + Sync>> {
         // Render template with context
         let mut context = Context::new();
 
         // Add basic context for homepage
         context.insert("current_route", "homepage");
-        context.insert("current_lang", "vi");
+        context.insert("current_lang", language);
+        context.insert("site_origin", &crate::services::shared::public_origin());
         // Fixed time for pre-rendered page - client side JS handles updates if needed
         let current_time = chrono::Utc::now()
             .format("%Y-%m-%d %H:%M:%S UTC")
@@ -152,12 +168,18 @@ impl DashboardHandlers {
         context.insert("current_time", &current_time);
 
         // Add homepage-specific data
-        context.insert("page_title", "Trang chủ - Crypto Dashboard");
-        context.insert("welcome_message", "Chào mừng đến Crypto Dashboard");
-        context.insert(
-            "description",
-            "Theo dõi và phân tích thị trường tiền mã hóa",
-        );
+        if language == "en" {
+            context.insert("page_title", "Home - Crypto Dashboard");
+            context.insert("welcome_message", "Welcome to Crypto Dashboard");
+            context.insert("description", "Track and analyze the cryptocurrency market");
+        } else {
+            context.insert("page_title", "Trang chủ - Crypto Dashboard");
+            context.insert("welcome_message", "Chào mừng đến Crypto Dashboard");
+            context.insert(
+                "description",
+                "Theo dõi và phân tích thị trường tiền mã hóa",
+            );
+        }
 
         // Inject WebSocket service URL from environment variable
         let ws_url = std::env::var("WEBSOCKET_SERVICE_URL").unwrap_or_else(|_| {
@@ -170,6 +192,9 @@ impl DashboardHandlers {
         });
         context.insert("websocket_url", &ws_url);
 
+        // Trend under the Fear & Greed gauge, read by market-indicators.js
+        context.insert("fng_sparkline", fng_sparkline);
+
         // Render the template using the registered components
         // Use synchronous render as it's fast enough
         match state.tera.render("home.html", &context) {
@@ -188,42 +213,58 @@ impl DashboardHandlers {
 
     /// Homepage handler with Tera rendering - OPTIMIZED RAM CACHING
     ///
-    /// Returns the pre-rendered homepage from RAM or Redis.
+    /// Returns the pre-rendered homepage from RAM or Redis. With `bypass_cache`
+    /// (`X-Cache-Bypass`) the lookup is skipped and the fresh render replaces the cached copy.
     ///
     /// # Errors
     ///
     /// Returns error if cache retrieval fails or rendering fails
-    pub async fn homepage_with_tera(&self, state: &Arc<AppState>) -> Layer5Result<RenderedContent> {
+    pub async fn homepage_with_tera(
+        &self,
+        state: &Arc<AppState>,
+        language: &str,
+        bypass_cache: bool,
+    ) -> Layer5Result<RenderedContent> {
         // Optimized: Return cached content from multi-tier cache
-        if let Ok(Some(cached)) = self
-            .data_service
-            .get_rendered_homepage_compressed(state)
-            .await
+        if !bypass_cache
+            && let Ok(Some(cached)) = self
+                .data_service
+                .get_rendered_homepage_compressed(state, language)
+                .await
         {
             debug!("⚡ Serving homepage from multi-tier cache");
             return Ok(RenderedContent {
-                data: cached,
-                cache_control: "public, max-age=300",
+                data: cached.into(),
+                cache_control: None,
                 cache_status: "HIT",
+                last_modified: None,
             });
         }
 
         // Fallback: If not initialized, render and return (lazy init)
         debug!("⚠️ Homepage cache miss (lazy init)");
-        let data = Self::render_homepage_internal(state).map_err(|e| {
-            crate::services::shared::error::Layer5Error::TemplateRender(e.to_string())
-        })?;
+        let fng_sparkline = Self::fng_sparkline(state).await;
+        let data =
+            Self::render_homepage_internal(state, language, &fng_sparkline).map_err(|e| {
+                crate::services::shared::error::Layer5Error::TemplateRender(e.to_string())
+            })?;
 
         // Try to set cache for next time
         let _ = self
             .data_service
-            .cache_rendered_homepage_compressed(state, &data)
+            .cache_rendered_homepage_compressed(state, &data, language)
             .await;
 
-        Ok(RenderedContent {
-            data,
-            cache_control: "public, max-age=300",
+        let content = RenderedContent {
+            data: data.into(),
+            cache_control: None,
             cache_status: "MISS",
+            last_modified: None,
+        };
+        Ok(if bypass_cache {
+            content.bypassed()
+        } else {
+            content
         })
     }
 }
@@ -273,12 +314,8 @@ mod tests {
             headers.get("content-type").ok_or("Missing content-type")?,
             "text/html; charset=utf-8"
         );
-        assert_eq!(
-            headers
-                .get("cache-control")
-                .ok_or("Missing cache-control")?,
-            "public, max-age=15"
-        );
+        // Cache-Control comes from the route's cache policy
+        assert!(headers.get("cache-control").is_none());
         assert_eq!(
             headers.get("x-cache").ok_or("Missing x-cache")?,
             "compressed"
@@ -8,6 +8,7 @@ use std::sync::Arc;
 use tracing::info;
 
 // Import from current state - will be refactored when lower layers are implemented
+use crate::services::data_communication::{CacheKeyBuilder, CacheRoute};
 use crate::state::AppState;
 
 /// Dashboard Data Service
@@ -34,6 +35,14 @@ impl DashboardDataService {
         }
     }
 
+    /// Cache key of the rendered homepage
+    #[must_use]
+    pub fn homepage_cache_key(language: &str) -> String {
+        CacheKeyBuilder::new(CacheRoute::Homepage)
+            .language(language)
+            .build()
+    }
+
     /// Get cached rendered homepage HTML with compression
     ///
     /// Checks cache for pre-rendered and compressed homepage HTML
@@ -58,11 +67,12 @@ impl DashboardDataService {
     pub async fn get_rendered_homepage_compressed(
         &self,
         state: &Arc<AppState>,
+        language: &str,
     ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>> {
-        let cache_key = "dashboard_homepage_compressed";
+        let cache_key = Self::homepage_cache_key(language);
         let cache_manager = &state.cache_manager;
 
-        if let Ok(Some(cached_value)) = cache_manager.get(cache_key).await {
+        if let Ok(Some(cached_value)) = cache_manager.get(&cache_key).await {
             // Try to parse as Vec<u8> (Legacy JSON)
             if let Ok(compressed_bytes) = serde_json::from_slice::<Vec<u8>>(&cached_value) {
                 info!("🔥 DashboardDataService: Cache HIT (Legacy) for compressed homepage");
@@ -89,17 +99,18 @@ impl DashboardDataService {
         &self,
         state: &Arc<AppState>,
         compressed_data: &[u8],
+        language: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let cache_key = "dashboard_homepage_compressed";
+        let cache_key = Self::homepage_cache_key(language);
 
-        // Cache the compressed data for 15 minutes in both L1 and L2
+        // Cache the compressed data in both L1 and L2 (homepage TTL)
         let cache_manager = &state.cache_manager;
         let compressed_bytes = multi_tier_cache::Bytes::from(compressed_data.to_vec());
         let result = cache_manager
             .set_with_strategy(
-                cache_key,
+                &cache_key,
                 compressed_bytes,
-                multi_tier_cache::CacheStrategy::ShortTerm, // 5 minutes (ShortTerm is actually 5 mins, architected as 15 in docs but 5 in code)
+                state.cache_config.homepage.strategy(),
             )
             .await;
 
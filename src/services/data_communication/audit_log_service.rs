@@ -0,0 +1,105 @@
+//! Audit Log Service
+//!
+//! Layer 3 data communication service for the admin audit trail stored in
+//! `admin_audit_log` (see `migrations/20261015000000_create_admin_audit_log.sql`).
+//! Every admin action is recorded with its actor, parameters and outcome.
+
+use serde::Serialize;
+use sqlx::FromRow;
+use tracing::{debug, warn};
+
+use crate::services::health_system::current_request_id;
+use crate::state::AppState;
+
+/// Max entries returned by one audit log read
+pub const MAX_AUDIT_PAGE_SIZE: i64 = 500;
+
+/// One recorded admin action
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub action: String,
+    pub actor: String,
+    /// Request parameters as JSON text
+    pub parameters: String,
+    /// `ok` or `error: <message>`
+    pub outcome: String,
+    pub request_id: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Audit Log Service
+///
+/// Layer 3 service responsible for admin audit log database operations.
+#[derive(Clone, Default)]
+pub struct AuditLogService;
+
+impl AuditLogService {
+    /// Create a new `AuditLogService`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Record an admin action, tagged with the id of the request being served
+    ///
+    /// A failed insert is logged rather than returned: the action itself already ran.
+    pub async fn record(
+        &self,
+        state: &AppState,
+        action: &str,
+        actor: &str,
+        parameters: &serde_json::Value,
+        outcome: &str,
+    ) {
+        let result = sqlx::query(
+            "INSERT INTO admin_audit_log (action, actor, parameters, outcome, request_id) \
+             VALUES ($1, $2, $3::jsonb, $4, $5)",
+        )
+        .bind(action)
+        .bind(actor)
+        .bind(parameters.to_string())
+        .bind(outcome)
+        .bind(current_request_id().map(|id| id.to_string()))
+        .execute(&state.db)
+        .await;
+
+        match result {
+            Ok(_) => debug!("📝 Audit: {} by {} ({})", action, actor, outcome),
+            Err(e) => warn!(
+                action,
+                actor,
+                parameters = %parameters,
+                outcome,
+                "⚠️ Failed to write admin audit log entry: {}",
+                e
+            ),
+        }
+    }
+
+    /// Fetch the newest audit entries, optionally only those of one action
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_recent(
+        &self,
+        state: &AppState,
+        action: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditLogEntry>, sqlx::Error> {
+        sqlx::query_as::<_, AuditLogEntry>(
+            "SELECT id, action, actor, parameters::text AS parameters, outcome, request_id, created_at \
+             FROM admin_audit_log \
+             WHERE $1::text IS NULL OR action = $1 \
+             ORDER BY created_at DESC, id DESC \
+             LIMIT $2 OFFSET $3",
+        )
+        .bind(action)
+        .bind(limit.clamp(1, MAX_AUDIT_PAGE_SIZE))
+        .bind(offset.max(0))
+        .fetch_all(&state.db)
+        .await
+    }
+}
@@ -0,0 +1,345 @@
+//! Caller Authentication
+//!
+//! Layer 3 authentication of API callers, either by `X-Api-Key` against the hashed
+//! keys in `api_keys` (see `migrations/20261015090000_create_api_keys.sql`) or by an
+//! OIDC bearer token (see `jwt`). `/admin/*` needs the `admin` scope and mutating
+//! requests (POST, PUT, PATCH, DELETE) elsewhere need `write`. Admin requests
+//! without credentials still fall back to the shared `ADMIN_TOKEN`.
+
+use axum::{
+    extract::{Request, State},
+    http::{Method, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use super::jwt::{JwtVerifier, looks_like_jwt};
+use crate::services::shared::{Layer5Error, authorize_admin};
+use crate::state::AppState;
+
+/// Header carrying an API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Mutating routes open to anonymous visitors (still CSRF-protected, or JSON only)
+const PUBLIC_MUTATIONS: &[&str] = &[
+    "/api/subscribe",
+    "/api/subscribe/confirm",
+    "/api/unsubscribe",
+];
+
+tokio::task_local! {
+    static CURRENT_CALLER: CallerIdentity;
+}
+
+/// What a key may do; each scope grants the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiScope {
+    Read,
+    Write,
+    Admin,
+}
+
+impl ApiScope {
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "read" => Some(Self::Read),
+            "write" => Some(Self::Write),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Scope a request needs: `admin` under `/admin`, `write` for other mutating
+    /// requests, none for reads and `PUBLIC_MUTATIONS`
+    #[must_use]
+    pub fn required_for(method: &Method, path: &str) -> Option<Self> {
+        if path == "/admin" || path.starts_with("/admin/") {
+            Some(Self::Admin)
+        } else if matches!(
+            *method,
+            Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+        ) && !PUBLIC_MUTATIONS.contains(&path)
+        {
+            Some(Self::Write)
+        } else {
+            None
+        }
+    }
+}
+
+/// An active key from `api_keys`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    pub id: i64,
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+}
+
+/// How a caller proved who they are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMethod {
+    ApiKey,
+    Jwt,
+}
+
+/// Authenticated caller, attached to the request extensions of its requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallerIdentity {
+    pub method: AuthMethod,
+    /// Key name or token subject
+    pub subject: String,
+    pub scopes: Vec<ApiScope>,
+    /// `api_keys.id` of an API key caller
+    pub key_id: Option<i64>,
+}
+
+impl CallerIdentity {
+    #[must_use]
+    pub fn allows(&self, required: ApiScope) -> bool {
+        self.scopes.iter().any(|&scope| scope >= required)
+    }
+
+    /// `key:<name>` or `jwt:<subject>`, as recorded in the audit log
+    #[must_use]
+    pub fn actor(&self) -> String {
+        let prefix = match self.method {
+            AuthMethod::ApiKey => "key",
+            AuthMethod::Jwt => "jwt",
+        };
+        format!("{prefix}:{}", self.subject)
+    }
+}
+
+impl From<ApiKey> for CallerIdentity {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            method: AuthMethod::ApiKey,
+            subject: key.name,
+            scopes: key.scopes,
+            key_id: Some(key.id),
+        }
+    }
+}
+
+/// Stored form of a key: hex blake3 hash
+#[must_use]
+pub fn hash_api_key(key: &str) -> String {
+    blake3::hash(key.trim().as_bytes()).to_hex().to_string()
+}
+
+/// Caller of the request being served, if authenticated
+#[must_use]
+pub fn current_caller() -> Option<CallerIdentity> {
+    CURRENT_CALLER.try_with(Clone::clone).ok()
+}
+
+/// API Key Service
+///
+/// Layer 3 service responsible for API key lookups.
+#[derive(Clone, Default)]
+pub struct ApiKeyService;
+
+impl ApiKeyService {
+    /// Create a new `ApiKeyService`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Look up an unrevoked key and record its use; `None` when unknown or revoked
+    ///
+    /// # Errors
+    /// Returns an error if the lookup query fails
+    pub async fn authenticate(
+        &self,
+        db: &PgPool,
+        key: &str,
+    ) -> Result<Option<ApiKey>, sqlx::Error> {
+        let row: Option<(i64, String, Vec<String>)> = sqlx::query_as(
+            "UPDATE api_keys SET last_used_at = now() \
+             WHERE key_hash = $1 AND revoked_at IS NULL \
+             RETURNING id, name, scopes",
+        )
+        .bind(hash_api_key(key))
+        .fetch_optional(db)
+        .await?;
+
+        Ok(row.map(|(id, name, scopes)| ApiKey {
+            id,
+            name,
+            scopes: scopes.iter().filter_map(|s| ApiScope::parse(s)).collect(),
+        }))
+    }
+}
+
+/// Credentials presented by a request
+enum Credentials {
+    ApiKey(String),
+    Jwt(String),
+}
+
+fn presented_credentials(request: &Request) -> Option<Credentials> {
+    let headers = request.headers();
+    if let Some(key) = headers
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+    {
+        return Some(Credentials::ApiKey(key.to_string()));
+    }
+    // Other bearer values are the shared ADMIN_TOKEN
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| JwtVerifier::current().is_some() && looks_like_jwt(token))
+        .map(|token| Credentials::Jwt(token.to_string()))
+}
+
+/// Resolve credentials to a caller
+async fn authenticate_caller(
+    state: &AppState,
+    credentials: Credentials,
+) -> Result<CallerIdentity, Layer5Error> {
+    match credentials {
+        Credentials::ApiKey(key) => {
+            let key = ApiKeyService::new().authenticate(&state.db, &key).await?;
+            key.map(CallerIdentity::from).ok_or_else(|| {
+                warn!("🔑 Rejected unknown or revoked API key");
+                Layer5Error::Unauthorized("Invalid API key".to_string())
+            })
+        }
+        Credentials::Jwt(token) => {
+            let Some(verifier) = JwtVerifier::current() else {
+                return Err(Layer5Error::Unauthorized(
+                    "JWTs are not accepted".to_string(),
+                ));
+            };
+            match verifier.verify(&token).await {
+                Ok(claims) => Ok(CallerIdentity {
+                    method: AuthMethod::Jwt,
+                    scopes: claims.scopes(),
+                    subject: claims.subject,
+                    key_id: None,
+                }),
+                Err(e) => {
+                    warn!("🔑 Rejected bearer token: {}", e);
+                    Err(Layer5Error::Unauthorized(format!(
+                        "Invalid bearer token: {e}"
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Middleware enforcing the scope each request needs
+///
+/// Presented credentials (`X-Api-Key` or a bearer JWT) must be valid (401) and carry
+/// the scope (403); the caller is then attached to the request extensions and
+/// `current_caller`. Without credentials, `/admin/*` requires `ADMIN_TOKEN` and other
+/// mutating requests are rejected.
+pub async fn authenticate(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let required = ApiScope::required_for(request.method(), request.uri().path());
+    let Some(credentials) = presented_credentials(&request) else {
+        return match required {
+            None => next.run(request).await,
+            Some(ApiScope::Admin) => match authorize_admin(request.headers()) {
+                Ok(()) => next.run(request).await,
+                Err(e) => e.into_response(),
+            },
+            Some(scope) => Layer5Error::Unauthorized(format!(
+                "An API key or bearer token with the {} scope is required",
+                scope.as_str()
+            ))
+            .into_response(),
+        };
+    };
+
+    let caller = match authenticate_caller(&state, credentials).await {
+        Ok(caller) => caller,
+        Err(e) => return e.into_response(),
+    };
+    if let Some(scope) = required.filter(|&scope| !caller.allows(scope)) {
+        warn!(caller = %caller.actor(), "🔑 Caller lacks the {} scope", scope.as_str());
+        return Layer5Error::Forbidden(format!(
+            "{} lacks the {} scope",
+            caller.actor(),
+            scope.as_str()
+        ))
+        .into_response();
+    }
+
+    debug!(caller = %caller.actor(), "🔑 Authenticated caller");
+    request.extensions_mut().insert(caller.clone());
+    CURRENT_CALLER.scope(caller, next.run(request)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(
+            ApiScope::required_for(&Method::GET, "/admin/perf"),
+            Some(ApiScope::Admin)
+        );
+        assert_eq!(
+            ApiScope::required_for(&Method::POST, "/admin/cache/purge"),
+            Some(ApiScope::Admin)
+        );
+        assert_eq!(
+            ApiScope::required_for(&Method::POST, "/api/reports"),
+            Some(ApiScope::Write)
+        );
+        assert_eq!(ApiScope::required_for(&Method::GET, "/crypto_report"), None);
+        assert_eq!(
+            ApiScope::required_for(&Method::POST, "/api/subscribe"),
+            None
+        );
+        assert_eq!(ApiScope::required_for(&Method::GET, "/administrator"), None);
+    }
+
+    #[test]
+    fn test_scopes_grant_lower_scopes() {
+        let caller = CallerIdentity::from(ApiKey {
+            id: 1,
+            name: "ci".to_string(),
+            scopes: vec![ApiScope::Write],
+        });
+        assert!(caller.allows(ApiScope::Read));
+        assert!(caller.allows(ApiScope::Write));
+        assert!(!caller.allows(ApiScope::Admin));
+        assert_eq!(caller.actor(), "key:ci");
+        assert_eq!(ApiScope::parse(" Admin "), Some(ApiScope::Admin));
+        assert_eq!(ApiScope::parse("root"), None);
+    }
+
+    #[test]
+    fn test_key_hash_is_b3sum_hex() {
+        let hash = hash_api_key("secret-key");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_api_key(" secret-key\n"));
+        assert_ne!(hash, hash_api_key("other-key"));
+    }
+}
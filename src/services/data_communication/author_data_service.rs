@@ -0,0 +1,186 @@
+//! Author Data Service
+//!
+//! Layer 3 data communication service for report authors.
+//! Reads the `authors` table and the `crypto_report_authors` relation
+//! (see `migrations/20261014000000_create_report_authors.sql`), which link
+//! one or more analysts to every published report.
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use super::crypto_data_service::{ReportRssData, ReportSummaryData};
+use crate::state::AppState;
+
+/// Max length of an author slug (`/author/{slug}`)
+const MAX_SLUG_LENGTH: usize = 64;
+
+/// Author profile
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuthorData {
+    pub id: i32,
+    pub slug: String,
+    pub name: String,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    /// External social profile, used as `sameAs` in JSON-LD
+    pub profile_url: Option<String>,
+}
+
+impl AuthorData {
+    /// Site-relative URL of the author archive page
+    #[must_use]
+    pub fn archive_path(&self) -> String {
+        format!("/author/{}", self.slug)
+    }
+}
+
+/// Whether a slug is well-formed (lowercase ASCII letters, digits and dashes)
+///
+/// Checked before touching the database so arbitrary paths are rejected cheaply.
+#[must_use]
+pub fn is_valid_author_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LENGTH
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && slug
+            .bytes()
+            .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-'))
+}
+
+/// Author Data Service
+///
+/// Layer 3 service responsible for author and report-author database operations.
+#[derive(Clone, Default)]
+pub struct AuthorDataService;
+
+impl AuthorDataService {
+    /// Create a new `AuthorDataService`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Fetch an author by slug
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_author_by_slug(
+        &self,
+        state: &Arc<AppState>,
+        slug: &str,
+    ) -> Result<Option<AuthorData>, sqlx::Error> {
+        debug!("🗄️ AuthorDataService: Fetching author '{}'", slug);
+
+        sqlx::query_as::<_, AuthorData>(
+            "SELECT id, slug, name, bio, avatar_url, profile_url FROM authors WHERE slug = $1",
+        )
+        .bind(slug)
+        .fetch_optional(&state.db)
+        .await
+    }
+
+    /// Fetch the authors of a report in byline order
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_report_authors(
+        &self,
+        state: &Arc<AppState>,
+        report_id: i32,
+    ) -> Result<Vec<AuthorData>, sqlx::Error> {
+        let authors = sqlx::query_as::<_, AuthorData>(
+            "SELECT a.id, a.slug, a.name, a.bio, a.avatar_url, a.profile_url \
+             FROM crypto_report_authors ra \
+             JOIN authors a ON a.id = ra.author_id \
+             WHERE ra.report_id = $1 \
+             ORDER BY ra.position, a.name",
+        )
+        .bind(report_id)
+        .fetch_all(&state.db)
+        .await?;
+
+        debug!(
+            "📊 AuthorDataService: Report {} has {} authors",
+            report_id,
+            authors.len()
+        );
+        Ok(authors)
+    }
+
+    /// Fetch an author's reports (newest first) for the archive page
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_author_reports(
+        &self,
+        state: &Arc<AppState>,
+        author_id: i32,
+        limit: i64,
+    ) -> Result<Vec<ReportSummaryData>, sqlx::Error> {
+        sqlx::query_as::<_, ReportSummaryData>(
+            "SELECT r.id, r.created_at \
+             FROM crypto_report r \
+             JOIN crypto_report_authors ra ON ra.report_id = r.id \
+             WHERE ra.author_id = $1 \
+             ORDER BY r.created_at DESC \
+             LIMIT $2",
+        )
+        .bind(author_id)
+        .bind(limit)
+        .fetch_all(&state.db)
+        .await
+    }
+
+    /// Fetch an author's reports with content for the per-author RSS feed
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_author_rss_reports(
+        &self,
+        state: &Arc<AppState>,
+        author_id: i32,
+        limit: i64,
+    ) -> Result<Vec<ReportRssData>, sqlx::Error> {
+        info!(
+            "🗄️ AuthorDataService: Fetching {} reports for author {} RSS feed",
+            limit, author_id
+        );
+
+        sqlx::query_as::<_, ReportRssData>(
+            "SELECT r.id, r.html_content, r.html_content_en, r.created_at \
+             FROM crypto_report r \
+             JOIN crypto_report_authors ra ON ra.report_id = r.id \
+             WHERE ra.author_id = $1 AND r.indexable \
+             ORDER BY r.created_at DESC \
+             LIMIT $2",
+        )
+        .bind(author_id)
+        .bind(limit)
+        .fetch_all(&state.db)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_author_slug_validation() {
+        assert!(is_valid_author_slug("nguyen-van-a"));
+        assert!(is_valid_author_slug("analyst42"));
+
+        assert!(!is_valid_author_slug(""));
+        assert!(!is_valid_author_slug("-leading"));
+        assert!(!is_valid_author_slug("Upper-Case"));
+        assert!(!is_valid_author_slug("../etc/passwd"));
+        assert!(!is_valid_author_slug(&"a".repeat(MAX_SLUG_LENGTH + 1)));
+    }
+}
@@ -0,0 +1,318 @@
+//! Page Cache Keys
+//!
+//! Single place that builds the cache keys of rendered pages and feeds. Every
+//! localized route carries the response language in its key (falling back to
+//! `DEFAULT_LANGUAGE`), so a page rendered for one language is never served to a
+//! visitor of the other.
+//!
+//! Key layout: `{version}:{prefix}_{segments...}_{language}_{variant}_{origin}{suffix}`, e.g.
+//! `a1b2c3d4e5f6:compressed_report_dsd_42_en_iframe` or `a1b2c3d4e5f6:crypto_reports_list_page_2_vi_compressed`.
+//! The origin segment is only present for requests served under a forwarded host
+//! (see `public_origin`), whose pages embed that host in their absolute URLs.
+//!
+//! `version` is the deploy identifier (`DEPLOY_ID` at runtime, else `BUILD_ID` at
+//! compile time, else the git commit recorded by `build.rs`, else its build
+//! timestamp): a deployment with changed templates starts with empty page keys
+//! instead of serving HTML rendered by the previous one. Old entries are never read
+//! again and simply expire.
+
+use std::fmt::Display;
+use std::sync::OnceLock;
+use tracing::info;
+
+use crate::services::health_system::build_info::GIT_COMMIT;
+use crate::services::shared::origin_key_segment;
+
+/// Languages pages are rendered in
+pub const SUPPORTED_LANGUAGES: [&str; 2] = ["vi", "en"];
+/// Language used when none (or an unsupported one) is requested
+pub const DEFAULT_LANGUAGE: &str = "vi";
+
+/// Max length of the deploy identifier in keys
+const MAX_VERSION_LENGTH: usize = 40;
+/// Commit hash prefix used when no deploy identifier is set
+const COMMIT_VERSION_LENGTH: usize = 12;
+
+/// Deploy identifier prefixed to every rendered-content key
+#[must_use]
+pub fn cache_version() -> &'static str {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        let version = parse_cache_version(
+            std::env::var("DEPLOY_ID")
+                .ok()
+                .as_deref()
+                .or(option_env!("BUILD_ID")),
+        );
+        info!("🏷️ Cache key version: {}", version);
+        version
+    })
+}
+
+/// Prefix an ad-hoc rendered-content key with `cache_version()`
+#[must_use]
+pub fn versioned_key(key: &str) -> String {
+    format!("{}:{key}", cache_version())
+}
+
+/// Sanitized deploy identifier, falling back to the build's own identity
+fn parse_cache_version(raw: Option<&str>) -> String {
+    let version: String = raw
+        .unwrap_or_default()
+        .trim()
+        .chars()
+        .take(MAX_VERSION_LENGTH)
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if version.is_empty() {
+        build_version(GIT_COMMIT, env!("BUILD_TIMESTAMP"))
+    } else {
+        version
+    }
+}
+
+/// Identifier of a build without `DEPLOY_ID` / `BUILD_ID`: its commit, or its
+/// build time when git was unavailable
+///
+/// Never the crate version, which rarely changes between deployments.
+fn build_version(commit: &str, built_at: &str) -> String {
+    let commit = commit.trim();
+    if !commit.is_empty()
+        && commit != "unknown"
+        && commit.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        commit.chars().take(COMMIT_VERSION_LENGTH).collect()
+    } else {
+        format!("build-{}", built_at.trim())
+    }
+}
+
+/// Map a requested language to a supported one
+#[must_use]
+pub fn normalize_language(language: &str) -> &'static str {
+    let language = language.trim();
+    SUPPORTED_LANGUAGES
+        .into_iter()
+        .find(|supported| supported.eq_ignore_ascii_case(language))
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
+
+/// Cached route families
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheRoute {
+    /// Report page (`/crypto_report`, `/crypto_report/{id}`)
+    ReportDsd,
+    /// Reports list page (`/crypto_reports_list`)
+    ReportsList,
+    /// Homepage (`/`)
+    Homepage,
+    /// Site-wide RSS feed (`/rss.xml`)
+    RssFeed,
+    /// Site-wide Atom feed (`/atom.xml`)
+    AtomFeed,
+    /// Site-wide JSON Feed (`/feed.json`)
+    JsonFeed,
+    /// Author archive page (`/author/{slug}`)
+    AuthorPage,
+    /// Per-author RSS feed (`/author/{slug}/rss.xml`)
+    AuthorRss,
+    /// `/sitemap.xml` (language-neutral)
+    Sitemap,
+}
+
+impl CacheRoute {
+    const fn prefix(self) -> &'static str {
+        match self {
+            Self::ReportDsd => "compressed_report_dsd",
+            Self::ReportsList => "crypto_reports_list_page",
+            Self::Homepage => "dashboard_homepage",
+            Self::RssFeed => "rss_feed",
+            Self::AtomFeed => "atom_feed",
+            Self::JsonFeed => "json_feed",
+            Self::AuthorPage => "author_page",
+            Self::AuthorRss => "author_rss",
+            Self::Sitemap => "sitemap",
+        }
+    }
+
+    const fn suffix(self) -> &'static str {
+        match self {
+            Self::ReportDsd => "",
+            Self::ReportsList | Self::Homepage | Self::AuthorPage => "_compressed",
+            Self::RssFeed | Self::AtomFeed | Self::AuthorRss | Self::Sitemap => "_xml_compressed",
+            Self::JsonFeed => "_json_compressed",
+        }
+    }
+
+    /// Whether responses of this route differ per language
+    #[must_use]
+    pub const fn is_localized(self) -> bool {
+        !matches!(self, Self::Sitemap)
+    }
+}
+
+/// Builder for page cache keys
+///
+/// ```
+/// use web_server_report::services::data_communication::{CacheKeyBuilder, CacheRoute};
+///
+/// let key = CacheKeyBuilder::new(CacheRoute::ReportsList)
+///     .segment(2)
+///     .language("en")
+///     .build();
+/// assert!(key.ends_with(":crypto_reports_list_page_2_en_compressed"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CacheKeyBuilder {
+    route: CacheRoute,
+    segments: Vec<String>,
+    language: &'static str,
+    variant: Option<String>,
+}
+
+impl CacheKeyBuilder {
+    /// Start a key for `route` in `DEFAULT_LANGUAGE`
+    #[must_use]
+    pub fn new(route: CacheRoute) -> Self {
+        Self {
+            route,
+            segments: Vec::new(),
+            language: DEFAULT_LANGUAGE,
+            variant: None,
+        }
+    }
+
+    /// Append an identifying segment (report id, page number, author slug)
+    #[must_use]
+    pub fn segment(mut self, segment: impl Display) -> Self {
+        self.segments.push(segment.to_string());
+        self
+    }
+
+    /// Set the response language (unsupported values fall back to the default)
+    #[must_use]
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = normalize_language(language);
+        self
+    }
+
+    /// Set a rendering variant, e.g. a non-default render strategy
+    #[must_use]
+    pub fn variant(mut self, variant: Option<&str>) -> Self {
+        self.variant = variant.map(ToString::to_string);
+        self
+    }
+
+    /// Build the versioned key
+    #[must_use]
+    pub fn build(&self) -> String {
+        let mut key = format!("{}:{}", cache_version(), self.route.prefix());
+        for segment in &self.segments {
+            key.push('_');
+            key.push_str(segment);
+        }
+        if self.route.is_localized() {
+            key.push('_');
+            key.push_str(self.language);
+        }
+        if let Some(variant) = &self.variant {
+            key.push('_');
+            key.push_str(variant);
+        }
+        if let Some(origin) = origin_key_segment() {
+            key.push('_');
+            key.push_str(&origin);
+        }
+        key.push_str(self.route.suffix());
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_localized_keys_include_language() {
+        let key = |route, language| CacheKeyBuilder::new(route).language(language).build();
+        assert_eq!(
+            key(CacheRoute::Homepage, "en"),
+            versioned_key("dashboard_homepage_en_compressed")
+        );
+        assert_eq!(
+            key(CacheRoute::Homepage, "vi"),
+            versioned_key("dashboard_homepage_vi_compressed")
+        );
+        assert_eq!(
+            key(CacheRoute::RssFeed, "en"),
+            versioned_key("rss_feed_en_xml_compressed")
+        );
+        assert_eq!(
+            key(CacheRoute::Sitemap, "en"),
+            versioned_key("sitemap_xml_compressed")
+        );
+        // No language set: default language, never an unlocalized key
+        assert_eq!(
+            CacheKeyBuilder::new(CacheRoute::ReportsList)
+                .segment(1)
+                .build(),
+            versioned_key("crypto_reports_list_page_1_vi_compressed")
+        );
+    }
+
+    #[test]
+    fn test_report_keys_with_variant() {
+        assert_eq!(
+            CacheKeyBuilder::new(CacheRoute::ReportDsd)
+                .segment(-1)
+                .language("vi")
+                .build(),
+            versioned_key("compressed_report_dsd_-1_vi")
+        );
+        assert_eq!(
+            CacheKeyBuilder::new(CacheRoute::ReportDsd)
+                .segment(42)
+                .language("EN")
+                .variant(Some("iframe"))
+                .build(),
+            versioned_key("compressed_report_dsd_42_en_iframe")
+        );
+        assert_eq!(
+            CacheKeyBuilder::new(CacheRoute::AuthorRss)
+                .segment("jane-doe")
+                .language("fr")
+                .build(),
+            versioned_key("author_rss_jane-doe_vi_xml_compressed")
+        );
+    }
+
+    #[test]
+    fn test_cache_version_sanitized() {
+        assert_eq!(
+            parse_cache_version(Some(" 2026.10.14-a1b2c3 ")),
+            "2026.10.14-a1b2c3"
+        );
+        assert_eq!(parse_cache_version(Some("feat/x:*")), "feat-x--");
+        let fallback = build_version(GIT_COMMIT, env!("BUILD_TIMESTAMP"));
+        assert_eq!(parse_cache_version(Some("")), fallback);
+        assert_eq!(parse_cache_version(None), fallback);
+        assert_ne!(fallback, env!("CARGO_PKG_VERSION"));
+        assert!(versioned_key("compressed_report_7").starts_with(cache_version()));
+    }
+
+    #[test]
+    fn test_build_version_from_commit_or_timestamp() {
+        assert_eq!(
+            build_version("0123456789abcdef0123", "1760500000"),
+            "0123456789ab"
+        );
+        assert_eq!(build_version("unknown", "1760500000"), "build-1760500000");
+        assert_eq!(build_version("", "1760500000"), "build-1760500000");
+    }
+}
@@ -8,10 +8,15 @@
 
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use tracing::{debug, error, info, warn};
 
 // Import from current state - will be refactored when lower layers are implemented
+use super::cache_keys::{CacheKeyBuilder, CacheRoute, versioned_key};
+use super::query_timing::{QueryParam, timed_query};
+use super::report_slug::{report_path, report_slug};
+use crate::services::crypto_reports::rendering::generate_list_page_schema;
+use crate::services::shared::{cache_key_stats, public_origin};
 use crate::state::AppState;
 use base64::{Engine, prelude::BASE64_STANDARD};
 
@@ -26,6 +31,27 @@ use base64::{Engine, prelude::BASE64_STANDARD};
 const MAX_COMPRESSED_ENTRY_SIZE: usize = 5 * 1024 * 1024; // 5MB per entry (soft limit for logging)
 const WARN_COMPRESSED_ENTRY_SIZE: usize = 2 * 1024 * 1024; // 2MB warning threshold
 
+/// Default hard limit for rendered report HTML before the oversized path kicks in
+const DEFAULT_REPORT_HTML_MAX_BYTES: usize = 5 * 1024 * 1024; // 5MB
+/// Max rows returned by the oversized reports query
+const OVERSIZED_REPORTS_LIMIT: i64 = 100;
+
+/// Rendered report HTML size limit (`REPORT_HTML_MAX_BYTES`, default 5MB)
+///
+/// Reports above this limit are streamed instead of buffered and never cached,
+/// so one pathological report cannot evict the whole L1 cache.
+#[must_use]
+pub fn report_html_max_bytes() -> usize {
+    static LIMIT: OnceLock<usize> = OnceLock::new();
+    *LIMIT.get_or_init(|| {
+        std::env::var("REPORT_HTML_MAX_BYTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&v: &usize| v > 0)
+            .unwrap_or(DEFAULT_REPORT_HTML_MAX_BYTES)
+    })
+}
+
 /// Report model for data layer - matches business logic model
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ReportData {
@@ -36,6 +62,24 @@ pub struct ReportData {
     pub html_content_en: Option<String>,
     pub js_content_en: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Last change of any report column (`Last-Modified` of its pages)
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// `false` keeps the report out of search engines, sitemaps and feeds
+    pub indexable: bool,
+}
+
+/// URL slug and indexing flag of a report, checked on every report request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportRoute {
+    pub slug: String,
+    /// `false` answers with `X-Robots-Tag: noindex, nofollow`
+    pub indexable: bool,
+}
+
+/// Cache key of a report's `ReportRoute`
+#[must_use]
+pub fn report_route_cache_key(report_id: i32) -> String {
+    format!("report_route_{report_id}")
 }
 
 /// Report summary for data layer
@@ -54,11 +98,35 @@ pub struct ReportSitemapData {
 }
 
 /// Report data for RSS feed generation
-/// Contains id, `html_content` for description extraction, and `created_at` for pubDate
+/// Contains id, `html_content` / `html_content_en` for description extraction, and `created_at` for pubDate
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ReportRssData {
     pub id: i32,
     pub html_content: String,
+    pub html_content_en: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ReportRssData {
+    /// HTML in `language`; English falls back to the Vietnamese content for
+    /// untranslated reports
+    #[must_use]
+    pub fn html_for_language(&self, language: &str) -> &str {
+        self.html_content_en
+            .as_deref()
+            .filter(|_| language == "en")
+            .unwrap_or(&self.html_content)
+    }
+}
+
+/// Content sizes of a report exceeding the HTML size limit
+/// Used by `/admin/reports/oversized` for editorial cleanup
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OversizedReportData {
+    pub id: i32,
+    pub html_bytes: i64,
+    pub html_en_bytes: Option<i64>,
+    pub total_bytes: i64,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -101,9 +169,16 @@ impl CryptoDataService {
     ) -> Result<Option<ReportData>, sqlx::Error> {
         info!("🗄️ CryptoDataService: Fetching latest crypto report from database");
 
-        let report = sqlx::query_as::<_, ReportData>(
-                "SELECT id, html_content, css_content, js_content, html_content_en, js_content_en, created_at FROM crypto_report ORDER BY created_at DESC LIMIT 1",
-            ).fetch_optional(&state.db).await?;
+        let report = timed_query(
+            state,
+            "latest_report",
+            &[],
+            sqlx::query_as::<_, ReportData>(
+                "SELECT id, html_content, css_content, js_content, html_content_en, js_content_en, created_at, updated_at, indexable FROM crypto_report ORDER BY created_at DESC LIMIT 1",
+            )
+            .fetch_optional(&state.db),
+        )
+        .await?;
 
         if let Some(ref report) = report {
             debug!(
@@ -119,7 +194,8 @@ impl CryptoDataService {
 
     /// Fetch all report IDs and creation dates for sitemap generation
     ///
-    /// Returns lightweight data for generating dynamic sitemap URLs.
+    /// Returns lightweight data for generating dynamic sitemap URLs; reports that
+    /// are not `indexable` are left out.
     /// Uses `MediumTerm` caching (1 hour) since sitemap doesn't need real-time updates.
     ///
     /// # Errors
@@ -132,10 +208,15 @@ impl CryptoDataService {
     ) -> Result<Vec<ReportSitemapData>, sqlx::Error> {
         info!("🗄️ CryptoDataService: Fetching all report IDs for sitemap from database");
 
-        let reports = sqlx::query_as::<_, ReportSitemapData>(
-            "SELECT id, created_at FROM crypto_report ORDER BY created_at DESC",
+        let reports = timed_query(
+            state,
+            "sitemap_report_ids",
+            &[],
+            sqlx::query_as::<_, ReportSitemapData>(
+                "SELECT id, created_at FROM crypto_report WHERE indexable ORDER BY created_at DESC",
+            )
+            .fetch_all(&state.db),
         )
-        .fetch_all(&state.db)
         .await?;
 
         debug!(
@@ -145,6 +226,38 @@ impl CryptoDataService {
         Ok(reports)
     }
 
+    /// Fetch IDs and creation dates of reports published since `since`
+    ///
+    /// Feeds the Google News sitemap, which only lists recent `indexable` reports.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_recent_report_ids(
+        &self,
+        state: &Arc<AppState>,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<ReportSitemapData>, sqlx::Error> {
+        let reports = timed_query(
+            state,
+            "recent_report_ids",
+            &[("since", &since)],
+            sqlx::query_as::<_, ReportSitemapData>(
+                "SELECT id, created_at FROM crypto_report WHERE indexable AND created_at >= $1 ORDER BY created_at DESC",
+            )
+            .bind(since)
+            .fetch_all(&state.db),
+        )
+        .await?;
+
+        debug!(
+            "📊 CryptoDataService: Retrieved {} reports published since {}",
+            reports.len(),
+            since
+        );
+        Ok(reports)
+    }
+
     /// Fetch related reports (older reports) for GEO optimization
     ///
     /// Returns a list of reports older than the current report for internal linking.
@@ -173,12 +286,17 @@ impl CryptoDataService {
             current_id
         );
 
-        let reports = sqlx::query_as::<_, ReportSummaryData>(
-            "SELECT id, created_at FROM crypto_report WHERE id < $1 ORDER BY id DESC LIMIT $2",
+        let reports = timed_query(
+            state,
+            "related_reports",
+            &[("current_id", &current_id), ("limit", &limit)],
+            sqlx::query_as::<_, ReportSummaryData>(
+                "SELECT id, created_at FROM crypto_report WHERE id < $1 ORDER BY id DESC LIMIT $2",
+            )
+            .bind(current_id)
+            .bind(limit)
+            .fetch_all(&state.db),
         )
-        .bind(current_id)
-        .bind(limit)
-        .fetch_all(&state.db)
         .await?;
 
         debug!(
@@ -191,7 +309,7 @@ impl CryptoDataService {
 
     /// Fetch reports for RSS feed generation
     ///
-    /// Returns a list of recent reports with `html_content` for description extraction.
+    /// Returns a list of recent `indexable` reports with `html_content` for description extraction.
     /// Uses `MediumTerm` caching (1 hour) to balance freshness with performance.
     ///
     /// # Arguments
@@ -215,12 +333,17 @@ impl CryptoDataService {
             limit
         );
 
-        let reports = sqlx::query_as::<_, ReportRssData>(
-                "SELECT id, html_content, created_at FROM crypto_report ORDER BY created_at DESC LIMIT $1",
+        let reports = timed_query(
+            state,
+            "rss_reports",
+            &[("limit", &limit)],
+            sqlx::query_as::<_, ReportRssData>(
+                "SELECT id, html_content, html_content_en, created_at FROM crypto_report WHERE indexable ORDER BY created_at DESC LIMIT $1",
             )
             .bind(limit)
-            .fetch_all(&state.db)
-            .await?;
+            .fetch_all(&state.db),
+        )
+        .await?;
 
         debug!(
             "📊 CryptoDataService: Retrieved {} reports for RSS feed",
@@ -229,6 +352,86 @@ impl CryptoDataService {
         Ok(reports)
     }
 
+    /// Fetch up to `limit` `indexable` reports published after `since`, newest first
+    ///
+    /// Feeds email digests with the reports a subscriber has not been sent yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_rss_reports_since(
+        &self,
+        state: &Arc<AppState>,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<ReportRssData>, sqlx::Error> {
+        timed_query(
+            state,
+            "rss_reports_since",
+            &[("since", &since), ("limit", &limit)],
+            sqlx::query_as::<_, ReportRssData>(
+                "SELECT id, html_content, html_content_en, created_at FROM crypto_report WHERE indexable AND created_at > $1 ORDER BY created_at DESC LIMIT $2",
+            )
+            .bind(since)
+            .bind(limit)
+            .fetch_all(&state.db),
+        )
+        .await
+    }
+
+    /// Count the `indexable` reports, the ones listed by feeds and their archives
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn count_feed_reports(&self, state: &Arc<AppState>) -> Result<i64, sqlx::Error> {
+        timed_query(
+            state,
+            "feed_reports_count",
+            &[],
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM crypto_report WHERE indexable")
+                .fetch_one(&state.db),
+        )
+        .await
+    }
+
+    /// Fetch archive page `page` (1 = oldest) of `page_size` reports for a feed archive
+    ///
+    /// Pages are counted from the oldest report, so a full page always holds the
+    /// same reports. Returned newest first, like `fetch_rss_reports`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_rss_archive(
+        &self,
+        state: &Arc<AppState>,
+        page: i64,
+        page_size: i64,
+    ) -> Result<Vec<ReportRssData>, sqlx::Error> {
+        let offset = (page - 1) * page_size;
+        let mut reports = timed_query(
+            state,
+            "rss_archive",
+            &[("page", &page), ("page_size", &page_size)],
+            sqlx::query_as::<_, ReportRssData>(
+                "SELECT id, html_content, html_content_en, created_at FROM crypto_report WHERE indexable ORDER BY created_at ASC, id ASC LIMIT $1 OFFSET $2",
+            )
+            .bind(page_size)
+            .bind(offset)
+            .fetch_all(&state.db),
+        )
+        .await?;
+        reports.reverse();
+
+        debug!(
+            "📊 CryptoDataService: Retrieved {} reports for RSS archive page {}",
+            reports.len(),
+            page
+        );
+        Ok(reports)
+    }
+
     /// Fetch crypto report by ID from database with intelligent caching (L1+L2)
     ///
     /// ✨ NEW: Uses type-safe automatic caching with `get_or_compute_typed()`
@@ -248,12 +451,17 @@ impl CryptoDataService {
             report_id
         );
 
-        let report = sqlx::query_as::<_, ReportData>(
-                "SELECT id, html_content, css_content, js_content, html_content_en, js_content_en, created_at FROM crypto_report WHERE id = $1",
+        let report = timed_query(
+            state,
+            "report_by_id",
+            &[("report_id", &report_id)],
+            sqlx::query_as::<_, ReportData>(
+                "SELECT id, html_content, css_content, js_content, html_content_en, js_content_en, created_at, updated_at, indexable FROM crypto_report WHERE id = $1",
             )
             .bind(report_id)
-            .fetch_optional(&state.db)
-            .await?;
+            .fetch_optional(&state.db),
+        )
+        .await?;
 
         if let Some(ref report) = report {
             debug!(
@@ -270,6 +478,46 @@ impl CryptoDataService {
         Ok(report)
     }
 
+    /// Fetch reports whose HTML (either language) exceeds `min_bytes`
+    ///
+    /// Sizes are computed in Postgres with `octet_length`, so report bodies are never
+    /// transferred. Ordered by total content size, largest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_oversized_reports(
+        &self,
+        state: &Arc<AppState>,
+        min_bytes: i64,
+    ) -> Result<Vec<OversizedReportData>, sqlx::Error> {
+        debug!(
+            "🗄️ CryptoDataService: Scanning for reports larger than {} bytes",
+            min_bytes
+        );
+
+        sqlx::query_as::<_, OversizedReportData>(
+            "SELECT id, \
+                octet_length(html_content)::BIGINT AS html_bytes, \
+                octet_length(html_content_en)::BIGINT AS html_en_bytes, \
+                (octet_length(html_content) \
+                    + COALESCE(octet_length(html_content_en), 0) \
+                    + COALESCE(octet_length(css_content), 0) \
+                    + COALESCE(octet_length(js_content), 0) \
+                    + COALESCE(octet_length(js_content_en), 0))::BIGINT AS total_bytes, \
+                created_at \
+             FROM crypto_report \
+             WHERE octet_length(html_content) > $1 \
+                OR COALESCE(octet_length(html_content_en), 0) > $1 \
+             ORDER BY total_bytes DESC \
+             LIMIT $2",
+        )
+        .bind(min_bytes)
+        .bind(OVERSIZED_REPORTS_LIMIT)
+        .fetch_all(&state.db)
+        .await
+    }
+
     /// Lấy nội dung compressed data của một report từ cache.
     /// ✅ PRODUCTION-SAFE: No size limits on read - only on write
     ///
@@ -283,7 +531,7 @@ impl CryptoDataService {
         report_id: i32,
     ) -> Result<Option<Vec<u8>>, anyhow::Error> {
         let cache_manager = &state.cache_manager;
-        let cache_key = format!("compressed_report_{report_id}");
+        let cache_key = versioned_key(&format!("compressed_report_{report_id}"));
 
         if let Ok(Some(cached_value)) = cache_manager.get(&cache_key).await {
             // In v0.6.1, it's already Bytes.
@@ -360,13 +608,15 @@ impl CryptoDataService {
 
         // ✅ Cache the data - Direct Bytes storage is most efficient
         let cache_manager = &state.cache_manager;
-        let cache_key = format!("compressed_report_{report_id}");
-        let strategy = multi_tier_cache::CacheStrategy::ShortTerm;
+        let cache_key = versioned_key(&format!("compressed_report_{report_id}"));
+        let strategy = state.cache_config.report.strategy();
         let bytes = multi_tier_cache::Bytes::from(compressed_data.to_vec());
 
         cache_manager
             .set_with_strategy(&cache_key, bytes, strategy)
             .await?;
+        // Other instances drop their L1 copy and pick up the new one from L2
+        state.cache_bus.publish_remove(&cache_key).await;
 
         debug!(
             "💾 Layer 3: Cached compressed data for {} ({}KB)",
@@ -375,10 +625,20 @@ impl CryptoDataService {
         Ok(())
     }
 
+    /// Cache key of a rendered DSD report page (`report_id` -1 = latest report)
+    #[must_use]
+    pub fn report_dsd_cache_key(report_id: i32, language: &str, variant: Option<&str>) -> String {
+        CacheKeyBuilder::new(CacheRoute::ReportDsd)
+            .segment(report_id)
+            .language(language)
+            .variant(variant)
+            .build()
+    }
+
     /// Get cached DSD rendered report
     ///
     /// Retrieves compressed HTML for Declarative Shadow DOM routes.
-    /// Cache key format: `compressed_report_dsd`_{`report_id`}_{language}
+    /// Keyed by language and render strategy variant (see `CacheKeyBuilder`)
     /// ✅ PRODUCTION-SAFE: No size limits on read - only on write
     ///
     /// # Errors
@@ -390,11 +650,14 @@ impl CryptoDataService {
         state: &Arc<AppState>,
         report_id: i32,
         language: &str,
+        variant: Option<&str>,
     ) -> Result<Option<Vec<u8>>, anyhow::Error> {
         let cache_manager = &state.cache_manager;
-        let cache_key = format!("compressed_report_dsd_{report_id}_{language}");
+        let cache_key = Self::report_dsd_cache_key(report_id, language, variant);
 
         if let Ok(Some(cached_value)) = cache_manager.get(&cache_key).await {
+            cache_key_stats().record_hit(&cache_key);
+
             // Try Base64 JSON format (Old Format)
             if let Ok(base64_string) = serde_json::from_slice::<String>(&cached_value)
                 && let Ok(compressed_bytes) = BASE64_STANDARD.decode(base64_string)
@@ -422,6 +685,7 @@ impl CryptoDataService {
             );
             return Ok(Some(cached_value.to_vec()));
         }
+        cache_key_stats().record_miss(&cache_key);
         Ok(None)
     }
 
@@ -444,6 +708,7 @@ impl CryptoDataService {
         report_id: i32,
         compressed_data: &[u8],
         language: &str,
+        variant: Option<&str>,
     ) -> Result<(), anyhow::Error> {
         let data_size = compressed_data.len();
         let kilobytes = data_size / 1024;
@@ -473,13 +738,22 @@ impl CryptoDataService {
 
         // ✅ Cache the data - Direct Bytes storage is most efficient
         let cache_manager = &state.cache_manager;
-        let cache_key = format!("compressed_report_dsd_{report_id}_{language}");
-        let strategy = multi_tier_cache::CacheStrategy::ShortTerm;
+        let cache_key = Self::report_dsd_cache_key(report_id, language, variant);
+        let strategy = state.cache_config.report.strategy();
         let bytes = multi_tier_cache::Bytes::from(compressed_data.to_vec());
+        let fresh_ttl = strategy.to_duration();
 
         cache_manager
             .set_with_strategy(&cache_key, bytes, strategy)
             .await?;
+        cache_key_stats().record_set(&cache_key, data_size, fresh_ttl);
+        state.cache_bus.publish_remove(&cache_key).await;
+
+        // Stale copy for stale-while-revalidate serving once the fresh entry expires
+        state
+            .stale_cache
+            .store(&cache_key, compressed_data, fresh_ttl)
+            .await;
 
         debug!(
             "💾 Layer 3: Cached DSD compressed data for {} (lang: {}) ({}KB)",
@@ -488,6 +762,145 @@ impl CryptoDataService {
         Ok(())
     }
 
+    /// Get the cached `Last-Modified` timestamp for a rendered report
+    ///
+    /// Stored alongside the DSD cache entries so cache hits can still emit
+    /// `Last-Modified` and answer `If-Modified-Since` without touching the database.
+    /// Cache key format: `report_last_modified_{report_id}` (-1 = latest report)
+    pub async fn get_report_last_modified(
+        &self,
+        state: &Arc<AppState>,
+        report_id: i32,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let cache_key = format!("report_last_modified_{report_id}");
+        let cached_value = state.cache_manager.get(&cache_key).await.ok()??;
+        let timestamp = std::str::from_utf8(&cached_value)
+            .ok()?
+            .parse::<i64>()
+            .ok()?;
+        chrono::DateTime::from_timestamp(timestamp, 0)
+    }
+
+    /// Cache the `Last-Modified` timestamp (the report's `updated_at`) for a rendered report
+    ///
+    /// Uses the same `ShortTerm` strategy as the DSD entries it describes.
+    /// Failures are logged only - conditional GET is an optimization.
+    pub async fn cache_report_last_modified(
+        &self,
+        state: &Arc<AppState>,
+        report_id: i32,
+        updated_at: &chrono::DateTime<chrono::Utc>,
+    ) {
+        let cache_key = format!("report_last_modified_{report_id}");
+        let bytes = multi_tier_cache::Bytes::from(updated_at.timestamp().to_string());
+
+        if let Err(e) = state
+            .cache_manager
+            .set_with_strategy(&cache_key, bytes, state.cache_config.report.strategy())
+            .await
+        {
+            warn!(
+                "⚠️ Layer 3: Failed to cache Last-Modified for report {}: {}",
+                report_id, e
+            );
+        }
+    }
+
+    /// Record that the publication of a report is being announced
+    ///
+    /// Returns `true` for the one caller that set `announced_at`, `false` when the
+    /// report was already announced (by this or another process) or does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn claim_report_announcement(
+        &self,
+        state: &Arc<AppState>,
+        report_id: i32,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let claimed = timed_query(
+            state,
+            "claim_report_announcement",
+            &[("report_id", &report_id)],
+            sqlx::query_scalar::<_, i32>(
+                "UPDATE crypto_report SET announced_at = $2 \
+                 WHERE id = $1 AND announced_at IS NULL RETURNING id",
+            )
+            .bind(report_id)
+            .bind(now)
+            .fetch_optional(&state.db),
+        )
+        .await?;
+        Ok(claimed.is_some())
+    }
+
+    /// Fetch the stored URL slug and indexing flag of a report (`None` = no such report)
+    ///
+    /// Cached with the report page strategy so slug checks on cache hits stay off
+    /// the database; a report purge drops the entry. Rows without a slug fall back
+    /// to `report_slug`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_report_route(
+        &self,
+        state: &Arc<AppState>,
+        report_id: i32,
+    ) -> Result<Option<ReportRoute>, sqlx::Error> {
+        let cache_key = report_route_cache_key(report_id);
+        if let Ok(Some(cached)) = state.cache_manager.get(&cache_key).await
+            && let Ok(route) = serde_json::from_slice::<ReportRoute>(&cached)
+        {
+            return Ok(Some(route));
+        }
+
+        let row = timed_query(
+            state,
+            "report_route",
+            &[("report_id", &report_id)],
+            sqlx::query_as::<_, (Option<String>, chrono::DateTime<chrono::Utc>, bool)>(
+                "SELECT slug, created_at, indexable FROM crypto_report WHERE id = $1",
+            )
+            .bind(report_id)
+            .fetch_optional(&state.db),
+        )
+        .await?;
+        let Some((slug, created_at, indexable)) = row else {
+            return Ok(None);
+        };
+        let route = ReportRoute {
+            slug: slug.unwrap_or_else(|| report_slug(created_at)),
+            indexable,
+        };
+
+        match serde_json::to_vec(&route) {
+            Ok(json) => {
+                if let Err(e) = state
+                    .cache_manager
+                    .set_with_strategy(
+                        &cache_key,
+                        multi_tier_cache::Bytes::from(json),
+                        state.cache_config.report.strategy(),
+                    )
+                    .await
+                {
+                    warn!(
+                        "⚠️ Layer 3: Failed to cache route for report {}: {}",
+                        report_id, e
+                    );
+                }
+            }
+            Err(e) => warn!(
+                "⚠️ Layer 3: Failed to serialize route for report {}: {}",
+                report_id, e
+            ),
+        }
+        Ok(Some(route))
+    }
+
     /// Get current cache statistics from the cache manager
     ///
     /// ✅ PRODUCTION-READY: Queries actual cache statistics from multi-tier-cache library
@@ -508,20 +921,35 @@ impl CryptoDataService {
     /// Step 1: Fetch reports from database
     /// Step 1: Fetch reports from database
     async fn fetch_reports_from_db(
-        db: &sqlx::PgPool,
+        state: &AppState,
         page: i64,
         per_page: i64,
     ) -> anyhow::Result<(i64, Vec<ReportSummaryData>)> {
         let offset = (page - 1) * per_page;
-
-        let total_fut =
-            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM crypto_report").fetch_one(db);
-        let rows_fut = sqlx::query_as::<_, ReportSummaryData>(
-            "SELECT id, created_at FROM crypto_report ORDER BY created_at DESC LIMIT $1 OFFSET $2",
-        )
-        .bind(per_page)
-        .bind(offset)
-        .fetch_all(db);
+        let db = &state.db;
+        let page_params: [QueryParam<'_>; 3] = [
+            ("page", &page),
+            ("per_page", &per_page),
+            ("offset", &offset),
+        ];
+
+        let total_fut = timed_query(
+            state,
+            "reports_count",
+            &[],
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM crypto_report").fetch_one(db),
+        );
+        let rows_fut = timed_query(
+            state,
+            "reports_page",
+            &page_params,
+            sqlx::query_as::<_, ReportSummaryData>(
+                "SELECT id, created_at FROM crypto_report ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+            )
+            .bind(per_page)
+            .bind(offset)
+            .fetch_all(db),
+        );
 
         let (total_res, rows_res) = tokio::join!(total_fut, rows_fut);
 
@@ -554,6 +982,7 @@ impl CryptoDataService {
                 let created_time = format!("{} UTC+7", dt.format("%H:%M:%S"));
                 serde_json::json!({
                     "id": r.id,
+                    "url": report_path(r.id, r.created_at),
                     "created_date": created_date,
                     "created_time": created_time
                 })
@@ -654,9 +1083,16 @@ impl CryptoDataService {
     fn render_reports_template_sync(
         tera: &tera::Tera,
         reports: &serde_json::Value,
+        language: &str,
     ) -> anyhow::Result<String> {
         let mut context = tera::Context::new();
         context.insert("reports", reports);
+        context.insert("current_lang", language);
+        context.insert("site_origin", &public_origin());
+        context.insert(
+            "breadcrumbs_schema",
+            &Self::reports_list_schema(reports, language),
+        );
         tera.render("crypto/routes/reports/list.html", &context)
             .map_err(|e| {
                 error!("❌ Layer 3: Reports list template render error: {:#?}", e);
@@ -664,6 +1100,22 @@ impl CryptoDataService {
             })
     }
 
+    /// `CollectionPage` + `BreadcrumbList` JSON-LD of a reports list context
+    fn reports_list_schema(reports: &serde_json::Value, language: &str) -> String {
+        let page = reports
+            .get("page")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(1);
+        let report_paths: Vec<&str> = reports
+            .get("items")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.get("url").and_then(serde_json::Value::as_str))
+            .collect();
+        generate_list_page_schema(page, None, language, &report_paths)
+    }
+
     /// Step 6: Compress HTML
     fn compress_html(html: &str, page: i64) -> anyhow::Result<Vec<u8>> {
         use flate2::{Compression, write::GzEncoder};
@@ -707,6 +1159,15 @@ impl CryptoDataService {
         Ok(compressed_data)
     }
 
+    /// Cache key of a rendered reports list page
+    #[must_use]
+    pub fn reports_list_cache_key(page: i64, language: &str) -> String {
+        CacheKeyBuilder::new(CacheRoute::ReportsList)
+            .segment(page)
+            .language(language)
+            .build()
+    }
+
     /// Fetch reports list with intelligent caching (L1+L2)
     ///
     /// ✅ MEMORY FIX: Uses manual cache get/set to avoid cloning Tera into async closure
@@ -721,8 +1182,9 @@ impl CryptoDataService {
         state: &Arc<AppState>,
         page: i64,
         per_page: i64,
+        language: &str,
     ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>> {
-        let cache_key = format!("crypto_reports_list_page_{page}_compressed");
+        let cache_key = Self::reports_list_cache_key(page, language);
 
         // Step 1: Try to get from cache first
         let cache_manager = &state.cache_manager;
@@ -742,13 +1204,31 @@ impl CryptoDataService {
         }
 
         // Step 2: Cache MISS - Generate fresh content
+        self.render_reports_list(state, page, per_page, language)
+            .await
+            .map(Some)
+    }
+
+    /// Render a reports list page from the database and cache it (no cache lookup)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if database query fails, template rendering fails or HTML compression fails
+    pub async fn render_reports_list(
+        &self,
+        state: &Arc<AppState>,
+        page: i64,
+        per_page: i64,
+        language: &str,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let cache_key = Self::reports_list_cache_key(page, language);
         info!(
             "🗄️ CryptoDataService: Generating reports list page {} from database",
             page
         );
 
         // Fetch from database
-        let (total, list) = Self::fetch_reports_from_db(&state.db, page, per_page).await?;
+        let (total, list) = Self::fetch_reports_from_db(state, page, per_page).await?;
 
         // Format report items
         let items = Self::format_report_items(list);
@@ -762,7 +1242,7 @@ impl CryptoDataService {
             Self::build_reports_context(&items, total, page, per_page, pages, &page_numbers);
 
         // ✅ MEMORY FIX: Render template synchronously without cloning Tera
-        let html = Self::render_reports_template_sync(&state.tera, &reports)?;
+        let html = Self::render_reports_template_sync(&state.tera, &reports, language)?;
         info!(
             "✅ Layer 3: Reports list template rendered successfully - {} items, page {} of {}",
             items_count, page, pages
@@ -774,7 +1254,8 @@ impl CryptoDataService {
         // Step 3: Cache the result
         let cache_manager = &state.cache_manager;
         let bytes = multi_tier_cache::Bytes::from(compressed_data.clone());
-        let strategy = multi_tier_cache::CacheStrategy::ShortTerm;
+        let strategy = state.cache_config.reports_list.strategy();
+        let fresh_ttl = strategy.to_duration();
 
         if let Err(e) = cache_manager
             .set_with_strategy(&cache_key, bytes, strategy)
@@ -785,6 +1266,10 @@ impl CryptoDataService {
                 page, e
             );
         }
-        Ok(Some(compressed_data))
+        state
+            .stale_cache
+            .store(&cache_key, &compressed_data, fresh_ttl)
+            .await;
+        Ok(compressed_data)
     }
 }
@@ -0,0 +1,180 @@
+//! Fear & Greed History
+//!
+//! Layer 3 data communication service for the `fng_daily` table (see
+//! `migrations/20261015090700_create_fng_daily.sql`). The `fng_recorder` task
+//! keeps the `fng_value` of the newest `market_data` entry of each UTC day, and
+//! `/api/crypto/fng/history` plus the homepage gauge sparkline read the last days
+//! back. Entries repeating the value already stored for their day are not written.
+
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use serde_json::Value;
+use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::query_timing::timed_query;
+use crate::dto::responses::FngHistoryPoint;
+use crate::state::AppState;
+use crate::stream::entry_id_millis;
+
+/// Days returned by `/api/crypto/fng/history` by default (and shown by the
+/// homepage sparkline) / at most
+pub const DEFAULT_FNG_DAYS: i32 = 30;
+pub const MAX_FNG_DAYS: i32 = 365;
+
+/// Fear & Greed index of one day
+#[derive(Debug, Clone, FromRow)]
+pub struct FngDay {
+    pub day: NaiveDate,
+    pub value: i16,
+}
+
+/// Fear & Greed History
+///
+/// Layer 3 service responsible for daily Fear & Greed database operations.
+#[derive(Clone, Default)]
+pub struct FngHistory;
+
+impl FngHistory {
+    /// Create a new `FngHistory`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Store `value` as the index of the day of `at`, unless a newer entry of that
+    /// day is already stored; returns whether the row changed
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn record(
+        &self,
+        db: &PgPool,
+        at: DateTime<Utc>,
+        value: i16,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "INSERT INTO fng_daily (day, value, updated_at) VALUES ($1, $2, $3) \
+             ON CONFLICT (day) DO UPDATE SET value = EXCLUDED.value, updated_at = EXCLUDED.updated_at \
+             WHERE fng_daily.updated_at < EXCLUDED.updated_at",
+        )
+        .bind(at.date_naive())
+        .bind(value)
+        .bind(at)
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Index of the last `days` days up to `today`, oldest first; days without
+    /// a recorded value are omitted
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_days(
+        &self,
+        state: &Arc<AppState>,
+        days: i32,
+        today: NaiveDate,
+    ) -> Result<Vec<FngDay>, sqlx::Error> {
+        let since = today - TimeDelta::days(i64::from(days));
+        timed_query(
+            state,
+            "fng_history_days",
+            &[("days", &days)],
+            sqlx::query_as::<_, FngDay>(
+                "SELECT day, value FROM fng_daily \
+                 WHERE day > $1 AND day <= $2 ORDER BY day",
+            )
+            .bind(since)
+            .bind(today)
+            .fetch_all(&state.db),
+        )
+        .await
+    }
+
+    /// `fetch_days` as response points
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_points(
+        &self,
+        state: &Arc<AppState>,
+        days: i32,
+        today: NaiveDate,
+    ) -> Result<Vec<FngHistoryPoint>, sqlx::Error> {
+        Ok(self
+            .fetch_days(state, days, today)
+            .await?
+            .into_iter()
+            .map(|day| FngHistoryPoint {
+                date: day.day.format("%Y-%m-%d").to_string(),
+                value: day.value,
+            })
+            .collect())
+    }
+}
+
+/// `fng_value` of a `market_data` payload, `None` when missing or out of range
+fn fng_value(data: &Value) -> Option<i16> {
+    data.get("fng_value")
+        .and_then(Value::as_i64)
+        .filter(|value| (0..=100).contains(value))
+        .and_then(|value| i16::try_from(value).ok())
+}
+
+/// Start the `fng_recorder` task storing the daily Fear & Greed index of the
+/// `market_data` live updates
+pub fn spawn_fng_recorder(state: &Arc<AppState>) {
+    let task_state = Arc::clone(state);
+    state.tasks.supervise("fng_recorder", move || {
+        let state = Arc::clone(&task_state);
+        async move {
+            let history = FngHistory::new();
+            let mut updates = state.streams.market_data().live_updates().subscribe();
+            let mut last_stored: Option<(NaiveDate, i16)> = None;
+            loop {
+                let update = updates.borrow_and_update().clone();
+                let sample = update.as_ref().and_then(|update| {
+                    let at = entry_id_millis(&update.entry_id)
+                        .and_then(|millis| i64::try_from(millis).ok())
+                        .and_then(DateTime::from_timestamp_millis)?;
+                    Some((update, at, fng_value(&update.data)?))
+                });
+                if let Some((update, at, value)) = sample
+                    && last_stored != Some((at.date_naive(), value))
+                {
+                    match history.record(&state.db, at, value).await {
+                        Ok(_) => last_stored = Some((at.date_naive(), value)),
+                        Err(e) => warn!(
+                            "⚠️ Failed to record Fear & Greed index of entry {}: {}",
+                            update.entry_id, e
+                        ),
+                    }
+                }
+                if updates.changed().await.is_err() {
+                    return Err("market data live updates closed".to_string());
+                }
+            }
+        }
+    });
+    info!("😨 Recording daily Fear & Greed index");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fng_value() {
+        assert_eq!(fng_value(&json!({"fng_value": 42})), Some(42));
+        assert_eq!(fng_value(&json!({"fng_value": 0})), Some(0));
+        assert_eq!(fng_value(&json!({"fng_value": 140})), None);
+        assert_eq!(fng_value(&json!({"fng_value": "42"})), None);
+        assert_eq!(fng_value(&json!({"btc_price_usd": 1.0})), None);
+    }
+}
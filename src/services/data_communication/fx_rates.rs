@@ -0,0 +1,242 @@
+//! FX Rates
+//!
+//! USD exchange rates behind the `?currency=` parameter of the JSON market data
+//! endpoints (dashboard data, quotes, candles). Report pages are not converted:
+//! their figures are part of the stored report HTML, and the live indicators are
+//! rendered in the browser from USD stream data.
+//!
+//! A rate comes from the `market_data` stream (`usd_vnd_rate`, `usd_eur_rate`)
+//! when the websocket service publishes it, else from the
+//! `FX_RATES` environment variable (`VND=25400,EUR=0.92`). The resolved rates are
+//! cached under `fx_rates` with the market data TTL; figures stay in USD in every
+//! other cache entry and are converted per request. Rates that are not positive
+//! and finite are ignored.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::OnceLock;
+use tracing::{info, warn};
+
+use crate::state::AppState;
+
+/// Cache key of the resolved rates
+const FX_RATES_CACHE_KEY: &str = "fx_rates";
+
+/// Currency figures can be returned in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Currency {
+    Usd,
+    Vnd,
+    Eur,
+}
+
+impl Currency {
+    pub const ALL: [Self; 3] = [Self::Usd, Self::Vnd, Self::Eur];
+
+    /// Currency of an ISO 4217 code, case-insensitive
+    #[must_use]
+    pub fn parse(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|currency| currency.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Usd => "USD",
+            Self::Vnd => "VND",
+            Self::Eur => "EUR",
+        }
+    }
+
+    #[must_use]
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Usd => "$",
+            Self::Vnd => "₫",
+            Self::Eur => "€",
+        }
+    }
+
+    /// Digits after the decimal separator when formatting an amount
+    #[must_use]
+    pub const fn decimals(self) -> usize {
+        match self {
+            Self::Vnd => 0,
+            Self::Usd | Self::Eur => 2,
+        }
+    }
+
+    /// `market_data` field carrying the units of this currency per USD
+    const fn stream_field(self) -> Option<&'static str> {
+        match self {
+            Self::Usd => None,
+            Self::Vnd => Some("usd_vnd_rate"),
+            Self::Eur => Some("usd_eur_rate"),
+        }
+    }
+}
+
+/// Units of each currency per USD (`None`: no rate known)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FxRates {
+    pub vnd: Option<f64>,
+    pub eur: Option<f64>,
+}
+
+impl FxRates {
+    /// Units of `currency` per USD
+    #[must_use]
+    pub fn rate(&self, currency: Currency) -> Option<f64> {
+        match currency {
+            Currency::Usd => Some(1.0),
+            Currency::Vnd => self.vnd,
+            Currency::Eur => self.eur,
+        }
+    }
+
+    fn rate_mut(&mut self, currency: Currency) -> Option<&mut Option<f64>> {
+        match currency {
+            Currency::Usd => None,
+            Currency::Vnd => Some(&mut self.vnd),
+            Currency::Eur => Some(&mut self.eur),
+        }
+    }
+
+    /// Parse `FX_RATES` (`VND=25400,EUR=0.92`); malformed pairs are skipped
+    #[must_use]
+    pub fn parse_config(raw: &str) -> Self {
+        let mut rates = Self::default();
+        for pair in raw.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let parsed = pair.split_once('=').and_then(|(code, rate)| {
+                let rate = rate
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|rate| is_usable(*rate))?;
+                Some((rates.rate_mut(Currency::parse(code)?)?, rate))
+            });
+            if let Some((slot, rate)) = parsed {
+                *slot = Some(rate);
+            } else {
+                warn!("⚠️ Ignoring FX_RATES entry {:?}", pair);
+            }
+        }
+        rates
+    }
+
+    /// Rates of a `market_data` payload, falling back to `config` per currency
+    #[must_use]
+    pub fn resolve(payload: Option<&Value>, config: &Self) -> Self {
+        let mut rates = config.clone();
+        for currency in Currency::ALL {
+            let published = currency
+                .stream_field()
+                .and_then(|field| payload?.get(field)?.as_f64())
+                .filter(|rate| is_usable(*rate));
+            if let (Some(slot), Some(rate)) = (rates.rate_mut(currency), published) {
+                *slot = Some(rate);
+            }
+        }
+        rates
+    }
+}
+
+/// Whether `rate` can convert amounts (positive and finite)
+fn is_usable(rate: f64) -> bool {
+    rate.is_finite() && rate > 0.0
+}
+
+/// Rates configured by `FX_RATES`, read once
+fn configured_rates() -> &'static FxRates {
+    static RATES: OnceLock<FxRates> = OnceLock::new();
+    RATES.get_or_init(|| {
+        let rates = std::env::var("FX_RATES")
+            .map(|raw| FxRates::parse_config(&raw))
+            .unwrap_or_default();
+        info!("💱 Configured FX rates: {:?}", rates);
+        rates
+    })
+}
+
+/// Currency Service
+///
+/// Layer 3 service resolving the USD exchange rates of the market data endpoints.
+#[derive(Clone, Default)]
+pub struct CurrencyService;
+
+impl CurrencyService {
+    /// Create a new `CurrencyService`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Current rates, cached with the market data TTL
+    ///
+    /// An unreadable stream only loses the published rates; the configured ones
+    /// are still returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CacheError` if the cache cannot be read or written
+    pub async fn rates(&self, state: &AppState) -> Result<FxRates, multi_tier_cache::CacheError> {
+        state
+            .cache_manager
+            .get_or_compute_typed(
+                FX_RATES_CACHE_KEY,
+                state.cache_config.market_data.strategy(),
+                || async {
+                    let payload = state
+                        .streams
+                        .market_data()
+                        .read_latest()
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!("⚠️ FX rates: market data unavailable: {}", e);
+                            None
+                        });
+                    Ok(FxRates::resolve(payload.as_ref(), configured_rates()))
+                },
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_currency_parse() {
+        assert_eq!(Currency::parse("vnd"), Some(Currency::Vnd));
+        assert_eq!(Currency::parse(" EUR "), Some(Currency::Eur));
+        assert!(Currency::parse("JPY").is_none());
+    }
+
+    #[test]
+    fn test_stream_rates_override_config() {
+        let config =
+            FxRates::parse_config("VND=25400, EUR=0.92, JPY=150, EUR=-1, USD=2, VND=inf, EUR=NaN");
+        assert_eq!(
+            config,
+            FxRates {
+                vnd: Some(25400.0),
+                eur: Some(0.92),
+            }
+        );
+
+        let payload = json!({"btc_price_usd": 1.0, "usd_vnd_rate": 26000.0, "usd_eur_rate": 0});
+        let rates = FxRates::resolve(Some(&payload), &config);
+        assert_eq!(rates.rate(Currency::Vnd), Some(26000.0));
+        // A non-positive published rate keeps the configured one
+        assert_eq!(rates.rate(Currency::Eur), Some(0.92));
+        assert_eq!(rates.rate(Currency::Usd), Some(1.0));
+        assert_eq!(
+            FxRates::resolve(None, &FxRates::default()).rate(Currency::Vnd),
+            None
+        );
+    }
+}
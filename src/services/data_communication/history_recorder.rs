@@ -0,0 +1,429 @@
+//! History Recorder
+//!
+//! Layer 3 data communication service for the `market_data_history` table (see
+//! `migrations/20261015090600_create_market_data_history.sql`). The
+//! `history_recorder` task stores every `market_data` entry pushed to live
+//! subscribers, and `/api/crypto/history` reads one metric back, averaged per
+//! bucket so a year of ~10 second entries becomes a few hundred chart points;
+//! `/api/crypto/ohlc` aggregates the coin prices into candles the same way.
+//!
+//! Live updates only carry the newest entry, so entries superseded before the
+//! task stores them are skipped; at the usual stream rate none are.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::market_assets::{MARKET_ASSETS, MarketAsset};
+use super::query_timing::timed_query;
+use crate::state::AppState;
+use crate::stream::entry_id_millis;
+
+/// Metric names accepted by `/api/crypto/history` and their `market_data` field
+const HISTORY_METRICS: &[(&str, &str)] = &[
+    ("btc_price", "btc_price_usd"),
+    ("eth_price", "eth_price_usd"),
+    ("bnb_price", "bnb_price_usd"),
+    ("sol_price", "sol_price_usd"),
+    ("xrp_price", "xrp_price_usd"),
+    ("ada_price", "ada_price_usd"),
+    ("link_price", "link_price_usd"),
+    ("gold_price", "gold_price_usd"),
+    ("dxy", "dxy_value"),
+    ("btc_dominance", "btc_market_cap_percentage"),
+    ("eth_dominance", "eth_market_cap_percentage"),
+    ("btc_rsi", "btc_rsi_14"),
+    ("market_cap", "market_cap_usd"),
+    ("volume_24h", "volume_24h_usd"),
+    ("fear_greed", "fng_value"),
+];
+
+/// A metric of the recorded market data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryMetric {
+    pub name: &'static str,
+    /// Field of the `market_data` payload
+    pub field: &'static str,
+}
+
+impl HistoryMetric {
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        HISTORY_METRICS
+            .iter()
+            .find(|(metric, _)| *metric == name)
+            .map(|&(name, field)| Self { name, field })
+    }
+
+    /// Every accepted metric name
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        HISTORY_METRICS.iter().map(|(name, _)| *name)
+    }
+
+    /// Price of an asset symbol (`BTC`, `xau`, ...; see `MARKET_ASSETS`)
+    #[must_use]
+    pub fn price(symbol: &str) -> Option<Self> {
+        let asset = MarketAsset::find(symbol)?;
+        HISTORY_METRICS
+            .iter()
+            .find(|(_, field)| *field == asset.price_field)
+            .map(|&(name, field)| Self { name, field })
+    }
+
+    /// Every symbol accepted by `price`
+    pub fn price_symbols() -> impl Iterator<Item = &'static str> {
+        MARKET_ASSETS
+            .iter()
+            .filter(|asset| Self::price(asset.symbol).is_some())
+            .map(|asset| asset.symbol)
+    }
+}
+
+/// Candle width of `/api/crypto/ohlc`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandleInterval {
+    FiveMinutes,
+    FifteenMinutes,
+    Hour,
+    FourHours,
+    Day,
+}
+
+impl CandleInterval {
+    pub const ALL: [Self; 5] = [
+        Self::FiveMinutes,
+        Self::FifteenMinutes,
+        Self::Hour,
+        Self::FourHours,
+        Self::Day,
+    ];
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|interval| interval.as_str() == value)
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FiveMinutes => "5m",
+            Self::FifteenMinutes => "15m",
+            Self::Hour => "1h",
+            Self::FourHours => "4h",
+            Self::Day => "1d",
+        }
+    }
+
+    #[must_use]
+    pub const fn width(self) -> TimeDelta {
+        match self {
+            Self::FiveMinutes => TimeDelta::minutes(5),
+            Self::FifteenMinutes => TimeDelta::minutes(15),
+            Self::Hour => TimeDelta::hours(1),
+            Self::FourHours => TimeDelta::hours(4),
+            Self::Day => TimeDelta::days(1),
+        }
+    }
+}
+
+/// Time span of a history query and the bucket its points are averaged over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryRange {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl HistoryRange {
+    pub const ALL: [Self; 6] = [
+        Self::Hour,
+        Self::Day,
+        Self::Week,
+        Self::Month,
+        Self::Quarter,
+        Self::Year,
+    ];
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|range| range.as_str() == value)
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hour => "1h",
+            Self::Day => "24h",
+            Self::Week => "7d",
+            Self::Month => "30d",
+            Self::Quarter => "90d",
+            Self::Year => "1y",
+        }
+    }
+
+    #[must_use]
+    pub const fn span(self) -> TimeDelta {
+        match self {
+            Self::Hour => TimeDelta::hours(1),
+            Self::Day => TimeDelta::days(1),
+            Self::Week => TimeDelta::days(7),
+            Self::Month => TimeDelta::days(30),
+            Self::Quarter => TimeDelta::days(90),
+            Self::Year => TimeDelta::days(365),
+        }
+    }
+
+    /// Bucket width, keeping every range between 60 and 365 points
+    #[must_use]
+    pub const fn bucket(self) -> TimeDelta {
+        match self {
+            Self::Hour => TimeDelta::minutes(1),
+            Self::Day => TimeDelta::minutes(5),
+            Self::Week => TimeDelta::hours(1),
+            Self::Month => TimeDelta::hours(4),
+            Self::Quarter => TimeDelta::hours(12),
+            Self::Year => TimeDelta::days(1),
+        }
+    }
+}
+
+/// Average of a metric over one bucket
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct HistoryPoint {
+    /// Start of the bucket
+    pub bucket: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// Open, high, low and close of a metric over one interval
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CandleRecord {
+    /// Start of the interval
+    pub bucket: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Recorded entries in the interval
+    pub samples: i64,
+}
+
+/// History Recorder
+///
+/// Layer 3 service responsible for market data history database operations.
+#[derive(Clone, Default)]
+pub struct HistoryRecorder;
+
+impl HistoryRecorder {
+    /// Create a new `HistoryRecorder`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Store a `market_data` entry, returning whether it was new
+    ///
+    /// Entries recorded by another instance, and IDs without a timestamp, are
+    /// not stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn record(
+        &self,
+        db: &PgPool,
+        entry_id: &str,
+        data: &Value,
+    ) -> Result<bool, sqlx::Error> {
+        let Some(recorded_at) = entry_id_millis(entry_id)
+            .and_then(|millis| i64::try_from(millis).ok())
+            .and_then(DateTime::from_timestamp_millis)
+        else {
+            return Ok(false);
+        };
+        let result = sqlx::query(
+            "INSERT INTO market_data_history (recorded_at, entry_id, data) \
+             VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        )
+        .bind(recorded_at)
+        .bind(entry_id)
+        .bind(data)
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Average of `metric` per bucket of `range` up to `now`, oldest first;
+    /// entries without a numeric value for it are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_series(
+        &self,
+        state: &Arc<AppState>,
+        metric: HistoryMetric,
+        range: HistoryRange,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<HistoryPoint>, sqlx::Error> {
+        let bucket_secs = range.bucket().as_seconds_f64();
+        let since = now - range.span();
+        timed_query(
+            state,
+            "market_history_series",
+            &[("metric", &metric.name), ("range", &range.as_str())],
+            sqlx::query_as::<_, HistoryPoint>(
+                "SELECT date_bin(make_interval(secs => $1), recorded_at, \
+                        TIMESTAMPTZ '2000-01-01 00:00:00+00') AS bucket, \
+                        AVG((data->>$2)::float8) AS value \
+                 FROM market_data_history \
+                 WHERE recorded_at >= $3 AND jsonb_typeof(data->$2) = 'number' \
+                 GROUP BY bucket ORDER BY bucket",
+            )
+            .bind(bucket_secs)
+            .bind(metric.field)
+            .bind(since)
+            .fetch_all(&state.db),
+        )
+        .await
+    }
+
+    /// The last `count` candles of `metric` up to `now`, oldest first (the last
+    /// one still forming); intervals without entries are omitted
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_candles(
+        &self,
+        state: &Arc<AppState>,
+        metric: HistoryMetric,
+        interval: CandleInterval,
+        count: i32,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<CandleRecord>, sqlx::Error> {
+        let width_secs = interval.width().as_seconds_f64();
+        let since = now - interval.width() * count;
+        timed_query(
+            state,
+            "market_history_candles",
+            &[
+                ("metric", &metric.name),
+                ("interval", &interval.as_str()),
+                ("count", &count),
+            ],
+            sqlx::query_as::<_, CandleRecord>(
+                "SELECT bucket, \
+                        prices[1] AS open, high, low, prices[array_length(prices, 1)] AS close, \
+                        samples \
+                 FROM ( \
+                     SELECT date_bin(make_interval(secs => $1), recorded_at, \
+                                TIMESTAMPTZ '2000-01-01 00:00:00+00') AS bucket, \
+                            array_agg((data->>$2)::float8 ORDER BY recorded_at) AS prices, \
+                            MAX((data->>$2)::float8) AS high, \
+                            MIN((data->>$2)::float8) AS low, \
+                            COUNT(*) AS samples \
+                     FROM market_data_history \
+                     WHERE recorded_at > $3 AND jsonb_typeof(data->$2) = 'number' \
+                     GROUP BY bucket \
+                 ) candles \
+                 ORDER BY bucket DESC LIMIT $4",
+            )
+            .bind(width_secs)
+            .bind(metric.field)
+            .bind(since)
+            .bind(i64::from(count))
+            .fetch_all(&state.db),
+        )
+        .await
+        .map(|mut candles| {
+            candles.reverse();
+            candles
+        })
+    }
+}
+
+/// Start the `history_recorder` task storing every `market_data` live update
+pub fn spawn_history_recorder(state: &Arc<AppState>) {
+    let task_state = Arc::clone(state);
+    state.tasks.supervise("history_recorder", move || {
+        let state = Arc::clone(&task_state);
+        async move {
+            let recorder = HistoryRecorder::new();
+            let mut updates = state.streams.market_data().live_updates().subscribe();
+            loop {
+                let update = updates.borrow_and_update().clone();
+                if let Some(update) = update
+                    && let Err(e) = recorder
+                        .record(&state.db, &update.entry_id, &update.data)
+                        .await
+                {
+                    warn!(
+                        "⚠️ Failed to record market data entry {}: {}",
+                        update.entry_id, e
+                    );
+                }
+                if updates.changed().await.is_err() {
+                    return Err("market data live updates closed".to_string());
+                }
+            }
+        }
+    });
+    info!("📈 Recording market data history");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_metric_parse() {
+        assert_eq!(
+            HistoryMetric::parse("btc_price").map(|metric| metric.field),
+            Some("btc_price_usd")
+        );
+        assert_eq!(
+            HistoryMetric::parse("fear_greed").map(|metric| metric.field),
+            Some("fng_value")
+        );
+        assert!(HistoryMetric::parse("btc_price_usd").is_none());
+        assert_eq!(HistoryMetric::names().count(), HISTORY_METRICS.len());
+    }
+
+    #[test]
+    fn test_price_symbols() {
+        assert_eq!(
+            HistoryMetric::price("btc").map(|metric| metric.field),
+            Some("btc_price_usd")
+        );
+        assert!(HistoryMetric::price("FEAR_GREED").is_none());
+        let symbols: Vec<&str> = HistoryMetric::price_symbols().collect();
+        assert_eq!(symbols.len(), MARKET_ASSETS.len());
+        assert!(
+            symbols
+                .iter()
+                .all(|symbol| HistoryMetric::price(symbol).is_some())
+        );
+        assert_eq!(CandleInterval::parse("4h"), Some(CandleInterval::FourHours));
+        assert!(CandleInterval::parse("2h").is_none());
+    }
+
+    #[test]
+    fn test_history_range_buckets() {
+        assert_eq!(HistoryRange::parse("7d"), Some(HistoryRange::Week));
+        assert!(HistoryRange::parse("2d").is_none());
+        for range in HistoryRange::ALL {
+            assert_eq!(HistoryRange::parse(range.as_str()), Some(range));
+            let points = range.span().num_seconds() / range.bucket().num_seconds();
+            assert!((60..=365).contains(&points), "{}: {points}", range.as_str());
+        }
+    }
+}
@@ -0,0 +1,496 @@
+//! JWT Bearer Authentication
+//!
+//! Validates OIDC access tokens (`Authorization: Bearer <jwt>`) issued by
+//! `JWT_ISSUER`, with signing keys fetched from `JWT_JWKS_URL` (default
+//! `<issuer>/.well-known/jwks.json`). RS256/384/512 and ES256/384 signatures are
+//! accepted; `exp`, `nbf`, `iss` and, when `JWT_AUDIENCE` is set, `aud` are checked.
+//! Roles come from the `JWT_ROLES_CLAIM` claim (default `roles`; a dotted path such
+//! as `realm_access.roles` reaches nested claims) and map onto API scopes:
+//! `admin`, `editor` (write) and `viewer` (read).
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use parking_lot::RwLock;
+use ring::signature::{self, RsaPublicKeyComponents, UnparsedPublicKey};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use super::auth::ApiScope;
+
+/// Keys are refetched after this long, and at most this often for unknown `kid`s
+const JWKS_CACHE_TTL: Duration = Duration::from_mins(10);
+const JWKS_MIN_REFRESH: Duration = Duration::from_secs(30);
+const JWKS_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Clock skew tolerated on `exp` / `nbf`
+const CLOCK_LEEWAY_SECS: i64 = 60;
+
+/// Why a token was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwtError {
+    Malformed(&'static str),
+    UnsupportedAlgorithm(String),
+    UnknownKey,
+    BadSignature,
+    Expired,
+    NotYetValid,
+    WrongIssuer,
+    WrongAudience,
+    Jwks(String),
+}
+
+impl fmt::Display for JwtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(what) => write!(f, "malformed token ({what})"),
+            Self::UnsupportedAlgorithm(alg) => write!(f, "unsupported algorithm {alg}"),
+            Self::UnknownKey => write!(f, "signing key not found in JWKS"),
+            Self::BadSignature => write!(f, "invalid signature"),
+            Self::Expired => write!(f, "token expired"),
+            Self::NotYetValid => write!(f, "token not yet valid"),
+            Self::WrongIssuer => write!(f, "unexpected issuer"),
+            Self::WrongAudience => write!(f, "unexpected audience"),
+            Self::Jwks(e) => write!(f, "JWKS unavailable: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for JwtError {}
+
+/// Issuer, audience and key source of accepted tokens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtConfig {
+    pub issuer: String,
+    pub audience: Option<String>,
+    pub jwks_url: String,
+    pub roles_claim: String,
+}
+
+impl JwtConfig {
+    /// `JWT_ISSUER`, `JWT_AUDIENCE`, `JWT_JWKS_URL` and `JWT_ROLES_CLAIM`; `None`
+    /// when no issuer is set
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let var = |name| {
+            std::env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let issuer = var("JWT_ISSUER")?;
+        let jwks_url = var("JWT_JWKS_URL")
+            .unwrap_or_else(|| format!("{}/.well-known/jwks.json", issuer.trim_end_matches('/')));
+        Some(Self {
+            audience: var("JWT_AUDIENCE"),
+            roles_claim: var("JWT_ROLES_CLAIM").unwrap_or_else(|| "roles".to_string()),
+            issuer,
+            jwks_url,
+        })
+    }
+}
+
+/// Verified claims of one token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtClaims {
+    pub subject: String,
+    pub roles: Vec<String>,
+}
+
+impl JwtClaims {
+    /// API scopes granted by the token's roles
+    #[must_use]
+    pub fn scopes(&self) -> Vec<ApiScope> {
+        self.roles
+            .iter()
+            .filter_map(|role| match role.to_ascii_lowercase().as_str() {
+                "editor" => Some(ApiScope::Write),
+                "viewer" | "reader" => Some(ApiScope::Read),
+                other => ApiScope::parse(other),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+/// One key of a JWKS document
+#[derive(Debug, Clone, Deserialize)]
+struct Jwk {
+    kid: Option<String>,
+    kty: String,
+    n: Option<String>,
+    e: Option<String>,
+    crv: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+impl Jwk {
+    /// Check `signature` over `message` with this key, for algorithm `alg`
+    fn verify(&self, alg: &str, message: &[u8], sig: &[u8]) -> Result<(), JwtError> {
+        let decode = |field: &Option<String>| {
+            field
+                .as_deref()
+                .and_then(|v| URL_SAFE_NO_PAD.decode(v).ok())
+                .ok_or(JwtError::UnknownKey)
+        };
+        match (alg, self.kty.as_str()) {
+            ("RS256" | "RS384" | "RS512", "RSA") => {
+                let params = match alg {
+                    "RS256" => &signature::RSA_PKCS1_2048_8192_SHA256,
+                    "RS384" => &signature::RSA_PKCS1_2048_8192_SHA384,
+                    _ => &signature::RSA_PKCS1_2048_8192_SHA512,
+                };
+                RsaPublicKeyComponents {
+                    n: decode(&self.n)?,
+                    e: decode(&self.e)?,
+                }
+                .verify(params, message, sig)
+                .map_err(|_| JwtError::BadSignature)
+            }
+            ("ES256" | "ES384", "EC") => {
+                let (params, curve) = if alg == "ES256" {
+                    (&signature::ECDSA_P256_SHA256_FIXED, "P-256")
+                } else {
+                    (&signature::ECDSA_P384_SHA384_FIXED, "P-384")
+                };
+                if self.crv.as_deref() != Some(curve) {
+                    return Err(JwtError::UnknownKey);
+                }
+                // Uncompressed SEC1 point: 0x04 || x || y
+                let mut point = vec![0x04];
+                point.extend(decode(&self.x)?);
+                point.extend(decode(&self.y)?);
+                UnparsedPublicKey::new(params, point)
+                    .verify(message, sig)
+                    .map_err(|_| JwtError::BadSignature)
+            }
+            _ => Err(JwtError::UnsupportedAlgorithm(alg.to_string())),
+        }
+    }
+}
+
+#[derive(Default)]
+struct JwksCache {
+    keys: Vec<Jwk>,
+    fetched_at: Option<Instant>,
+}
+
+/// Verifies tokens against the issuer's current JWKS
+pub struct JwtVerifier {
+    config: JwtConfig,
+    client: reqwest::Client,
+    jwks: RwLock<JwksCache>,
+}
+
+impl JwtVerifier {
+    #[must_use]
+    pub fn new(config: JwtConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::builder()
+                .timeout(JWKS_FETCH_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            jwks: RwLock::new(JwksCache::default()),
+        }
+    }
+
+    /// Process-wide verifier, `None` when `JWT_ISSUER` is not set
+    #[must_use]
+    pub fn current() -> Option<&'static Self> {
+        static VERIFIER: OnceLock<Option<JwtVerifier>> = OnceLock::new();
+        VERIFIER
+            .get_or_init(|| {
+                JwtConfig::from_env().map(|config| {
+                    info!(
+                        "🔐 Accepting JWTs from {} (keys: {})",
+                        config.issuer, config.jwks_url
+                    );
+                    Self::new(config)
+                })
+            })
+            .as_ref()
+    }
+
+    /// Verify the signature and claims of `token`
+    ///
+    /// # Errors
+    /// Returns why the token was rejected
+    pub async fn verify(&self, token: &str) -> Result<JwtClaims, JwtError> {
+        let (header, _, _) = split_token(token)?;
+        let header: JwtHeader = decode_segment(header)?;
+        if self.find_key(header.kid.as_deref(), false).is_none() {
+            self.refresh_jwks().await?;
+        }
+        let key = self
+            .find_key(header.kid.as_deref(), true)
+            .ok_or(JwtError::UnknownKey)?;
+        verify_token(&self.config, &key, token, chrono::Utc::now().timestamp())
+    }
+
+    /// Cached key for `kid`; stale caches only count when `allow_stale`
+    fn find_key(&self, kid: Option<&str>, allow_stale: bool) -> Option<Jwk> {
+        let cache = self.jwks.read();
+        let fresh = cache
+            .fetched_at
+            .is_some_and(|at| at.elapsed() < JWKS_CACHE_TTL);
+        if !fresh && !allow_stale {
+            return None;
+        }
+        cache
+            .keys
+            .iter()
+            .find(|key| kid.is_none() || key.kid.as_deref() == kid)
+            .cloned()
+    }
+
+    async fn refresh_jwks(&self) -> Result<(), JwtError> {
+        let recently_fetched = self
+            .jwks
+            .read()
+            .fetched_at
+            .is_some_and(|at| at.elapsed() < JWKS_MIN_REFRESH);
+        if recently_fetched {
+            return Ok(());
+        }
+        let result = async {
+            self.client
+                .get(&self.config.jwks_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<JwkSet>()
+                .await
+        }
+        .await;
+        match result {
+            Ok(set) => {
+                *self.jwks.write() = JwksCache {
+                    keys: set.keys,
+                    fetched_at: Some(Instant::now()),
+                };
+                Ok(())
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ Failed to fetch JWKS from {}: {}",
+                    self.config.jwks_url, e
+                );
+                Err(JwtError::Jwks(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Whether `value` has the `header.payload.signature` shape of a JWT
+#[must_use]
+pub fn looks_like_jwt(value: &str) -> bool {
+    split_token(value).is_ok()
+}
+
+fn split_token(token: &str) -> Result<(&str, &str, &str), JwtError> {
+    let mut parts = token.split('.');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(header), Some(payload), Some(sig), None)
+            if !header.is_empty() && !payload.is_empty() && !sig.is_empty() =>
+        {
+            Ok((header, payload, sig))
+        }
+        _ => Err(JwtError::Malformed("expected three segments")),
+    }
+}
+
+fn decode_segment<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T, JwtError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| JwtError::Malformed("invalid base64url"))?;
+    serde_json::from_slice(&bytes).map_err(|_| JwtError::Malformed("invalid JSON"))
+}
+
+/// Check the signature of `token` with `key`, then its claims at time `now`
+fn verify_token(
+    config: &JwtConfig,
+    key: &Jwk,
+    token: &str,
+    now: i64,
+) -> Result<JwtClaims, JwtError> {
+    let (header_segment, payload_segment, sig_segment) = split_token(token)?;
+    let header: JwtHeader = decode_segment(header_segment)?;
+    let sig = URL_SAFE_NO_PAD
+        .decode(sig_segment)
+        .map_err(|_| JwtError::Malformed("invalid signature encoding"))?;
+    let signed = format!("{header_segment}.{payload_segment}");
+    key.verify(&header.alg, signed.as_bytes(), &sig)?;
+
+    let claims: Value = decode_segment(payload_segment)?;
+    let number = |name: &str| claims.get(name).and_then(Value::as_i64);
+    if number("exp").is_none_or(|exp| now > exp + CLOCK_LEEWAY_SECS) {
+        return Err(JwtError::Expired);
+    }
+    if number("nbf").is_some_and(|nbf| now + CLOCK_LEEWAY_SECS < nbf) {
+        return Err(JwtError::NotYetValid);
+    }
+    if claims.get("iss").and_then(Value::as_str) != Some(config.issuer.as_str()) {
+        return Err(JwtError::WrongIssuer);
+    }
+    if let Some(audience) = &config.audience {
+        let matches = match claims.get("aud") {
+            Some(Value::String(aud)) => aud == audience,
+            Some(Value::Array(auds)) => auds.iter().any(|aud| aud.as_str() == Some(audience)),
+            _ => false,
+        };
+        if !matches {
+            return Err(JwtError::WrongAudience);
+        }
+    }
+
+    let pointer = format!("/{}", config.roles_claim.replace('.', "/"));
+    let roles = match claims.pointer(&pointer) {
+        Some(Value::Array(roles)) => roles
+            .iter()
+            .filter_map(Value::as_str)
+            .map(ToString::to_string)
+            .collect(),
+        // OAuth-style space-separated list
+        Some(Value::String(roles)) => roles.split_whitespace().map(ToString::to_string).collect(),
+        _ => Vec::new(),
+    };
+    Ok(JwtClaims {
+        subject: claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        roles,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair};
+    use serde_json::json;
+
+    const NOW: i64 = 1_800_000_000;
+
+    fn config() -> JwtConfig {
+        JwtConfig {
+            issuer: "https://id.example.com".to_string(),
+            audience: Some("reports-api".to_string()),
+            jwks_url: "https://id.example.com/.well-known/jwks.json".to_string(),
+            roles_claim: "realm_access.roles".to_string(),
+        }
+    }
+
+    /// ES256 key pair and its public JWK
+    fn key_pair() -> Result<(EcdsaKeyPair, Jwk), Box<dyn std::error::Error>> {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng)
+            .map_err(|e| e.to_string())?;
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+            .map_err(|e| e.to_string())?;
+        let point = pair.public_key().as_ref();
+        let coordinate = |range: std::ops::Range<usize>| {
+            point.get(range).map(|bytes| URL_SAFE_NO_PAD.encode(bytes))
+        };
+        let jwk = Jwk {
+            kid: Some("k1".to_string()),
+            kty: "EC".to_string(),
+            n: None,
+            e: None,
+            crv: Some("P-256".to_string()),
+            x: coordinate(1..33),
+            y: coordinate(33..65),
+        };
+        Ok((pair, jwk))
+    }
+
+    fn sign(pair: &EcdsaKeyPair, claims: &Value) -> Result<String, Box<dyn std::error::Error>> {
+        let header = URL_SAFE_NO_PAD.encode(json!({"alg": "ES256", "kid": "k1"}).to_string());
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let signed = format!("{header}.{payload}");
+        let sig = pair
+            .sign(&SystemRandom::new(), signed.as_bytes())
+            .map_err(|e| e.to_string())?;
+        Ok(format!("{signed}.{}", URL_SAFE_NO_PAD.encode(sig.as_ref())))
+    }
+
+    fn with_claim(name: &str, value: Value) -> Value {
+        let mut claims = claims();
+        if let Some(map) = claims.as_object_mut() {
+            map.insert(name.to_string(), value);
+        }
+        claims
+    }
+
+    fn claims() -> Value {
+        json!({
+            "iss": "https://id.example.com",
+            "aud": ["account", "reports-api"],
+            "sub": "user-42",
+            "exp": NOW + 300,
+            "realm_access": {"roles": ["editor", "offline_access"]}
+        })
+    }
+
+    #[test]
+    fn test_valid_token() -> Result<(), Box<dyn std::error::Error>> {
+        let (pair, jwk) = key_pair()?;
+        let verified = verify_token(&config(), &jwk, &sign(&pair, &claims())?, NOW)?;
+        assert_eq!(verified.subject, "user-42");
+        assert_eq!(verified.scopes(), vec![ApiScope::Write]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejected_tokens() -> Result<(), Box<dyn std::error::Error>> {
+        let (pair, jwk) = key_pair()?;
+        let check = |claims: &Value| -> Result<_, Box<dyn std::error::Error>> {
+            Ok(verify_token(&config(), &jwk, &sign(&pair, claims)?, NOW))
+        };
+
+        let expired = with_claim("exp", json!(NOW - 120));
+        assert_eq!(check(&expired)?, Err(JwtError::Expired));
+        let other_issuer = with_claim("iss", json!("https://evil.example.com"));
+        assert_eq!(check(&other_issuer)?, Err(JwtError::WrongIssuer));
+        let other_audience = with_claim("aud", json!("billing"));
+        assert_eq!(check(&other_audience)?, Err(JwtError::WrongAudience));
+
+        let token = sign(&pair, &claims())?;
+        let (other_pair, _) = key_pair()?;
+        let forged = format!(
+            "{}.{}",
+            token.rsplit_once('.').map_or("", |(signed, _)| signed),
+            sign(&other_pair, &claims())?
+                .rsplit_once('.')
+                .map_or("", |(_, sig)| sig)
+        );
+        assert_eq!(
+            verify_token(&config(), &jwk, &forged, NOW),
+            Err(JwtError::BadSignature)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_shape() {
+        assert!(looks_like_jwt("a.b.c"));
+        assert!(!looks_like_jwt("change-me-admin-token"));
+        assert!(!looks_like_jwt("a.b.c.d"));
+        assert!(!looks_like_jwt("a..c"));
+    }
+}
@@ -0,0 +1,206 @@
+//! Market Assets
+//!
+//! The assets quoted by `market_data` stream entries, with the payload fields of
+//! their price and 24h change. Entries only need `btc_price_usd`; an asset whose
+//! price field is missing is simply not listed. Each asset's quote is cached under
+//! its own key (`api_asset:{SYMBOL}`) by `/api/crypto/assets/{symbol}`.
+
+use serde_json::Value;
+
+use crate::dto::responses::AssetQuoteResponse;
+
+/// What an asset is, for grouping on the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Crypto,
+    Commodity,
+    /// Quoted in index points rather than USD
+    Index,
+}
+
+impl AssetKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Crypto => "crypto",
+            Self::Commodity => "commodity",
+            Self::Index => "index",
+        }
+    }
+}
+
+/// One asset of the market data stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketAsset {
+    /// Uppercase ticker, e.g. `BTC`
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub kind: AssetKind,
+    /// Payload field of the price (USD, or points for an index)
+    pub price_field: &'static str,
+    /// Payload field of the 24h change (percent)
+    pub change_field: &'static str,
+}
+
+const fn asset(
+    symbol: &'static str,
+    name: &'static str,
+    kind: AssetKind,
+    price_field: &'static str,
+    change_field: &'static str,
+) -> MarketAsset {
+    MarketAsset {
+        symbol,
+        name,
+        kind,
+        price_field,
+        change_field,
+    }
+}
+
+/// Every asset, in dashboard order
+pub const MARKET_ASSETS: &[MarketAsset] = &[
+    asset(
+        "BTC",
+        "Bitcoin",
+        AssetKind::Crypto,
+        "btc_price_usd",
+        "btc_change_24h",
+    ),
+    asset(
+        "ETH",
+        "Ethereum",
+        AssetKind::Crypto,
+        "eth_price_usd",
+        "eth_change_24h",
+    ),
+    asset(
+        "BNB",
+        "BNB",
+        AssetKind::Crypto,
+        "bnb_price_usd",
+        "bnb_change_24h",
+    ),
+    asset(
+        "SOL",
+        "Solana",
+        AssetKind::Crypto,
+        "sol_price_usd",
+        "sol_change_24h",
+    ),
+    asset(
+        "XRP",
+        "XRP",
+        AssetKind::Crypto,
+        "xrp_price_usd",
+        "xrp_change_24h",
+    ),
+    asset(
+        "ADA",
+        "Cardano",
+        AssetKind::Crypto,
+        "ada_price_usd",
+        "ada_change_24h",
+    ),
+    asset(
+        "LINK",
+        "Chainlink",
+        AssetKind::Crypto,
+        "link_price_usd",
+        "link_change_24h",
+    ),
+    asset(
+        "XAU",
+        "Gold",
+        AssetKind::Commodity,
+        "gold_price_usd",
+        "gold_change_24h",
+    ),
+    asset(
+        "DXY",
+        "US Dollar Index",
+        AssetKind::Index,
+        "dxy_value",
+        "dxy_change_24h",
+    ),
+];
+
+impl MarketAsset {
+    /// Asset of a symbol, case-insensitive
+    #[must_use]
+    pub fn find(symbol: &str) -> Option<&'static Self> {
+        MARKET_ASSETS
+            .iter()
+            .find(|asset| asset.symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Cache key of the asset's latest quote
+    #[must_use]
+    pub fn cache_key(&self) -> String {
+        format!("api_asset:{}", self.symbol)
+    }
+
+    /// Quote of the asset in a market data payload, `None` when it has no price
+    #[must_use]
+    pub fn quote(&self, payload: &Value) -> Option<AssetQuoteResponse> {
+        let price = payload.get(self.price_field)?.as_f64()?;
+        Some(AssetQuoteResponse {
+            symbol: self.symbol.to_string(),
+            name: self.name.to_string(),
+            kind: self.kind.as_str().to_string(),
+            price,
+            change_24h: payload.get(self.change_field).and_then(Value::as_f64),
+            currency: None,
+        })
+    }
+}
+
+/// Quotes of every asset present in a market data payload, in dashboard order
+#[must_use]
+pub fn asset_quotes(payload: &Value) -> Vec<AssetQuoteResponse> {
+    MARKET_ASSETS
+        .iter()
+        .filter_map(|asset| asset.quote(payload))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_asset_quotes() {
+        let payload = json!({
+            "btc_price_usd": 45000.0,
+            "btc_change_24h": -1.5,
+            "gold_price_usd": 2400.0,
+            "dxy_value": 104.2,
+            "dxy_change_24h": 0.1,
+        });
+        let quotes = asset_quotes(&payload);
+        let symbols: Vec<&str> = quotes.iter().map(|quote| quote.symbol.as_str()).collect();
+        assert_eq!(symbols, ["BTC", "XAU", "DXY"]);
+        assert_eq!(
+            quotes.first().and_then(|quote| quote.change_24h),
+            Some(-1.5)
+        );
+        assert_eq!(
+            quotes.get(1).map(|quote| quote.kind.as_str()),
+            Some("commodity")
+        );
+        assert_eq!(quotes.get(1).and_then(|quote| quote.change_24h), None);
+
+        assert_eq!(
+            MarketAsset::find("xau").map(|asset| asset.name),
+            Some("Gold")
+        );
+        assert!(MarketAsset::find("DOGE").is_none());
+        assert_eq!(
+            MarketAsset::find("dxy")
+                .map(MarketAsset::cache_key)
+                .as_deref(),
+            Some("api_asset:DXY")
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Message Transport
+//!
+//! Layer 3 abstraction over how the data published by the websocket service
+//! reaches this service. Every transport feeds the same `StreamRegistry` readers,
+//! so validation, dead-lettering, caching, checkpoints and live updates behave
+//! the same whichever transport delivered an entry.
+//!
+//! - `redis` (default): Redis Streams consumer groups or followers, plus retention
+//! - `nats`: NATS `JetStream` pull consumers (see `nats_transport`)
+//!
+//! Environment:
+//! - `MESSAGE_TRANSPORT`: `redis` or `nats`
+
+use std::sync::Arc;
+use tracing::info;
+
+use super::nats_transport::{NatsConfig, NatsTransport};
+use crate::cluster::ClusterConfig;
+use crate::redis_endpoint::RedisEndpoint;
+use crate::stream::{ConsumerGroupConfig, StreamRegistry};
+use crate::tasks::TaskRegistry;
+
+/// Source of the entries of the registered streams
+pub trait MessageTransport: Send + Sync {
+    /// Transport name for logs and diagnostics
+    fn name(&self) -> &'static str;
+
+    /// Start consuming every registered stream as supervised background tasks
+    fn start(&self, streams: &StreamRegistry, tasks: &TaskRegistry);
+}
+
+/// Redis Streams, read through consumer groups or per-instance followers
+pub struct RedisStreamsTransport {
+    endpoint: Arc<RedisEndpoint>,
+    /// `None` when consumer groups are disabled
+    consumer_config: Option<ConsumerGroupConfig>,
+}
+
+impl RedisStreamsTransport {
+    #[must_use]
+    pub const fn new(
+        endpoint: Arc<RedisEndpoint>,
+        consumer_config: Option<ConsumerGroupConfig>,
+    ) -> Self {
+        Self {
+            endpoint,
+            consumer_config,
+        }
+    }
+}
+
+impl MessageTransport for RedisStreamsTransport {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    fn start(&self, streams: &StreamRegistry, tasks: &TaskRegistry) {
+        // Replicas share each registered stream through one consumer group,
+        // or each follows every stream when groups are disabled
+        match &self.consumer_config {
+            Some(config) => streams.start_consumer_groups(&self.endpoint, config, tasks),
+            None => streams.start_followers(&self.endpoint, tasks),
+        }
+        // Trimming is once per host: only worker 0 of a cluster runs it
+        if ClusterConfig::current().runs_singletons() {
+            streams.start_retention(&self.endpoint, tasks);
+        }
+    }
+}
+
+/// Transport selected by `MESSAGE_TRANSPORT`
+///
+/// # Errors
+/// Returns an error for an unknown transport or an invalid NATS configuration.
+pub fn transport_from_env(
+    endpoint: &Arc<RedisEndpoint>,
+) -> Result<Arc<dyn MessageTransport>, String> {
+    let selected = std::env::var("MESSAGE_TRANSPORT").unwrap_or_default();
+    let transport: Arc<dyn MessageTransport> = match selected.trim().to_ascii_lowercase().as_str() {
+        "" | "redis" => Arc::new(RedisStreamsTransport::new(
+            Arc::clone(endpoint),
+            ConsumerGroupConfig::from_env(),
+        )),
+        "nats" => Arc::new(NatsTransport::new(NatsConfig::from_env()?)),
+        other => {
+            return Err(format!(
+                "Unknown MESSAGE_TRANSPORT '{other}' (expected 'redis' or 'nats')"
+            ));
+        }
+    };
+    info!("📡 Consuming inter-service data over {}", transport.name());
+    Ok(transport)
+}
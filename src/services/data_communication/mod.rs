@@ -3,6 +3,49 @@
 //! Layer 3 data communication services.
 //! Handles all data-related communication between business logic and infrastructure.
 
+pub mod audit_log_service;
+pub mod auth;
+pub mod author_data_service;
+pub mod cache_keys;
 pub mod crypto_data_service;
+pub mod fng_history;
+pub mod fx_rates;
+pub mod history_recorder;
+pub mod jwt;
+pub mod market_assets;
+pub mod message_transport;
+pub mod nats_transport;
+pub mod query_timing;
+pub mod redirect_service;
+pub mod report_audit_service;
+pub mod report_slug;
+pub mod stream_publisher;
 
+pub use audit_log_service::{AuditLogEntry, AuditLogService, MAX_AUDIT_PAGE_SIZE};
+pub use auth::{
+    API_KEY_HEADER, ApiKey, ApiKeyService, ApiScope, AuthMethod, CallerIdentity, authenticate,
+    current_caller, hash_api_key,
+};
+pub use author_data_service::*;
+pub use cache_keys::{
+    CacheKeyBuilder, CacheRoute, DEFAULT_LANGUAGE, SUPPORTED_LANGUAGES, cache_version,
+    normalize_language, versioned_key,
+};
 pub use crypto_data_service::*;
+pub use fng_history::{DEFAULT_FNG_DAYS, FngDay, FngHistory, MAX_FNG_DAYS, spawn_fng_recorder};
+pub use fx_rates::{Currency, CurrencyService, FxRates};
+pub use history_recorder::{
+    CandleInterval, CandleRecord, HistoryMetric, HistoryPoint, HistoryRange, HistoryRecorder,
+    spawn_history_recorder,
+};
+pub use jwt::{JwtClaims, JwtConfig, JwtError, JwtVerifier};
+pub use market_assets::{AssetKind, MARKET_ASSETS, MarketAsset, asset_quotes};
+pub use message_transport::{MessageTransport, RedisStreamsTransport, transport_from_env};
+pub use nats_transport::{NatsConfig, NatsTransport};
+pub use query_timing::{slow_query_threshold, timed_query};
+pub use redirect_service::{RedirectRecord, RedirectService};
+pub use report_audit_service::{
+    MAX_REPORT_AUDIT_PAGE_SIZE, ReportAuditEntry, ReportAuditRecord, ReportAuditService,
+};
+pub use report_slug::{REPORT_TITLE_VI, parse_report_segment, report_path, report_slug, slugify};
+pub use stream_publisher::{ReportEvent, StreamPublisher};
@@ -0,0 +1,124 @@
+//! Query Timing
+//!
+//! Times report and list queries, feeds their latency into the metrics registry and
+//! logs the ones slower than `SLOW_QUERY_THRESHOLD_MS` (default 200ms) with their
+//! parameters and row count, so pagination queries that degrade as `crypto_report`
+//! grows show up before users notice.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::state::AppState;
+
+/// Default duration above which a query is logged as slow
+const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(200);
+
+/// Slow query threshold (`SLOW_QUERY_THRESHOLD_MS`, default 200ms)
+#[must_use]
+pub fn slow_query_threshold() -> Duration {
+    static THRESHOLD: OnceLock<Duration> = OnceLock::new();
+    *THRESHOLD.get_or_init(|| {
+        std::env::var("SLOW_QUERY_THRESHOLD_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&v: &u64| v > 0)
+            .map_or(DEFAULT_SLOW_QUERY_THRESHOLD, Duration::from_millis)
+    })
+}
+
+/// Named query parameter for the slow query log
+pub type QueryParam<'a> = (&'a str, &'a (dyn Display + Sync));
+
+/// Rows returned by a query result, for the slow query log
+pub trait RowCount {
+    fn row_count(&self) -> usize;
+}
+
+impl<T> RowCount for Vec<T> {
+    fn row_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> RowCount for Option<T> {
+    fn row_count(&self) -> usize {
+        usize::from(self.is_some())
+    }
+}
+
+impl RowCount for i64 {
+    fn row_count(&self) -> usize {
+        1
+    }
+}
+
+/// Run `query`, record its latency under `name` and log it when slow
+///
+/// # Errors
+///
+/// Returns the query's own error unchanged
+pub async fn timed_query<T, F>(
+    state: &AppState,
+    name: &'static str,
+    params: &[QueryParam<'_>],
+    query: F,
+) -> Result<T, sqlx::Error>
+where
+    T: RowCount,
+    F: Future<Output = Result<T, sqlx::Error>>,
+{
+    let started = Instant::now();
+    let result = query.await;
+    let elapsed = started.elapsed();
+
+    let slow = elapsed >= slow_query_threshold();
+    state.health.metrics.record_query(name, elapsed, slow);
+    if let Err(e) = &result {
+        state.health.db_pool.record_error(e);
+    }
+    if slow {
+        let rows = result.as_ref().map_or_else(
+            |e| format!("error: {e}"),
+            |rows| rows.row_count().to_string(),
+        );
+        warn!(
+            query = name,
+            params = %format_params(params),
+            rows = %rows,
+            elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            "🐢 Slow query {} took {}ms",
+            name,
+            elapsed.as_millis()
+        );
+    }
+    result
+}
+
+/// `name=value` pairs joined with `, `
+fn format_params(params: &[QueryParam<'_>]) -> String {
+    params
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_params_and_row_counts() {
+        assert_eq!(
+            format_params(&[("page", &3), ("per_page", &10)]),
+            "page=3, per_page=10"
+        );
+        assert_eq!(format_params(&[]), "");
+        assert_eq!(vec![1, 2, 3].row_count(), 3);
+        assert_eq!(None::<i32>.row_count(), 0);
+        assert_eq!(42_i64.row_count(), 1);
+    }
+}
@@ -0,0 +1,90 @@
+//! Redirect Service
+//!
+//! Layer 3 data communication service for the `redirects` table (see
+//! `migrations/20261015090300_create_redirects.sql`), read by the Layer 5
+//! `RedirectManager` and written through the admin API.
+
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+
+/// One stored redirect
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct RedirectRecord {
+    pub id: i64,
+    pub source_path: String,
+    pub target: String,
+    /// 301 or 302
+    pub status_code: i16,
+    pub created_by: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Redirect Service
+///
+/// Layer 3 service responsible for redirect table database operations.
+#[derive(Clone, Default)]
+pub struct RedirectService;
+
+impl RedirectService {
+    /// Create a new `RedirectService`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Fetch every redirect, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_all(&self, db: &PgPool) -> Result<Vec<RedirectRecord>, sqlx::Error> {
+        sqlx::query_as::<_, RedirectRecord>(
+            "SELECT id, source_path, target, status_code, created_by, created_at, updated_at \
+             FROM redirects ORDER BY id",
+        )
+        .fetch_all(db)
+        .await
+    }
+
+    /// Insert a redirect, or replace the target and status of an existing source
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn upsert(
+        &self,
+        db: &PgPool,
+        source_path: &str,
+        target: &str,
+        status_code: i16,
+        actor: &str,
+    ) -> Result<RedirectRecord, sqlx::Error> {
+        sqlx::query_as::<_, RedirectRecord>(
+            "INSERT INTO redirects (source_path, target, status_code, created_by) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (source_path) DO UPDATE \
+             SET target = EXCLUDED.target, status_code = EXCLUDED.status_code, updated_at = now() \
+             RETURNING id, source_path, target, status_code, created_by, created_at, updated_at",
+        )
+        .bind(source_path)
+        .bind(target)
+        .bind(status_code)
+        .bind(actor)
+        .fetch_one(db)
+        .await
+    }
+
+    /// Delete a redirect, returning whether it existed
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn delete(&self, db: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM redirects WHERE id = $1")
+            .bind(id)
+            .execute(db)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,116 @@
+//! Report Audit Service
+//!
+//! Layer 3 data communication service for the report modification trail stored in
+//! `report_audit` (see `migrations/20261015090100_create_report_audit.sql`).
+
+use serde::Serialize;
+use sqlx::FromRow;
+use tracing::{debug, warn};
+
+use crate::services::health_system::current_request_id;
+use crate::state::AppState;
+
+/// Max entries returned by one report audit read
+pub const MAX_REPORT_AUDIT_PAGE_SIZE: i64 = 200;
+
+/// One report mutation to record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportAuditRecord {
+    pub report_id: i32,
+    /// `create`, `update` or `delete`
+    pub action: &'static str,
+    pub api_key_id: Option<i64>,
+    pub actor: String,
+    pub changed_fields: Vec<String>,
+    /// Hex blake3 hash of the new values of `changed_fields`
+    pub changes_hash: String,
+}
+
+/// One recorded report mutation
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ReportAuditEntry {
+    pub id: i64,
+    pub report_id: i32,
+    pub action: String,
+    pub api_key_id: Option<i64>,
+    pub actor: String,
+    pub changed_fields: Vec<String>,
+    pub changes_hash: String,
+    pub request_id: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Report Audit Service
+///
+/// Layer 3 service responsible for report audit database operations.
+#[derive(Clone, Default)]
+pub struct ReportAuditService;
+
+impl ReportAuditService {
+    /// Create a new `ReportAuditService`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Record a report mutation, tagged with the id of the request being served
+    ///
+    /// A failed insert is logged rather than returned: the mutation itself already ran.
+    pub async fn record(&self, state: &AppState, record: &ReportAuditRecord) {
+        let result = sqlx::query(
+            "INSERT INTO report_audit \
+             (report_id, action, api_key_id, actor, changed_fields, changes_hash, request_id) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(record.report_id)
+        .bind(record.action)
+        .bind(record.api_key_id)
+        .bind(&record.actor)
+        .bind(&record.changed_fields)
+        .bind(&record.changes_hash)
+        .bind(current_request_id().map(|id| id.to_string()))
+        .execute(&state.db)
+        .await;
+
+        match result {
+            Ok(_) => debug!(
+                "📝 Report audit: {} report {} by {}",
+                record.action, record.report_id, record.actor
+            ),
+            Err(e) => warn!(
+                report_id = record.report_id,
+                action = record.action,
+                actor = %record.actor,
+                "⚠️ Failed to write report audit entry: {}",
+                e
+            ),
+        }
+    }
+
+    /// Fetch the newest mutations of one report
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn fetch_for_report(
+        &self,
+        state: &AppState,
+        report_id: i32,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ReportAuditEntry>, sqlx::Error> {
+        sqlx::query_as::<_, ReportAuditEntry>(
+            "SELECT id, report_id, action, api_key_id, actor, changed_fields, changes_hash, \
+             request_id, created_at \
+             FROM report_audit \
+             WHERE report_id = $1 \
+             ORDER BY created_at DESC, id DESC \
+             LIMIT $2 OFFSET $3",
+        )
+        .bind(report_id)
+        .bind(limit.clamp(1, MAX_REPORT_AUDIT_PAGE_SIZE))
+        .bind(offset.max(0))
+        .fetch_all(&state.db)
+        .await
+    }
+}
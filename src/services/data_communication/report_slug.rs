@@ -0,0 +1,134 @@
+//! Report URL Slugs
+//!
+//! Report pages live at `/crypto_report/{id}-{slug}`, the slug being the
+//! Vietnamese report title without its `#id` (the URL already carries the id),
+//! e.g. `/crypto_report/123-phan-tich-thi-truong-crypto-10-03-2024`.
+//!
+//! `crypto_report.slug` is filled on insert by the `crypto_report_slug` trigger
+//! (see `migrations/20261015090200_add_crypto_report_slug.sql`), which mirrors
+//! `report_slug`; links are built from `report_path` without a database read.
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Title of every report in Vietnamese, followed by `#id - dd/mm/yyyy`
+pub const REPORT_TITLE_VI: &str = "Phân Tích Thị Trường Crypto";
+
+/// Max length of a generated slug
+const MAX_SLUG_LENGTH: usize = 80;
+
+/// Lowercase ASCII slug of `text`: Vietnamese diacritics are folded, every other
+/// run of non-alphanumeric characters becomes one dash
+#[must_use]
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(fold_vietnamese)
+    {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    if slug.len() > MAX_SLUG_LENGTH {
+        slug.truncate(MAX_SLUG_LENGTH);
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Slug of the report created at `created_at` (date in Vietnamese time, UTC+7)
+#[must_use]
+pub fn report_slug(created_at: DateTime<Utc>) -> String {
+    let vn_time = created_at + Duration::hours(7);
+    slugify(&format!("{REPORT_TITLE_VI} {}", vn_time.format("%d/%m/%Y")))
+}
+
+/// Canonical site-relative URL of a report page
+#[must_use]
+pub fn report_path(report_id: i32, created_at: DateTime<Utc>) -> String {
+    format!("/crypto_report/{report_id}-{}", report_slug(created_at))
+}
+
+/// Split a `/crypto_report/{segment}` path segment into the report id and the
+/// slug, if any: `123` and `123-some-slug` are both accepted
+#[must_use]
+pub fn parse_report_segment(segment: &str) -> Option<(i32, Option<&str>)> {
+    let (id, slug) = match segment.split_once('-') {
+        Some((id, slug)) => (id, Some(slug)),
+        None => (segment, None),
+    };
+    let valid_id = !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit());
+    valid_id
+        .then(|| id.parse().ok())
+        .flatten()
+        .map(|id| (id, slug))
+}
+
+/// Base letter of a Vietnamese (lowercase) letter with diacritics
+fn fold_vietnamese(c: char) -> char {
+    const FOLDS: &[(char, &str)] = &[
+        ('a', "àáạảãâầấậẩẫăằắặẳẵ"),
+        ('e', "èéẹẻẽêềếệểễ"),
+        ('i', "ìíịỉĩ"),
+        ('o', "òóọỏõôồốộổỗơờớợởỡ"),
+        ('u', "ùúụủũưừứựửữ"),
+        ('y', "ỳýỵỷỹ"),
+        ('d', "đ"),
+    ];
+    FOLDS
+        .iter()
+        .find(|(_, accented)| accented.contains(c))
+        .map_or(c, |(base, _)| *base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(
+            slugify("Phân Tích Thị Trường Crypto #123 - 10/03/2024"),
+            "phan-tich-thi-truong-crypto-123-10-03-2024"
+        );
+        assert_eq!(slugify("  Đồng USD & Bitcoin!  "), "dong-usd-bitcoin");
+        assert_eq!(slugify("---"), "");
+        assert!(slugify(&"ab ".repeat(60)).len() <= MAX_SLUG_LENGTH);
+        assert!(!slugify(&"ab ".repeat(60)).ends_with('-'));
+    }
+
+    #[test]
+    fn test_report_slug_uses_vietnamese_date() -> Result<(), Box<dyn std::error::Error>> {
+        // 20:00 UTC on March 9 is already March 10 in Vietnam
+        let created_at = Utc
+            .with_ymd_and_hms(2024, 3, 9, 20, 0, 0)
+            .single()
+            .ok_or("Invalid time")?;
+        assert_eq!(
+            report_slug(created_at),
+            "phan-tich-thi-truong-crypto-10-03-2024"
+        );
+        assert_eq!(
+            report_path(123, created_at),
+            "/crypto_report/123-phan-tich-thi-truong-crypto-10-03-2024"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_report_segment() {
+        assert_eq!(parse_report_segment("123"), Some((123, None)));
+        assert_eq!(
+            parse_report_segment("123-phan-tich"),
+            Some((123, Some("phan-tich")))
+        );
+        assert_eq!(parse_report_segment("123-"), Some((123, Some(""))));
+        assert_eq!(parse_report_segment("-1"), None);
+        assert_eq!(parse_report_segment("+5"), None);
+        assert_eq!(parse_report_segment("abc-123"), None);
+        assert_eq!(parse_report_segment("99999999999"), None);
+    }
+}
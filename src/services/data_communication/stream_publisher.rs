@@ -0,0 +1,160 @@
+//! Report Event Publisher
+//!
+//! Layer 3 component writing report lifecycle events to the `report_events` stream,
+//! so the websocket service and other consumers can notify clients without polling
+//! the database. Each entry has `event`, `report_id` and `occurred_at` fields plus
+//! the trace context of the request that caused it.
+//!
+//! Reports are written to the database outside this service, so events describe
+//! what this service observes:
+//! - `report_published`: a newer report became the latest one
+//! - `report_updated`: an operator purged a report's cached pages after editing it
+//!
+//! Delivery is at-least-once: every replica that notices a new latest report
+//! publishes it, so consumers should de-duplicate on `event` + `report_id`.
+//!
+//! Environment:
+//! - `REPORT_EVENTS_PUBLISH`: `false` disables publishing
+
+use anyhow::Result;
+use multi_tier_cache::CacheManager;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::services::shared::TraceContext;
+
+/// Approximate max length of the report events stream
+const REPORT_EVENTS_MAXLEN: usize = 10_000;
+
+/// Report lifecycle events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportEvent {
+    Published,
+    Updated,
+}
+
+impl ReportEvent {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Published => "report_published",
+            Self::Updated => "report_updated",
+        }
+    }
+}
+
+/// Publishes report events to a Redis stream
+pub struct StreamPublisher {
+    /// `None` when publishing is disabled
+    cache_manager: Option<Arc<CacheManager>>,
+    stream_key: String,
+}
+
+impl StreamPublisher {
+    /// Publisher writing to `stream_key`
+    #[must_use]
+    pub const fn new(cache_manager: Arc<CacheManager>, stream_key: String) -> Self {
+        Self {
+            cache_manager: Some(cache_manager),
+            stream_key,
+        }
+    }
+
+    /// Publisher that drops every event
+    #[must_use]
+    pub const fn disabled(stream_key: String) -> Self {
+        Self {
+            cache_manager: None,
+            stream_key,
+        }
+    }
+
+    /// Publisher for `stream_key` unless `REPORT_EVENTS_PUBLISH=false`
+    #[must_use]
+    pub fn from_env(cache_manager: &Arc<CacheManager>, stream_key: String) -> Self {
+        let disabled = std::env::var("REPORT_EVENTS_PUBLISH").is_ok_and(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "false" | "0" | "off"
+            )
+        });
+        if disabled {
+            info!("⏭️ Report events publishing disabled (REPORT_EVENTS_PUBLISH=false)");
+            Self::disabled(stream_key)
+        } else {
+            Self::new(Arc::clone(cache_manager), stream_key)
+        }
+    }
+
+    /// Stream the events are written to
+    #[must_use]
+    pub fn stream_key(&self) -> &str {
+        &self.stream_key
+    }
+
+    /// Publish one report event; returns the entry ID (`None` when disabled)
+    ///
+    /// # Errors
+    /// Returns an error if the streaming backend is unavailable or the XADD fails.
+    pub async fn publish_report_event(
+        &self,
+        event: ReportEvent,
+        report_id: i32,
+        trace: &TraceContext,
+    ) -> Result<Option<String>> {
+        let Some(cache_manager) = &self.cache_manager else {
+            return Ok(None);
+        };
+        let mut fields = event_fields(event, report_id, &chrono::Utc::now().to_rfc3339());
+        trace.inject_into_fields(&mut fields);
+
+        let entry_id = cache_manager
+            .publish_to_stream(&self.stream_key, fields, Some(REPORT_EVENTS_MAXLEN))
+            .await?;
+        info!(
+            trace_id = %trace.trace_id,
+            "📤 Published '{}' for report {} to {} ({})",
+            event.as_str(), report_id, self.stream_key, entry_id
+        );
+        Ok(Some(entry_id))
+    }
+}
+
+fn event_fields(event: ReportEvent, report_id: i32, occurred_at: &str) -> Vec<(String, String)> {
+    vec![
+        ("event".to_string(), event.as_str().to_string()),
+        ("report_id".to_string(), report_id.to_string()),
+        ("occurred_at".to_string(), occurred_at.to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_disabled_publisher_drops_events() -> Result<()> {
+        let publisher = StreamPublisher::disabled("report_events_stream".to_string());
+        let entry_id = publisher
+            .publish_report_event(ReportEvent::Published, 42, &TraceContext::new_root())
+            .await?;
+        assert_eq!(entry_id, None);
+        assert_eq!(publisher.stream_key(), "report_events_stream");
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_fields() {
+        assert_eq!(
+            event_fields(ReportEvent::Updated, 7, "2026-10-14T08:00:00Z"),
+            vec![
+                ("event".to_string(), "report_updated".to_string()),
+                ("report_id".to_string(), "7".to_string()),
+                (
+                    "occurred_at".to_string(),
+                    "2026-10-14T08:00:00Z".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! Operational Diagnostics
+//!
+//! Runs every runbook check in one pass for `/admin/diagnostics`: database and Redis
+//! latency, market data stream lag, free disk space for template/static directories,
+//! template load errors, background task status and the recent error index.
+//!
+//! Each check gets a severity and, when not ok, the first thing on-call should try.
+//! Checks run concurrently and are returned most severe first.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::dto::responses::{
+    DiagnosticCheck, DiagnosticSeverity, DiagnosticsResponse, RecentErrorEntry,
+};
+use crate::services::shared::recent_errors;
+use crate::state::AppState;
+use crate::tasks::TaskState;
+
+/// Timeout for each latency probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Latency above which DB / Redis probes are reported as slow
+const DB_SLOW_LATENCY: Duration = Duration::from_millis(100);
+const REDIS_SLOW_LATENCY: Duration = Duration::from_millis(50);
+/// Market data older than this is stale / critically stale
+const STREAM_LAG_WARNING: Duration = Duration::from_mins(1);
+const STREAM_LAG_CRITICAL: Duration = Duration::from_mins(5);
+/// Free disk ratio below which the filesystem is reported
+const DISK_FREE_WARNING: f64 = 0.10;
+const DISK_FREE_CRITICAL: f64 = 0.05;
+/// Window and thresholds for the recent error check
+const ERROR_WINDOW: chrono::Duration = chrono::Duration::minutes(15);
+const ERROR_COUNT_WARNING: usize = 5;
+const ERROR_COUNT_CRITICAL: usize = 25;
+/// Recent errors included in the report
+const RECENT_ERRORS_SHOWN: usize = 20;
+
+/// Directories templates and static assets are served from
+const SERVED_DIRECTORIES: [&str; 3] = [
+    "dashboards",
+    "shared_components",
+    "dashboards/crypto_dashboard/assets",
+];
+
+/// Run all checks and build the prioritized report
+pub async fn run_diagnostics(state: &Arc<AppState>) -> DiagnosticsResponse {
+    let started = Instant::now();
+
+    let (database, redis, stream) = tokio::join!(
+        database_check(state),
+        redis_check(state),
+        stream_lag_check(state)
+    );
+    let disk = tokio::task::spawn_blocking(|| {
+        SERVED_DIRECTORIES
+            .iter()
+            .map(|dir| disk_check(dir))
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_else(|e| {
+        vec![check(
+            "disk",
+            DiagnosticSeverity::Warning,
+            format!("Disk check task failed: {e}"),
+            Some("Retry the diagnostics call; check server logs if it keeps failing"),
+        )]
+    });
+
+    let mut checks = vec![database, redis, stream];
+    checks.extend(disk);
+    checks.push(template_check(&state.template_errors));
+    checks.push(task_check(state));
+    checks.push(error_rate_check());
+    // Stable sort keeps the declaration order within a severity
+    checks.sort_by_key(|check| std::cmp::Reverse(check.severity));
+
+    let index = recent_errors();
+    DiagnosticsResponse {
+        status: overall_severity(&checks),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        duration_ms: millis(started.elapsed()),
+        checks,
+        recent_errors: index
+            .latest(RECENT_ERRORS_SHOWN)
+            .into_iter()
+            .map(|error| RecentErrorEntry {
+                at: error.at.to_rfc3339(),
+                kind: error.kind.to_string(),
+                status: error.status,
+                message: error.message,
+                request_id: error.request_id,
+            })
+            .collect(),
+        error_totals: index
+            .totals()
+            .into_iter()
+            .map(|(kind, count)| (kind.to_string(), count))
+            .collect::<BTreeMap<_, _>>(),
+    }
+}
+
+async fn database_check(state: &AppState) -> DiagnosticCheck {
+    let result = timed(sqlx::query("SELECT 1").execute(&state.db)).await;
+    latency_check(
+        "database",
+        result,
+        DB_SLOW_LATENCY,
+        "Check PostgreSQL availability, DATABASE_URL and the connection pool",
+    )
+}
+
+async fn redis_check(state: &AppState) -> DiagnosticCheck {
+    // Probe key is never cached, so the lookup falls through L1 to Redis
+    let result = timed(state.cache_manager.get("_diagnostics_probe")).await;
+    latency_check(
+        "redis",
+        result,
+        REDIS_SLOW_LATENCY,
+        "Check Redis availability and REDIS_URL; pages are served from L1 only meanwhile",
+    )
+}
+
+async fn stream_lag_check(state: &AppState) -> DiagnosticCheck {
+    let action = "Check that the websocket service is running and publishing to market_data_stream";
+    match tokio::time::timeout(
+        PROBE_TIMEOUT,
+        state.streams.market_data().latest_entry_age(),
+    )
+    .await
+    {
+        Ok(Ok(Some(age))) => {
+            let severity = stream_lag_severity(age);
+            check(
+                "market_stream",
+                severity,
+                format!("Latest market data entry is {}s old", age.as_secs()),
+                (severity != DiagnosticSeverity::Ok).then_some(action),
+            )
+        }
+        Ok(Ok(None)) => check(
+            "market_stream",
+            DiagnosticSeverity::Critical,
+            "Market data stream is empty".to_string(),
+            Some(action),
+        ),
+        Ok(Err(e)) => check(
+            "market_stream",
+            DiagnosticSeverity::Critical,
+            format!("Failed to read market data stream: {e}"),
+            Some(action),
+        ),
+        Err(_) => check(
+            "market_stream",
+            DiagnosticSeverity::Critical,
+            format!("Stream read timed out after {}s", PROBE_TIMEOUT.as_secs()),
+            Some(action),
+        ),
+    }
+}
+
+fn disk_check(dir: &str) -> DiagnosticCheck {
+    let name = format!("disk:{dir}");
+    let path = Path::new(dir);
+    if !path.is_dir() {
+        return check(
+            &name,
+            DiagnosticSeverity::Critical,
+            format!("Directory {dir} is missing"),
+            Some("Redeploy: templates and static assets are read from the working directory"),
+        );
+    }
+
+    match disk_space(path) {
+        Some(space) => {
+            let severity = disk_severity(space.free_ratio());
+            check(
+                &name,
+                severity,
+                format!(
+                    "{} MiB free of {} MiB ({:.1}%)",
+                    space.available_bytes / (1024 * 1024),
+                    space.total_bytes / (1024 * 1024),
+                    space.free_ratio() * 100.0
+                ),
+                (severity != DiagnosticSeverity::Ok)
+                    .then_some("Free disk space: rotate logs and remove old build artifacts"),
+            )
+        }
+        None => check(
+            &name,
+            DiagnosticSeverity::Ok,
+            format!("Directory {dir} present (free space unavailable on this platform)"),
+            None,
+        ),
+    }
+}
+
+fn template_check(errors: &[String]) -> DiagnosticCheck {
+    if errors.is_empty() {
+        check(
+            "templates",
+            DiagnosticSeverity::Ok,
+            "All templates loaded".to_string(),
+            None,
+        )
+    } else {
+        check(
+            "templates",
+            DiagnosticSeverity::Critical,
+            format!("{} template error(s): {}", errors.len(), errors.join("; ")),
+            Some("Fix the template syntax and restart; affected pages return 500"),
+        )
+    }
+}
+
+fn task_check(state: &AppState) -> DiagnosticCheck {
+    let statuses = state.tasks.statuses();
+    let severity = statuses
+        .iter()
+        .map(|status| task_severity(&status.state))
+        .max()
+        .unwrap_or(DiagnosticSeverity::Ok);
+    let summary = if statuses.is_empty() {
+        "No background tasks started".to_string()
+    } else {
+        statuses
+            .iter()
+            .map(|status| match &status.state {
+                TaskState::Running => format!("{}: running", status.name),
+                TaskState::Completed => format!("{}: completed", status.name),
+                TaskState::Failed(e) => format!("{}: failed ({e})", status.name),
+                TaskState::Panicked(e) => format!("{}: panicked ({e})", status.name),
+                TaskState::Restarting(e) => format!(
+                    "{}: restarting after failure #{} ({e})",
+                    status.name, status.restarts
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    check(
+        "background_tasks",
+        severity,
+        summary,
+        (severity != DiagnosticSeverity::Ok)
+            .then_some("Inspect the task's log lines; restart the worker if a task panicked"),
+    )
+}
+
+fn error_rate_check() -> DiagnosticCheck {
+    let count = recent_errors().count_since(chrono::Utc::now() - ERROR_WINDOW);
+    let severity = error_count_severity(count);
+    check(
+        "recent_errors",
+        severity,
+        format!(
+            "{count} server error(s) in the last {} minutes",
+            ERROR_WINDOW.num_minutes()
+        ),
+        (severity != DiagnosticSeverity::Ok)
+            .then_some("Group recent_errors by kind; the most frequent kind usually names the failing dependency"),
+    )
+}
+
+/// Build a latency check from a timed probe (`None` = timed out)
+fn latency_check<T, E: std::fmt::Display>(
+    name: &str,
+    result: Option<(Result<T, E>, Duration)>,
+    slow: Duration,
+    action: &str,
+) -> DiagnosticCheck {
+    match result {
+        Some((Ok(_), latency)) => {
+            let severity = if latency > slow {
+                DiagnosticSeverity::Warning
+            } else {
+                DiagnosticSeverity::Ok
+            };
+            DiagnosticCheck {
+                latency_ms: Some(millis(latency)),
+                ..check(
+                    name,
+                    severity,
+                    format!("Responded in {}ms", millis(latency)),
+                    (severity != DiagnosticSeverity::Ok).then_some(action),
+                )
+            }
+        }
+        Some((Err(e), latency)) => DiagnosticCheck {
+            latency_ms: Some(millis(latency)),
+            ..check(
+                name,
+                DiagnosticSeverity::Critical,
+                format!("Probe failed: {e}"),
+                Some(action),
+            )
+        },
+        None => check(
+            name,
+            DiagnosticSeverity::Critical,
+            format!("Probe timed out after {}s", PROBE_TIMEOUT.as_secs()),
+            Some(action),
+        ),
+    }
+}
+
+/// Run `probe` with `PROBE_TIMEOUT`, returning its output and latency
+async fn timed<T>(probe: impl Future<Output = T>) -> Option<(T, Duration)> {
+    let started = Instant::now();
+    tokio::time::timeout(PROBE_TIMEOUT, probe)
+        .await
+        .ok()
+        .map(|output| (output, started.elapsed()))
+}
+
+fn check(
+    name: &str,
+    severity: DiagnosticSeverity,
+    summary: String,
+    action: Option<&str>,
+) -> DiagnosticCheck {
+    DiagnosticCheck {
+        name: name.to_string(),
+        severity,
+        summary,
+        latency_ms: None,
+        action: action.map(ToString::to_string),
+    }
+}
+
+fn overall_severity(checks: &[DiagnosticCheck]) -> DiagnosticSeverity {
+    checks
+        .iter()
+        .map(|check| check.severity)
+        .max()
+        .unwrap_or(DiagnosticSeverity::Ok)
+}
+
+fn stream_lag_severity(age: Duration) -> DiagnosticSeverity {
+    if age > STREAM_LAG_CRITICAL {
+        DiagnosticSeverity::Critical
+    } else if age > STREAM_LAG_WARNING {
+        DiagnosticSeverity::Warning
+    } else {
+        DiagnosticSeverity::Ok
+    }
+}
+
+fn disk_severity(free_ratio: f64) -> DiagnosticSeverity {
+    if free_ratio < DISK_FREE_CRITICAL {
+        DiagnosticSeverity::Critical
+    } else if free_ratio < DISK_FREE_WARNING {
+        DiagnosticSeverity::Warning
+    } else {
+        DiagnosticSeverity::Ok
+    }
+}
+
+fn task_severity(state: &TaskState) -> DiagnosticSeverity {
+    match state {
+        TaskState::Running | TaskState::Completed => DiagnosticSeverity::Ok,
+        TaskState::Failed(_) | TaskState::Restarting(_) => DiagnosticSeverity::Warning,
+        TaskState::Panicked(_) => DiagnosticSeverity::Critical,
+    }
+}
+
+fn error_count_severity(count: usize) -> DiagnosticSeverity {
+    if count >= ERROR_COUNT_CRITICAL {
+        DiagnosticSeverity::Critical
+    } else if count >= ERROR_COUNT_WARNING {
+        DiagnosticSeverity::Warning
+    } else {
+        DiagnosticSeverity::Ok
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Filesystem capacity for a path
+struct DiskSpace {
+    total_bytes: u64,
+    available_bytes: u64,
+}
+
+impl DiskSpace {
+    #[allow(clippy::cast_precision_loss)] // ratio for display and thresholds only
+    fn free_ratio(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.available_bytes as f64 / self.total_bytes as f64
+        }
+    }
+}
+
+#[cfg(unix)]
+fn disk_space(path: &Path) -> Option<DiskSpace> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stats` points to writable
+    // memory of the right type; it is only read after statvfs reports success.
+    let stats = unsafe {
+        if libc::statvfs(c_path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+
+    #[allow(clippy::useless_conversion)] // field widths differ between platforms
+    let fragment_size = u64::from(stats.f_frsize);
+    #[allow(clippy::useless_conversion)]
+    let (blocks, available) = (u64::from(stats.f_blocks), u64::from(stats.f_bavail));
+    Some(DiskSpace {
+        total_bytes: blocks.saturating_mul(fragment_size),
+        available_bytes: available.saturating_mul(fragment_size),
+    })
+}
+
+#[cfg(not(unix))]
+fn disk_space(_path: &Path) -> Option<DiskSpace> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_severities() {
+        assert_eq!(
+            stream_lag_severity(Duration::from_secs(5)),
+            DiagnosticSeverity::Ok
+        );
+        assert_eq!(
+            stream_lag_severity(Duration::from_mins(2)),
+            DiagnosticSeverity::Warning
+        );
+        assert_eq!(
+            stream_lag_severity(Duration::from_mins(10)),
+            DiagnosticSeverity::Critical
+        );
+        assert_eq!(disk_severity(0.5), DiagnosticSeverity::Ok);
+        assert_eq!(disk_severity(0.08), DiagnosticSeverity::Warning);
+        assert_eq!(disk_severity(0.01), DiagnosticSeverity::Critical);
+        assert_eq!(error_count_severity(0), DiagnosticSeverity::Ok);
+        assert_eq!(error_count_severity(5), DiagnosticSeverity::Warning);
+        assert_eq!(error_count_severity(100), DiagnosticSeverity::Critical);
+    }
+
+    #[test]
+    fn test_latency_check_outcomes() {
+        let fast = latency_check(
+            "db",
+            Some((Ok::<(), String>(()), Duration::from_millis(3))),
+            DB_SLOW_LATENCY,
+            "act",
+        );
+        assert_eq!(fast.severity, DiagnosticSeverity::Ok);
+        assert_eq!(fast.latency_ms, Some(3));
+        assert!(fast.action.is_none());
+
+        let slow = latency_check(
+            "db",
+            Some((Ok::<(), String>(()), Duration::from_millis(250))),
+            DB_SLOW_LATENCY,
+            "act",
+        );
+        assert_eq!(slow.severity, DiagnosticSeverity::Warning);
+        assert_eq!(slow.action.as_deref(), Some("act"));
+
+        let timed_out = latency_check::<(), String>("db", None, DB_SLOW_LATENCY, "act");
+        assert_eq!(timed_out.severity, DiagnosticSeverity::Critical);
+    }
+
+    #[test]
+    fn test_template_and_disk_checks() {
+        assert_eq!(template_check(&[]).severity, DiagnosticSeverity::Ok);
+        assert_eq!(
+            template_check(&["home.html: unexpected tag".to_string()]).severity,
+            DiagnosticSeverity::Critical
+        );
+        assert_eq!(
+            disk_check("definitely/missing/dir").severity,
+            DiagnosticSeverity::Critical
+        );
+        assert!(disk_space(Path::new(".")).is_none_or(|space| space.total_bytes > 0));
+    }
+}
@@ -0,0 +1,223 @@
+//! Access Log
+//!
+//! One `access_log` event per request with method, path, status, response bytes,
+//! latency and the class of the user agent — a text line, or a JSON object with
+//! `LOG_FORMAT=json`. Runs inside the `request` span, so JSON lines also carry the
+//! request id and matched route. `ACCESS_LOG=false` turns it off, and paths under
+//! the `ACCESS_LOG_EXCLUDE` prefixes (probes, metrics and static assets by default)
+//! are skipped. Events use the `access_log` target, so `RUST_LOG=warn,access_log=info`
+//! keeps them while quieting everything else.
+
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+/// Prefixes skipped unless `ACCESS_LOG_EXCLUDE` says otherwise
+const DEFAULT_EXCLUDED_PREFIXES: &[&str] = &[
+    "/health",
+    "/metrics",
+    "/shared_assets",
+    "/shared_components",
+    "/crypto_dashboard",
+    "/stock_dashboard",
+    "/robots.txt",
+];
+
+/// Access log settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessLogConfig {
+    pub enabled: bool,
+    /// Path prefixes that are not logged
+    pub excluded_prefixes: Vec<String>,
+}
+
+impl Default for AccessLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            excluded_prefixes: DEFAULT_EXCLUDED_PREFIXES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl AccessLogConfig {
+    /// `ACCESS_LOG` (default on) and `ACCESS_LOG_EXCLUDE` (comma-separated prefixes;
+    /// empty logs every path)
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("ACCESS_LOG").map_or(defaults.enabled, |v| {
+                !matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "0" | "false" | "off"
+                )
+            }),
+            excluded_prefixes: std::env::var("ACCESS_LOG_EXCLUDE").map_or(
+                defaults.excluded_prefixes,
+                |v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|prefix| !prefix.is_empty())
+                        .map(ToString::to_string)
+                        .collect()
+                },
+            ),
+        }
+    }
+
+    /// Process-wide config, read from the environment on first use
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static CONFIG: OnceLock<AccessLogConfig> = OnceLock::new();
+        CONFIG.get_or_init(Self::from_env)
+    }
+
+    /// Whether `path` is one of the prefixes or below one (`/health` covers
+    /// `/health/ready` but not `/healthz`)
+    #[must_use]
+    pub fn is_excluded(&self, path: &str) -> bool {
+        self.excluded_prefixes.iter().any(|prefix| {
+            path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+            })
+        })
+    }
+}
+
+/// Coarse kind of client, from the `User-Agent` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserAgentClass {
+    Bot,
+    Mobile,
+    Browser,
+    /// curl, wget, HTTP libraries
+    Tool,
+    Unknown,
+}
+
+impl UserAgentClass {
+    #[must_use]
+    pub fn classify(user_agent: Option<&str>) -> Self {
+        let Some(user_agent) = user_agent.filter(|ua| !ua.trim().is_empty()) else {
+            return Self::Unknown;
+        };
+        let ua = user_agent.to_ascii_lowercase();
+        let contains_any = |needles: &[&str]| needles.iter().any(|needle| ua.contains(needle));
+        if contains_any(&["bot", "crawler", "spider", "slurp", "facebookexternalhit"]) {
+            Self::Bot
+        } else if contains_any(&[
+            "curl/",
+            "wget/",
+            "python-requests",
+            "python-urllib",
+            "go-http-client",
+            "reqwest",
+            "okhttp",
+            "httpie",
+            "postman",
+        ]) {
+            Self::Tool
+        } else if contains_any(&["mobile", "android", "iphone", "ipad"]) {
+            Self::Mobile
+        } else if ua.starts_with("mozilla/") {
+            Self::Browser
+        } else {
+            Self::Unknown
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bot => "bot",
+            Self::Mobile => "mobile",
+            Self::Browser => "browser",
+            Self::Tool => "tool",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// Middleware writing the access log line of every request not excluded
+pub async fn access_log(request: Request, next: Next) -> Response {
+    let config = AccessLogConfig::current();
+    if !config.enabled || config.is_excluded(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let user_agent = UserAgentClass::classify(
+        request
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok()),
+    );
+    let started = Instant::now();
+    let response = next.run(request).await;
+
+    let status = response.status().as_u16();
+    let latency_ms = (started.elapsed().as_secs_f64() * 100_000.0).round() / 100.0;
+    // Streamed bodies have no length up front
+    let bytes = response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    tracing::info!(
+        target: "access_log",
+        method = %method,
+        path = %path,
+        status,
+        bytes,
+        latency_ms,
+        user_agent = user_agent.as_str(),
+        "{} {} {} {} {:.2}ms {}",
+        method,
+        path,
+        status,
+        bytes.map_or_else(|| "-".to_string(), |b| b.to_string()),
+        latency_ms,
+        user_agent.as_str()
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excluded_paths() {
+        let config = AccessLogConfig::default();
+        assert!(config.is_excluded("/health"));
+        assert!(config.is_excluded("/health/ready"));
+        assert!(config.is_excluded("/shared_assets/css/app.css"));
+        assert!(!config.is_excluded("/healthz"));
+        assert!(!config.is_excluded("/crypto_report/42"));
+        assert!(!config.is_excluded("/"));
+    }
+
+    #[test]
+    fn test_user_agent_classes() {
+        let classify = |ua| UserAgentClass::classify(Some(ua));
+        assert_eq!(
+            classify("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"),
+            UserAgentClass::Bot
+        );
+        assert_eq!(
+            classify("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) Safari/604.1"),
+            UserAgentClass::Mobile
+        );
+        assert_eq!(
+            classify("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0"),
+            UserAgentClass::Browser
+        );
+        assert_eq!(classify("curl/8.5.0"), UserAgentClass::Tool);
+        assert_eq!(UserAgentClass::classify(None), UserAgentClass::Unknown);
+    }
+}
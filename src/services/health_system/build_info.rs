@@ -0,0 +1,52 @@
+//! Build Information
+//!
+//! Crate version, git commit, build time and compiler version captured by
+//! `build.rs`, so deploy tooling can check which build is serving traffic.
+
+use crate::dto::responses::VersionResponse;
+use std::time::Duration;
+
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_COMMIT: &str = env!("BUILD_GIT_COMMIT");
+pub const RUSTC_VERSION: &str = env!("BUILD_RUSTC_VERSION");
+/// Unix seconds when the build script last ran
+const BUILD_TIMESTAMP: &str = env!("BUILD_TIMESTAMP");
+
+/// Build time as RFC 3339, `None` when the build script could not record it
+#[must_use]
+pub fn build_time() -> Option<String> {
+    BUILD_TIMESTAMP
+        .parse::<i64>()
+        .ok()
+        .filter(|&secs| secs > 0)
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|at| at.to_rfc3339())
+}
+
+/// Version details of this build plus the process uptime
+#[must_use]
+pub fn version_info(uptime: Duration) -> VersionResponse {
+    VersionResponse {
+        name: env!("CARGO_PKG_NAME"),
+        version: CRATE_VERSION,
+        git_commit: GIT_COMMIT,
+        build_time: build_time(),
+        rustc_version: RUSTC_VERSION,
+        uptime_secs: uptime.as_secs(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_metadata_is_recorded() {
+        let info = version_info(Duration::from_secs(42));
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(info.rustc_version.starts_with("rustc "));
+        assert!(!info.git_commit.is_empty());
+        assert!(info.build_time.is_some());
+        assert_eq!(info.uptime_secs, 42);
+    }
+}
@@ -0,0 +1,156 @@
+//! Database Pool Monitor
+//!
+//! `SELECT 1` succeeds even while every pooled connection is busy and requests
+//! queue for one, so pool exhaustion needs its own signals: a background sampler
+//! times how long acquiring a connection takes, and acquire timeouts are counted
+//! both from the sampler and from real queries (`sqlx::Error::PoolTimedOut`).
+
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use super::metrics::{Histogram, MetricsWriter};
+use crate::dto::responses::DbPoolStats;
+use crate::tasks::TaskRegistry;
+
+/// Default interval between acquire samples
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Acquire wait times and timeouts of the database pool
+#[derive(Debug, Default)]
+pub struct PoolMonitor {
+    acquire_wait: Histogram,
+    last_wait_micros: AtomicU64,
+    timeouts: AtomicU64,
+}
+
+impl PoolMonitor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time one `acquire()`, returning the connection to the pool right away
+    pub async fn sample(&self, pool: &PgPool) {
+        let started = Instant::now();
+        let result = pool.acquire().await;
+        let waited = started.elapsed();
+        match result {
+            Ok(_connection) => {
+                self.acquire_wait.observe(waited);
+                self.last_wait_micros.store(
+                    u64::try_from(waited.as_micros()).unwrap_or(u64::MAX),
+                    Ordering::Relaxed,
+                );
+            }
+            Err(e) => {
+                self.record_error(&e);
+                warn!("⚠️ Database pool sample failed after {:?}: {}", waited, e);
+            }
+        }
+    }
+
+    /// Count a query error that was caused by waiting too long for a connection
+    pub fn record_error(&self, error: &sqlx::Error) {
+        if matches!(error, sqlx::Error::PoolTimedOut) {
+            self.timeouts.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[must_use]
+    pub fn timeouts(&self) -> u64 {
+        self.timeouts.load(Ordering::Relaxed)
+    }
+
+    /// Sample every `DB_POOL_SAMPLE_SECS` (default 15s), supervised as `db_pool_sampler`
+    pub fn spawn_sampler(self: &Arc<Self>, pool: PgPool, tasks: &TaskRegistry) {
+        let interval = std::env::var("DB_POOL_SAMPLE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs: &u64| secs > 0)
+            .map_or(DEFAULT_SAMPLE_INTERVAL, Duration::from_secs);
+        let monitor = Arc::clone(self);
+        tasks.supervise("db_pool_sampler", move || {
+            let (monitor, pool) = (Arc::clone(&monitor), pool.clone());
+            async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    monitor.sample(&pool).await;
+                }
+            }
+        });
+    }
+
+    /// Current pool usage plus the sampled acquire waits
+    #[must_use]
+    pub fn stats(&self, pool: &PgPool) -> DbPoolStats {
+        let size = pool.size();
+        let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX);
+        DbPoolStats {
+            size,
+            idle,
+            in_use: size.saturating_sub(idle),
+            max_connections: pool.options().get_max_connections(),
+            last_acquire_ms: micros_to_ms(self.last_wait_micros.load(Ordering::Relaxed)),
+            acquire_p95_ms: self
+                .acquire_wait
+                .quantile(0.95)
+                .map(|secs| (secs * 100_000.0).round() / 100.0),
+            acquire_timeouts: self.timeouts(),
+        }
+    }
+
+    /// Write pool usage, acquire wait histogram and timeouts
+    pub fn write_metrics(&self, writer: &mut MetricsWriter, pool: &PgPool) {
+        let stats = self.stats(pool);
+        writer.header(
+            "db_pool_connections",
+            "gauge",
+            "Open database connections by state",
+        );
+        writer.sample("db_pool_connections", &[("state", "idle")], stats.idle);
+        writer.sample("db_pool_connections", &[("state", "in_use")], stats.in_use);
+        writer.gauge(
+            "db_pool_max_connections",
+            "Configured database pool size",
+            stats.max_connections,
+        );
+        writer.header(
+            "db_pool_acquire_seconds",
+            "histogram",
+            "Sampled time to acquire a pooled database connection",
+        );
+        writer.histogram("db_pool_acquire_seconds", &[], &self.acquire_wait);
+        writer.header(
+            "db_pool_acquire_timeouts_total",
+            "counter",
+            "Connection acquires that hit the pool's acquire timeout",
+        );
+        writer.sample(
+            "db_pool_acquire_timeouts_total",
+            &[],
+            stats.acquire_timeouts,
+        );
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn micros_to_ms(micros: u64) -> f64 {
+    (micros as f64 / 10.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_pool_timeouts_are_counted() {
+        let monitor = PoolMonitor::new();
+        monitor.record_error(&sqlx::Error::PoolTimedOut);
+        monitor.record_error(&sqlx::Error::RowNotFound);
+        assert_eq!(monitor.timeouts(), 1);
+        assert!((micros_to_ms(1_234) - 1.23).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,313 @@
+//! Per-Route Error Rates
+//!
+//! Counts requests and 5xx responses per route in a sliding window of fixed time
+//! slots. A route whose error rate exceeds `ERROR_RATE_THRESHOLD` (with at least
+//! `ERROR_RATE_MIN_REQUESTS` requests in the window) raises an alert: a structured
+//! `error_rate_alert` log event, and readiness turns `degraded` until it recovers.
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Number of slots the window is split into
+pub(super) const WINDOW_SLOTS: usize = 10;
+const DEFAULT_WINDOW: Duration = Duration::from_mins(5);
+const DEFAULT_THRESHOLD: f64 = 0.05;
+const DEFAULT_MIN_REQUESTS: u64 = 20;
+
+/// Alerting limits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorRateConfig {
+    pub window: Duration,
+    /// Share of 5xx responses (0-1) above which a route alerts
+    pub threshold: f64,
+    /// Requests needed in the window before a route can alert
+    pub min_requests: u64,
+}
+
+impl Default for ErrorRateConfig {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            threshold: DEFAULT_THRESHOLD,
+            min_requests: DEFAULT_MIN_REQUESTS,
+        }
+    }
+}
+
+impl ErrorRateConfig {
+    /// `ERROR_RATE_WINDOW_SECS`, `ERROR_RATE_THRESHOLD` and `ERROR_RATE_MIN_REQUESTS`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            window: env_parse::<u64>("ERROR_RATE_WINDOW_SECS")
+                .filter(|&secs| secs >= WINDOW_SLOTS as u64)
+                .map_or(defaults.window, Duration::from_secs),
+            threshold: env_parse::<f64>("ERROR_RATE_THRESHOLD")
+                .filter(|threshold| (0.0..=1.0).contains(threshold))
+                .unwrap_or(defaults.threshold),
+            min_requests: env_parse("ERROR_RATE_MIN_REQUESTS").unwrap_or(defaults.min_requests),
+        }
+    }
+}
+
+pub(super) fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|v| v.trim().parse().ok())
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Slot {
+    /// Slot number since the tracker started; a stale number means the slot is reused
+    epoch: u64,
+    total: u64,
+    bad: u64,
+}
+
+/// Event and bad-event counts over the last `WINDOW_SLOTS` slots; also used by the
+/// SLO tracker
+#[derive(Debug, Default)]
+pub(super) struct SlotWindow {
+    slots: [Slot; WINDOW_SLOTS],
+}
+
+impl SlotWindow {
+    pub(super) fn record(&mut self, epoch: u64, is_bad: bool) {
+        let Some(slot) = self.slots.get_mut(slot_index(epoch)) else {
+            return;
+        };
+        if slot.epoch != epoch {
+            *slot = Slot {
+                epoch,
+                ..Slot::default()
+            };
+        }
+        slot.total += 1;
+        slot.bad += u64::from(is_bad);
+    }
+
+    /// `(total, bad)` within the window ending at `epoch`
+    pub(super) fn totals(&self, epoch: u64) -> (u64, u64) {
+        self.slots
+            .iter()
+            .filter(|slot| slot.total > 0 && epoch.saturating_sub(slot.epoch) < WINDOW_SLOTS as u64)
+            .fold((0, 0), |(total, bad), slot| {
+                (total + slot.total, bad + slot.bad)
+            })
+    }
+}
+
+/// Slot number of `since_start` for a window of `window` length
+pub(super) fn window_epoch(window: Duration, since_start: Duration) -> u64 {
+    let slot_millis = (window / u32::try_from(WINDOW_SLOTS).unwrap_or(1))
+        .as_millis()
+        .max(1);
+    u64::try_from(since_start.as_millis() / slot_millis).unwrap_or(u64::MAX)
+}
+
+#[derive(Debug, Default)]
+struct RouteWindow {
+    counts: SlotWindow,
+    alerting: bool,
+}
+
+fn slot_index(epoch: u64) -> usize {
+    usize::try_from(epoch % WINDOW_SLOTS as u64).unwrap_or(0)
+}
+
+/// Error rate of one route over the window
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteErrorRate {
+    pub route: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+    pub alerting: bool,
+}
+
+/// Sliding-window 5xx rates per route
+#[derive(Debug)]
+pub struct ErrorRateTracker {
+    config: ErrorRateConfig,
+    started: Instant,
+    routes: DashMap<String, Mutex<RouteWindow>>,
+}
+
+impl ErrorRateTracker {
+    #[must_use]
+    pub fn new(config: ErrorRateConfig) -> Self {
+        Self {
+            config,
+            started: Instant::now(),
+            routes: DashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &ErrorRateConfig {
+        &self.config
+    }
+
+    /// Count one response to `route`, alerting when its error rate crosses the threshold
+    pub fn record(&self, route: &str, status: u16) {
+        self.record_at(route, status, self.started.elapsed());
+    }
+
+    fn record_at(&self, route: &str, status: u16, since_start: Duration) {
+        let epoch = self.epoch(since_start);
+        // Avoid allocating the key for routes already tracked (the common case)
+        if !self.routes.contains_key(route) {
+            self.routes.entry(route.to_string()).or_default();
+        }
+        let Some(window) = self.routes.get(route) else {
+            return;
+        };
+        let mut window = window.lock();
+        window.counts.record(epoch, status >= 500);
+
+        let (requests, errors) = window.counts.totals(epoch);
+        let rate = error_rate(requests, errors);
+        let over = self.is_over(requests, rate);
+        if over && !window.alerting {
+            warn!(
+                alert = "error_rate_alert",
+                route,
+                requests,
+                errors,
+                error_rate = rate,
+                threshold = self.config.threshold,
+                window_secs = self.config.window.as_secs(),
+                "🚨 Error rate of {} is {:.1}% over the last {}s",
+                route,
+                rate * 100.0,
+                self.config.window.as_secs()
+            );
+        } else if !over && window.alerting {
+            info!(
+                alert = "error_rate_recovered",
+                route,
+                requests,
+                errors,
+                error_rate = rate,
+                "✅ Error rate of {} back to {:.1}%",
+                route,
+                rate * 100.0
+            );
+        }
+        window.alerting = over;
+    }
+
+    /// Per-route error rates over the window, alerting routes first
+    #[must_use]
+    pub fn rates(&self) -> Vec<RouteErrorRate> {
+        self.rates_at(self.started.elapsed())
+    }
+
+    fn rates_at(&self, since_start: Duration) -> Vec<RouteErrorRate> {
+        let epoch = self.epoch(since_start);
+        let mut rates: Vec<RouteErrorRate> = self
+            .routes
+            .iter()
+            .filter_map(|entry| {
+                let window = entry.value().lock();
+                let (requests, errors) = window.counts.totals(epoch);
+                (requests > 0).then(|| RouteErrorRate {
+                    route: entry.key().clone(),
+                    requests,
+                    errors,
+                    error_rate: error_rate(requests, errors),
+                    // Recomputed: a route that stopped failing and getting traffic recovers
+                    alerting: self.is_over(requests, error_rate(requests, errors)),
+                })
+            })
+            .collect();
+        rates.sort_by(|a, b| {
+            b.alerting
+                .cmp(&a.alerting)
+                .then_with(|| b.error_rate.total_cmp(&a.error_rate))
+                .then_with(|| a.route.cmp(&b.route))
+        });
+        rates
+    }
+
+    /// Routes currently over the threshold
+    #[must_use]
+    pub fn alerting_routes(&self) -> Vec<RouteErrorRate> {
+        self.rates()
+            .into_iter()
+            .filter(|rate| rate.alerting)
+            .collect()
+    }
+
+    fn is_over(&self, requests: u64, rate: f64) -> bool {
+        requests >= self.config.min_requests && rate > self.config.threshold
+    }
+
+    fn epoch(&self, since_start: Duration) -> u64 {
+        window_epoch(self.config.window, since_start)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn error_rate(requests: u64, errors: u64) -> f64 {
+    if requests == 0 {
+        0.0
+    } else {
+        errors as f64 / requests as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> ErrorRateTracker {
+        ErrorRateTracker::new(ErrorRateConfig {
+            window: Duration::from_secs(100),
+            threshold: 0.1,
+            min_requests: 10,
+        })
+    }
+
+    #[test]
+    fn test_alerts_only_above_threshold_with_enough_traffic() {
+        let tracker = tracker();
+        let now = Duration::from_secs(5);
+        // 2 errors in 5 requests: above 10% but below the minimum volume
+        for status in [500, 500, 200, 200, 200] {
+            tracker.record_at("/api/x", status, now);
+        }
+        assert!(tracker.rates_at(now).iter().all(|rate| !rate.alerting));
+
+        for _ in 0..5 {
+            tracker.record_at("/api/x", 200, now);
+        }
+        let rates = tracker.rates_at(now);
+        assert_eq!(
+            rates
+                .iter()
+                .map(|r| (r.requests, r.errors, r.alerting))
+                .collect::<Vec<_>>(),
+            vec![(10, 2, true)]
+        );
+    }
+
+    #[test]
+    fn test_old_slots_leave_the_window() {
+        let tracker = tracker();
+        for _ in 0..10 {
+            tracker.record_at("/", 503, Duration::from_secs(1));
+        }
+        // 10s slots: everything recorded at 1s is outside the window at 105s
+        tracker.record_at("/", 200, Duration::from_secs(105));
+        let rates = tracker.rates_at(Duration::from_secs(105));
+        assert_eq!(
+            rates
+                .iter()
+                .map(|r| (r.requests, r.errors, r.alerting))
+                .collect::<Vec<_>>(),
+            vec![(1, 0, false)]
+        );
+    }
+}
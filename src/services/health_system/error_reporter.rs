@@ -0,0 +1,136 @@
+//! Error Reporting
+//!
+//! Server errors (`Layer5Error`s answered with a 5xx) and panics are handed to the
+//! installed `ErrorReporter`, so they are aggregated by an external service instead
+//! of living only in the logs. Without the `sentry` feature, or without a
+//! `SENTRY_DSN`, no reporter is installed and reporting is a no-op.
+
+use std::panic::PanicHookInfo;
+use std::sync::OnceLock;
+use tracing::error;
+
+use super::request_id::current_request_id;
+
+static ERROR_REPORTER: OnceLock<Box<dyn ErrorReporter>> = OnceLock::new();
+
+/// How bad a reported error is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorLevel {
+    /// A request failed
+    Error,
+    /// A thread panicked
+    Fatal,
+}
+
+impl ErrorLevel {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Fatal => "fatal",
+        }
+    }
+}
+
+/// One error handed to the reporter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorEvent {
+    pub level: ErrorLevel,
+    /// Error kind, e.g. `database`, `template_render` or `panic`
+    pub kind: &'static str,
+    pub message: String,
+    /// `X-Request-Id` of the failed request, when reported while serving one
+    pub request_id: Option<String>,
+    /// `file:line` of a panic
+    pub location: Option<String>,
+}
+
+impl ErrorEvent {
+    /// Event for an error of `kind`, tagged with the id of the request being served
+    #[must_use]
+    pub fn new(level: ErrorLevel, kind: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            kind,
+            message: message.into(),
+            request_id: current_request_id().map(|id| id.to_string()),
+            location: None,
+        }
+    }
+}
+
+/// Destination of server errors and panics
+///
+/// `report` is called on request paths and from the panic hook, so it must not
+/// block; implementations queue events and deliver them in the background.
+pub trait ErrorReporter: Send + Sync {
+    fn report(&self, event: ErrorEvent);
+}
+
+/// Install the process-wide reporter; returns `false` when one is already installed
+pub fn set_error_reporter(reporter: Box<dyn ErrorReporter>) -> bool {
+    ERROR_REPORTER.set(reporter).is_ok()
+}
+
+/// Hand `event` to the installed reporter, if any
+pub fn report_error(event: ErrorEvent) {
+    if let Some(reporter) = ERROR_REPORTER.get() {
+        reporter.report(event);
+    }
+}
+
+/// Install the reporter configured by the environment and the panic hook
+///
+/// Must run inside the Tokio runtime: reporters deliver from a background task.
+pub fn init_error_reporting() {
+    #[cfg(feature = "sentry")]
+    if let Some(reporter) = super::sentry::SentryReporter::from_env() {
+        set_error_reporter(Box::new(reporter));
+    }
+    install_panic_hook();
+}
+
+/// Report panics, then run the previous hook (which prints the panic message)
+fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let event = panic_event(info);
+        error!(
+            kind = "panic",
+            location = event.location.as_deref().unwrap_or("unknown"),
+            "💥 Panic: {}",
+            event.message
+        );
+        report_error(event);
+        previous(info);
+    }));
+}
+
+fn panic_event(info: &PanicHookInfo<'_>) -> ErrorEvent {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic with a non-string payload".to_string());
+    ErrorEvent {
+        location: info
+            .location()
+            .map(|location| format!("{}:{}", location.file(), location.line())),
+        ..ErrorEvent::new(ErrorLevel::Fatal, "panic", message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_outside_a_request() {
+        let event = ErrorEvent::new(ErrorLevel::Fatal, "panic", "boom");
+        assert_eq!(event.level.as_str(), "fatal");
+        assert_eq!(event.message, "boom");
+        assert_eq!(event.request_id, None);
+        assert_eq!(event.location, None);
+    }
+}
@@ -0,0 +1,170 @@
+//! Heartbeat Push
+//!
+//! When `HEARTBEAT_URL` is set, POSTs a small JSON heartbeat (version, uptime and
+//! the health report summary) there every `HEARTBEAT_INTERVAL_SECS` (default 60s).
+//! Push monitors such as healthchecks.io alert when heartbeats stop arriving, which
+//! catches a dead server even when nothing can reach it to poll `/health`.
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::dto::common::HealthStatus;
+use crate::dto::responses::HealthReport;
+use crate::state::AppState;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_mins(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where and how often to send heartbeats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeartbeatConfig {
+    pub url: String,
+    pub interval: Duration,
+}
+
+impl HeartbeatConfig {
+    /// `HEARTBEAT_URL` and `HEARTBEAT_INTERVAL_SECS`; `None` when no URL is set
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("HEARTBEAT_URL")
+            .ok()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())?;
+        let interval = std::env::var("HEARTBEAT_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs: &u64| secs > 0)
+            .map_or(DEFAULT_INTERVAL, Duration::from_secs);
+        Some(Self { url, interval })
+    }
+}
+
+/// Body of one heartbeat
+#[derive(Debug, Serialize)]
+struct Heartbeat<'a> {
+    service: &'static str,
+    version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance: Option<&'a str>,
+    uptime_secs: u64,
+    status: HealthStatus,
+    /// Names of the islands that failed their check
+    unhealthy_islands: Vec<&'a str>,
+}
+
+fn heartbeat<'a>(
+    report: &'a HealthReport,
+    uptime: Duration,
+    instance: Option<&'a str>,
+) -> Heartbeat<'a> {
+    Heartbeat {
+        service: env!("CARGO_PKG_NAME"),
+        version: env!("CARGO_PKG_VERSION"),
+        instance,
+        uptime_secs: uptime.as_secs(),
+        status: report.status.clone(),
+        unhealthy_islands: report
+            .islands
+            .iter()
+            .filter(|island| !matches!(island.status, HealthStatus::Healthy))
+            .map(|island| island.name.as_str())
+            .collect(),
+    }
+}
+
+/// Start the `heartbeat` task when `HEARTBEAT_URL` is configured
+pub fn spawn_heartbeat(state: &Arc<AppState>) {
+    let Some(config) = HeartbeatConfig::from_env() else {
+        debug!("⏭️ Heartbeat push disabled (HEARTBEAT_URL not set)");
+        return;
+    };
+    info!(
+        "💓 Sending heartbeats to {} every {}s",
+        config.url,
+        config.interval.as_secs()
+    );
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let instance = std::env::var("HOSTNAME").ok();
+    let task_state = Arc::clone(state);
+    state.tasks.supervise("heartbeat", move || {
+        let (state, client, config, instance) = (
+            Arc::clone(&task_state),
+            client.clone(),
+            config.clone(),
+            instance.clone(),
+        );
+        async move {
+            let mut ticker = tokio::time::interval(config.interval);
+            loop {
+                ticker.tick().await;
+                let report = state.health_check().await;
+                let body = heartbeat(&report, state.health.metrics.uptime(), instance.as_deref());
+                let result = client
+                    .post(&config.url)
+                    .json(&body)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status);
+                // A missed heartbeat is what the monitor alerts on; keep trying
+                if let Err(e) = result {
+                    warn!("⚠️ Heartbeat to {} failed: {}", config.url, e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::responses::{DbPoolStats, IslandHealth, MemoryStats};
+
+    fn island(name: &str, status: HealthStatus) -> IslandHealth {
+        IslandHealth {
+            name: name.to_string(),
+            status,
+            checked_at: String::new(),
+            duration_ms: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_heartbeat_lists_unhealthy_islands() {
+        let report = HealthReport {
+            status: HealthStatus::Unhealthy,
+            checked_at: String::new(),
+            duration_ms: 3,
+            islands: vec![
+                island("database", HealthStatus::Healthy),
+                island("cache_system", HealthStatus::Unhealthy),
+            ],
+            db_pool: DbPoolStats {
+                size: 0,
+                idle: 0,
+                in_use: 0,
+                max_connections: 0,
+                last_acquire_ms: 0.0,
+                acquire_p95_ms: None,
+                acquire_timeouts: 0,
+            },
+            memory: MemoryStats {
+                rss_bytes: None,
+                peak_rss_bytes: None,
+                rss_growth_bytes: None,
+                threads: None,
+                l1_cache_bytes: 0,
+                l1_cache_max_bytes: 0,
+            },
+        };
+        let body = heartbeat(&report, Duration::from_secs(90), Some("web-1"));
+        assert_eq!(body.unhealthy_islands, vec!["cache_system"]);
+        assert_eq!(body.uptime_secs, 90);
+        assert!(matches!(body.status, HealthStatus::Unhealthy));
+    }
+}
@@ -0,0 +1,83 @@
+//! Log Output Format
+//!
+//! `LOG_FORMAT=json` switches the tracing subscriber to one JSON object per line for
+//! log aggregation; anything else keeps the human-readable text output. In both
+//! modes events written while serving a request carry the `request` span fields
+//! (`request_id`, `method`, `path`, `route`, `status`, `latency_ms`), and events
+//! written while an island initializes or is health-checked carry an `island` span,
+//! so `RUST_LOG=island{island=cache_system}=debug` narrows output to one island.
+
+use std::sync::OnceLock;
+use tracing_subscriber::EnvFilter;
+
+/// Log output format selected at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+static LOG_FORMAT: OnceLock<LogFormat> = OnceLock::new();
+
+impl LogFormat {
+    /// Parse a `LOG_FORMAT` value; unknown values fall back to text
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        if value.trim().eq_ignore_ascii_case("json") {
+            Self::Json
+        } else {
+            Self::Text
+        }
+    }
+
+    #[must_use]
+    pub fn from_env() -> Self {
+        std::env::var("LOG_FORMAT").map_or(Self::Text, |value| Self::parse(&value))
+    }
+
+    /// Format the subscriber was initialized with (text before `init_tracing`)
+    #[must_use]
+    pub fn current() -> Self {
+        LOG_FORMAT.get().copied().unwrap_or_default()
+    }
+}
+
+/// Span for work done on behalf of one Service Island (initialization, health checks)
+#[must_use]
+pub fn island_span(island: &'static str) -> tracing::Span {
+    tracing::info_span!("island", island)
+}
+
+/// Install the global tracing subscriber
+///
+/// Filtering follows `RUST_LOG` (defaults to `info`), the format `LOG_FORMAT`.
+pub fn init_tracing() {
+    let format = LogFormat::from_env();
+    let _ = LOG_FORMAT.set(format);
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    match format {
+        LogFormat::Json => tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .init(),
+        LogFormat::Text => tracing_subscriber::fmt().with_env_filter(filter).init(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!(LogFormat::parse("json"), LogFormat::Json);
+        assert_eq!(LogFormat::parse(" JSON "), LogFormat::Json);
+        assert_eq!(LogFormat::parse("text"), LogFormat::Text);
+        assert_eq!(LogFormat::parse("pretty"), LogFormat::Text);
+    }
+}
@@ -0,0 +1,472 @@
+//! Metrics Registry
+//!
+//! Request counters and per-route latency histograms recorded by the
+//! `record_request_metrics` middleware, plus a writer for the Prometheus text
+//! exposition format. Gauges that already live elsewhere (cache statistics, pool
+//! size, stream lag) are read at scrape time instead of being copied in here.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use dashmap::DashMap;
+use std::fmt::{Display, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::state::AppState;
+
+/// Upper bounds (seconds) of the latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Cumulative latency histogram
+#[derive(Debug, Default)]
+pub struct Histogram {
+    /// Observations at or below each `LATENCY_BUCKETS` bound
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    pub fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&self.buckets) {
+            if secs <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(
+            u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Sum of all observations in seconds
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn sum_secs(&self) -> f64 {
+        self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0
+    }
+
+    /// Estimate the `q` quantile (0-1) in seconds, interpolating linearly inside the
+    /// bucket that contains it like Prometheus' `histogram_quantile`
+    ///
+    /// Observations above the last bucket are reported as its bound.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let total = self.count();
+        if total == 0 {
+            return None;
+        }
+        let rank = q.clamp(0.0, 1.0) * total as f64;
+        let mut lower_bound = 0.0;
+        let mut below = 0;
+        for (bound, cumulative) in self.buckets() {
+            if cumulative as f64 >= rank && cumulative > below {
+                let in_bucket = (cumulative - below) as f64;
+                let position = (rank - below as f64) / in_bucket;
+                return Some(lower_bound + (bound - lower_bound) * position);
+            }
+            lower_bound = bound;
+            below = cumulative;
+        }
+        Some(lower_bound)
+    }
+
+    /// `(bound, cumulative count)` per bucket, without `+Inf`
+    #[must_use]
+    pub fn buckets(&self) -> Vec<(f64, u64)> {
+        LATENCY_BUCKETS
+            .iter()
+            .zip(&self.buckets)
+            .map(|(bound, bucket)| (*bound, bucket.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+/// Latency summary of one route
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteLatency {
+    pub route: String,
+    pub count: u64,
+    pub sum_secs: f64,
+    pub p50_secs: Option<f64>,
+    pub p95_secs: Option<f64>,
+    pub p99_secs: Option<f64>,
+}
+
+/// Labels of one request counter
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct RequestKey {
+    route: String,
+    method: String,
+    status: u16,
+}
+
+/// HTTP metrics collected since startup
+#[derive(Debug)]
+pub struct MetricsRegistry {
+    started: Instant,
+    requests: DashMap<RequestKey, AtomicU64>,
+    latency: DashMap<String, Histogram>,
+    query_latency: DashMap<&'static str, Histogram>,
+    slow_queries: DashMap<&'static str, AtomicU64>,
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            requests: DashMap::new(),
+            latency: DashMap::new(),
+            query_latency: DashMap::new(),
+            slow_queries: DashMap::new(),
+        }
+    }
+
+    /// Time since the registry (and so the process) started
+    #[must_use]
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Count one request to `route` (the matched path pattern) and its latency
+    pub fn record_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let key = RequestKey {
+            route: route.to_string(),
+            method: method.to_string(),
+            status,
+        };
+        self.requests
+            .entry(key)
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+        self.latency
+            .entry(route.to_string())
+            .or_default()
+            .observe(elapsed);
+    }
+
+    /// Record one database query's latency, and count it when it was slow
+    pub fn record_query(&self, name: &'static str, elapsed: Duration, slow: bool) {
+        self.query_latency.entry(name).or_default().observe(elapsed);
+        if slow {
+            self.slow_queries
+                .entry(name)
+                .or_default()
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Write the query latency histograms and slow query counters
+    pub fn write_query_metrics(&self, writer: &mut MetricsWriter) {
+        let mut queries: Vec<&'static str> = self.query_latency.iter().map(|e| *e.key()).collect();
+        queries.sort_unstable();
+        writer.header(
+            "db_query_duration_seconds",
+            "histogram",
+            "Database query latency by query",
+        );
+        for query in &queries {
+            if let Some(histogram) = self.query_latency.get(query) {
+                writer.histogram("db_query_duration_seconds", &[("query", query)], &histogram);
+            }
+        }
+        writer.header(
+            "db_slow_queries_total",
+            "counter",
+            "Queries slower than SLOW_QUERY_THRESHOLD_MS by query",
+        );
+        for query in &queries {
+            let slow = self
+                .slow_queries
+                .get(query)
+                .map_or(0, |count| count.load(Ordering::Relaxed));
+            writer.sample("db_slow_queries_total", &[("query", query)], slow);
+        }
+    }
+
+    /// Request count and latency percentiles of every route seen, busiest first
+    #[must_use]
+    pub fn route_latencies(&self) -> Vec<RouteLatency> {
+        let mut routes: Vec<RouteLatency> = self
+            .latency
+            .iter()
+            .map(|entry| {
+                let histogram = entry.value();
+                RouteLatency {
+                    route: entry.key().clone(),
+                    count: histogram.count(),
+                    sum_secs: histogram.sum_secs(),
+                    p50_secs: histogram.quantile(0.5),
+                    p95_secs: histogram.quantile(0.95),
+                    p99_secs: histogram.quantile(0.99),
+                }
+            })
+            .collect();
+        routes.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.route.cmp(&b.route)));
+        routes
+    }
+
+    /// Write the request counters and latency histograms
+    pub fn write_http_metrics(&self, writer: &mut MetricsWriter) {
+        let mut requests: Vec<(RequestKey, u64)> = self
+            .requests
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().load(Ordering::Relaxed)))
+            .collect();
+        requests.sort();
+        writer.header(
+            "http_requests_total",
+            "counter",
+            "HTTP requests by route, method and status",
+        );
+        for (key, count) in &requests {
+            let status = key.status.to_string();
+            writer.sample(
+                "http_requests_total",
+                &[
+                    ("route", &key.route),
+                    ("method", &key.method),
+                    ("status", &status),
+                ],
+                count,
+            );
+        }
+
+        let mut routes: Vec<String> = self
+            .latency
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect();
+        routes.sort();
+        writer.header(
+            "http_request_duration_seconds",
+            "histogram",
+            "HTTP request latency by route",
+        );
+        for route in &routes {
+            if let Some(histogram) = self.latency.get(route) {
+                writer.histogram(
+                    "http_request_duration_seconds",
+                    &[("route", route)],
+                    &histogram,
+                );
+            }
+        }
+    }
+}
+
+/// Builds a Prometheus text exposition (format 0.0.4)
+#[derive(Debug, Default)]
+pub struct MetricsWriter {
+    out: String,
+}
+
+impl MetricsWriter {
+    /// `Content-Type` of the rendered text
+    pub const CONTENT_TYPE: &'static str = "text/plain; version=0.0.4; charset=utf-8";
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `# HELP` and `# TYPE` lines of a metric family
+    pub fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {name} {kind}");
+    }
+
+    /// One sample line
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (label, label_value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{label}=\"{}\"", escape_label(label_value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    /// A single unlabeled gauge with its header
+    pub fn gauge(&mut self, name: &str, help: &str, value: impl Display) {
+        self.header(name, "gauge", help);
+        self.sample(name, &[], value);
+    }
+
+    /// `_bucket`, `_sum` and `_count` samples of one histogram (header written separately)
+    pub fn histogram(&mut self, name: &str, labels: &[(&str, &str)], histogram: &Histogram) {
+        let bucket_name = format!("{name}_bucket");
+        for (bound, count) in histogram.buckets() {
+            let bound = bound.to_string();
+            let mut bucket_labels = labels.to_vec();
+            bucket_labels.push(("le", &bound));
+            self.sample(&bucket_name, &bucket_labels, count);
+        }
+        let mut inf_labels = labels.to_vec();
+        inf_labels.push(("le", "+Inf"));
+        self.sample(&bucket_name, &inf_labels, histogram.count());
+        self.sample(&format!("{name}_sum"), labels, histogram.sum_secs());
+        self.sample(&format!("{name}_count"), labels, histogram.count());
+    }
+
+    #[must_use]
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Escape `\`, `"` and newlines in a label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Middleware counting every matched request and its latency
+pub async fn record_request_metrics(
+    State(state): State<Arc<AppState>>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let route = matched_path.map_or_else(
+        || "unmatched".to_string(),
+        |matched| matched.as_str().to_string(),
+    );
+    // Runs inside the `request` span opened by `propagate_request_id`
+    tracing::Span::current().record("route", route.as_str());
+    let response = next.run(request).await;
+    let status = response.status().as_u16();
+    let elapsed = started.elapsed();
+    state
+        .health
+        .metrics
+        .record_request(&method, &route, status, elapsed);
+    state.health.error_rates.record(&route, status);
+    state.health.slos.record(&route, status, elapsed);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_metrics_exposition() {
+        let registry = MetricsRegistry::new();
+        registry.record_request("GET", "/crypto_report/{id}", 200, Duration::from_millis(30));
+        registry.record_request(
+            "GET",
+            "/crypto_report/{id}",
+            200,
+            Duration::from_millis(300),
+        );
+        registry.record_request("GET", "/health", 503, Duration::from_millis(2));
+
+        let mut writer = MetricsWriter::new();
+        registry.write_http_metrics(&mut writer);
+        let text = writer.finish();
+
+        assert!(text.contains("# TYPE http_requests_total counter"));
+        assert!(text.contains(
+            "http_requests_total{route=\"/crypto_report/{id}\",method=\"GET\",status=\"200\"} 2"
+        ));
+        assert!(
+            text.contains("http_requests_total{route=\"/health\",method=\"GET\",status=\"503\"} 1")
+        );
+        assert!(text.contains(
+            "http_request_duration_seconds_bucket{route=\"/crypto_report/{id}\",le=\"0.05\"} 1"
+        ));
+        assert!(text.contains(
+            "http_request_duration_seconds_bucket{route=\"/crypto_report/{id}\",le=\"+Inf\"} 2"
+        ));
+        assert!(text.contains("http_request_duration_seconds_count{route=\"/health\"} 1"));
+    }
+
+    #[test]
+    fn test_quantiles_interpolate_within_buckets() -> Result<(), String> {
+        let histogram = Histogram::default();
+        assert_eq!(histogram.quantile(0.5), None);
+        // 90 requests in (0.025, 0.05], 10 in (0.5, 1.0]
+        for _ in 0..90 {
+            histogram.observe(Duration::from_millis(40));
+        }
+        for _ in 0..10 {
+            histogram.observe(Duration::from_millis(700));
+        }
+
+        let p50 = histogram.quantile(0.5).ok_or("no p50")?;
+        assert!((p50 - (0.025 + 0.025 * 50.0 / 90.0)).abs() < 1e-9);
+        let p99 = histogram.quantile(0.99).ok_or("no p99")?;
+        assert!((p99 - 0.95).abs() < 1e-9);
+
+        let slow = Histogram::default();
+        slow.observe(Duration::from_secs(30));
+        assert_eq!(slow.quantile(0.99), Some(10.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_route_latencies_busiest_first() {
+        let registry = MetricsRegistry::new();
+        registry.record_request("GET", "/health", 200, Duration::from_millis(1));
+        registry.record_request("GET", "/", 200, Duration::from_millis(10));
+        registry.record_request("GET", "/", 200, Duration::from_millis(20));
+
+        let routes = registry.route_latencies();
+        let order: Vec<(&str, u64)> = routes.iter().map(|r| (r.route.as_str(), r.count)).collect();
+        assert_eq!(order, vec![("/", 2), ("/health", 1)]);
+    }
+
+    #[test]
+    fn test_query_metrics() {
+        let registry = MetricsRegistry::new();
+        registry.record_query("reports_page", Duration::from_millis(5), false);
+        registry.record_query("reports_page", Duration::from_millis(900), true);
+        registry.record_query("report_by_id", Duration::from_millis(3), false);
+
+        let mut writer = MetricsWriter::new();
+        registry.write_query_metrics(&mut writer);
+        let text = writer.finish();
+
+        assert!(text.contains("db_query_duration_seconds_count{query=\"reports_page\"} 2"));
+        assert!(text.contains("db_slow_queries_total{query=\"reports_page\"} 1"));
+        assert!(text.contains("db_slow_queries_total{query=\"report_by_id\"} 0"));
+    }
+
+    #[test]
+    fn test_label_escaping() {
+        let mut writer = MetricsWriter::new();
+        writer.sample("x", &[("path", "a\"b\\c\nd")], 1);
+        assert_eq!(writer.finish(), "x{path=\"a\\\"b\\\\c\\nd\"} 1\n");
+    }
+}
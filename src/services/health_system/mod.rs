@@ -0,0 +1,186 @@
+//! Health System Island - Layer 4: Observability
+//!
+//! Owns the metrics registry fed by the request middleware and renders the
+//! Prometheus exposition served at `/metrics`: HTTP request counts and latency
+//! histograms per route, cache hit rates, database pool usage and stream lag.
+//! Also assigns each request its `X-Request-Id` for log correlation, writes the
+//! access log, and answers
+//! the liveness / readiness probes and the per-island health report, and tracks
+//! per-route error rates that turn readiness degraded when they exceed limits.
+//! Reports process memory alongside the L1 cache weight, and tracks SLO error
+//! budgets over a rolling window. Server errors and panics go to the installed
+//! `ErrorReporter` (Sentry with the `sentry` feature), and optional heartbeats are
+//! pushed to an external monitor. The `profiling` feature adds runtime task dumps,
+//! worker utilization profiles and memory snapshots for admins.
+
+pub mod access_log;
+pub mod build_info;
+pub mod db_pool;
+pub mod error_rate;
+pub mod error_reporter;
+pub mod heartbeat;
+pub mod logging;
+pub mod metrics;
+pub mod process_stats;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+pub mod readiness;
+pub mod report;
+pub mod request_id;
+#[cfg(feature = "sentry")]
+pub mod sentry;
+pub mod slo;
+pub mod template_metrics;
+
+pub use access_log::{AccessLogConfig, UserAgentClass, access_log};
+pub use build_info::version_info;
+pub use db_pool::PoolMonitor;
+pub use error_rate::{ErrorRateConfig, ErrorRateTracker, RouteErrorRate};
+pub use error_reporter::{
+    ErrorEvent, ErrorLevel, ErrorReporter, init_error_reporting, report_error, set_error_reporter,
+};
+pub use heartbeat::{HeartbeatConfig, spawn_heartbeat};
+pub use logging::{LogFormat, init_tracing, island_span};
+pub use metrics::{
+    Histogram, MetricsRegistry, MetricsWriter, RouteLatency, record_request_metrics,
+};
+pub use process_stats::PerformanceMonitor;
+pub use readiness::check_readiness;
+pub use request_id::{
+    REQUEST_ID_HEADER, RequestId, current_request_id, propagate_request_id, with_request_id,
+};
+pub use slo::{SLO_DEFINITIONS, SloDefinition, SloKind, SloStatus, SloTracker};
+pub use template_metrics::{TemplateMetrics, template_metrics};
+
+use std::sync::Arc;
+
+use crate::dto::responses::HealthReport;
+use crate::state::AppState;
+
+/// Health System Island
+#[derive(Debug)]
+pub struct HealthSystemIsland {
+    pub metrics: MetricsRegistry,
+    /// 5xx rates per route, alerting above `ERROR_RATE_THRESHOLD`
+    pub error_rates: ErrorRateTracker,
+    /// Database pool acquire waits and timeouts
+    pub db_pool: Arc<PoolMonitor>,
+    /// Resident memory, threads and L1 weight
+    pub performance: PerformanceMonitor,
+    /// Rolling compliance of `SLO_DEFINITIONS`
+    pub slos: SloTracker,
+}
+
+impl Default for HealthSystemIsland {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthSystemIsland {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            metrics: MetricsRegistry::new(),
+            error_rates: ErrorRateTracker::new(ErrorRateConfig::from_env()),
+            db_pool: Arc::new(PoolMonitor::new()),
+            performance: PerformanceMonitor::new(),
+            slos: SloTracker::from_env(),
+        }
+    }
+
+    /// Check every island, see `report::build_health_report`
+    pub async fn health_check(&self, state: &AppState) -> HealthReport {
+        report::build_health_report(state).await
+    }
+
+    /// Render every metric in the Prometheus text format
+    pub async fn render_metrics(&self, state: &AppState) -> String {
+        let l1_usage = state.l1_cache.usage().await;
+        let mut writer = MetricsWriter::new();
+        writer.gauge(
+            "process_uptime_seconds",
+            "Seconds since the process started",
+            self.metrics.uptime().as_secs(),
+        );
+        self.performance.write_metrics(&mut writer, &l1_usage);
+        self.metrics.write_http_metrics(&mut writer);
+        self.metrics.write_query_metrics(&mut writer);
+        template_metrics().write_metrics(&mut writer);
+        crate::services::shared::CrawlControl::current().write_metrics(&mut writer);
+        write_cache_metrics(&mut writer, state);
+        self.db_pool.write_metrics(&mut writer, &state.db);
+        write_stream_metrics(&mut writer, state);
+        writer.finish()
+    }
+}
+
+fn write_cache_metrics(writer: &mut MetricsWriter, state: &AppState) {
+    let cache_stats = state.cache_manager.get_stats();
+    writer.header(
+        "cache_requests_total",
+        "counter",
+        "Cache lookups through the multi-tier cache",
+    );
+    writer.sample("cache_requests_total", &[], cache_stats.total_requests);
+    writer.header("cache_hits_total", "counter", "Cache hits by tier");
+    writer.sample("cache_hits_total", &[("tier", "l1")], cache_stats.l1_hits);
+    writer.sample("cache_hits_total", &[("tier", "l2")], cache_stats.l2_hits);
+    writer.header(
+        "cache_misses_total",
+        "counter",
+        "Lookups missing every tier",
+    );
+    writer.sample("cache_misses_total", &[], cache_stats.misses);
+    writer.gauge(
+        "cache_hit_ratio",
+        "Share of lookups served from any tier (0-1)",
+        cache_stats.hit_rate / 100.0,
+    );
+    writer.gauge(
+        "cache_l2_available",
+        "1 while Redis L2 is reachable, 0 while degraded to L1 only",
+        u8::from(state.redis_backend.is_available()),
+    );
+}
+
+fn write_stream_metrics(writer: &mut MetricsWriter, state: &AppState) {
+    writer.header(
+        "stream_data_age_seconds",
+        "gauge",
+        "Age of the newest entry seen on each stream",
+    );
+    for reader in state.streams.iter() {
+        if let Some(age) = reader.freshness().data_age {
+            writer.sample(
+                "stream_data_age_seconds",
+                &[("stream", &reader.definition.name)],
+                age.as_secs(),
+            );
+        }
+    }
+    writer.header(
+        "stream_entries_consumed_total",
+        "counter",
+        "Entries consumed from each stream",
+    );
+    for reader in state.streams.iter() {
+        writer.sample(
+            "stream_entries_consumed_total",
+            &[("stream", &reader.definition.name)],
+            reader.freshness().entries_consumed,
+        );
+    }
+    writer.header(
+        "stream_dead_lettered_total",
+        "counter",
+        "Entries rejected to each stream's dead-letter queue",
+    );
+    for reader in state.streams.iter() {
+        writer.sample(
+            "stream_dead_lettered_total",
+            &[("stream", &reader.definition.name)],
+            reader.dead_letters().count(),
+        );
+    }
+}
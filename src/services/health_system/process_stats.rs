@@ -0,0 +1,150 @@
+//! Process Memory Stats
+//!
+//! Resident memory, its peak and growth since startup, the OS thread count and the
+//! L1 cache weight, so memory growth (e.g. from piling up `spawn_blocking` threads)
+//! can be told apart from a cache that is simply full. The process uses the system
+//! allocator, which keeps no allocation statistics, so allocated bytes are not
+//! reported. Resident memory and threads come from `/proc/self/status` and are
+//! unavailable on other platforms.
+
+use crate::dto::responses::MemoryStats;
+use crate::l1_cache::TierUsage;
+
+use super::metrics::MetricsWriter;
+
+/// Process figures read from `/proc/self/status`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ProcStatus {
+    rss_bytes: Option<u64>,
+    peak_rss_bytes: Option<u64>,
+    threads: Option<u64>,
+}
+
+/// Samples process memory for `/metrics` and `/health/details`
+#[derive(Debug)]
+pub struct PerformanceMonitor {
+    /// Resident memory when the monitor was created
+    baseline_rss_bytes: Option<u64>,
+}
+
+impl Default for PerformanceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerformanceMonitor {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            baseline_rss_bytes: read_proc_status().rss_bytes,
+        }
+    }
+
+    /// Current process memory plus the given L1 usage
+    #[must_use]
+    pub fn snapshot(&self, l1: &TierUsage) -> MemoryStats {
+        let status = read_proc_status();
+        MemoryStats {
+            rss_bytes: status.rss_bytes,
+            peak_rss_bytes: status.peak_rss_bytes,
+            rss_growth_bytes: status
+                .rss_bytes
+                .zip(self.baseline_rss_bytes)
+                .map(|(now, start)| now.cast_signed().saturating_sub(start.cast_signed())),
+            threads: status.threads,
+            l1_cache_bytes: l1.bytes,
+            l1_cache_max_bytes: l1.max_bytes,
+        }
+    }
+
+    /// Write resident memory, thread count and L1 weight
+    pub fn write_metrics(&self, writer: &mut MetricsWriter, l1: &TierUsage) {
+        let stats = self.snapshot(l1);
+        if let Some(rss) = stats.rss_bytes {
+            writer.gauge(
+                "process_resident_memory_bytes",
+                "Resident memory of the process",
+                rss,
+            );
+        }
+        if let Some(peak) = stats.peak_rss_bytes {
+            writer.gauge(
+                "process_resident_memory_peak_bytes",
+                "Highest resident memory of the process since it started",
+                peak,
+            );
+        }
+        if let Some(threads) = stats.threads {
+            writer.gauge(
+                "process_threads",
+                "OS threads of the process, including blocking pool threads",
+                threads,
+            );
+        }
+        writer.gauge(
+            "cache_l1_bytes",
+            "Weight of keys and values held in the L1 cache",
+            stats.l1_cache_bytes,
+        );
+        writer.gauge(
+            "cache_l1_max_bytes",
+            "Configured L1 cache budget (CACHE_L1_MAX_MB)",
+            stats.l1_cache_max_bytes,
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_status() -> ProcStatus {
+    std::fs::read_to_string("/proc/self/status")
+        .map(|text| parse_proc_status(&text))
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_proc_status() -> ProcStatus {
+    ProcStatus::default()
+}
+
+/// Pick `VmRSS`, `VmHWM` (in kB) and `Threads` out of `/proc/<pid>/status`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_status(text: &str) -> ProcStatus {
+    let mut status = ProcStatus::default();
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let number = value
+            .split_whitespace()
+            .next()
+            .and_then(|n| n.parse::<u64>().ok());
+        match key {
+            "VmRSS" => status.rss_bytes = number.map(|kb| kb.saturating_mul(1024)),
+            "VmHWM" => status.peak_rss_bytes = number.map(|kb| kb.saturating_mul(1024)),
+            "Threads" => status.threads = number,
+            _ => {}
+        }
+    }
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_status() {
+        let text =
+            "Name:\tweb-server-report\nVmHWM:\t   20480 kB\nVmRSS:\t   10240 kB\nThreads:\t17\n";
+        assert_eq!(
+            parse_proc_status(text),
+            ProcStatus {
+                rss_bytes: Some(10_485_760),
+                peak_rss_bytes: Some(20_971_520),
+                threads: Some(17),
+            }
+        );
+        assert_eq!(parse_proc_status("garbage"), ProcStatus::default());
+    }
+}
@@ -0,0 +1,195 @@
+//! Runtime Profiling (`profiling` feature)
+//!
+//! On-demand views of the Tokio runtime and process memory for chasing latency
+//! spikes, served under `/admin/debug/`:
+//! - task dump: supervised background tasks plus runtime counters (alive tasks,
+//!   global queue depth, per-worker busy time and parks)
+//! - CPU profile: per-worker utilization and peak queue depth sampled over a few
+//!   seconds. A worker near 100% with few parks is stuck in a long poll (blocking
+//!   code on the async runtime); all workers busy with a growing queue is overload.
+//! - heap snapshot: every `kB` figure of `/proc/self/status` (RSS split into anon /
+//!   file, data segment, ...) next to the L1 cache weight
+//!
+//! The process uses the system allocator and no sampling profiler is linked in, so
+//! these are runtime-level figures, not symbolized stacks or allocation sites.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tokio::runtime::{Handle, RuntimeMetrics};
+
+use crate::dto::responses::{
+    CpuProfileResponse, HeapSnapshotResponse, RuntimeDumpResponse, TaskDumpEntry, WorkerProfile,
+    WorkerStats,
+};
+use crate::state::AppState;
+use crate::tasks::TaskState;
+
+/// Default / max duration of a CPU profile
+pub const DEFAULT_PROFILE_DURATION: Duration = Duration::from_secs(5);
+pub const MAX_PROFILE_DURATION: Duration = Duration::from_mins(1);
+/// Interval between queue depth samples during a profile
+const QUEUE_SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Background tasks and runtime counters
+#[must_use]
+pub fn runtime_dump(state: &AppState) -> RuntimeDumpResponse {
+    let metrics = Handle::current().metrics();
+    RuntimeDumpResponse {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+        worker_stats: (0..metrics.num_workers())
+            .map(|worker| WorkerStats {
+                worker,
+                busy_secs: metrics.worker_total_busy_duration(worker).as_secs_f64(),
+                park_count: metrics.worker_park_count(worker),
+            })
+            .collect(),
+        tasks: state
+            .tasks
+            .statuses()
+            .into_iter()
+            .map(|status| {
+                let (state, error) = match status.state {
+                    TaskState::Running => ("running", None),
+                    TaskState::Completed => ("completed", None),
+                    TaskState::Failed(e) => ("failed", Some(e)),
+                    TaskState::Panicked(e) => ("panicked", Some(e)),
+                    TaskState::Restarting(e) => ("restarting", Some(e)),
+                };
+                TaskDumpEntry {
+                    name: status.name.to_string(),
+                    state,
+                    error,
+                    started_at: status.started_at.to_rfc3339(),
+                    finished_at: status.finished_at.map(|at| at.to_rfc3339()),
+                    restarts: status.restarts,
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Busy time and parks of every worker at one instant
+fn worker_snapshot(metrics: &RuntimeMetrics) -> Vec<(Duration, u64)> {
+    (0..metrics.num_workers())
+        .map(|worker| {
+            (
+                metrics.worker_total_busy_duration(worker),
+                metrics.worker_park_count(worker),
+            )
+        })
+        .collect()
+}
+
+/// Sample worker utilization and queue depth for `duration`
+pub async fn cpu_profile(duration: Duration) -> CpuProfileResponse {
+    let duration = duration.min(MAX_PROFILE_DURATION);
+    let metrics = Handle::current().metrics();
+    let before = worker_snapshot(&metrics);
+    let started = Instant::now();
+
+    let mut max_queue_depth = metrics.global_queue_depth();
+    while started.elapsed() < duration {
+        tokio::time::sleep(QUEUE_SAMPLE_INTERVAL.min(duration)).await;
+        max_queue_depth = max_queue_depth.max(metrics.global_queue_depth());
+    }
+
+    let elapsed = started.elapsed();
+    let after = worker_snapshot(&metrics);
+    let workers: Vec<WorkerProfile> = before
+        .iter()
+        .zip(&after)
+        .enumerate()
+        .map(
+            |(worker, ((busy_before, parks_before), (busy_after, parks_after)))| WorkerProfile {
+                worker,
+                utilization: utilization(busy_after.saturating_sub(*busy_before), elapsed),
+                parks: parks_after.saturating_sub(*parks_before),
+            },
+        )
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    let mean_utilization = if workers.is_empty() {
+        0.0
+    } else {
+        workers.iter().map(|w| w.utilization).sum::<f64>() / workers.len() as f64
+    };
+
+    CpuProfileResponse {
+        duration_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+        mean_utilization,
+        max_global_queue_depth: max_queue_depth,
+        workers,
+    }
+}
+
+/// Share (0-1) of `elapsed` a worker spent busy
+fn utilization(busy: Duration, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.0;
+    }
+    (busy.as_secs_f64() / elapsed.as_secs_f64()).clamp(0.0, 1.0)
+}
+
+/// Process memory figures and L1 cache weight
+pub async fn heap_snapshot(state: &AppState) -> HeapSnapshotResponse {
+    let l1_usage = state.l1_cache.usage().await;
+    let proc_status_bytes = std::fs::read_to_string("/proc/self/status")
+        .map(|text| parse_kb_fields(&text))
+        .unwrap_or_default();
+    HeapSnapshotResponse {
+        memory: state.health.performance.snapshot(&l1_usage),
+        proc_status_bytes,
+        l1_entries: l1_usage.entries,
+    }
+}
+
+/// Every `Name: <n> kB` line of `/proc/<pid>/status`, in bytes
+fn parse_kb_fields(text: &str) -> BTreeMap<String, u64> {
+    text.lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let kb = value
+                .trim()
+                .strip_suffix("kB")?
+                .trim()
+                .parse::<u64>()
+                .ok()?;
+            Some((key.to_string(), kb.saturating_mul(1024)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_kb_fields() {
+        let fields =
+            parse_kb_fields("Name:\tserver\nVmRSS:\t  2048 kB\nRssAnon:\t 1024 kB\nThreads:\t9\n");
+        assert_eq!(fields.get("VmRSS"), Some(&2_097_152));
+        assert_eq!(fields.get("RssAnon"), Some(&1_048_576));
+        assert!(!fields.contains_key("Threads"));
+        assert!(!fields.contains_key("Name"));
+    }
+
+    #[test]
+    fn test_utilization_is_clamped() {
+        let second = Duration::from_secs(1);
+        assert!((utilization(Duration::from_millis(250), second) - 0.25).abs() < 1e-9);
+        assert!((utilization(Duration::from_secs(2), second) - 1.0).abs() < 1e-9);
+        assert!(utilization(second, Duration::ZERO).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_cpu_profile_covers_every_worker() {
+        let profile = cpu_profile(Duration::from_millis(20)).await;
+        assert_eq!(
+            profile.workers.len(),
+            Handle::current().metrics().num_workers()
+        );
+        assert!(profile.duration_ms >= 20);
+    }
+}
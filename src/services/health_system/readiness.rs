@@ -0,0 +1,147 @@
+//! Liveness and Readiness
+//!
+//! `/health/live` only says the process answers requests. `/health/ready` checks
+//! what a replica needs to serve correct pages — database, Redis, templates and
+//! fresh stream data — so an orchestrator stops routing traffic to a degraded
+//! replica instead of restarting it. A route over its error rate limit turns the
+//! replica `degraded`, which is also answered with 503.
+
+use std::time::{Duration, Instant};
+
+use crate::dto::common::HealthStatus;
+use crate::dto::responses::{ComponentReadiness, ReadinessResponse};
+use crate::state::AppState;
+use crate::stream::FreshnessStatus;
+
+/// Timeout of the database probe; readiness polls must answer quickly
+const DB_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Run every readiness check; ready only when all components are
+pub async fn check_readiness(state: &AppState) -> ReadinessResponse {
+    let components = vec![
+        database_readiness(state).await,
+        redis_readiness(state),
+        template_readiness(&state.template_errors),
+        stream_readiness(state),
+    ];
+    let error_rates = error_rate_readiness(state);
+    let status = if !components.iter().all(|component| component.ready) {
+        HealthStatus::Unhealthy
+    } else if error_rates.ready {
+        HealthStatus::Healthy
+    } else {
+        HealthStatus::Degraded
+    };
+    let mut components = components;
+    components.push(error_rates);
+    ReadinessResponse {
+        status,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        components,
+    }
+}
+
+async fn database_readiness(state: &AppState) -> ComponentReadiness {
+    let started = Instant::now();
+    let result =
+        tokio::time::timeout(DB_PROBE_TIMEOUT, sqlx::query("SELECT 1").execute(&state.db)).await;
+    let latency_ms = Some(u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX));
+    let (ready, detail) = match result {
+        Ok(Ok(_)) => (true, "reachable".to_string()),
+        Ok(Err(e)) => (false, format!("query failed: {e}")),
+        Err(_) => (
+            false,
+            format!("no answer within {}s", DB_PROBE_TIMEOUT.as_secs()),
+        ),
+    };
+    ComponentReadiness {
+        name: "database".to_string(),
+        ready,
+        detail,
+        latency_ms,
+    }
+}
+
+/// Redis reachability as tracked by the L2 monitor, so polls don't add Redis load
+fn redis_readiness(state: &AppState) -> ComponentReadiness {
+    let ready = state.redis_backend.is_available();
+    ComponentReadiness {
+        name: "redis".to_string(),
+        ready,
+        detail: if ready {
+            "reachable".to_string()
+        } else {
+            "unreachable, serving from L1 only".to_string()
+        },
+        latency_ms: None,
+    }
+}
+
+fn template_readiness(errors: &[String]) -> ComponentReadiness {
+    ComponentReadiness {
+        name: "templates".to_string(),
+        ready: errors.is_empty(),
+        detail: if errors.is_empty() {
+            "all templates loaded".to_string()
+        } else {
+            format!("{} template error(s)", errors.len())
+        },
+        latency_ms: None,
+    }
+}
+
+/// Every registered stream must have delivered data within its `stale_after`
+fn stream_readiness(state: &AppState) -> ComponentReadiness {
+    let not_fresh: Vec<String> = state
+        .streams
+        .iter()
+        .filter_map(|reader| {
+            let status = reader.freshness().status(reader.definition.stale_after);
+            (status != FreshnessStatus::Fresh)
+                .then(|| format!("{}: {}", reader.definition.name, status.as_str()))
+        })
+        .collect();
+    ComponentReadiness {
+        name: "streams".to_string(),
+        ready: not_fresh.is_empty(),
+        detail: if not_fresh.is_empty() {
+            "all streams fresh".to_string()
+        } else {
+            not_fresh.join(", ")
+        },
+        latency_ms: None,
+    }
+}
+
+/// No route may be over its 5xx rate limit
+fn error_rate_readiness(state: &AppState) -> ComponentReadiness {
+    let alerting = state.health.error_rates.alerting_routes();
+    let threshold = state.health.error_rates.config().threshold;
+    ComponentReadiness {
+        name: "error_rate".to_string(),
+        ready: alerting.is_empty(),
+        detail: if alerting.is_empty() {
+            format!("all routes below {:.1}% errors", threshold * 100.0)
+        } else {
+            alerting
+                .iter()
+                .map(|rate| format!("{}: {:.1}% errors", rate.route, rate.error_rate * 100.0))
+                .collect::<Vec<_>>()
+                .join(", ")
+        },
+        latency_ms: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_errors_make_replica_unready() {
+        assert!(template_readiness(&[]).ready);
+        let component = template_readiness(&["view.html: unexpected tag".to_string()]);
+        assert!(!component.ready);
+        assert_eq!(component.detail, "1 template error(s)");
+    }
+}
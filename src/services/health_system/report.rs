@@ -0,0 +1,180 @@
+//! Component Health Report
+//!
+//! Checks every island concurrently and records its status, when it was checked,
+//! how long the check took and why it failed, for `/health` and `/health/details`.
+//! Each check is bounded by `HEALTH_CHECK_TIMEOUT_MS` (default 2s), so one hanging
+//! dependency is reported as timed out instead of stalling the whole report.
+
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::{Instrument, debug, warn};
+
+use super::logging::island_span;
+use crate::dto::common::HealthStatus;
+use crate::dto::responses::{HealthReport, IslandHealth};
+use crate::state::AppState;
+
+const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest a single island check may take, from `HEALTH_CHECK_TIMEOUT_MS`
+fn check_timeout() -> Duration {
+    static TIMEOUT: OnceLock<Duration> = OnceLock::new();
+    *TIMEOUT.get_or_init(|| {
+        std::env::var("HEALTH_CHECK_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&ms: &u64| ms > 0)
+            .map_or(DEFAULT_CHECK_TIMEOUT, Duration::from_millis)
+    })
+}
+
+/// Check every island concurrently and collect the results
+pub async fn build_health_report(state: &AppState) -> HealthReport {
+    let started = Instant::now();
+    let checked_at = chrono::Utc::now().to_rfc3339();
+    let timeout = check_timeout();
+
+    let (database, cache_system, market_data_stream, crypto_reports, dashboard) = tokio::join!(
+        check_island("database", timeout, database_check(state)),
+        check_island("cache_system", timeout, cache_check(state)),
+        check_island("market_data_stream", timeout, stream_check(state)),
+        check_island("crypto_reports", timeout, async {
+            component_check(state.crypto_handlers.health_check(), "handlers not ready")
+        }),
+        check_island("dashboard", timeout, async {
+            component_check(
+                state.dashboard_handlers.health_check(),
+                "handlers not ready",
+            )
+        }),
+    );
+    let islands = vec![
+        database,
+        cache_system,
+        market_data_stream,
+        crypto_reports,
+        dashboard,
+    ];
+
+    HealthReport {
+        status: if islands
+            .iter()
+            .all(|island| matches!(island.status, HealthStatus::Healthy))
+        {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy
+        },
+        checked_at,
+        duration_ms: elapsed_ms(started),
+        islands,
+        db_pool: state.health.db_pool.stats(&state.db),
+        memory: state
+            .health
+            .performance
+            .snapshot(&state.l1_cache.usage().await),
+    }
+}
+
+/// Time one island check, failing it after `timeout`; `Err` carries the failure reason
+async fn check_island(
+    name: &'static str,
+    timeout: Duration,
+    check: impl Future<Output = Result<(), String>>,
+) -> IslandHealth {
+    let checked_at = chrono::Utc::now().to_rfc3339();
+    let started = Instant::now();
+    let span = island_span(name);
+    let result = tokio::time::timeout(timeout, check.instrument(span.clone()))
+        .await
+        .unwrap_or_else(|_| Err(format!("timed out after {}ms", timeout.as_millis())));
+    span.in_scope(|| match &result {
+        Ok(()) => debug!(duration_ms = elapsed_ms(started), "✅ Health check passed"),
+        Err(e) => warn!(
+            duration_ms = elapsed_ms(started),
+            "⚠️ Health check failed: {}", e
+        ),
+    });
+    IslandHealth {
+        name: name.to_string(),
+        status: if result.is_ok() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy
+        },
+        checked_at,
+        duration_ms: elapsed_ms(started),
+        error: result.err(),
+    }
+}
+
+async fn database_check(state: &AppState) -> Result<(), String> {
+    sqlx::query("SELECT 1")
+        .execute(&state.db)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("query failed: {e}"))
+}
+
+async fn cache_check(state: &AppState) -> Result<(), String> {
+    if !state.redis_backend.is_available() {
+        return Err("Redis L2 unreachable, serving from L1 only".to_string());
+    }
+    state
+        .cache_manager
+        .get("_health_check")
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("cache lookup failed: {e}"))
+}
+
+async fn stream_check(state: &AppState) -> Result<(), String> {
+    match state.streams.market_data().health_check().await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("stream reader cannot reach Redis".to_string()),
+        Err(e) => Err(format!("stream check failed: {e}")),
+    }
+}
+
+fn component_check(ok: bool, reason: &str) -> Result<(), String> {
+    if ok { Ok(()) } else { Err(reason.to_string()) }
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    #[tokio::test]
+    async fn test_island_records_failure_reason() {
+        let healthy = check_island("ok", TIMEOUT, async { Ok(()) }).await;
+        assert!(matches!(healthy.status, HealthStatus::Healthy));
+        assert_eq!(healthy.error, None);
+
+        let failed = check_island("db", TIMEOUT, async {
+            Err("query failed: timeout".to_string())
+        })
+        .await;
+        assert!(matches!(failed.status, HealthStatus::Unhealthy));
+        assert_eq!(failed.error.as_deref(), Some("query failed: timeout"));
+        assert_eq!(failed.name, "db");
+    }
+
+    #[tokio::test]
+    async fn test_hanging_island_times_out() {
+        let hung = check_island(
+            "cache_system",
+            Duration::from_millis(50),
+            std::future::pending(),
+        )
+        .await;
+        assert!(matches!(hung.status, HealthStatus::Unhealthy));
+        assert_eq!(hung.error.as_deref(), Some("timed out after 50ms"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Request ID Propagation
+//!
+//! Every request gets an `X-Request-Id`: the caller's (or load balancer's) value when
+//! it is well-formed, otherwise a freshly generated one. The id is recorded on the
+//! `request` tracing span wrapping the handler, so every log line written while
+//! serving the request carries it, and it is echoed in the response header and in
+//! error bodies so users can report it and operators can grep for it.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+use tracing::Instrument;
+
+use crate::services::shared::trace_context::random_hex;
+
+/// `X-Request-Id` request/response header
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest incoming id accepted before a new one is generated
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: RequestId;
+}
+
+/// Id of one request, also available as a request extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Random 32-char hex id
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(random_hex::<16>())
+    }
+
+    /// Accept a caller-supplied id if it is short and only contains `[A-Za-z0-9._:-]`,
+    /// so it can't inject anything into log lines
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let valid = !value.is_empty()
+            && value.len() <= MAX_REQUEST_ID_LENGTH
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b':' | b'-'));
+        valid.then(|| Self(value.to_string()))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Id of the request being served on this task, if any
+///
+/// Only set inside the `propagate_request_id` middleware; work moved to a spawned
+/// task does not inherit it.
+#[must_use]
+pub fn current_request_id() -> Option<RequestId> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Append the current request id to an error body, so users can quote it
+#[must_use]
+pub fn with_request_id(message: &str) -> String {
+    match current_request_id() {
+        Some(request_id) => format!("{message}\nRequest ID: {request_id}"),
+        None => message.to_string(),
+    }
+}
+
+/// Middleware assigning the request id, the `request` span and the response header
+pub async fn propagate_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(RequestId::parse)
+        .unwrap_or_else(RequestId::generate);
+    request.extensions_mut().insert(request_id.clone());
+
+    // `route` is filled in once routing matched, `status`/`latency_ms` on completion
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+        route = tracing::field::Empty,
+        status = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+    );
+    let started = Instant::now();
+    let mut response = CURRENT_REQUEST_ID
+        .scope(
+            request_id.clone(),
+            next.run(request).instrument(span.clone()),
+        )
+        .await;
+
+    let status = response.status().as_u16();
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    span.record("status", status);
+    span.record("latency_ms", latency_ms);
+
+    if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, http::StatusCode, middleware, routing::get};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/echo",
+                get(|| async {
+                    current_request_id().map_or_else(String::new, |id| id.to_string())
+                }),
+            )
+            .layer(middleware::from_fn(propagate_request_id))
+    }
+
+    #[test]
+    fn test_parse_rejects_unsafe_ids() {
+        assert_eq!(
+            RequestId::parse(" abc-123_x.y:z ").map(|id| id.to_string()),
+            Some("abc-123_x.y:z".to_string())
+        );
+        assert!(RequestId::parse("").is_none());
+        assert!(RequestId::parse("line\nbreak").is_none());
+        assert!(RequestId::parse("has space").is_none());
+        assert!(RequestId::parse(&"a".repeat(MAX_REQUEST_ID_LENGTH + 1)).is_none());
+        assert_eq!(RequestId::generate().as_str().len(), 32);
+    }
+
+    #[tokio::test]
+    async fn test_incoming_id_is_propagated() -> Result<(), Box<dyn std::error::Error>> {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/echo")
+                    .header("x-request-id", "lb-42")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(&REQUEST_ID_HEADER),
+            Some(&HeaderValue::from_static("lb-42"))
+        );
+        let body = axum::body::to_bytes(response.into_body(), 1024).await?;
+        assert_eq!(&body[..], b"lb-42");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_error_body_carries_id() {
+        assert_eq!(with_request_id("boom"), "boom");
+        let body = CURRENT_REQUEST_ID
+            .scope(RequestId("req-1".to_string()), async {
+                with_request_id("boom")
+            })
+            .await;
+        assert_eq!(body, "boom\nRequest ID: req-1");
+    }
+
+    #[tokio::test]
+    async fn test_missing_id_is_generated() -> Result<(), Box<dyn std::error::Error>> {
+        let response = app()
+            .oneshot(Request::builder().uri("/missing").body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let id = response
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .ok_or("no request id header")?;
+        assert!(RequestId::parse(id).is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+//! Service Level Objectives
+//!
+//! Each objective counts the requests to its routes as good or bad over a rolling
+//! window (`SLO_WINDOW_SECS`, default 24h): availability objectives count 5xx
+//! responses as bad, latency objectives count successful responses slower than
+//! their threshold as bad. The share of bad requests the objective tolerates is its
+//! error budget; `/admin/slo` reports how much of it is left.
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+use super::error_rate::{SlotWindow, WINDOW_SLOTS, env_parse, window_epoch};
+
+const DEFAULT_WINDOW: Duration = Duration::from_hours(24);
+
+/// What makes a request good
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SloKind {
+    /// Answered without a 5xx
+    Availability,
+    /// Answered without a 5xx within the threshold; 5xx responses are left to the
+    /// availability objectives
+    Latency(Duration),
+}
+
+/// One objective over a set of routes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SloDefinition {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Route paths as registered, e.g. `/crypto_report/{id}`
+    pub routes: &'static [&'static str],
+    pub kind: SloKind,
+    /// Share of good requests to reach (0-1)
+    pub objective: f64,
+}
+
+impl SloDefinition {
+    /// `Some(is_good)` when the request counts towards this objective
+    fn classify(&self, route: &str, status: u16, elapsed: Duration) -> Option<bool> {
+        if !self.routes.contains(&route) {
+            return None;
+        }
+        match self.kind {
+            SloKind::Availability => Some(status < 500),
+            SloKind::Latency(threshold) => (status < 500).then_some(elapsed <= threshold),
+        }
+    }
+}
+
+const REPORT_ROUTES: &[&str] = &["/crypto_report", "/crypto_report/{id}"];
+const DASHBOARD_API_ROUTES: &[&str] = &["/api/crypto/dashboard-summary", "/api/dashboard/data"];
+
+/// Objectives tracked by every instance
+pub const SLO_DEFINITIONS: &[SloDefinition] = &[
+    SloDefinition {
+        name: "report_view_latency",
+        description: "99% of report views answered within 200ms",
+        routes: REPORT_ROUTES,
+        kind: SloKind::Latency(Duration::from_millis(200)),
+        objective: 0.99,
+    },
+    SloDefinition {
+        name: "report_view_availability",
+        description: "99.9% of report views answered without a server error",
+        routes: REPORT_ROUTES,
+        kind: SloKind::Availability,
+        objective: 0.999,
+    },
+    SloDefinition {
+        name: "dashboard_api_availability",
+        description: "99.5% of dashboard API calls answered without a server error",
+        routes: DASHBOARD_API_ROUTES,
+        kind: SloKind::Availability,
+        objective: 0.995,
+    },
+];
+
+/// Compliance of one objective over the window
+#[derive(Debug, Clone, PartialEq)]
+pub struct SloStatus {
+    pub definition: SloDefinition,
+    pub total: u64,
+    pub good: u64,
+    /// Share of good requests, `None` without traffic
+    pub compliance: Option<f64>,
+    /// Share of the error budget left; negative once it is overspent
+    pub budget_remaining: f64,
+}
+
+/// Rolling good / bad counts per objective
+#[derive(Debug)]
+pub struct SloTracker {
+    window: Duration,
+    started: Instant,
+    definitions: &'static [SloDefinition],
+    counts: Vec<Mutex<SlotWindow>>,
+}
+
+impl SloTracker {
+    #[must_use]
+    pub fn new(definitions: &'static [SloDefinition], window: Duration) -> Self {
+        Self {
+            window,
+            started: Instant::now(),
+            definitions,
+            counts: definitions
+                .iter()
+                .map(|_| Mutex::new(SlotWindow::default()))
+                .collect(),
+        }
+    }
+
+    /// Track `SLO_DEFINITIONS` over `SLO_WINDOW_SECS` (default 24h)
+    #[must_use]
+    pub fn from_env() -> Self {
+        let window = env_parse::<u64>("SLO_WINDOW_SECS")
+            .filter(|&secs| secs >= WINDOW_SLOTS as u64)
+            .map_or(DEFAULT_WINDOW, Duration::from_secs);
+        Self::new(SLO_DEFINITIONS, window)
+    }
+
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Count one response towards every objective covering `route`
+    pub fn record(&self, route: &str, status: u16, elapsed: Duration) {
+        self.record_at(route, status, elapsed, self.started.elapsed());
+    }
+
+    fn record_at(&self, route: &str, status: u16, elapsed: Duration, since_start: Duration) {
+        let epoch = window_epoch(self.window, since_start);
+        for (definition, counts) in self.definitions.iter().zip(&self.counts) {
+            if let Some(good) = definition.classify(route, status, elapsed) {
+                counts.lock().record(epoch, !good);
+            }
+        }
+    }
+
+    /// Compliance and remaining error budget of every objective
+    #[must_use]
+    pub fn statuses(&self) -> Vec<SloStatus> {
+        self.statuses_at(self.started.elapsed())
+    }
+
+    fn statuses_at(&self, since_start: Duration) -> Vec<SloStatus> {
+        let epoch = window_epoch(self.window, since_start);
+        self.definitions
+            .iter()
+            .zip(&self.counts)
+            .map(|(definition, counts)| {
+                let (total, bad) = counts.lock().totals(epoch);
+                slo_status(*definition, total, bad)
+            })
+            .collect()
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn slo_status(definition: SloDefinition, total: u64, bad: u64) -> SloStatus {
+    let allowed_bad = total as f64 * (1.0 - definition.objective);
+    SloStatus {
+        definition,
+        total,
+        good: total - bad,
+        compliance: (total > 0).then(|| (total - bad) as f64 / total as f64),
+        budget_remaining: if allowed_bad > 0.0 {
+            1.0 - bad as f64 / allowed_bad
+        } else {
+            1.0
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLOS: &[SloDefinition] = &[
+        SloDefinition {
+            name: "latency",
+            description: "",
+            routes: &["/r"],
+            kind: SloKind::Latency(Duration::from_millis(200)),
+            objective: 0.9,
+        },
+        SloDefinition {
+            name: "availability",
+            description: "",
+            routes: &["/r"],
+            kind: SloKind::Availability,
+            objective: 0.9,
+        },
+    ];
+
+    #[test]
+    fn test_budget_spent_by_bad_requests() {
+        let tracker = SloTracker::new(SLOS, Duration::from_secs(100));
+        let now = Duration::from_secs(1);
+        for _ in 0..18 {
+            tracker.record_at("/r", 200, Duration::from_millis(50), now);
+        }
+        tracker.record_at("/r", 200, Duration::from_millis(900), now);
+        tracker.record_at("/r", 503, Duration::from_millis(5), now);
+        tracker.record_at("/other", 503, Duration::from_millis(5), now);
+
+        let statuses = tracker.statuses_at(now);
+        let summary: Vec<(u64, u64)> = statuses.iter().map(|s| (s.total, s.good)).collect();
+        // The 503 only counts against availability
+        assert_eq!(summary, vec![(19, 18), (20, 19)]);
+        let availability = statuses.get(1).map(|s| s.budget_remaining);
+        assert!(availability.is_some_and(|budget| (budget - 0.5).abs() < 1e-9));
+
+        // Everything leaves the window; an idle objective keeps its full budget
+        let later = tracker.statuses_at(Duration::from_secs(200));
+        assert!(
+            later
+                .iter()
+                .all(|s| s.compliance.is_none() && (s.budget_remaining - 1.0).abs() < 1e-9)
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Template Render Metrics
+//!
+//! Render duration, output size and gzip ratio per template, recorded by the
+//! `TemplateOrchestrator` and the DSD handler. Rendering code has no `AppState` at
+//! hand, so like the recent error index this is a process-wide registry.
+
+use dashmap::DashMap;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use super::metrics::{Histogram, MetricsWriter};
+
+static TEMPLATE_METRICS: LazyLock<TemplateMetrics> = LazyLock::new(TemplateMetrics::default);
+
+/// Global template metrics
+#[must_use]
+pub fn template_metrics() -> &'static TemplateMetrics {
+    &TEMPLATE_METRICS
+}
+
+#[derive(Debug, Default)]
+struct TemplateStats {
+    render: Histogram,
+    output_bytes: AtomicU64,
+    last_output_bytes: AtomicU64,
+    /// Uncompressed / compressed bytes of the renders that were gzipped
+    gzip_input_bytes: AtomicU64,
+    gzip_output_bytes: AtomicU64,
+}
+
+/// Per-template render statistics since startup
+#[derive(Debug, Default)]
+pub struct TemplateMetrics {
+    templates: DashMap<String, TemplateStats>,
+}
+
+impl TemplateMetrics {
+    /// Record one render of `template` producing `output_bytes` of HTML
+    pub fn record_render(&self, template: &str, elapsed: Duration, output_bytes: usize) {
+        let bytes = u64::try_from(output_bytes).unwrap_or(u64::MAX);
+        self.with_stats(template, |stats| {
+            stats.render.observe(elapsed);
+            stats.output_bytes.fetch_add(bytes, Ordering::Relaxed);
+            stats.last_output_bytes.store(bytes, Ordering::Relaxed);
+        });
+    }
+
+    /// Record the gzip result of one render of `template`
+    pub fn record_compression(
+        &self,
+        template: &str,
+        original_bytes: usize,
+        compressed_bytes: usize,
+    ) {
+        self.with_stats(template, |stats| {
+            stats.gzip_input_bytes.fetch_add(
+                u64::try_from(original_bytes).unwrap_or(u64::MAX),
+                Ordering::Relaxed,
+            );
+            stats.gzip_output_bytes.fetch_add(
+                u64::try_from(compressed_bytes).unwrap_or(u64::MAX),
+                Ordering::Relaxed,
+            );
+        });
+    }
+
+    fn with_stats(&self, template: &str, update: impl FnOnce(&TemplateStats)) {
+        if let Some(stats) = self.templates.get(template) {
+            update(&stats);
+            return;
+        }
+        update(&self.templates.entry(template.to_string()).or_default());
+    }
+
+    /// Write render histograms, output sizes and compression ratios
+    pub fn write_metrics(&self, writer: &mut MetricsWriter) {
+        let mut names: Vec<String> = self.templates.iter().map(|e| e.key().clone()).collect();
+        names.sort();
+
+        writer.header(
+            "template_render_seconds",
+            "histogram",
+            "Template render duration by template",
+        );
+        for name in &names {
+            if let Some(stats) = self.templates.get(name) {
+                writer.histogram(
+                    "template_render_seconds",
+                    &[("template", name)],
+                    &stats.render,
+                );
+            }
+        }
+        writer.header(
+            "template_output_bytes_total",
+            "counter",
+            "Rendered HTML bytes by template",
+        );
+        for name in &names {
+            if let Some(stats) = self.templates.get(name) {
+                writer.sample(
+                    "template_output_bytes_total",
+                    &[("template", name)],
+                    stats.output_bytes.load(Ordering::Relaxed),
+                );
+            }
+        }
+        writer.header(
+            "template_last_output_bytes",
+            "gauge",
+            "Rendered HTML bytes of the latest render by template",
+        );
+        for name in &names {
+            if let Some(stats) = self.templates.get(name) {
+                writer.sample(
+                    "template_last_output_bytes",
+                    &[("template", name)],
+                    stats.last_output_bytes.load(Ordering::Relaxed),
+                );
+            }
+        }
+        writer.header(
+            "template_compression_ratio",
+            "gauge",
+            "Bytes saved by gzip (0-1) over all renders by template",
+        );
+        for name in &names {
+            if let Some(ratio) = self
+                .templates
+                .get(name)
+                .and_then(|stats| compression_ratio(&stats))
+            {
+                writer.sample("template_compression_ratio", &[("template", name)], ratio);
+            }
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn compression_ratio(stats: &TemplateStats) -> Option<f64> {
+    let input = stats.gzip_input_bytes.load(Ordering::Relaxed);
+    let output = stats.gzip_output_bytes.load(Ordering::Relaxed);
+    (input > 0).then(|| 1.0 - output as f64 / input as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_metrics_exposition() {
+        let metrics = TemplateMetrics::default();
+        metrics.record_render("view.html", Duration::from_millis(20), 4000);
+        metrics.record_render("view.html", Duration::from_millis(40), 6000);
+        metrics.record_compression("view.html", 6000, 1500);
+        metrics.record_render("list.html", Duration::from_millis(5), 100);
+
+        let mut writer = MetricsWriter::new();
+        metrics.write_metrics(&mut writer);
+        let text = writer.finish();
+
+        assert!(text.contains("template_render_seconds_count{template=\"view.html\"} 2"));
+        assert!(text.contains("template_output_bytes_total{template=\"view.html\"} 10000"));
+        assert!(text.contains("template_last_output_bytes{template=\"view.html\"} 6000"));
+        assert!(text.contains("template_compression_ratio{template=\"view.html\"} 0.75"));
+        // Never gzipped: no ratio sample
+        assert!(!text.contains("template_compression_ratio{template=\"list.html\"}"));
+    }
+}
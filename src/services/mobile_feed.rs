@@ -0,0 +1,329 @@
+//! Mobile Feed Service
+//!
+//! Assembles the companion app home screen (`/api/mobile/v1/feed`) from the latest
+//! report, the market snapshot and the top movers in a single small payload.
+//!
+//! Delta support: every section has a content fingerprint folded into the feed
+//! `cursor`. When the app sends its previous cursor back as `?since=`, sections whose
+//! fingerprint is unchanged are omitted and listed in `unchanged`.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::dto::mobile::v1::{
+    MobileCacheHints, MobileFeedResponse, MobileFeedSection, MobileMarketSnapshot, MobileMover,
+    MobileReportSummary,
+};
+use crate::dto::responses::DashboardDataResponse;
+use crate::services::crypto_reports::rendering::{GeoMetadata, Report};
+use crate::services::shared::RssCreator;
+use crate::state::AppState;
+
+/// Current feed contract version (first cursor segment)
+const FEED_VERSION: u8 = 1;
+/// Cache hints sent to the app (market data refreshes every few seconds upstream)
+pub const FEED_MAX_AGE_SECS: u32 = 30;
+pub const FEED_STALE_WHILE_REVALIDATE_SECS: u32 = 120;
+/// Number of coins in the top movers list
+const TOP_MOVERS_COUNT: usize = 5;
+/// Max characters of the report excerpt
+const SUMMARY_LENGTH: usize = 200;
+/// Hex characters kept from each section fingerprint
+const FINGERPRINT_LENGTH: usize = 12;
+
+/// Built feed plus whether the client's copy is already current
+pub struct MobileFeed {
+    pub response: MobileFeedResponse,
+    /// `since` matched every section; answer `304 Not Modified`
+    pub not_modified: bool,
+}
+
+/// Mobile Feed Service
+#[derive(Clone, Copy, Default)]
+pub struct MobileFeedService;
+
+impl MobileFeedService {
+    /// Create a new `MobileFeedService`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the feed for `language`, omitting sections unchanged since `since`
+    ///
+    /// Sections that cannot be loaded are left out rather than failing the whole feed.
+    pub async fn build_feed(
+        &self,
+        state: &Arc<AppState>,
+        language: &str,
+        since: Option<&str>,
+    ) -> MobileFeed {
+        let latest_report = match state
+            .crypto_handlers
+            .report_creator
+            .fetch_and_cache_latest_report(state)
+            .await
+        {
+            Ok(report) => report.map(|report| report_summary(&report, language)),
+            Err(e) => {
+                warn!("⚠️ [Mobile] Failed to fetch latest report: {}", e);
+                None
+            }
+        };
+
+        let market_data = match state.streams.market_data().read_latest().await {
+            Ok(Some(data)) => serde_json::from_value::<DashboardDataResponse>(data)
+                .inspect_err(|e| warn!("⚠️ [Mobile] Invalid market data: {}", e))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("⚠️ [Mobile] Failed to read market data: {}", e);
+                None
+            }
+        };
+        let market = market_data.as_ref().map(market_snapshot);
+        let top_movers = market_data
+            .as_ref()
+            .map(|data| top_movers(data, TOP_MOVERS_COUNT));
+
+        assemble_feed(
+            latest_report,
+            market,
+            top_movers,
+            since,
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        )
+    }
+}
+
+/// Combine sections into the feed, applying the `since` delta
+fn assemble_feed(
+    latest_report: Option<MobileReportSummary>,
+    market: Option<MobileMarketSnapshot>,
+    top_movers: Option<Vec<MobileMover>>,
+    since: Option<&str>,
+    generated_at: String,
+) -> MobileFeed {
+    let fingerprints = [
+        fingerprint(&latest_report),
+        fingerprint(&market),
+        fingerprint(&top_movers),
+    ];
+    let cursor = format!(
+        "{FEED_VERSION}.{}.{}.{}",
+        fingerprints[0], fingerprints[1], fingerprints[2]
+    );
+    let previous = since.and_then(parse_cursor);
+    let is_unchanged = |index: usize| {
+        previous
+            .as_ref()
+            .and_then(|previous| previous.get(index))
+            .zip(fingerprints.get(index))
+            .is_some_and(|(old, new)| old == new)
+    };
+
+    let mut response = MobileFeedResponse {
+        version: FEED_VERSION,
+        cursor,
+        generated_at,
+        cache: MobileCacheHints {
+            max_age: FEED_MAX_AGE_SECS,
+            stale_while_revalidate: FEED_STALE_WHILE_REVALIDATE_SECS,
+        },
+        unchanged: Vec::new(),
+        latest_report,
+        market,
+        top_movers,
+    };
+
+    if is_unchanged(0) {
+        response.latest_report = None;
+        response.unchanged.push(MobileFeedSection::LatestReport);
+    }
+    if is_unchanged(1) {
+        response.market = None;
+        response.unchanged.push(MobileFeedSection::Market);
+    }
+    if is_unchanged(2) {
+        response.top_movers = None;
+        response.unchanged.push(MobileFeedSection::TopMovers);
+    }
+
+    let not_modified = response.unchanged.len() == fingerprints.len();
+    debug!(
+        "📱 [Mobile] Feed built: cursor={}, unchanged={:?}",
+        response.cursor, response.unchanged
+    );
+    MobileFeed {
+        response,
+        not_modified,
+    }
+}
+
+/// Report card for the home screen
+fn report_summary(report: &Report, language: &str) -> MobileReportSummary {
+    let metadata = GeoMetadata::from_report(report);
+    let (title, html) = if language == "en" {
+        (
+            metadata.title_en,
+            report
+                .html_content_en
+                .as_deref()
+                .unwrap_or(&report.html_content),
+        )
+    } else {
+        (metadata.title_vi, report.html_content.as_str())
+    };
+
+    MobileReportSummary {
+        id: report.id,
+        title,
+        summary: RssCreator::extract_description(html, SUMMARY_LENGTH),
+        url: metadata.canonical_url,
+        published_at: metadata.date_published,
+    }
+}
+
+fn market_snapshot(data: &DashboardDataResponse) -> MobileMarketSnapshot {
+    MobileMarketSnapshot {
+        market_cap_usd: data.market_cap_usd,
+        market_cap_change_24h: data.market_cap_change_percentage_24h_usd,
+        volume_24h_usd: data.volume_24h_usd,
+        btc_dominance: data.btc_market_cap_percentage,
+        fear_greed: data.fng_value,
+        updated_at: data.last_updated.clone(),
+        partial: data.partial_failure,
+    }
+}
+
+/// Coins with the largest absolute 24h change, descending
+fn top_movers(data: &DashboardDataResponse, count: usize) -> Vec<MobileMover> {
+    let mut movers = [
+        ("BTC", data.btc_price_usd, data.btc_change_24h),
+        ("ETH", data.eth_price_usd, data.eth_change_24h),
+        ("BNB", data.bnb_price_usd, data.bnb_change_24h),
+        ("SOL", data.sol_price_usd, data.sol_change_24h),
+        ("XRP", data.xrp_price_usd, data.xrp_change_24h),
+        ("ADA", data.ada_price_usd, data.ada_change_24h),
+        ("LINK", data.link_price_usd, data.link_change_24h),
+    ];
+    movers.sort_by(|a, b| b.2.abs().total_cmp(&a.2.abs()));
+
+    movers
+        .iter()
+        .take(count)
+        .map(|(symbol, price_usd, change_24h)| MobileMover {
+            symbol: (*symbol).to_string(),
+            price_usd: *price_usd,
+            change_24h: *change_24h,
+        })
+        .collect()
+}
+
+/// Short content hash of a section
+fn fingerprint<T: Serialize>(section: &T) -> String {
+    let bytes = serde_json::to_vec(section).unwrap_or_default();
+    let mut hex = blake3::hash(&bytes).to_hex().to_string();
+    hex.truncate(FINGERPRINT_LENGTH);
+    hex
+}
+
+/// Section fingerprints from a cursor of the current feed version
+fn parse_cursor(cursor: &str) -> Option<Vec<&str>> {
+    let mut parts = cursor.trim().split('.');
+    if parts.next()? != FEED_VERSION.to_string() {
+        return None;
+    }
+    let fingerprints: Vec<&str> = parts.collect();
+    (fingerprints.len() == 3).then_some(fingerprints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_market() -> MobileMarketSnapshot {
+        MobileMarketSnapshot {
+            market_cap_usd: 3.3e12,
+            market_cap_change_24h: 1.0,
+            volume_24h_usd: 2.6e11,
+            btc_dominance: 57.0,
+            fear_greed: 50,
+            updated_at: "2026-10-14T08:00:00Z".to_string(),
+            partial: false,
+        }
+    }
+
+    fn sample_report() -> MobileReportSummary {
+        MobileReportSummary {
+            id: 1,
+            title: "Report #1".to_string(),
+            summary: "Summary".to_string(),
+            url: "https://cryptodashboard.me/crypto_report/1".to_string(),
+            published_at: "2026-10-14T07:00:00Z".to_string(),
+        }
+    }
+
+    fn build(market: MobileMarketSnapshot, since: Option<&str>) -> MobileFeed {
+        assemble_feed(
+            Some(sample_report()),
+            Some(market),
+            Some(vec![]),
+            since,
+            "2026-10-14T08:00:00Z".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_delta_omits_unchanged_sections() {
+        let first = build(sample_market(), None);
+        assert!(first.response.unchanged.is_empty());
+        assert!(!first.not_modified);
+
+        let mut market = sample_market();
+        market.fear_greed = 55;
+        let delta = build(market, Some(&first.response.cursor));
+        assert_eq!(
+            delta.response.unchanged,
+            vec![
+                MobileFeedSection::LatestReport,
+                MobileFeedSection::TopMovers
+            ]
+        );
+        assert!(delta.response.latest_report.is_none());
+        assert!(delta.response.market.is_some());
+        assert!(!delta.not_modified);
+
+        let same = build(sample_market(), Some(&first.response.cursor));
+        assert!(same.not_modified);
+    }
+
+    #[test]
+    fn test_invalid_cursor_returns_full_feed() {
+        for since in ["", "garbage", "2.a.b.c", "1.a.b"] {
+            let feed = build(sample_market(), Some(since));
+            assert!(feed.response.unchanged.is_empty(), "cursor {since:?}");
+        }
+    }
+
+    #[test]
+    fn test_top_movers_sorted_by_absolute_change() -> Result<(), serde_json::Error> {
+        let data: DashboardDataResponse = serde_json::from_value(serde_json::json!({
+            "btc_price_usd": 60000.0, "btc_change_24h": 1.0, "btc_market_cap_percentage": 55.0,
+            "btc_rsi_14": 50.0, "eth_price_usd": 3000.0, "eth_change_24h": -8.0,
+            "eth_market_cap_percentage": 12.0, "bnb_price_usd": 600.0, "bnb_change_24h": 0.5,
+            "sol_price_usd": 150.0, "sol_change_24h": 4.0, "xrp_price_usd": 0.6,
+            "xrp_change_24h": -0.1, "ada_price_usd": 0.4, "ada_change_24h": 2.0,
+            "link_price_usd": 15.0, "link_change_24h": 0.0, "market_cap_usd": 2.5e12,
+            "market_cap_change_percentage_24h_usd": 1.0, "volume_24h_usd": 1e11,
+            "fng_value": 60, "us_stock_indices": {}, "fetch_duration_ms": 10,
+            "partial_failure": false, "last_updated": "2026-10-14T08:00:00Z",
+            "timestamp": "2026-10-14T08:00:00Z"
+        }))?;
+
+        let symbols: Vec<String> = top_movers(&data, 3).into_iter().map(|m| m.symbol).collect();
+        assert_eq!(symbols, vec!["ETH", "SOL", "ADA"]);
+        Ok(())
+    }
+}
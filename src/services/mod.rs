@@ -2,4 +2,8 @@ pub mod crypto_reports;
 pub mod dashboard;
 pub mod dashboard_data_service;
 pub mod data_communication;
+pub mod diagnostics;
+pub mod health_system;
+pub mod mobile_feed;
+pub mod notifications;
 pub mod shared;
@@ -0,0 +1,184 @@
+//! Subscription Confirmation
+//!
+//! Double opt-in of the digests: `POST /api/subscribe` mails the address a link to
+//! `CONFIRM_PATH`, and digests only go out once it is followed. That link and the
+//! unsubscribe link of every digest open a page whose button POSTs the change
+//! back, so mail scanners prefetching links neither confirm nor unsubscribe anyone.
+
+use lettre::message::Mailbox;
+use lettre::message::header::ContentType;
+use lettre::{AsyncTransport, Message};
+use tracing::{debug, warn};
+
+use super::digest::{DigestConfig, UNSUBSCRIBE_PATH, mailer};
+use crate::services::shared::sitemap_creator::BASE_URL;
+
+/// Path of the confirmation link (`?token=`)
+pub const CONFIRM_PATH: &str = "/api/subscribe/confirm";
+
+/// Change made by the button of a subscription link page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionAction {
+    Confirm,
+    Unsubscribe,
+}
+
+impl SubscriptionAction {
+    /// Path the page is served at and its form posts to
+    #[must_use]
+    pub const fn path(self) -> &'static str {
+        match self {
+            Self::Confirm => CONFIRM_PATH,
+            Self::Unsubscribe => UNSUBSCRIBE_PATH,
+        }
+    }
+
+    const fn title(self) -> &'static str {
+        match self {
+            Self::Confirm => "Xác nhận đăng ký / Confirm subscription",
+            Self::Unsubscribe => "Hủy đăng ký / Unsubscribe",
+        }
+    }
+
+    const fn prompt(self) -> &'static str {
+        match self {
+            Self::Confirm => {
+                "Nhấn nút bên dưới để nhận bản tin CryptoDashboard.<br>Press the button below to receive CryptoDashboard digests."
+            }
+            Self::Unsubscribe => {
+                "Nhấn nút bên dưới để ngừng nhận bản tin CryptoDashboard.<br>Press the button below to stop receiving CryptoDashboard digests."
+            }
+        }
+    }
+
+    /// Plain-text answer once the change is made
+    #[must_use]
+    pub const fn done(self) -> &'static str {
+        match self {
+            Self::Confirm => {
+                "Bạn đã xác nhận đăng ký bản tin CryptoDashboard.\nYour CryptoDashboard digest subscription is confirmed.\n"
+            }
+            Self::Unsubscribe => {
+                "Bạn đã hủy đăng ký bản tin CryptoDashboard.\nYou have been unsubscribed from CryptoDashboard digests.\n"
+            }
+        }
+    }
+
+    /// HTML page whose button POSTs `token` (checked with `is_valid_token`) back to
+    /// `path`, with the CSRF hidden field `csrf_field`
+    #[must_use]
+    pub fn render_page(self, token: &str, csrf_field: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"vi\">\n<head>\n<meta charset=\"UTF-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n\
+             <meta name=\"robots\" content=\"noindex\">\n<title>{title}</title>\n</head>\n\
+             <body style=\"font-family:Arial,Helvetica,sans-serif;max-width:480px;margin:48px auto;padding:0 16px;\">\n\
+             <h1 style=\"font-size:22px;\">{title}</h1>\n<p>{prompt}</p>\n\
+             <form method=\"post\" action=\"{path}\">\n{csrf_field}\n\
+             <input type=\"hidden\" name=\"token\" value=\"{token}\">\n\
+             <button type=\"submit\">{title}</button>\n</form>\n</body>\n</html>\n",
+            title = self.title(),
+            prompt = self.prompt(),
+            path = self.path(),
+        )
+    }
+}
+
+/// Whether `token` has the shape of a subscription token (64 hex characters)
+#[must_use]
+pub fn is_valid_token(token: &str) -> bool {
+    token.len() == 64 && token.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Absolute confirmation link of a subscription
+#[must_use]
+pub fn confirm_url(token: &str) -> String {
+    format!("{BASE_URL}{CONFIRM_PATH}?token={token}")
+}
+
+/// Plain-text confirmation email in `language` (`en` or Vietnamese)
+fn confirmation_message(
+    config: &DigestConfig,
+    to: &str,
+    language: &str,
+    confirm_url: &str,
+) -> Result<Message, String> {
+    let (subject, body) = if language == "en" {
+        (
+            "Confirm your CryptoDashboard digest subscription",
+            format!(
+                "Open this link to start receiving CryptoDashboard market report digests:\n\n{confirm_url}\n\nIf you did not subscribe, ignore this email and you will not hear from us again.\n"
+            ),
+        )
+    } else {
+        (
+            "Xác nhận đăng ký bản tin CryptoDashboard",
+            format!(
+                "Mở liên kết này để bắt đầu nhận bản tin báo cáo thị trường CryptoDashboard:\n\n{confirm_url}\n\nNếu bạn không đăng ký, hãy bỏ qua email này, bạn sẽ không nhận thêm email nào.\n"
+            ),
+        )
+    };
+    Message::builder()
+        .from(config.from.parse::<Mailbox>().map_err(|e| e.to_string())?)
+        .to(to.parse::<Mailbox>().map_err(|e| e.to_string())?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body)
+        .map_err(|e| e.to_string())
+}
+
+/// Mail the confirmation link of `token` to `email` in the background; failures
+/// are logged only, as the subscriber can subscribe again
+pub fn send_confirmation(email: &str, language: &str, token: &str) {
+    let Some(mailer) = mailer() else {
+        return;
+    };
+    let message = match confirmation_message(
+        DigestConfig::current(),
+        email,
+        language,
+        &confirm_url(token),
+    ) {
+        Ok(message) => message,
+        Err(e) => {
+            warn!("⚠️ Failed to build confirmation email: {}", e);
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        match mailer.send(message).await {
+            Ok(_) => debug!("📧 Sent subscription confirmation email"),
+            Err(e) => warn!("⚠️ Failed to send confirmation email: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_page() {
+        let token = "ab".repeat(32);
+        assert!(is_valid_token(&token));
+        assert!(!is_valid_token("\"><script>"));
+        assert!(!is_valid_token(&token[..62]));
+
+        let html = SubscriptionAction::Unsubscribe.render_page(&token, "<input name=\"csrf\">");
+        assert!(html.contains("<form method=\"post\" action=\"/api/unsubscribe\">"));
+        assert!(html.contains(&format!("name=\"token\" value=\"{token}\"")));
+        assert!(html.contains("<input name=\"csrf\">"));
+    }
+
+    #[test]
+    fn test_confirmation_message() {
+        let url = confirm_url("abc");
+        assert_eq!(
+            url,
+            "https://cryptodashboard.me/api/subscribe/confirm?token=abc"
+        );
+        let config = DigestConfig::parse(None, None, None);
+        assert!(confirmation_message(&config, "reader@example.org", "en", &url).is_ok());
+        assert!(confirmation_message(&config, "not an address", "vi", &url).is_err());
+    }
+}
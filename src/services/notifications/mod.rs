@@ -0,0 +1,19 @@
+//! Notifications Island
+//!
+//! Email digests of new reports:
+//! - `subscription_service`: the `email_subscriptions` table
+//! - `confirmation`: double opt-in emails and the confirm / unsubscribe pages
+//! - `digest`: rendering and the scheduled SMTP sender
+
+pub mod confirmation;
+pub mod digest;
+pub mod subscription_service;
+
+pub use confirmation::{
+    CONFIRM_PATH, SubscriptionAction, confirm_url, is_valid_token, send_confirmation,
+};
+pub use digest::{
+    DIGEST_TEMPLATE, DigestConfig, DigestEmail, DigestFrequency, UNSUBSCRIBE_PATH, render_digest,
+    spawn_digest_scheduler, unsubscribe_url, validate_subscription,
+};
+pub use subscription_service::{DueSubscription, SubscriptionService};
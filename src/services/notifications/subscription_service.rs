@@ -0,0 +1,199 @@
+//! Subscription Service
+//!
+//! Database operations of the `email_subscriptions` table (see
+//! `migrations/20261015090500_create_email_subscriptions.sql` and
+//! `migrations/20261015100000_add_email_subscription_confirmation.sql`): subscribing,
+//! confirming and unsubscribing, and claiming the confirmed subscribers a digest is
+//! due for.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use sqlx::{FromRow, PgPool};
+
+use super::digest::DigestFrequency;
+
+/// Bytes of randomness in an unsubscribe or confirmation token (hex-encoded)
+const TOKEN_BYTES: usize = 32;
+
+/// Minimum delay between two confirmation emails to one unconfirmed address
+const CONFIRMATION_RESEND_INTERVAL: TimeDelta = TimeDelta::hours(1);
+
+/// A subscriber claimed for a digest
+#[derive(Debug, Clone, FromRow)]
+pub struct DueSubscription {
+    pub id: i64,
+    pub email: String,
+    pub language: String,
+    pub unsubscribe_token: String,
+    /// Start of the digest: the previous digest, or the subscription
+    pub since: DateTime<Utc>,
+}
+
+/// Subscription Service
+#[derive(Clone, Default)]
+pub struct SubscriptionService;
+
+impl SubscriptionService {
+    /// Create a new `SubscriptionService`
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Subscribe `email` (lowercase) pending confirmation, or change the frequency
+    /// and language of a subscription that is still unconfirmed
+    ///
+    /// Returns the confirmation token when a confirmation email is due: for a new
+    /// row, or an unconfirmed one last mailed over `CONFIRMATION_RESEND_INTERVAL`
+    /// ago. A confirmed subscription is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn subscribe(
+        &self,
+        db: &PgPool,
+        email: &str,
+        frequency: DigestFrequency,
+        language: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "INSERT INTO email_subscriptions \
+                 (email, frequency, language, unsubscribe_token, confirmation_token, confirmation_sent_at) \
+             VALUES ($1, $2, $3, $4, $5, $6) \
+             ON CONFLICT (email) DO UPDATE \
+             SET frequency = EXCLUDED.frequency, language = EXCLUDED.language, \
+                 confirmation_sent_at = EXCLUDED.confirmation_sent_at \
+             WHERE email_subscriptions.confirmed_at IS NULL \
+               AND email_subscriptions.confirmation_sent_at < $7 \
+             RETURNING confirmation_token",
+        )
+        .bind(email)
+        .bind(frequency.as_str())
+        .bind(language)
+        .bind(generate_token())
+        .bind(generate_token())
+        .bind(now)
+        .bind(now - CONFIRMATION_RESEND_INTERVAL)
+        .fetch_optional(db)
+        .await
+    }
+
+    /// Confirm the subscription holding the confirmation `token`, returning whether
+    /// it exists (confirming twice is harmless)
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn confirm(
+        &self,
+        db: &PgPool,
+        token: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE email_subscriptions SET confirmed_at = COALESCE(confirmed_at, $2) \
+             WHERE confirmation_token = $1",
+        )
+        .bind(token)
+        .bind(now)
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Delete the subscriptions still unconfirmed that were made before
+    /// `created_before`, returning how many
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn purge_unconfirmed(
+        &self,
+        db: &PgPool,
+        created_before: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM email_subscriptions WHERE confirmed_at IS NULL AND created_at < $1",
+        )
+        .bind(created_before)
+        .execute(db)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete the subscription holding `token`, returning whether it existed
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn unsubscribe(&self, db: &PgPool, token: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM email_subscriptions WHERE unsubscribe_token = $1")
+            .bind(token)
+            .execute(db)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Claim up to `limit` confirmed subscribers of `frequency` whose last digest (or
+    /// subscription) is older than `due_before`, marking them sent at `now`
+    ///
+    /// Rows locked by another instance are skipped, so each digest is claimed once
+    /// across replicas; `release` puts back a digest that could not be sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn claim_due(
+        &self,
+        db: &PgPool,
+        frequency: DigestFrequency,
+        due_before: DateTime<Utc>,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<DueSubscription>, sqlx::Error> {
+        sqlx::query_as::<_, DueSubscription>(
+            "WITH due AS ( \
+                 SELECT id, COALESCE(last_sent_at, created_at) AS since \
+                 FROM email_subscriptions \
+                 WHERE frequency = $1 AND confirmed_at IS NOT NULL \
+                   AND COALESCE(last_sent_at, created_at) < $2 \
+                 ORDER BY id LIMIT $4 \
+                 FOR UPDATE SKIP LOCKED \
+             ) \
+             UPDATE email_subscriptions s SET last_sent_at = $3 \
+             FROM due WHERE s.id = due.id \
+             RETURNING s.id, s.email, s.language, s.unsubscribe_token, due.since",
+        )
+        .bind(frequency.as_str())
+        .bind(due_before)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(db)
+        .await
+    }
+
+    /// Restore the digest start of a subscriber whose digest was not sent
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if database connection fails or query execution fails
+    pub async fn release(
+        &self,
+        db: &PgPool,
+        subscription: &DueSubscription,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE email_subscriptions SET last_sent_at = $2 WHERE id = $1")
+            .bind(subscription.id)
+            .bind(subscription.since)
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Random unsubscribe or confirmation token (64 hex characters)
+fn generate_token() -> String {
+    let bytes: [u8; TOKEN_BYTES] = rand::random();
+    blake3::Hash::from_bytes(bytes).to_hex().to_string()
+}
@@ -0,0 +1,454 @@
+//! Bot Detection and Crawl Throttling
+//!
+//! Every request is classified from its `User-Agent`: search engine bots, AI
+//! crawlers, scripts (HTTP libraries, empty agents, unidentified bots) and humans.
+//! Crawler classes get their own token buckets on top of the per-IP rate limit:
+//! AI crawlers share one bucket per crawler (`BOT_AI_BURST` / `BOT_AI_PER_SEC`,
+//! default 10 at 0.2/s) and scripts one per client IP (`BOT_SCRIPT_BURST` /
+//! `BOT_SCRIPT_PER_SEC`, default 20 at 1/s); over the limit they get 429. Classes
+//! listed in `BOT_CACHE_ONLY` (default `ai`) get report pages and the reports list
+//! from cache (or stale copies) only: where a page would have to be rendered they
+//! get 503 with `Retry-After`.
+//! Requests, throttles and cache-only misses are counted per class for `/metrics`.
+//! `BOT_THROTTLE=false` keeps the classification and metrics but stops throttling.
+//! Like the rate limit, buckets are per process (N workers allow N times the burst).
+
+use axum::{
+    extract::Request,
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tracing::debug;
+
+use super::client_ip::client_ip;
+use super::rate_limit::{TokenBuckets, too_many_requests};
+use crate::services::health_system::{MetricsWriter, UserAgentClass, with_request_id};
+
+/// AI crawlers and assistants fetching pages for training or answers
+const AI_CRAWLERS: &[&str] = &[
+    "gptbot",
+    "chatgpt-user",
+    "oai-searchbot",
+    "claudebot",
+    "claude-web",
+    "claude-user",
+    "anthropic-ai",
+    "ccbot",
+    "perplexitybot",
+    "perplexity-user",
+    "bytespider",
+    "amazonbot",
+    "google-extended",
+    "applebot-extended",
+    "meta-externalagent",
+    "meta-externalfetcher",
+    "cohere-ai",
+    "diffbot",
+    "youbot",
+    "timpibot",
+    "imagesiftbot",
+    "ai2bot",
+    "mistralai-user",
+];
+
+/// Search engine and link preview bots
+const SEARCH_BOTS: &[&str] = &[
+    "googlebot",
+    "google-inspectiontool",
+    "adsbot-google",
+    "bingbot",
+    "duckduckbot",
+    "yandexbot",
+    "baiduspider",
+    "applebot",
+    "slurp",
+    "coccocbot",
+    "facebookexternalhit",
+    "twitterbot",
+    "linkedinbot",
+    "telegrambot",
+    "discordbot",
+];
+
+/// Paths never throttled: assets, probes and crawl instructions
+const EXEMPT_PREFIXES: &[&str] = &[
+    "/shared_assets",
+    "/shared_components",
+    "/crypto_dashboard",
+    "/stock_dashboard",
+    "/robots.txt",
+    "/health",
+    "/metrics",
+];
+
+/// Suggested wait when a cache-only crawler asks for an uncached page
+const CACHE_ONLY_RETRY_AFTER_SECS: u32 = 3600;
+
+/// Kind of client, as far as crawl policy is concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrawlerClass {
+    SearchBot,
+    AiCrawler,
+    /// HTTP libraries, empty user agents and bots we don't know
+    Script,
+    Human,
+}
+
+impl CrawlerClass {
+    pub const ALL: [Self; 4] = [Self::SearchBot, Self::AiCrawler, Self::Script, Self::Human];
+
+    /// Class of `user_agent` and, for known crawlers, the crawler name
+    #[must_use]
+    pub fn classify(user_agent: Option<&str>) -> (Self, Option<&'static str>) {
+        let ua = user_agent.unwrap_or_default().to_ascii_lowercase();
+        let known = |names: &[&'static str]| names.iter().copied().find(|name| ua.contains(name));
+        // AI crawlers first: several of them also match generic search bot names
+        if let Some(name) = known(AI_CRAWLERS) {
+            return (Self::AiCrawler, Some(name));
+        }
+        if let Some(name) = known(SEARCH_BOTS) {
+            return (Self::SearchBot, Some(name));
+        }
+        let class = match UserAgentClass::classify(user_agent) {
+            UserAgentClass::Browser | UserAgentClass::Mobile => Self::Human,
+            UserAgentClass::Bot | UserAgentClass::Tool | UserAgentClass::Unknown => Self::Script,
+        };
+        (class, None)
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SearchBot => "search",
+            Self::AiCrawler => "ai",
+            Self::Script => "script",
+            Self::Human => "human",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|class| class.as_str() == value.trim().to_ascii_lowercase())
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::SearchBot => 0,
+            Self::AiCrawler => 1,
+            Self::Script => 2,
+            Self::Human => 3,
+        }
+    }
+}
+
+/// Crawl throttling settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct BotConfig {
+    pub throttle: bool,
+    pub ai_burst: f64,
+    pub ai_per_sec: f64,
+    pub script_burst: f64,
+    pub script_per_sec: f64,
+    /// Classes that never trigger renders
+    pub cache_only: Vec<CrawlerClass>,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        Self {
+            throttle: true,
+            ai_burst: 10.0,
+            ai_per_sec: 0.2,
+            script_burst: 20.0,
+            script_per_sec: 1.0,
+            cache_only: vec![CrawlerClass::AiCrawler],
+        }
+    }
+}
+
+impl BotConfig {
+    /// `BOT_THROTTLE`, `BOT_AI_BURST`, `BOT_AI_PER_SEC`, `BOT_SCRIPT_BURST`,
+    /// `BOT_SCRIPT_PER_SEC` and `BOT_CACHE_ONLY` (comma-separated classes, empty for none)
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let positive = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(default)
+        };
+        Self {
+            throttle: std::env::var("BOT_THROTTLE").map_or(defaults.throttle, |v| {
+                !matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "0" | "false" | "off"
+                )
+            }),
+            ai_burst: positive("BOT_AI_BURST", defaults.ai_burst),
+            ai_per_sec: positive("BOT_AI_PER_SEC", defaults.ai_per_sec),
+            script_burst: positive("BOT_SCRIPT_BURST", defaults.script_burst),
+            script_per_sec: positive("BOT_SCRIPT_PER_SEC", defaults.script_per_sec),
+            cache_only: std::env::var("BOT_CACHE_ONLY").map_or(defaults.cache_only, |v| {
+                v.split(',').filter_map(CrawlerClass::parse).collect()
+            }),
+        }
+    }
+}
+
+/// Per-class counters
+#[derive(Debug, Default)]
+struct ClassCounters([AtomicU64; 4]);
+
+impl ClassCounters {
+    fn increment(&self, class: CrawlerClass) {
+        if let Some(counter) = self.0.get(class.index()) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn get(&self, class: CrawlerClass) -> u64 {
+        self.0
+            .get(class.index())
+            .map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+}
+
+/// Crawl policy state: buckets per crawler and per script client, plus metrics
+pub struct CrawlControl {
+    config: BotConfig,
+    ai_buckets: TokenBuckets<&'static str>,
+    script_buckets: TokenBuckets<String>,
+    requests: ClassCounters,
+    throttled: ClassCounters,
+    cache_only_misses: ClassCounters,
+}
+
+impl CrawlControl {
+    #[must_use]
+    pub fn new(config: BotConfig) -> Self {
+        Self {
+            ai_buckets: TokenBuckets::new(config.ai_burst, config.ai_per_sec),
+            script_buckets: TokenBuckets::new(config.script_burst, config.script_per_sec),
+            config,
+            requests: ClassCounters::default(),
+            throttled: ClassCounters::default(),
+            cache_only_misses: ClassCounters::default(),
+        }
+    }
+
+    /// Process-wide instance, configured from the environment on first use
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static CONTROL: OnceLock<CrawlControl> = OnceLock::new();
+        CONTROL.get_or_init(|| Self::new(BotConfig::from_env()))
+    }
+
+    /// Take one token for the client; `Err` carries how long until one is available
+    ///
+    /// AI crawlers have one bucket per crawler, scripts one per `client`.
+    ///
+    /// # Errors
+    /// Returns the wait until the next token when the client's bucket is empty
+    pub fn check(
+        &self,
+        class: CrawlerClass,
+        crawler: Option<&'static str>,
+        client: &str,
+        now: Instant,
+    ) -> Result<(), std::time::Duration> {
+        if !self.config.throttle {
+            return Ok(());
+        }
+        match class {
+            CrawlerClass::AiCrawler => self.ai_buckets.check(crawler.unwrap_or("ai"), now),
+            CrawlerClass::Script => self.script_buckets.check(client.to_string(), now),
+            CrawlerClass::SearchBot | CrawlerClass::Human => Ok(()),
+        }
+    }
+
+    /// Whether `class` is only served from cache
+    #[must_use]
+    pub fn is_cache_only(&self, class: CrawlerClass) -> bool {
+        self.config.cache_only.contains(&class)
+    }
+
+    /// Write the per-class request, throttle and cache-only miss counters
+    pub fn write_metrics(&self, writer: &mut MetricsWriter) {
+        let counters = [
+            (
+                "crawler_requests_total",
+                "Requests by crawler class",
+                &self.requests,
+            ),
+            (
+                "crawler_throttled_total",
+                "Requests answered 429 by crawl throttling, by crawler class",
+                &self.throttled,
+            ),
+            (
+                "crawler_cache_only_misses_total",
+                "Uncached pages refused to cache-only crawler classes",
+                &self.cache_only_misses,
+            ),
+        ];
+        for (name, help, counter) in counters {
+            writer.header(name, "counter", help);
+            for class in CrawlerClass::ALL {
+                writer.sample(name, &[("class", class.as_str())], counter.get(class));
+            }
+        }
+    }
+}
+
+tokio::task_local! {
+    /// Crawler class of the request being served
+    static CURRENT_CRAWLER: CrawlerClass;
+}
+
+/// Crawler class of the current request (inside `crawl_control`)
+#[must_use]
+pub fn current_crawler_class() -> Option<CrawlerClass> {
+    CURRENT_CRAWLER.try_with(|class| *class).ok()
+}
+
+/// 503 for a cache-only crawler asking for a page that would have to be rendered
+///
+/// Handlers call this after their cache and stale lookups missed; `None` means the
+/// render may go ahead.
+#[must_use]
+pub fn cache_only_miss() -> Option<Response> {
+    let class = current_crawler_class()?;
+    let control = CrawlControl::current();
+    if !control.is_cache_only(class) {
+        return None;
+    }
+    control.cache_only_misses.increment(class);
+    debug!(
+        class = class.as_str(),
+        "🕷️ Cache-only crawler missed the cache"
+    );
+    let mut response = (
+        StatusCode::SERVICE_UNAVAILABLE,
+        with_request_id("This page is not cached yet, try again later"),
+    )
+        .into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(CACHE_ONLY_RETRY_AFTER_SECS),
+    );
+    Some(response)
+}
+
+/// Middleware classifying the client and throttling crawlers
+pub async fn crawl_control(request: Request, next: Next) -> Response {
+    let control = CrawlControl::current();
+    let (class, crawler) = CrawlerClass::classify(
+        request
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok()),
+    );
+    control.requests.increment(class);
+
+    let path = request.uri().path();
+    let exempt = EXEMPT_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix));
+    if !exempt {
+        let client = client_ip(&request).map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+        if let Err(retry_after) = control.check(class, crawler, &client, Instant::now()) {
+            control.throttled.increment(class);
+            debug!(
+                class = class.as_str(),
+                crawler, %client, path, "🕷️ Crawler throttled"
+            );
+            return too_many_requests(retry_after);
+        }
+    }
+
+    CURRENT_CRAWLER.scope(class, next.run(request)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_classify_user_agents() {
+        let classify = |ua| CrawlerClass::classify(Some(ua));
+        assert_eq!(
+            classify(
+                "Mozilla/5.0 AppleWebKit/537.36 (compatible; GPTBot/1.2; +https://openai.com/gptbot)"
+            ),
+            (CrawlerClass::AiCrawler, Some("gptbot"))
+        );
+        assert_eq!(
+            classify("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"),
+            (CrawlerClass::SearchBot, Some("googlebot"))
+        );
+        assert_eq!(classify("python-requests/2.31").0, CrawlerClass::Script);
+        assert_eq!(classify("SomeNewBot/0.1").0, CrawlerClass::Script);
+        assert_eq!(CrawlerClass::classify(None).0, CrawlerClass::Script);
+        assert_eq!(
+            classify("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0").0,
+            CrawlerClass::Human
+        );
+    }
+
+    #[test]
+    fn test_crawler_buckets() {
+        let control = CrawlControl::new(BotConfig {
+            ai_burst: 1.0,
+            ai_per_sec: 1.0,
+            script_burst: 1.0,
+            ..BotConfig::default()
+        });
+        let now = Instant::now();
+        let ai = CrawlerClass::AiCrawler;
+        assert!(control.check(ai, Some("gptbot"), "1.1.1.1", now).is_ok());
+        // Same crawler from another address shares the bucket
+        assert_eq!(
+            control.check(ai, Some("gptbot"), "2.2.2.2", now),
+            Err(Duration::from_secs(1))
+        );
+        assert!(control.check(ai, Some("ccbot"), "1.1.1.1", now).is_ok());
+
+        let script = CrawlerClass::Script;
+        assert!(control.check(script, None, "1.1.1.1", now).is_ok());
+        assert!(control.check(script, None, "1.1.1.1", now).is_err());
+        assert!(control.check(script, None, "2.2.2.2", now).is_ok());
+        for _ in 0..100 {
+            assert!(
+                control
+                    .check(CrawlerClass::Human, None, "3.3.3.3", now)
+                    .is_ok()
+            );
+        }
+        assert!(control.is_cache_only(ai));
+        assert!(!control.is_cache_only(CrawlerClass::SearchBot));
+    }
+
+    #[tokio::test]
+    async fn test_cache_only_miss_outside_a_request() {
+        assert!(cache_only_miss().is_none());
+        let response = CURRENT_CRAWLER
+            .scope(CrawlerClass::AiCrawler, async { cache_only_miss() })
+            .await;
+        assert_eq!(
+            response.map(|r| r.status()),
+            Some(StatusCode::SERVICE_UNAVAILABLE)
+        );
+        let human = CURRENT_CRAWLER
+            .scope(CrawlerClass::Human, async { cache_only_miss() })
+            .await;
+        assert!(human.is_none());
+    }
+}
@@ -0,0 +1,202 @@
+//! Per-Key Cache Usage Tracking
+//!
+//! `CacheManagerStats` only reports totals, so page cache helpers record hits, misses
+//! and stored sizes per key here. `/admin/cache/stats` uses it for top keys and for
+//! entry count / memory estimates per tier.
+//!
+//! Tracking is bounded to `MAX_TRACKED_KEYS`; keys seen after that are only counted
+//! in `untracked_events`.
+
+use dashmap::DashMap;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Max distinct keys tracked (matches the L1 capacity)
+const MAX_TRACKED_KEYS: usize = 1000;
+
+/// Process-wide tracker used by the cache helpers
+static CACHE_KEY_STATS: LazyLock<CacheKeyStats> =
+    LazyLock::new(|| CacheKeyStats::new(MAX_TRACKED_KEYS));
+
+/// Global per-key cache usage tracker
+#[must_use]
+pub fn cache_key_stats() -> &'static CacheKeyStats {
+    &CACHE_KEY_STATS
+}
+
+#[derive(Debug, Default)]
+struct KeyCounters {
+    hits: u64,
+    misses: u64,
+    size_bytes: usize,
+    /// Last write time and TTL, used to estimate whether the entry is still cached
+    stored: Option<(Instant, Duration)>,
+}
+
+/// Usage snapshot of one cache key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyUsage {
+    pub key: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub size_bytes: usize,
+}
+
+/// Estimated number of live entries and their payload size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Residency {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+/// Bounded per-key hit/miss/size counters
+pub struct CacheKeyStats {
+    keys: DashMap<String, KeyCounters>,
+    max_keys: usize,
+    untracked_events: AtomicU64,
+}
+
+impl CacheKeyStats {
+    /// Create a tracker for at most `max_keys` distinct keys
+    #[must_use]
+    pub fn new(max_keys: usize) -> Self {
+        Self {
+            keys: DashMap::new(),
+            max_keys,
+            untracked_events: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a cache hit
+    pub fn record_hit(&self, key: &str) {
+        self.update(key, |counters| counters.hits += 1);
+    }
+
+    /// Record a cache miss
+    pub fn record_miss(&self, key: &str) {
+        self.update(key, |counters| counters.misses += 1);
+    }
+
+    /// Record a write of `size_bytes` expiring after `ttl`
+    pub fn record_set(&self, key: &str, size_bytes: usize, ttl: Duration) {
+        self.update(key, |counters| {
+            counters.size_bytes = size_bytes;
+            counters.stored = Some((Instant::now(), ttl));
+        });
+    }
+
+    /// Events dropped because the key limit was reached
+    #[must_use]
+    pub fn untracked_events(&self) -> u64 {
+        self.untracked_events.load(Ordering::Relaxed)
+    }
+
+    /// Most requested keys (hits + misses), descending
+    #[must_use]
+    pub fn top_keys(&self, limit: usize) -> Vec<KeyUsage> {
+        let mut usage: Vec<KeyUsage> = self
+            .keys
+            .iter()
+            .map(|entry| KeyUsage {
+                key: entry.key().clone(),
+                hits: entry.hits,
+                misses: entry.misses,
+                size_bytes: entry.size_bytes,
+            })
+            .collect();
+        usage.sort_by(|a, b| {
+            (b.hits + b.misses)
+                .cmp(&(a.hits + a.misses))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        usage.truncate(limit);
+        usage
+    }
+
+    /// Estimate entries still cached in a tier whose own TTL is capped at `max_ttl`
+    #[must_use]
+    pub fn residency(&self, max_ttl: Option<Duration>) -> Residency {
+        self.residency_at(Instant::now(), max_ttl)
+    }
+
+    fn residency_at(&self, now: Instant, max_ttl: Option<Duration>) -> Residency {
+        self.keys
+            .iter()
+            .filter_map(|entry| {
+                let (stored_at, ttl) = entry.stored?;
+                let ttl = max_ttl.map_or(ttl, |max| ttl.min(max));
+                (now.saturating_duration_since(stored_at) < ttl).then_some(entry.size_bytes)
+            })
+            .fold(Residency::default(), |acc, size| Residency {
+                entries: acc.entries + 1,
+                bytes: acc.bytes + u64::try_from(size).unwrap_or(u64::MAX),
+            })
+    }
+
+    fn update(&self, key: &str, apply: impl FnOnce(&mut KeyCounters)) {
+        if let Some(mut counters) = self.keys.get_mut(key) {
+            apply(&mut counters);
+            return;
+        }
+        if self.keys.len() >= self.max_keys {
+            self.untracked_events.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        apply(&mut self.keys.entry(key.to_string()).or_default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_keys_ordered_by_requests() {
+        let stats = CacheKeyStats::new(10);
+        stats.record_hit("a");
+        stats.record_hit("b");
+        stats.record_hit("b");
+        stats.record_miss("c");
+        stats.record_miss("c");
+        stats.record_miss("c");
+
+        let top: Vec<String> = stats.top_keys(2).into_iter().map(|k| k.key).collect();
+        assert_eq!(top, vec!["c".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_key_limit_counts_untracked() {
+        let stats = CacheKeyStats::new(1);
+        stats.record_hit("a");
+        stats.record_hit("b");
+        stats.record_hit("a");
+
+        assert_eq!(stats.top_keys(10).len(), 1);
+        assert_eq!(stats.untracked_events(), 1);
+    }
+
+    #[test]
+    fn test_residency_respects_tier_ttl() {
+        let stats = CacheKeyStats::new(10);
+        stats.record_set("short", 100, Duration::from_mins(5));
+        stats.record_set("long", 50, Duration::from_hours(1));
+        stats.record_miss("never_stored");
+
+        let later = Instant::now() + Duration::from_mins(10);
+        assert_eq!(
+            stats.residency_at(later, None),
+            Residency {
+                entries: 1,
+                bytes: 50
+            }
+        );
+        assert_eq!(
+            stats
+                .residency_at(later, Some(Duration::from_mins(2)))
+                .entries,
+            0
+        );
+        assert_eq!(stats.residency(None).entries, 2);
+    }
+}
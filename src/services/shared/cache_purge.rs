@@ -0,0 +1,223 @@
+//! Targeted Cache Purge
+//!
+//! Resolves an operator purge request (exact key, key prefix or report id) into the
+//! cache keys and patterns to invalidate across L1 and L2 (on every instance), including the
+//! stale-while-revalidate copies, so a stale page can be fixed without a full flush.
+
+use multi_tier_cache::CacheManager;
+use tracing::{info, warn};
+
+use super::error::{Layer5Error, Layer5Result};
+use super::stale_while_revalidate::stale_key;
+use crate::cache_invalidation::CacheInvalidationBus;
+use crate::services::data_communication::{report_route_cache_key, versioned_key};
+
+/// What an operator asked to purge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CachePurgeTarget {
+    /// One exact cache key
+    Key(String),
+    /// All keys starting with a prefix
+    Prefix(String),
+    /// Every cached page/metadata entry for a report
+    Report {
+        report_id: i32,
+        /// Also purge `-1` ("latest report") entries
+        is_latest: bool,
+    },
+}
+
+/// A single invalidation to run against the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PurgeOperation {
+    Exact(String),
+    Pattern(String),
+}
+
+impl PurgeOperation {
+    /// Key or pattern, for responses and logs
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Exact(key) | Self::Pattern(key) => key,
+        }
+    }
+}
+
+impl CachePurgeTarget {
+    /// Build a target from request fields - exactly one must be set
+    ///
+    /// # Errors
+    ///
+    /// Returns `Layer5Error::InvalidInput` if zero or several fields are set,
+    /// or a key/prefix is empty or contains glob characters
+    pub fn from_parts(
+        key: Option<String>,
+        prefix: Option<String>,
+        report_id: Option<i32>,
+        latest_report_id: i32,
+    ) -> Layer5Result<Self> {
+        match (key, prefix, report_id) {
+            (Some(key), None, None) => Ok(Self::Key(validate_key_part(&key)?)),
+            (None, Some(prefix), None) => Ok(Self::Prefix(validate_key_part(&prefix)?)),
+            (None, None, Some(report_id)) => Ok(Self::Report {
+                report_id,
+                is_latest: report_id == latest_report_id,
+            }),
+            _ => Err(Layer5Error::InvalidInput(
+                "Exactly one of key, prefix or report_id is required".to_string(),
+            )),
+        }
+    }
+
+    /// Cache operations covering the target and its stale copies
+    #[must_use]
+    pub fn operations(&self) -> Vec<PurgeOperation> {
+        match self {
+            Self::Key(key) => vec![
+                PurgeOperation::Exact(key.clone()),
+                PurgeOperation::Exact(stale_key(key)),
+            ],
+            Self::Prefix(prefix) => vec![
+                PurgeOperation::Pattern(format!("{prefix}*")),
+                PurgeOperation::Pattern(format!("{}*", stale_key(prefix))),
+            ],
+            Self::Report {
+                report_id,
+                is_latest,
+            } => {
+                let mut operations = report_operations(*report_id);
+                // Slug and indexing flag (keyed by real ids only)
+                operations.push(PurgeOperation::Exact(report_route_cache_key(*report_id)));
+                if *is_latest {
+                    operations.extend(report_operations(-1));
+                }
+                operations
+            }
+        }
+    }
+}
+
+/// Keys holding rendered output and metadata for one report id
+fn report_operations(report_id: i32) -> Vec<PurgeOperation> {
+    let dsd_prefix = versioned_key(&format!("compressed_report_dsd_{report_id}_"));
+    vec![
+        PurgeOperation::Pattern(format!("{dsd_prefix}*")),
+        PurgeOperation::Pattern(format!("{}*", stale_key(&dsd_prefix))),
+        PurgeOperation::Exact(versioned_key(&format!("compressed_report_{report_id}"))),
+        PurgeOperation::Exact(format!("report_last_modified_{report_id}")),
+    ]
+}
+
+/// Reject empty keys and glob characters (use `/admin/cache/clear` for a full flush)
+fn validate_key_part(value: &str) -> Layer5Result<String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(Layer5Error::InvalidInput(
+            "Cache key or prefix must not be empty".to_string(),
+        ));
+    }
+    if value.contains(['*', '?', '[', ']']) {
+        return Err(Layer5Error::InvalidInput(
+            "Cache key or prefix must not contain glob characters".to_string(),
+        ));
+    }
+    Ok(value)
+}
+
+/// Run the purge against all cache tiers and announce it on the invalidation bus
+///
+/// Returns the keys/patterns that were invalidated.
+///
+/// # Errors
+///
+/// Returns `Layer5Error::Cache` if any invalidation fails
+pub async fn purge_cache(
+    cache_manager: &CacheManager,
+    cache_bus: &CacheInvalidationBus,
+    target: &CachePurgeTarget,
+) -> Layer5Result<Vec<String>> {
+    let operations = target.operations();
+    let mut purged = Vec::with_capacity(operations.len());
+
+    for operation in operations {
+        let result = match &operation {
+            PurgeOperation::Exact(key) => cache_manager.invalidate(key).await,
+            PurgeOperation::Pattern(pattern) => cache_manager.invalidate_pattern(pattern).await,
+        };
+        if let Err(e) = result {
+            warn!("⚠️ Cache purge failed for {}: {}", operation.as_str(), e);
+            return Err(Layer5Error::Cache(format!(
+                "Failed to purge {}: {e}",
+                operation.as_str()
+            )));
+        }
+        match &operation {
+            PurgeOperation::Exact(key) => cache_bus.publish_remove(key).await,
+            PurgeOperation::Pattern(pattern) => cache_bus.publish_pattern(pattern).await,
+        }
+        purged.push(operation.as_str().to_string());
+    }
+
+    info!("🧹 Cache purge completed: {:?}", purged);
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_requires_exactly_one_field() {
+        assert!(CachePurgeTarget::from_parts(None, None, None, 1).is_err());
+        assert!(CachePurgeTarget::from_parts(Some("a".into()), Some("b".into()), None, 1).is_err());
+        assert!(CachePurgeTarget::from_parts(Some("*".into()), None, None, 1).is_err());
+        assert!(CachePurgeTarget::from_parts(None, Some("  ".into()), None, 1).is_err());
+        assert_eq!(
+            CachePurgeTarget::from_parts(None, None, Some(5), 5).ok(),
+            Some(CachePurgeTarget::Report {
+                report_id: 5,
+                is_latest: true
+            })
+        );
+    }
+
+    #[test]
+    fn test_prefix_covers_stale_copies() {
+        let operations = CachePurgeTarget::Prefix("crypto_reports_list_page_".into()).operations();
+        assert_eq!(
+            operations,
+            vec![
+                PurgeOperation::Pattern("crypto_reports_list_page_*".into()),
+                PurgeOperation::Pattern("swr:crypto_reports_list_page_*".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_latest_report_also_purges_latest_alias() {
+        let target = CachePurgeTarget::Report {
+            report_id: 42,
+            is_latest: true,
+        };
+        let keys: Vec<String> = target
+            .operations()
+            .iter()
+            .map(|op| op.as_str().to_string())
+            .collect();
+
+        let dsd_42 = versioned_key("compressed_report_dsd_42_*");
+        assert!(keys.contains(&dsd_42));
+        assert!(keys.contains(&format!("swr:{dsd_42}")));
+        assert!(keys.contains(&"report_last_modified_42".to_string()));
+        assert!(keys.contains(&versioned_key("compressed_report_dsd_-1_*")));
+        assert!(keys.contains(&"report_route_42".to_string()));
+        assert!(!keys.contains(&"report_route_-1".to_string()));
+
+        let not_latest = CachePurgeTarget::Report {
+            report_id: 42,
+            is_latest: false,
+        };
+        assert_eq!(not_latest.operations().len(), 5);
+    }
+}
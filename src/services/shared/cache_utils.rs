@@ -9,11 +9,14 @@ use axum::{
     response::IntoResponse,
 };
 use base64::{Engine, prelude::BASE64_STANDARD};
+use chrono::{DateTime, Utc};
 use flate2::{Compression, write::GzEncoder};
 use multi_tier_cache::{Bytes, CacheManager, CacheStrategy};
 use std::io::Write;
 use tracing::{debug, info, warn};
 
+use super::cache_key_stats::cache_key_stats;
+
 /// Compress XML/JSON string to gzip format
 ///
 /// # Errors
@@ -32,7 +35,11 @@ pub async fn try_get_cached_compressed(
     cache_manager: &CacheManager,
     cache_key: &str,
 ) -> Option<Vec<u8>> {
-    let cached_value = cache_manager.get(cache_key).await.ok()??;
+    let Some(cached_value) = cache_manager.get(cache_key).await.ok().flatten() else {
+        cache_key_stats().record_miss(cache_key);
+        return None;
+    };
+    cache_key_stats().record_hit(cache_key);
 
     // To support transition from legacy Base64 JSON format:
     if let Ok(base64_string) = serde_json::from_slice::<String>(&cached_value)
@@ -62,30 +69,65 @@ pub async fn cache_compressed_data(
     label: &str,
 ) {
     let bytes = Bytes::from(compressed_data.to_vec());
-    match cache_manager
+    let ttl = strategy.to_duration();
+    if let Err(e) = cache_manager
         .set_with_strategy(cache_key, bytes, strategy)
         .await
     {
-        Err(e) => {
-            warn!("⚠️ Cache: Failed to cache {label} at {cache_key}: {e}");
-        }
-        _ => {
-            info!("💾 Cache: {label} cached at {cache_key} successfully");
-        }
+        warn!("⚠️ Cache: Failed to cache {label} at {cache_key}: {e}");
+    } else {
+        cache_key_stats().record_set(cache_key, compressed_data.len(), ttl);
+        info!("💾 Cache: {label} cached at {cache_key} successfully");
+    }
+}
+
+/// Cache key of the `Last-Modified` timestamp stored next to `cache_key`
+fn last_modified_key(cache_key: &str) -> String {
+    format!("{cache_key}_last_modified")
+}
+
+/// Read the `Last-Modified` timestamp cached next to a compressed entry
+pub async fn get_cached_last_modified(
+    cache_manager: &CacheManager,
+    cache_key: &str,
+) -> Option<DateTime<Utc>> {
+    let cached_value = cache_manager
+        .get(&last_modified_key(cache_key))
+        .await
+        .ok()??;
+    let timestamp = std::str::from_utf8(&cached_value).ok()?.parse().ok()?;
+    DateTime::from_timestamp(timestamp, 0)
+}
+
+/// Cache the `Last-Modified` timestamp of a compressed entry, with the entry's strategy
+///
+/// Failures are logged only - conditional GET is an optimization.
+pub async fn cache_last_modified(
+    cache_manager: &CacheManager,
+    cache_key: &str,
+    last_modified: &DateTime<Utc>,
+    strategy: CacheStrategy,
+) {
+    let bytes = Bytes::from(last_modified.timestamp().to_string());
+    if let Err(e) = cache_manager
+        .set_with_strategy(&last_modified_key(cache_key), bytes, strategy)
+        .await
+    {
+        warn!("⚠️ Cache: Failed to cache Last-Modified of {cache_key}: {e}");
     }
 }
 
 /// Build a gzip-compressed response with standard headers
+///
+/// `Cache-Control` is left to the route's cache policy.
 pub fn build_standard_compressed_response(
     compressed_data: Vec<u8>,
     content_type: &'static str,
-    max_age: u32,
     cache_status: &str,
 ) -> Response<Body> {
     Response::builder()
         .status(StatusCode::OK)
         .header(header::CONTENT_TYPE, content_type)
-        .header(header::CACHE_CONTROL, format!("public, max-age={max_age}"))
         .header(header::CONTENT_ENCODING, "gzip")
         .header("x-cache", cache_status)
         .body(Body::from(compressed_data))
@@ -0,0 +1,129 @@
+//! Client IP Resolution
+//!
+//! Behind a reverse proxy the socket peer is the proxy, and the client is the entry
+//! the proxy appended to `X-Forwarded-For`. `TRUSTED_PROXY_HOPS` (default 0) is the
+//! number of proxies in front of the server: the client is taken that many entries
+//! from the right, so entries a client forges at the left are ignored. The header
+//! is only read when the socket peer is one of the `TRUSTED_PROXIES`; otherwise,
+//! and with `0` hops, the socket peer is used, so a client connecting directly
+//! cannot pick its own address.
+
+use axum::{extract::ConnectInfo, extract::Request, http::HeaderMap};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+
+use super::public_origin::TrustedProxies;
+
+const DEFAULT_TRUSTED_PROXY_HOPS: usize = 0;
+
+/// Number of reverse proxies whose `X-Forwarded-For` entries are trusted
+fn trusted_proxy_hops() -> usize {
+    static HOPS: OnceLock<usize> = OnceLock::new();
+    *HOPS.get_or_init(|| {
+        std::env::var("TRUSTED_PROXY_HOPS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_TRUSTED_PROXY_HOPS)
+    })
+}
+
+/// Client address of `request`: from `X-Forwarded-For` when it came through a
+/// trusted proxy, else the socket peer (when the server was started with connect info)
+#[must_use]
+pub fn client_ip(request: &Request) -> Option<IpAddr> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    resolve_client_ip(
+        request.headers(),
+        peer,
+        trusted_proxy_hops(),
+        TrustedProxies::current(),
+    )
+}
+
+fn resolve_client_ip(
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    hops: usize,
+    proxies: &TrustedProxies,
+) -> Option<IpAddr> {
+    if hops == 0 || !proxies.trusts(peer) {
+        return peer;
+    }
+    // Several X-Forwarded-For headers form one list, in order
+    let forwarded: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    forwarded
+        .len()
+        .checked_sub(hops)
+        .and_then(|index| forwarded.get(index))
+        .and_then(|entry| entry.parse().ok())
+        .or(peer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forwarded_client_ip() -> Result<(), Box<dyn std::error::Error>> {
+        let proxies = TrustedProxies::parse("10.0.0.0/8");
+        let peer: IpAddr = "10.0.0.1".parse()?;
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            resolve_client_ip(&headers, Some(peer), 1, &proxies),
+            Some(peer)
+        );
+
+        headers.insert("x-forwarded-for", "6.6.6.6, 203.0.113.7".parse()?);
+        assert_eq!(
+            resolve_client_ip(&headers, Some(peer), 1, &proxies),
+            Some("203.0.113.7".parse()?)
+        );
+        assert_eq!(
+            resolve_client_ip(&headers, Some(peer), 2, &proxies),
+            Some("6.6.6.6".parse()?)
+        );
+        // Fewer entries than proxies: fall back to the peer
+        assert_eq!(
+            resolve_client_ip(&headers, Some(peer), 3, &proxies),
+            Some(peer)
+        );
+        assert_eq!(
+            resolve_client_ip(&headers, Some(peer), 0, &proxies),
+            Some(peer)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_untrusted_peer_cannot_forge_its_address() -> Result<(), Box<dyn std::error::Error>> {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "10.0.0.1".parse()?);
+        let direct: IpAddr = "198.51.100.9".parse()?;
+        assert_eq!(
+            resolve_client_ip(
+                &headers,
+                Some(direct),
+                1,
+                &TrustedProxies::parse("10.0.0.0/8")
+            ),
+            Some(direct)
+        );
+        // No TRUSTED_PROXIES: the header is never read
+        assert_eq!(
+            resolve_client_ip(&headers, Some(direct), 1, &TrustedProxies::default()),
+            Some(direct)
+        );
+        assert_eq!(DEFAULT_TRUSTED_PROXY_HOPS, 0);
+        Ok(())
+    }
+}
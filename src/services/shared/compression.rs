@@ -3,9 +3,11 @@
 //! Shared compression logic for HTTP responses across Layer 5 components.
 //! Eliminates duplicate compression code in handlers.
 
+use axum::body::{Body, Bytes};
 use flate2::{Compression, write::GzEncoder};
-use std::io::Write;
-use tracing::info;
+use std::io::{self, Write};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
 
 use super::error::{Layer5Error, Layer5Result};
 
@@ -113,6 +115,75 @@ pub fn compress_html(html: &str) -> Layer5Result<Vec<u8>> {
     compress_html_to_gzip(html).map(|(data, _)| data)
 }
 
+/// Chunk size for streamed gzip output
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// `Write` adapter that forwards compressed chunks to an HTTP body channel
+struct ChannelWriter {
+    tx: mpsc::Sender<io::Result<Bytes>>,
+    buffer: Vec<u8>,
+}
+
+impl ChannelWriter {
+    fn send_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = Bytes::from(std::mem::replace(
+            &mut self.buffer,
+            Vec::with_capacity(STREAM_CHUNK_SIZE),
+        ));
+        self.tx
+            .blocking_send(Ok(chunk))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "client disconnected"))
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= STREAM_CHUNK_SIZE {
+            self.send_buffer()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_buffer()
+    }
+}
+
+/// Compress HTML to gzip as a streamed response body
+///
+/// Used for oversized content: compression runs on a blocking thread and emits
+/// 64KB chunks as they are produced, so the full compressed payload is never
+/// buffered in memory and the client starts receiving bytes immediately.
+pub fn stream_html_to_gzip(html: String) -> Body {
+    let (tx, mut rx) = mpsc::channel::<io::Result<Bytes>>(4);
+
+    tokio::task::spawn_blocking(move || {
+        let writer = ChannelWriter {
+            tx: tx.clone(),
+            buffer: Vec::with_capacity(STREAM_CHUNK_SIZE),
+        };
+        let mut encoder = GzEncoder::new(writer, Compression::default());
+
+        let result = html
+            .as_bytes()
+            .chunks(STREAM_CHUNK_SIZE)
+            .try_for_each(|chunk| encoder.write_all(chunk))
+            .and_then(|()| encoder.finish())
+            .and_then(|mut writer| writer.flush());
+
+        if let Err(e) = result {
+            warn!("⚠️ Streaming gzip compression aborted: {}", e);
+            let _ = tx.blocking_send(Err(e));
+        }
+    });
+
+    Body::from_stream(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +224,20 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_stream_html_to_gzip_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Read;
+
+        // Larger than one chunk to exercise multi-chunk streaming
+        let html = "<p>oversized report content</p>".repeat(10_000);
+        let body = stream_html_to_gzip(html.clone());
+        let compressed = axum::body::to_bytes(body, usize::MAX).await?;
+
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(compressed.as_ref()).read_to_string(&mut decoded)?;
+        assert_eq!(decoded, html);
+
+        Ok(())
+    }
 }
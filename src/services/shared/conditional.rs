@@ -0,0 +1,270 @@
+//! Conditional GET Utilities
+//!
+//! Helpers for `Last-Modified` / `If-Modified-Since` and `ETag` / `If-None-Match`
+//! revalidation (RFC 9110 §13.1). Lets browsers, CDNs and feed pollers revalidate
+//! cached reports, feeds and sitemaps with a bodiless `304 Not Modified` instead of
+//! downloading the gzip payload again.
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, TimeZone, Utc};
+
+use super::cache_utils::build_standard_compressed_response;
+
+/// Format a timestamp as an IMF-fixdate HTTP date (e.g. `Sun, 06 Nov 1994 08:49:37 GMT`)
+#[must_use]
+pub fn format_http_date(timestamp: &DateTime<Utc>) -> String {
+    timestamp.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Parse an HTTP date header value
+///
+/// Accepts IMF-fixdate and any other RFC 2822 compatible form. Returns `None`
+/// for malformed values so callers fall back to a full response.
+#[must_use]
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Truncate a timestamp to whole seconds (HTTP dates carry no sub-second precision)
+fn truncate_to_seconds(timestamp: &DateTime<Utc>) -> DateTime<Utc> {
+    Utc.timestamp_opt(timestamp.timestamp(), 0)
+        .single()
+        .unwrap_or(*timestamp)
+}
+
+/// Check whether the request's `If-Modified-Since` header allows a 304 response
+///
+/// Returns `true` only when the header parses and the resource has not been
+/// modified after that instant.
+#[must_use]
+pub fn is_not_modified(headers: &HeaderMap, last_modified: &DateTime<Utc>) -> bool {
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_http_date)
+        .is_some_and(|since| truncate_to_seconds(last_modified) <= since)
+}
+
+/// Build a bodiless `304 Not Modified` response
+///
+/// Repeats `Last-Modified` (and a per-response `Cache-Control` override) so caches can
+/// refresh their stored metadata; otherwise the route's cache policy adds `Cache-Control`.
+#[must_use]
+pub fn build_not_modified_response(
+    cache_control: Option<&'static str>,
+    last_modified: &DateTime<Utc>,
+) -> Response {
+    let mut builder = Response::builder()
+        .status(StatusCode::NOT_MODIFIED)
+        .header("last-modified", format_http_date(last_modified));
+    if let Some(cache_control) = cache_control {
+        builder = builder.header("cache-control", cache_control);
+    }
+    builder
+        .body(Body::empty())
+        .unwrap_or_else(|_| StatusCode::NOT_MODIFIED.into_response())
+}
+
+/// Weak entity tag of a gzip payload (`W/"<16 hex of its BLAKE3 hash>"`)
+///
+/// Hashing the served bytes also catches reports edited in place, which leave
+/// the newest report timestamp unchanged.
+#[must_use]
+pub fn content_etag(compressed_data: &[u8]) -> String {
+    let hash = blake3::hash(compressed_data).to_hex();
+    format!("W/\"{}\"", hash.get(..16).unwrap_or_default())
+}
+
+/// Check whether the request's `If-None-Match` header lists `etag` (or is `*`)
+///
+/// Uses the weak comparison of RFC 9110 §8.8.3.2, as required for `If-None-Match`.
+#[must_use]
+pub fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
+
+/// Serve a cached gzip payload with `ETag` (and `Last-Modified` when known),
+/// answering `304 Not Modified` to a matching conditional request
+///
+/// `If-None-Match` takes precedence; `If-Modified-Since` is only evaluated
+/// without it (RFC 9110 §13.2.2).
+#[must_use]
+pub fn conditional_compressed_response(
+    headers: &HeaderMap,
+    compressed_data: Vec<u8>,
+    content_type: &'static str,
+    cache_status: &str,
+    last_modified: Option<&DateTime<Utc>>,
+) -> Response {
+    let etag = content_etag(&compressed_data);
+    let not_modified = if headers.contains_key(header::IF_NONE_MATCH) {
+        etag_matches(headers, &etag)
+    } else {
+        last_modified.is_some_and(|last_modified| is_not_modified(headers, last_modified))
+    };
+
+    let mut response = match last_modified {
+        Some(last_modified) if not_modified => build_not_modified_response(None, last_modified),
+        None if not_modified => StatusCode::NOT_MODIFIED.into_response(),
+        _ => {
+            let mut response =
+                build_standard_compressed_response(compressed_data, content_type, cache_status);
+            if let Some(value) = last_modified.and_then(|last_modified| {
+                HeaderValue::from_str(&format_http_date(last_modified)).ok()
+            }) {
+                response.headers_mut().insert(header::LAST_MODIFIED, value);
+            }
+            response
+        }
+    };
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_time() -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
+        Ok(DateTime::parse_from_rfc3339("2025-01-15T08:30:45.250Z")?.with_timezone(&Utc))
+    }
+
+    #[test]
+    fn test_format_and_parse_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let time = sample_time()?;
+        let formatted = format_http_date(&time);
+        assert_eq!(formatted, "Wed, 15 Jan 2025 08:30:45 GMT");
+
+        let parsed = parse_http_date(&formatted).ok_or("failed to parse http date")?;
+        assert_eq!(parsed.timestamp(), time.timestamp());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_not_modified() -> Result<(), Box<dyn std::error::Error>> {
+        let time = sample_time()?;
+        let mut headers = HeaderMap::new();
+        assert!(!is_not_modified(&headers, &time));
+
+        // Same second as Last-Modified (sub-second precision ignored)
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_str(&format_http_date(&time))?,
+        );
+        assert!(is_not_modified(&headers, &time));
+
+        // Resource changed after the client's copy
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Tue, 14 Jan 2025 08:30:45 GMT"),
+        );
+        assert!(!is_not_modified(&headers, &time));
+
+        // Malformed header falls back to full response
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_static("yesterday"),
+        );
+        assert!(!is_not_modified(&headers, &time));
+        Ok(())
+    }
+
+    #[test]
+    fn test_not_modified_response_has_no_body() -> Result<(), Box<dyn std::error::Error>> {
+        let time = sample_time()?;
+        let response = build_not_modified_response(Some("public, max-age=300"), &time);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            response.headers().get("last-modified"),
+            Some(&HeaderValue::from_static("Wed, 15 Jan 2025 08:30:45 GMT"))
+        );
+        assert!(response.headers().get("content-encoding").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_etag_matches() -> Result<(), Box<dyn std::error::Error>> {
+        let etag = content_etag(b"feed");
+        assert!(etag.starts_with("W/\"") && etag.len() == 20);
+        assert_ne!(etag, content_etag(b"feed, edited"));
+
+        let mut headers = HeaderMap::new();
+        assert!(!etag_matches(&headers, &etag));
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"other\", {}", etag.trim_start_matches("W/")))?,
+        );
+        assert!(etag_matches(&headers, &etag));
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"other\""));
+        assert!(!etag_matches(&headers, &etag));
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(etag_matches(&headers, &etag));
+        Ok(())
+    }
+
+    #[test]
+    fn test_conditional_compressed_response() -> Result<(), Box<dyn std::error::Error>> {
+        let time = sample_time()?;
+        let body = b"gzip".to_vec();
+        let etag = content_etag(&body);
+
+        let response = conditional_compressed_response(
+            &HeaderMap::new(),
+            body.clone(),
+            "application/rss+xml",
+            "HIT",
+            Some(&time),
+        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::ETAG),
+            Some(&HeaderValue::from_str(&etag)?)
+        );
+        assert_eq!(
+            response.headers().get(header::LAST_MODIFIED),
+            Some(&HeaderValue::from_static("Wed, 15 Jan 2025 08:30:45 GMT"))
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&etag)?);
+        let response =
+            conditional_compressed_response(&headers, body.clone(), "text/xml", "HIT", None);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+
+        // A stale ETag wins over a current If-Modified-Since
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("W/\"stale\""),
+        );
+        headers.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_str(&format_http_date(&time))?,
+        );
+        let response =
+            conditional_compressed_response(&headers, body.clone(), "text/xml", "HIT", Some(&time));
+        assert_eq!(response.status(), StatusCode::OK);
+
+        headers.remove(header::IF_NONE_MATCH);
+        let response =
+            conditional_compressed_response(&headers, body, "text/xml", "HIT", Some(&time));
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+//! CORS Policy for the JSON APIs
+//!
+//! The front-end and websocket services run on other origins, so browsers need CORS
+//! headers to call `/api/*`. `CORS_ALLOWED_ORIGINS` (comma-separated origins, or `*`)
+//! enables the policy; unset or empty sends no CORS headers at all. Methods, request
+//! headers and the preflight cache lifetime come from `CORS_ALLOWED_METHODS`,
+//! `CORS_ALLOWED_HEADERS` and `CORS_MAX_AGE_SECS`.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::warn;
+
+const DEFAULT_METHODS: &[Method] = &[Method::GET, Method::HEAD, Method::OPTIONS];
+const DEFAULT_HEADERS: &[&str] = &["accept", "authorization", "content-type", "x-api-key"];
+const DEFAULT_MAX_AGE: Duration = Duration::from_mins(10);
+/// Response headers scripts on other origins may read
+const EXPOSED_HEADERS: &[&str] = &["x-request-id", "x-cache"];
+
+/// Origins allowed to call the APIs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorsOrigins {
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+/// CORS settings for `/api/*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// `None` disables CORS
+    pub origins: Option<CorsOrigins>,
+    pub methods: Vec<Method>,
+    pub headers: Vec<HeaderName>,
+    pub max_age: Duration,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            origins: None,
+            methods: DEFAULT_METHODS.to_vec(),
+            headers: DEFAULT_HEADERS
+                .iter()
+                .map(|name| HeaderName::from_static(name))
+                .collect(),
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Build from the raw variable values (`None` = unset); invalid entries are skipped
+    #[must_use]
+    pub fn parse(
+        origins: Option<&str>,
+        methods: Option<&str>,
+        headers: Option<&str>,
+        max_age_secs: Option<&str>,
+    ) -> Self {
+        let defaults = Self::default();
+        let origins = origins.and_then(|value| {
+            let entries: Vec<&str> = split_list(value).collect();
+            if entries.contains(&"*") {
+                return Some(CorsOrigins::Any);
+            }
+            let list: Vec<HeaderValue> = parse_entries("CORS_ALLOWED_ORIGINS", entries, |origin| {
+                HeaderValue::from_str(origin.trim_end_matches('/')).ok()
+            });
+            (!list.is_empty()).then_some(CorsOrigins::List(list))
+        });
+        Self {
+            origins,
+            methods: methods.map_or(defaults.methods, |value| {
+                parse_entries("CORS_ALLOWED_METHODS", split_list(value), |method| {
+                    Method::from_bytes(method.to_ascii_uppercase().as_bytes()).ok()
+                })
+            }),
+            headers: headers.map_or(defaults.headers, |value| {
+                parse_entries("CORS_ALLOWED_HEADERS", split_list(value), |name| {
+                    HeaderName::from_bytes(name.as_bytes()).ok()
+                })
+            }),
+            max_age: max_age_secs
+                .and_then(|value| value.trim().parse().ok())
+                .map_or(defaults.max_age, Duration::from_secs),
+        }
+    }
+
+    /// `CORS_ALLOWED_ORIGINS`, `CORS_ALLOWED_METHODS`, `CORS_ALLOWED_HEADERS` and `CORS_MAX_AGE_SECS`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::parse(
+            var("CORS_ALLOWED_ORIGINS").as_deref(),
+            var("CORS_ALLOWED_METHODS").as_deref(),
+            var("CORS_ALLOWED_HEADERS").as_deref(),
+            var("CORS_MAX_AGE_SECS").as_deref(),
+        )
+    }
+
+    /// Process-wide settings, read on first use
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static CONFIG: OnceLock<CorsConfig> = OnceLock::new();
+        CONFIG.get_or_init(Self::from_env)
+    }
+
+    /// The CORS layer, or `None` when no origin is allowed
+    #[must_use]
+    pub fn layer(&self) -> Option<CorsLayer> {
+        let allow_origin = match self.origins.as_ref()? {
+            CorsOrigins::Any => AllowOrigin::from(Any),
+            CorsOrigins::List(origins) => AllowOrigin::list(origins.iter().cloned()),
+        };
+        Some(
+            CorsLayer::new()
+                .allow_origin(allow_origin)
+                .allow_methods(self.methods.clone())
+                .allow_headers(self.headers.clone())
+                .expose_headers(
+                    EXPOSED_HEADERS
+                        .iter()
+                        .map(|name| HeaderName::from_static(name))
+                        .collect::<Vec<_>>(),
+                )
+                .max_age(self.max_age),
+        )
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Parse every entry, logging the ones that are invalid
+fn parse_entries<'a, T>(
+    variable: &str,
+    entries: impl IntoIterator<Item = &'a str>,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Vec<T> {
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let parsed = parse(entry);
+            if parsed.is_none() {
+                warn!("⚠️ Ignoring invalid {} entry: {}", variable, entry);
+            }
+            parsed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, extract::Request, http::header, routing::get};
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_parse_config() {
+        let config = CorsConfig::parse(
+            Some("https://app.example.com/, https://ws.example.com"),
+            Some("get, post"),
+            Some("content-type, bad header"),
+            Some("120"),
+        );
+        assert_eq!(
+            config.origins,
+            Some(CorsOrigins::List(vec![
+                HeaderValue::from_static("https://app.example.com"),
+                HeaderValue::from_static("https://ws.example.com"),
+            ]))
+        );
+        assert_eq!(config.methods, [Method::GET, Method::POST]);
+        assert_eq!(config.headers, [HeaderName::from_static("content-type")]);
+        assert_eq!(config.max_age, Duration::from_mins(2));
+
+        assert_eq!(
+            CorsConfig::parse(Some("https://a.example, *"), None, None, None).origins,
+            Some(CorsOrigins::Any)
+        );
+        let disabled = CorsConfig::parse(Some(" "), None, None, None);
+        assert_eq!(disabled.origins, None);
+        assert!(disabled.layer().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_and_simple_requests() -> Result<(), Box<dyn std::error::Error>> {
+        let config = CorsConfig::parse(Some("https://app.example.com"), None, None, None);
+        let layer = config.layer().ok_or("CORS should be enabled")?;
+        let app = Router::new()
+            .route("/api/health", get(|| async { "ok" }))
+            .layer(layer);
+
+        let preflight = app
+            .clone()
+            .oneshot(
+                Request::options("/api/health")
+                    .header(header::ORIGIN, "https://app.example.com")
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        let headers = preflight.headers();
+        assert_eq!(
+            headers.get(header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some(&HeaderValue::from_static("https://app.example.com"))
+        );
+        assert_eq!(
+            headers.get(header::ACCESS_CONTROL_MAX_AGE),
+            Some(&HeaderValue::from_static("600"))
+        );
+
+        let foreign = app
+            .oneshot(
+                Request::get("/api/health")
+                    .header(header::ORIGIN, "https://evil.example")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert!(
+            foreign
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+//! CSRF Protection for Form-Based Routes
+//!
+//! Double-submit cookie: pages rendering a form take a `CsrfToken` and embed it with
+//! the Tera `csrf_field(token=...)` helper; the token is also set as the
+//! `csrf_token` cookie. Mutating requests (POST, PUT, PATCH, DELETE) must then send
+//! the same value in the `csrf_token` form field or the `X-CSRF-Token` header, or
+//! they answer 403. Other sites can make a browser send the cookie but cannot read it
+//! to submit it.
+//!
+//! Requests carrying API credentials (`X-Api-Key`, `Authorization`,
+//! `X-Admin-Token`) are exempt: browsers never attach those on their own. So are
+//! `application/json` bodies sent to `/api/*`, e.g. anonymous `POST /api/subscribe`
+//! from a cross-origin front end: a cross-site form cannot send that content type,
+//! and a script needs a CORS preflight (`CORS_ALLOWED_ORIGINS`) to send it.
+
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Request},
+    http::{HeaderMap, HeaderValue, Method, StatusCode, header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::warn;
+
+use super::response_builder::{build_error_response, build_problem_response};
+use super::security::{ADMIN_TOKEN_HEADER, constant_time_compare};
+use crate::services::data_communication::API_KEY_HEADER;
+
+/// Cookie holding the token
+pub const CSRF_COOKIE: &str = "csrf_token";
+/// Header carrying the submitted token (scripts)
+pub const CSRF_HEADER: &str = "x-csrf-token";
+/// Form field carrying the submitted token (HTML forms)
+pub const CSRF_FIELD: &str = "csrf_token";
+
+/// Random bytes per token (hex-encoded in the cookie)
+const TOKEN_BYTES: usize = 32;
+
+/// The request's CSRF token, for embedding in a form
+///
+/// Taking it makes the middleware set the cookie when the visitor has none yet, so
+/// pages without forms stay cookie-free (and cacheable).
+#[derive(Debug, Clone)]
+pub struct CsrfToken {
+    value: Arc<str>,
+    used: Arc<AtomicBool>,
+}
+
+impl CsrfToken {
+    fn new(value: String) -> Self {
+        Self {
+            value: value.into(),
+            used: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Token value (hex)
+    #[must_use]
+    pub fn value(&self) -> &str {
+        self.used.store(true, Ordering::Relaxed);
+        &self.value
+    }
+
+    /// Hidden `<input>` carrying the token
+    #[must_use]
+    pub fn hidden_field(&self) -> String {
+        hidden_field(self.value())
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for CsrfToken {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Self>().cloned().ok_or_else(|| {
+            warn!("⚠️ CsrfToken requested on a route without the csrf_protect middleware");
+            build_error_response(StatusCode::INTERNAL_SERVER_ERROR, "CSRF token unavailable")
+        })
+    }
+}
+
+/// Register the `csrf_field(token=...)` function with Tera
+///
+/// `{{ csrf_field(token=csrf_token) }}` renders the hidden form field.
+pub fn register_csrf_helper(tera: &mut tera::Tera) {
+    tera.register_function(
+        "csrf_field",
+        |args: &HashMap<String, serde_json::Value>| -> tera::Result<serde_json::Value> {
+            let token = args
+                .get("token")
+                .and_then(serde_json::Value::as_str)
+                .filter(|token| is_valid_token(token))
+                .ok_or_else(|| tera::Error::msg("csrf_field needs a valid `token` argument"))?;
+            Ok(hidden_field(token).into())
+        },
+    );
+}
+
+fn hidden_field(token: &str) -> String {
+    format!("<input type=\"hidden\" name=\"{CSRF_FIELD}\" value=\"{token}\">")
+}
+
+/// Middleware enforcing the double-submit token on mutating requests
+pub async fn csrf_protect(request: Request, next: Next) -> Response {
+    let cookie_token = cookie_token(request.headers()).map(str::to_string);
+    let mut request = if requires_token(request.method(), request.uri().path(), request.headers()) {
+        let path = request.uri().path().to_string();
+        let (request, submitted) = match submitted_token(request).await {
+            Ok(parts) => parts,
+            Err(response) => return response,
+        };
+        let matches = cookie_token
+            .as_deref()
+            .zip(submitted.as_deref())
+            .is_some_and(|(cookie, submitted)| {
+                constant_time_compare(cookie.as_bytes(), submitted.as_bytes())
+            });
+        if !matches {
+            warn!("🛡️ Rejected {} without a matching CSRF token", path);
+            return build_problem_response(
+                StatusCode::FORBIDDEN,
+                "CSRF token missing or invalid",
+                "Submit the csrf_token form field or X-CSRF-Token header matching the csrf_token cookie",
+                &path,
+            );
+        }
+        request
+    } else {
+        request
+    };
+
+    let fresh = cookie_token.is_none();
+    let token = CsrfToken::new(cookie_token.unwrap_or_else(generate_token));
+    request.extensions_mut().insert(token.clone());
+    let mut response = next.run(request).await;
+
+    if fresh && token.used.load(Ordering::Relaxed) {
+        let cookie = format!(
+            "{CSRF_COOKIE}={}; Path=/; HttpOnly; Secure; SameSite=Strict",
+            token.value
+        );
+        if let Ok(value) = HeaderValue::from_str(&cookie) {
+            let headers = response.headers_mut();
+            headers.append(header::SET_COOKIE, value);
+            // A page carrying a per-visitor token must not be shared by caches
+            headers.insert(
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("private, no-store"),
+            );
+        }
+    }
+    response
+}
+
+/// Mutating methods without API credentials, other than JSON bodies to `/api/*`
+fn requires_token(method: &Method, path: &str, headers: &HeaderMap) -> bool {
+    let safe = matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    );
+    let token_authenticated = [
+        API_KEY_HEADER,
+        header::AUTHORIZATION.as_str(),
+        ADMIN_TOKEN_HEADER,
+    ]
+    .iter()
+    .any(|name| headers.contains_key(*name));
+    let json_api = path.starts_with("/api/")
+        && headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .is_some_and(|essence| essence.trim().eq_ignore_ascii_case("application/json"));
+    !safe && !token_authenticated && !json_api
+}
+
+/// Token from the header, else from an urlencoded form body (buffered and restored)
+async fn submitted_token(request: Request) -> Result<(Request, Option<String>), Response> {
+    if let Some(token) = request
+        .headers()
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+    {
+        let token = token.trim().to_string();
+        return Ok((request, Some(token)));
+    }
+    let is_form = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/x-www-form-urlencoded"));
+    if !is_form {
+        return Ok((request, None));
+    }
+
+    // The body is already capped by `enforce_request_limits`
+    let path = request.uri().path().to_string();
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, usize::MAX).await.map_err(|_| {
+        build_problem_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Request body too large",
+            "The form body could not be read",
+            &path,
+        )
+    })?;
+    // Tokens are hex, so the urlencoded field value needs no decoding
+    let token = std::str::from_utf8(&bytes).ok().and_then(|form| {
+        form.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(name, _)| *name == CSRF_FIELD)
+            .map(|(_, value)| value.to_string())
+    });
+    Ok((Request::from_parts(parts, Body::from(bytes)), token))
+}
+
+/// The `csrf_token` cookie, when it holds a well-formed token
+fn cookie_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|cookies| cookies.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| name.trim() == CSRF_COOKIE)
+        .map(|(_, value)| value.trim())
+        .filter(|token| is_valid_token(token))
+}
+
+fn is_valid_token(token: &str) -> bool {
+    token.len() == TOKEN_BYTES * 2 && token.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn generate_token() -> String {
+    let bytes: [u8; TOKEN_BYTES] = rand::random();
+    blake3::Hash::from_bytes(bytes).to_hex().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware, routing::get};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/admin/reports/new",
+                get(|token: CsrfToken| async move { token.hidden_field() })
+                    .post(|| async { "saved" }),
+            )
+            .route("/plain", get(|| async { "no form" }))
+            .route(
+                "/api/subscribe",
+                axum::routing::post(|| async { "accepted" }),
+            )
+            .layer(middleware::from_fn(csrf_protect))
+    }
+
+    fn post(token_cookie: Option<&str>) -> axum::http::request::Builder {
+        let builder = Request::post("/admin/reports/new");
+        match token_cookie {
+            Some(token) => {
+                builder.header(header::COOKIE, format!("lang=vi; {CSRF_COOKIE}={token}"))
+            }
+            None => builder,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_issued_only_to_form_pages() -> Result<(), Box<dyn std::error::Error>> {
+        let page = app()
+            .oneshot(Request::get("/admin/reports/new").body(Body::empty())?)
+            .await?;
+        let cookie = page
+            .headers()
+            .get(header::SET_COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(cookie.starts_with("csrf_token=") && cookie.contains("SameSite=Strict"));
+        assert_eq!(
+            page.headers().get(header::CACHE_CONTROL),
+            Some(&HeaderValue::from_static("private, no-store"))
+        );
+
+        let plain = app()
+            .oneshot(Request::get("/plain").body(Body::empty())?)
+            .await?;
+        assert!(plain.headers().get(header::SET_COOKIE).is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mutating_requests_need_matching_token() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let token = generate_token();
+        let other = generate_token();
+
+        let missing = app()
+            .oneshot(post(Some(&token)).body(Body::empty())?)
+            .await?;
+        assert_eq!(missing.status(), StatusCode::FORBIDDEN);
+        let mismatch = app()
+            .oneshot(
+                post(Some(&token))
+                    .header(CSRF_HEADER, &other)
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(mismatch.status(), StatusCode::FORBIDDEN);
+        let no_cookie = app()
+            .oneshot(post(None).header(CSRF_HEADER, &token).body(Body::empty())?)
+            .await?;
+        assert_eq!(no_cookie.status(), StatusCode::FORBIDDEN);
+
+        let header_ok = app()
+            .oneshot(
+                post(Some(&token))
+                    .header(CSRF_HEADER, &token)
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(header_ok.status(), StatusCode::OK);
+        let form_ok = app()
+            .oneshot(
+                post(Some(&token))
+                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(Body::from(format!("title=Hi&{CSRF_FIELD}={token}")))?,
+            )
+            .await?;
+        assert_eq!(form_ok.status(), StatusCode::OK);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_token_authenticated_requests_are_exempt() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let response = app()
+            .oneshot(
+                post(None)
+                    .header(API_KEY_HEADER, "key")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_json_api_requests_are_exempt() -> Result<(), Box<dyn std::error::Error>> {
+        let json = app()
+            .oneshot(
+                Request::post("/api/subscribe")
+                    .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+                    .body(Body::from(r#"{"email":"reader@example.org"}"#))?,
+            )
+            .await?;
+        assert_eq!(json.status(), StatusCode::OK);
+
+        // Forms to the API, and JSON to other paths, still need the token
+        let form = app()
+            .oneshot(
+                Request::post("/api/subscribe")
+                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(Body::from("email=reader%40example.org"))?,
+            )
+            .await?;
+        assert_eq!(form.status(), StatusCode::FORBIDDEN);
+        let json_elsewhere = app()
+            .oneshot(
+                post(None)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from("{}"))?,
+            )
+            .await?;
+        assert_eq!(json_elsewhere.status(), StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tera_helper() -> Result<(), Box<dyn std::error::Error>> {
+        let mut tera = tera::Tera::default();
+        register_csrf_helper(&mut tera);
+        tera.add_raw_template("form", "<form>{{ csrf_field(token=csrf_token) }}</form>")?;
+        let token = generate_token();
+        let mut context = tera::Context::new();
+        context.insert("csrf_token", &token);
+        let html = tera.render("form", &context)?;
+        assert!(html.contains(&format!("name=\"csrf_token\" value=\"{token}\"")));
+
+        context.insert("csrf_token", "\"><script>");
+        assert!(tera.render("form", &context).is_err());
+        Ok(())
+    }
+}
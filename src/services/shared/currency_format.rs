@@ -0,0 +1,148 @@
+//! Currency Formatting
+//!
+//! Locale-aware number and money formatting, and the Tera filters exposing it:
+//! `{{ price | money(currency="VND", lang=current_lang) }}` and
+//! `{{ value | number(decimals=1, lang=current_lang) }}`. Vietnamese groups
+//! thousands with `.` and writes decimals after `,`; English the other way round.
+//!
+//! Report pages render no amounts server-side, so no template uses the filters
+//! yet and `?currency=` stays limited to the JSON market data endpoints.
+
+use std::collections::HashMap;
+
+use crate::services::data_communication::Currency;
+
+/// Thousands and decimal separators of a language
+fn separators(lang: &str) -> (char, char) {
+    if lang == "en" {
+        (',', '.')
+    } else {
+        ('.', ',')
+    }
+}
+
+/// `value` with `decimals` digits and the separators of `lang`
+#[must_use]
+pub fn format_number(value: f64, decimals: usize, lang: &str) -> String {
+    let (thousands, decimal) = separators(lang);
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = formatted
+        .split_once('.')
+        .map_or((formatted.as_str(), None), |(int, frac)| (int, Some(frac)));
+
+    let mut out = String::with_capacity(formatted.len() + int_part.len() / 3 + 1);
+    // Rounding can turn a tiny negative value into zero, which has no sign
+    if value < 0.0 && formatted.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        out.push('-');
+    }
+    for (i, digit) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            out.push(thousands);
+        }
+        out.push(digit);
+    }
+    if let Some(frac) = frac_part {
+        out.push(decimal);
+        out.push_str(frac);
+    }
+    out
+}
+
+/// `amount` of `currency` as shown to readers of `lang`, e.g. `$96,000.00` or
+/// `2.450.000.000 ₫`
+#[must_use]
+pub fn format_money(amount: f64, currency: Currency, lang: &str) -> String {
+    let number = format_number(amount, currency.decimals(), lang);
+    if lang == "en" && currency != Currency::Vnd {
+        match number.strip_prefix('-') {
+            Some(unsigned) => format!("-{}{unsigned}", currency.symbol()),
+            None => format!("{}{number}", currency.symbol()),
+        }
+    } else {
+        format!("{number} {}", currency.symbol())
+    }
+}
+
+fn number_arg(value: &serde_json::Value, filter: &str) -> tera::Result<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| tera::Error::msg(format!("{filter} needs a number, got {value}")))
+}
+
+fn lang_arg(args: &HashMap<String, serde_json::Value>) -> &str {
+    args.get("lang")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or(crate::services::data_communication::DEFAULT_LANGUAGE)
+}
+
+/// Register the `money` and `number` filters with Tera
+pub fn register_currency_filters(tera: &mut tera::Tera) {
+    tera.register_filter(
+        "money",
+        |value: &serde_json::Value,
+         args: &HashMap<String, serde_json::Value>|
+         -> tera::Result<serde_json::Value> {
+            let amount = number_arg(value, "money")?;
+            let currency = match args.get("currency").and_then(serde_json::Value::as_str) {
+                None => Currency::Usd,
+                Some(code) => Currency::parse(code)
+                    .ok_or_else(|| tera::Error::msg(format!("money: unknown currency {code}")))?,
+            };
+            Ok(format_money(amount, currency, lang_arg(args)).into())
+        },
+    );
+    tera.register_filter(
+        "number",
+        |value: &serde_json::Value,
+         args: &HashMap<String, serde_json::Value>|
+         -> tera::Result<serde_json::Value> {
+            let number = number_arg(value, "number")?;
+            let decimals = args
+                .get("decimals")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|decimals| usize::try_from(decimals).ok())
+                .unwrap_or(0)
+                .min(8);
+            Ok(format_number(number, decimals, lang_arg(args)).into())
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_per_language() {
+        assert_eq!(format_number(1_234_567.891, 2, "en"), "1,234,567.89");
+        assert_eq!(format_number(1_234_567.891, 2, "vi"), "1.234.567,89");
+        assert_eq!(format_number(-999.0, 0, "en"), "-999");
+        assert_eq!(format_number(-0.001, 2, "en"), "0.00");
+
+        assert_eq!(format_money(96_000.0, Currency::Usd, "en"), "$96,000.00");
+        assert_eq!(format_money(-12.5, Currency::Eur, "en"), "-€12.50");
+        assert_eq!(
+            format_money(2_450_000_000.4, Currency::Vnd, "vi"),
+            "2.450.000.000 ₫"
+        );
+        assert_eq!(format_money(96_000.0, Currency::Usd, "vi"), "96.000,00 $");
+    }
+
+    #[test]
+    fn test_tera_filters() -> Result<(), tera::Error> {
+        let mut tera = tera::Tera::default();
+        register_currency_filters(&mut tera);
+        let mut context = tera::Context::new();
+        context.insert("price", &25_400_000.0);
+        let rendered = tera.render_str(
+            r#"{{ price | money(currency="vnd", lang="en") }} / {{ price | number(decimals=1) }}"#,
+            &context,
+        )?;
+        assert_eq!(rendered, "25,400,000 ₫ / 25.400.000,0");
+        assert!(
+            tera.render_str(r#"{{ price | money(currency="JPY") }}"#, &context)
+                .is_err()
+        );
+        Ok(())
+    }
+}
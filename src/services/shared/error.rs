@@ -5,6 +5,9 @@
 
 use std::fmt;
 
+use super::error_index::recent_errors;
+use crate::services::health_system::{ErrorEvent, ErrorLevel, report_error, with_request_id};
+
 /// Result type alias for Layer 5 operations
 pub type Layer5Result<T> = Result<T, Layer5Error>;
 
@@ -17,7 +20,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Layer5Error {
     /// Database operation failed
     Database(String),
@@ -29,10 +32,14 @@ pub enum Layer5Error {
     Cache(String),
     /// Timeout occurred
     Timeout(String),
+    /// A dependency cannot serve the request right now
+    Unavailable(String),
     /// Invalid input provided
     InvalidInput(String),
     /// Resource not found
     NotFound(String),
+    /// No valid credentials were presented
+    Unauthorized(String),
     /// Authentication/authorization failed
     Forbidden(String),
     /// Task join error (from `spawn_blocking`)
@@ -49,8 +56,10 @@ impl fmt::Display for Layer5Error {
             Self::Compression(msg) => write!(f, "Compression error: {msg}"),
             Self::Cache(msg) => write!(f, "Cache error: {msg}"),
             Self::Timeout(msg) => write!(f, "Timeout: {msg}"),
+            Self::Unavailable(msg) => write!(f, "Service unavailable: {msg}"),
             Self::InvalidInput(msg) => write!(f, "Invalid input: {msg}"),
             Self::NotFound(msg) => write!(f, "Not found: {msg}"),
+            Self::Unauthorized(msg) => write!(f, "Unauthorized: {msg}"),
             Self::Forbidden(msg) => write!(f, "Forbidden: {msg}"),
             Self::TaskJoin(msg) => write!(f, "Task join error: {msg}"),
             Self::Internal(msg) => write!(f, "Internal error: {msg}"),
@@ -125,20 +134,46 @@ impl Layer5Error {
     pub fn status_code(&self) -> StatusCode {
         match self {
             Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             Self::Forbidden(_) => StatusCode::FORBIDDEN,
             Self::InvalidInput(_) => StatusCode::BAD_REQUEST,
             Self::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            Self::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
+
+    /// Short `snake_case` name of the error variant
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Database(_) => "database",
+            Self::TemplateRender(_) => "template_render",
+            Self::Compression(_) => "compression",
+            Self::Cache(_) => "cache",
+            Self::Timeout(_) => "timeout",
+            Self::Unavailable(_) => "unavailable",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::NotFound(_) => "not_found",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::Forbidden(_) => "forbidden",
+            Self::TaskJoin(_) => "task_join",
+            Self::Internal(_) => "internal",
+        }
+    }
 }
 
 impl IntoResponse for Layer5Error {
     fn into_response(self) -> Response {
         let status = self.status_code();
-        let body = self.to_string();
+        let message = self.to_string();
+        // Server-side failures feed the diagnostics error index and the error reporter
+        if status.is_server_error() {
+            recent_errors().record(self.kind(), status.as_u16(), &message);
+            report_error(ErrorEvent::new(ErrorLevel::Error, self.kind(), &message));
+        }
 
-        (status, body).into_response()
+        (status, with_request_id(&message)).into_response()
     }
 }
 
@@ -165,6 +200,10 @@ mod tests {
             Layer5Error::InvalidInput("any".to_string()).status_code(),
             StatusCode::BAD_REQUEST
         );
+        assert_eq!(
+            Layer5Error::Unauthorized("any".to_string()).status_code(),
+            StatusCode::UNAUTHORIZED
+        );
         assert_eq!(
             Layer5Error::Forbidden("any".to_string()).status_code(),
             StatusCode::FORBIDDEN
@@ -173,6 +212,10 @@ mod tests {
             Layer5Error::Timeout("any".to_string()).status_code(),
             StatusCode::GATEWAY_TIMEOUT
         );
+        assert_eq!(
+            Layer5Error::Unavailable("any".to_string()).status_code(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
         assert_eq!(
             Layer5Error::Internal("any".to_string()).status_code(),
             StatusCode::INTERNAL_SERVER_ERROR
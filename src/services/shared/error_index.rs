@@ -0,0 +1,139 @@
+//! Recent Error Index
+//!
+//! Bounded in-memory log of the most recent server-side (5xx) `Layer5Error`s, filled
+//! when an error is turned into a response. `/admin/diagnostics` shows it so on-call
+//! engineers see what failed without grepping logs.
+
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::LazyLock;
+
+use crate::services::health_system::current_request_id;
+
+/// Max errors kept in the index
+const MAX_RECENT_ERRORS: usize = 50;
+/// Max characters kept per error message
+const MAX_MESSAGE_LENGTH: usize = 300;
+
+static RECENT_ERRORS: LazyLock<RecentErrors> =
+    LazyLock::new(|| RecentErrors::new(MAX_RECENT_ERRORS));
+
+/// Global recent error index
+#[must_use]
+pub fn recent_errors() -> &'static RecentErrors {
+    &RECENT_ERRORS
+}
+
+/// One recorded error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedError {
+    pub at: chrono::DateTime<chrono::Utc>,
+    /// Error kind, e.g. `database` or `template_render`
+    pub kind: &'static str,
+    pub status: u16,
+    pub message: String,
+    /// `X-Request-Id` of the failed request, when recorded while serving one
+    pub request_id: Option<String>,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: VecDeque<RecordedError>,
+    totals: HashMap<&'static str, u64>,
+}
+
+/// Ring buffer of recent errors plus per-kind totals since startup
+pub struct RecentErrors {
+    inner: Mutex<Inner>,
+    capacity: usize,
+}
+
+impl RecentErrors {
+    /// Create an index keeping the last `capacity` errors
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner::default()),
+            capacity,
+        }
+    }
+
+    /// Record an error, tagged with the id of the request being served
+    pub fn record(&self, kind: &'static str, status: u16, message: &str) {
+        let message = message.chars().take(MAX_MESSAGE_LENGTH).collect();
+        let mut inner = self.inner.lock();
+        if inner.entries.len() >= self.capacity {
+            inner.entries.pop_front();
+        }
+        inner.entries.push_back(RecordedError {
+            at: chrono::Utc::now(),
+            kind,
+            status,
+            message,
+            request_id: current_request_id().map(|id| id.to_string()),
+        });
+        *inner.totals.entry(kind).or_default() += 1;
+    }
+
+    /// Most recent errors, newest first
+    #[must_use]
+    pub fn latest(&self, limit: usize) -> Vec<RecordedError> {
+        self.inner
+            .lock()
+            .entries
+            .iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Errors recorded since `since`
+    #[must_use]
+    pub fn count_since(&self, since: chrono::DateTime<chrono::Utc>) -> usize {
+        self.inner
+            .lock()
+            .entries
+            .iter()
+            .filter(|entry| entry.at >= since)
+            .count()
+    }
+
+    /// Total errors per kind since startup
+    #[must_use]
+    pub fn totals(&self) -> HashMap<&'static str, u64> {
+        self.inner.lock().totals.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_keeps_newest() {
+        let errors = RecentErrors::new(2);
+        errors.record("database", 500, "first");
+        errors.record("database", 500, "second");
+        errors.record("timeout", 504, "third");
+
+        let latest: Vec<String> = errors.latest(10).into_iter().map(|e| e.message).collect();
+        assert_eq!(latest, vec!["third".to_string(), "second".to_string()]);
+        assert_eq!(errors.totals().get("database"), Some(&2));
+        assert_eq!(
+            errors.count_since(chrono::Utc::now() - chrono::Duration::minutes(1)),
+            2
+        );
+    }
+
+    #[test]
+    fn test_long_messages_truncated() {
+        let errors = RecentErrors::new(1);
+        errors.record("internal", 500, &"x".repeat(MAX_MESSAGE_LENGTH * 2));
+        let latest = errors.latest(1);
+        assert_eq!(
+            latest.first().map(|e| e.message.len()),
+            Some(MAX_MESSAGE_LENGTH)
+        );
+    }
+}
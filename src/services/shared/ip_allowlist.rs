@@ -0,0 +1,311 @@
+//! Admin IP Allowlist
+//!
+//! `ADMIN_ALLOWLIST` (comma-separated CIDRs or addresses, e.g.
+//! `10.0.0.0/8,203.0.113.7,fd00::/8`) restricts `/admin/*` and `/metrics` to those
+//! networks, on top of their credentials. The client address is the socket peer,
+//! or `X-Forwarded-For` only behind `TRUSTED_PROXIES` (see `client_ip`). Other
+//! clients get 403 and the attempt is recorded in the admin audit log as
+//! `allowlist_rejected`: one row per client per `REJECTION_AUDIT_INTERVAL`, counting
+//! the attempts it suppressed, and at most `MAX_REJECTION_AUDITS` rows per interval
+//! overall, so unauthenticated requests cannot flood the table. Unset or empty
+//! allows every address; set without a single valid entry denies every address,
+//! so a typo cannot open the admin endpoints.
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::{error, warn};
+
+use super::client_ip::client_ip;
+use super::error::Layer5Error;
+use crate::services::data_communication::AuditLogService;
+use crate::state::AppState;
+
+/// One allowed network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNet {
+    /// `addr/prefix`, or a bare address for a single host
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let (addr, prefix) = value
+            .trim()
+            .split_once('/')
+            .map_or((value.trim(), None), |(addr, prefix)| (addr, Some(prefix)));
+        let addr = addr.parse::<IpAddr>().ok()?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse().ok()?,
+            None => max,
+        };
+        (prefix <= max).then_some(Self { addr, prefix })
+    }
+
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        // IPv4 clients may arrive as IPv4-mapped IPv6 on dual-stack sockets
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            IpAddr::V4(_) => ip,
+        };
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Networks allowed to reach admin endpoints; an empty list allows everyone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdminAllowlist {
+    networks: Vec<IpNet>,
+    /// Some entry was given, so only `networks` are allowed (none if all were invalid)
+    restricted: bool,
+}
+
+impl AdminAllowlist {
+    /// Parse a comma-separated list, skipping (and logging) invalid entries
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        let entries: Vec<&str> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .collect();
+        let networks: Vec<IpNet> = entries
+            .iter()
+            .filter_map(|entry| {
+                let net = IpNet::parse(entry);
+                if net.is_none() {
+                    warn!("⚠️ Ignoring invalid ADMIN_ALLOWLIST entry: {}", entry);
+                }
+                net
+            })
+            .collect();
+        if !entries.is_empty() && networks.is_empty() {
+            error!("❌ ADMIN_ALLOWLIST has no valid entry - denying every admin request");
+        }
+        Self {
+            networks,
+            restricted: !entries.is_empty(),
+        }
+    }
+
+    /// Process-wide list from `ADMIN_ALLOWLIST`
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static ALLOWLIST: OnceLock<AdminAllowlist> = OnceLock::new();
+        ALLOWLIST.get_or_init(|| {
+            std::env::var("ADMIN_ALLOWLIST")
+                .map(|value| Self::parse(&value))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Whether `ip` may reach admin endpoints (unknown clients only when unrestricted)
+    #[must_use]
+    pub fn allows(&self, ip: Option<IpAddr>) -> bool {
+        !self.restricted || ip.is_some_and(|ip| self.networks.iter().any(|net| net.contains(ip)))
+    }
+}
+
+/// Window in which a client gets one `allowlist_rejected` audit row
+const REJECTION_AUDIT_INTERVAL: Duration = Duration::from_mins(1);
+
+/// `allowlist_rejected` audit rows written per window across all clients
+const MAX_REJECTION_AUDITS: usize = 60;
+
+/// Fixed-window throttle of the `allowlist_rejected` audit rows
+#[derive(Debug)]
+struct RejectionAudits {
+    window_start: Instant,
+    /// Clients audited in the current window, with the attempts not audited since
+    audited: HashMap<String, u64>,
+    /// The same for the previous window, reported by a client's next row
+    previous: HashMap<String, u64>,
+    /// Attempts of clients over `MAX_REJECTION_AUDITS` in the current window
+    dropped: u64,
+}
+
+impl RejectionAudits {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            audited: HashMap::new(),
+            previous: HashMap::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Count a rejected attempt of `client`; returns the number of its attempts
+    /// suppressed before this one when an audit row should be written
+    fn admit(&mut self, client: &str, now: Instant) -> Option<u64> {
+        if now.duration_since(self.window_start) >= REJECTION_AUDIT_INTERVAL {
+            if self.dropped > 0 {
+                warn!(
+                    "🚫 {} admin requests from outside the allowlist were not audited",
+                    self.dropped
+                );
+            }
+            self.previous = std::mem::take(&mut self.audited);
+            self.window_start = now;
+            self.dropped = 0;
+        }
+        if let Some(suppressed) = self.audited.get_mut(client) {
+            *suppressed += 1;
+            return None;
+        }
+        if self.audited.len() >= MAX_REJECTION_AUDITS {
+            self.dropped += 1;
+            return None;
+        }
+        self.audited.insert(client.to_string(), 0);
+        Some(self.previous.remove(client).unwrap_or(0))
+    }
+
+    /// Process-wide throttle
+    fn current() -> &'static Mutex<Self> {
+        static AUDITS: OnceLock<Mutex<RejectionAudits>> = OnceLock::new();
+        AUDITS.get_or_init(|| Mutex::new(Self::new(Instant::now())))
+    }
+}
+
+/// Paths the allowlist applies to
+fn is_restricted_path(path: &str) -> bool {
+    path == "/admin" || path.starts_with("/admin/") || path == "/metrics"
+}
+
+/// Middleware rejecting admin requests from outside `ADMIN_ALLOWLIST`
+pub async fn enforce_admin_allowlist(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let allowlist = AdminAllowlist::current();
+    let path = request.uri().path();
+    if !is_restricted_path(path) {
+        return next.run(request).await;
+    }
+    let client = client_ip(&request);
+    if allowlist.allows(client) {
+        return next.run(request).await;
+    }
+
+    let client = client.map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+    let admitted = RejectionAudits::current()
+        .lock()
+        .admit(&client, Instant::now());
+    if let Some(suppressed) = admitted {
+        warn!(%client, path, "🚫 Admin request from outside the allowlist");
+        AuditLogService::new()
+            .record(
+                &state,
+                "allowlist_rejected",
+                &client,
+                &json!({
+                    "method": request.method().as_str(),
+                    "path": path,
+                    "suppressed": suppressed,
+                }),
+                "rejected",
+            )
+            .await;
+    }
+    Layer5Error::Forbidden(format!(
+        "{path} is not available from {client} (not in the admin allowlist)"
+    ))
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        value.parse().ok()
+    }
+
+    #[test]
+    fn test_cidr_matching() {
+        let allowlist =
+            AdminAllowlist::parse("10.0.0.0/8, 203.0.113.7, fd00::/8, bogus, 1.2.3.4/40");
+        assert!(allowlist.allows(ip("10.20.30.40")));
+        assert!(allowlist.allows(ip("203.0.113.7")));
+        assert!(allowlist.allows(ip("::ffff:10.1.1.1")));
+        assert!(allowlist.allows(ip("fd12::1")));
+        assert!(!allowlist.allows(ip("203.0.113.8")));
+        assert!(!allowlist.allows(ip("11.0.0.1")));
+        assert!(!allowlist.allows(None));
+        assert_eq!(allowlist.networks.len(), 3);
+    }
+
+    #[test]
+    fn test_empty_allowlist_allows_everyone() {
+        let allowlist = AdminAllowlist::parse(" ");
+        assert!(allowlist.allows(ip("198.51.100.1")));
+        assert!(allowlist.allows(None));
+        assert!(
+            IpNet::parse("0.0.0.0/0").is_some_and(|net| net.contains(IpAddr::from([8, 8, 8, 8])))
+        );
+    }
+
+    #[test]
+    fn test_allowlist_without_valid_entries_denies_everyone() {
+        let allowlist = AdminAllowlist::parse("10.0.0.0/33, bogus");
+        assert!(allowlist.networks.is_empty());
+        assert!(!allowlist.allows(ip("10.0.0.1")));
+        assert!(!allowlist.allows(ip("198.51.100.1")));
+        assert!(!allowlist.allows(None));
+    }
+
+    #[test]
+    fn test_rejection_audits_are_throttled() {
+        let start = Instant::now();
+        let mut audits = RejectionAudits::new(start);
+        assert_eq!(audits.admit("198.51.100.1", start), Some(0));
+        assert_eq!(audits.admit("198.51.100.1", start), None);
+        assert_eq!(audits.admit("198.51.100.1", start), None);
+
+        // The next window's row reports the attempts suppressed in this one
+        let later = start + REJECTION_AUDIT_INTERVAL;
+        assert_eq!(audits.admit("198.51.100.1", later), Some(2));
+
+        // Rows per window are capped across clients
+        for i in 1..MAX_REJECTION_AUDITS {
+            assert!(audits.admit(&format!("client-{i}"), later).is_some());
+        }
+        assert_eq!(audits.admit("203.0.113.1", later), None);
+        assert_eq!(audits.dropped, 1);
+    }
+
+    #[test]
+    fn test_restricted_paths() {
+        assert!(is_restricted_path("/admin/cache/stats"));
+        assert!(is_restricted_path("/metrics"));
+        assert!(!is_restricted_path("/administrator"));
+        assert!(!is_restricted_path("/health"));
+    }
+}
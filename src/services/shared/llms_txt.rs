@@ -0,0 +1,196 @@
+//! llms.txt Generator for Layer 5 Business Logic
+//!
+//! `/llms.txt` tells AI agents what the site contains, which report URLs are
+//! canonical and how a report should be cited, in the Markdown layout of the
+//! llms.txt proposal. It lists the latest `LLMS_TXT_REPORTS` reports; the
+//! `/llms-full.txt` variant lists every report of the index with its vi/en URLs.
+//!
+//! Reference: <https://llmstxt.org/>
+
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+use std::fmt::Write;
+use tracing::info;
+
+use super::error::{Layer5Error, Layer5Result};
+use super::public_origin::public_origin;
+use super::sitemap_creator::report_language_alternates;
+use crate::services::data_communication::report_path;
+
+/// Reports listed by `/llms.txt` (newest first)
+pub const LLMS_TXT_REPORTS: usize = 30;
+
+/// Site name used in titles and citations
+const SITE_NAME: &str = "Crypto Dashboard";
+
+/// llms.txt generator
+pub struct LlmsTxtCreator;
+
+impl LlmsTxtCreator {
+    /// Generate `/llms.txt` (`full = false`) or `/llms-full.txt` (`full = true`)
+    ///
+    /// # Arguments
+    /// * `report_data` - Tuples (`report_id`, `created_at`) of the report index
+    /// * `full` - List every report with its language variants instead of the latest ones
+    ///
+    /// # Errors
+    ///
+    /// Returns error if text writing fails
+    pub fn generate(
+        mut report_data: Vec<(i32, DateTime<Utc>)>,
+        full: bool,
+    ) -> Layer5Result<String> {
+        report_data.sort_by_key(|&(id, created_at)| std::cmp::Reverse((created_at, id)));
+        let total = report_data.len();
+        if !full {
+            report_data.truncate(LLMS_TXT_REPORTS);
+        }
+
+        let origin = public_origin();
+        let mut text = String::with_capacity(2_000 + report_data.len() * 200);
+        Self::write_header(&mut text, &origin, total).map_err(text_error)?;
+
+        if full {
+            writeln!(text, "## All reports\n").map_err(text_error)?;
+        } else {
+            writeln!(text, "## Latest reports\n").map_err(text_error)?;
+        }
+        for &(id, created_at) in &report_data {
+            write!(
+                text,
+                "- [{}]({origin}{}): published {}",
+                Self::title(id, created_at),
+                report_path(id, created_at),
+                created_at.format("%Y-%m-%d")
+            )
+            .map_err(text_error)?;
+            if full {
+                for alternate in report_language_alternates(id, created_at)
+                    .iter()
+                    .filter(|alternate| alternate.hreflang != "x-default")
+                {
+                    write!(text, ", [{}]({})", alternate.hreflang, alternate.href)
+                        .map_err(text_error)?;
+                }
+            }
+            writeln!(text).map_err(text_error)?;
+        }
+
+        if !full {
+            writeln!(
+                text,
+                "\n## Optional\n\n\
+                 - [Full report index]({origin}/llms-full.txt): every report with its vi/en URLs\n\
+                 - [Sitemap]({origin}/sitemap.xml)"
+            )
+            .map_err(text_error)?;
+        }
+
+        info!(
+            "llms{}.txt generated: {} of {} reports",
+            if full { "-full" } else { "" },
+            report_data.len(),
+            total
+        );
+        Ok(text)
+    }
+
+    /// Title, summary, site sections and citation format
+    fn write_header(text: &mut String, origin: &str, total: usize) -> std::fmt::Result {
+        writeln!(
+            text,
+            "# {SITE_NAME}\n\n\
+             > Daily crypto market analysis reports in Vietnamese and English, with a \
+             live dashboard of prices, market cap, Fear & Greed index and dominance. \
+             {total} reports are published.\n"
+        )?;
+        writeln!(
+            text,
+            "Reports are written in Vietnamese; append `?lang=en` to a report URL for the \
+             English version. The canonical URL of a report is \
+             `{origin}/crypto_report/{{id}}-{{slug}}`; bare `/crypto_report/{{id}}` URLs \
+             redirect to it.\n"
+        )?;
+        writeln!(
+            text,
+            "## Citation\n\n\
+             Cite a report by its title, publisher, publication date and canonical URL:\n\n\
+             `{SITE_NAME}. \"Báo cáo Thị trường Crypto #{{id}} - {{dd/mm/yyyy}}\". \
+             Published {{yyyy-mm-dd}}. {origin}/crypto_report/{{id}}-{{slug}}`\n\n\
+             Market figures in a report describe the time of publication, not the present.\n"
+        )?;
+        writeln!(
+            text,
+            "## Site\n\n\
+             - [Dashboard]({origin}/): live market data\n\
+             - [Latest report]({origin}/crypto_report): most recent analysis\n\
+             - [Reports list]({origin}/crypto_reports_list): all reports, newest first\n\
+             - [RSS feed]({origin}/rss.xml): new reports as they are published\n\
+             - [Atom feed]({origin}/atom.xml): the same reports in Atom 1.0\n\
+             - [JSON Feed]({origin}/feed.json): the same reports with full HTML and tags\n"
+        )
+    }
+
+    /// Report title with the date in Vietnamese timezone (UTC+7), as in the RSS feed
+    fn title(id: i32, created_at: DateTime<Utc>) -> String {
+        let vn_offset = FixedOffset::east_opt(7 * 3600)
+            .or_else(|| FixedOffset::east_opt(0))
+            .unwrap_or_else(|| Utc.fix());
+        let vn_time = created_at.with_timezone(&vn_offset);
+        format!(
+            "Báo cáo Thị trường Crypto #{id} - {}",
+            vn_time.format("%d/%m/%Y")
+        )
+    }
+}
+
+fn text_error(e: std::fmt::Error) -> Layer5Error {
+    Layer5Error::Internal(format!("llms.txt write error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    /// Reports 1..=count, one per day from 2024-03-11 09:00 UTC+7
+    fn reports(count: i32) -> Vec<(i32, DateTime<Utc>)> {
+        let start = DateTime::from_timestamp(1_710_036_000, 0).unwrap_or_default();
+        (1..=count)
+            .map(|id| (id, start + TimeDelta::days(i64::from(id))))
+            .collect()
+    }
+
+    #[test]
+    fn test_llms_txt_lists_latest_reports() -> Result<(), Box<dyn std::error::Error>> {
+        let text = LlmsTxtCreator::generate(reports(40), false)
+            .map_err(|e| format!("Failed to generate llms.txt: {e}"))?;
+
+        assert!(text.starts_with("# Crypto Dashboard\n\n> "));
+        assert!(text.contains("40 reports are published"));
+        assert!(text.contains("## Citation"));
+        assert_eq!(
+            text.matches("](https://cryptodashboard.me/crypto_report/")
+                .count(),
+            30
+        );
+        let newest = text.find("[Báo cáo Thị trường Crypto #40 - 19/04/2024](https://cryptodashboard.me/crypto_report/40-");
+        let older = text.find("[Báo cáo Thị trường Crypto #11 - ");
+        assert!(newest.is_some() && newest < older);
+        assert!(!text.contains("Crypto #10 - "));
+        assert!(text.contains("(https://cryptodashboard.me/llms-full.txt)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_llms_full_txt_lists_every_report() -> Result<(), Box<dyn std::error::Error>> {
+        let text = LlmsTxtCreator::generate(reports(40), true)
+            .map_err(|e| format!("Failed to generate llms-full.txt: {e}"))?;
+
+        assert!(text.contains("## All reports"));
+        assert!(text.contains("Crypto #1 - 11/03/2024"));
+        assert!(text.contains("?lang=en)"));
+        assert!(text.contains("?lang=vi)"));
+        assert!(!text.contains("/llms-full.txt)"));
+        Ok(())
+    }
+}
@@ -1,33 +1,121 @@
 //! Shared Utilities for Layer 5 Business Logic
 //!
 //! This module contains common utilities used across Layer 5 components:
+//! - `bot_detection`: User-agent classes, crawl throttling and cache-only crawlers
+//! - `cache_key_stats`: Per-key hit/miss/size tracking for cache statistics
+//! - `cache_purge`: Targeted cache purge by key, prefix or report id
+//! - `client_ip`: Client address behind trusted reverse proxies
 //! - compression: Gzip compression for HTTP responses
+//! - cors: CORS policy for the JSON APIs
+//! - csrf: Double-submit cookie CSRF protection for form-based routes
+//! - `currency_format`: Locale-aware money / number formatting and its Tera filters
+//! - conditional: `Last-Modified` / `If-Modified-Since` revalidation helpers
 //! - `response_builder`: Safe HTTP response construction
+//! - robots: Environment-aware robots.txt with per-bot rules
 //! - error: Custom error types for Layer 5 operations
+//! - `error_index`: Recent server errors for admin diagnostics
+//! - `ip_allowlist`: CIDR allowlist for admin endpoints and metrics
+//! - `llms_txt`: `/llms.txt` site summary and citation guide for AI agents
+//! - `news_sitemap`: Google News sitemap of the last 48 hours of reports
+//! - `og_image`: PNG cards for chat / social embeds (dashboard snapshot)
+//! - `publish_notifier`: `WebSub` hub and `IndexNow` pings when a report is published
+//! - `public_origin`: Scheme and host of absolute URLs behind trusted proxies
+//! - redirects: Stored 301 / 302 redirects of legacy URLs
+//! - `rate_limit`: Per-IP token bucket limiting of expensive routes
+//! - `request_limits`: Body size and query length caps (413 / 414)
 //! - websocket: WebSocket URL resolution utilities
-//! - security: Cryptographically secure token generation
-//! - `sitemap_creator`: Dynamic sitemap.xml generation
+//! - `single_flight`: Coalescing of concurrent computations of the same key
+//! - security: Cryptographically secure token generation and admin authorization
+//! - `sitemap_creator`: Sitemap index and chunked sub-sitemaps
+//! - `stale_while_revalidate`: Stale page copies served while re-rendering in the background
+//! - `trace_context`: W3C trace context propagation through Redis Streams
+//! - `validated_query`: Query extractor answering 400 with per-field errors
 
+pub mod bot_detection;
+pub mod cache_key_stats;
+pub mod cache_purge;
 pub mod cache_utils;
+pub mod client_ip;
 pub mod compression;
+pub mod conditional;
+pub mod cors;
+pub mod csrf;
+pub mod currency_format;
 pub mod error;
+pub mod error_index;
+pub mod ip_allowlist;
+pub mod llms_txt;
+pub mod news_sitemap;
+pub mod og_image;
+pub mod public_origin;
+pub mod publish_notifier;
+pub mod rate_limit;
+pub mod redirects;
+pub mod request_limits;
 pub mod response_builder;
+pub mod robots;
 pub mod rss_creator;
 pub mod security;
+pub mod single_flight;
 pub mod sitemap_creator;
+pub mod stale_while_revalidate;
+pub mod trace_context;
+pub mod validated_query;
 pub mod websocket;
 
+pub use bot_detection::{
+    BotConfig, CrawlControl, CrawlerClass, cache_only_miss, crawl_control, current_crawler_class,
+};
+pub use cache_key_stats::cache_key_stats;
+pub use cache_purge::{CachePurgeTarget, purge_cache};
 pub use cache_utils::{
-    build_standard_compressed_response, cache_compressed_data, compress_data,
-    try_get_cached_compressed,
+    build_standard_compressed_response, cache_compressed_data, cache_last_modified, compress_data,
+    get_cached_last_modified, try_get_cached_compressed,
+};
+pub use client_ip::client_ip;
+pub use compression::{CompressionStats, compress_html_to_gzip, stream_html_to_gzip};
+pub use conditional::{
+    build_not_modified_response, conditional_compressed_response, content_etag, etag_matches,
+    format_http_date, is_not_modified,
 };
-pub use compression::{CompressionStats, compress_html_to_gzip};
+pub use cors::{CorsConfig, CorsOrigins};
+pub use csrf::{CsrfToken, csrf_protect, register_csrf_helper};
+pub use currency_format::{format_money, format_number, register_currency_filters};
 pub use error::{Layer5Error, Layer5Result};
+pub use error_index::recent_errors;
+pub use ip_allowlist::{AdminAllowlist, IpNet, enforce_admin_allowlist};
+pub use llms_txt::{LLMS_TXT_REPORTS, LlmsTxtCreator};
+pub use news_sitemap::NewsSitemapCreator;
+pub use og_image::{CARD_HEIGHT, CARD_WIDTH, Canvas, render_dashboard_card};
+pub use public_origin::{TrustedProxies, origin_key_segment, public_origin, resolve_public_origin};
+pub use publish_notifier::{
+    INDEXNOW_KEY_PATH, NotifierConfig, indexnow_key, spawn_publish_notifier, websub_topics,
+};
+pub use rate_limit::{RateLimitConfig, RateLimiter, TokenBuckets, rate_limit, too_many_requests};
+pub use redirects::{
+    RedirectManager, RedirectRule, RedirectTable, redirect_legacy_urls, validate_redirect,
+};
+pub use request_limits::{RequestLimits, enforce_request_limits};
 pub use response_builder::{
-    build_compressed_response, build_error_response, build_forbidden_response, build_html_response,
-    build_not_found_response, build_sandboxed_response, build_shadow_dom_response,
+    LANGUAGE_VARY, build_compressed_response, build_error_response, build_forbidden_response,
+    build_html_response, build_not_found_response, build_problem_response,
+    build_problem_response_with, build_sandboxed_response, build_shadow_dom_response,
+};
+pub use robots::{BotRule, RobotsAccess, RobotsConfig};
+pub use rss_creator::{
+    FeedBuilder, FeedContentConfig, FeedEnclosure, FeedFormat, RssCreator, feed_query, report_tag,
+    report_tags, report_title,
+};
+pub use security::{
+    admin_actor, authorize_admin, cache_bypass_requested, generate_sandbox_token,
+    sandboxed_report_path, sign_sandbox_url, verify_sandbox_token, verify_sandbox_url,
+};
+pub use single_flight::{Flight, SingleFlight};
+pub use sitemap_creator::{
+    LanguageAlternate, MAX_URLS_PER_SITEMAP, REPORTS_LIST_PAGE_SIZE, SitemapCreator,
+    SitemapSection, report_language_alternates,
 };
-pub use rss_creator::RssCreator;
-pub use security::{generate_sandbox_token, verify_sandbox_token};
-pub use sitemap_creator::SitemapCreator;
+pub use stale_while_revalidate::StaleCache;
+pub use trace_context::TraceContext;
+pub use validated_query::ValidatedQuery;
 pub use websocket::get_websocket_url;
@@ -0,0 +1,149 @@
+//! Google News Sitemap Generator for Layer 5 Business Logic
+//!
+//! `/news-sitemap.xml` lists the reports published in the last `NEWS_WINDOW_HOURS`
+//! hours with `news:` publication metadata, so daily reports are eligible for
+//! Google News surfaces. Older articles are dropped as Google ignores them.
+//!
+//! Reference: <https://developers.google.com/search/docs/crawling-indexing/sitemaps/news-sitemap>
+
+use chrono::{DateTime, FixedOffset, Offset, TimeDelta, Utc};
+use std::fmt::Write;
+use tracing::info;
+
+use super::error::{Layer5Error, Layer5Result};
+use super::public_origin::public_origin;
+use crate::services::data_communication::report_path;
+
+/// Age limit of listed reports
+pub const NEWS_WINDOW_HOURS: i64 = 48;
+
+/// Max URLs per news sitemap (Google News limit)
+pub const MAX_NEWS_URLS: usize = 1_000;
+
+/// `news:name`, which must match the publication name known to Google News
+const PUBLICATION_NAME: &str = "CryptoDashboard";
+
+/// `news:language` of the canonical report URL
+const PUBLICATION_LANGUAGE: &str = "vi";
+
+/// Google News sitemap generator
+pub struct NewsSitemapCreator;
+
+impl NewsSitemapCreator {
+    /// Oldest publication time listed at `now`
+    #[must_use]
+    pub fn window_start(now: DateTime<Utc>) -> DateTime<Utc> {
+        now - TimeDelta::hours(NEWS_WINDOW_HOURS)
+    }
+
+    /// Generate the news sitemap XML
+    ///
+    /// # Arguments
+    /// * `report_data` - Tuples (`report_id`, `created_at`); reports outside the
+    ///   window ending at `now` are skipped
+    /// * `now` - Current time
+    ///
+    /// # Errors
+    ///
+    /// Returns error if XML writing fails
+    pub fn generate_news_sitemap_xml(
+        mut report_data: Vec<(i32, DateTime<Utc>)>,
+        now: DateTime<Utc>,
+    ) -> Layer5Result<String> {
+        let window_start = Self::window_start(now);
+        report_data.retain(|&(_, created_at)| created_at >= window_start && created_at <= now);
+        report_data.sort_by_key(|&(id, created_at)| std::cmp::Reverse((created_at, id)));
+        report_data.truncate(MAX_NEWS_URLS);
+
+        let mut xml = String::with_capacity(300 + report_data.len() * 600);
+        writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).map_err(xml_error)?;
+        writeln!(
+            xml,
+            r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:news="http://www.google.com/schemas/sitemap-news/0.9">"#
+        )
+        .map_err(xml_error)?;
+        let origin = public_origin();
+        for (id, created_at) in &report_data {
+            write!(
+                xml,
+                "  <url>\n    <loc>{origin}{}</loc>\n    <news:news>\n      \
+                 <news:publication>\n        <news:name>{PUBLICATION_NAME}</news:name>\n        \
+                 <news:language>{PUBLICATION_LANGUAGE}</news:language>\n      </news:publication>\n      \
+                 <news:publication_date>{}</news:publication_date>\n      \
+                 <news:title>{}</news:title>\n    </news:news>\n  </url>\n",
+                report_path(*id, *created_at),
+                created_at.format("%Y-%m-%dT%H:%M:%SZ"),
+                Self::title(*id, *created_at)
+            )
+            .map_err(xml_error)?;
+        }
+        writeln!(xml, "</urlset>").map_err(xml_error)?;
+
+        info!(
+            "News sitemap generated: {} reports from the last {} hours",
+            report_data.len(),
+            NEWS_WINDOW_HOURS
+        );
+        Ok(xml)
+    }
+
+    /// Report title with the date in Vietnamese timezone (UTC+7), as in the RSS feed
+    fn title(id: i32, created_at: DateTime<Utc>) -> String {
+        let vn_offset = FixedOffset::east_opt(7 * 3600)
+            .or_else(|| FixedOffset::east_opt(0))
+            .unwrap_or_else(|| Utc.fix());
+        let vn_time = created_at.with_timezone(&vn_offset);
+        format!(
+            "Báo cáo Thị trường Crypto #{id} - {}",
+            vn_time.format("%d/%m/%Y")
+        )
+    }
+}
+
+fn xml_error(e: std::fmt::Error) -> Layer5Error {
+    Layer5Error::Internal(format!("XML write error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_news_sitemap_lists_recent_reports() -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc
+            .with_ymd_and_hms(2024, 3, 10, 12, 0, 0)
+            .single()
+            .ok_or("Invalid time")?;
+        let reports = vec![
+            (7, now - TimeDelta::hours(50)),
+            (8, now - TimeDelta::hours(30)),
+            (9, now - TimeDelta::hours(2)),
+        ];
+
+        let xml = NewsSitemapCreator::generate_news_sitemap_xml(reports, now)
+            .map_err(|e| format!("Failed to generate XML: {e}"))?;
+
+        assert!(xml.contains(r#"xmlns:news="http://www.google.com/schemas/sitemap-news/0.9""#));
+        assert!(!xml.contains("/crypto_report/7-"));
+        let newest = xml.find("<loc>https://cryptodashboard.me/crypto_report/9-phan-tich-thi-truong-crypto-10-03-2024</loc>");
+        let older = xml.find("<loc>https://cryptodashboard.me/crypto_report/8-phan-tich-thi-truong-crypto-09-03-2024</loc>");
+        assert!(newest.is_some() && newest < older);
+        assert!(xml.contains("<news:name>CryptoDashboard</news:name>"));
+        assert!(xml.contains("<news:language>vi</news:language>"));
+        assert!(
+            xml.contains("<news:publication_date>2024-03-10T10:00:00Z</news:publication_date>")
+        );
+        assert!(xml.contains("<news:title>Báo cáo Thị trường Crypto #9 - 10/03/2024</news:title>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_news_sitemap_without_recent_reports() -> Result<(), Box<dyn std::error::Error>> {
+        let xml = NewsSitemapCreator::generate_news_sitemap_xml(vec![], Utc::now())
+            .map_err(|e| format!("Failed to generate XML: {e}"))?;
+        assert!(xml.contains("<urlset"));
+        assert!(!xml.contains("<url>"));
+        Ok(())
+    }
+}
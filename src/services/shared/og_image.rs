@@ -0,0 +1,354 @@
+//! OG Image Renderer
+//!
+//! Server-side PNG cards for chat and social embeds, drawn without an image or
+//! font dependency: an RGB canvas of filled rectangles, a built-in 5×7 bitmap
+//! font (ASCII letters shown uppercase, digits and price punctuation) and a PNG
+//! encoder deflating the scanlines with flate2. `render_dashboard_card` draws
+//! `/api/crypto/dashboard-summary/snapshot.png`.
+
+use chrono::{DateTime, Utc};
+use flate2::{Compression, write::ZlibEncoder};
+use std::io::Write;
+
+use super::currency_format::format_money;
+use crate::dto::responses::DashboardDataResponse;
+use crate::services::data_communication::Currency;
+
+/// Size of the cards, the 1.91:1 ratio preferred by Open Graph consumers
+pub const CARD_WIDTH: usize = 1200;
+pub const CARD_HEIGHT: usize = 630;
+
+/// Color of a pixel
+pub type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [15, 23, 42];
+const PANEL: Rgb = [30, 41, 59];
+const TEXT: Rgb = [241, 245, 249];
+const MUTED: Rgb = [148, 163, 184];
+const ACCENT: Rgb = [247, 147, 26];
+const UP: Rgb = [34, 197, 94];
+const DOWN: Rgb = [239, 68, 68];
+const WARNING: Rgb = [245, 158, 11];
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+/// Blank columns after each glyph, in font pixels
+const GLYPH_SPACING: usize = 1;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Rows of a glyph, the leftmost column in bit 4; unknown characters draw as `?`
+#[allow(clippy::unreadable_literal)]
+#[rustfmt::skip]
+const fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0; GLYPH_HEIGHT],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '$' => [0b00100, 0b01111, 0b10100, 0b01110, 0b00101, 0b11110, 0b00100],
+        '%' => [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011],
+        '+' => [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        ',' => [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        '/' => [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000],
+        '&' => [0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101],
+        '(' => [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010],
+        ')' => [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000],
+        '!' => [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100],
+        _ => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100],
+    }
+}
+
+/// RGB canvas encoded as an 8-bit truecolor PNG
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Canvas of `width` × `height` pixels filled with `background`
+    #[must_use]
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: background.repeat(width * height),
+        }
+    }
+
+    /// Fill a rectangle, clipped to the canvas
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        let right = (x + width).min(self.width);
+        if x >= right {
+            return;
+        }
+        for row in y..(y + height).min(self.height) {
+            let start = (row * self.width + x) * 3;
+            let end = (row * self.width + right) * 3;
+            if let Some(span) = self.pixels.get_mut(start..end) {
+                for pixel in span.chunks_exact_mut(3) {
+                    pixel.copy_from_slice(&color);
+                }
+            }
+        }
+    }
+
+    /// Width of `text` drawn at `scale` (pixels per font pixel)
+    #[must_use]
+    pub fn text_width(text: &str, scale: usize) -> usize {
+        let glyphs = text.chars().count();
+        (glyphs * (GLYPH_WIDTH + GLYPH_SPACING)).saturating_sub(GLYPH_SPACING) * scale
+    }
+
+    /// Largest scale up to `max_scale` at which `text` fits in `max_width` (at least 1)
+    #[must_use]
+    pub fn fit_scale(text: &str, max_width: usize, max_scale: usize) -> usize {
+        (1..=max_scale)
+            .rev()
+            .find(|&scale| Self::text_width(text, scale) <= max_width)
+            .unwrap_or(1)
+    }
+
+    /// Draw `text` with its top-left corner at (`x`, `y`); returns its width
+    pub fn draw_text(&mut self, x: usize, y: usize, text: &str, scale: usize, color: Rgb) -> usize {
+        let advance = (GLYPH_WIDTH + GLYPH_SPACING) * scale;
+        for (i, c) in text.chars().enumerate() {
+            let left = x + i * advance;
+            for (row, bits) in glyph(c).into_iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (0b10000 >> col) != 0 {
+                        self.fill_rect(left + col * scale, y + row * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+        Self::text_width(text, scale)
+    }
+
+    /// The canvas as a PNG file
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the canvas is too large for PNG or compression fails
+    pub fn encode_png(&self) -> std::io::Result<Vec<u8>> {
+        let too_large = |_| std::io::Error::other("canvas too large for PNG");
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&u32::try_from(self.width).map_err(too_large)?.to_be_bytes());
+        header.extend_from_slice(&u32::try_from(self.height).map_err(too_large)?.to_be_bytes());
+        // 8-bit truecolor, deflate, adaptive filtering, no interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        // Every scanline starts with filter type 0 (none)
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        for scanline in self.pixels.chunks_exact(self.width * 3) {
+            encoder.write_all(&[0])?;
+            encoder.write_all(scanline)?;
+        }
+        let image_data = encoder.finish()?;
+
+        let mut png = Vec::with_capacity(image_data.len() + 64);
+        png.extend_from_slice(&PNG_SIGNATURE);
+        write_chunk(&mut png, *b"IHDR", &header)?;
+        write_chunk(&mut png, *b"IDAT", &image_data)?;
+        write_chunk(&mut png, *b"IEND", &[])?;
+        Ok(png)
+    }
+}
+
+/// Append a PNG chunk: length, type, data and the CRC of type and data
+fn write_chunk(png: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) -> std::io::Result<()> {
+    let length = u32::try_from(data.len()).map_err(|_| std::io::Error::other("chunk too large"))?;
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(&kind);
+    crc.update(data);
+    png.extend_from_slice(&length.to_be_bytes());
+    png.extend_from_slice(&kind);
+    png.extend_from_slice(data);
+    png.extend_from_slice(&crc.finalize().to_be_bytes());
+    Ok(())
+}
+
+/// Large USD amount with a T / B / M suffix, e.g. `$2.45T`
+fn compact_usd(amount: f64) -> String {
+    [(1e12, "T"), (1e9, "B"), (1e6, "M")]
+        .into_iter()
+        .find(|(unit, _)| amount.abs() >= *unit)
+        .map_or_else(
+            || format_money(amount, Currency::Usd, "en"),
+            |(unit, suffix)| format!("${:.2}{suffix}", amount / unit),
+        )
+}
+
+fn change_color(change: f64) -> Rgb {
+    if change < 0.0 { DOWN } else { UP }
+}
+
+/// Sentiment label of a Fear & Greed index value
+const fn fng_label(value: i32) -> &'static str {
+    match value {
+        ..=24 => "EXTREME FEAR",
+        25..=44 => "FEAR",
+        45..=55 => "NEUTRAL",
+        56..=74 => "GREED",
+        _ => "EXTREME GREED",
+    }
+}
+
+/// One metric tile of the dashboard card
+struct Tile {
+    label: &'static str,
+    value: String,
+    detail: String,
+    detail_color: Rgb,
+}
+
+/// Dashboard metrics as a `CARD_WIDTH` × `CARD_HEIGHT` PNG card: BTC, ETH and SOL
+/// prices, market cap, volume and the Fear & Greed index, in USD
+///
+/// # Errors
+///
+/// Returns error if PNG encoding fails
+pub fn render_dashboard_card(
+    summary: &DashboardDataResponse,
+    now: DateTime<Utc>,
+) -> std::io::Result<Vec<u8>> {
+    const MARGIN: usize = 60;
+    const GAP: usize = 30;
+    const TILE_WIDTH: usize = (CARD_WIDTH - 2 * MARGIN - 2 * GAP) / 3;
+    const TILE_HEIGHT: usize = 170;
+    const TILE_PADDING: usize = 24;
+
+    let price_tile = |label, price: f64, change: f64| Tile {
+        label,
+        value: format_money(price, Currency::Usd, "en"),
+        detail: format!("{change:+.2}% 24H"),
+        detail_color: change_color(change),
+    };
+    let tiles = [
+        price_tile("BITCOIN", summary.btc_price_usd, summary.btc_change_24h),
+        price_tile("ETHEREUM", summary.eth_price_usd, summary.eth_change_24h),
+        price_tile("SOLANA", summary.sol_price_usd, summary.sol_change_24h),
+        Tile {
+            label: "MARKET CAP",
+            value: compact_usd(summary.market_cap_usd),
+            detail: format!("{:+.2}% 24H", summary.market_cap_change_percentage_24h_usd),
+            detail_color: change_color(summary.market_cap_change_percentage_24h_usd),
+        },
+        Tile {
+            label: "24H VOLUME",
+            value: compact_usd(summary.volume_24h_usd),
+            detail: format!("BTC DOM {:.1}%", summary.btc_market_cap_percentage),
+            detail_color: MUTED,
+        },
+        Tile {
+            label: "FEAR & GREED",
+            value: summary.fng_value.to_string(),
+            detail: fng_label(summary.fng_value).to_string(),
+            detail_color: ACCENT,
+        },
+    ];
+
+    let mut canvas = Canvas::new(CARD_WIDTH, CARD_HEIGHT, BACKGROUND);
+    canvas.fill_rect(0, 0, CARD_WIDTH, 12, ACCENT);
+    canvas.draw_text(MARGIN, 56, "CRYPTO DASHBOARD", 7, TEXT);
+    canvas.draw_text(
+        MARGIN,
+        125,
+        &now.format("%Y-%m-%d %H:%M UTC").to_string(),
+        3,
+        MUTED,
+    );
+    if summary.stale {
+        let badge = "STALE DATA";
+        let x = CARD_WIDTH - MARGIN - Canvas::text_width(badge, 3);
+        canvas.draw_text(x, 125, badge, 3, WARNING);
+    }
+
+    for (i, tile) in tiles.iter().enumerate() {
+        let x = MARGIN + (i % 3) * (TILE_WIDTH + GAP);
+        let y = 190 + (i / 3) * (TILE_HEIGHT + GAP);
+        let inner_width = TILE_WIDTH - 2 * TILE_PADDING;
+        canvas.fill_rect(x, y, TILE_WIDTH, TILE_HEIGHT, PANEL);
+        canvas.draw_text(x + TILE_PADDING, y + TILE_PADDING, tile.label, 3, MUTED);
+        let scale = Canvas::fit_scale(&tile.value, inner_width, 5);
+        canvas.draw_text(x + TILE_PADDING, y + 64, &tile.value, scale, TEXT);
+        let scale = Canvas::fit_scale(&tile.detail, inner_width, 3);
+        canvas.draw_text(
+            x + TILE_PADDING,
+            y + 124,
+            &tile.detail,
+            scale,
+            tile.detail_color,
+        );
+    }
+    canvas.draw_text(MARGIN, CARD_HEIGHT - 45, "PRICES IN USD", 3, MUTED);
+
+    canvas.encode_png()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_encoding() -> std::io::Result<()> {
+        let mut canvas = Canvas::new(4, 3, BACKGROUND);
+        canvas.fill_rect(1, 1, 10, 10, ACCENT);
+        assert_eq!(canvas.pixels.get(..3), Some(&BACKGROUND[..]));
+        assert_eq!(canvas.pixels.get(15..18), Some(&ACCENT[..]));
+
+        let png = canvas.encode_png()?;
+        assert_eq!(png.get(..8), Some(&PNG_SIGNATURE[..]));
+        assert_eq!(png.get(12..16), Some(&b"IHDR"[..]));
+        assert_eq!(png.get(16..24), Some(&[0, 0, 0, 4, 0, 0, 0, 3][..]));
+        assert_eq!(png.get(png.len() - 8..png.len() - 4), Some(&b"IEND"[..]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_text_layout() {
+        assert_eq!(Canvas::text_width("", 3), 0);
+        assert_eq!(Canvas::text_width("AB", 2), 22);
+        assert_eq!(Canvas::fit_scale("$100,000.00", 292, 5), 4);
+        assert_eq!(compact_usd(2_450_000_000_000.0), "$2.45T");
+        assert_eq!(compact_usd(95_000_000_000.0), "$95.00B");
+        assert_eq!(compact_usd(12_345.0), "$12,345.00");
+    }
+}
@@ -0,0 +1,309 @@
+//! Public Origin Resolution
+//!
+//! Absolute URLs (canonical links, OG tags, sitemaps, RSS) are built from the
+//! origin the client used. Behind the CDN that origin only appears in
+//! `X-Forwarded-Proto` / `X-Forwarded-Host`, which are honored when the socket
+//! peer is in `TRUSTED_PROXIES` (comma-separated CIDRs or addresses, e.g.
+//! `10.0.0.0/8,fd00::/8`) and the host is listed in `PUBLIC_HOSTS`
+//! (comma-separated, e.g. `cryptodashboard.me,www.cryptodashboard.me`). Only the
+//! last value of each header counts: proxies append to them, so earlier values
+//! are whatever the client sent. Otherwise every URL uses the default origin
+//! `https://cryptodashboard.me`.
+//!
+//! `resolve_public_origin` scopes the origin for the request; cache keys built
+//! while it differs from the default carry an extra segment, so pages rendered
+//! for one host are never served on another.
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+use tracing::warn;
+
+use super::ip_allowlist::IpNet;
+use super::sitemap_creator::BASE_URL;
+
+/// Max length of a forwarded host (DNS name limit plus a port)
+const MAX_HOST_LENGTH: usize = 259;
+
+/// Reverse proxies whose forwarded headers are trusted; empty trusts none
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    networks: Vec<IpNet>,
+}
+
+impl TrustedProxies {
+    /// Parse a comma-separated list, skipping (and logging) invalid entries
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        Self {
+            networks: value
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .filter_map(|entry| {
+                    let net = IpNet::parse(entry);
+                    if net.is_none() {
+                        warn!("⚠️ Ignoring invalid TRUSTED_PROXIES entry: {}", entry);
+                    }
+                    net
+                })
+                .collect(),
+        }
+    }
+
+    /// Process-wide list from `TRUSTED_PROXIES`
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static PROXIES: OnceLock<TrustedProxies> = OnceLock::new();
+        PROXIES.get_or_init(|| {
+            std::env::var("TRUSTED_PROXIES")
+                .map(|value| Self::parse(&value))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Whether requests from `peer` may set the public origin
+    #[must_use]
+    pub fn trusts(&self, peer: Option<IpAddr>) -> bool {
+        peer.is_some_and(|ip| self.networks.iter().any(|net| net.contains(ip)))
+    }
+}
+
+/// Hosts a trusted forwarded host may name; empty accepts none
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublicHosts {
+    hosts: Vec<String>,
+}
+
+impl PublicHosts {
+    /// Parse a comma-separated list, skipping (and logging) invalid entries
+    #[must_use]
+    pub fn parse(value: &str) -> Self {
+        Self {
+            hosts: value
+                .split(',')
+                .map(|entry| entry.trim().to_ascii_lowercase())
+                .filter(|entry| !entry.is_empty())
+                .filter(|entry| {
+                    let valid = is_valid_host(entry);
+                    if !valid {
+                        warn!("⚠️ Ignoring invalid PUBLIC_HOSTS entry: {}", entry);
+                    }
+                    valid
+                })
+                .collect(),
+        }
+    }
+
+    /// Process-wide list from `PUBLIC_HOSTS`
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static HOSTS: OnceLock<PublicHosts> = OnceLock::new();
+        HOSTS.get_or_init(|| {
+            std::env::var("PUBLIC_HOSTS")
+                .map(|value| Self::parse(&value))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Whether `host` (lowercase) is listed
+    #[must_use]
+    pub fn allows(&self, host: &str) -> bool {
+        self.hosts.iter().any(|allowed| allowed == host)
+    }
+}
+
+tokio::task_local! {
+    /// Origin of the request being served, when it differs from `BASE_URL`
+    static PUBLIC_ORIGIN: String;
+}
+
+/// Scheme and host of absolute URLs for the current request, without a trailing
+/// slash (`BASE_URL` outside a request or without trusted forwarded headers)
+#[must_use]
+pub fn public_origin() -> String {
+    PUBLIC_ORIGIN
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| BASE_URL.to_string())
+}
+
+/// Cache key segment of the current origin; `None` for the default origin
+#[must_use]
+pub fn origin_key_segment() -> Option<String> {
+    PUBLIC_ORIGIN.try_with(|origin| origin_segment(origin)).ok()
+}
+
+fn origin_segment(origin: &str) -> String {
+    let hash = blake3::hash(origin.as_bytes()).to_hex();
+    format!("origin-{}", hash.get(..12).unwrap_or_default())
+}
+
+/// Scope the public origin of trusted forwarded requests
+pub async fn resolve_public_origin(request: Request, next: Next) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    match forwarded_origin(
+        request.headers(),
+        peer,
+        TrustedProxies::current(),
+        PublicHosts::current(),
+    ) {
+        Some(origin) if origin != BASE_URL => PUBLIC_ORIGIN.scope(origin, next.run(request)).await,
+        _ => next.run(request).await,
+    }
+}
+
+/// `{proto}://{host}` from the forwarded headers of a trusted peer; a missing
+/// proto defaults to https, a missing, malformed or unlisted host gives `None`
+fn forwarded_origin(
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    proxies: &TrustedProxies,
+    hosts: &PublicHosts,
+) -> Option<String> {
+    if !proxies.trusts(peer) {
+        return None;
+    }
+    // Chained proxies append their own values; the last one is the trusted peer's
+    let last_value = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty())
+    };
+    let host =
+        last_value("x-forwarded-host").filter(|host| is_valid_host(host) && hosts.allows(host))?;
+    let proto = match last_value("x-forwarded-proto").as_deref() {
+        None | Some("https") => "https",
+        Some("http") => "http",
+        Some(_) => return None,
+    };
+    Some(format!("{proto}://{host}"))
+}
+
+/// DNS name or IPv4 address, with an optional port (lowercase input)
+fn is_valid_host(host: &str) -> bool {
+    let (name, port) = host
+        .split_once(':')
+        .map_or((host, None), |(name, port)| (name, Some(port)));
+    let valid_name = !name.is_empty()
+        && !name.starts_with(['.', '-'])
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'.' | b'-'));
+    let valid_port = port.is_none_or(|port| port.parse::<u16>().is_ok_and(|port| port > 0));
+    host.len() <= MAX_HOST_LENGTH && valid_name && valid_port
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forwarded_origin_from_trusted_proxy() -> Result<(), Box<dyn std::error::Error>> {
+        let proxies = TrustedProxies::parse("10.0.0.0/8, bogus");
+        let hosts = PublicHosts::parse("news.example.org, bad host");
+        let proxy: IpAddr = "10.1.2.3".parse()?;
+        let mut headers = HeaderMap::new();
+        // The client's own value comes first, the proxy appends the real host
+        headers.insert(
+            "x-forwarded-host",
+            "evil.example, News.Example.org".parse()?,
+        );
+        assert_eq!(
+            forwarded_origin(&headers, Some(proxy), &proxies, &hosts).as_deref(),
+            Some("https://news.example.org")
+        );
+
+        headers.insert("x-forwarded-proto", "https, http".parse()?);
+        assert_eq!(
+            forwarded_origin(&headers, Some(proxy), &proxies, &hosts).as_deref(),
+            Some("http://news.example.org")
+        );
+
+        // Untrusted peers and an empty list never set the origin
+        assert_eq!(
+            forwarded_origin(&headers, Some("203.0.113.7".parse()?), &proxies, &hosts),
+            None
+        );
+        assert_eq!(forwarded_origin(&headers, None, &proxies, &hosts), None);
+        assert_eq!(
+            forwarded_origin(&headers, Some(proxy), &TrustedProxies::parse(""), &hosts),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unlisted_forwarded_hosts_are_ignored() -> Result<(), Box<dyn std::error::Error>> {
+        let proxies = TrustedProxies::parse("10.0.0.0/8");
+        let proxy = Some("10.1.2.3".parse()?);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-host",
+            "news.example.org, evil.example".parse()?,
+        );
+        let hosts = PublicHosts::parse("news.example.org");
+        assert_eq!(forwarded_origin(&headers, proxy, &proxies, &hosts), None);
+        // Unset PUBLIC_HOSTS accepts no forwarded host
+        headers.insert("x-forwarded-host", "news.example.org".parse()?);
+        assert_eq!(
+            forwarded_origin(&headers, proxy, &proxies, &PublicHosts::default()),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_forwarded_headers_are_ignored() -> Result<(), Box<dyn std::error::Error>> {
+        let proxies = TrustedProxies::parse("127.0.0.1");
+        let hosts = PublicHosts::parse("example.org,localhost:3000");
+        let peer = Some("127.0.0.1".parse()?);
+        for (host, proto) in [
+            ("evil.com/path", "https"),
+            ("a\"b.com", "https"),
+            ("example.org:99999", "https"),
+            ("example.org", "javascript"),
+            ("", "https"),
+        ] {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-forwarded-host", host.parse()?);
+            headers.insert("x-forwarded-proto", proto.parse()?);
+            assert_eq!(
+                forwarded_origin(&headers, peer, &proxies, &hosts),
+                None,
+                "{host}"
+            );
+        }
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-host", "localhost:3000".parse()?);
+        assert_eq!(
+            forwarded_origin(&headers, peer, &proxies, &hosts).as_deref(),
+            Some("https://localhost:3000")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_scoped_origin() {
+        assert_eq!(public_origin(), BASE_URL);
+        assert_eq!(origin_key_segment(), None);
+
+        let (origin, segment) = PUBLIC_ORIGIN
+            .scope("https://news.example.org".to_string(), async {
+                (public_origin(), origin_key_segment())
+            })
+            .await;
+        assert_eq!(origin, "https://news.example.org");
+        assert!(segment.is_some_and(|s| s.starts_with("origin-") && s.len() == 19));
+    }
+}
@@ -0,0 +1,362 @@
+//! Publish Notifier
+//!
+//! When a report is published (a `report_published` event on the `report_events`
+//! stream), tells feed subscribers and search engines about it within seconds
+//! instead of waiting for their next poll:
+//! - `WEBSUB_HUB_URL`: `WebSub` hub pinged (`hub.mode=publish`) for every site-wide
+//!   feed (RSS, Atom and JSON Feed, vi and en). The feeds advertise the hub with a
+//!   `rel="hub"` link so readers can subscribe to it.
+//! - `INDEXNOW_KEY`: `IndexNow` key; the vi/en URLs of the report are submitted to
+//!   `INDEXNOW_ENDPOINT` (default `https://api.indexnow.org/indexnow`), and the key
+//!   is served at `/indexnow.txt` for verification.
+//!
+//! Both are off when unset. Topic and report URLs use the default origin, like
+//! RSS guids. Unindexable reports are not submitted; failed pings are logged.
+//!
+//! Every process reads the events, so a report is first claimed in the database
+//! (`crypto_report.announced_at`): only the process that claims it pings, once per
+//! report across workers and replicas, and every event of a bulk publish is seen.
+
+use axum::{
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use super::rss_creator::{FeedFormat, feed_query};
+use super::sitemap_creator::BASE_URL;
+use crate::services::data_communication::CryptoDataService;
+use crate::state::AppState;
+use crate::stream::REPORT_EVENTS;
+
+const DEFAULT_INDEXNOW_ENDPOINT: &str = "https://api.indexnow.org/indexnow";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Path serving the `IndexNow` key (`keyLocation`)
+pub const INDEXNOW_KEY_PATH: &str = "/indexnow.txt";
+
+/// Where to announce published reports
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotifierConfig {
+    pub websub_hub: Option<String>,
+    pub indexnow_key: Option<String>,
+    pub indexnow_endpoint: String,
+}
+
+impl NotifierConfig {
+    /// Parse the variable values; a hub or endpoint must be an http(s) URL, and a
+    /// key 8-128 characters of `a-z`, `A-Z`, `0-9` and `-` (`IndexNow`'s format)
+    #[must_use]
+    pub fn parse(
+        websub_hub: Option<&str>,
+        indexnow_key: Option<&str>,
+        indexnow_endpoint: Option<&str>,
+    ) -> Self {
+        let url = |name: &str, value: Option<&str>| {
+            let value = value.map(str::trim).filter(|value| !value.is_empty())?;
+            if value.starts_with("https://") || value.starts_with("http://") {
+                Some(value.to_string())
+            } else {
+                warn!("⚠️ Ignoring invalid {}: {}", name, value);
+                None
+            }
+        };
+        let indexnow_key = indexnow_key
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .and_then(|key| {
+                let valid = (8..=128).contains(&key.len())
+                    && key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-');
+                if !valid {
+                    warn!("⚠️ Ignoring invalid INDEXNOW_KEY");
+                }
+                valid.then(|| key.to_string())
+            });
+        Self {
+            websub_hub: url("WEBSUB_HUB_URL", websub_hub),
+            indexnow_key,
+            indexnow_endpoint: url("INDEXNOW_ENDPOINT", indexnow_endpoint)
+                .unwrap_or_else(|| DEFAULT_INDEXNOW_ENDPOINT.to_string()),
+        }
+    }
+
+    /// `WEBSUB_HUB_URL`, `INDEXNOW_KEY` and `INDEXNOW_ENDPOINT`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok();
+        Self::parse(
+            var("WEBSUB_HUB_URL").as_deref(),
+            var("INDEXNOW_KEY").as_deref(),
+            var("INDEXNOW_ENDPOINT").as_deref(),
+        )
+    }
+
+    /// Process-wide settings
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static CONFIG: OnceLock<NotifierConfig> = OnceLock::new();
+        CONFIG.get_or_init(Self::from_env)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.websub_hub.is_some() || self.indexnow_key.is_some()
+    }
+}
+
+/// Site-wide feed URLs a hub is pinged for (every format and language)
+#[must_use]
+pub fn websub_topics() -> Vec<String> {
+    [FeedFormat::Rss, FeedFormat::Atom, FeedFormat::Json]
+        .into_iter()
+        .flat_map(|format| {
+            ["vi", "en"].map(|language| {
+                format!(
+                    "{BASE_URL}/{}{}",
+                    format.file_name(),
+                    feed_query(language, None)
+                )
+            })
+        })
+        .collect()
+}
+
+/// `IndexNow` submission body
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexNowSubmission<'a> {
+    host: &'a str,
+    key: &'a str,
+    key_location: String,
+    url_list: Vec<String>,
+}
+
+fn indexnow_submission<'a>(key: &'a str, report_url: &str) -> IndexNowSubmission<'a> {
+    IndexNowSubmission {
+        host: BASE_URL
+            .split_once("://")
+            .map_or(BASE_URL, |(_, host)| host),
+        key,
+        key_location: format!("{BASE_URL}{INDEXNOW_KEY_PATH}"),
+        url_list: vec![
+            format!("{report_url}?lang=vi"),
+            format!("{report_url}?lang=en"),
+        ],
+    }
+}
+
+/// Ping the hub and `IndexNow` for a newly published report, unless another
+/// process already claimed it
+async fn notify_published(
+    state: &Arc<AppState>,
+    client: &reqwest::Client,
+    config: &NotifierConfig,
+    report_id: i32,
+) {
+    match CryptoDataService::new()
+        .claim_report_announcement(state, report_id, chrono::Utc::now())
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            debug!("⏭️ Report {} is missing or already announced", report_id);
+            return;
+        }
+        Err(e) => {
+            warn!(
+                "⚠️ Failed to claim the announcement of report {}: {}",
+                report_id, e
+            );
+            return;
+        }
+    }
+
+    if let Some(hub) = &config.websub_hub {
+        for topic in websub_topics() {
+            let result = client
+                .post(hub)
+                .form(&[("hub.mode", "publish"), ("hub.url", topic.as_str())])
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+            match result {
+                Ok(_) => debug!("📣 WebSub hub notified for {}", topic),
+                Err(e) => warn!("⚠️ WebSub ping for {} failed: {}", topic, e),
+            }
+        }
+    }
+
+    let Some(key) = &config.indexnow_key else {
+        return;
+    };
+    let route = match CryptoDataService::new()
+        .fetch_report_route(state, report_id)
+        .await
+    {
+        Ok(Some(route)) if route.indexable => route,
+        Ok(_) => {
+            debug!(
+                "⏭️ Report {} is missing or unindexable, not submitted",
+                report_id
+            );
+            return;
+        }
+        Err(e) => {
+            warn!(
+                "⚠️ Failed to look up report {} for IndexNow: {}",
+                report_id, e
+            );
+            return;
+        }
+    };
+    let report_url = format!("{BASE_URL}/crypto_report/{report_id}-{}", route.slug);
+    let result = client
+        .post(&config.indexnow_endpoint)
+        .json(&indexnow_submission(key, &report_url))
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status);
+    match result {
+        Ok(_) => info!("📣 Submitted report {} to IndexNow", report_id),
+        Err(e) => warn!(
+            "⚠️ IndexNow submission of report {} failed: {}",
+            report_id, e
+        ),
+    }
+}
+
+/// Start the `publish_notifier` task when a hub or `IndexNow` key is configured
+pub fn spawn_publish_notifier(state: &Arc<AppState>) {
+    let config = NotifierConfig::current();
+    if !config.is_enabled() {
+        debug!("⏭️ Publish notifier disabled (WEBSUB_HUB_URL / INDEXNOW_KEY not set)");
+        return;
+    }
+    let Some(reader) = state.streams.get(REPORT_EVENTS).cloned() else {
+        info!("⏭️ Publish notifier disabled: the report_events stream is not read");
+        return;
+    };
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let task_state = Arc::clone(state);
+    state.tasks.supervise("publish_notifier", move || {
+        let (state, client) = (Arc::clone(&task_state), client.clone());
+        let mut events = reader.live_updates().subscribe_events();
+        async move {
+            loop {
+                let update = match events.recv().await {
+                    Ok(update) => update,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("⚠️ Publish notifier missed {} report events", missed);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if let Some(report_id) = published_report_id(&update.data) {
+                    notify_published(&state, &client, config, report_id).await;
+                }
+            }
+            Err("report_events updates closed".to_string())
+        }
+    });
+    info!(
+        "📣 Announcing published reports (WebSub: {}, IndexNow: {})",
+        config.websub_hub.is_some(),
+        config.indexnow_key.is_some()
+    );
+}
+
+/// Report of a `report_published` event
+fn published_report_id(event: &serde_json::Value) -> Option<i32> {
+    if event.get("event")?.as_str()? != "report_published" {
+        return None;
+    }
+    i32::try_from(event.get("report_id")?.as_i64()?).ok()
+}
+
+/// `/indexnow.txt`: the `IndexNow` key, proving the site owns the submitted URLs
+pub async fn indexnow_key() -> Response {
+    match &NotifierConfig::current().indexnow_key {
+        Some(key) => (
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            key.clone(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifier_config() {
+        let config = NotifierConfig::parse(
+            Some(" https://pubsubhubbub.appspot.com/ "),
+            Some("0123456789abcdef"),
+            None,
+        );
+        assert_eq!(
+            config.websub_hub.as_deref(),
+            Some("https://pubsubhubbub.appspot.com/")
+        );
+        assert_eq!(config.indexnow_key.as_deref(), Some("0123456789abcdef"));
+        assert_eq!(config.indexnow_endpoint, DEFAULT_INDEXNOW_ENDPOINT);
+        assert!(config.is_enabled());
+
+        let config = NotifierConfig::parse(Some("hub.example.org"), Some("short"), Some(""));
+        assert_eq!(config.websub_hub, None);
+        assert_eq!(config.indexnow_key, None);
+        assert!(!config.is_enabled());
+    }
+
+    #[test]
+    fn test_published_report_id() {
+        use serde_json::json;
+
+        assert_eq!(
+            published_report_id(&json!({"event": "report_published", "report_id": 42})),
+            Some(42)
+        );
+        assert_eq!(
+            published_report_id(&json!({"event": "report_updated", "report_id": 42})),
+            None
+        );
+        assert_eq!(
+            published_report_id(&json!({"event": "report_published"})),
+            None
+        );
+    }
+
+    #[test]
+    fn test_websub_topics_and_indexnow_body() -> Result<(), serde_json::Error> {
+        let topics = websub_topics();
+        assert_eq!(topics.len(), 6);
+        assert!(topics.contains(&"https://cryptodashboard.me/rss.xml".to_string()));
+        assert!(topics.contains(&"https://cryptodashboard.me/atom.xml?lang=en".to_string()));
+        assert!(topics.contains(&"https://cryptodashboard.me/feed.json".to_string()));
+
+        let body = serde_json::to_value(indexnow_submission(
+            "0123456789abcdef",
+            "https://cryptodashboard.me/crypto_report/7-slug",
+        ))?;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "host": "cryptodashboard.me",
+                "key": "0123456789abcdef",
+                "keyLocation": "https://cryptodashboard.me/indexnow.txt",
+                "urlList": [
+                    "https://cryptodashboard.me/crypto_report/7-slug?lang=vi",
+                    "https://cryptodashboard.me/crypto_report/7-slug?lang=en"
+                ]
+            })
+        );
+        Ok(())
+    }
+}
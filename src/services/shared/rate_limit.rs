@@ -0,0 +1,337 @@
+//! Per-IP Rate Limiting
+//!
+//! A token bucket per client IP (see `client_ip`) guards the routes that can force
+//! renders and database reads (report pages, the reports list and author archives by
+//! default), so a bot walking ids or pages cannot keep the cache cold. Each client
+//! gets `RATE_LIMIT_BURST` requests (default 30) refilled at `RATE_LIMIT_PER_SEC`
+//! (default 2); over the limit it gets 429 with `Retry-After`. `RATE_LIMIT_ROUTES`
+//! (comma-separated route patterns) replaces the default route list, and
+//! `RATE_LIMIT=false` turns limiting off.
+//!
+//! Buckets live in process memory: with `WORKERS` > 1 each worker keeps its own,
+//! so a client spread over N workers may get up to N times the burst.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use super::client_ip::client_ip;
+use crate::services::health_system::with_request_id;
+
+/// Routes limited unless `RATE_LIMIT_ROUTES` says otherwise
+const DEFAULT_LIMITED_ROUTES: &[&str] = &[
+    "/crypto_report/{id}",
+    "/crypto_reports_list",
+    "/author/{slug}",
+    "/author/{slug}/rss.xml",
+    "/api/subscribe",
+];
+const DEFAULT_BURST: f64 = 30.0;
+const DEFAULT_PER_SEC: f64 = 2.0;
+/// Past this many tracked clients, refilled (idle) buckets are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+/// Over `MAX_TRACKED_CLIENTS`, idle buckets are swept at most this often
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+/// Hard cap on tracked clients; clients beyond it share one overflow bucket
+const MAX_BUCKETS: usize = 100_000;
+
+/// Rate limit settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub burst: f64,
+    pub per_sec: f64,
+    /// Route patterns (as matched by the router) that are limited
+    pub routes: Vec<String>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            burst: DEFAULT_BURST,
+            per_sec: DEFAULT_PER_SEC,
+            routes: DEFAULT_LIMITED_ROUTES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// `RATE_LIMIT`, `RATE_LIMIT_BURST`, `RATE_LIMIT_PER_SEC` and `RATE_LIMIT_ROUTES`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let positive = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(default)
+        };
+        Self {
+            enabled: std::env::var("RATE_LIMIT").map_or(defaults.enabled, |v| {
+                !matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "0" | "false" | "off"
+                )
+            }),
+            burst: positive("RATE_LIMIT_BURST", defaults.burst),
+            per_sec: positive("RATE_LIMIT_PER_SEC", defaults.per_sec),
+            routes: std::env::var("RATE_LIMIT_ROUTES").map_or(defaults.routes, |v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|route| !route.is_empty())
+                    .map(ToString::to_string)
+                    .collect()
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token buckets keyed by client (or any other key), all with the same size and rate
+///
+/// Memory is bounded: past `MAX_TRACKED_CLIENTS`, idle buckets are swept at most
+/// once per `SWEEP_INTERVAL` (not on every request), and once `MAX_BUCKETS` clients
+/// are tracked, new ones share a single overflow bucket until a sweep makes room.
+pub struct TokenBuckets<K> {
+    burst: f64,
+    per_sec: f64,
+    buckets: DashMap<K, Bucket>,
+    overflow: Mutex<Bucket>,
+    last_sweep: Mutex<Instant>,
+    max_tracked: usize,
+    max_buckets: usize,
+}
+
+impl<K: Eq + Hash + Clone> TokenBuckets<K> {
+    #[must_use]
+    pub fn new(burst: f64, per_sec: f64) -> Self {
+        Self::with_limits(burst, per_sec, MAX_TRACKED_CLIENTS, MAX_BUCKETS)
+    }
+
+    fn with_limits(burst: f64, per_sec: f64, max_tracked: usize, max_buckets: usize) -> Self {
+        Self {
+            burst,
+            per_sec,
+            buckets: DashMap::new(),
+            overflow: Mutex::new(Bucket {
+                tokens: burst,
+                refilled_at: Instant::now(),
+            }),
+            last_sweep: Mutex::new(Instant::now()),
+            max_tracked,
+            max_buckets,
+        }
+    }
+
+    /// Take one token for `key` at `now`; `Err` carries how long until one is available
+    ///
+    /// # Errors
+    /// Returns the wait until the next token when the bucket is empty
+    pub fn check(&self, key: K, now: Instant) -> Result<(), Duration> {
+        if let Some(mut bucket) = self.buckets.get_mut(&key) {
+            return self.take(&mut bucket, now);
+        }
+
+        if self.buckets.len() >= self.max_tracked && self.sweep_due(now) {
+            self.evict_idle(now);
+        }
+        if self.buckets.len() >= self.max_buckets {
+            return self.take(&mut self.overflow.lock(), now);
+        }
+        let mut bucket = self.buckets.entry(key).or_insert(Bucket {
+            tokens: self.burst,
+            refilled_at: now,
+        });
+        self.take(&mut bucket, now)
+    }
+
+    /// Refill `bucket` up to `now`, then take one token from it
+    fn take(&self, bucket: &mut Bucket, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.per_sec).min(self.burst);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.per_sec,
+            ))
+        }
+    }
+
+    /// Whether `SWEEP_INTERVAL` has passed since the last sweep (and claim this one)
+    fn sweep_due(&self, now: Instant) -> bool {
+        let mut last_sweep = self.last_sweep.lock();
+        if now.saturating_duration_since(*last_sweep) < SWEEP_INTERVAL {
+            return false;
+        }
+        *last_sweep = now;
+        true
+    }
+
+    /// Drop buckets that have refilled completely; they behave like new clients
+    fn evict_idle(&self, now: Instant) {
+        let full_after = Duration::from_secs_f64(self.burst / self.per_sec);
+        self.buckets
+            .retain(|_, bucket| now.saturating_duration_since(bucket.refilled_at) < full_after);
+    }
+}
+
+/// Token buckets of every client seen recently
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: TokenBuckets<IpAddr>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            buckets: TokenBuckets::new(config.burst, config.per_sec),
+            config,
+        }
+    }
+
+    /// Process-wide limiter, configured from the environment on first use
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+        LIMITER.get_or_init(|| Self::new(RateLimitConfig::from_env()))
+    }
+
+    /// Whether requests to `route` are limited
+    #[must_use]
+    pub fn applies_to(&self, route: &str) -> bool {
+        self.config.enabled && self.config.routes.iter().any(|r| r == route)
+    }
+
+    /// Take one token for `client` at `now`; `Err` carries how long until one is available
+    ///
+    /// # Errors
+    /// Returns the wait until the next token when the bucket is empty
+    pub fn check(&self, client: IpAddr, now: Instant) -> Result<(), Duration> {
+        self.buckets.check(client, now)
+    }
+}
+
+/// 429 response asking the client to come back after `retry_after`
+#[must_use]
+pub fn too_many_requests(retry_after: Duration) -> Response {
+    // Round up so clients never retry before a token is back
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        with_request_id("Too many requests, slow down"),
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(secs.max(1)));
+    response
+}
+
+/// Middleware answering 429 to clients over the limit on limited routes
+pub async fn rate_limit(
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limiter = RateLimiter::current();
+    let is_limited_route = matched_path.is_some_and(|path| limiter.applies_to(path.as_str()));
+    let Some(client) = client_ip(&request).filter(|_| is_limited_route) else {
+        return next.run(request).await;
+    };
+
+    match limiter.check(client, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            debug!(%client, path = request.uri().path(), "🚦 Rate limited");
+            too_many_requests(retry_after)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(burst: f64, per_sec: f64) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig {
+            burst,
+            per_sec,
+            ..RateLimitConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_bucket_empties_and_refills() -> Result<(), Box<dyn std::error::Error>> {
+        let limiter = limiter(2.0, 1.0);
+        let client: IpAddr = "203.0.113.7".parse()?;
+        let start = Instant::now();
+        assert!(limiter.check(client, start).is_ok());
+        assert!(limiter.check(client, start).is_ok());
+        let wait = limiter.check(client, start).err();
+        assert_eq!(wait, Some(Duration::from_secs(1)));
+
+        // Other clients have their own bucket
+        assert!(limiter.check("198.51.100.1".parse()?, start).is_ok());
+        assert!(
+            limiter
+                .check(client, start + Duration::from_secs(1))
+                .is_ok()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_buckets_are_capped() {
+        let buckets = TokenBuckets::with_limits(1.0, 1.0, 2, 3);
+        let start = Instant::now();
+        for client in 0..3 {
+            assert!(buckets.check(client, start).is_ok());
+        }
+
+        // Past the hard cap, new clients share the overflow bucket
+        assert!(buckets.check(3, start).is_ok());
+        assert!(buckets.check(4, start).is_err());
+        assert_eq!(buckets.buckets.len(), 3);
+
+        // Once the tracked buckets have refilled, a sweep makes room again
+        let later = start + Duration::from_secs(2);
+        assert!(buckets.check(5, later).is_ok());
+        assert_eq!(buckets.buckets.len(), 1);
+    }
+
+    #[test]
+    fn test_limited_routes() {
+        let limiter = limiter(DEFAULT_BURST, DEFAULT_PER_SEC);
+        assert!(limiter.applies_to("/crypto_report/{id}"));
+        assert!(!limiter.applies_to("/health"));
+        let disabled = RateLimiter::new(RateLimitConfig {
+            enabled: false,
+            ..RateLimitConfig::default()
+        });
+        assert!(!disabled.applies_to("/crypto_report/{id}"));
+    }
+}
@@ -0,0 +1,408 @@
+//! Redirect Management
+//!
+//! `RedirectManager` answers legacy URLs (old Flask paths, renamed slugs) with the
+//! 301 / 302 stored in the `redirects` table, so links keep working without a
+//! redeploy. A source is an exact path, or a prefix ending in `/*` whose remainder
+//! is appended to a target ending in `/*` (`/report/*` → `/crypto_report/*`).
+//! Sources match without a trailing slash, and the request's query string is
+//! carried over to the target. Leading `/` and `\` of a remainder are dropped, and
+//! a location that would still start with `//` or `/\` (scheme-relative, i.e.
+//! another host) is never answered.
+//!
+//! The table is loaded at startup, reloaded on every admin change and every
+//! `REDIRECTS_RELOAD_SECS` (default 60), so changes made through another instance
+//! apply everywhere. Only GET and HEAD requests are redirected.
+
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::RwLock;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use super::error::{Layer5Error, Layer5Result};
+use crate::services::data_communication::{RedirectRecord, RedirectService};
+use crate::state::AppState;
+use crate::tasks::TaskRegistry;
+
+/// Default interval between reloads of the table
+const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Max length of a source path or target
+const MAX_REDIRECT_LENGTH: usize = 2048;
+
+/// Suffix marking a prefix source (and the place of the remainder in its target)
+const WILDCARD: &str = "/*";
+
+/// Where and how a source path redirects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectRule {
+    pub target: String,
+    pub status: StatusCode,
+}
+
+/// Exact and prefix rules, as loaded from the database
+#[derive(Debug, Clone, Default)]
+pub struct RedirectTable {
+    exact: HashMap<String, RedirectRule>,
+    /// Longest prefix first, so the most specific rule wins
+    prefixes: Vec<(String, RedirectRule)>,
+}
+
+impl RedirectTable {
+    /// Build from stored records, skipping (and logging) invalid ones
+    #[must_use]
+    pub fn from_records(records: &[RedirectRecord]) -> Self {
+        let mut table = Self::default();
+        for record in records {
+            let status = validate_redirect(
+                &record.source_path,
+                &record.target,
+                u16::try_from(record.status_code).ok(),
+            );
+            let Ok(status) = status else {
+                warn!(
+                    "⚠️ Ignoring invalid redirect #{}: {} -> {}",
+                    record.id, record.source_path, record.target
+                );
+                continue;
+            };
+            let rule = RedirectRule {
+                target: record.target.clone(),
+                status,
+            };
+            match record.source_path.strip_suffix(WILDCARD) {
+                Some(prefix) => table.prefixes.push((format!("{prefix}/"), rule)),
+                None => {
+                    table
+                        .exact
+                        .insert(normalize_path(&record.source_path).to_string(), rule);
+                }
+            }
+        }
+        table
+            .prefixes
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        table
+    }
+
+    /// Number of rules
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.exact.len() + self.prefixes.len()
+    }
+
+    /// Whether the table has no rules
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Status and `Location` of a request for `path?query`, if a rule matches and
+    /// the location stays on a path of this site or the rule's own URL
+    #[must_use]
+    pub fn resolve(&self, path: &str, query: Option<&str>) -> Option<(StatusCode, String)> {
+        let path = normalize_path(path);
+        let (rule, mut location) = if let Some(rule) = self.exact.get(path) {
+            (rule, rule.target.clone())
+        } else {
+            let (prefix, rule) = self.prefixes.iter().find(|(prefix, _)| {
+                path.starts_with(prefix.as_str()) || Some(path) == prefix.strip_suffix('/')
+            })?;
+            // `/old//evil.com` must not become `//evil.com`
+            let remainder = path
+                .get(prefix.len()..)
+                .unwrap_or_default()
+                .trim_start_matches(['/', '\\']);
+            let location = match rule.target.strip_suffix(WILDCARD) {
+                Some(base) if remainder.is_empty() => base.to_string(),
+                Some(base) => format!("{base}/{remainder}"),
+                None => rule.target.clone(),
+            };
+            (rule, location)
+        };
+        if is_scheme_relative(&location) {
+            warn!("⚠️ Not redirecting {} to {}", path, location);
+            return None;
+        }
+        if let Some(query) = query.filter(|query| !query.is_empty()) {
+            location.push(if location.contains('?') { '&' } else { '?' });
+            location.push_str(query);
+        }
+        Some((rule.status, location))
+    }
+}
+
+/// Current redirect table, swapped as a whole on reload
+#[derive(Default)]
+pub struct RedirectManager {
+    table: RwLock<Arc<RedirectTable>>,
+}
+
+impl RedirectManager {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the table with the rows of the `redirects` table
+    ///
+    /// # Errors
+    ///
+    /// Returns `sqlx::Error` if the table cannot be read; the previous rules stay
+    pub async fn reload(&self, db: &PgPool) -> Result<usize, sqlx::Error> {
+        let records = RedirectService::new().fetch_all(db).await?;
+        let table = RedirectTable::from_records(&records);
+        let count = table.len();
+        *self.table.write() = Arc::new(table);
+        debug!("🔀 Loaded {} redirects", count);
+        Ok(count)
+    }
+
+    /// The current table
+    #[must_use]
+    pub fn table(&self) -> Arc<RedirectTable> {
+        Arc::clone(&self.table.read())
+    }
+
+    /// Reload every `REDIRECTS_RELOAD_SECS`, supervised as `redirect_reloader`
+    pub fn spawn_reloader(self: &Arc<Self>, db: PgPool, tasks: &TaskRegistry) {
+        let interval = std::env::var("REDIRECTS_RELOAD_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&secs: &u64| secs > 0)
+            .map_or(DEFAULT_RELOAD_INTERVAL, Duration::from_secs);
+        let manager = Arc::clone(self);
+        tasks.supervise("redirect_reloader", move || {
+            let (manager, db) = (Arc::clone(&manager), db.clone());
+            async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    if let Err(e) = manager.reload(&db).await {
+                        warn!("⚠️ Failed to reload redirects: {}", e);
+                    }
+                }
+            }
+        });
+        info!("🔀 Redirects reloaded every {:?}", interval);
+    }
+}
+
+/// Check a redirect before it is stored, returning its status (default 301)
+///
+/// # Errors
+///
+/// Returns `InvalidInput` for a source that is not a plain path (or a `/*`
+/// prefix), a target that is neither a path nor an http(s) URL, a redirect to
+/// itself, or a status other than 301 / 302
+pub fn validate_redirect(
+    source: &str,
+    target: &str,
+    status: Option<u16>,
+) -> Layer5Result<StatusCode> {
+    let invalid = |message: &str| Err(Layer5Error::InvalidInput(message.to_string()));
+    let is_clean = |value: &str| {
+        !value.is_empty()
+            && value.len() <= MAX_REDIRECT_LENGTH
+            && !value.chars().any(|c| c.is_whitespace() || c.is_control())
+    };
+
+    let source_path = source.strip_suffix(WILDCARD).unwrap_or(source);
+    if !is_clean(source) || !source.starts_with('/') || is_scheme_relative(source) {
+        return invalid("source must be a path starting with /");
+    }
+    if source_path.contains(['?', '#', '*']) {
+        return invalid("source must not have a query, fragment or inner wildcard");
+    }
+    let target_is_url = target.starts_with("https://") || target.starts_with("http://");
+    if !is_clean(target)
+        || !(target_is_url || target.starts_with('/'))
+        || is_scheme_relative(target)
+    {
+        return invalid("target must be a path starting with / or an http(s) URL");
+    }
+    if target.strip_suffix(WILDCARD).is_some() && !source.ends_with(WILDCARD) {
+        return invalid("only a /* source may redirect to a /* target");
+    }
+    if normalize_path(source) == normalize_path(target) {
+        return invalid("source and target are the same");
+    }
+    match status {
+        None | Some(301) => Ok(StatusCode::MOVED_PERMANENTLY),
+        Some(302) => Ok(StatusCode::FOUND),
+        Some(_) => invalid("status must be 301 or 302"),
+    }
+}
+
+/// Whether browsers read `location` as another host (`//host`, or `/\host`)
+fn is_scheme_relative(location: &str) -> bool {
+    location.starts_with("//") || location.starts_with("/\\")
+}
+
+/// `path` without its trailing slash (except the root)
+fn normalize_path(path: &str) -> &str {
+    match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    }
+}
+
+/// Middleware answering GET / HEAD requests for a redirected path
+pub async fn redirect_legacy_urls(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let table = state.redirects.table();
+    if table.is_empty() {
+        return next.run(request).await;
+    }
+    match table.resolve(request.uri().path(), request.uri().query()) {
+        Some((status, location)) => {
+            debug!(
+                "🔀 Redirecting {} to {} ({})",
+                request.uri().path(),
+                location,
+                status
+            );
+            (status, [(header::LOCATION, location)]).into_response()
+        }
+        None => next.run(request).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: i64, source_path: &str, target: &str, status_code: i16) -> RedirectRecord {
+        RedirectRecord {
+            id,
+            source_path: source_path.to_string(),
+            target: target.to_string(),
+            status_code,
+            created_by: "test".to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_exact_and_prefix_redirects() {
+        let table = RedirectTable::from_records(&[
+            record(1, "/report/*", "/crypto_report/*", 301),
+            record(2, "/report/archive", "/crypto_reports_list", 302),
+            record(3, "/about/", "https://example.org/about?ref=old", 301),
+            record(4, "no-slash", "/", 301),
+        ]);
+        assert_eq!(table.len(), 3);
+
+        assert_eq!(
+            table.resolve("/report/42", Some("lang=en")),
+            Some((
+                StatusCode::MOVED_PERMANENTLY,
+                "/crypto_report/42?lang=en".to_string()
+            ))
+        );
+        assert_eq!(
+            table.resolve("/report", None),
+            Some((StatusCode::MOVED_PERMANENTLY, "/crypto_report".to_string()))
+        );
+        // The exact rule wins over the prefix
+        assert_eq!(
+            table.resolve("/report/archive/", None),
+            Some((StatusCode::FOUND, "/crypto_reports_list".to_string()))
+        );
+        assert_eq!(
+            table.resolve("/about", Some("x=1")),
+            Some((
+                StatusCode::MOVED_PERMANENTLY,
+                "https://example.org/about?ref=old&x=1".to_string()
+            ))
+        );
+        assert_eq!(table.resolve("/reports", None), None);
+        assert_eq!(table.resolve("/", None), None);
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let table = RedirectTable::from_records(&[
+            record(1, "/old/*", "/new/*", 301),
+            record(2, "/old/pdf/*", "/files/*", 301),
+        ]);
+        assert_eq!(
+            table
+                .resolve("/old/pdf/7", None)
+                .map(|(_, location)| location),
+            Some("/files/7".to_string())
+        );
+        assert_eq!(
+            table.resolve("/old/7", None).map(|(_, location)| location),
+            Some("/new/7".to_string())
+        );
+    }
+
+    #[test]
+    fn test_prefix_remainder_cannot_leave_the_site() {
+        let table = RedirectTable::from_records(&[record(1, "/old/*", "/*", 301)]);
+        assert_eq!(
+            table
+                .resolve("/old//evil.com", None)
+                .map(|(_, location)| location),
+            Some("/evil.com".to_string())
+        );
+        assert_eq!(
+            table
+                .resolve("/old/\\evil.com", None)
+                .map(|(_, location)| location),
+            Some("/evil.com".to_string())
+        );
+        assert_eq!(
+            table
+                .resolve("/old/reports/1", None)
+                .map(|(_, location)| location),
+            Some("/reports/1".to_string())
+        );
+        assert!(is_scheme_relative("/\\evil.com"));
+        assert!(!is_scheme_relative("/evil.com"));
+    }
+
+    #[test]
+    fn test_validate_redirect() {
+        assert_eq!(
+            validate_redirect("/old", "/new", None).ok(),
+            Some(StatusCode::MOVED_PERMANENTLY)
+        );
+        assert_eq!(
+            validate_redirect("/old/*", "https://example.org/*", Some(302)).ok(),
+            Some(StatusCode::FOUND)
+        );
+        for (source, target, status) in [
+            ("old", "/new", None),
+            ("//evil.com", "/new", None),
+            ("/old?x=1", "/new", None),
+            ("/o*d", "/new", None),
+            ("/old", "javascript:alert(1)", None),
+            ("/old", "//evil.com", None),
+            ("/old", "/\\evil.com", None),
+            ("/old", "/new\r\nSet-Cookie: x", None),
+            ("/old", "/new/*", None),
+            ("/same/", "/same", None),
+            ("/old", "/new", Some(307)),
+        ] {
+            assert!(
+                validate_redirect(source, target, status).is_err(),
+                "{source} -> {target}"
+            );
+        }
+    }
+}
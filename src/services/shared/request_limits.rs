@@ -0,0 +1,195 @@
+//! Request Size Limits
+//!
+//! Caps request bodies at `MAX_BODY_BYTES` (default 1 MiB), or tighter per-route
+//! limits for endpoints that only take small JSON documents, and query strings at
+//! `MAX_QUERY_LENGTH` characters (default 2048). Oversized requests answer 413 /
+//! 414 with an `application/problem+json` body before any handler buffers them.
+//! A declared `Content-Length` is checked up front; chunked bodies are wrapped so
+//! reading past the limit fails.
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    http::{StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use http_body_util::Limited;
+use std::sync::OnceLock;
+
+use super::response_builder::build_problem_response;
+
+const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+const DEFAULT_MAX_QUERY_LENGTH: usize = 2048;
+
+/// Body limits of routes that need less than the global limit
+const ROUTE_BODY_LIMITS: &[(&str, usize)] = &[
+    ("/admin/cache/purge", 16 * 1024),
+    ("/admin/streams/replay", 16 * 1024),
+];
+
+/// Global limits from the environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    pub max_body_bytes: usize,
+    pub max_query_length: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_query_length: DEFAULT_MAX_QUERY_LENGTH,
+        }
+    }
+}
+
+impl RequestLimits {
+    /// `MAX_BODY_BYTES` and `MAX_QUERY_LENGTH`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let positive = |name: &str, default: usize| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|&v: &usize| v > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            max_body_bytes: positive("MAX_BODY_BYTES", defaults.max_body_bytes),
+            max_query_length: positive("MAX_QUERY_LENGTH", defaults.max_query_length),
+        }
+    }
+
+    /// Process-wide limits, read on first use
+    #[must_use]
+    pub fn current() -> Self {
+        static LIMITS: OnceLock<RequestLimits> = OnceLock::new();
+        *LIMITS.get_or_init(Self::from_env)
+    }
+
+    /// Body limit of `route`: its own limit when tighter, else the global one
+    #[must_use]
+    pub fn body_limit(&self, route: Option<&str>) -> usize {
+        ROUTE_BODY_LIMITS
+            .iter()
+            .find(|(path, _)| Some(*path) == route)
+            .map_or(self.max_body_bytes, |&(_, limit)| {
+                limit.min(self.max_body_bytes)
+            })
+    }
+}
+
+/// Middleware rejecting oversized queries (414) and bodies (413)
+pub async fn enforce_request_limits(
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limits = RequestLimits::current();
+    let path = request.uri().path().to_string();
+
+    let query_length = request.uri().query().map_or(0, str::len);
+    if query_length > limits.max_query_length {
+        return build_problem_response(
+            StatusCode::URI_TOO_LONG,
+            "Query string too long",
+            &format!(
+                "The query string is {query_length} characters; the limit is {}",
+                limits.max_query_length
+            ),
+            &path,
+        );
+    }
+
+    let limit = limits.body_limit(matched_path.as_ref().map(MatchedPath::as_str));
+    let declared = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if let Some(declared) = declared.filter(|&len| len > limit as u64) {
+        return build_problem_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Request body too large",
+            &format!("The body is {declared} bytes; the limit for this endpoint is {limit}"),
+            &path,
+        );
+    }
+
+    next.run(request.map(|body| Body::new(Limited::new(body, limit))))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware, routing::post};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/admin/cache/purge",
+                post(|body: axum::body::Bytes| async move { body.len().to_string() }),
+            )
+            .route_layer(middleware::from_fn(enforce_request_limits))
+    }
+
+    #[test]
+    fn test_route_body_limits() {
+        let limits = RequestLimits::default();
+        assert_eq!(limits.body_limit(Some("/admin/cache/purge")), 16 * 1024);
+        assert_eq!(
+            limits.body_limit(Some("/crypto_report/{id}")),
+            DEFAULT_MAX_BODY_BYTES
+        );
+        assert_eq!(limits.body_limit(None), DEFAULT_MAX_BODY_BYTES);
+
+        // A lower global limit also caps the per-route ones
+        let tight = RequestLimits {
+            max_body_bytes: 1024,
+            ..limits
+        };
+        assert_eq!(tight.body_limit(Some("/admin/cache/purge")), 1024);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_requests_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let long_query = format!(
+            "/admin/cache/purge?q={}",
+            "a".repeat(DEFAULT_MAX_QUERY_LENGTH)
+        );
+        let response = app()
+            .oneshot(Request::post(long_query).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::URI_TOO_LONG);
+
+        let response = app()
+            .oneshot(
+                Request::post("/admin/cache/purge")
+                    .header(header::CONTENT_LENGTH, 20_000)
+                    .body(Body::from(vec![b'x'; 20_000]))?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Without a Content-Length the body is cut off while being read
+        let chunked =
+            Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(vec![
+                b'x';
+                20_000
+            ])]));
+        let response = app()
+            .oneshot(Request::post("/admin/cache/purge").body(chunked)?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let response = app()
+            .oneshot(Request::post("/admin/cache/purge").body(Body::from("{}"))?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        Ok(())
+    }
+}
@@ -9,10 +9,13 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
+use crate::services::health_system::{current_request_id, with_request_id};
+
+/// `Vary` value for responses localized from the language cookie or `Accept-Language`
+pub const LANGUAGE_VARY: &str = "Accept-Language, Cookie";
+
 /// Cache control header values
 pub mod cache_control {
-    /// Short cache for dynamic content (15 seconds)
-    pub const SHORT: &str = "public, max-age=15";
     /// Private cache for user-specific content (1 hour)
     pub const PRIVATE_LONG: &str = "private, max-age=3600";
     /// No cache for real-time data
@@ -31,14 +34,13 @@ pub mod security_headers {
 
 /// Build a compressed HTML response with proper headers
 ///
-/// This function is guaranteed to never panic. If response building fails
+/// `Cache-Control` is left to the route's cache policy. This function is guaranteed to never panic. If response building fails
 /// (which should never happen with valid inputs), it returns a safe error response.
 #[inline]
 #[must_use]
 pub fn build_compressed_response(compressed_data: Vec<u8>, cache_status: &str) -> Response {
     Response::builder()
         .status(StatusCode::OK)
-        .header("cache-control", cache_control::SHORT)
         .header("x-cache", cache_status)
         .header("content-type", "text/html; charset=utf-8")
         .header("content-encoding", "gzip")
@@ -47,13 +49,12 @@ pub fn build_compressed_response(compressed_data: Vec<u8>, cache_status: &str) -
         .into_response()
 }
 
-/// Build a standard HTML response (uncompressed)
+/// Build a standard HTML response (uncompressed, `Cache-Control` from the route policy)
 #[inline]
 #[must_use]
 pub fn build_html_response(html: String, cache_status: &str) -> Response {
     Response::builder()
         .status(StatusCode::OK)
-        .header("cache-control", cache_control::SHORT)
         .header("x-cache", cache_status)
         .header("content-type", "text/html; charset=utf-8")
         .body(Body::from(html))
@@ -68,7 +69,46 @@ pub fn build_error_response(status: StatusCode, message: &str) -> Response {
     Response::builder()
         .status(status)
         .header("content-type", "text/plain; charset=utf-8")
-        .body(Body::from(message.to_string()))
+        .body(Body::from(with_request_id(message)))
+        .unwrap_or_else(|_| fallback_error_response())
+        .into_response()
+}
+
+/// Build an RFC 9457 `application/problem+json` response
+///
+/// `instance` is the request path; the request id is added when one is being served.
+#[must_use]
+pub fn build_problem_response(
+    status: StatusCode,
+    title: &str,
+    detail: &str,
+    instance: &str,
+) -> Response {
+    build_problem_response_with(status, title, detail, instance, serde_json::Map::new())
+}
+
+/// Build a problem response carrying extension members (e.g. per-field `errors`)
+#[must_use]
+pub fn build_problem_response_with(
+    status: StatusCode,
+    title: &str,
+    detail: &str,
+    instance: &str,
+    extensions: serde_json::Map<String, serde_json::Value>,
+) -> Response {
+    let mut problem = extensions;
+    problem.insert("type".to_string(), "about:blank".into());
+    problem.insert("title".to_string(), title.into());
+    problem.insert("status".to_string(), status.as_u16().into());
+    problem.insert("detail".to_string(), detail.into());
+    problem.insert("instance".to_string(), instance.into());
+    if let Some(request_id) = current_request_id() {
+        problem.insert("request_id".to_string(), request_id.to_string().into());
+    }
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/problem+json")
+        .body(Body::from(serde_json::Value::Object(problem).to_string()))
         .unwrap_or_else(|_| fallback_error_response())
         .into_response()
 }
@@ -157,4 +197,22 @@ mod tests {
         let response = build_error_response(StatusCode::NOT_FOUND, "Not found");
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[test]
+    fn test_build_problem_response() {
+        let response = build_problem_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Request body too large",
+            "too big",
+            "/admin/cache/purge",
+        );
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            response
+                .headers()
+                .get("content-type")
+                .and_then(|v| v.to_str().ok()),
+            Some("application/problem+json")
+        );
+    }
 }
@@ -0,0 +1,249 @@
+//! robots.txt Generation
+//!
+//! `/robots.txt` follows the deployment mode (`APP_ENV`): staging disallows every
+//! crawler so it never competes with the live site, while production (and
+//! development) allow all crawlers except on `/admin/` and link the sitemap.
+//!
+//! Per-bot groups come from `ROBOTS_BOT_RULES`, so SEO can tune crawler access
+//! without a code change: `;`-separated `<user-agent>=<allow|disallow>[ <path>,...]`
+//! entries, the path defaulting to `/`, e.g.
+//! `GPTBot=allow;CCBot=disallow;Bytespider=disallow /api/,/crypto_report/`.
+//! Unset keeps the built-in AI crawler allowances; an empty value removes them.
+//! `ROBOTS_SITEMAP_URL` overrides the advertised sitemap, which is otherwise
+//! `/sitemap.xml` on the request's public origin.
+
+use std::fmt::Write;
+use std::sync::OnceLock;
+use tracing::warn;
+
+use super::public_origin::public_origin;
+use crate::secrets::AppEnv;
+
+/// AI crawlers allowed on the whole site unless `ROBOTS_BOT_RULES` says otherwise
+const DEFAULT_AI_ALLOWANCES: &[&str] = &[
+    // OpenAI (ChatGPT & Search)
+    "GPTBot",
+    "ChatGPT-User",
+    "OAI-SearchBot",
+    // xAI (Grok)
+    "GrokBot",
+    "xAI-Grok",
+    // Anthropic (Claude)
+    "ClaudeBot",
+    "anthropic-ai",
+    // Google (Gemini)
+    "Google-Extended",
+    // Perplexity AI
+    "PerplexityBot",
+    "Perplexity-User",
+    // Common Crawl (data source for many LLMs)
+    "CCBot",
+];
+
+/// Paths no crawler should fetch, even in production
+const PRIVATE_PATHS: &[&str] = &["/admin/"];
+
+/// Access a rule grants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RobotsAccess {
+    Allow,
+    Disallow,
+}
+
+/// Rules of one user agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BotRule {
+    pub user_agent: String,
+    pub access: RobotsAccess,
+    pub paths: Vec<String>,
+}
+
+/// robots.txt settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RobotsConfig {
+    pub env: AppEnv,
+    /// Fixed sitemap URL; `None` links `/sitemap.xml` on the public origin
+    pub sitemap_url: Option<String>,
+    pub bot_rules: Vec<BotRule>,
+}
+
+impl RobotsConfig {
+    /// Build from the raw variable values (`None` = unset); invalid rules are skipped
+    #[must_use]
+    pub fn parse(env: AppEnv, bot_rules: Option<&str>, sitemap_url: Option<&str>) -> Self {
+        let bot_rules = bot_rules.map_or_else(default_bot_rules, |value| {
+            value
+                .split(';')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .filter_map(|entry| {
+                    let rule = parse_rule(entry);
+                    if rule.is_none() {
+                        warn!("⚠️ Ignoring invalid ROBOTS_BOT_RULES entry: {}", entry);
+                    }
+                    rule
+                })
+                .collect()
+        });
+        Self {
+            env,
+            sitemap_url: sitemap_url
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(ToString::to_string),
+            bot_rules,
+        }
+    }
+
+    /// `APP_ENV`, `ROBOTS_BOT_RULES` and `ROBOTS_SITEMAP_URL`
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::parse(
+            AppEnv::current(),
+            std::env::var("ROBOTS_BOT_RULES").ok().as_deref(),
+            std::env::var("ROBOTS_SITEMAP_URL").ok().as_deref(),
+        )
+    }
+
+    /// Process-wide settings, read on first use
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static CONFIG: OnceLock<RobotsConfig> = OnceLock::new();
+        CONFIG.get_or_init(Self::from_env)
+    }
+
+    /// The robots.txt body
+    #[must_use]
+    pub fn render(&self) -> String {
+        if self.env == AppEnv::Staging {
+            return "# Staging environment: not for indexing\nUser-agent: *\nDisallow: /\n"
+                .to_string();
+        }
+
+        let mut body = String::from("User-agent: *\nAllow: /\n");
+        for path in PRIVATE_PATHS {
+            let _ = writeln!(body, "Disallow: {path}");
+        }
+        for rule in &self.bot_rules {
+            let directive = match rule.access {
+                RobotsAccess::Allow => "Allow",
+                RobotsAccess::Disallow => "Disallow",
+            };
+            let _ = write!(body, "\nUser-agent: {}\n", rule.user_agent);
+            for path in &rule.paths {
+                let _ = writeln!(body, "{directive}: {path}");
+            }
+            // A group replaces the `*` group for that bot, so repeat the private paths
+            if rule.access == RobotsAccess::Allow {
+                for path in PRIVATE_PATHS {
+                    let _ = writeln!(body, "Disallow: {path}");
+                }
+            }
+        }
+        let sitemap_url = self
+            .sitemap_url
+            .clone()
+            .unwrap_or_else(|| format!("{}/sitemap.xml", public_origin()));
+        let _ = write!(body, "\nSitemap: {sitemap_url}\n");
+        body
+    }
+}
+
+fn default_bot_rules() -> Vec<BotRule> {
+    DEFAULT_AI_ALLOWANCES
+        .iter()
+        .map(|agent| BotRule {
+            user_agent: (*agent).to_string(),
+            access: RobotsAccess::Allow,
+            paths: vec!["/".to_string()],
+        })
+        .collect()
+}
+
+/// `<user-agent>=<allow|disallow>[ <path>,...]`
+fn parse_rule(entry: &str) -> Option<BotRule> {
+    let (user_agent, rule) = entry.split_once('=')?;
+    let user_agent = user_agent.trim();
+    let valid_agent = !user_agent.is_empty()
+        && user_agent
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '*'));
+    if !valid_agent {
+        return None;
+    }
+    let rule = rule.trim();
+    let (access, paths) = rule.split_once(char::is_whitespace).unwrap_or((rule, ""));
+    let access = match access.to_ascii_lowercase().as_str() {
+        "allow" => RobotsAccess::Allow,
+        "disallow" => RobotsAccess::Disallow,
+        _ => return None,
+    };
+    let mut paths: Vec<String> = paths
+        .split(',')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(ToString::to_string)
+        .collect();
+    if paths.iter().any(|path| !path.starts_with('/')) {
+        return None;
+    }
+    if paths.is_empty() {
+        paths.push("/".to_string());
+    }
+    Some(BotRule {
+        user_agent: user_agent.to_string(),
+        access,
+        paths,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_production_robots() {
+        let robots = RobotsConfig::parse(AppEnv::Production, None, None).render();
+        assert!(robots.starts_with("User-agent: *\nAllow: /\nDisallow: /admin/\n"));
+        assert!(robots.contains("\nUser-agent: GPTBot\nAllow: /\nDisallow: /admin/\n"));
+        assert!(robots.contains("User-agent: CCBot"));
+        assert!(robots.ends_with("\nSitemap: https://cryptodashboard.me/sitemap.xml\n"));
+    }
+
+    #[test]
+    fn test_staging_disallows_everything() {
+        let robots = RobotsConfig::parse(AppEnv::Staging, None, None).render();
+        assert!(robots.contains("User-agent: *\nDisallow: /\n"));
+        assert!(!robots.contains("Allow: /\n"));
+        assert!(!robots.contains("Sitemap:"));
+    }
+
+    #[test]
+    fn test_custom_bot_rules() {
+        let config = RobotsConfig::parse(
+            AppEnv::Production,
+            Some(
+                "GPTBot=allow; CCBot=disallow; Bytespider=Disallow /api/, /crypto_report/; bad; Evil Bot=allow; X=maybe",
+            ),
+            Some("https://example.org/sitemap.xml"),
+        );
+        let agents: Vec<_> = config
+            .bot_rules
+            .iter()
+            .map(|rule| rule.user_agent.as_str())
+            .collect();
+        assert_eq!(agents, ["GPTBot", "CCBot", "Bytespider"]);
+
+        let robots = config.render();
+        assert!(robots.contains("\nUser-agent: CCBot\nDisallow: /\n"));
+        assert!(
+            robots
+                .contains("\nUser-agent: Bytespider\nDisallow: /api/\nDisallow: /crypto_report/\n")
+        );
+        assert!(!robots.contains("ClaudeBot"));
+        assert!(robots.ends_with("Sitemap: https://example.org/sitemap.xml\n"));
+
+        let no_rules = RobotsConfig::parse(AppEnv::Production, Some(""), None);
+        assert!(no_rules.bot_rules.is_empty());
+    }
+}
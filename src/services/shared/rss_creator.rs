@@ -1,81 +1,504 @@
-//! RSS Feed XML Generator for Layer 5 Business Logic
+//! Feed XML Generator for Layer 5 Business Logic
 //!
-//! Generates dynamic RSS 2.0 feed following the RSS 2.0 specification.
+//! Generates dynamic RSS 2.0, Atom 1.0 and JSON Feed 1.1 feeds of the latest reports.
 //! This module creates XML content for search engines and AI bots to discover new reports.
 //!
-//! Reference: <https://www.rssboard.org/rss-specification>
+//! Reference: <https://www.rssboard.org/rss-specification>,
+//! <https://www.rfc-editor.org/rfc/rfc4287>, <https://www.jsonfeed.org/version/1.1/>,
+//! <https://www.rfc-editor.org/rfc/rfc5005>
 //!
 //! Features:
-//! - RFC 822 date formatting for pubDate
+//! - RFC 822 date formatting for pubDate, RFC 3339 for Atom timestamps
 //! - HTML content extraction for descriptions
 //! - XML entity escaping
 //! - Atom namespace for self-referencing link
+//! - Per-author feeds with `dc:creator` attribution
+//! - Per-tag feeds (`?tag=btc`), with tags as RSS / Atom categories
+//! - Optional full report HTML (`content:encoded`) and an enclosure in RSS items,
+//!   see `FeedContentConfig`
+//! - Sanitized `content_html` and derived topic tags in JSON Feed items
+//! - RFC 5005 archives of the site feed in RSS / Atom (`?archive=N`): full pages of
+//!   `RSS_FEED_LIMIT` reports counted from the oldest, so an archive URL always lists
+//!   the same reports, chained with `prev-archive` / `next-archive` links
+//!
+//! All formats share `FeedBuilder`; an Atom entry's and a JSON Feed item's `id` is
+//! the RSS `guid`, so a reader switching formats does not see the reports again.
 
-use chrono::{DateTime, FixedOffset, Offset, Utc};
+use chrono::{DateTime, FixedOffset, Offset, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
 use std::fmt::Write;
-use tracing::info;
+use std::sync::OnceLock;
+use tracing::{info, warn};
 
 use super::error::{Layer5Error, Layer5Result};
-use crate::services::data_communication::crypto_data_service::ReportRssData;
-
-/// Base URL for the website
-const BASE_URL: &str = "https://cryptodashboard.me";
+use super::public_origin::public_origin;
+use super::publish_notifier::NotifierConfig;
+use super::sitemap_creator::BASE_URL;
+use crate::services::crypto_reports::rendering::sanitize_report_html;
+use crate::services::data_communication::{
+    AuthorData, crypto_data_service::ReportRssData, report_path,
+};
 
 /// Maximum characters for description extraction
 const MAX_DESCRIPTION_LENGTH: usize = 300;
 
+/// Image attached by `FEED_ENCLOSURE=image` (the default OG image)
+const ENCLOSURE_IMAGE_PATH: &str = "/shared_assets/images/image.jpg";
+
+/// Placeholder for the report id in `FEED_PDF_URL`
+const PDF_URL_ID: &str = "{id}";
+
+/// Topic tags and the words of a report's text that imply them
+const REPORT_TAGS: &[(&str, &[&str])] = &[
+    ("btc", &["bitcoin", "btc"]),
+    ("eth", &["ethereum", "eth"]),
+    ("sol", &["solana", "sol"]),
+    ("bnb", &["bnb"]),
+    ("xrp", &["xrp", "ripple"]),
+    ("stablecoin", &["stablecoin", "stablecoins", "usdt", "usdc"]),
+    ("defi", &["defi"]),
+    ("etf", &["etf", "etfs"]),
+];
+
+/// Feed syndication format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    /// RSS 2.0 (`rss.xml`)
+    Rss,
+    /// Atom 1.0 (`atom.xml`)
+    Atom,
+    /// JSON Feed 1.1 (`feed.json`)
+    Json,
+}
+
+impl FeedFormat {
+    /// File name of the feed under its path
+    #[must_use]
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::Rss => "rss.xml",
+            Self::Atom => "atom.xml",
+            Self::Json => "feed.json",
+        }
+    }
+
+    /// `Content-Type` of the feed
+    #[must_use]
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml; charset=utf-8",
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+
+    const fn mime_type(self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml",
+            Self::Atom => "application/atom+xml",
+            Self::Json => "application/feed+json",
+        }
+    }
+}
+
 /// RSS Feed metadata
 struct FeedMetadata {
-    title: &'static str,
-    link: &'static str,
-    description: &'static str,
+    title: String,
+    link: String,
+    description: String,
     language: &'static str,
+    /// Path the feed files live under (`""` for the site, `/author/{slug}`)
+    feed_path: String,
+    /// Topic tag the feed is limited to
+    tag: Option<&'static str>,
+    /// Item creator (`dc:creator`), set for per-author feeds
+    creator: Option<String>,
 }
 
-impl Default for FeedMetadata {
-    fn default() -> Self {
+impl FeedMetadata {
+    /// Channel metadata for the site-wide feed in `language` (`en` or Vietnamese)
+    fn site(language: &str) -> Self {
+        let origin = public_origin();
+        if language == "en" {
+            Self {
+                title: "CryptoDashboard - Crypto Market Reports".to_string(),
+                link: origin,
+                description: "Daily crypto market analysis reports with real-time data from Binance, CoinGecko and other trusted sources".to_string(),
+                language: "en-US",
+                feed_path: String::new(),
+                tag: None,
+                creator: None,
+            }
+        } else {
+            Self {
+                title: "CryptoDashboard - Báo cáo Thị trường Crypto".to_string(),
+                link: origin,
+                description: "Báo cáo phân tích thị trường crypto hàng ngày với dữ liệu real-time từ Binance, CoinGecko và các nguồn uy tín".to_string(),
+                language: "vi-VN",
+                feed_path: String::new(),
+                tag: None,
+                creator: None,
+            }
+        }
+    }
+
+    /// Channel metadata for an author's feed
+    fn for_author(author: &AuthorData, language: &str) -> Self {
+        let archive_path = author.archive_path();
         Self {
-            title: "CryptoDashboard - Báo cáo Thị trường Crypto",
-            link: BASE_URL,
-            description: "Báo cáo phân tích thị trường crypto hàng ngày với dữ liệu real-time từ Binance, CoinGecko và các nguồn uy tín",
-            language: "vi-VN",
+            title: format!("{} - CryptoDashboard", author.name),
+            description: author.bio.clone().unwrap_or_else(|| {
+                if language == "en" {
+                    format!("Crypto market analysis reports by {}", author.name)
+                } else {
+                    format!("Báo cáo phân tích thị trường crypto của {}", author.name)
+                }
+            }),
+            link: format!("{}{archive_path}", public_origin()),
+            feed_path: archive_path,
+            creator: Some(author.name.clone()),
+            ..Self::site(language)
         }
     }
+
+    fn is_english(&self) -> bool {
+        self.language == "en-US"
+    }
+
+    /// Path and query of the feed file in `format`
+    fn feed_file(&self, format: FeedFormat) -> String {
+        let language = if self.is_english() { "en" } else { "vi" };
+        format!(
+            "{}/{}{}",
+            self.feed_path,
+            format.file_name(),
+            feed_query(language, self.tag)
+        )
+    }
+
+    /// Absolute URL of the feed itself (self link)
+    fn self_link(&self, format: FeedFormat) -> String {
+        format!("{}{}", public_origin(), self.feed_file(format))
+    }
+
+    /// Absolute URL of archive page `page` of the feed
+    fn archive_link(&self, format: FeedFormat, page: u32) -> String {
+        let file = self.feed_file(format);
+        let separator = if file.contains('?') { '&' } else { '?' };
+        format!("{}{file}{separator}archive={page}", public_origin())
+    }
 }
 
-/// RSS Feed XML generator
-pub struct RssCreator;
+/// One report as a feed item / entry
+struct FeedEntry<'a> {
+    report_id: i32,
+    title: String,
+    link: String,
+    /// RSS `guid`, Atom and JSON Feed `id`: the bare-ID URL on the default origin,
+    /// which predates slugs and must not change; it redirects to the slug URL
+    id: String,
+    published: DateTime<Utc>,
+    summary: String,
+    /// Report HTML in the feed language
+    content: &'a str,
+    /// Topic tags of `content`
+    tags: Vec<&'static str>,
+}
 
-impl RssCreator {
-    /// Generate complete RSS 2.0 XML feed from report data
+impl<'a> FeedEntry<'a> {
+    fn new(report: &'a ReportRssData, metadata: &FeedMetadata) -> Self {
+        let language = if metadata.is_english() { "en" } else { "vi" };
+        let title = report_title(report.id, report.created_at, language);
+
+        // English feeds fall back to the Vietnamese content for untranslated reports
+        let content = report.html_for_language(language);
+
+        Self {
+            report_id: report.id,
+            title,
+            link: format!(
+                "{}{}",
+                public_origin(),
+                report_path(report.id, report.created_at)
+            ),
+            id: format!("{}/crypto_report/{}", BASE_URL, report.id),
+            published: report.created_at,
+            summary: RssCreator::extract_description(content, MAX_DESCRIPTION_LENGTH),
+            content,
+            tags: report_tags(content),
+        }
+    }
+}
+
+/// Media attached to each RSS item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedEnclosure {
+    /// The default OG image, `length` bytes long (0 when unknown)
+    Image { length: u64 },
+    /// The report PDF at `url_template` with `{id}` replaced by the report id;
+    /// a path is resolved against the public origin
+    Pdf { url_template: String },
+}
+
+/// Optional content of RSS items, for readers that want complete offline copies
+///
+/// - `FEED_FULL_CONTENT`: `true` adds the sanitized report HTML as `content:encoded`
+/// - `FEED_ENCLOSURE`: `image` (OG image) or `pdf` attaches an enclosure (unset: none)
+/// - `FEED_PDF_URL`: PDF location for `pdf`, e.g. `https://files.example.org/reports/{id}.pdf`
+///
+/// RSS readers generally handle a single enclosure per item, so only one is attached.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedContentConfig {
+    pub full_content: bool,
+    pub enclosure: Option<FeedEnclosure>,
+}
+
+impl FeedContentConfig {
+    /// Parse the variable values, logging unusable ones; `image_length` is the
+    /// size of the enclosure image
+    #[must_use]
+    pub fn parse(
+        full_content: Option<&str>,
+        enclosure: Option<&str>,
+        pdf_url: Option<&str>,
+        image_length: u64,
+    ) -> Self {
+        let full_content = full_content.is_some_and(|value| {
+            matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "on"
+            )
+        });
+        let pdf_url = pdf_url
+            .map(str::trim)
+            .filter(|url| url.contains(PDF_URL_ID));
+        let enclosure = match enclosure.map(|value| value.trim().to_ascii_lowercase()) {
+            None => None,
+            Some(value) if value.is_empty() || value == "none" => None,
+            Some(value) if value == "image" => Some(FeedEnclosure::Image {
+                length: image_length,
+            }),
+            Some(value) if value == "pdf" => {
+                if pdf_url.is_none() {
+                    warn!("⚠️ FEED_ENCLOSURE=pdf needs a FEED_PDF_URL containing {{id}}");
+                }
+                pdf_url.map(|url| FeedEnclosure::Pdf {
+                    url_template: url.to_string(),
+                })
+            }
+            Some(value) => {
+                warn!("⚠️ Ignoring invalid FEED_ENCLOSURE: {}", value);
+                None
+            }
+        };
+        Self {
+            full_content,
+            enclosure,
+        }
+    }
+
+    /// `FEED_FULL_CONTENT`, `FEED_ENCLOSURE` and `FEED_PDF_URL`
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok();
+        let image_length = std::fs::metadata(format!(".{ENCLOSURE_IMAGE_PATH}"))
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        Self::parse(
+            var("FEED_FULL_CONTENT").as_deref(),
+            var("FEED_ENCLOSURE").as_deref(),
+            var("FEED_PDF_URL").as_deref(),
+            image_length,
+        )
+    }
+
+    /// Process-wide settings
+    #[must_use]
+    pub fn current() -> &'static Self {
+        static CONFIG: OnceLock<FeedContentConfig> = OnceLock::new();
+        CONFIG.get_or_init(Self::from_env)
+    }
+}
+
+/// Place of a feed document in the feed archive (RFC 5005 §4)
+#[derive(Debug, Clone, Copy)]
+struct FeedArchive {
+    /// Archive page shown by the document; `None` for the subscription feed
+    page: Option<u32>,
+    /// Number of full archive pages
+    pages: u32,
+}
+
+/// Feed of reports in one format, for the site or an author
+pub struct FeedBuilder {
+    metadata: FeedMetadata,
+    format: FeedFormat,
+    max_items: usize,
+    content: FeedContentConfig,
+    /// `WebSub` hub advertised for subscriptions
+    hub: Option<String>,
+    archive: Option<FeedArchive>,
+}
+
+impl FeedBuilder {
+    /// Site-wide feed in `language` (`en` for English, anything else for Vietnamese)
+    #[must_use]
+    pub fn site(language: &str, format: FeedFormat) -> Self {
+        Self {
+            metadata: FeedMetadata::site(language),
+            format,
+            max_items: usize::MAX,
+            content: FeedContentConfig::current().clone(),
+            hub: NotifierConfig::current().websub_hub.clone(),
+            archive: None,
+        }
+    }
+
+    /// Feed of one author's reports (`/author/{slug}/rss.xml`)
+    #[must_use]
+    pub fn author(author: &AuthorData, language: &str, format: FeedFormat) -> Self {
+        Self {
+            metadata: FeedMetadata::for_author(author, language),
+            format,
+            max_items: usize::MAX,
+            content: FeedContentConfig::current().clone(),
+            hub: NotifierConfig::current().websub_hub.clone(),
+            archive: None,
+        }
+    }
+
+    /// Limit the feed to the first `max_items` reports tagged `tag` (see `report_tags`)
     ///
-    /// # Arguments
-    /// * `reports` - Vector of `ReportRssData` from database
+    /// Titles and self links name the tag; reports without it are left out.
+    #[must_use]
+    pub fn with_tag(mut self, tag: &'static str, max_items: usize) -> Self {
+        self.metadata.title = format!("{} - {}", self.metadata.title, tag.to_uppercase());
+        self.metadata.tag = Some(tag);
+        self.max_items = max_items;
+        self
+    }
+
+    /// Replace the RSS item content settings (`FeedContentConfig::current` by default)
+    #[must_use]
+    pub fn with_content(mut self, content: FeedContentConfig) -> Self {
+        self.content = content;
+        self
+    }
+
+    /// Replace the advertised `WebSub` hub (`WEBSUB_HUB_URL` by default)
+    #[must_use]
+    pub fn with_hub(mut self, hub: Option<String>) -> Self {
+        self.hub = hub;
+        self
+    }
+
+    /// Link the RSS / Atom document into the feed archive: `page` is the archive
+    /// page it shows (`None` for the subscription feed), `pages` the number of
+    /// full archive pages
     ///
-    /// # Returns
-    /// # Returns
-    /// Complete RSS 2.0 XML string
+    /// The subscription feed links the newest archive page as `prev-archive`; an
+    /// archive page is marked `fh:archive` and links its neighbours and the
+    /// subscription feed (`current`).
+    #[must_use]
+    pub fn with_archive(mut self, page: Option<u32>, pages: u32) -> Self {
+        self.archive = Some(FeedArchive { page, pages });
+        self
+    }
+
+    /// Absolute URL of the document: its archive page, or the feed itself
+    fn document_link(&self) -> String {
+        match self.archive.and_then(|archive| archive.page) {
+            Some(page) => self.metadata.archive_link(self.format, page),
+            None => self.metadata.self_link(self.format),
+        }
+    }
+
+    /// `(rel, href)` of the archive links of the document
+    fn archive_links(&self) -> Vec<(&'static str, String)> {
+        let Some(FeedArchive { page, pages }) = self.archive else {
+            return Vec::new();
+        };
+        let archive = |page| self.metadata.archive_link(self.format, page);
+        match page {
+            None if pages > 0 => vec![("prev-archive", archive(pages))],
+            None => Vec::new(),
+            Some(page) => {
+                let mut links = vec![("current", self.metadata.self_link(self.format))];
+                if page > 1 {
+                    links.push(("prev-archive", archive(page - 1)));
+                }
+                if page < pages {
+                    links.push(("next-archive", archive(page + 1)));
+                }
+                links
+            }
+        }
+    }
+
+    /// Whether the document is an archive page (`fh:archive`)
+    fn is_archive_page(&self) -> bool {
+        self.archive.is_some_and(|archive| archive.page.is_some())
+    }
+
+    /// Generate the feed XML of `reports` (newest first)
     ///
     /// # Errors
     ///
     /// Returns error if XML writing fails
-    pub fn generate_rss_xml(reports: &[ReportRssData]) -> Layer5Result<String> {
-        let metadata = FeedMetadata::default();
-        let now = Utc::now();
+    pub fn build(&self, reports: &[ReportRssData]) -> Layer5Result<String> {
+        let entries: Vec<FeedEntry> = reports
+            .iter()
+            .map(|report| FeedEntry::new(report, &self.metadata))
+            .filter(|entry| {
+                self.metadata
+                    .tag
+                    .is_none_or(|tag| entry.tags.contains(&tag))
+            })
+            .take(self.max_items)
+            .collect();
+
+        let feed = if self.format == FeedFormat::Json {
+            self.json_feed(&entries)?
+        } else {
+            // Pre-calculate capacity to minimize allocations
+            // Each item entry is approximately 500-700 bytes
+            let estimated_capacity = 1000 + (entries.len() * 700);
+            let mut xml = String::with_capacity(estimated_capacity);
+
+            // XML declaration
+            writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+                .map_err(|e| Layer5Error::Internal(format!("Failed to write XML header: {e}")))?;
+            if self.format == FeedFormat::Atom {
+                self.write_atom(&mut xml, &entries).map_err(xml_error)?;
+            } else {
+                self.write_rss(&mut xml, &entries)?;
+            }
+            xml
+        };
 
-        // Pre-calculate capacity to minimize allocations
-        // Each item entry is approximately 500-700 bytes
-        let estimated_capacity = 1000 + (reports.len() * 700);
-        let mut xml = String::with_capacity(estimated_capacity);
+        info!(
+            "📡 {:?} feed generated successfully: {} items, {} bytes",
+            self.format,
+            entries.len(),
+            feed.len()
+        );
 
-        // XML declaration
-        writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)
-            .map_err(|e| Layer5Error::Internal(format!("Failed to write XML header: {e}")))?;
+        Ok(feed)
+    }
 
-        // RSS 2.0 opening tag with Atom namespace for self-referencing link
+    /// Write the RSS 2.0 document
+    ///
+    /// `lastBuildDate` is the newest item's date, like the Atom `updated`, so the
+    /// document (and its `ETag`) only changes when a report is published; an empty
+    /// feed leaves it out.
+    fn write_rss(&self, xml: &mut String, entries: &[FeedEntry]) -> Layer5Result<()> {
+        // RSS 2.0 opening tag with Atom namespace for self-referencing link,
+        // Dublin Core for item creators, Content for full report HTML and Feed
+        // History for archive pages
         writeln!(
             xml,
-            r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">"#
+            r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:fh="http://purl.org/syndication/history/1.0">"#
         )
         .map_err(|e| Layer5Error::Internal(format!("Failed to write rss tag: {e}")))?;
 
@@ -84,11 +507,12 @@ impl RssCreator {
             .map_err(|e| Layer5Error::Internal(format!("Failed to write channel: {e}")))?;
 
         // Channel metadata
-        Self::write_channel_metadata(&mut xml, &metadata, &now)?;
+        let last_build = entries.iter().map(|entry| entry.published).max();
+        self.write_channel_metadata(xml, last_build.as_ref())?;
 
         // Write items
-        for report in reports {
-            Self::write_item(&mut xml, report)?;
+        for entry in entries {
+            self.write_item(xml, entry)?;
         }
 
         // Close channel and rss
@@ -97,36 +521,36 @@ impl RssCreator {
         writeln!(xml, "</rss>")
             .map_err(|e| Layer5Error::Internal(format!("Failed to close rss: {e}")))?;
 
-        info!(
-            "📡 RSS feed generated successfully: {} items, {} bytes",
-            reports.len(),
-            xml.len()
-        );
-
-        Ok(xml)
+        Ok(())
     }
 
     /// Write channel metadata section
     fn write_channel_metadata(
+        &self,
         xml: &mut String,
-        metadata: &FeedMetadata,
-        now: &DateTime<Utc>,
+        last_build: Option<&DateTime<Utc>>,
     ) -> Layer5Result<()> {
+        let metadata = &self.metadata;
+
         // Required elements
         writeln!(
             xml,
             "    <title>{}</title>",
-            Self::escape_xml(metadata.title)
+            RssCreator::escape_xml(&metadata.title)
         )
         .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
-        writeln!(xml, "    <link>{}</link>", metadata.link)
-            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        writeln!(
+            xml,
+            "    <link>{}</link>",
+            RssCreator::escape_xml(&metadata.link)
+        )
+        .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
         writeln!(
             xml,
             "    <description>{}</description>",
-            Self::escape_xml(metadata.description)
+            RssCreator::escape_xml(&metadata.description)
         )
         .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
@@ -134,20 +558,49 @@ impl RssCreator {
         writeln!(xml, "    <language>{}</language>", metadata.language)
             .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
-        writeln!(
-            xml,
-            "    <lastBuildDate>{}</lastBuildDate>",
-            Self::format_rfc822_date(now)
-        )
-        .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        if let Some(last_build) = last_build {
+            writeln!(
+                xml,
+                "    <lastBuildDate>{}</lastBuildDate>",
+                RssCreator::format_rfc822_date(last_build)
+            )
+            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
 
         // Atom self-referencing link (recommended for feed readers)
         writeln!(
             xml,
-            r#"    <atom:link href="{BASE_URL}/rss.xml" rel="self" type="application/rss+xml"/>"#
+            r#"    <atom:link href="{}" rel="self" type="{}"/>"#,
+            RssCreator::escape_xml(&self.document_link()),
+            self.format.mime_type()
         )
         .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
+        // Feed archive (RFC 5005)
+        for (rel, href) in self.archive_links() {
+            writeln!(
+                xml,
+                r#"    <atom:link href="{}" rel="{rel}" type="{}"/>"#,
+                RssCreator::escape_xml(&href),
+                self.format.mime_type()
+            )
+            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+        if self.is_archive_page() {
+            writeln!(xml, "    <fh:archive/>")
+                .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+
+        // WebSub hub notified when a report is published
+        if let Some(hub) = &self.hub {
+            writeln!(
+                xml,
+                r#"    <atom:link href="{}" rel="hub"/>"#,
+                RssCreator::escape_xml(hub)
+            )
+            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+
         // Generator
         writeln!(
             xml,
@@ -163,67 +616,411 @@ impl RssCreator {
     }
 
     /// Write a single item entry
-    fn write_item(xml: &mut String, report: &ReportRssData) -> Layer5Result<()> {
+    fn write_item(&self, xml: &mut String, entry: &FeedEntry) -> Layer5Result<()> {
         writeln!(xml, "    <item>")
             .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
-        // Title with date in Vietnamese timezone (UTC+7)
-        // Safe: 7 * 3600 = 25200 seconds is well within the valid range (±86400 seconds)
-        // Double fallback: first to UTC (offset 0), then to a compile-time verified UTC offset
-        let vn_offset = FixedOffset::east_opt(7 * 3600)
-            .or_else(|| FixedOffset::east_opt(0))
-            .unwrap_or_else(|| Utc.fix());
-        let vn_time = report.created_at.with_timezone(&vn_offset);
-        let date_str = vn_time.format("%d/%m/%Y").to_string();
-        let title = format!("Báo cáo Thị trường Crypto #{} - {}", report.id, date_str);
-
-        writeln!(xml, "      <title>{}</title>", Self::escape_xml(&title))
-            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        writeln!(
+            xml,
+            "      <title>{}</title>",
+            RssCreator::escape_xml(&entry.title)
+        )
+        .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
         // Link
-        let link = format!("{}/crypto_report/{}", BASE_URL, report.id);
-        writeln!(xml, "      <link>{link}</link>")
+        writeln!(xml, "      <link>{}</link>", entry.link)
             .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
-        // GUID (globally unique identifier) - same as link, marked as permalink
-        writeln!(xml, r#"      <guid isPermaLink="true">{link}</guid>"#)
+        // GUID (globally unique identifier)
+        writeln!(xml, r#"      <guid isPermaLink="true">{}</guid>"#, entry.id)
             .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
         // Publication date in RFC 822 format
         writeln!(
             xml,
             "      <pubDate>{}</pubDate>",
-            Self::format_rfc822_date(&report.created_at)
+            RssCreator::format_rfc822_date(&entry.published)
         )
         .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
-        // Description - extract from HTML content
-        let description = Self::extract_description(&report.html_content, MAX_DESCRIPTION_LENGTH);
+        // Description - extracted from HTML content
         writeln!(
             xml,
             "      <description>{}</description>",
-            Self::escape_xml(&description)
+            RssCreator::escape_xml(&entry.summary)
         )
         .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
+        for tag in &entry.tags {
+            writeln!(xml, "      <category>{tag}</category>")
+                .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+
+        // Full report HTML; a "]]>" in it is split across two CDATA sections
+        if self.content.full_content {
+            writeln!(
+                xml,
+                "      <content:encoded><![CDATA[{}]]></content:encoded>",
+                sanitize_report_html(entry.content).replace("]]>", "]]]]><![CDATA[>")
+            )
+            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+
+        if let Some(enclosure) = &self.content.enclosure {
+            let (url, length, mime_type) = match enclosure {
+                FeedEnclosure::Image { length } => (
+                    format!("{}{ENCLOSURE_IMAGE_PATH}", public_origin()),
+                    *length,
+                    "image/jpeg",
+                ),
+                FeedEnclosure::Pdf { url_template } => {
+                    let url = url_template.replace(PDF_URL_ID, &entry.report_id.to_string());
+                    let url = if url.starts_with('/') {
+                        format!("{}{url}", public_origin())
+                    } else {
+                        url
+                    };
+                    // Size of an externally generated PDF is unknown
+                    (url, 0, "application/pdf")
+                }
+            };
+            writeln!(
+                xml,
+                r#"      <enclosure url="{}" length="{length}" type="{mime_type}"/>"#,
+                RssCreator::escape_xml(&url)
+            )
+            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+
+        if let Some(creator) = self.metadata.creator.as_deref() {
+            writeln!(
+                xml,
+                "      <dc:creator>{}</dc:creator>",
+                RssCreator::escape_xml(creator)
+            )
+            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+
         writeln!(xml, "    </item>")
             .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
 
         Ok(())
     }
 
+    /// Write the Atom 1.0 document
+    ///
+    /// The feed `updated` is the newest entry's date (build time for an empty
+    /// feed), so it only changes when a report is published. Reports are not
+    /// edited in place, so an entry's `updated` is its publication date.
+    fn write_atom(&self, xml: &mut String, entries: &[FeedEntry]) -> std::fmt::Result {
+        let metadata = &self.metadata;
+        let escape = RssCreator::escape_xml;
+        let updated = entries
+            .iter()
+            .map(|entry| entry.published)
+            .max()
+            .unwrap_or_else(Utc::now);
+
+        writeln!(
+            xml,
+            r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:fh="http://purl.org/syndication/history/1.0" xml:lang="{}">"#,
+            metadata.language
+        )?;
+        // Feed id: the feed URL on the default origin, stable across hosts
+        writeln!(
+            xml,
+            "  <id>{}</id>",
+            escape(&format!("{BASE_URL}{}", metadata.feed_file(self.format)))
+        )?;
+        writeln!(xml, "  <title>{}</title>", escape(&metadata.title))?;
+        writeln!(
+            xml,
+            "  <subtitle>{}</subtitle>",
+            escape(&metadata.description)
+        )?;
+        writeln!(
+            xml,
+            r#"  <link rel="alternate" type="text/html" href="{}"/>"#,
+            escape(&metadata.link)
+        )?;
+        writeln!(
+            xml,
+            r#"  <link rel="self" type="{}" href="{}"/>"#,
+            self.format.mime_type(),
+            escape(&self.document_link())
+        )?;
+        for (rel, href) in self.archive_links() {
+            writeln!(
+                xml,
+                r#"  <link rel="{rel}" type="{}" href="{}"/>"#,
+                self.format.mime_type(),
+                escape(&href)
+            )?;
+        }
+        if self.is_archive_page() {
+            writeln!(xml, "  <fh:archive/>")?;
+        }
+        if let Some(hub) = &self.hub {
+            writeln!(xml, r#"  <link rel="hub" href="{}"/>"#, escape(hub))?;
+        }
+        writeln!(
+            xml,
+            "  <updated>{}</updated>",
+            format_rfc3339_date(&updated)
+        )?;
+        writeln!(
+            xml,
+            "  <author>\n    <name>{}</name>\n    <uri>{}</uri>\n  </author>",
+            escape(metadata.creator.as_deref().unwrap_or("CryptoDashboard")),
+            escape(&metadata.link)
+        )?;
+        writeln!(
+            xml,
+            "  <generator>CryptoDashboard Rust Web Server</generator>"
+        )?;
+
+        for entry in entries {
+            let published = format_rfc3339_date(&entry.published);
+            writeln!(xml, "  <entry>")?;
+            writeln!(xml, "    <id>{}</id>", entry.id)?;
+            writeln!(xml, "    <title>{}</title>", escape(&entry.title))?;
+            writeln!(
+                xml,
+                r#"    <link rel="alternate" type="text/html" href="{}"/>"#,
+                escape(&entry.link)
+            )?;
+            writeln!(xml, "    <published>{published}</published>")?;
+            writeln!(xml, "    <updated>{published}</updated>")?;
+            writeln!(xml, "    <summary>{}</summary>", escape(&entry.summary))?;
+            for tag in &entry.tags {
+                writeln!(xml, r#"    <category term="{tag}"/>"#)?;
+            }
+            writeln!(xml, "  </entry>")?;
+        }
+
+        writeln!(xml, "</feed>")
+    }
+
+    /// Serialize the JSON Feed 1.1 document
+    ///
+    /// Items carry the sanitized report HTML as `content_html` and the topic
+    /// tags of `report_tags`.
+    fn json_feed(&self, entries: &[FeedEntry]) -> Layer5Result<String> {
+        let metadata = &self.metadata;
+        let authors = vec![JsonFeedAuthor {
+            name: metadata.creator.as_deref().unwrap_or("CryptoDashboard"),
+            url: &metadata.link,
+        }];
+        let feed = JsonFeed {
+            version: "https://jsonfeed.org/version/1.1",
+            title: &metadata.title,
+            home_page_url: &metadata.link,
+            feed_url: metadata.self_link(self.format),
+            description: &metadata.description,
+            language: metadata.language,
+            authors: authors.clone(),
+            hubs: self
+                .hub
+                .iter()
+                .map(|hub| JsonFeedHub {
+                    hub_type: "WebSub",
+                    url: hub,
+                })
+                .collect(),
+            items: entries
+                .iter()
+                .map(|entry| JsonFeedItem {
+                    id: &entry.id,
+                    url: &entry.link,
+                    title: &entry.title,
+                    content_html: sanitize_report_html(entry.content),
+                    summary: &entry.summary,
+                    date_published: format_rfc3339_date(&entry.published),
+                    date_modified: format_rfc3339_date(&entry.published),
+                    tags: entry.tags.clone(),
+                    authors: authors.clone(),
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&feed)
+            .map_err(|e| Layer5Error::Internal(format!("JSON Feed serialization error: {e}")))
+    }
+}
+
+/// JSON Feed 1.1 document
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: String,
+    description: &'a str,
+    language: &'static str,
+    authors: Vec<JsonFeedAuthor<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hubs: Vec<JsonFeedHub<'a>>,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+/// JSON Feed hub object (real-time notifications)
+#[derive(Serialize)]
+struct JsonFeedHub<'a> {
+    #[serde(rename = "type")]
+    hub_type: &'static str,
+    url: &'a str,
+}
+
+/// JSON Feed author object
+#[derive(Serialize, Clone)]
+struct JsonFeedAuthor<'a> {
+    name: &'a str,
+    url: &'a str,
+}
+
+/// JSON Feed item
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    content_html: String,
+    summary: &'a str,
+    date_published: String,
+    date_modified: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<&'static str>,
+    authors: Vec<JsonFeedAuthor<'a>>,
+}
+
+/// Title of a report in `language` (`en`, anything else for Vietnamese), with
+/// its date in Vietnamese timezone (UTC+7)
+#[must_use]
+pub fn report_title(report_id: i32, created_at: DateTime<Utc>, language: &str) -> String {
+    let vn_time = created_at.with_timezone(&vn_offset());
+    if language == "en" {
+        format!(
+            "Crypto Market Report #{report_id} - {}",
+            vn_time.format("%Y-%m-%d")
+        )
+    } else {
+        format!(
+            "Báo cáo Thị trường Crypto #{report_id} - {}",
+            vn_time.format("%d/%m/%Y")
+        )
+    }
+}
+
+/// The known topic tag matching `tag` (case-insensitive)
+#[must_use]
+pub fn report_tag(tag: &str) -> Option<&'static str> {
+    REPORT_TAGS
+        .iter()
+        .map(|&(known, _)| known)
+        .find(|known| known.eq_ignore_ascii_case(tag.trim()))
+}
+
+/// Query string selecting a site or author feed variant (`?tag=btc&lang=en`);
+/// Vietnamese, the default language, is left implicit
+#[must_use]
+pub fn feed_query(language: &str, tag: Option<&str>) -> String {
+    let params: Vec<String> = tag
+        .map(|tag| format!("tag={tag}"))
+        .into_iter()
+        .chain((language == "en").then(|| "lang=en".to_string()))
+        .collect();
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    }
+}
+
+/// Topic tags (`btc`, `eth`, `defi`, ...) of a report, from the words of its text
+#[must_use]
+pub fn report_tags(html: &str) -> Vec<&'static str> {
+    // Text outside tags, lowercased, with tag boundaries as word breaks
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.extend(c.to_lowercase()),
+            _ => {}
+        }
+    }
+    let words: HashSet<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    REPORT_TAGS
+        .iter()
+        .filter(|(_, keywords)| keywords.iter().any(|keyword| words.contains(keyword)))
+        .map(|&(tag, _)| tag)
+        .collect()
+}
+
+/// RSS Feed XML generator
+pub struct RssCreator;
+
+impl RssCreator {
+    /// Generate complete RSS 2.0 XML feed from report data
+    ///
+    /// # Arguments
+    /// * `reports` - Vector of `ReportRssData` from database
+    /// * `language` - `en` for the English feed, anything else for Vietnamese
+    ///
+    /// # Returns
+    /// Complete RSS 2.0 XML string
+    ///
+    /// # Errors
+    ///
+    /// Returns error if XML writing fails
+    pub fn generate_rss_xml(reports: &[ReportRssData], language: &str) -> Layer5Result<String> {
+        FeedBuilder::site(language, FeedFormat::Rss).build(reports)
+    }
+
+    /// Generate the site-wide Atom 1.0 feed (`/atom.xml`)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if XML writing fails
+    pub fn generate_atom_xml(reports: &[ReportRssData], language: &str) -> Layer5Result<String> {
+        FeedBuilder::site(language, FeedFormat::Atom).build(reports)
+    }
+
+    /// Generate the site-wide JSON Feed 1.1 (`/feed.json`)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if JSON serialization fails
+    pub fn generate_json_feed(reports: &[ReportRssData], language: &str) -> Layer5Result<String> {
+        FeedBuilder::site(language, FeedFormat::Json).build(reports)
+    }
+
+    /// Generate an RSS 2.0 feed of one author's reports (`/author/{slug}/rss.xml`)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if XML writing fails
+    pub fn generate_author_rss_xml(
+        author: &AuthorData,
+        reports: &[ReportRssData],
+        language: &str,
+    ) -> Layer5Result<String> {
+        FeedBuilder::author(author, language, FeedFormat::Rss).build(reports)
+    }
+
     /// Format `DateTime` to RFC 822 standard for RSS pubDate
     ///
     /// Format: "Sun, 23 Nov 2025 14:00:00 +0700"
     /// RSS 2.0 requires dates in RFC 822 format
     fn format_rfc822_date(dt: &DateTime<Utc>) -> String {
         // Convert to Vietnam timezone (UTC+7) for display
-        let vn_offset = FixedOffset::east_opt(7 * 3600).unwrap_or_else(|| {
-            // Fallback to UTC if VN offset fails (should not happen with constant)
-            // Fallback to UTC if VN offset fails (should not happen with constant)
-            FixedOffset::east_opt(0).unwrap_or(Utc.fix())
-        });
-        let vn_time = dt.with_timezone(&vn_offset);
+        let vn_time = dt.with_timezone(&vn_offset());
 
         // RFC 822 format: "Sun, 23 Nov 2025 14:00:00 +0700"
         vn_time.format("%a, %d %b %Y %H:%M:%S %z").to_string()
@@ -233,7 +1030,7 @@ impl RssCreator {
     ///
     /// Removes HTML tags and extracts first N characters for RSS description.
     /// Adds ellipsis if content is truncated.
-    fn extract_description(html: &str, max_len: usize) -> String {
+    pub(crate) fn extract_description(html: &str, max_len: usize) -> String {
         // Simple HTML tag removal - strip all tags
         let mut result = String::with_capacity(max_len + 10);
         let mut in_tag = false;
@@ -296,6 +1093,28 @@ impl RssCreator {
     }
 }
 
+/// Vietnamese timezone (UTC+7)
+///
+/// Safe: 7 * 3600 = 25200 seconds is well within the valid range (±86400 seconds)
+/// Double fallback: first to UTC (offset 0), then to a compile-time verified UTC offset
+fn vn_offset() -> FixedOffset {
+    FixedOffset::east_opt(7 * 3600)
+        .or_else(|| FixedOffset::east_opt(0))
+        .unwrap_or_else(|| Utc.fix())
+}
+
+/// Format `DateTime` to RFC 3339 for Atom timestamps, in Vietnamese timezone
+///
+/// Format: "2025-11-23T14:00:00+07:00"
+fn format_rfc3339_date(dt: &DateTime<Utc>) -> String {
+    dt.with_timezone(&vn_offset())
+        .to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+fn xml_error(e: std::fmt::Error) -> Layer5Error {
+    Layer5Error::Internal(format!("XML write error: {e}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,6 +1126,7 @@ mod tests {
             ReportRssData {
                 id: 1,
                 html_content: "<div>Báo cáo thị trường crypto ngày hôm nay</div>".to_string(),
+                html_content_en: None,
                 created_at: Utc
                     .with_ymd_and_hms(2025, 11, 23, 7, 0, 0)
                     .single()
@@ -315,6 +1135,7 @@ mod tests {
             ReportRssData {
                 id: 2,
                 html_content: "<p>Bitcoin tăng mạnh</p>".to_string(),
+                html_content_en: Some("<p>Bitcoin rallies</p>".to_string()),
                 created_at: Utc
                     .with_ymd_and_hms(2025, 11, 22, 7, 0, 0)
                     .single()
@@ -322,7 +1143,7 @@ mod tests {
             },
         ];
 
-        let xml = RssCreator::generate_rss_xml(&reports)?;
+        let xml = RssCreator::generate_rss_xml(&reports, "vi")?;
 
         // Verify XML structure
         assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
@@ -335,25 +1156,343 @@ mod tests {
         assert!(xml.contains("<title>CryptoDashboard"));
         assert!(xml.contains("<language>vi-VN</language>"));
         assert!(xml.contains("<ttl>60</ttl>"));
+        // Last build is the newest report, so rebuilding yields the same document
+        assert!(xml.contains("<lastBuildDate>Sun, 23 Nov 2025 14:00:00 +0700</lastBuildDate>"));
+        assert_eq!(xml, RssCreator::generate_rss_xml(&reports, "vi")?);
 
         // Verify items
         assert!(xml.contains("<item>"));
         assert!(xml.contains("Báo cáo Thị trường Crypto #1"));
         assert!(xml.contains("Báo cáo Thị trường Crypto #2"));
-        assert!(xml.contains("<link>https://cryptodashboard.me/crypto_report/1</link>"));
-        assert!(xml.contains("<guid isPermaLink=\"true\">"));
+        assert!(xml.contains(
+            "<link>https://cryptodashboard.me/crypto_report/1-phan-tich-thi-truong-crypto-"
+        ));
+        assert!(xml.contains(
+            "<guid isPermaLink=\"true\">https://cryptodashboard.me/crypto_report/1</guid>"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_english_rss() -> Layer5Result<()> {
+        let reports = vec![ReportRssData {
+            id: 2,
+            html_content: "<p>Bitcoin tăng mạnh</p>".to_string(),
+            html_content_en: Some("<p>Bitcoin rallies</p>".to_string()),
+            created_at: Utc
+                .with_ymd_and_hms(2025, 11, 22, 7, 0, 0)
+                .single()
+                .ok_or(Layer5Error::Internal("Invalid date".into()))?,
+        }];
 
+        let xml = RssCreator::generate_rss_xml(&reports, "en")?;
+
+        assert!(xml.contains("<language>en-US</language>"));
+        assert!(xml.contains("Crypto Market Report #2 - 2025-11-22"));
+        assert!(xml.contains("<description>Bitcoin rallies</description>"));
+        assert!(xml.contains("/rss.xml?lang=en"));
         Ok(())
     }
 
+    #[test]
+    fn test_generate_atom_feed() -> Layer5Result<()> {
+        let reports = vec![
+            ReportRssData {
+                id: 2,
+                html_content: "<p>Bitcoin tăng mạnh</p>".to_string(),
+                html_content_en: Some("<p>Bitcoin rallies & holds</p>".to_string()),
+                created_at: Utc
+                    .with_ymd_and_hms(2025, 11, 23, 7, 0, 0)
+                    .single()
+                    .ok_or(Layer5Error::Internal("Invalid date".into()))?,
+            },
+            ReportRssData {
+                id: 1,
+                html_content: "<p>ETH phục hồi</p>".to_string(),
+                html_content_en: None,
+                created_at: Utc
+                    .with_ymd_and_hms(2025, 11, 22, 7, 0, 0)
+                    .single()
+                    .ok_or(Layer5Error::Internal("Invalid date".into()))?,
+            },
+        ];
+
+        let xml = FeedBuilder::site("en", FeedFormat::Atom)
+            .with_hub(Some("https://hub.example.org/".to_string()))
+            .build(&reports)?;
+
+        assert!(
+            xml.contains(r#"xmlns:fh="http://purl.org/syndication/history/1.0" xml:lang="en-US">"#)
+        );
+        assert!(xml.contains("<id>https://cryptodashboard.me/atom.xml?lang=en</id>"));
+        assert!(xml.contains(r#"<link rel="hub" href="https://hub.example.org/"/>"#));
+        assert!(xml.contains(
+            r#"<link rel="self" type="application/atom+xml" href="https://cryptodashboard.me/atom.xml?lang=en"/>"#
+        ));
+        // Feed updated is the newest entry, not the build time
+        assert!(xml.contains("  <updated>2025-11-23T14:00:00+07:00</updated>"));
+        assert!(xml.contains("<id>https://cryptodashboard.me/crypto_report/2</id>"));
+        assert!(xml.contains("<published>2025-11-22T14:00:00+07:00</published>"));
+        assert!(xml.contains("<summary>Bitcoin rallies &amp; holds</summary>"));
+        assert!(xml.contains("<summary>ETH phục hồi</summary>"));
+        assert!(xml.contains(
+            r#"href="https://cryptodashboard.me/crypto_report/1-phan-tich-thi-truong-crypto-"#
+        ));
+        assert_eq!(xml.matches("<entry>").count(), 2);
+        assert!(xml.trim_end().ends_with("</feed>"));
+        assert!(!xml.contains("<rss"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_json_feed() -> Result<(), Box<dyn std::error::Error>> {
+        let reports = vec![ReportRssData {
+            id: 3,
+            html_content: "<h2>Bitcoin</h2><p>ETH và DeFi phục hồi</p>".to_string(),
+            html_content_en: Some(
+                "<h2>Bitcoin</h2><p onclick=\"x()\">ETF inflows</p><script>x()</script>"
+                    .to_string(),
+            ),
+            created_at: Utc
+                .with_ymd_and_hms(2025, 11, 23, 7, 0, 0)
+                .single()
+                .ok_or("Invalid date")?,
+        }];
+
+        let json = RssCreator::generate_json_feed(&reports, "en")?;
+        let feed: serde_json::Value = serde_json::from_str(&json)?;
+        let field = |pointer: &str| feed.pointer(pointer).cloned().unwrap_or_default();
+
+        assert_eq!(field("/version"), "https://jsonfeed.org/version/1.1");
+        assert_eq!(
+            field("/feed_url"),
+            "https://cryptodashboard.me/feed.json?lang=en"
+        );
+        assert_eq!(field("/language"), "en-US");
+        assert_eq!(field("/authors/0/name"), "CryptoDashboard");
+
+        assert_eq!(
+            field("/items/0/id"),
+            "https://cryptodashboard.me/crypto_report/3"
+        );
+        assert_eq!(
+            field("/items/0/title"),
+            "Crypto Market Report #3 - 2025-11-23"
+        );
+        assert_eq!(
+            field("/items/0/date_published"),
+            "2025-11-23T14:00:00+07:00"
+        );
+        assert_eq!(field("/items/0/tags"), serde_json::json!(["btc", "etf"]));
+        let content = field("/items/0/content_html");
+        let content = content.as_str().unwrap_or_default();
+        assert!(content.contains("ETF inflows"));
+        assert!(!content.contains("onclick") && !content.contains("<script"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_tags() {
+        assert_eq!(
+            report_tags("<p>Bitcoin (BTC) và Solana</p><p>USDT</p>"),
+            vec!["btc", "sol", "stablecoin"]
+        );
+        // Words only: "ethereal" and class names are not tags
+        assert_eq!(
+            report_tags(r#"<div class="btc">ethereal</div>"#),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn test_generate_tag_feed() -> Layer5Result<()> {
+        let report = |id, html: &str| -> Layer5Result<ReportRssData> {
+            Ok(ReportRssData {
+                id,
+                html_content: html.to_string(),
+                html_content_en: None,
+                created_at: Utc
+                    .with_ymd_and_hms(2025, 11, 23, 7, 0, 0)
+                    .single()
+                    .ok_or(Layer5Error::Internal("Invalid date".into()))?,
+            })
+        };
+        let reports = vec![
+            report(3, "<p>Bitcoin và Ethereum</p>")?,
+            report(2, "<p>Solana</p>")?,
+            report(1, "<p>BTC</p>")?,
+        ];
+
+        let xml = FeedBuilder::site("en", FeedFormat::Rss)
+            .with_tag("btc", 1)
+            .build(&reports)?;
+
+        assert!(xml.contains("<title>CryptoDashboard - Crypto Market Reports - BTC</title>"));
+        assert!(xml.contains(r#"href="https://cryptodashboard.me/rss.xml?tag=btc&amp;lang=en""#));
+        assert!(xml.contains("Crypto Market Report #3"));
+        assert!(xml.contains("<category>btc</category>\n      <category>eth</category>"));
+        // Untagged report left out, tagged one past the limit too
+        assert!(!xml.contains("Crypto Market Report #2"));
+        assert!(!xml.contains("Crypto Market Report #1"));
+
+        assert_eq!(report_tag(" BTC "), Some("btc"));
+        assert_eq!(report_tag("doge"), None);
+        assert_eq!(feed_query("vi", None), "");
+        assert_eq!(feed_query("vi", Some("eth")), "?tag=eth");
+        Ok(())
+    }
+
+    #[test]
+    fn test_feed_archive_links() -> Layer5Result<()> {
+        let reports = vec![ReportRssData {
+            id: 21,
+            html_content: "<p>Bitcoin</p>".to_string(),
+            html_content_en: None,
+            created_at: Utc
+                .with_ymd_and_hms(2025, 11, 23, 7, 0, 0)
+                .single()
+                .ok_or(Layer5Error::Internal("Invalid date".into()))?,
+        }];
+
+        // Subscription feed links the newest full archive page
+        let xml = FeedBuilder::site("vi", FeedFormat::Rss)
+            .with_archive(None, 3)
+            .build(&reports)?;
+        assert!(xml.contains(
+            r#"<atom:link href="https://cryptodashboard.me/rss.xml?archive=3" rel="prev-archive""#
+        ));
+        assert!(!xml.contains("<fh:archive/>"));
+
+        // Middle archive page of the English Atom feed
+        let xml = FeedBuilder::site("en", FeedFormat::Atom)
+            .with_archive(Some(2), 3)
+            .build(&reports)?;
+        assert!(xml.contains("<id>https://cryptodashboard.me/atom.xml?lang=en</id>"));
+        assert!(xml.contains(
+            r#"<link rel="self" type="application/atom+xml" href="https://cryptodashboard.me/atom.xml?lang=en&amp;archive=2"/>"#
+        ));
+        assert!(xml.contains(
+            r#"<link rel="current" type="application/atom+xml" href="https://cryptodashboard.me/atom.xml?lang=en"/>"#
+        ));
+        assert!(
+            xml.contains(r#"href="https://cryptodashboard.me/atom.xml?lang=en&amp;archive=1""#)
+        );
+        assert!(
+            xml.contains(r#"href="https://cryptodashboard.me/atom.xml?lang=en&amp;archive=3""#)
+        );
+        assert!(xml.contains("  <fh:archive/>"));
+
+        // The oldest page has no previous page, the newest no next one
+        let xml = FeedBuilder::site("vi", FeedFormat::Rss)
+            .with_archive(Some(1), 1)
+            .build(&reports)?;
+        assert!(xml.contains(r#"rel="current""#));
+        assert!(!xml.contains("prev-archive") && !xml.contains("next-archive"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_full_content_rss() -> Layer5Result<()> {
+        let reports = vec![ReportRssData {
+            id: 9,
+            html_content: "<p onclick=\"x()\">Bitcoin ]]> ETH</p><script>x()</script>".to_string(),
+            html_content_en: None,
+            created_at: Utc
+                .with_ymd_and_hms(2025, 11, 23, 7, 0, 0)
+                .single()
+                .ok_or(Layer5Error::Internal("Invalid date".into()))?,
+        }];
+
+        let xml = FeedBuilder::site("vi", FeedFormat::Rss)
+            .with_content(FeedContentConfig::parse(
+                Some("true"),
+                Some("pdf"),
+                Some("/files/{id}.pdf"),
+                0,
+            ))
+            .build(&reports)?;
+
+        assert!(xml.contains(
+            "<content:encoded><![CDATA[<p>Bitcoin ]]]]><![CDATA[> ETH</p>]]></content:encoded>"
+        ));
+        assert!(xml.contains(
+            r#"<enclosure url="https://cryptodashboard.me/files/9.pdf" length="0" type="application/pdf"/>"#
+        ));
+
+        let xml = FeedBuilder::site("vi", FeedFormat::Rss)
+            .with_content(FeedContentConfig::parse(None, Some("image"), None, 2048))
+            .build(&reports)?;
+        assert!(!xml.contains("<content:encoded>"));
+        assert!(xml.contains(
+            r#"<enclosure url="https://cryptodashboard.me/shared_assets/images/image.jpg" length="2048" type="image/jpeg"/>"#
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_feed_content_config() {
+        assert_eq!(
+            FeedContentConfig::parse(None, None, None, 0),
+            FeedContentConfig::default()
+        );
+        // A PDF enclosure needs a URL with the id placeholder
+        assert_eq!(
+            FeedContentConfig::parse(Some("on"), Some("pdf"), Some("/report.pdf"), 0),
+            FeedContentConfig {
+                full_content: true,
+                enclosure: None,
+            }
+        );
+        assert_eq!(
+            FeedContentConfig::parse(Some("no"), Some("video"), None, 0),
+            FeedContentConfig::default()
+        );
+    }
+
     #[test]
     fn test_generate_rss_empty_reports() -> Layer5Result<()> {
-        let xml = RssCreator::generate_rss_xml(&[])?;
+        let xml = RssCreator::generate_rss_xml(&[], "vi")?;
 
         // Should still have valid channel
         assert!(xml.contains("<channel>"));
         assert!(xml.contains("<title>CryptoDashboard"));
         assert!(!xml.contains("<item>"));
+        assert!(!xml.contains("<lastBuildDate>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_generate_author_rss() -> Layer5Result<()> {
+        let author = AuthorData {
+            id: 7,
+            slug: "tran-minh".to_string(),
+            name: "Trần Minh & Co".to_string(),
+            bio: None,
+            avatar_url: None,
+            profile_url: None,
+        };
+        let reports = vec![ReportRssData {
+            id: 42,
+            html_content: "<p>ETH phục hồi</p>".to_string(),
+            html_content_en: None,
+            created_at: Utc
+                .with_ymd_and_hms(2025, 11, 23, 7, 0, 0)
+                .single()
+                .ok_or(Layer5Error::Internal("Invalid date".into()))?,
+        }];
+
+        let xml = RssCreator::generate_author_rss_xml(&author, &reports, "vi")?;
+
+        assert!(xml.contains("<title>Trần Minh &amp; Co - CryptoDashboard</title>"));
+        assert!(xml.contains("<link>https://cryptodashboard.me/author/tran-minh</link>"));
+        assert!(xml.contains(
+            r#"<atom:link href="https://cryptodashboard.me/author/tran-minh/rss.xml" rel="self""#
+        ));
+        assert!(xml.contains("<dc:creator>Trần Minh &amp; Co</dc:creator>"));
+        assert!(xml.contains("/crypto_report/42-phan-tich-thi-truong-crypto-"));
 
         Ok(())
     }
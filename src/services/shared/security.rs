@@ -1,68 +1,330 @@
 //! Security Token Generation Utilities
 //!
-//! Provides cryptographically secure token generation for sandbox/Shadow DOM tokens.
-//! Replaces the insecure DefaultHasher-based implementation.
+//! Provides signed, expiring tokens for sandbox/Shadow DOM content requests, and
+//! signed, expiring URLs for the sandboxed iframe endpoint so embeds cannot be
+//! hotlinked indefinitely.
+//! Also guards administrative endpoints (and the `X-Cache-Bypass` debug header) with
+//! the `ADMIN_TOKEN` shared secret or `admin`-scoped caller credentials.
 
-/// Generate a cryptographically secure sandbox token
+use axum::http::{HeaderMap, header};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use super::error::{Layer5Error, Layer5Result};
+use crate::services::data_communication::{ApiScope, current_caller};
+
+/// Header carrying the admin token (alternative to `Authorization: Bearer`)
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// Header naming the operator behind an admin request, recorded in the audit log
+pub const ADMIN_ACTOR_HEADER: &str = "x-admin-actor";
+
+/// Max characters kept of the `X-Admin-Actor` header
+const MAX_ACTOR_LENGTH: usize = 64;
+
+/// Header asking page handlers to skip cache lookups and render fresh (admin only)
+pub const CACHE_BYPASS_HEADER: &str = "x-cache-bypass";
+
+/// Default lifetime of a sandbox token window
+const DEFAULT_SANDBOX_TOKEN_TTL: Duration = Duration::from_hours(24);
+
+/// Key context of sandbox token signatures (`blake3::derive_key`)
+const SANDBOX_TOKEN_CONTEXT: &str = "web-server-report 2026-10 sandbox token";
+
+/// Signing key of sandbox tokens
 ///
-/// Uses HMAC-like construction with blake3 for fast, secure token generation.
-/// The token is derived from `report_id` and `created_at` timestamp.
+/// Derived from `SANDBOX_TOKEN_SECRET`; without it a random per-process key is used,
+/// so tokens stop verifying after a restart and on other replicas. Startup
+/// (`SecretsAudit`) only allows that for a single process without Redis or disk cache.
+fn sandbox_token_key() -> &'static [u8; 32] {
+    static KEY: OnceLock<[u8; 32]> = OnceLock::new();
+    KEY.get_or_init(|| {
+        std::env::var("SANDBOX_TOKEN_SECRET")
+            .ok()
+            .filter(|secret| !secret.trim().is_empty())
+            .map_or_else(
+                || {
+                    warn!("⚠️ SANDBOX_TOKEN_SECRET not set, sandbox tokens use a per-process key");
+                    rand::random()
+                },
+                |secret| blake3::derive_key(SANDBOX_TOKEN_CONTEXT, secret.trim().as_bytes()),
+            )
+    })
+}
+
+/// Token window length (`SANDBOX_TOKEN_TTL_SECS`)
+fn sandbox_token_ttl() -> Duration {
+    static TTL: OnceLock<Duration> = OnceLock::new();
+    *TTL.get_or_init(|| {
+        std::env::var("SANDBOX_TOKEN_TTL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|&secs: &u64| secs > 0)
+            .map_or(DEFAULT_SANDBOX_TOKEN_TTL, Duration::from_secs)
+    })
+}
+
+/// Keyed blake3 signature (128 bits, hex) of a report's token expiring at `expires`
+fn sandbox_signature(
+    key: &[u8; 32],
+    report_id: i32,
+    created_at: &chrono::DateTime<chrono::Utc>,
+    expires: i64,
+) -> String {
+    let timestamp_nanos = created_at.timestamp_nanos_opt().unwrap_or(0);
+    let input = format!("{report_id}:{timestamp_nanos}:{expires}");
+    let hash = blake3::keyed_hash(key, input.as_bytes());
+    hash.to_hex().chars().take(32).collect()
+}
+
+/// Expiry of a signature issued at `now`: the end of the window after the current one
+fn window_expiry(now: i64, ttl_secs: i64) -> i64 {
+    (now.div_euclid(ttl_secs) + 2) * ttl_secs
+}
+
+/// Sandbox token issued at `now` (unix seconds) for windows of `ttl_secs`
 ///
-/// # Security
-/// - Uses blake3 which is cryptographically secure
-/// - Tokens are unpredictable and cannot be forged
-/// - Different from `DefaultHasher` which is NOT cryptographically secure
+/// Expiry is rounded to the end of the next window, so every render within one
+/// window embeds the same token (cached pages stay identical) and a token lives
+/// between one and two windows.
+fn issue_sandbox_token(
+    key: &[u8; 32],
+    report_id: i32,
+    created_at: &chrono::DateTime<chrono::Utc>,
+    now: i64,
+    ttl_secs: i64,
+) -> String {
+    let expires = window_expiry(now, ttl_secs);
+    format!(
+        "sb_{expires}_{}",
+        sandbox_signature(key, report_id, created_at, expires)
+    )
+}
+
+fn check_sandbox_token(
+    key: &[u8; 32],
+    token: &str,
+    report_id: i32,
+    created_at: &chrono::DateTime<chrono::Utc>,
+    now: i64,
+) -> bool {
+    let Some((expires, signature)) = token
+        .strip_prefix("sb_")
+        .and_then(|rest| rest.split_once('_'))
+    else {
+        return false;
+    };
+    let Ok(expires) = expires.parse::<i64>() else {
+        return false;
+    };
+    let expected = sandbox_signature(key, report_id, created_at, expires);
+    // Signature first, so a forged token costs the same whether or not it expired
+    constant_time_compare(signature.as_bytes(), expected.as_bytes()) && now <= expires
+}
+
+/// Generate a signed, expiring sandbox / Shadow DOM token for a report
 ///
-/// # Performance
-/// - blake3 is optimized for speed (SIMD, parallel)
-/// - ~1GB/s on modern CPUs
-/// - Minimal overhead compared to `DefaultHasher`
-#[inline]
+/// `sb_<expires>_<signature>`: the signature is a keyed blake3 hash of the report id,
+/// its `created_at` and the expiry (unix seconds), so tokens cannot be derived from
+/// public report data nor reused after they expire.
 #[must_use]
 pub fn generate_sandbox_token(
     report_id: i32,
     created_at: &chrono::DateTime<chrono::Utc>,
 ) -> String {
-    // Create input data by combining report_id and timestamp
-    let timestamp_nanos = created_at.timestamp_nanos_opt().unwrap_or(0);
-    let input = format!("{report_id}:{timestamp_nanos}");
-
-    // Use blake3 for cryptographically secure hashing
-    let hash = blake3::hash(input.as_bytes());
-
-    // Take first 8 bytes (64 bits) for compact token
-    let hash_bytes = hash.as_bytes();
-    let mut token = String::with_capacity(19); // "sb_" + 16 hex chars
-    token.push_str("sb_");
-
-    for byte in &hash_bytes[..8] {
-        const HEX: &[u8] = b"0123456789abcdef";
-        // Safe: byte >> 4 and byte & 0xf always produce 0-15, HEX has 16 elements (indices 0-15)
-        // Using .get() with .unwrap_or() to satisfy clippy::indexing_slicing while maintaining safety
-        token.push(HEX.get((byte >> 4) as usize).copied().unwrap_or(b'0') as char);
-        token.push(HEX.get((byte & 0xf) as usize).copied().unwrap_or(b'0') as char);
-    }
-
-    token
+    let ttl_secs = i64::try_from(sandbox_token_ttl().as_secs()).unwrap_or(i64::MAX);
+    issue_sandbox_token(
+        sandbox_token_key(),
+        report_id,
+        created_at,
+        chrono::Utc::now().timestamp(),
+        ttl_secs,
+    )
 }
 
-/// Verify that a sandbox token matches the expected token for a report
+/// Verify that a sandbox token was issued for a report and has not expired
 ///
 /// Constant-time comparison to prevent timing attacks.
-#[inline]
 #[must_use]
 pub fn verify_sandbox_token(
     token: &str,
     report_id: i32,
     created_at: &chrono::DateTime<chrono::Utc>,
 ) -> bool {
-    let expected = generate_sandbox_token(report_id, created_at);
-    constant_time_compare(token.as_bytes(), expected.as_bytes())
+    check_sandbox_token(
+        sandbox_token_key(),
+        token,
+        report_id,
+        created_at,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+/// Path of the sandboxed iframe content of a report (`id` may be `latest`)
+#[must_use]
+pub fn sandboxed_report_path(id: impl std::fmt::Display) -> String {
+    format!("/api/crypto_reports/{id}/sandboxed")
+}
+
+/// Keyed blake3 signature (128 bits, hex) of `path` expiring at `expires`
+fn sandbox_path_signature(key: &[u8; 32], path: &str, expires: i64) -> String {
+    let input = format!("url:{path}:{expires}");
+    let hash = blake3::keyed_hash(key, input.as_bytes());
+    hash.to_hex().chars().take(32).collect()
+}
+
+fn issue_sandbox_url_query(key: &[u8; 32], path: &str, now: i64, ttl_secs: i64) -> String {
+    let expires = window_expiry(now, ttl_secs);
+    format!(
+        "expires={expires}&sig={}",
+        sandbox_path_signature(key, path, expires)
+    )
+}
+
+fn check_sandbox_url(key: &[u8; 32], path: &str, expires: &str, signature: &str, now: i64) -> bool {
+    let Ok(expires) = expires.parse::<i64>() else {
+        return false;
+    };
+    let expected = sandbox_path_signature(key, path, expires);
+    constant_time_compare(signature.as_bytes(), expected.as_bytes()) && now <= expires
+}
+
+/// Sign a sandbox content path, returning the `expires=<unix>&sig=<signature>` query
+///
+/// The signature covers the path and the expiry, so a copied iframe URL stops
+/// working after at most two token windows and cannot be reused for another report.
+#[must_use]
+pub fn sign_sandbox_url(path: &str) -> String {
+    let ttl_secs = i64::try_from(sandbox_token_ttl().as_secs()).unwrap_or(i64::MAX);
+    issue_sandbox_url_query(
+        sandbox_token_key(),
+        path,
+        chrono::Utc::now().timestamp(),
+        ttl_secs,
+    )
+}
+
+/// Verify the `expires` / `sig` query parameters of a signed sandbox URL
+#[must_use]
+pub fn verify_sandbox_url(path: &str, expires: &str, signature: &str) -> bool {
+    check_sandbox_url(
+        sandbox_token_key(),
+        path,
+        expires,
+        signature,
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+/// Configured admin token (`ADMIN_TOKEN`), `None` when unset or empty
+fn admin_token() -> Option<&'static str> {
+    static TOKEN: OnceLock<Option<String>> = OnceLock::new();
+    TOKEN
+        .get_or_init(|| {
+            std::env::var("ADMIN_TOKEN")
+                .ok()
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty())
+        })
+        .as_deref()
+}
+
+/// Extract the admin token presented by the caller
+///
+/// Accepts `Authorization: Bearer <token>` or `X-Admin-Token: <token>`.
+fn presented_admin_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| {
+            headers
+                .get(ADMIN_TOKEN_HEADER)
+                .and_then(|value| value.to_str().ok())
+        })
+        .map(str::trim)
+}
+
+/// Check the request carries the expected admin token (constant-time)
+fn is_admin_authorized(headers: &HeaderMap, expected: &str) -> bool {
+    presented_admin_token(headers)
+        .is_some_and(|token| constant_time_compare(token.as_bytes(), expected.as_bytes()))
+}
+
+/// Authorize an administrative request against `ADMIN_TOKEN`
+///
+/// Requests already authenticated with the `admin` scope (API key or JWT) pass. Otherwise
+/// admin endpoints guarded by this check stay disabled while no token is configured.
+///
+/// # Errors
+///
+/// Returns `Layer5Error::Forbidden` if no token is configured or the presented token is wrong
+pub fn authorize_admin(headers: &HeaderMap) -> Layer5Result<()> {
+    if current_caller().is_some_and(|caller| caller.allows(ApiScope::Admin)) {
+        return Ok(());
+    }
+    let Some(expected) = admin_token() else {
+        return Err(Layer5Error::Forbidden(
+            "Admin endpoints are disabled (ADMIN_TOKEN not configured)".to_string(),
+        ));
+    };
+    if is_admin_authorized(headers, expected) {
+        Ok(())
+    } else {
+        Err(Layer5Error::Forbidden("Invalid admin token".to_string()))
+    }
+}
+
+/// Who performed an admin request, for the audit log
+///
+/// Authenticated callers are recorded as `key:<name>` or `jwt:<subject>`. The shared
+/// `ADMIN_TOKEN` carries no identity, so operators name themselves with
+/// `X-Admin-Actor`; requests without it are recorded as `admin`.
+#[must_use]
+pub fn admin_actor(headers: &HeaderMap) -> String {
+    if let Some(caller) = current_caller() {
+        return caller.actor().chars().take(MAX_ACTOR_LENGTH).collect();
+    }
+    headers
+        .get(ADMIN_ACTOR_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|actor| !actor.is_empty())
+        .map_or_else(
+            || "admin".to_string(),
+            |actor| actor.chars().take(MAX_ACTOR_LENGTH).collect(),
+        )
+}
+
+/// Whether the request sends `X-Cache-Bypass: 1` (or `true`) with a valid admin token
+///
+/// Without a valid token the header is ignored and the request is served from cache
+/// as usual, so the bypass cannot be used to force renders.
+#[must_use]
+pub fn cache_bypass_requested(headers: &HeaderMap) -> bool {
+    if !bypass_header_enabled(headers) {
+        return false;
+    }
+    match authorize_admin(headers) {
+        Ok(()) => true,
+        Err(e) => {
+            debug!("🚧 Ignoring {} header: {}", CACHE_BYPASS_HEADER, e);
+            false
+        }
+    }
+}
+
+fn bypass_header_enabled(headers: &HeaderMap) -> bool {
+    headers
+        .get(CACHE_BYPASS_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true"))
 }
 
 /// Constant-time byte comparison to prevent timing attacks
 #[inline]
-fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
+pub(crate) fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
     if a.len() != b.len() {
         return false;
     }
@@ -78,32 +340,130 @@ fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
 mod tests {
     use super::*;
     use chrono::Utc;
+    use std::collections::HashMap;
+
+    const KEY: [u8; 32] = [7; 32];
+    const DAY: i64 = 86_400;
 
     #[test]
     fn test_generate_sandbox_token() {
-        let now = Utc::now();
-        let token = generate_sandbox_token(42, &now);
-
-        // Token should start with "sb_"
+        let created = Utc::now();
+        let now = 1_800_000_000;
+        let token = issue_sandbox_token(&KEY, 42, &created, now, DAY);
         assert!(token.starts_with("sb_"));
 
-        // Token should be consistent for same inputs
-        let token2 = generate_sandbox_token(42, &now);
-        assert_eq!(token, token2);
-
-        // Different report_id should produce different token
-        let token3 = generate_sandbox_token(43, &now);
-        assert_ne!(token, token3);
+        // Stable within a window, different across reports and keys
+        assert_eq!(
+            token,
+            issue_sandbox_token(&KEY, 42, &created, now + 60, DAY)
+        );
+        assert_ne!(token, issue_sandbox_token(&KEY, 43, &created, now, DAY));
+        assert_ne!(token, issue_sandbox_token(&[8; 32], 42, &created, now, DAY));
     }
 
     #[test]
     fn test_verify_sandbox_token() {
-        let now = Utc::now();
-        let token = generate_sandbox_token(42, &now);
+        let created = Utc::now();
+        let now = 1_800_000_000;
+        let token = issue_sandbox_token(&KEY, 42, &created, now, DAY);
+
+        assert!(check_sandbox_token(&KEY, &token, 42, &created, now));
+        assert!(check_sandbox_token(&KEY, &token, 42, &created, now + DAY));
+        assert!(!check_sandbox_token(
+            &KEY,
+            &token,
+            42,
+            &created,
+            now + 2 * DAY
+        ));
+        assert!(!check_sandbox_token(&KEY, &token, 43, &created, now));
+        assert!(!check_sandbox_token(&[8; 32], &token, 42, &created, now));
+        assert!(!check_sandbox_token(&KEY, "sb_invalid", 42, &created, now));
+
+        // Pushing the expiry out breaks the signature
+        let (_, signature) = token.rsplit_once('_').unwrap_or_default();
+        let extended = format!("sb_{}_{signature}", now + 365 * DAY);
+        assert!(!check_sandbox_token(&KEY, &extended, 42, &created, now));
+    }
+
+    #[test]
+    fn test_public_sandbox_token_round_trip() {
+        let created = Utc::now();
+        let token = generate_sandbox_token(7, &created);
+        assert!(verify_sandbox_token(&token, 7, &created));
+    }
+
+    #[test]
+    fn test_signed_sandbox_url() {
+        let now = 1_800_000_000;
+        let path = sandboxed_report_path(42);
+        let query = issue_sandbox_url_query(&KEY, &path, now, DAY);
+        let params: HashMap<&str, &str> = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .collect();
+        let expires = params.get("expires").copied().unwrap_or_default();
+        let sig = params.get("sig").copied().unwrap_or_default();
+
+        assert!(check_sandbox_url(&KEY, &path, expires, sig, now));
+        assert!(check_sandbox_url(&KEY, &path, expires, sig, now + DAY));
+        assert!(!check_sandbox_url(&KEY, &path, expires, sig, now + 2 * DAY));
+        assert!(!check_sandbox_url(
+            &KEY,
+            &sandboxed_report_path(43),
+            expires,
+            sig,
+            now
+        ));
+        assert!(!check_sandbox_url(&[8; 32], &path, expires, sig, now));
+        assert!(!check_sandbox_url(&KEY, &path, "soon", sig, now));
+
+        // Pushing the expiry out breaks the signature
+        let extended = (now + 365 * DAY).to_string();
+        assert!(!check_sandbox_url(&KEY, &path, &extended, sig, now));
+
+        // Token and URL signatures are not interchangeable
+        let token = issue_sandbox_token(&KEY, 42, &Utc::now(), now, DAY);
+        let (_, token_sig) = token.rsplit_once('_').unwrap_or_default();
+        assert!(!check_sandbox_url(&KEY, &path, expires, token_sig, now));
+    }
+
+    #[test]
+    fn test_admin_token_sources() -> Result<(), Box<dyn std::error::Error>> {
+        let mut headers = HeaderMap::new();
+        assert!(!is_admin_authorized(&headers, "s3cret"));
+
+        headers.insert(header::AUTHORIZATION, "Bearer s3cret".parse()?);
+        assert!(is_admin_authorized(&headers, "s3cret"));
+        assert!(!is_admin_authorized(&headers, "other"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ADMIN_TOKEN_HEADER, "s3cret".parse()?);
+        assert!(is_admin_authorized(&headers, "s3cret"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_admin_actor() -> Result<(), Box<dyn std::error::Error>> {
+        let mut headers = HeaderMap::new();
+        assert_eq!(admin_actor(&headers), "admin");
+        headers.insert(ADMIN_ACTOR_HEADER, " alice ".parse()?);
+        assert_eq!(admin_actor(&headers), "alice");
+        headers.insert(ADMIN_ACTOR_HEADER, "x".repeat(100).parse()?);
+        assert_eq!(admin_actor(&headers).len(), MAX_ACTOR_LENGTH);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bypass_header_values() -> Result<(), Box<dyn std::error::Error>> {
+        let mut headers = HeaderMap::new();
+        assert!(!bypass_header_enabled(&headers));
 
-        assert!(verify_sandbox_token(&token, 42, &now));
-        assert!(!verify_sandbox_token(&token, 43, &now));
-        assert!(!verify_sandbox_token("sb_invalid", 42, &now));
+        headers.insert(CACHE_BYPASS_HEADER, "TRUE".parse()?);
+        assert!(bypass_header_enabled(&headers));
+        headers.insert(CACHE_BYPASS_HEADER, "0".parse()?);
+        assert!(!bypass_header_enabled(&headers));
+        Ok(())
     }
 
     #[test]
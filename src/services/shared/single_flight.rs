@@ -0,0 +1,191 @@
+//! Single-Flight Request Coalescing
+//!
+//! Collapses concurrent computations of the same key into one: the first caller (the
+//! leader) runs the work while callers arriving in the meantime wait for a clone of
+//! its result. Cache-miss paths use it so a burst of requests for an uncached page
+//! costs one DB fetch and one render instead of one per request.
+//!
+//! When the leader is dropped before finishing (e.g. its client disconnected), the
+//! waiters retry and one of them takes over.
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use std::future::Future;
+use tokio::sync::broadcast;
+
+/// Outcome of `SingleFlight::run`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Flight<T> {
+    /// This caller ran the computation
+    Led(T),
+    /// This caller waited for another caller's computation
+    Joined(T),
+}
+
+impl<T> Flight<T> {
+    /// Whether the value came from another caller's computation
+    #[must_use]
+    pub const fn is_joined(&self) -> bool {
+        matches!(self, Self::Joined(_))
+    }
+
+    /// The computed value
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Led(value) | Self::Joined(value) => value,
+        }
+    }
+}
+
+enum Claim<T> {
+    Leader(broadcast::Sender<T>),
+    Follower(broadcast::Receiver<T>),
+}
+
+/// Per-key de-duplication of in-flight computations
+pub struct SingleFlight<T> {
+    in_flight: DashMap<String, broadcast::Sender<T>>,
+}
+
+impl<T: Clone> Default for SingleFlight<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    /// Create an empty group
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            in_flight: DashMap::new(),
+        }
+    }
+
+    /// Number of keys currently being computed
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Run `compute` for `key`, or wait for the run already in flight
+    ///
+    /// Only the leader's `compute` is called; it should re-check the cache first,
+    /// since a caller arriving just after a previous run finished becomes a new leader.
+    pub async fn run<F, Fut>(&self, key: &str, compute: F) -> Flight<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let sender = loop {
+            match self.claim(key) {
+                Claim::Leader(sender) => break sender,
+                Claim::Follower(mut receiver) => {
+                    if let Ok(value) = receiver.recv().await {
+                        return Flight::Joined(value);
+                    }
+                    // Leader dropped without a result: try to take over
+                }
+            }
+        };
+
+        let guard = FlightGuard {
+            in_flight: &self.in_flight,
+            key,
+        };
+        let value = compute().await;
+        // Unregister before publishing so late arrivals never subscribe to a finished run
+        drop(guard);
+        // No receivers just means nobody was waiting
+        let _ = sender.send(value.clone());
+        Flight::Led(value)
+    }
+
+    fn claim(&self, key: &str) -> Claim<T> {
+        match self.in_flight.entry(key.to_string()) {
+            Entry::Occupied(entry) => Claim::Follower(entry.get().subscribe()),
+            Entry::Vacant(entry) => {
+                let (sender, _) = broadcast::channel(1);
+                entry.insert(sender.clone());
+                Claim::Leader(sender)
+            }
+        }
+    }
+}
+
+/// Removes the leader's registration when it finishes or is dropped
+struct FlightGuard<'a, T> {
+    in_flight: &'a DashMap<String, broadcast::Sender<T>>,
+    key: &'a str,
+}
+
+impl<T> Drop for FlightGuard<'_, T> {
+    fn drop(&mut self) {
+        self.in_flight.remove(self.key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_calls_compute_once() -> Result<(), tokio::task::JoinError> {
+        let flights = Arc::new(SingleFlight::<u32>::new());
+        let computations = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..100)
+            .map(|_| {
+                let flights = Arc::clone(&flights);
+                let computations = Arc::clone(&computations);
+                tokio::spawn(async move {
+                    flights
+                        .run("report_42", || async {
+                            computations.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            42
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        let mut joined = 0;
+        for handle in handles {
+            let flight = handle.await?;
+            joined += usize::from(flight.is_joined());
+            assert_eq!(flight.into_inner(), 42);
+        }
+        assert_eq!(computations.load(Ordering::SeqCst), 1);
+        assert_eq!(joined, 99);
+        assert_eq!(flights.in_flight(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_waiter_takes_over_from_cancelled_leader() {
+        let flights = Arc::new(SingleFlight::<u32>::new());
+
+        let leader = {
+            let flights = Arc::clone(&flights);
+            tokio::spawn(async move { flights.run("report_7", std::future::pending::<u32>).await })
+        };
+        while flights.in_flight() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        let waiter = {
+            let flights = Arc::clone(&flights);
+            tokio::spawn(async move { flights.run("report_7", || async { 7 }).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        leader.abort();
+
+        let flight = tokio::time::timeout(Duration::from_secs(1), waiter).await;
+        assert!(matches!(flight, Ok(Ok(Flight::Led(7)))));
+        assert_eq!(flights.in_flight(), 0);
+    }
+}
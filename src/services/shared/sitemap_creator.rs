@@ -1,9 +1,13 @@
 //! Sitemap XML Generator for Layer 5 Business Logic
 //!
-//! Generates dynamic sitemap.xml following the sitemap protocol 0.9 specification.
-//! This module creates XML content for SEO purposes, including:
-//! - Static pages (homepage, `crypto_report` index, reports list)
-//! - Dynamic pages (individual crypto reports from database)
+//! Generates dynamic sitemaps following the sitemap protocol 0.9 specification.
+//! `/sitemap.xml` is a sitemap index; the URLs live in sub-sitemaps of at most
+//! `MAX_URLS_PER_SITEMAP` entries under `/sitemaps/`:
+//! - `static.xml`: static pages (homepage, `crypto_report` index, reports list)
+//! - `lists-N.xml`: further reports list pages
+//! - `reports-N.xml`: individual crypto reports from the database
+//!
+//! Report URLs carry `xhtml:link` hreflang alternates for their vi/en variants.
 //!
 //! Reference: <https://www.sitemaps.org/protocol.html>
 
@@ -12,9 +16,11 @@ use std::fmt::Write;
 use tracing::info;
 
 use super::error::{Layer5Error, Layer5Result};
+use super::public_origin::public_origin;
+use crate::services::data_communication::{SUPPORTED_LANGUAGES, report_path};
 
-/// Base URL for the website
-const BASE_URL: &str = "https://cryptodashboard.me";
+/// Public origin of the website when the request carries no trusted forwarded host
+pub const BASE_URL: &str = "https://cryptodashboard.me";
 
 /// Represents a single URL entry in the sitemap
 #[derive(Debug, Clone)]
@@ -27,6 +33,38 @@ pub struct SitemapEntry {
     pub changefreq: ChangeFrequency,
     /// Priority of this URL relative to other URLs (0.0 to 1.0)
     pub priority: f32,
+    /// Language variants, written as `xhtml:link` alternates
+    pub alternates: Vec<LanguageAlternate>,
+}
+
+/// One language variant of a page (`hreflang` annotation)
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct LanguageAlternate {
+    /// BCP 47 language code, or `x-default` for the language-negotiated URL
+    pub hreflang: &'static str,
+    /// Absolute URL of the variant
+    pub href: String,
+}
+
+/// The vi/en variants of a report page plus the `x-default` URL, which picks the
+/// language from the visitor's cookie or `Accept-Language`
+#[must_use]
+pub fn report_language_alternates(
+    report_id: i32,
+    created_at: DateTime<Utc>,
+) -> Vec<LanguageAlternate> {
+    let url = format!("{}{}", public_origin(), report_path(report_id, created_at));
+    SUPPORTED_LANGUAGES
+        .iter()
+        .map(|lang| LanguageAlternate {
+            hreflang: lang,
+            href: format!("{url}?lang={lang}"),
+        })
+        .chain(std::iter::once(LanguageAlternate {
+            hreflang: "x-default",
+            href: url.clone(),
+        }))
+        .collect()
 }
 
 /// Change frequency hints for search engines
@@ -56,103 +94,254 @@ impl ChangeFrequency {
     }
 }
 
+/// Max URLs per sub-sitemap (the protocol allows 50,000 URLs / 50 MB per file)
+pub const MAX_URLS_PER_SITEMAP: usize = 10_000;
+
+/// Reports per `/crypto_reports_list` page
+pub const REPORTS_LIST_PAGE_SIZE: i64 = 10;
+
+/// One sub-sitemap listed by the sitemap index (chunk numbers are 1-based)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SitemapSection {
+    /// Homepage, latest report and the first reports list page
+    Static,
+    /// Reports list pages 2 and up
+    Lists(usize),
+    /// Report pages, oldest first so that full chunks never change
+    Reports(usize),
+}
+
+impl SitemapSection {
+    /// File name under `/sitemaps/`, e.g. `reports-2.xml`
+    #[must_use]
+    pub fn file_name(self) -> String {
+        match self {
+            Self::Static => "static.xml".to_string(),
+            Self::Lists(chunk) => format!("lists-{chunk}.xml"),
+            Self::Reports(chunk) => format!("reports-{chunk}.xml"),
+        }
+    }
+
+    /// Section of a `/sitemaps/` file name
+    #[must_use]
+    pub fn parse(file_name: &str) -> Option<Self> {
+        let stem = file_name.strip_suffix(".xml")?;
+        if stem == "static" {
+            return Some(Self::Static);
+        }
+        let (kind, chunk) = stem.split_once('-')?;
+        let chunk = chunk.parse().ok().filter(|&chunk: &usize| chunk > 0)?;
+        match kind {
+            "lists" => Some(Self::Lists(chunk)),
+            "reports" => Some(Self::Reports(chunk)),
+            _ => None,
+        }
+    }
+}
+
 /// Sitemap XML generator
 pub struct SitemapCreator;
 
 impl SitemapCreator {
-    /// Generate complete sitemap XML from static and dynamic entries
+    /// Generate the sitemap index (`/sitemap.xml`) linking every sub-sitemap
     ///
     /// # Arguments
-    /// * `report_data` - Vector of tuples (`report_id`, `created_at`) from database
+    /// * `report_data` - Tuples (`report_id`, `created_at`) of every report
     ///
-    /// # Returns
-    /// Complete sitemap XML string
+    /// # Errors
+    ///
+    /// Returns error if XML writing fails
+    pub fn generate_sitemap_index(report_data: &[(i32, DateTime<Utc>)]) -> Layer5Result<String> {
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let reports = Self::oldest_first(report_data.to_vec());
+        let list_chunks = Self::list_page_urls(reports.len()).div_ceil(MAX_URLS_PER_SITEMAP);
+
+        let mut sections = vec![(SitemapSection::Static, today.clone())];
+        sections
+            .extend((1..=list_chunks).map(|chunk| (SitemapSection::Lists(chunk), today.clone())));
+        sections.extend(
+            reports
+                .chunks(MAX_URLS_PER_SITEMAP)
+                .enumerate()
+                .map(|(i, chunk)| {
+                    let lastmod = chunk
+                        .iter()
+                        .map(|(_, created_at)| *created_at)
+                        .max()
+                        .map_or_else(|| today.clone(), |date| date.format("%Y-%m-%d").to_string());
+                    (SitemapSection::Reports(i + 1), lastmod)
+                }),
+        );
+
+        let mut xml = String::with_capacity(200 + sections.len() * 150);
+        writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).map_err(xml_error)?;
+        writeln!(
+            xml,
+            r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#
+        )
+        .map_err(xml_error)?;
+        let origin = public_origin();
+        for (section, lastmod) in &sections {
+            writeln!(
+                xml,
+                "  <sitemap>\n    <loc>{origin}/sitemaps/{}</loc>\n    <lastmod>{lastmod}</lastmod>\n  </sitemap>",
+                section.file_name()
+            )
+            .map_err(xml_error)?;
+        }
+        writeln!(xml, "</sitemapindex>").map_err(xml_error)?;
+
+        info!(
+            "Sitemap index generated: {} sub-sitemaps for {} reports",
+            sections.len(),
+            reports.len()
+        );
+        Ok(xml)
+    }
+
+    /// Generate one sub-sitemap, `None` when the chunk does not exist
+    ///
+    /// # Arguments
+    /// * `section` - Sub-sitemap to render
+    /// * `report_data` - Tuples (`report_id`, `created_at`) of every report
     ///
     /// # Errors
     ///
-    /// Returns error if XML writing fails or string formatting fails
-    pub fn generate_sitemap_xml(report_data: Vec<(i32, DateTime<Utc>)>) -> Layer5Result<String> {
+    /// Returns error if XML writing fails
+    pub fn generate_section_xml(
+        section: SitemapSection,
+        report_data: Vec<(i32, DateTime<Utc>)>,
+    ) -> Layer5Result<Option<String>> {
         let today = Utc::now().format("%Y-%m-%d").to_string();
+        let entries = match section {
+            SitemapSection::Static => Self::get_static_entries(&today),
+            SitemapSection::Lists(chunk) => {
+                let pages = Self::list_page_urls(report_data.len());
+                let first = (chunk - 1) * MAX_URLS_PER_SITEMAP;
+                if first >= pages {
+                    return Ok(None);
+                }
+                // Page 1 is the plain list URL in the static section
+                let origin = public_origin();
+                (first..pages.min(first + MAX_URLS_PER_SITEMAP))
+                    .map(|i| SitemapEntry {
+                        loc: format!("{origin}/crypto_reports_list?page={}", i + 2),
+                        lastmod: Some(today.clone()),
+                        changefreq: ChangeFrequency::Daily,
+                        priority: 0.5,
+                        alternates: Vec::new(),
+                    })
+                    .collect()
+            }
+            SitemapSection::Reports(chunk) => {
+                let reports = Self::oldest_first(report_data);
+                let Some(chunk) = reports.chunks(MAX_URLS_PER_SITEMAP).nth(chunk - 1) else {
+                    return Ok(None);
+                };
+                Self::create_dynamic_entries(chunk.to_vec())
+            }
+        };
+        Self::write_urlset(&entries).map(Some)
+    }
 
-        // Pre-calculate capacity to minimize allocations
-        // Each URL entry is approximately 300-400 bytes
-        let estimated_capacity = 500 + (report_data.len() * 400);
-        let mut xml = String::with_capacity(estimated_capacity);
+    /// Sub-sitemaps whose content changes when `report_id` is published or updated:
+    /// the static pages, every reports list chunk and the report chunk holding it
+    ///
+    /// # Arguments
+    /// * `report_data` - Tuples (`report_id`, `created_at`) of every report
+    /// * `report_id` - Published or updated report; not in the index, no report chunk
+    #[must_use]
+    pub fn sections_for_report(
+        report_data: &[(i32, DateTime<Utc>)],
+        report_id: i32,
+    ) -> Vec<SitemapSection> {
+        let reports = Self::oldest_first(report_data.to_vec());
+        let list_chunks = Self::list_page_urls(reports.len()).div_ceil(MAX_URLS_PER_SITEMAP);
+        let report_chunk = reports
+            .iter()
+            .position(|&(id, _)| id == report_id)
+            .map(|position| SitemapSection::Reports(position / MAX_URLS_PER_SITEMAP + 1));
+
+        std::iter::once(SitemapSection::Static)
+            .chain((1..=list_chunks).map(SitemapSection::Lists))
+            .chain(report_chunk)
+            .collect()
+    }
 
-        // XML declaration and urlset opening tag
+    /// `<urlset>` document of `entries`
+    fn write_urlset(entries: &[SitemapEntry]) -> Layer5Result<String> {
+        // Each URL entry is approximately 300-400 bytes
+        let mut xml = String::with_capacity(500 + entries.len() * 400);
         writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)
             .map_err(|e| Layer5Error::Internal(format!("Failed to write XML header: {e}")))?;
         writeln!(
             xml,
-            r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#
+            r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:xhtml="http://www.w3.org/1999/xhtml">"#
         )
         .map_err(|e| Layer5Error::Internal(format!("Failed to write urlset: {e}")))?;
-
-        // Static entries
-        let static_entries = Self::get_static_entries(&today);
-        for entry in &static_entries {
-            Self::write_url_entry(&mut xml, entry)?;
-        }
-
-        // Dynamic entries from database
-        let dynamic_entries = Self::create_dynamic_entries(report_data);
-        for entry in &dynamic_entries {
+        for entry in entries {
             Self::write_url_entry(&mut xml, entry)?;
         }
-
-        // Close urlset
         writeln!(xml, "</urlset>")
             .map_err(|e| Layer5Error::Internal(format!("Failed to close urlset: {e}")))?;
+        Ok(xml)
+    }
 
-        let total_urls = static_entries.len() + dynamic_entries.len();
-        info!(
-            "Sitemap generated successfully: {} total URLs ({} static, {} dynamic)",
-            total_urls,
-            static_entries.len(),
-            dynamic_entries.len()
-        );
+    /// Reports list pages after the first one
+    fn list_page_urls(report_count: usize) -> usize {
+        let page_size = usize::try_from(REPORTS_LIST_PAGE_SIZE).unwrap_or(1);
+        report_count.div_ceil(page_size).saturating_sub(1)
+    }
 
-        Ok(xml)
+    fn oldest_first(mut report_data: Vec<(i32, DateTime<Utc>)>) -> Vec<(i32, DateTime<Utc>)> {
+        report_data.sort_by_key(|&(id, created_at)| (created_at, id));
+        report_data
     }
 
     /// Get static page entries
     fn get_static_entries(today: &str) -> Vec<SitemapEntry> {
+        let origin = public_origin();
         vec![
             // Homepage - highest priority
             SitemapEntry {
-                loc: BASE_URL.to_string(),
+                loc: origin.clone(),
                 lastmod: Some(today.to_string()),
                 changefreq: ChangeFrequency::Daily,
                 priority: 1.0,
+                alternates: Vec::new(),
             },
             // Latest crypto report index
             SitemapEntry {
-                loc: format!("{BASE_URL}/crypto_report"),
+                loc: format!("{origin}/crypto_report"),
                 lastmod: Some(today.to_string()),
                 changefreq: ChangeFrequency::Daily,
                 priority: 0.9,
+                alternates: Vec::new(),
             },
             // Reports list page
             SitemapEntry {
-                loc: format!("{BASE_URL}/crypto_reports_list"),
+                loc: format!("{origin}/crypto_reports_list"),
                 lastmod: Some(today.to_string()),
                 changefreq: ChangeFrequency::Daily,
                 priority: 0.8,
+                alternates: Vec::new(),
             },
         ]
     }
 
     /// Create dynamic entries from database report data
     fn create_dynamic_entries(report_data: Vec<(i32, DateTime<Utc>)>) -> Vec<SitemapEntry> {
+        let origin = public_origin();
         report_data
             .into_iter()
             .map(|(id, created_at)| {
                 let lastmod = created_at.format("%Y-%m-%d").to_string();
                 SitemapEntry {
-                    loc: format!("{BASE_URL}/crypto_report/{id}"),
+                    loc: format!("{origin}{}", report_path(id, created_at)),
                     lastmod: Some(lastmod),
                     changefreq: ChangeFrequency::Monthly,
                     priority: 0.7,
+                    alternates: report_language_alternates(id, created_at),
                 }
             })
             .collect()
@@ -173,6 +362,17 @@ impl SitemapCreator {
                 .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
         }
 
+        // Language variants (hreflang)
+        for alternate in &entry.alternates {
+            writeln!(
+                xml,
+                r#"    <xhtml:link rel="alternate" hreflang="{}" href="{}"/>"#,
+                alternate.hreflang,
+                Self::escape_xml(&alternate.href)
+            )
+            .map_err(|e| Layer5Error::Internal(format!("XML write error: {e}")))?;
+        }
+
         // Change frequency (optional)
         writeln!(
             xml,
@@ -215,6 +415,10 @@ impl SitemapCreator {
     }
 }
 
+fn xml_error(e: std::fmt::Error) -> Layer5Error {
+    Layer5Error::Internal(format!("XML write error: {e}"))
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -225,53 +429,154 @@ mod tests {
     fn test_generate_sitemap_with_reports() -> Result<(), Box<dyn std::error::Error>> {
         let reports = vec![
             (
-                1,
-                Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0)
+                2,
+                Utc.with_ymd_and_hms(2024, 2, 20, 12, 30, 0)
                     .single()
                     .ok_or("Invalid time")?,
             ),
             (
-                2,
-                Utc.with_ymd_and_hms(2024, 2, 20, 12, 30, 0)
+                1,
+                Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0)
                     .single()
                     .ok_or("Invalid time")?,
             ),
         ];
 
-        let xml = SitemapCreator::generate_sitemap_xml(reports)
-            .map_err(|e| format!("Failed to generate XML: {e}"))?;
+        let index = SitemapCreator::generate_sitemap_index(&reports)
+            .map_err(|e| format!("Failed to generate index: {e}"))?;
+        assert!(index.contains("<sitemapindex"));
+        assert!(index.contains("<loc>https://cryptodashboard.me/sitemaps/static.xml</loc>"));
+        assert!(index.contains(
+            "<loc>https://cryptodashboard.me/sitemaps/reports-1.xml</loc>\n    <lastmod>2024-02-20</lastmod>"
+        ));
+        // Two reports fit on the first list page
+        assert!(!index.contains("lists-1.xml"));
+
+        let xml = SitemapCreator::generate_section_xml(SitemapSection::Reports(1), reports)
+            .map_err(|e| format!("Failed to generate XML: {e}"))?
+            .ok_or("Missing reports chunk")?;
 
         // Verify XML structure
         assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
         assert!(xml.contains("<urlset"));
         assert!(xml.contains("</urlset>"));
 
-        // Verify static URLs
-        assert!(xml.contains("<loc>https://cryptodashboard.me</loc>"));
-        assert!(xml.contains("<loc>https://cryptodashboard.me/crypto_report</loc>"));
-        assert!(xml.contains("<loc>https://cryptodashboard.me/crypto_reports_list</loc>"));
-
-        // Verify dynamic URLs
-        assert!(xml.contains("<loc>https://cryptodashboard.me/crypto_report/1</loc>"));
-        assert!(xml.contains("<loc>https://cryptodashboard.me/crypto_report/2</loc>"));
+        // Verify dynamic URLs, oldest first
+        let first = xml.find("<loc>https://cryptodashboard.me/crypto_report/1-phan-tich-thi-truong-crypto-15-01-2024</loc>");
+        let second = xml.find("<loc>https://cryptodashboard.me/crypto_report/2-phan-tich-thi-truong-crypto-20-02-2024</loc>");
+        assert!(first.is_some() && first < second);
 
         // Verify lastmod format
         assert!(xml.contains("<lastmod>2024-01-15</lastmod>"));
         assert!(xml.contains("<lastmod>2024-02-20</lastmod>"));
+
+        // Verify hreflang alternates
+        assert!(xml.contains(r#"xmlns:xhtml="http://www.w3.org/1999/xhtml""#));
+        assert!(xml.contains(
+            r#"<xhtml:link rel="alternate" hreflang="en" href="https://cryptodashboard.me/crypto_report/1-phan-tich-thi-truong-crypto-15-01-2024?lang=en"/>"#
+        ));
+        assert_eq!(xml.matches(r#"hreflang="x-default""#).count(), 2);
         Ok(())
     }
 
     #[test]
     fn test_generate_sitemap_empty_reports() -> Result<(), Box<dyn std::error::Error>> {
-        let xml = SitemapCreator::generate_sitemap_xml(vec![])
-            .map_err(|e| format!("Failed to generate XML: {e}"))?;
+        let xml = SitemapCreator::generate_section_xml(SitemapSection::Static, vec![])
+            .map_err(|e| format!("Failed to generate XML: {e}"))?
+            .ok_or("Missing static section")?;
 
         // Should still have static URLs
         assert!(xml.contains("<loc>https://cryptodashboard.me</loc>"));
+        assert!(xml.contains("<loc>https://cryptodashboard.me/crypto_report</loc>"));
+        assert!(xml.contains("<loc>https://cryptodashboard.me/crypto_reports_list</loc>"));
         assert!(xml.contains("<priority>1.0</priority>"));
+
+        let index = SitemapCreator::generate_sitemap_index(&[])
+            .map_err(|e| format!("Failed to generate index: {e}"))?;
+        assert_eq!(index.matches("<sitemap>").count(), 1);
+        assert_eq!(
+            SitemapCreator::generate_section_xml(SitemapSection::Reports(1), vec![])
+                .map_err(|e| format!("Failed to generate XML: {e}"))?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sitemap_chunks() -> Result<(), Box<dyn std::error::Error>> {
+        let created = Utc
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .single()
+            .ok_or("Invalid time")?;
+        let count = i32::try_from(MAX_URLS_PER_SITEMAP)? + 5;
+        let reports: Vec<_> = (1..=count).map(|id| (id, created)).collect();
+
+        let index = SitemapCreator::generate_sitemap_index(&reports)
+            .map_err(|e| format!("Failed to generate index: {e}"))?;
+        assert!(index.contains("/sitemaps/reports-2.xml"));
+        assert!(!index.contains("/sitemaps/reports-3.xml"));
+        assert!(index.contains("/sitemaps/lists-1.xml"));
+
+        let second =
+            SitemapCreator::generate_section_xml(SitemapSection::Reports(2), reports.clone())
+                .map_err(|e| format!("Failed to generate XML: {e}"))?
+                .ok_or("Missing second chunk")?;
+        assert_eq!(second.matches("<url>").count(), 5);
+        assert!(second.contains(&format!(
+            "/crypto_report/{count}-phan-tich-thi-truong-crypto-01-01-2024</loc>"
+        )));
+
+        // 1,001 list pages: page 1 is static, pages 2..=1001 are in lists-1
+        let lists = SitemapCreator::generate_section_xml(SitemapSection::Lists(1), reports)
+            .map_err(|e| format!("Failed to generate XML: {e}"))?
+            .ok_or("Missing list pages")?;
+        assert_eq!(lists.matches("<url>").count(), 1000);
+        assert!(lists.contains("/crypto_reports_list?page=1001</loc>"));
         Ok(())
     }
 
+    #[test]
+    fn test_sections_for_report() -> Result<(), Box<dyn std::error::Error>> {
+        let created = Utc
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .single()
+            .ok_or("Invalid time")?;
+        let count = i32::try_from(MAX_URLS_PER_SITEMAP)? + 5;
+        let reports: Vec<_> = (1..=count).map(|id| (id, created)).collect();
+
+        assert_eq!(
+            SitemapCreator::sections_for_report(&reports, count),
+            vec![
+                SitemapSection::Static,
+                SitemapSection::Lists(1),
+                SitemapSection::Reports(2)
+            ]
+        );
+        assert_eq!(
+            SitemapCreator::sections_for_report(&reports, 3).last(),
+            Some(&SitemapSection::Reports(1))
+        );
+        assert_eq!(
+            SitemapCreator::sections_for_report(&[(1, created), (2, created)], 99),
+            vec![SitemapSection::Static]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_section_file_names() {
+        for section in [
+            SitemapSection::Static,
+            SitemapSection::Lists(1),
+            SitemapSection::Reports(12),
+        ] {
+            assert_eq!(SitemapSection::parse(&section.file_name()), Some(section));
+        }
+        assert_eq!(SitemapSection::parse("reports-0.xml"), None);
+        assert_eq!(SitemapSection::parse("reports-1"), None);
+        assert_eq!(SitemapSection::parse("authors-1.xml"), None);
+    }
+
     #[test]
     fn test_escape_xml() {
         assert_eq!(SitemapCreator::escape_xml("test&value"), "test&amp;value");
@@ -0,0 +1,183 @@
+//! Stale-While-Revalidate Serving
+//!
+//! Every rendered compressed page is also kept as a "stale copy" in L2 that outlives
+//! the fresh entry by the configured max staleness. When the fresh entry has expired,
+//! routes serve the stale copy immediately and re-render in a background task, so no
+//! request waits on the DB + Tera + gzip path while a recent copy exists.
+//!
+//! Environment:
+//! - `CACHE_MAX_STALENESS_SECS`: how long past expiry a page may still be served
+//!   (default 3600, `0` disables stale serving)
+
+use dashmap::DashMap;
+use multi_tier_cache::{Bytes, L2CacheBackend};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Default max staleness: one hour past the fresh TTL
+const DEFAULT_MAX_STALENESS: Duration = Duration::from_hours(1);
+
+/// Key prefix for stale copies in L2
+const STALE_KEY_PREFIX: &str = "swr:";
+
+/// L2 store of stale page copies plus background refresh de-duplication
+pub struct StaleCache {
+    l2: Arc<dyn L2CacheBackend>,
+    max_staleness: Duration,
+    refreshing: Arc<DashMap<String, ()>>,
+}
+
+impl StaleCache {
+    /// Create the stale store on top of the shared L2 backend
+    #[must_use]
+    pub fn new(l2: Arc<dyn L2CacheBackend>, max_staleness: Duration) -> Self {
+        Self {
+            l2,
+            max_staleness,
+            refreshing: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Create the stale store with `CACHE_MAX_STALENESS_SECS`
+    #[must_use]
+    pub fn from_env(l2: Arc<dyn L2CacheBackend>) -> Self {
+        let max_staleness =
+            parse_max_staleness(std::env::var("CACHE_MAX_STALENESS_SECS").ok().as_deref());
+        Self::new(l2, max_staleness)
+    }
+
+    /// Whether stale serving is enabled
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        !self.max_staleness.is_zero()
+    }
+
+    /// Keep a stale copy of a freshly cached page (`fresh_ttl` + max staleness)
+    pub async fn store(&self, cache_key: &str, data: &[u8], fresh_ttl: Duration) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let ttl = fresh_ttl + self.max_staleness;
+        if let Err(e) = self
+            .l2
+            .set_with_ttl(&stale_key(cache_key), Bytes::copy_from_slice(data), ttl)
+            .await
+        {
+            warn!("⚠️ Failed to store stale copy for {}: {}", cache_key, e);
+        }
+    }
+
+    /// Fetch the stale copy of an expired page, if still within max staleness
+    pub async fn get(&self, cache_key: &str) -> Option<Vec<u8>> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.l2
+            .get(&stale_key(cache_key))
+            .await
+            .map(|bytes| bytes.to_vec())
+    }
+
+    /// Run `refresh` in the background unless a refresh for this key is already running
+    ///
+    /// Returns `true` if a refresh task was spawned.
+    pub fn spawn_refresh<F>(&self, cache_key: &str, refresh: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let Some(guard) = claim_refresh(&self.refreshing, cache_key) else {
+            debug!("♻️ Background refresh already running for {}", cache_key);
+            return false;
+        };
+
+        tokio::spawn(async move {
+            let _guard = guard;
+            refresh.await;
+        });
+        true
+    }
+}
+
+/// Releases the refresh slot when the background task finishes (or panics)
+struct RefreshGuard {
+    refreshing: Arc<DashMap<String, ()>>,
+    cache_key: String,
+}
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.refreshing.remove(&self.cache_key);
+    }
+}
+
+/// Claim the refresh slot for a key
+fn claim_refresh(refreshing: &Arc<DashMap<String, ()>>, cache_key: &str) -> Option<RefreshGuard> {
+    match refreshing.entry(cache_key.to_string()) {
+        dashmap::Entry::Occupied(_) => None,
+        dashmap::Entry::Vacant(slot) => {
+            slot.insert(());
+            Some(RefreshGuard {
+                refreshing: Arc::clone(refreshing),
+                cache_key: cache_key.to_string(),
+            })
+        }
+    }
+}
+
+/// L2 key of the stale copy for a cache key
+pub(crate) fn stale_key(cache_key: &str) -> String {
+    format!("{STALE_KEY_PREFIX}{cache_key}")
+}
+
+/// Parse `CACHE_MAX_STALENESS_SECS` (invalid values fall back to the default)
+fn parse_max_staleness(value: Option<&str>) -> Duration {
+    match value.map(str::trim) {
+        Some(value) => value.parse::<u64>().map_or_else(
+            |e| {
+                warn!(
+                    "⚠️ Invalid CACHE_MAX_STALENESS_SECS '{}', using {:?}: {}",
+                    value, DEFAULT_MAX_STALENESS, e
+                );
+                DEFAULT_MAX_STALENESS
+            },
+            Duration::from_secs,
+        ),
+        None => DEFAULT_MAX_STALENESS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_max_staleness() {
+        assert_eq!(parse_max_staleness(None), DEFAULT_MAX_STALENESS);
+        assert_eq!(parse_max_staleness(Some("120")), Duration::from_mins(2));
+        assert_eq!(parse_max_staleness(Some("0")), Duration::ZERO);
+        assert_eq!(parse_max_staleness(Some("soon")), DEFAULT_MAX_STALENESS);
+    }
+
+    #[test]
+    fn test_refresh_is_deduplicated_per_key() {
+        let refreshing = Arc::new(DashMap::new());
+        let guard = claim_refresh(&refreshing, "page_1");
+        assert!(guard.is_some());
+        assert!(claim_refresh(&refreshing, "page_1").is_none());
+        assert!(claim_refresh(&refreshing, "page_2").is_some());
+
+        drop(guard);
+        assert!(claim_refresh(&refreshing, "page_1").is_some());
+    }
+
+    #[test]
+    fn test_stale_key_is_namespaced() {
+        assert_eq!(
+            stale_key("compressed_report_dsd_-1_vi"),
+            "swr:compressed_report_dsd_-1_vi"
+        );
+    }
+}
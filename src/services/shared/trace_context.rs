@@ -0,0 +1,187 @@
+//! Cross-Service Trace Context
+//!
+//! Minimal W3C Trace Context (`traceparent`) support so actions started here can be
+//! joined with the websocket service's traces. Incoming HTTP requests may carry a
+//! `traceparent` header; the context is then written into Redis Stream entry fields
+//! when publishing events, and read back from entries consumed from the stream.
+
+use axum::http::HeaderMap;
+use std::fmt::Write;
+
+/// HTTP header / stream field name for the W3C trace context
+pub const TRACEPARENT_FIELD: &str = "traceparent";
+/// Plain trace id field, convenient for log search on the consumer side
+pub const TRACE_ID_FIELD: &str = "trace_id";
+
+/// W3C trace context (version 00)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// 32 lowercase hex chars
+    pub trace_id: String,
+    /// 16 lowercase hex chars identifying the current span
+    pub span_id: String,
+    /// Sampled flag from the trace-flags byte
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Start a new trace with random ids
+    #[must_use]
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_hex::<16>(),
+            span_id: random_hex::<8>(),
+            sampled: true,
+        }
+    }
+
+    /// Continue the caller's trace from the `traceparent` header, or start a new one
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        headers
+            .get(TRACEPARENT_FIELD)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::parse_traceparent)
+            .map_or_else(Self::new_root, |parent| parent.child())
+    }
+
+    /// Create a child span within the same trace
+    #[must_use]
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: random_hex::<8>(),
+            sampled: self.sampled,
+        }
+    }
+
+    /// Parse a `traceparent` value (`00-<trace_id>-<span_id>-<flags>`)
+    #[must_use]
+    pub fn parse_traceparent(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        if version != "00" || parts.next().is_some() {
+            return None;
+        }
+        if !is_lower_hex(trace_id, 32) || !is_lower_hex(span_id, 16) || !is_lower_hex(flags, 2) {
+            return None;
+        }
+        // All-zero ids are invalid per spec
+        if trace_id.bytes().all(|b| b == b'0') || span_id.bytes().all(|b| b == b'0') {
+            return None;
+        }
+
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            sampled: flags & 0x01 == 0x01,
+        })
+    }
+
+    /// Format as a `traceparent` value
+    #[must_use]
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{}",
+            self.trace_id,
+            self.span_id,
+            if self.sampled { "01" } else { "00" }
+        )
+    }
+
+    /// Append trace fields to a Redis Stream entry
+    pub fn inject_into_fields(&self, fields: &mut Vec<(String, String)>) {
+        fields.push((TRACEPARENT_FIELD.to_string(), self.to_traceparent()));
+        fields.push((TRACE_ID_FIELD.to_string(), self.trace_id.clone()));
+    }
+
+    /// Read the trace context from Redis Stream entry fields, if present
+    #[must_use]
+    pub fn extract_from_fields(fields: &[(String, String)]) -> Option<Self> {
+        fields
+            .iter()
+            .find(|(key, _)| key == TRACEPARENT_FIELD)
+            .and_then(|(_, value)| Self::parse_traceparent(value))
+    }
+
+    /// Whether a stream field carries trace metadata rather than payload
+    #[must_use]
+    pub fn is_trace_field(key: &str) -> bool {
+        key == TRACEPARENT_FIELD || key == TRACE_ID_FIELD
+    }
+}
+
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// `N` random bytes as lowercase hex
+pub(crate) fn random_hex<const N: usize>() -> String {
+    let bytes: [u8; N] = rand::random();
+    bytes
+        .iter()
+        .fold(String::with_capacity(N * 2), |mut acc, byte| {
+            let _ = write!(acc, "{byte:02x}");
+            acc
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const SAMPLE: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_parse_and_format_traceparent() -> Result<(), Box<dyn std::error::Error>> {
+        let ctx = TraceContext::parse_traceparent(SAMPLE).ok_or("valid traceparent rejected")?;
+        assert_eq!(ctx.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(ctx.span_id, "00f067aa0ba902b7");
+        assert!(ctx.sampled);
+        assert_eq!(ctx.to_traceparent(), SAMPLE);
+
+        assert!(TraceContext::parse_traceparent("garbage").is_none());
+        assert!(
+            TraceContext::parse_traceparent(
+                "00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+            )
+            .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_headers_continues_trace() {
+        let mut headers = HeaderMap::new();
+        headers.insert(TRACEPARENT_FIELD, HeaderValue::from_static(SAMPLE));
+
+        let ctx = TraceContext::from_headers(&headers);
+        assert_eq!(ctx.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_ne!(ctx.span_id, "00f067aa0ba902b7");
+
+        let root = TraceContext::from_headers(&HeaderMap::new());
+        assert_eq!(root.trace_id.len(), 32);
+        assert_eq!(root.span_id.len(), 16);
+    }
+
+    #[test]
+    fn test_stream_field_round_trip() {
+        let ctx = TraceContext::new_root();
+        let mut fields = vec![("event".to_string(), "cache_cleared".to_string())];
+        ctx.inject_into_fields(&mut fields);
+
+        assert_eq!(fields.len(), 3);
+        assert_eq!(TraceContext::extract_from_fields(&fields), Some(ctx));
+        assert!(TraceContext::is_trace_field("traceparent"));
+        assert!(!TraceContext::is_trace_field("event"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Validated Query Extractor
+//!
+//! `ValidatedQuery<T>` deserializes the query string into `T::Params` and validates it
+//! into `T`. Invalid requests get 400 `application/problem+json` with an `errors`
+//! array of `{ field, message }` entries, rather than a silently defaulted value.
+
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{StatusCode, request::Parts},
+    response::Response,
+};
+
+use super::response_builder::build_problem_response_with;
+use crate::dto::requests::{FieldError, ValidateQuery};
+
+/// Query parameters that passed `T::validate`
+#[derive(Debug, Clone)]
+pub struct ValidatedQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ValidatedQuery<T>
+where
+    S: Send + Sync,
+    T: ValidateQuery,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let path = parts.uri.path();
+        let Query(params) = Query::<T::Params>::try_from_uri(&parts.uri)
+            .map_err(|rejection| invalid_query_response(path, &rejection.body_text(), &[]))?;
+        T::validate(params).map(Self).map_err(|errors| {
+            invalid_query_response(path, "Some query parameters are invalid", &errors)
+        })
+    }
+}
+
+fn invalid_query_response(path: &str, detail: &str, errors: &[FieldError]) -> Response {
+    let mut extensions = serde_json::Map::new();
+    extensions.insert(
+        "errors".to_string(),
+        serde_json::to_value(errors).unwrap_or_default(),
+    );
+    build_problem_response_with(
+        StatusCode::BAD_REQUEST,
+        "Invalid query parameters",
+        detail,
+        path,
+        extensions,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::requests::ReportsListQuery;
+    use axum::{Router, body::Body, extract::Request, routing::get};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new().route(
+            "/crypto_reports_list",
+            get(
+                |ValidatedQuery(query): ValidatedQuery<ReportsListQuery>| async move {
+                    query.page.to_string()
+                },
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_invalid_fields_are_reported() -> Result<(), Box<dyn std::error::Error>> {
+        let response = app()
+            .oneshot(Request::get("/crypto_reports_list?page=2&utm_source=x").body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.into_body().collect().await?.to_bytes(), "2");
+
+        let response = app()
+            .oneshot(Request::get("/crypto_reports_list?page=abc&lang=fr").body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let problem: serde_json::Value =
+            serde_json::from_slice(&response.into_body().collect().await?.to_bytes())?;
+        let fields: Vec<_> = problem
+            .pointer("/errors")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|error| error.pointer("/field").and_then(serde_json::Value::as_str))
+            .collect();
+        assert_eq!(fields, ["page", "lang"]);
+        Ok(())
+    }
+}
@@ -5,34 +5,66 @@ use std::sync::{
     atomic::{AtomicI32, AtomicU64},
 };
 use tera::Tera;
-use tracing::{debug, info, warn};
+use tracing::{Instrument, debug, info, warn};
+
+use crate::cache_config::{CacheConfig, init_cache_config};
+use crate::cache_resilience::ResilientL2Backend;
+use crate::disk_cache::DiskCache;
+use crate::l1_cache::WeightedL1Cache;
+use crate::redis_endpoint::{FailoverStreams, RedisEndpoint};
+use crate::services::health_system::island_span;
 
 // Import cache system from library
 use multi_tier_cache::{
-    CacheManager, CacheSystemBuilder, RedisStreams, backends::moka_cache::MokaCacheConfig,
-    backends::redis_cache::RedisCache,
+    CacheBackend, CacheManager, CacheSystemBuilder, L2CacheBackend, TierConfig,
 };
-use std::time::Duration;
 
 use crate::assets::load_chart_modules;
+
 /// Core Application State
 ///
 /// Replaces the complex `ServiceIslands` architecture with a standard Axum state that holds:
 /// - Database pool
 /// - Tera templates
-/// - Multi-tier Cache Manager
+/// - Multi-tier Cache Manager (byte-budgeted L1, degrading to L1 only while Redis is down)
+/// - Cross-instance L1 invalidation bus
+/// - Cache TTL / `max-age` configuration per content class
+/// - Registered Redis stream readers
+/// - Report lifecycle event publisher
 /// - Shared static components (Chart modules)
+/// - Stale page copies for stale-while-revalidate serving
+/// - Background task registry
 /// - Application counters
 pub struct AppState {
     pub db: PgPool,
     pub tera: Arc<Tera>,
     pub cache_manager: Arc<CacheManager>,
+    pub cache_config: &'static CacheConfig,
+    /// Announces purges/rewrites so other instances evict their L1 copies
+    pub cache_bus: crate::cache_invalidation::CacheInvalidationBus,
+    /// Redis L2 backend; `is_available()` is false while degraded to L1 only
+    pub redis_backend: Arc<ResilientL2Backend>,
+    /// L1 tier, kept for its byte usage and eviction counters
+    pub l1_cache: Arc<WeightedL1Cache>,
+    /// L3 tier when `CACHE_DISK_DIR` is set
+    pub disk_cache: Option<Arc<DiskCache>>,
     pub chart_modules_content: Arc<String>,
     pub request_counter: AtomicU64,
+    /// Layer 4 metrics behind `/metrics`, health and readiness tracking
+    pub health: crate::services::health_system::HealthSystemIsland,
     pub cached_latest_id: AtomicI32,
     pub crypto_handlers: crate::services::crypto_reports::handlers::CryptoHandlers,
     pub dashboard_handlers: crate::services::dashboard::DashboardHandlers,
-    pub redis_stream_reader: crate::stream::RedisStreamReader,
+    /// Readers of the market data, report event and notification streams
+    pub streams: crate::stream::StreamRegistry,
+    /// Writes report lifecycle events to the `report_events` stream
+    pub report_events: crate::services::data_communication::StreamPublisher,
+    pub stale_cache: crate::services::shared::StaleCache,
+    /// Stored redirects of legacy URLs, reloaded in the background
+    pub redirects: Arc<crate::services::shared::RedirectManager>,
+    pub tasks: crate::tasks::TaskRegistry,
+    /// Template load/parse errors from startup (reported by `/admin/diagnostics`)
+    pub template_errors: Vec<String>,
 }
 
 impl AppState {
@@ -46,74 +78,223 @@ impl AppState {
         // 1. Initialize DB
         let database_url = std::env::var("DATABASE_URL")
             .unwrap_or_else(|_| "postgresql://localhost/crypto_reports".to_string());
-        let db = PgPool::connect(&database_url).await?;
+        let db = PgPool::connect(&database_url)
+            .instrument(island_span("database"))
+            .await?;
 
         // 2. Initialize Templates
-        let tera = Arc::new(Self::initialize_template_engine());
+        let (tera, template_errors) =
+            island_span("crypto_reports").in_scope(Self::initialize_template_engine);
+        let tera = Arc::new(tera);
 
         // 3. Initialize Cache System
-        let moka_config = MokaCacheConfig {
-            max_capacity: 1000,
-            time_to_live: Duration::from_secs(30 * 60), // 30 mins
-            time_to_idle: Duration::from_secs(2 * 60),  // 2 mins
+        let cache_span = island_span("cache_system");
+        let cache_config = init_cache_config().map_err(|e| anyhow::anyhow!(e))?;
+        let tasks = crate::tasks::TaskRegistry::new();
+        let (redis_endpoint, redis_backend, redis_streams) = Self::connect_redis(&tasks)
+            .instrument(cache_span.clone())
+            .await?;
+
+        // Optional zstd storage encoding for L2 (served bytes stay gzip)
+        #[cfg(feature = "zstd-cache")]
+        let l2_backend: Arc<dyn L2CacheBackend> = {
+            let codec = crate::cache_storage::ZstdCodec::from_env()
+                .map_err(|e| anyhow::anyhow!("Failed to load zstd cache dictionary: {e}"))?;
+            info!("🗜️ L2 cache storage: zstd");
+            Arc::new(crate::cache_storage::TranscodingL2Backend::new(
+                redis_backend.clone(),
+                codec,
+            ))
         };
+        #[cfg(not(feature = "zstd-cache"))]
+        let l2_backend: Arc<dyn L2CacheBackend> = redis_backend.clone();
 
-        let redis_url =
-            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-        let redis_backend = Arc::new(
-            RedisCache::with_url(&redis_url)
-                .await
-                .map_err(|e| anyhow::anyhow!("Failed to initialize Redis cache backend: {}", e))?,
-        );
+        // Stale page copies share the L2 backend (and its storage encoding)
+        let stale_cache = crate::services::shared::StaleCache::from_env(Arc::clone(&l2_backend));
 
-        let redis_streams = Arc::new(
-            RedisStreams::new(&redis_url)
-                .await
-                .map_err(|e| anyhow::anyhow!("Failed to initialize Redis streams backend: {e}"))?,
-        );
+        // L1 is built here so the invalidation bus can evict from it directly
+        let l1_backend = Arc::new(WeightedL1Cache::from_env());
+        let l1_for_bus: Arc<dyn CacheBackend> = l1_backend.clone();
+        let cache_bus = match crate::cache_invalidation::CacheInvalidationBus::from_env(
+            &redis_endpoint,
+            Arc::clone(&l1_for_bus),
+        )
+        .instrument(cache_span.clone())
+        .await
+        {
+            Ok(bus) => bus,
+            Err(e) => {
+                warn!("⚠️ Cache invalidation bus unavailable, L1 stays per-instance: {e}");
+                crate::cache_invalidation::CacheInvalidationBus::disabled(l1_for_bus)
+            }
+        };
+
+        // Optional persistent L3 tier (needs explicit tiers: L1 Moka + L2 + disk)
+        let disk_cache = cache_span.in_scope(Self::open_disk_cache)?;
+        let cache_builder = match &disk_cache {
+            Some((disk_cache, ttl_scale)) => {
+                CacheSystemBuilder::new()
+                    .with_tier(l1_backend.clone(), TierConfig::as_l1())
+                    .with_tier(l2_backend, TierConfig::as_l2())
+                    .with_tier(
+                        disk_cache.clone(),
+                        // Disk hits always promote: they only happen on an empty L1/L2
+                        TierConfig::as_l3()
+                            .with_ttl_scale(*ttl_scale)
+                            .with_promotion_frequency(1),
+                    )
+            }
+            None => CacheSystemBuilder::new()
+                .with_l1(l1_backend.clone())
+                .with_l2(l2_backend),
+        };
 
-        let cache_system = CacheSystemBuilder::new()
-            .with_moka_config(moka_config)
-            .with_l2(redis_backend)
+        let cache_system = cache_builder
             .with_streams(redis_streams)
             .build()
+            .instrument(cache_span)
             .await?;
         let cache_manager: Arc<CacheManager> = cache_system.cache_manager.clone();
 
+        // Consume the registered streams over the configured transport
+        let streams = island_span("market_data_stream")
+            .in_scope(|| Self::start_streams(&cache_manager, &redis_endpoint, &tasks))?;
+
         // 4. Initialize Chart Modules
-        let chart_modules_content = Arc::new(load_chart_modules()?);
+        let chart_modules_content =
+            Arc::new(island_span("dashboard").in_scope(load_chart_modules)?);
+
+        // Layer 4: sample pool acquire waits in the background
+        let health = island_span("health_system").in_scope(|| {
+            let health = crate::services::health_system::HealthSystemIsland::new();
+            health.db_pool.spawn_sampler(db.clone(), &tasks);
+            health
+        });
 
         info!("✅ Application State initialized successfully");
 
         Ok(Self {
+            // Before `db` and `tasks` move into the state
+            redirects: Self::load_redirects(&db, &tasks).await,
             db,
             tera,
             cache_manager: cache_manager.clone(),
+            cache_config,
+            cache_bus,
             chart_modules_content,
             request_counter: AtomicU64::new(0),
+            health,
             cached_latest_id: AtomicI32::new(0),
             crypto_handlers: crate::services::crypto_reports::handlers::CryptoHandlers::new(),
             dashboard_handlers: crate::services::dashboard::DashboardHandlers::new(),
-            redis_stream_reader: crate::stream::RedisStreamReader::new(Arc::clone(&cache_manager)),
+            report_events: Self::report_event_publisher(&streams, &cache_manager),
+            streams,
+            stale_cache,
+            redis_backend,
+            l1_cache: l1_backend,
+            disk_cache: disk_cache.map(|(disk_cache, _)| disk_cache),
+            tasks,
+            template_errors,
         })
     }
 
-    /// Health check
-    pub async fn health_check(&self) -> bool {
-        // Just return true or add more checks
-        self.redis_stream_reader
-            .health_check()
+    /// Resolve `REDIS_URL` (single node or Sentinel) and connect the L2 and stream backends
+    ///
+    /// Redis outages (at startup or later) degrade to L1-only instead of failing.
+    async fn connect_redis(
+        tasks: &crate::tasks::TaskRegistry,
+    ) -> Result<(
+        Arc<RedisEndpoint>,
+        Arc<ResilientL2Backend>,
+        Arc<FailoverStreams>,
+    )> {
+        let redis_url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let redis_endpoint = Arc::new(
+            RedisEndpoint::connect(&redis_url)
+                .await
+                .map_err(|e| anyhow::anyhow!(e))?,
+        );
+        redis_endpoint.spawn_monitor(tasks);
+
+        let redis_backend = Arc::new(ResilientL2Backend::connect(&redis_endpoint).await);
+        redis_backend.spawn_monitor(Arc::clone(&redis_endpoint), tasks);
+
+        let redis_streams = Arc::new(FailoverStreams::new(Arc::clone(&redis_endpoint)));
+        if let Err(e) = redis_streams.backend().await {
+            warn!("⚠️ Redis streams unavailable at startup, reconnecting on use: {e}");
+        }
+        Ok((redis_endpoint, redis_backend, redis_streams))
+    }
+
+    /// Publisher for the `report_events` stream, using its configured key when it is read too
+    fn report_event_publisher(
+        streams: &crate::stream::StreamRegistry,
+        cache_manager: &Arc<CacheManager>,
+    ) -> crate::services::data_communication::StreamPublisher {
+        let stream_key = streams.get(crate::stream::REPORT_EVENTS).map_or_else(
+            || crate::stream::StreamDefinition::named(crate::stream::REPORT_EVENTS).stream_key,
+            |reader| reader.definition.stream_key.clone(),
+        );
+        crate::services::data_communication::StreamPublisher::from_env(cache_manager, stream_key)
+    }
+
+    /// Consume the registered streams over the configured transport
+    fn start_streams(
+        cache_manager: &Arc<CacheManager>,
+        redis_endpoint: &Arc<RedisEndpoint>,
+        tasks: &crate::tasks::TaskRegistry,
+    ) -> Result<crate::stream::StreamRegistry> {
+        let streams = crate::stream::StreamRegistry::from_env(cache_manager);
+        crate::services::data_communication::transport_from_env(redis_endpoint)
+            .map_err(anyhow::Error::msg)?
+            .start(&streams, tasks);
+        Ok(streams)
+    }
+
+    /// Load the redirect table and keep it reloading; a failed first load starts empty
+    async fn load_redirects(
+        db: &PgPool,
+        tasks: &crate::tasks::TaskRegistry,
+    ) -> Arc<crate::services::shared::RedirectManager> {
+        let redirects = Arc::new(crate::services::shared::RedirectManager::new());
+        match redirects
+            .reload(db)
+            .instrument(island_span("database"))
             .await
-            .unwrap_or(false)
+        {
+            Ok(count) => info!("🔀 Loaded {} redirects", count),
+            Err(e) => warn!("⚠️ Redirects unavailable at startup, retrying in background: {e}"),
+        }
+        redirects.spawn_reloader(db.clone(), tasks);
+        redirects
+    }
+
+    /// Open the L3 tier when `CACHE_DISK_DIR` is set, with its TTL scale
+    fn open_disk_cache() -> Result<Option<(Arc<DiskCache>, f64)>> {
+        let Some(disk_config) = crate::disk_cache::DiskCacheConfig::from_env() else {
+            return Ok(None);
+        };
+        let disk_cache = DiskCache::open(&disk_config)
+            .map_err(|e| anyhow::anyhow!("Failed to open L3 disk cache: {e}"))?;
+        Ok(Some((Arc::new(disk_cache), disk_config.ttl_scale)))
     }
 
-    fn initialize_template_engine() -> Tera {
+    /// Health check - per-island report from the Health System Island
+    pub async fn health_check(&self) -> crate::dto::responses::HealthReport {
+        self.health.health_check(self).await
+    }
+
+    /// Load templates, returning the engine and every load/parse error encountered
+    fn initialize_template_engine() -> (Tera, Vec<String>) {
         debug!("📝 Initializing Tera template engine...");
 
+        let mut errors = Vec::new();
         let mut tera = match Tera::new("dashboards/**/*.html") {
             Ok(t) => t,
             Err(e) => {
                 warn!("Template parsing error: {}", e);
+                errors.push(format!("dashboards/**/*.html: {e}"));
                 Tera::default()
             }
         };
@@ -132,6 +313,10 @@ impl AppState {
                 "dashboards/crypto_dashboard/routes/reports/list.html",
                 "crypto/routes/reports/list.html",
             ),
+            (
+                "dashboards/crypto_dashboard/routes/authors/archive.html",
+                "crypto/routes/authors/archive.html",
+            ),
             (
                 "shared_components/theme_toggle.html",
                 "crypto/components/theme_toggle.html",
@@ -145,16 +330,23 @@ impl AppState {
                 "shared/components/market-indicators.html",
             ),
             ("dashboards/home.html", "home.html"),
+            ("dashboards/emails/digest.html", "emails/digest.html"),
         ];
 
         for (path, name) in templates {
             if let Err(e) = tera.add_template_file(path, Some(name)) {
                 warn!("Failed to load template {path}: {e}");
+                errors.push(format!("{path}: {e}"));
             }
         }
 
+        // `csrf_field(token=...)` for the hidden CSRF field of HTML forms
+        crate::services::shared::register_csrf_helper(&mut tera);
+        // `money(currency=..., lang=...)` / `number(decimals=..., lang=...)` filters
+        crate::services::shared::register_currency_filters(&mut tera);
+
         tera.autoescape_on(vec![]);
         info!("✅ Tera template engine initialized");
-        tera
+        (tera, errors)
     }
 }
@@ -0,0 +1,152 @@
+//! Stream Checkpoints
+//!
+//! The ID of the last entry each stream's consumers processed, kept in memory and
+//! persisted in Redis under `{stream_key}:checkpoint`. Consumers load it when they
+//! connect and skip entries at or below it, so an entry handled before a restart
+//! (a reclaimed pending entry, a redelivery after a consumer-group reset) is not
+//! cached and pushed to live subscribers a second time.
+//!
+//! Replicas of a consumer group share the key; the Lua update only ever moves it
+//! forward, so a replica finishing an older batch late cannot rewind it. Admin
+//! replays bypass the checkpoint on purpose.
+
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, warn};
+
+use super::entry_id_parts;
+
+/// Sets `KEYS[1]` to `ARGV[1]` only if that entry ID is newer than the stored one
+const ADVANCE_SCRIPT: &str = r"
+local current = redis.call('GET', KEYS[1])
+if current then
+  local cm, cs = string.match(current, '^(%d+)-(%d+)$')
+  local nm, ns = string.match(ARGV[1], '^(%d+)-(%d+)$')
+  if cm and nm then
+    cm, cs, nm, ns = tonumber(cm), tonumber(cs), tonumber(nm), tonumber(ns)
+    if nm < cm or (nm == cm and ns <= cs) then
+      return 0
+    end
+  end
+end
+redis.call('SET', KEYS[1], ARGV[1])
+return 1
+";
+
+/// Last processed entry of one stream
+#[derive(Debug)]
+pub struct StreamCheckpoint {
+    key: String,
+    last_entry_id: Mutex<Option<String>>,
+    skipped: AtomicU64,
+}
+
+impl StreamCheckpoint {
+    /// Checkpoint of the stream at `stream_key`
+    #[must_use]
+    pub fn new(stream_key: &str) -> Self {
+        Self {
+            key: format!("{stream_key}:checkpoint"),
+            last_entry_id: Mutex::new(None),
+            skipped: AtomicU64::new(0),
+        }
+    }
+
+    /// Redis key the checkpoint is persisted under
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Newest entry ID processed (by this instance or loaded from Redis)
+    #[must_use]
+    pub fn last_entry_id(&self) -> Option<String> {
+        self.last_entry_id.lock().ok().and_then(|last| last.clone())
+    }
+
+    /// Already-seen entries skipped since startup
+    #[must_use]
+    pub fn skipped(&self) -> u64 {
+        self.skipped.load(Ordering::Relaxed)
+    }
+
+    /// Whether `entry_id` is newer than the checkpoint; counts a skip if not
+    pub(super) fn is_new(&self, entry_id: &str) -> bool {
+        let last = self.last_entry_id();
+        let is_new = last
+            .as_deref()
+            .is_none_or(|last| entry_id_parts(entry_id) > entry_id_parts(last));
+        if !is_new {
+            self.skipped.fetch_add(1, Ordering::Relaxed);
+            debug!(
+                "⏭️ Skipping already processed entry {} (checkpoint {})",
+                entry_id,
+                last.unwrap_or_default()
+            );
+        }
+        is_new
+    }
+
+    /// Move the in-memory checkpoint forward to `entry_id` (never backwards)
+    pub(super) fn advance(&self, entry_id: &str) {
+        let Ok(mut last) = self.last_entry_id.lock() else {
+            return;
+        };
+        let newer = last
+            .as_deref()
+            .is_none_or(|last| entry_id_parts(entry_id) > entry_id_parts(last));
+        if newer && entry_id_parts(entry_id).is_some() {
+            *last = Some(entry_id.to_string());
+        }
+    }
+
+    /// Seed the checkpoint from Redis (on connect)
+    pub(super) async fn load(&self, conn: &mut ConnectionManager) {
+        let stored: redis::RedisResult<Option<String>> = conn.get(&self.key).await;
+        match stored {
+            Ok(Some(entry_id)) => self.advance(&entry_id),
+            Ok(None) => {}
+            Err(e) => warn!("⚠️ Failed to load checkpoint {}: {}", self.key, e),
+        }
+    }
+
+    /// Record `entry_id` as processed, in memory and in Redis
+    ///
+    /// A failed write is only logged: the in-memory checkpoint still dedupes
+    /// until the next restart.
+    pub(super) async fn commit(&self, conn: &mut ConnectionManager, entry_id: &str) {
+        self.advance(entry_id);
+        let advanced: redis::RedisResult<i32> = redis::Script::new(ADVANCE_SCRIPT)
+            .key(&self.key)
+            .arg(entry_id)
+            .invoke_async(conn)
+            .await;
+        if let Err(e) = advanced {
+            warn!("⚠️ Failed to persist checkpoint {}: {}", self.key, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_only_moves_forward() {
+        let checkpoint = StreamCheckpoint::new("market_data_stream");
+        assert_eq!(checkpoint.key(), "market_data_stream:checkpoint");
+        assert!(checkpoint.is_new("1700-0"));
+
+        checkpoint.advance("1700-5");
+        checkpoint.advance("1699-9");
+        checkpoint.advance("garbage");
+        assert_eq!(checkpoint.last_entry_id().as_deref(), Some("1700-5"));
+
+        assert!(!checkpoint.is_new("1700-5"));
+        assert!(!checkpoint.is_new("999-0"));
+        assert!(checkpoint.is_new("1700-6"));
+        assert_eq!(checkpoint.skipped(), 2);
+    }
+}
@@ -0,0 +1,517 @@
+//! Background Stream Consumers
+//!
+//! Both consumers wait on Redis with a blocking read (`BLOCK`), so a new entry is
+//! cached as soon as it is published and an idle stream costs one command per
+//! block period instead of a poll per second:
+//! - `GroupConsumer`: `XREADGROUP` member; replicas split the stream and `XACK`
+//!   what they processed, reclaiming entries left pending by crashed members
+//! - `TailConsumer`: plain `XREAD` from the newest entry, every instance reads
+//!   everything (used when consumer groups are disabled, and to feed live updates)
+//!
+//! Redis errors are retried with a capped exponential backoff (1s up to 30s), and
+//! each consumer reconnects if a Sentinel failover moved the master in the
+//! meantime. Anything else that ends a consumer is restarted by the task supervisor.
+
+use redis::AsyncCommands;
+use redis::aio::{ConnectionManager, ConnectionManagerConfig};
+use redis::streams::{
+    StreamAutoClaimOptions, StreamAutoClaimReply, StreamId, StreamReadOptions, StreamReadReply,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use multi_tier_cache::CacheManager;
+
+use crate::redis_endpoint::RedisEndpoint;
+use crate::tasks::Backoff;
+
+use super::{
+    ConsumerGroupConfig, DeadLetterQueue, LiveUpdates, RedisStreamReader, StreamCheckpoint,
+    StreamDefinition, StreamFreshness, entry_id_parts, freshness, store_latest,
+};
+
+/// Max entries per XREAD / XREADGROUP / XAUTOCLAIM call
+const CONSUMER_BATCH: usize = 100;
+/// How long one blocking read waits for new entries
+const CONSUMER_BLOCK: Duration = Duration::from_secs(5);
+/// First / max delay before retrying after a Redis error (doubling in between)
+const CONSUMER_RETRY_INITIAL: Duration = Duration::from_secs(1);
+const CONSUMER_RETRY_MAX: Duration = Duration::from_secs(30);
+/// XAUTOCLAIM rounds per reclaim pass (bounds the work after a long outage)
+const MAX_CLAIM_ROUNDS: usize = 10;
+
+/// What both consumers do with a delivered entry
+pub(super) struct EntryHandler {
+    pub(super) cache_manager: Arc<CacheManager>,
+    pub(super) definition: StreamDefinition,
+    pub(super) freshness: Arc<StreamFreshness>,
+    pub(super) dead_letters: Arc<DeadLetterQueue>,
+    pub(super) live: Arc<LiveUpdates>,
+    pub(super) checkpoint: Arc<StreamCheckpoint>,
+    /// This consumer sees every entry of the stream in this process, so it feeds
+    /// `subscribe_events`; a consumer group member only gets a share
+    pub(super) announces_events: bool,
+}
+
+impl EntryHandler {
+    /// Store an entry as the stream's latest snapshot and push it to live (and,
+    /// when `announces_events`, event) subscribers
+    ///
+    /// Returns `false` when the entry failed validation and was dead-lettered.
+    async fn cache_entry(&self, entry: &StreamId) -> bool {
+        self.cache_fields(&entry.id, &entry_fields(entry)).await
+    }
+
+    /// `cache_entry` for an entry already split into field/value pairs
+    pub(super) async fn cache_fields(&self, entry_id: &str, fields: &[(String, String)]) -> bool {
+        let data = match RedisStreamReader::decode(&self.definition, fields) {
+            Ok(data) => data,
+            Err(reason) => {
+                self.dead_letters
+                    .send(
+                        &self.cache_manager,
+                        &self.definition.stream_key,
+                        entry_id,
+                        fields,
+                        &reason,
+                    )
+                    .await;
+                return false;
+            }
+        };
+        if let Err(e) = store_latest(&self.cache_manager, &self.definition, &data).await {
+            warn!("⚠️ Failed to cache stream entry {}: {}", entry_id, e);
+        }
+        if self.announces_events {
+            self.live.announce(entry_id, data);
+        } else {
+            self.live.publish(entry_id, data);
+        }
+        true
+    }
+
+    /// Announce the entries of a batch older than `newest` to event subscribers, in
+    /// ID order; only the newest is cached
+    fn announce_older(&self, entries: &[StreamId], newest: &StreamId) {
+        for entry in older_new_entries(entries, newest, &self.checkpoint) {
+            match RedisStreamReader::decode(&self.definition, &entry_fields(entry)) {
+                Ok(data) => self.live.announce(&entry.id, data),
+                Err(reason) => debug!("⏭️ Skipping invalid entry {}: {}", entry.id, reason),
+            }
+        }
+    }
+
+    /// Push `entries` to live and event subscribers only, in ID order (another
+    /// consumer caches them); returns the newest
+    fn notify_entries<'a>(&self, entries: &'a [StreamId]) -> Option<&'a StreamId> {
+        let mut ordered: Vec<&StreamId> = entries.iter().collect();
+        ordered.sort_by_key(|entry| entry_id_parts(&entry.id));
+        for entry in &ordered {
+            // Rejected entries are dead-lettered by the consumer that caches them
+            if let Ok(data) = RedisStreamReader::decode(&self.definition, &entry_fields(entry)) {
+                self.live.announce(&entry.id, data);
+            }
+        }
+        ordered.last().copied()
+    }
+
+    /// Cache the newest of `entries` and record it as consumed, unless the
+    /// checkpoint shows it was already processed; the others are only announced
+    async fn cache_newest<'a>(
+        &self,
+        conn: &mut ConnectionManager,
+        entries: &'a [StreamId],
+    ) -> Option<&'a StreamId> {
+        let newest = newest_entry(entries)?;
+        if !self.checkpoint.is_new(&newest.id) {
+            return Some(newest);
+        }
+        if self.announces_events {
+            self.announce_older(entries, newest);
+        }
+        if self.cache_entry(newest).await {
+            self.freshness.record(&newest.id, freshness::now_millis());
+        }
+        self.checkpoint.commit(conn, &newest.id).await;
+        Some(newest)
+    }
+}
+
+/// Entry with the highest ID
+fn newest_entry(entries: &[StreamId]) -> Option<&StreamId> {
+    entries.iter().max_by_key(|entry| entry_id_parts(&entry.id))
+}
+
+/// Entries of a batch other than `newest` that are past the checkpoint, in ID order
+fn older_new_entries<'a>(
+    entries: &'a [StreamId],
+    newest: &StreamId,
+    checkpoint: &StreamCheckpoint,
+) -> Vec<&'a StreamId> {
+    let mut older: Vec<&StreamId> = entries
+        .iter()
+        .filter(|entry| entry.id != newest.id && checkpoint.is_new(&entry.id))
+        .collect();
+    older.sort_by_key(|entry| entry_id_parts(&entry.id));
+    older
+}
+
+/// Field/value pairs of an entry (non-string values are skipped)
+fn entry_fields(entry: &StreamId) -> Vec<(String, String)> {
+    entry
+        .map
+        .iter()
+        .filter_map(|(field, value)| {
+            redis::from_redis_value_ref::<String>(value)
+                .ok()
+                .map(|value| (field.clone(), value))
+        })
+        .collect()
+}
+
+/// Connection to the current master for blocking reads, retrying while Redis is down
+///
+/// Returns the connection with the endpoint generation it was opened under.
+async fn connect_blocking(endpoint: &RedisEndpoint) -> Result<(ConnectionManager, u64), String> {
+    // Reads block server-side, longer than the default response timeout
+    let connection_config = ConnectionManagerConfig::new()
+        .set_response_timeout(Some(CONSUMER_BLOCK + Duration::from_secs(2)));
+    let mut retry = retry_backoff();
+    loop {
+        let generation = endpoint.generation();
+        let Some(url) = endpoint.master_url() else {
+            warn!("⚠️ Stream consumer waiting for the Redis master to be resolved");
+            tokio::time::sleep(retry.next_delay()).await;
+            continue;
+        };
+        let client = redis::Client::open(url).map_err(|e| e.to_string())?;
+        match client
+            .get_connection_manager_with_config(connection_config.clone())
+            .await
+        {
+            Ok(conn) => return Ok((conn, generation)),
+            Err(e) => {
+                let delay = retry.next_delay();
+                warn!(
+                    "⚠️ Stream consumer cannot reach Redis, retrying in {:?}: {}",
+                    delay, e
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+fn retry_backoff() -> Backoff {
+    Backoff::new(CONSUMER_RETRY_INITIAL, CONSUMER_RETRY_MAX)
+}
+
+/// Log the way back after consecutive errors and start the backoff over
+fn recovered(stream_key: &str, failures: &mut u32, retry: &mut Backoff) {
+    if *failures > 0 {
+        info!(
+            "✅ Stream consumer on {} recovered after {} errors",
+            stream_key, failures
+        );
+        *failures = 0;
+        retry.reset();
+    }
+}
+
+fn block_millis() -> usize {
+    usize::try_from(CONSUMER_BLOCK.as_millis()).unwrap_or(usize::MAX)
+}
+
+/// One consumer group member reading a registered stream
+pub(super) struct GroupConsumer {
+    pub(super) handler: EntryHandler,
+    pub(super) config: ConsumerGroupConfig,
+}
+
+impl GroupConsumer {
+    /// Connect (retrying while Redis is down), then read until the process exits
+    pub(super) async fn run(self, endpoint: &RedisEndpoint) -> Result<(), String> {
+        let (mut conn, mut generation) = connect_blocking(endpoint).await?;
+        self.handler.checkpoint.load(&mut conn).await;
+
+        self.ensure_group(&mut conn).await;
+        info!(
+            "👥 Consuming {} as {}/{}",
+            self.handler.definition.stream_key, self.config.group, self.config.consumer
+        );
+
+        let stream_key = &self.handler.definition.stream_key;
+        let (mut retry, mut failures) = (retry_backoff(), 0);
+        let mut next_claim = Instant::now();
+        loop {
+            match self.poll(&mut conn, &mut next_claim).await {
+                Ok(()) => recovered(stream_key, &mut failures, &mut retry),
+                Err(e) if e.code() == Some("NOGROUP") => self.ensure_group(&mut conn).await,
+                Err(e) => {
+                    failures += 1;
+                    let delay = retry.next_delay();
+                    warn!(
+                        "⚠️ Stream consumer error on {} (retrying in {:?}): {}",
+                        stream_key, delay, e
+                    );
+                    tokio::time::sleep(delay).await;
+                    if endpoint.generation() != generation {
+                        (conn, generation) = connect_blocking(endpoint).await?;
+                        self.ensure_group(&mut conn).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Create the group (and the stream) if missing, starting at new entries
+    async fn ensure_group(&self, conn: &mut ConnectionManager) {
+        let created: redis::RedisResult<()> = conn
+            .xgroup_create_mkstream(&self.handler.definition.stream_key, &self.config.group, "$")
+            .await;
+        match created {
+            Ok(()) => info!(
+                "👥 Created consumer group {} on {}",
+                self.config.group, self.handler.definition.stream_key
+            ),
+            Err(e) if e.code() == Some("BUSYGROUP") => {}
+            Err(e) => warn!(
+                "⚠️ Failed to create consumer group {}: {}",
+                self.config.group, e
+            ),
+        }
+    }
+
+    /// Reclaim idle pending entries when due, then wait for new ones
+    async fn poll(
+        &self,
+        conn: &mut ConnectionManager,
+        next_claim: &mut Instant,
+    ) -> redis::RedisResult<()> {
+        if Instant::now() >= *next_claim {
+            self.reclaim(conn).await?;
+            *next_claim = Instant::now() + self.config.claim_idle;
+        }
+
+        let options = StreamReadOptions::default()
+            .group(&self.config.group, &self.config.consumer)
+            .count(CONSUMER_BATCH)
+            .block(block_millis());
+        let reply: StreamReadReply = conn
+            .xread_options(&[&self.handler.definition.stream_key], &[">"], &options)
+            .await?;
+        let entries: Vec<StreamId> = reply.keys.into_iter().flat_map(|key| key.ids).collect();
+        self.handle(conn, &entries, false).await
+    }
+
+    /// Take over entries another consumer received but never acknowledged
+    async fn reclaim(&self, conn: &mut ConnectionManager) -> redis::RedisResult<()> {
+        let min_idle_ms = u64::try_from(self.config.claim_idle.as_millis()).unwrap_or(u64::MAX);
+        let mut start = "0-0".to_string();
+        for _ in 0..MAX_CLAIM_ROUNDS {
+            let reply: StreamAutoClaimReply = conn
+                .xautoclaim_options(
+                    &self.handler.definition.stream_key,
+                    &self.config.group,
+                    &self.config.consumer,
+                    min_idle_ms,
+                    &start,
+                    StreamAutoClaimOptions::default().count(CONSUMER_BATCH),
+                )
+                .await?;
+            if !reply.claimed.is_empty() {
+                info!(
+                    "♻️ Reclaimed {} pending entries on {}",
+                    reply.claimed.len(),
+                    self.handler.definition.stream_key
+                );
+                self.handle(conn, &reply.claimed, true).await?;
+            }
+            if reply.next_stream_id == "0-0" {
+                break;
+            }
+            start = reply.next_stream_id;
+        }
+        Ok(())
+    }
+
+    /// Cache the newest entry of a batch, then acknowledge the whole batch
+    ///
+    /// Reclaimed entries are usually older than what another replica already
+    /// cached, so they only overwrite the cache if still the stream's newest entry.
+    async fn handle(
+        &self,
+        conn: &mut ConnectionManager,
+        entries: &[StreamId],
+        reclaimed: bool,
+    ) -> redis::RedisResult<()> {
+        let Some(newest) = newest_entry(entries) else {
+            return Ok(());
+        };
+        let stream_key = &self.handler.definition.stream_key;
+
+        let is_current = if reclaimed {
+            let latest: redis::streams::StreamRangeReply =
+                conn.xrevrange_count(stream_key, "+", "-", 1).await?;
+            latest
+                .ids
+                .first()
+                .is_some_and(|entry| entry.id == newest.id)
+        } else {
+            true
+        };
+        // Rejected entries are still acknowledged: their copy lives in the dead-letter stream
+        if is_current {
+            self.handler.cache_newest(conn, entries).await;
+        } else {
+            self.handler
+                .freshness
+                .record(&newest.id, freshness::now_millis());
+        }
+
+        let ids: Vec<&str> = entries.iter().map(|entry| entry.id.as_str()).collect();
+        let _: usize = conn.xack(stream_key, &self.config.group, &ids).await?;
+        debug!(
+            "✅ Acknowledged {} entries on {} (newest {})",
+            ids.len(),
+            stream_key,
+            newest.id
+        );
+        Ok(())
+    }
+}
+
+/// Reads every new entry of a stream with blocking `XREAD`, without a group
+pub(super) struct TailConsumer {
+    pub(super) handler: EntryHandler,
+    /// Only feed live updates; a consumer group caches the entries
+    pub(super) notify_only: bool,
+}
+
+impl TailConsumer {
+    /// Connect (retrying while Redis is down), then follow the stream until the process exits
+    ///
+    /// Starts at `$` (entries published from now on); the latest entry at startup
+    /// is still read on the first cache miss.
+    pub(super) async fn run(self, endpoint: &RedisEndpoint) -> Result<(), String> {
+        let (mut conn, mut generation) = connect_blocking(endpoint).await?;
+        if !self.notify_only {
+            self.handler.checkpoint.load(&mut conn).await;
+        }
+        info!(
+            "📡 Following {} with blocking XREAD",
+            self.handler.definition.stream_key
+        );
+
+        let stream_key = &self.handler.definition.stream_key;
+        let (mut retry, mut failures) = (retry_backoff(), 0);
+        let mut last_id = "$".to_string();
+        loop {
+            match self.poll(&mut conn, &last_id).await {
+                Ok(newest_id) => {
+                    recovered(stream_key, &mut failures, &mut retry);
+                    if let Some(newest_id) = newest_id {
+                        last_id = newest_id;
+                    }
+                }
+                Err(e) => {
+                    failures += 1;
+                    let delay = retry.next_delay();
+                    warn!(
+                        "⚠️ Stream reader error on {} (retrying in {:?}): {}",
+                        stream_key, delay, e
+                    );
+                    tokio::time::sleep(delay).await;
+                    if endpoint.generation() != generation {
+                        (conn, generation) = connect_blocking(endpoint).await?;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wait for entries after `last_id`; returns the newest one read
+    async fn poll(
+        &self,
+        conn: &mut ConnectionManager,
+        last_id: &str,
+    ) -> redis::RedisResult<Option<String>> {
+        let options = StreamReadOptions::default()
+            .count(CONSUMER_BATCH)
+            .block(block_millis());
+        let reply: StreamReadReply = conn
+            .xread_options(&[&self.handler.definition.stream_key], &[last_id], &options)
+            .await?;
+        let entries: Vec<StreamId> = reply.keys.into_iter().flat_map(|key| key.ids).collect();
+        let newest = if self.notify_only {
+            self.handler.notify_entries(&entries)
+        } else {
+            self.handler.cache_newest(conn, &entries).await
+        };
+        Ok(newest.map(|newest| newest.id.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_newest_entry_by_id_order() {
+        let entry = |id: &str| StreamId {
+            id: id.to_string(),
+            map: HashMap::from([(
+                "btc_price_usd".to_string(),
+                redis::Value::BulkString(b"45000".to_vec()),
+            )]),
+            ..StreamId::default()
+        };
+        let entries = vec![entry("1700-9"), entry("1700-10"), entry("999-50")];
+        assert_eq!(
+            newest_entry(&entries).map(|entry| entry.id.as_str()),
+            Some("1700-10")
+        );
+        assert!(newest_entry(&[]).is_none());
+        assert_eq!(
+            entries.first().map(entry_fields),
+            Some(vec![("btc_price_usd".to_string(), "45000".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_every_event_of_a_batch_is_announced() {
+        let event = |id: &str, report_id: &str| StreamId {
+            id: id.to_string(),
+            map: HashMap::from([
+                (
+                    "event".to_string(),
+                    redis::Value::BulkString(b"report_published".to_vec()),
+                ),
+                (
+                    "report_id".to_string(),
+                    redis::Value::BulkString(report_id.as_bytes().to_vec()),
+                ),
+            ]),
+            ..StreamId::default()
+        };
+        // Two reports published within one block, read newest first
+        let batch = vec![event("5000-1", "8"), event("5000-0", "7")];
+        let Some(newest) = newest_entry(&batch) else {
+            panic!("batch has a newest entry");
+        };
+        assert_eq!(newest.id, "5000-1");
+
+        let checkpoint = StreamCheckpoint::new("report_events_stream");
+        let older: Vec<&str> = older_new_entries(&batch, newest, &checkpoint)
+            .into_iter()
+            .map(|entry| entry.id.as_str())
+            .collect();
+        // The older event is announced before the newest one is cached and announced
+        assert_eq!(older, ["5000-0"]);
+
+        checkpoint.advance("5000-0");
+        assert!(older_new_entries(&batch, newest, &checkpoint).is_empty());
+    }
+}
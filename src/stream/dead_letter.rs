@@ -0,0 +1,178 @@
+//! Dead-Letter Handling
+//!
+//! Entries whose decoded payload is malformed (a broken JSON field, a market data
+//! entry violating `MarketDataStreamEntry`, ...) are never cached:
+//! pages would otherwise render that garbage until the TTL runs out. The raw fields
+//! are copied instead to the stream's dead-letter stream (`{stream_key}:dead_letter`
+//! unless `STREAM_<NAME>_DEAD_LETTER_KEY` is set), prefixed with `dlq_reason`,
+//! `dlq_source_stream`, `dlq_entry_id` and `dlq_failed_at`, and counted per stream
+//! for `/health`.
+
+use multi_tier_cache::CacheManager;
+use serde_json::Value;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::warn;
+
+use crate::dto::streams::StreamEntryError;
+
+/// Approximate max length of each dead-letter stream
+const DEAD_LETTER_MAXLEN: usize = 10_000;
+
+/// Check a decoded entry of a stream without a typed schema before it is cached
+///
+/// # Errors
+/// Returns the reason the payload was rejected.
+pub fn validate_payload(payload: &Value) -> Result<(), StreamEntryError> {
+    let Some(fields) = payload.as_object() else {
+        return Err(StreamEntryError::Schema(
+            "payload is not a JSON object".to_string(),
+        ));
+    };
+    if fields.is_empty() {
+        return Err(StreamEntryError::Empty);
+    }
+    for (key, value) in fields {
+        if let Some(text) = value.as_str()
+            && (text.starts_with('{') || text.starts_with('['))
+        {
+            return Err(StreamEntryError::InvalidJson { field: key.clone() });
+        }
+    }
+    Ok(())
+}
+
+/// Dead-letter stream of one registered stream
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    stream_key: String,
+    dead_lettered: AtomicU64,
+    /// Last entry sent, so a rejected latest entry read on every cache miss is sent once
+    last_entry_id: Mutex<Option<String>>,
+}
+
+impl DeadLetterQueue {
+    #[must_use]
+    pub fn new(stream_key: String) -> Self {
+        Self {
+            stream_key,
+            dead_lettered: AtomicU64::new(0),
+            last_entry_id: Mutex::new(None),
+        }
+    }
+
+    /// Redis key of the dead-letter stream
+    #[must_use]
+    pub fn stream_key(&self) -> &str {
+        &self.stream_key
+    }
+
+    /// Entries rejected since startup
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.dead_lettered.load(Ordering::Relaxed)
+    }
+
+    /// Copy a rejected entry to the dead-letter stream, once per entry ID
+    ///
+    /// A failed XADD is only logged: the entry is still kept out of the cache.
+    pub async fn send(
+        &self,
+        cache_manager: &CacheManager,
+        source_stream: &str,
+        entry_id: &str,
+        fields: &[(String, String)],
+        reason: &StreamEntryError,
+    ) {
+        if !self.is_new(entry_id) {
+            return;
+        }
+        self.dead_lettered.fetch_add(1, Ordering::Relaxed);
+        warn!(
+            "☠️ Rejected {} entry {}: {} (moved to {})",
+            source_stream, entry_id, reason, self.stream_key
+        );
+        let fields = dead_letter_fields(source_stream, entry_id, fields, &reason.to_string());
+        if let Err(e) = cache_manager
+            .publish_to_stream(&self.stream_key, fields, Some(DEAD_LETTER_MAXLEN))
+            .await
+        {
+            warn!(
+                "⚠️ Failed to write {} entry {} to {}: {}",
+                source_stream, entry_id, self.stream_key, e
+            );
+        }
+    }
+
+    fn is_new(&self, entry_id: &str) -> bool {
+        let Ok(mut last) = self.last_entry_id.lock() else {
+            return true;
+        };
+        if last.as_deref() == Some(entry_id) {
+            return false;
+        }
+        *last = Some(entry_id.to_string());
+        true
+    }
+}
+
+/// Dead-letter entry: rejection metadata followed by the raw fields
+fn dead_letter_fields(
+    source_stream: &str,
+    entry_id: &str,
+    fields: &[(String, String)],
+    reason: &str,
+) -> Vec<(String, String)> {
+    let mut entry = Vec::with_capacity(fields.len() + 4);
+    entry.push(("dlq_reason".to_string(), reason.to_string()));
+    entry.push(("dlq_source_stream".to_string(), source_stream.to_string()));
+    entry.push(("dlq_entry_id".to_string(), entry_id.to_string()));
+    entry.push(("dlq_failed_at".to_string(), chrono::Utc::now().to_rfc3339()));
+    entry.extend_from_slice(fields);
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_untyped_payload_validation() {
+        assert_eq!(
+            validate_payload(&json!({"event": "created", "report_id": 42})),
+            Ok(())
+        );
+        assert_eq!(validate_payload(&json!({})), Err(StreamEntryError::Empty));
+        assert!(matches!(
+            validate_payload(&json!([1, 2])),
+            Err(StreamEntryError::Schema(_))
+        ));
+        assert_eq!(
+            validate_payload(&json!({"data": "[oops"})),
+            Err(StreamEntryError::InvalidJson {
+                field: "data".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_dead_letter_entry_and_dedup() {
+        let fields = vec![("btc_price_usd".to_string(), "-1".to_string())];
+        let entry = dead_letter_fields("market_data_stream", "1700-0", &fields, "bad price");
+        assert_eq!(
+            entry.first(),
+            Some(&("dlq_reason".to_string(), "bad price".to_string()))
+        );
+        assert_eq!(
+            entry.get(2),
+            Some(&("dlq_entry_id".to_string(), "1700-0".to_string()))
+        );
+        assert_eq!(entry.last(), fields.last());
+
+        let queue = DeadLetterQueue::new("market_data_stream:dead_letter".to_string());
+        assert!(queue.is_new("1700-0"));
+        assert!(!queue.is_new("1700-0"));
+        assert!(queue.is_new("1701-0"));
+    }
+}
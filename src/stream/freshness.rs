@@ -0,0 +1,176 @@
+//! Stream Freshness Tracking
+//!
+//! Each reader records the producer timestamp (from the entry ID) of the newest
+//! entry it has consumed, and when it consumed it. The resulting data age - how far
+//! the cached snapshot is behind now - is what tells operators that the websocket
+//! service has stopped publishing, even while every request is still served from
+//! the cache. `FieldFreshness` does the same per payload field, for entries that
+//! arrive with some fields missing (a partial fetch upstream).
+
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use super::entry_id_millis;
+
+/// Whether a stream's data is recent enough
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreshnessStatus {
+    Fresh,
+    /// Newest consumed entry is older than the stream's `stale_after`
+    Stale,
+    /// Nothing consumed since startup
+    NoData,
+}
+
+impl FreshnessStatus {
+    /// Status of data `data_age` old against `stale_after` (`None`: never stale)
+    #[must_use]
+    pub fn of(data_age: Option<Duration>, stale_after: Option<Duration>) -> Self {
+        match (data_age, stale_after) {
+            (None, _) => Self::NoData,
+            (Some(age), Some(limit)) if age > limit => Self::Stale,
+            (Some(_), _) => Self::Fresh,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Fresh => "fresh",
+            Self::Stale => "stale",
+            Self::NoData => "no_data",
+        }
+    }
+}
+
+/// Point-in-time view of a stream's freshness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreshnessSnapshot {
+    /// Now minus the producer timestamp of the newest consumed entry
+    pub data_age: Option<Duration>,
+    /// Time since this instance last consumed an entry
+    pub since_consumed: Option<Duration>,
+    pub entries_consumed: u64,
+}
+
+impl FreshnessSnapshot {
+    /// Status against `stale_after` (`None`: the stream is never considered stale)
+    #[must_use]
+    pub fn status(&self, stale_after: Option<Duration>) -> FreshnessStatus {
+        FreshnessStatus::of(self.data_age, stale_after)
+    }
+}
+
+/// Last consumed entry of one stream (0 = never)
+#[derive(Debug, Default)]
+pub struct StreamFreshness {
+    newest_entry_ms: AtomicU64,
+    consumed_at_ms: AtomicU64,
+    entries_consumed: AtomicU64,
+}
+
+impl StreamFreshness {
+    /// Record a consumed entry; older entries (e.g. reclaimed ones) never move the age back
+    pub fn record(&self, entry_id: &str, now_ms: u64) {
+        if let Some(entry_ms) = entry_id_millis(entry_id) {
+            self.newest_entry_ms.fetch_max(entry_ms, Ordering::Relaxed);
+        }
+        self.consumed_at_ms.store(now_ms, Ordering::Relaxed);
+        self.entries_consumed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn snapshot(&self, now_ms: u64) -> FreshnessSnapshot {
+        let age =
+            |at_ms: u64| (at_ms > 0).then(|| Duration::from_millis(now_ms.saturating_sub(at_ms)));
+        FreshnessSnapshot {
+            data_age: age(self.newest_entry_ms.load(Ordering::Relaxed)),
+            since_consumed: age(self.consumed_at_ms.load(Ordering::Relaxed)),
+            entries_consumed: self.entries_consumed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Producer timestamp of the newest entry carrying each top-level payload field
+#[derive(Debug, Default)]
+pub struct FieldFreshness {
+    updated_ms: Mutex<HashMap<String, u64>>,
+}
+
+impl FieldFreshness {
+    /// Record the non-null fields of an entry; older entries never move a field back
+    pub fn record(&self, entry_id: &str, data: &Value) {
+        let (Some(entry_ms), Some(fields)) = (entry_id_millis(entry_id), data.as_object()) else {
+            return;
+        };
+        let mut updated_ms = self.updated_ms.lock();
+        for (field, _) in fields.iter().filter(|(_, value)| !value.is_null()) {
+            let at = updated_ms.entry(field.clone()).or_default();
+            *at = (*at).max(entry_ms);
+        }
+    }
+
+    /// Unix milliseconds each field last arrived at
+    #[must_use]
+    pub fn updated_ms(&self) -> HashMap<String, u64> {
+        self.updated_ms.lock().clone()
+    }
+}
+
+/// Current unix time in milliseconds
+#[must_use]
+pub fn now_millis() -> u64 {
+    u64::try_from(chrono::Utc::now().timestamp_millis()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_age_tracks_newest_entry() {
+        let freshness = StreamFreshness::default();
+        let empty = freshness.snapshot(10_000);
+        assert_eq!(
+            empty.status(Some(Duration::from_mins(1))),
+            FreshnessStatus::NoData
+        );
+
+        freshness.record("5000-0", 6_000);
+        // A reclaimed, older entry doesn't make the data look older
+        freshness.record("1000-3", 7_000);
+        let snapshot = freshness.snapshot(10_000);
+        assert_eq!(snapshot.data_age, Some(Duration::from_secs(5)));
+        assert_eq!(snapshot.since_consumed, Some(Duration::from_secs(3)));
+        assert_eq!(snapshot.entries_consumed, 2);
+
+        assert_eq!(
+            snapshot.status(Some(Duration::from_secs(10))),
+            FreshnessStatus::Fresh
+        );
+        assert_eq!(
+            snapshot.status(Some(Duration::from_secs(4))),
+            FreshnessStatus::Stale
+        );
+        assert_eq!(snapshot.status(None), FreshnessStatus::Fresh);
+    }
+
+    #[test]
+    fn test_fields_keep_their_newest_entry() {
+        let fields = FieldFreshness::default();
+        fields.record("5000-0", &json!({"btc_price_usd": 1.0, "fng_value": 40}));
+        // A partial entry only refreshes the fields it carries
+        fields.record("8000-0", &json!({"btc_price_usd": 2.0, "fng_value": null}));
+        fields.record("6000-0", &json!({"fng_value": 41}));
+        fields.record("not-an-id", &json!({"eth_price_usd": 1.0}));
+
+        let updated = fields.updated_ms();
+        assert_eq!(updated.get("btc_price_usd"), Some(&8000));
+        assert_eq!(updated.get("fng_value"), Some(&6000));
+        assert!(!updated.contains_key("eth_price_usd"));
+    }
+}
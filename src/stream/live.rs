@@ -0,0 +1,172 @@
+//! Live Stream Updates
+//!
+//! In-process fan-out of each stream's newest accepted entry, for pushing updates
+//! to browsers (`/api/crypto/stream`, `/api/crypto/dashboard-summary/stream`). A `watch` channel is used rather than a
+//! queue: subscribers only ever need the latest snapshot, so a slow client skips
+//! intermediate entries instead of lagging behind. Every accepted entry passes
+//! through here, so it also keeps when each payload field last arrived.
+//!
+//! Streams of events rather than snapshots (`report_events`) are also fanned out
+//! entry by entry on a `broadcast` channel (`subscribe_events`): the consumer that
+//! reads every entry in this process (the tail consumer, or the live follower next
+//! to a consumer group) announces each of its entries in ID order, so background
+//! jobs see every event even when several arrive in one read.
+
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+
+use super::entry_id_parts;
+use super::freshness::FieldFreshness;
+
+/// Newest accepted entry of a stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamUpdate {
+    pub entry_id: String,
+    pub data: Arc<Value>,
+}
+
+/// Entries an event subscriber can fall behind by before it misses some
+const EVENT_BUFFER: usize = 1024;
+
+/// Latest-value and per-entry channels of one stream
+#[derive(Debug)]
+pub struct LiveUpdates {
+    sender: watch::Sender<Option<StreamUpdate>>,
+    events: broadcast::Sender<StreamUpdate>,
+    fields: FieldFreshness,
+}
+
+impl Default for LiveUpdates {
+    fn default() -> Self {
+        Self {
+            sender: watch::Sender::new(None),
+            events: broadcast::Sender::new(EVENT_BUFFER),
+            fields: FieldFreshness::default(),
+        }
+    }
+}
+
+impl LiveUpdates {
+    /// Publish an accepted entry as the latest snapshot; older entries (reclaims,
+    /// replays) are ignored
+    pub fn publish(&self, entry_id: &str, data: Value) {
+        self.publish_update(StreamUpdate {
+            entry_id: entry_id.to_string(),
+            data: Arc::new(data),
+        });
+    }
+
+    /// Publish an entry the caller consumed as an event to `subscribe_events`, and as
+    /// the latest snapshot when it is newer
+    pub fn announce(&self, entry_id: &str, data: Value) {
+        let update = StreamUpdate {
+            entry_id: entry_id.to_string(),
+            data: Arc::new(data),
+        };
+        // No subscribers is not an error: nothing in this process needs the events
+        let _ = self.events.send(update.clone());
+        self.publish_update(update);
+    }
+
+    fn publish_update(&self, update: StreamUpdate) {
+        self.fields.record(&update.entry_id, &update.data);
+        let entry = entry_id_parts(&update.entry_id);
+        self.sender.send_if_modified(|current| {
+            let newer = current
+                .as_ref()
+                .is_none_or(|current| entry > entry_id_parts(&current.entry_id));
+            if newer {
+                *current = Some(update);
+            }
+            newer
+        });
+    }
+
+    /// Receiver starting at the current latest update
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<Option<StreamUpdate>> {
+        self.sender.subscribe()
+    }
+
+    /// Receiver of every entry announced from now on, in order
+    ///
+    /// A subscriber more than `EVENT_BUFFER` entries behind gets
+    /// `RecvError::Lagged` and misses the oldest ones.
+    #[must_use]
+    pub fn subscribe_events(&self) -> broadcast::Receiver<StreamUpdate> {
+        self.events.subscribe()
+    }
+
+    /// When each payload field of the published entries last arrived
+    #[must_use]
+    pub fn field_freshness(&self) -> &FieldFreshness {
+        &self.fields
+    }
+
+    /// Connected subscribers
+    #[must_use]
+    pub fn subscribers(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_only_newer_entries_are_published() {
+        let live = LiveUpdates::default();
+        let mut receiver = live.subscribe();
+        assert!(receiver.borrow_and_update().is_none());
+
+        live.publish("2000-0", json!({"btc_price_usd": 45000}));
+        assert!(receiver.has_changed().unwrap_or(false));
+        live.publish("1000-5", json!({"btc_price_usd": 1}));
+        assert_eq!(
+            receiver
+                .borrow_and_update()
+                .as_ref()
+                .map(|update| update.entry_id.as_str()),
+            Some("2000-0")
+        );
+        live.publish("1000-5", json!({"btc_price_usd": 1}));
+        assert!(!receiver.has_changed().unwrap_or(true));
+    }
+
+    #[test]
+    fn test_announced_batch_reaches_event_subscribers() {
+        let live = LiveUpdates::default();
+        let mut snapshots = live.subscribe();
+        let mut events = live.subscribe_events();
+
+        // Two report events consumed in one read: both reach event subscribers
+        live.announce(
+            "3000-0",
+            json!({"event": "report_published", "report_id": 7}),
+        );
+        live.announce(
+            "3000-1",
+            json!({"event": "report_published", "report_id": 8}),
+        );
+        let ids: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+            .filter_map(|update| update.data.get("report_id")?.as_i64())
+            .collect();
+        assert_eq!(ids, [7, 8]);
+
+        // Snapshot subscribers only need the newest
+        assert_eq!(
+            snapshots
+                .borrow_and_update()
+                .as_ref()
+                .map(|update| update.entry_id.as_str()),
+            Some("3000-1")
+        );
+
+        // Snapshot-only publishing is not an event
+        live.publish("3000-2", json!({"event": "report_updated", "report_id": 8}));
+        assert!(events.try_recv().is_err());
+    }
+}
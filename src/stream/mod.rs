@@ -0,0 +1,777 @@
+//! Redis Streams
+//!
+//! Readers for the streams published by the websocket service. Which streams are
+//! read, and the cache key and TTL of each, come from the `StreamRegistry`.
+//! Entries failing validation go to a dead-letter stream instead of the cache, and
+//! the background consumers checkpoint the last entry they processed.
+
+mod checkpoint;
+mod consumer;
+mod dead_letter;
+mod freshness;
+mod live;
+mod registry;
+mod retention;
+
+pub use checkpoint::StreamCheckpoint;
+pub use dead_letter::{DeadLetterQueue, validate_payload};
+pub use freshness::{FieldFreshness, FreshnessSnapshot, FreshnessStatus, StreamFreshness};
+pub use live::{LiveUpdates, StreamUpdate};
+pub use registry::{
+    MARKET_DATA, REPORT_EVENTS, SYSTEM_NOTIFICATIONS, StreamDefinition, StreamRegistry,
+};
+pub use retention::RetentionStats;
+
+use anyhow::Result;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+// Import CacheManager from library
+use multi_tier_cache::{Bytes, CacheManager};
+
+use crate::dto::streams::{MarketDataStreamEntry, StreamEntryError};
+use crate::redis_endpoint::RedisEndpoint;
+use crate::services::shared::trace_context::TraceContext;
+use consumer::{EntryHandler, GroupConsumer, TailConsumer};
+use retention::RetentionTarget;
+
+/// Stream for control events consumed by the websocket service (purges, report events)
+pub const SERVICE_EVENTS_STREAM: &str = "service_events_stream";
+/// Approximate max length for the service events stream
+const SERVICE_EVENTS_MAXLEN: usize = 1000;
+
+const DEFAULT_CONSUMER_GROUP: &str = "web_server_report";
+const DEFAULT_CLAIM_IDLE: Duration = Duration::from_mins(1);
+
+/// Consumer group settings for reading the registered streams
+///
+/// Replicas sharing one group split the stream between them: each entry is
+/// delivered to one consumer, and stays pending until that consumer `XACK`s it.
+/// Entries left pending by a crashed consumer are reclaimed by the others once
+/// they have been idle for `claim_idle`.
+///
+/// Environment:
+/// - `STREAM_CONSUMER_GROUPS`: `false` disables the group; every instance then follows
+///   each stream on its own with blocking `XREAD`
+/// - `STREAM_CONSUMER_GROUP`: group name (default `web_server_report`)
+/// - `STREAM_CONSUMER_NAME`: consumer name, unique per process (default `{HOSTNAME}-{pid}`)
+/// - `STREAM_CLAIM_IDLE_SECS`: idle time before pending entries are reclaimed (default 60)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerGroupConfig {
+    pub group: String,
+    pub consumer: String,
+    pub claim_idle: Duration,
+}
+
+impl ConsumerGroupConfig {
+    /// Read `STREAM_CONSUMER_*`; `None` when disabled
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let env = |name: &str| std::env::var(name).ok();
+        Self::parse(
+            env("STREAM_CONSUMER_GROUPS").as_deref(),
+            env("STREAM_CONSUMER_GROUP").as_deref(),
+            env("STREAM_CONSUMER_NAME").as_deref(),
+            env("STREAM_CLAIM_IDLE_SECS").as_deref(),
+            env("HOSTNAME").as_deref(),
+        )
+    }
+
+    fn parse(
+        enabled: Option<&str>,
+        group: Option<&str>,
+        consumer: Option<&str>,
+        claim_idle_secs: Option<&str>,
+        hostname: Option<&str>,
+    ) -> Option<Self> {
+        let disabled = enabled.is_some_and(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "false" | "0" | "off"
+            )
+        });
+        if disabled {
+            return None;
+        }
+        let non_empty = |value: Option<&str>| {
+            value
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(ToString::to_string)
+        };
+        let consumer = non_empty(consumer).unwrap_or_else(|| {
+            let host = non_empty(hostname).unwrap_or_else(|| "web-server-report".to_string());
+            format!("{host}-{}", std::process::id())
+        });
+        Some(Self {
+            group: non_empty(group).unwrap_or_else(|| DEFAULT_CONSUMER_GROUP.to_string()),
+            consumer,
+            claim_idle: claim_idle_secs
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .map_or(DEFAULT_CLAIM_IDLE, Duration::from_secs),
+        })
+    }
+}
+
+/// Redis Stream Reader
+///
+/// Reads one registered stream on cache miss, and continuously in the background
+/// as a consumer group member (`consumer_group`) or a plain follower (`tail`).
+/// Clones share the same counters and live channel.
+#[derive(Clone)]
+pub struct RedisStreamReader {
+    pub cache_manager: Arc<CacheManager>,
+    pub definition: StreamDefinition,
+    freshness: Arc<StreamFreshness>,
+    dead_letters: Arc<DeadLetterQueue>,
+    live: Arc<LiveUpdates>,
+    retention: Arc<RetentionStats>,
+    checkpoint: Arc<StreamCheckpoint>,
+}
+
+impl RedisStreamReader {
+    /// Create a reader for one stream
+    #[must_use]
+    pub fn new(cache_manager: Arc<CacheManager>, definition: StreamDefinition) -> Self {
+        let dead_letters = Arc::new(DeadLetterQueue::new(definition.dead_letter_key.clone()));
+        let checkpoint = Arc::new(StreamCheckpoint::new(&definition.stream_key));
+        Self {
+            cache_manager,
+            definition,
+            freshness: Arc::new(StreamFreshness::default()),
+            dead_letters,
+            live: Arc::new(LiveUpdates::default()),
+            retention: Arc::new(RetentionStats::default()),
+            checkpoint,
+        }
+    }
+
+    /// Age of the newest entry this instance consumed, and when it consumed it
+    #[must_use]
+    pub fn freshness(&self) -> FreshnessSnapshot {
+        self.freshness.snapshot(freshness::now_millis())
+    }
+
+    /// Newest accepted entry, for pushing updates to connected clients
+    #[must_use]
+    pub fn live_updates(&self) -> &LiveUpdates {
+        &self.live
+    }
+
+    /// Last entry the background consumers processed
+    #[must_use]
+    pub fn checkpoint(&self) -> &StreamCheckpoint {
+        &self.checkpoint
+    }
+
+    /// Entries the retention task trimmed from this stream
+    #[must_use]
+    pub fn retention(&self) -> &RetentionStats {
+        &self.retention
+    }
+
+    fn retention_target(&self) -> Option<RetentionTarget> {
+        self.definition.max_len.map(|max_len| RetentionTarget {
+            stream_key: self.definition.stream_key.clone(),
+            max_len,
+            stats: Arc::clone(&self.retention),
+        })
+    }
+
+    /// Dead-letter stream of this reader's rejected entries
+    #[must_use]
+    pub fn dead_letters(&self) -> &DeadLetterQueue {
+        &self.dead_letters
+    }
+
+    /// Read the latest entry using cache-first strategy with automatic fallback
+    ///
+    /// Uses `get_or_compute_typed` for automatic cache management.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying cache or stream interactions fail.
+    pub async fn read_latest(&self) -> Result<Option<Value>> {
+        info!(
+            "📖 Reading latest {} entry (cache-first with auto-fallback)...",
+            self.definition.name
+        );
+
+        let result = self
+            .cache_manager
+            .get_or_compute_typed(
+                &self.definition.cache_key,
+                self.definition.strategy(),
+                || async {
+                    // Compute function: only called on cache miss
+                    info!("💾 Cache miss - reading from Redis Stream...");
+
+                    self.read_from_stream().await.map_err(|e| {
+                        info!("❌ Failed to read from Redis Stream: {}", e);
+                        multi_tier_cache::CacheError::BackendError(e.to_string())
+                    })
+                },
+            )
+            .await;
+
+        result.map_err(anyhow::Error::from)
+    }
+
+    /// Read from Redis Stream
+    async fn read_from_stream(&self) -> Result<Option<Value>> {
+        // Use cache_manager's stream reading functionality
+        let entries = self
+            .cache_manager
+            .read_stream_latest(&self.definition.stream_key, 1)
+            .await?;
+
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        // Get the first (and only) entry
+        let (entry_id, fields) = entries
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Stream entry missing"))?;
+        info!("📨 Stream entry ID: {}", entry_id);
+
+        // Join the producer's trace when the entry carries trace context
+        if let Some(trace) = TraceContext::extract_from_fields(fields) {
+            debug!(
+                trace_id = %trace.trace_id,
+                parent_span_id = %trace.span_id,
+                "🔗 Stream entry {} carries trace context",
+                entry_id
+            );
+        }
+
+        // Convert stream fields back to JSON; malformed entries are never cached
+        let json_data = match Self::decode(&self.definition, fields) {
+            Ok(data) => data,
+            Err(reason) => {
+                self.dead_letters
+                    .send(
+                        &self.cache_manager,
+                        &self.definition.stream_key,
+                        entry_id,
+                        fields,
+                        &reason,
+                    )
+                    .await;
+                anyhow::bail!("Rejected stream entry {entry_id}: {reason}");
+            }
+        };
+        self.freshness.record(entry_id, freshness::now_millis());
+        self.live.publish(entry_id, json_data.clone());
+
+        Ok(Some(json_data))
+    }
+
+    /// Re-read the newest `count` entries and run them through the pipeline again
+    ///
+    /// Malformed entries go to the dead-letter stream and the newest valid one
+    /// replaces the cached snapshot, e.g. after a validation fix or a cache flush.
+    ///
+    /// # Errors
+    /// Returns an error if the stream cannot be read or the snapshot cannot be cached.
+    pub async fn replay(&self, count: usize) -> Result<ReplayOutcome> {
+        let entries = self
+            .cache_manager
+            .read_stream_latest(&self.definition.stream_key, count)
+            .await?;
+        let (latest, rejected) = partition_replayed(&self.definition, &entries);
+
+        for ((entry_id, fields), reason) in &rejected {
+            self.dead_letters
+                .send(
+                    &self.cache_manager,
+                    &self.definition.stream_key,
+                    entry_id,
+                    fields,
+                    reason,
+                )
+                .await;
+        }
+        let cached_entry_id = match latest {
+            Some((entry_id, data)) => {
+                store_latest(&self.cache_manager, &self.definition, &data).await?;
+                self.freshness.record(entry_id, freshness::now_millis());
+                self.live.publish(entry_id, data);
+                Some(entry_id.to_string())
+            }
+            None => None,
+        };
+
+        info!(
+            "🔁 Replayed {} entries of {} ({} rejected, cached {:?})",
+            entries.len(),
+            self.definition.stream_key,
+            rejected.len(),
+            cached_entry_id
+        );
+        Ok(ReplayOutcome {
+            entries_read: entries.len(),
+            rejected: rejected.len(),
+            cached_entry_id,
+        })
+    }
+
+    /// Decode an entry and validate the payload
+    ///
+    /// Market data goes through `MarketDataStreamEntry`; other streams have no
+    /// schema and only need a well-formed object.
+    fn decode(
+        definition: &StreamDefinition,
+        fields: &[(String, String)],
+    ) -> Result<Value, StreamEntryError> {
+        if definition.name == MARKET_DATA {
+            let entry = Self::decode_market_data(fields)?;
+            return serde_json::to_value(entry)
+                .map_err(|e| StreamEntryError::Schema(e.to_string()));
+        }
+        let data = Self::stream_fields_to_json(fields);
+        validate_payload(&data).map(|()| data)
+    }
+
+    /// Deserialize a market data entry, flat or with a nested JSON `data` field
+    ///
+    /// # Errors
+    /// Returns the schema or range violation that rejects the entry.
+    pub fn decode_market_data(
+        fields: &[(String, String)],
+    ) -> Result<MarketDataStreamEntry, StreamEntryError> {
+        let payload: Vec<&(String, String)> = fields
+            .iter()
+            .filter(|(key, _)| !TraceContext::is_trace_field(key))
+            .collect();
+        let value = match payload.as_slice() {
+            [(key, data)] if key == "data" => serde_json::from_str(data)
+                .map_err(|_| StreamEntryError::InvalidJson { field: key.clone() })?,
+            // Each value is read as JSON; plain text stays a string
+            _ => Value::Object(
+                payload
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = serde_json::from_str(value)
+                            .unwrap_or_else(|_| Value::String(value.clone()));
+                        (key.clone(), value)
+                    })
+                    .collect(),
+            ),
+        };
+        MarketDataStreamEntry::from_value(value)
+    }
+
+    /// Convert Redis Stream fields to JSON
+    fn stream_fields_to_json(fields: &[(String, String)]) -> Value {
+        // Special case: If there's only one payload field named "data" containing JSON string
+        let mut payload = fields
+            .iter()
+            .filter(|(key, _)| !TraceContext::is_trace_field(key));
+        if let Some((key, value)) = payload.next()
+            && payload.next().is_none()
+            && key == "data"
+            && let Ok(data) = serde_json::from_str::<Value>(value)
+        {
+            info!("📦 Unwrapped nested 'data' field from Redis Stream");
+            return data;
+        }
+
+        // General case: parse each field
+        let mut map = serde_json::Map::new();
+
+        for (key, value) in fields {
+            // Trace metadata is not part of the payload
+            if TraceContext::is_trace_field(key) {
+                continue;
+            }
+
+            // Try to parse value as different types
+            let json_value = if value == "null" {
+                Value::Null
+            } else if value == "true" {
+                Value::Bool(true)
+            } else if value == "false" {
+                Value::Bool(false)
+            } else if let Ok(int) = value.parse::<i64>() {
+                Value::Number(serde_json::Number::from(int))
+            } else if let Ok(num) = value.parse::<f64>() {
+                // NaN / infinity stay strings so validation rejects them
+                serde_json::Number::from_f64(num)
+                    .map_or_else(|| Value::String(value.clone()), Value::Number)
+            } else if value.starts_with('{') || value.starts_with('[') {
+                // Try to parse as JSON object or array
+                serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.clone()))
+            } else {
+                Value::String(value.clone())
+            };
+
+            map.insert(key.clone(), json_value);
+        }
+
+        Value::Object(map)
+    }
+
+    /// Publish a control event for the websocket service, carrying trace context
+    ///
+    /// The entry contains an `event` field, the given payload fields and the
+    /// `traceparent`/`trace_id` fields so the consumer can join the same trace.
+    ///
+    /// # Errors
+    /// Returns an error if the streaming backend is unavailable or the XADD fails.
+    pub async fn publish_service_event(
+        &self,
+        event: &str,
+        payload: Vec<(String, String)>,
+        trace: &TraceContext,
+    ) -> Result<String> {
+        let mut fields = Vec::with_capacity(payload.len() + 3);
+        fields.push(("event".to_string(), event.to_string()));
+        fields.extend(payload);
+        trace.inject_into_fields(&mut fields);
+
+        let entry_id = self
+            .cache_manager
+            .publish_to_stream(SERVICE_EVENTS_STREAM, fields, Some(SERVICE_EVENTS_MAXLEN))
+            .await?;
+
+        info!(
+            trace_id = %trace.trace_id,
+            "📤 Published '{}' event to {} ({})",
+            event, SERVICE_EVENTS_STREAM, entry_id
+        );
+        Ok(entry_id)
+    }
+
+    /// Age of the newest entry, from its stream entry ID timestamp
+    ///
+    /// Returns `None` when the stream is empty.
+    ///
+    /// # Errors
+    /// Returns an error if the stream cannot be read.
+    pub async fn latest_entry_age(&self) -> Result<Option<std::time::Duration>> {
+        let entries = self
+            .cache_manager
+            .read_stream_latest(&self.definition.stream_key, 1)
+            .await?;
+        let Some((entry_id, _)) = entries.first() else {
+            return Ok(None);
+        };
+        let published_ms = entry_id_millis(entry_id)
+            .ok_or_else(|| anyhow::anyhow!("Invalid stream entry ID: {entry_id}"))?;
+        let now_ms = freshness::now_millis();
+
+        Ok(Some(std::time::Duration::from_millis(
+            now_ms.saturating_sub(published_ms),
+        )))
+    }
+
+    /// Consume the stream as a consumer group member until the process exits
+    ///
+    /// Every delivered entry refreshes the stream's cache entry and is acknowledged,
+    /// so handlers find fresh data in the cache instead of reading the stream on
+    /// every miss.
+    ///
+    /// # Errors
+    /// The future only fails for an invalid master URL; Redis errors are retried.
+    pub fn consumer_group(
+        &self,
+        endpoint: &Arc<RedisEndpoint>,
+        config: ConsumerGroupConfig,
+    ) -> impl Future<Output = Result<(), String>> + Send + use<> {
+        let consumer = GroupConsumer {
+            handler: self.entry_handler(false),
+            config,
+        };
+        let endpoint = Arc::clone(endpoint);
+        async move { consumer.run(&endpoint).await }
+    }
+
+    /// Follow the stream with blocking `XREAD` until the process exits
+    ///
+    /// Like `consumer_group`, but this instance reads every entry on its own.
+    ///
+    /// # Errors
+    /// The future only fails for an invalid master URL; Redis errors are retried.
+    pub fn tail(
+        &self,
+        endpoint: &Arc<RedisEndpoint>,
+    ) -> impl Future<Output = Result<(), String>> + Send + use<> {
+        let consumer = TailConsumer {
+            handler: self.entry_handler(true),
+            notify_only: false,
+        };
+        let endpoint = Arc::clone(endpoint);
+        async move { consumer.run(&endpoint).await }
+    }
+
+    /// Follow the stream only to feed `live_updates`, without caching
+    ///
+    /// In a consumer group each replica receives a share of the entries; this lets
+    /// every replica still push every update to its own connected clients.
+    ///
+    /// # Errors
+    /// The future only fails for an invalid master URL; Redis errors are retried.
+    pub fn live_follower(
+        &self,
+        endpoint: &Arc<RedisEndpoint>,
+    ) -> impl Future<Output = Result<(), String>> + Send + use<> {
+        let consumer = TailConsumer {
+            handler: self.entry_handler(true),
+            notify_only: true,
+        };
+        let endpoint = Arc::clone(endpoint);
+        async move { consumer.run(&endpoint).await }
+    }
+
+    /// Process one entry delivered by another transport (see `MessageTransport`):
+    /// validate, cache and push it to live subscribers like a consumed stream entry
+    ///
+    /// Entries at or below the checkpoint are skipped. Returns `false` when the
+    /// entry was skipped or rejected.
+    pub async fn ingest(&self, entry_id: &str, fields: &[(String, String)]) -> bool {
+        if !self.checkpoint.is_new(entry_id) {
+            return false;
+        }
+        self.checkpoint.advance(entry_id);
+        let accepted = self
+            .entry_handler(true)
+            .cache_fields(entry_id, fields)
+            .await;
+        if accepted {
+            self.freshness.record(entry_id, freshness::now_millis());
+        }
+        accepted
+    }
+
+    fn entry_handler(&self, announces_events: bool) -> EntryHandler {
+        EntryHandler {
+            cache_manager: Arc::clone(&self.cache_manager),
+            definition: self.definition.clone(),
+            freshness: Arc::clone(&self.freshness),
+            dead_letters: Arc::clone(&self.dead_letters),
+            live: Arc::clone(&self.live),
+            checkpoint: Arc::clone(&self.checkpoint),
+            announces_events,
+        }
+    }
+
+    /// Health check
+    ///
+    /// # Errors
+    /// Returns an error if the underlying cache check fails with a fatal error.
+    pub async fn health_check(&self) -> Result<bool> {
+        // Try to connect to Redis
+        match self.cache_manager.get("_health_check").await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                info!("❌ Redis Stream Reader health check failed: {}", e);
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// Result of `RedisStreamReader::replay`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayOutcome {
+    pub entries_read: usize,
+    /// Entries sent to the dead-letter stream
+    pub rejected: usize,
+    /// Entry now cached as the latest snapshot (`None`: no valid entry)
+    pub cached_entry_id: Option<String>,
+}
+
+/// Entry ID and fields, as returned by `read_stream_latest`
+type RawEntry = (String, Vec<(String, String)>);
+/// Newest valid entry ID with its payload, and the rejected entries (oldest first) with their reason
+type PartitionedReplay<'a> = (
+    Option<(&'a str, Value)>,
+    Vec<(&'a RawEntry, StreamEntryError)>,
+);
+
+/// Split replayed entries (newest first, as XREVRANGE returns them)
+fn partition_replayed<'a>(
+    definition: &StreamDefinition,
+    entries: &'a [RawEntry],
+) -> PartitionedReplay<'a> {
+    let mut latest = None;
+    let mut rejected = Vec::new();
+    for entry in entries {
+        match RedisStreamReader::decode(definition, &entry.1) {
+            Ok(data) => {
+                if latest.is_none() {
+                    latest = Some((entry.0.as_str(), data));
+                }
+            }
+            Err(reason) => rejected.push((entry, reason)),
+        }
+    }
+    rejected.reverse();
+    (latest, rejected)
+}
+
+/// Store a decoded payload as the stream's latest snapshot
+async fn store_latest(
+    cache_manager: &CacheManager,
+    definition: &StreamDefinition,
+    data: &Value,
+) -> Result<()> {
+    let bytes = Bytes::from(serde_json::to_vec(data)?);
+    cache_manager
+        .set_with_strategy(&definition.cache_key, bytes, definition.strategy())
+        .await?;
+    Ok(())
+}
+
+/// Millisecond timestamp part of a Redis stream entry ID (`<ms>-<seq>`)
+#[must_use]
+pub fn entry_id_millis(entry_id: &str) -> Option<u64> {
+    entry_id_parts(entry_id).map(|(millis, _)| millis)
+}
+
+/// `(ms, seq)` of a stream entry ID, ordered like the stream
+fn entry_id_parts(entry_id: &str) -> Option<(u64, u64)> {
+    let (millis, seq) = entry_id.split_once('-')?;
+    Some((millis.parse().ok()?, seq.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_id_millis() {
+        assert_eq!(entry_id_millis("1760428800000-0"), Some(1_760_428_800_000));
+        assert_eq!(entry_id_millis("1760428800000"), None);
+        assert_eq!(entry_id_millis("abc-1"), None);
+        // Same millisecond: the sequence decides
+        assert!(entry_id_parts("1760428800000-10") > entry_id_parts("1760428800000-9"));
+    }
+
+    #[test]
+    fn test_consumer_group_config() {
+        assert_eq!(
+            ConsumerGroupConfig::parse(Some("false"), None, None, None, None),
+            None
+        );
+        let config = ConsumerGroupConfig::parse(None, None, None, Some("30"), Some("web-1"));
+        assert_eq!(
+            config,
+            Some(ConsumerGroupConfig {
+                group: DEFAULT_CONSUMER_GROUP.to_string(),
+                consumer: format!("web-1-{}", std::process::id()),
+                claim_idle: Duration::from_secs(30),
+            })
+        );
+        let config = ConsumerGroupConfig::parse(
+            Some("true"),
+            Some("reports"),
+            Some(" worker-a "),
+            Some("0"),
+            None,
+        );
+        assert_eq!(
+            config.map(|c| (c.group, c.consumer, c.claim_idle)),
+            Some((
+                "reports".to_string(),
+                "worker-a".to_string(),
+                DEFAULT_CLAIM_IDLE
+            ))
+        );
+    }
+
+    #[test]
+    fn test_stream_fields_to_json() -> Result<()> {
+        let fields = vec![
+            ("btc_price_usd".to_string(), "45000.5".to_string()),
+            ("btc_change_24h".to_string(), "2.5".to_string()),
+            ("fng_value".to_string(), "75".to_string()),
+            ("partial_failure".to_string(), "false".to_string()),
+        ];
+
+        let result = RedisStreamReader::stream_fields_to_json(&fields);
+
+        assert_eq!(
+            result
+                .get("btc_price_usd")
+                .ok_or_else(|| anyhow::anyhow!("missing btc_price_usd"))?,
+            45000.5
+        );
+        assert_eq!(
+            result
+                .get("btc_change_24h")
+                .ok_or_else(|| anyhow::anyhow!("missing btc_change_24h"))?,
+            2.5
+        );
+        assert_eq!(
+            result
+                .get("fng_value")
+                .ok_or_else(|| anyhow::anyhow!("missing fng_value"))?,
+            75
+        );
+        assert_eq!(
+            result
+                .get("partial_failure")
+                .ok_or_else(|| anyhow::anyhow!("missing partial_failure"))?,
+            false
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay_caches_newest_valid_entry() {
+        let entry = |id: &str, price: &str| {
+            (
+                id.to_string(),
+                vec![("btc_price_usd".to_string(), price.to_string())],
+            )
+        };
+        // Newest first, like XREVRANGE
+        let entries = vec![
+            entry("300-0", "oops"),
+            entry("200-0", "45000"),
+            entry("150-0", "-1"),
+            entry("100-0", "44000"),
+        ];
+        let (latest, rejected) =
+            partition_replayed(&StreamDefinition::named(MARKET_DATA), &entries);
+        assert_eq!(
+            latest.map(|(id, data)| (id, data.get("btc_price_usd").cloned())),
+            Some(("200-0", Some(Value::from(45000.0))))
+        );
+        let rejected_ids: Vec<&str> = rejected.iter().map(|(entry, _)| entry.0.as_str()).collect();
+        assert_eq!(rejected_ids, ["150-0", "300-0"]);
+    }
+
+    #[test]
+    fn test_stream_fields_to_json_skips_trace_fields() -> Result<()> {
+        let fields = vec![
+            (
+                "data".to_string(),
+                r#"{"btc_price_usd": 45000.5}"#.to_string(),
+            ),
+            (
+                "traceparent".to_string(),
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+            ),
+            (
+                "trace_id".to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736".to_string(),
+            ),
+        ];
+
+        let result = RedisStreamReader::stream_fields_to_json(&fields);
+
+        assert_eq!(
+            result
+                .get("btc_price_usd")
+                .ok_or_else(|| anyhow::anyhow!("missing btc_price_usd"))?,
+            45000.5
+        );
+        assert!(result.get("traceparent").is_none());
+        Ok(())
+    }
+}
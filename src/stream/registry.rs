@@ -0,0 +1,353 @@
+//! Named Stream Registry
+//!
+//! The streams this service reads, each with its Redis key, the cache key its latest
+//! entry is kept under and that entry's TTL. Built-in streams:
+//! - `market_data`: `market_data_stream` -> `latest_market_data` (market data TTL)
+//! - `report_events`: `report_events_stream` -> `latest_report_event` (5 minutes)
+//! - `system_notifications`: `system_notifications_stream` -> `latest_system_notification` (1 hour)
+//!
+//! Environment:
+//! - `STREAMS`: comma-separated stream names to read (default: the built-in ones);
+//!   `market_data` is always read. Other names get `{name}_stream` / `latest_{name}`.
+//! - `STREAM_<NAME>_KEY`, `STREAM_<NAME>_CACHE_KEY`, `STREAM_<NAME>_TTL_SECS`: per-stream
+//!   overrides, e.g. `STREAM_REPORT_EVENTS_TTL_SECS=60`
+//! - `STREAM_<NAME>_STALE_AFTER_SECS`: data age at which the stream is reported stale
+//!   (default 60 for `market_data`; event streams are never stale unless set)
+//! - `STREAM_<NAME>_DEAD_LETTER_KEY`: where rejected entries go (default `{key}:dead_letter`)
+//! - `STREAM_<NAME>_MAXLEN`: entries kept by the retention task (default 10000, `0` keeps all)
+
+use futures::future::{Either, try_join_all};
+use multi_tier_cache::{CacheManager, CacheStrategy};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+use super::{ConsumerGroupConfig, RedisStreamReader, retention};
+use crate::cache_config::cache_config;
+use crate::redis_endpoint::RedisEndpoint;
+use crate::tasks::TaskRegistry;
+
+/// Market data snapshots published by the websocket service
+pub const MARKET_DATA: &str = "market_data";
+/// Report created/updated events
+pub const REPORT_EVENTS: &str = "report_events";
+/// Operator-facing notifications (maintenance, incidents)
+pub const SYSTEM_NOTIFICATIONS: &str = "system_notifications";
+
+const BUILTIN_STREAMS: [&str; 3] = [MARKET_DATA, REPORT_EVENTS, SYSTEM_NOTIFICATIONS];
+const DEFAULT_TTL: Duration = Duration::from_mins(5);
+/// Entries kept per stream by the retention task
+const DEFAULT_MAX_LEN: usize = 10_000;
+/// Market data is published every few seconds; a minute without it means the publisher stopped
+const MARKET_DATA_STALE_AFTER: Duration = Duration::from_mins(1);
+
+/// One registered stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamDefinition {
+    /// Registry name, e.g. `report_events`
+    pub name: String,
+    /// Redis stream key
+    pub stream_key: String,
+    /// Cache key of the latest entry
+    pub cache_key: String,
+    /// TTL of the cached latest entry
+    pub ttl: Duration,
+    /// Data age above which the stream is reported stale (`None`: quiet is normal)
+    pub stale_after: Option<Duration>,
+    /// Redis stream receiving entries that fail validation
+    pub dead_letter_key: String,
+    /// Approximate length the stream is trimmed to (`None`: never trimmed)
+    pub max_len: Option<usize>,
+}
+
+impl StreamDefinition {
+    /// Defaults for a stream name (built-in or custom)
+    #[must_use]
+    pub fn named(name: &str) -> Self {
+        let (stream_key, cache_key, ttl) = match name {
+            MARKET_DATA => (
+                "market_data_stream",
+                "latest_market_data",
+                cache_config().market_data.ttl(),
+            ),
+            REPORT_EVENTS => ("report_events_stream", "latest_report_event", DEFAULT_TTL),
+            SYSTEM_NOTIFICATIONS => (
+                "system_notifications_stream",
+                "latest_system_notification",
+                Duration::from_hours(1),
+            ),
+            _ => {
+                return Self {
+                    name: name.to_string(),
+                    stream_key: format!("{name}_stream"),
+                    cache_key: format!("latest_{name}"),
+                    ttl: DEFAULT_TTL,
+                    stale_after: None,
+                    dead_letter_key: dead_letter_key(&format!("{name}_stream")),
+                    max_len: Some(DEFAULT_MAX_LEN),
+                };
+            }
+        };
+        Self {
+            name: name.to_string(),
+            stream_key: stream_key.to_string(),
+            cache_key: cache_key.to_string(),
+            ttl,
+            stale_after: (name == MARKET_DATA).then_some(MARKET_DATA_STALE_AFTER),
+            dead_letter_key: dead_letter_key(stream_key),
+            max_len: Some(DEFAULT_MAX_LEN),
+        }
+    }
+
+    /// Strategy to pass to the cache manager
+    #[must_use]
+    pub const fn strategy(&self) -> CacheStrategy {
+        CacheStrategy::Custom(self.ttl)
+    }
+
+    fn with_overrides(mut self, env: &impl Fn(&str) -> Option<String>) -> Self {
+        let upper = self.name.to_ascii_uppercase();
+        let var = |suffix: &str| {
+            env(&format!("STREAM_{upper}_{suffix}"))
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        if let Some(stream_key) = var("KEY") {
+            self.dead_letter_key = dead_letter_key(&stream_key);
+            self.stream_key = stream_key;
+        }
+        if let Some(cache_key) = var("CACHE_KEY") {
+            self.cache_key = cache_key;
+        }
+        if let Some(ttl) = var("TTL_SECS").and_then(|v| v.parse::<u64>().ok()) {
+            self.ttl = Duration::from_secs(ttl);
+        }
+        if let Some(secs) = var("STALE_AFTER_SECS").and_then(|v| v.parse::<u64>().ok()) {
+            self.stale_after = (secs > 0).then(|| Duration::from_secs(secs));
+        }
+        if let Some(dead_letter_key) = var("DEAD_LETTER_KEY") {
+            self.dead_letter_key = dead_letter_key;
+        }
+        if let Some(max_len) = var("MAXLEN").and_then(|v| v.parse::<usize>().ok()) {
+            self.max_len = (max_len > 0).then_some(max_len);
+        }
+        self
+    }
+}
+
+fn dead_letter_key(stream_key: &str) -> String {
+    format!("{stream_key}:dead_letter")
+}
+
+/// Stream definitions from `STREAMS` and the per-stream overrides
+fn definitions_from_sources(env: impl Fn(&str) -> Option<String>) -> Vec<StreamDefinition> {
+    let mut names = vec![MARKET_DATA.to_string()];
+    match env("STREAMS") {
+        Some(list) => names.extend(
+            list.split(',')
+                .map(|name| name.trim().to_ascii_lowercase())
+                .filter(|name| !name.is_empty()),
+        ),
+        None => names.extend(BUILTIN_STREAMS.iter().map(ToString::to_string)),
+    }
+
+    let mut definitions: Vec<StreamDefinition> = Vec::with_capacity(names.len());
+    for name in names {
+        if definitions.iter().all(|definition| definition.name != name) {
+            definitions.push(StreamDefinition::named(&name).with_overrides(&env));
+        }
+    }
+    definitions
+}
+
+/// Readers of every registered stream
+pub struct StreamRegistry {
+    market_data: RedisStreamReader,
+    others: Vec<RedisStreamReader>,
+}
+
+impl StreamRegistry {
+    /// Registry of `definitions`; `market_data` is added with defaults if missing
+    #[must_use]
+    pub fn new(cache_manager: &Arc<CacheManager>, definitions: Vec<StreamDefinition>) -> Self {
+        let mut market_data = None;
+        let mut others = Vec::new();
+        for definition in definitions {
+            let reader = RedisStreamReader::new(Arc::clone(cache_manager), definition);
+            if market_data.is_none() && reader.definition.name == MARKET_DATA {
+                market_data = Some(reader);
+            } else if reader.definition.name != MARKET_DATA {
+                others.push(reader);
+            }
+        }
+        let market_data = market_data.unwrap_or_else(|| {
+            RedisStreamReader::new(
+                Arc::clone(cache_manager),
+                StreamDefinition::named(MARKET_DATA),
+            )
+        });
+        Self {
+            market_data,
+            others,
+        }
+    }
+
+    /// Registry configured by `STREAMS` / `STREAM_<NAME>_*`
+    #[must_use]
+    pub fn from_env(cache_manager: &Arc<CacheManager>) -> Self {
+        let registry = Self::new(
+            cache_manager,
+            definitions_from_sources(|name| std::env::var(name).ok()),
+        );
+        info!(
+            "📚 Streams: {}",
+            registry
+                .iter()
+                .map(|reader| format!(
+                    "{}={}",
+                    reader.definition.name, reader.definition.stream_key
+                ))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        registry
+    }
+
+    /// Reader of the market data stream
+    #[must_use]
+    pub const fn market_data(&self) -> &RedisStreamReader {
+        &self.market_data
+    }
+
+    /// Reader of a stream by registry name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&RedisStreamReader> {
+        self.iter().find(|reader| reader.definition.name == name)
+    }
+
+    /// Every reader, market data first
+    pub fn iter(&self) -> impl Iterator<Item = &RedisStreamReader> {
+        std::iter::once(&self.market_data).chain(self.others.iter())
+    }
+
+    /// Consume every stream through the consumer group, supervised as the
+    /// `stream_consumers` task
+    ///
+    /// Every stream is also followed on its own so live updates and events reach
+    /// every replica, not only the group member that was handed the entry.
+    pub fn start_consumer_groups(
+        &self,
+        endpoint: &Arc<RedisEndpoint>,
+        config: &ConsumerGroupConfig,
+        tasks: &TaskRegistry,
+    ) {
+        let readers: Vec<RedisStreamReader> = self.iter().cloned().collect();
+        let endpoint = Arc::clone(endpoint);
+        let config = config.clone();
+        tasks.supervise("stream_consumers", move || {
+            let consumers: Vec<_> = readers
+                .iter()
+                .map(|reader| Either::Left(reader.consumer_group(&endpoint, config.clone())))
+                .chain(
+                    readers
+                        .iter()
+                        .map(|reader| Either::Right(reader.live_follower(&endpoint))),
+                )
+                .collect();
+            async move { try_join_all(consumers).await.map(|_| ()) }
+        });
+    }
+
+    /// Follow every stream with blocking `XREAD` (no consumer group), supervised as
+    /// the `stream_followers` task
+    pub fn start_followers(&self, endpoint: &Arc<RedisEndpoint>, tasks: &TaskRegistry) {
+        let readers: Vec<RedisStreamReader> = self.iter().cloned().collect();
+        let endpoint = Arc::clone(endpoint);
+        tasks.supervise("stream_followers", move || {
+            let followers: Vec<_> = readers
+                .iter()
+                .map(|reader| reader.tail(&endpoint))
+                .collect();
+            async move { try_join_all(followers).await.map(|_| ()) }
+        });
+    }
+
+    /// Trim every stream to its `max_len` every `STREAM_TRIM_INTERVAL_SECS`,
+    /// supervised as the `stream_retention` task
+    pub fn start_retention(&self, endpoint: &Arc<RedisEndpoint>, tasks: &TaskRegistry) {
+        let targets: Vec<_> = self
+            .iter()
+            .filter_map(RedisStreamReader::retention_target)
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        let interval =
+            retention::trim_interval(std::env::var("STREAM_TRIM_INTERVAL_SECS").ok().as_deref());
+        let endpoint = Arc::clone(endpoint);
+        let targets = Arc::new(targets);
+        tasks.supervise("stream_retention", move || {
+            let (endpoint, targets) = (Arc::clone(&endpoint), Arc::clone(&targets));
+            async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    retention::trim_streams(&endpoint, &targets).await;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_streams_by_default() {
+        let definitions = definitions_from_sources(|_| None);
+        let names: Vec<&str> = definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, BUILTIN_STREAMS);
+        assert_eq!(
+            definitions.first().map(|d| d.cache_key.as_str()),
+            Some("latest_market_data")
+        );
+    }
+
+    #[test]
+    fn test_streams_list_and_overrides() {
+        let env = |name: &str| match name {
+            "STREAMS" => Some("report_events, price_alerts,market_data".to_string()),
+            "STREAM_REPORT_EVENTS_TTL_SECS" => Some("60".to_string()),
+            "STREAM_REPORT_EVENTS_STALE_AFTER_SECS" => Some("600".to_string()),
+            "STREAM_PRICE_ALERTS_KEY" => Some("alerts:v2".to_string()),
+            "STREAM_PRICE_ALERTS_MAXLEN" => Some("0".to_string()),
+            "STREAM_REPORT_EVENTS_MAXLEN" => Some("500".to_string()),
+            _ => None,
+        };
+        let definitions = definitions_from_sources(env);
+        assert_eq!(
+            definitions,
+            vec![
+                StreamDefinition::named(MARKET_DATA),
+                StreamDefinition {
+                    name: REPORT_EVENTS.to_string(),
+                    stream_key: "report_events_stream".to_string(),
+                    cache_key: "latest_report_event".to_string(),
+                    ttl: Duration::from_mins(1),
+                    stale_after: Some(Duration::from_mins(10)),
+                    dead_letter_key: "report_events_stream:dead_letter".to_string(),
+                    max_len: Some(500),
+                },
+                StreamDefinition {
+                    name: "price_alerts".to_string(),
+                    stream_key: "alerts:v2".to_string(),
+                    cache_key: "latest_price_alerts".to_string(),
+                    ttl: DEFAULT_TTL,
+                    stale_after: None,
+                    dead_letter_key: "alerts:v2:dead_letter".to_string(),
+                    max_len: None,
+                },
+            ]
+        );
+    }
+}
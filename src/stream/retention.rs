@@ -0,0 +1,134 @@
+//! Stream Retention
+//!
+//! Producers append to the streams without a bound, and consumers only move the
+//! consumer group's cursor, so consumed entries would otherwise stay in Redis
+//! forever. The retention task trims every stream with a `max_len` to roughly that
+//! many entries (`XTRIM MAXLEN ~`, which lets Redis drop whole macro nodes and is
+//! much cheaper than an exact trim) and counts what it removed per stream.
+//!
+//! Environment:
+//! - `STREAM_<NAME>_MAXLEN`: entries kept per stream (default 10000, `0` disables)
+//! - `STREAM_TRIM_INTERVAL_SECS`: delay between trim passes (default 300)
+
+use redis::AsyncCommands;
+use redis::streams::StreamMaxlen;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use super::freshness;
+use crate::redis_endpoint::RedisEndpoint;
+
+/// Default delay between trim passes
+const DEFAULT_TRIM_INTERVAL: Duration = Duration::from_mins(5);
+
+/// Trim counters of one stream
+#[derive(Debug, Default)]
+pub struct RetentionStats {
+    trimmed: AtomicU64,
+    /// Unix millis of the last successful trim (0: never)
+    last_trim_ms: AtomicU64,
+}
+
+impl RetentionStats {
+    /// Entries removed since startup
+    #[must_use]
+    pub fn trimmed(&self) -> u64 {
+        self.trimmed.load(Ordering::Relaxed)
+    }
+
+    /// Seconds since the last successful trim
+    #[must_use]
+    pub fn last_trim_secs_ago(&self) -> Option<u64> {
+        match self.last_trim_ms.load(Ordering::Relaxed) {
+            0 => None,
+            at => Some(freshness::now_millis().saturating_sub(at) / 1000),
+        }
+    }
+
+    fn record(&self, trimmed: usize) {
+        self.trimmed.fetch_add(
+            u64::try_from(trimmed).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        self.last_trim_ms
+            .store(freshness::now_millis(), Ordering::Relaxed);
+    }
+}
+
+/// One stream the retention task trims
+pub(super) struct RetentionTarget {
+    pub(super) stream_key: String,
+    pub(super) max_len: usize,
+    pub(super) stats: Arc<RetentionStats>,
+}
+
+/// Trim every target once
+///
+/// A fresh connection is opened per pass, so passes follow Sentinel failovers and
+/// a Redis outage only skips the pass.
+pub(super) async fn trim_streams(endpoint: &RedisEndpoint, targets: &[RetentionTarget]) {
+    let Some(url) = endpoint.master_url() else {
+        return;
+    };
+    let connection = match redis::Client::open(url) {
+        Ok(client) => client.get_multiplexed_async_connection().await,
+        Err(e) => Err(e),
+    };
+    let mut conn = match connection {
+        Ok(conn) => conn,
+        Err(e) => {
+            warn!("⚠️ Stream retention skipped, Redis unreachable: {}", e);
+            return;
+        }
+    };
+
+    for target in targets {
+        let (stream_key, max_len) = (&target.stream_key, target.max_len);
+        let trimmed: redis::RedisResult<usize> =
+            conn.xtrim(stream_key, StreamMaxlen::Approx(max_len)).await;
+        match trimmed {
+            Ok(trimmed) => {
+                target.stats.record(trimmed);
+                if trimmed > 0 {
+                    info!(
+                        "✂️ Trimmed {} entries from {} (MAXLEN ~{})",
+                        trimmed, stream_key, max_len
+                    );
+                } else {
+                    debug!("✂️ Nothing to trim on {}", stream_key);
+                }
+            }
+            Err(e) => warn!("⚠️ Failed to trim {}: {}", stream_key, e),
+        }
+    }
+}
+
+/// `STREAM_TRIM_INTERVAL_SECS`, at least one second
+pub(super) fn trim_interval(value: Option<&str>) -> Duration {
+    value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map_or(DEFAULT_TRIM_INTERVAL, |secs| {
+            Duration::from_secs(secs.max(1))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_counters() {
+        let stats = RetentionStats::default();
+        assert_eq!(stats.last_trim_secs_ago(), None);
+        stats.record(120);
+        stats.record(0);
+        assert_eq!(stats.trimmed(), 120);
+        assert_eq!(stats.last_trim_secs_ago(), Some(0));
+
+        assert_eq!(trim_interval(None), DEFAULT_TRIM_INTERVAL);
+        assert_eq!(trim_interval(Some("60")), Duration::from_mins(1));
+        assert_eq!(trim_interval(Some("0")), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,298 @@
+//! Background Task Registry
+//!
+//! Long-running background work (cache warm-up, ...) is spawned through
+//! `TaskRegistry::spawn` so its state (running, completed, failed or panicked) can be
+//! inspected at runtime, e.g. by `/admin/diagnostics`.
+//!
+//! Loops that must outlive any single Redis or network error (stream consumers,
+//! monitors) use `TaskRegistry::supervise` instead: a failed or panicked run is
+//! restarted after an exponential backoff (1s doubling up to 1 minute, reset once a
+//! run stayed up for a minute), and every stop/restart is logged.
+
+use dashmap::DashMap;
+use futures::FutureExt;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// First restart delay of a supervised task
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+/// Upper bound of the restart delay
+const MAX_RESTART_BACKOFF: Duration = Duration::from_mins(1);
+/// A run lasting this long counts as healthy and resets the backoff
+const HEALTHY_RUN: Duration = Duration::from_mins(1);
+
+/// Lifecycle state of a background task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskState {
+    Running,
+    Completed,
+    /// The task returned an error
+    Failed(String),
+    /// The task panicked
+    Panicked(String),
+    /// A supervised run failed or panicked; it restarts after the backoff
+    Restarting(String),
+}
+
+/// Last known status of a named task
+#[derive(Debug, Clone)]
+pub struct TaskStatus {
+    pub name: &'static str,
+    pub state: TaskState,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Times a supervised task was restarted
+    pub restarts: u32,
+}
+
+/// Capped exponential delay between retries
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    next: Duration,
+}
+
+impl Backoff {
+    #[must_use]
+    pub const fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            next: initial,
+        }
+    }
+
+    /// Delay before the next attempt; each call doubles the following one up to `max`
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+
+    /// Start over at the initial delay (after a successful attempt)
+    pub const fn reset(&mut self) {
+        self.next = self.initial;
+    }
+}
+
+/// Tracks named background tasks
+///
+/// Cloning shares the same registry.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    tasks: Arc<DashMap<&'static str, TaskStatus>>,
+}
+
+impl TaskRegistry {
+    /// Create an empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawn `job` on the runtime and track it under `name`
+    ///
+    /// Spawning again under the same name replaces the previous status.
+    pub fn spawn<F>(&self, name: &'static str, job: F) -> JoinHandle<()>
+    where
+        F: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.register(name);
+        let tasks = Arc::clone(&self.tasks);
+        tokio::spawn(async move {
+            let state = run_guarded(name, job).await;
+            finish(&tasks, name, state);
+        })
+    }
+
+    /// Run the future built by `job` under `name`, restarting it with exponential
+    /// backoff whenever it fails or panics
+    ///
+    /// Supervision ends only when a run returns `Ok(())`.
+    pub fn supervise<F, Fut>(&self, name: &'static str, job: F) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let backoff = Backoff::new(INITIAL_RESTART_BACKOFF, MAX_RESTART_BACKOFF);
+        self.supervise_with(name, backoff, job)
+    }
+
+    fn supervise_with<F, Fut>(
+        &self,
+        name: &'static str,
+        mut backoff: Backoff,
+        mut job: F,
+    ) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.register(name);
+        let tasks = Arc::clone(&self.tasks);
+        tokio::spawn(async move {
+            loop {
+                let started = Instant::now();
+                let reason = match run_guarded(name, job()).await {
+                    TaskState::Failed(reason) | TaskState::Panicked(reason) => reason,
+                    state => {
+                        finish(&tasks, name, state);
+                        return;
+                    }
+                };
+                if started.elapsed() >= HEALTHY_RUN {
+                    backoff.reset();
+                }
+                let delay = backoff.next_delay();
+                let restarts = tasks.get_mut(name).map_or(1, |mut status| {
+                    status.state = TaskState::Restarting(reason);
+                    status.restarts += 1;
+                    status.restarts
+                });
+                warn!(
+                    "🔁 Background task '{}' stopped, restart #{} in {:?}",
+                    name, restarts, delay
+                );
+                tokio::time::sleep(delay).await;
+                if let Some(mut status) = tasks.get_mut(name) {
+                    status.state = TaskState::Running;
+                }
+                info!("▶️ Background task '{}' restarted", name);
+            }
+        })
+    }
+
+    fn register(&self, name: &'static str) {
+        self.tasks.insert(
+            name,
+            TaskStatus {
+                name,
+                state: TaskState::Running,
+                started_at: chrono::Utc::now(),
+                finished_at: None,
+                restarts: 0,
+            },
+        );
+    }
+
+    /// Status of every tracked task, sorted by name
+    #[must_use]
+    pub fn statuses(&self) -> Vec<TaskStatus> {
+        let mut statuses: Vec<TaskStatus> = self
+            .tasks
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        statuses.sort_by_key(|status| status.name);
+        statuses
+    }
+}
+
+/// Await `job`, turning its result or panic into the task's final state
+async fn run_guarded<F>(name: &'static str, job: F) -> TaskState
+where
+    F: Future<Output = Result<(), String>>,
+{
+    match AssertUnwindSafe(job).catch_unwind().await {
+        Ok(Ok(())) => TaskState::Completed,
+        Ok(Err(e)) => {
+            warn!("⚠️ Background task '{}' failed: {}", name, e);
+            TaskState::Failed(e)
+        }
+        Err(panic) => {
+            let message = panic_message(panic.as_ref());
+            error!("💥 Background task '{}' panicked: {}", name, message);
+            TaskState::Panicked(message)
+        }
+    }
+}
+
+fn finish(tasks: &DashMap<&'static str, TaskStatus>, name: &'static str, state: TaskState) {
+    if let Some(mut status) = tasks.get_mut(name) {
+        status.state = state;
+        status.finished_at = Some(chrono::Utc::now());
+    }
+}
+
+/// Best-effort text of a panic payload
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    panic
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_registry_tracks_outcomes() -> Result<(), tokio::task::JoinError> {
+        let registry = TaskRegistry::new();
+        registry.spawn("ok", async { Ok(()) }).await?;
+        registry
+            .spawn("failing", async { Err("boom".to_string()) })
+            .await?;
+        registry
+            .spawn("panicking", async { panic!("kaboom") })
+            .await?;
+
+        let states: Vec<(&str, TaskState)> = registry
+            .statuses()
+            .into_iter()
+            .map(|status| (status.name, status.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("failing", TaskState::Failed("boom".to_string())),
+                ("ok", TaskState::Completed),
+                ("panicking", TaskState::Panicked("kaboom".to_string())),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_supervised_task_restarts_until_success() -> Result<(), tokio::task::JoinError> {
+        let registry = TaskRegistry::new();
+        let runs = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let counter = Arc::clone(&runs);
+        let backoff = Backoff::new(Duration::from_millis(1), Duration::from_millis(2));
+        registry
+            .supervise_with("flaky", backoff, move || {
+                let run = counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                async move {
+                    match run {
+                        0 => Err("redis down".to_string()),
+                        1 => panic!("lost connection"),
+                        _ => Ok(()),
+                    }
+                }
+            })
+            .await?;
+
+        assert_eq!(runs.load(std::sync::atomic::Ordering::Relaxed), 3);
+        let status = registry.statuses().into_iter().next();
+        assert_eq!(
+            status.map(|status| (status.state, status.restarts)),
+            Some((TaskState::Completed, 2))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+        let delays: Vec<u64> = (0..5).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,178 @@
+//! Startup Cache Warm-up
+//!
+//! Pre-renders the most visited pages right after `AppState` initialization so the
+//! first real visitor is served from cache instead of paying the cold DB + Tera + gzip
+//! path. Each page is rendered through the same code path as its route, in parallel
+//! background tasks, so the server starts accepting connections immediately.
+//!
+//! Environment:
+//! - `CACHE_WARMUP`: `false` to skip warm-up (default enabled)
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::routes::{rss_feed, seo};
+use crate::services::data_communication::DEFAULT_LANGUAGE;
+use crate::state::AppState;
+
+/// Whether warm-up is enabled for a raw `CACHE_WARMUP` value
+fn warmup_enabled(value: Option<&str>) -> bool {
+    !value.is_some_and(|v| v.trim().eq_ignore_ascii_case("false") || v.trim() == "0")
+}
+
+/// Spawn cache warm-up in the background, tracked as the `cache_warmup` task
+///
+/// Returns `None` when disabled via `CACHE_WARMUP=false`.
+pub fn spawn_cache_warmup(state: &Arc<AppState>) -> Option<JoinHandle<()>> {
+    if !warmup_enabled(std::env::var("CACHE_WARMUP").ok().as_deref()) {
+        info!("⏭️ Cache warm-up disabled (CACHE_WARMUP=false)");
+        return None;
+    }
+
+    let task_state = Arc::clone(state);
+    Some(state.tasks.spawn(
+        "cache_warmup",
+        async move { warm_up_caches(&task_state).await },
+    ))
+}
+
+/// Pre-render latest report, first list page, homepage, sitemap and RSS in parallel
+///
+/// # Errors
+/// Returns a summary when some pages could not be warmed (each failure is logged).
+pub async fn warm_up_caches(state: &Arc<AppState>) -> Result<(), String> {
+    info!("🔥 Warming up caches...");
+    let started = Instant::now();
+
+    let latest_report = warm("latest report", {
+        let state = Arc::clone(state);
+        async move {
+            let chart_modules_content = state
+                .crypto_handlers
+                .report_creator
+                .get_chart_modules_content(&state);
+            state
+                .crypto_handlers
+                .render_crypto_index_dsd(
+                    &state,
+                    &HashMap::new(),
+                    &HeaderMap::new(),
+                    chart_modules_content,
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    });
+
+    let reports_list = warm("reports list page 1", {
+        let state = Arc::clone(state);
+        async move {
+            state
+                .crypto_handlers
+                .crypto_reports_list_with_tera(&state, 1, DEFAULT_LANGUAGE, false)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    });
+
+    let homepage = warm("homepage", {
+        let state = Arc::clone(state);
+        async move {
+            state.dashboard_handlers.init_homepage_cache(&state).await;
+            Ok(())
+        }
+    });
+
+    let sitemap = warm("sitemap.xml", {
+        let state = Arc::clone(state);
+        async move { response_result(seo::sitemap_xml(State(state), HeaderMap::new()).await) }
+    });
+
+    let rss = warm("rss.xml", {
+        let state = Arc::clone(state);
+        async move {
+            response_result(
+                rss_feed::rss_feed(State(state), Query(HashMap::new()), HeaderMap::new()).await,
+            )
+        }
+    });
+
+    let results = tokio::join!(latest_report, reports_list, homepage, sitemap, rss);
+    let results = [results.0, results.1, results.2, results.3, results.4];
+    let warmed = results.iter().filter(|ok| **ok).count();
+
+    info!(
+        "✅ Cache warm-up finished: {}/{} pages in {:?}",
+        warmed,
+        results.len(),
+        started.elapsed()
+    );
+
+    if warmed == results.len() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} pages failed to warm up",
+            results.len() - warmed,
+            results.len()
+        ))
+    }
+}
+
+/// Run one warm-up job on its own task, logging the outcome
+async fn warm<F>(name: &'static str, job: F) -> bool
+where
+    F: Future<Output = Result<(), String>> + Send + 'static,
+{
+    let started = Instant::now();
+    match tokio::spawn(job).await {
+        Ok(Ok(())) => {
+            info!("🔥 Warmed {} in {:?}", name, started.elapsed());
+            true
+        }
+        Ok(Err(e)) => {
+            warn!("⚠️ Cache warm-up for {} failed: {}", name, e);
+            false
+        }
+        Err(e) => {
+            warn!("⚠️ Cache warm-up task for {} aborted: {}", name, e);
+            false
+        }
+    }
+}
+
+/// Map a route response to a warm-up result
+fn response_result(response: impl IntoResponse) -> Result<(), String> {
+    let status = response.into_response().status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("responded with {status}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warmup_enabled_by_default() {
+        assert!(warmup_enabled(None));
+        assert!(warmup_enabled(Some("true")));
+        assert!(!warmup_enabled(Some("false")));
+        assert!(!warmup_enabled(Some(" FALSE ")));
+        assert!(!warmup_enabled(Some("0")));
+    }
+}
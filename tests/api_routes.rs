@@ -86,3 +86,100 @@ async fn test_websocket_stats_redirect() {
 
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+#[ignore = "requires running database and Redis"]
+async fn test_mobile_feed_delta() {
+    let app = get_app().await.expect("Failed to initialize app");
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/mobile/v1/feed")
+                .body(Body::empty())
+                .expect("Failed to build request"),
+        )
+        .await
+        .expect("Failed to get response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response
+        .headers()
+        .get("etag")
+        .expect("Missing ETag")
+        .clone();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/mobile/v1/feed")
+                .header("if-none-match", etag)
+                .body(Body::empty())
+                .expect("Failed to build request"),
+        )
+        .await
+        .expect("Failed to get response");
+    assert!(matches!(
+        response.status(),
+        StatusCode::NOT_MODIFIED | StatusCode::OK
+    ));
+}
+
+#[tokio::test]
+#[ignore = "requires running database and Redis"]
+async fn test_market_data_sse() {
+    let app = get_app().await.expect("Failed to initialize app");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/crypto/stream")
+                .body(Body::empty())
+                .expect("Failed to build request"),
+        )
+        .await
+        .expect("Failed to get response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok()),
+        Some("text/event-stream")
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires running database and Redis, and SMTP_URL"]
+async fn test_anonymous_subscribe() {
+    let app = get_app().await.expect("Failed to initialize app");
+
+    // No API credentials and no CSRF cookie, as from a cross-origin front end
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/subscribe")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"email":"reader@example.org"}"#))
+                .expect("Failed to build request"),
+        )
+        .await
+        .expect("Failed to get response");
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    // Following an unsubscribe link only renders the confirmation form
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/api/unsubscribe?token={}", "0".repeat(64)))
+                .body(Body::empty())
+                .expect("Failed to build request"),
+        )
+        .await
+        .expect("Failed to get response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("set-cookie").is_some());
+}